@@ -0,0 +1,2226 @@
+mod annotations;
+mod bookmarks;
+mod calendar;
+mod cascade;
+mod cultural_details;
+mod encounters;
+mod extensions;
+mod fast_forward;
+mod fuel_market;
+mod handout;
+mod hazards;
+mod hex_grid;
+mod history;
+mod hooks;
+mod journal;
+mod law_categories;
+mod legend;
+mod map_template;
+mod name_layout;
+mod names;
+mod nobility;
+mod number_format;
+mod orbital;
+mod ownership;
+mod politics;
+mod polities;
+mod randomization_tables;
+mod regen_all;
+mod religion;
+mod roll_log;
+mod rules;
+mod rules_reference;
+mod script;
+mod sector;
+mod serialize;
+mod sophonts;
+mod standalone_map;
+mod starport;
+mod stellar;
+mod subtype;
+mod summary;
+mod thumbnail;
+mod trade;
+mod travel;
+mod travel_advisory;
+mod travel_codes;
+mod validation;
+mod world;
+mod world_sheet;
+mod xboat;
+
+pub use annotations::{Annotation, AnnotationColor, AnnotationShape};
+pub use bookmarks::Bookmark;
+pub use calendar::{ImperialDate, DAYS_PER_YEAR};
+pub use cascade::WorldField;
+pub use cultural_details::{CulturalDetails, CulturalFacet};
+pub use encounters::{Encounter, EncounterKind, ENCOUNTER_TABLE_SIZE};
+pub use extensions::ExtensionRolls;
+pub use fast_forward::GrowthRules;
+pub use fuel_market::{FuelAvailability, FuelKind, FuelMarket, FuelPrice};
+pub use handout::HandoutOptions;
+pub use hazards::{Hazard, HazardKind, HazardSeverity};
+pub use hex_grid::HexOrientation;
+pub use history::{current_timestamp, file_timestamp_str, WorldChange};
+pub use journal::{journal_links, JournalEntry, DEFAULT_JOURNAL_DATE};
+pub use law_categories::{LawCategories, LawCategory};
+pub use legend::{LegendEntry, LegendSymbol};
+pub use map_template::{
+    LayoutTransform, MapFormat, MapLayout, MapOptions, MapRenderError, MapRenderer, MapStyle,
+    MapTemplate, WorldLabels,
+};
+pub use name_layout::NameLayout;
+pub use names::{phonetic_spelling, random_faction_name, random_subsector_name, NameStyle};
+pub use nobility::NOBLE_RANKS;
+pub use number_format::{CountStyle, LengthUnit, NumberFormat};
+pub use orbital::{Facility, FacilityKind, FacilitySize};
+pub use politics::PoliticalEvent;
+pub use polities::Polity;
+pub use randomization_tables::*;
+pub use regen_all::WorldAttribute;
+pub use religion::{Religion, ReligionAspect};
+pub use roll_log::{roll_logging, set_roll_logging, RollLogEntry};
+pub use rules::RuleSet;
+pub use rules_reference::{rule_entry, search_rules, RuleEntry, RuleTopic, RULES_REFERENCE};
+pub use script::Script;
+pub use sector::SectorLocation;
+pub use serialize::{SchemaError, TravellerMapTable, WorldList, SCHEMA_VERSION};
+pub use sophonts::Sophont;
+pub use starport::{Repairs, Shipyard, StarportDetails, TonnageClass, WeeklyTraffic};
+pub use subtype::WorldSubtype;
+pub use summary::SummaryFormat;
+pub use trade::{TradeGood, TRADE_GOODS_TABLE};
+pub use travel::{plan_route, Refueling, RouteLeg, ShipSpec};
+pub use travel_codes::{TravelCodeCondition, TravelCodePolicy};
+pub use validation::Inconsistency;
+pub use world::{Faction, TradeCode, TravelCode, World, WorldImage};
+pub use world_sheet::SheetFormat;
+
+use std::{
+    collections::BTreeMap,
+    convert::TryFrom,
+    error::Error,
+    fmt, io,
+    ops::{Add, Sub},
+    str::{self, FromStr},
+    thread,
+};
+
+use lazy_static::lazy_static;
+use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::dice;
+
+use map_template::{
+    AMBER_ZONE_CLASS, RED_ZONE_CLASS, ROUTE_CLASS, STARPORT_TL_CLASS, WORLD_NAME_CLASS,
+    WORLD_PROFILE_CLASS, XBOAT_ROUTE_CLASS,
+};
+use names::random_names;
+use serialize::{CsvTable, JsonableSubsector, SectorMetadata, T5Table};
+
+pub const SUBSECTOR_TEMPLATE_SVG: &str = include_str!("../resources/subsector_grid_template.svg");
+
+/// Radius of the circle drawn around amber and red zone worlds
+const TRAVEL_ZONE_RADIUS: f64 = 8.5;
+
+/// Largest modifier to the world presence roll from clustering
+const CLUSTER_MAX_DM: f64 = 3.0;
+/// Distance in hexes between the random points of the density field used for clustering
+const CLUSTER_SPACING: usize = 3;
+
+lazy_static! {
+    static ref SUBSECTOR_GRID_SVG: Result<String, MapRenderError> = subsector_grid_svg(true);
+    static ref SUBSECTOR_GRID_SVG_WITHOUT_HEX_NUMBERS: Result<String, MapRenderError> =
+        subsector_grid_svg(false);
+    pub static ref DEFAULT_MAP_TEMPLATE: MapTemplate = MapTemplate::default();
+    pub static ref CENTER_MARKERS: BTreeMap<Point, Translation> =
+        DEFAULT_MAP_TEMPLATE.center_markers.clone();
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    /** Get the number of hexes (i.e. parsecs) between this `Point` and `other`.
+
+    Subsector maps use "flat-topped" hexes where even-numbered columns are shifted down half a hex.
+    */
+    pub fn distance(&self, other: &Point) -> u32 {
+        let (q1, r1) = self.axial();
+        let (q2, r2) = other.axial();
+        let dq = q1 - q2;
+        let dr = r1 - r2;
+        ((dq.abs() + dr.abs() + (dq + dr).abs()) / 2) as u32
+    }
+
+    /** Convert this `Point` into axial hex coordinates. */
+    fn axial(&self) -> (i32, i32) {
+        // Zero-indexed, so the shifted down columns are the odd ones here
+        let column = self.x - 1;
+        let row = self.y - 1;
+        (column, row - (column - (column & 1)) / 2)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}{:02}", self.x, self.y)
+    }
+}
+
+impl TryFrom<&str> for Point {
+    type Error = Box<dyn Error>;
+    fn try_from(string: &str) -> Result<Self, Self::Error> {
+        let string = string.trim();
+
+        // Handle old and new prefix respectively
+        let string = string.strip_prefix('\'').unwrap_or(string);
+        let string = string.strip_prefix('_').unwrap_or(string);
+        let string = string.trim();
+
+        let mut chars = string.chars();
+
+        let mut x_str = String::new();
+        let mut y_str = String::new();
+        for string in [&mut x_str, &mut y_str] {
+            for _ in 0..2 {
+                let c = chars.next().ok_or("World location string too short")?;
+                string.push(c);
+            }
+        }
+
+        // After removing prefixes and process four characters, there should be nothing left
+        if chars.next().is_some() {
+            return Err("World location string too long".into());
+        }
+
+        let x: i32 = x_str.parse()?;
+        let y: i32 = y_str.parse()?;
+        Ok(Self { x, y })
+    }
+}
+
+impl FromStr for Point {
+    type Err = Box<dyn Error>;
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        Self::try_from(string)
+    }
+}
+
+#[derive(Debug)]
+enum PolityColor {
+    Turqoise,
+    Yellow,
+    Periwinkle,
+    Red,
+    Blue,
+    Orange,
+    Pear,
+    Lavender,
+    Grey,
+    Violet,
+    Pistachio,
+    Gold,
+}
+
+impl PolityColor {
+    const ALL_VALUES: [PolityColor; 12] = [
+        Self::Turqoise,
+        Self::Yellow,
+        Self::Periwinkle,
+        Self::Red,
+        Self::Blue,
+        Self::Orange,
+        Self::Pear,
+        Self::Lavender,
+        Self::Grey,
+        Self::Violet,
+        Self::Pistachio,
+        Self::Gold,
+    ];
+
+    fn class(&self) -> String {
+        let lower = self.to_string().to_lowercase();
+        format!("hex-color-{lower}")
+    }
+
+    /** The fill color of the color's class in the map template, as RGB. */
+    fn rgb(&self) -> [u8; 3] {
+        match self {
+            Self::Turqoise => [0x8d, 0xd3, 0xc7],
+            Self::Yellow => [0xff, 0xff, 0xb3],
+            Self::Periwinkle => [0xbe, 0xba, 0xda],
+            Self::Red => [0xfb, 0x80, 0x72],
+            Self::Blue => [0x80, 0xb1, 0xd3],
+            Self::Orange => [0xfd, 0xb4, 0x62],
+            Self::Pear => [0xb3, 0xde, 0x69],
+            Self::Lavender => [0xfc, 0xcd, 0xe5],
+            Self::Grey => [0xd9, 0xd9, 0xd9],
+            Self::Violet => [0xbc, 0x80, 0xbd],
+            Self::Pistachio => [0xcc, 0xeb, 0xc5],
+            Self::Gold => [0xff, 0xed, 0x6f],
+        }
+    }
+}
+
+impl fmt::Display for PolityColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Turqoise => "Turqoise",
+            Self::Yellow => "Yellow",
+            Self::Periwinkle => "Periwinkle",
+            Self::Red => "Red",
+            Self::Blue => "Blue",
+            Self::Orange => "Orange",
+            Self::Pear => "Pear",
+            Self::Lavender => "Lavender",
+            Self::Grey => "Grey",
+            Self::Violet => "Violet",
+            Self::Pistachio => "Pistachio",
+            Self::Gold => "Gold",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Translation {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Translation {
+    fn try_from_transform_str(transform: &str) -> Result<Self, Box<dyn Error>> {
+        let translate_args: Vec<&str> = transform
+            .strip_prefix("translate(")
+            .ok_or(format!("Incorrect prefix in transform '{transform}'"))?
+            .strip_suffix(')')
+            .ok_or(format!("Unclosed tranform '{transform}'"))?
+            .split(',')
+            .collect();
+
+        let x: f64 = translate_args
+            .first()
+            .ok_or(format!("Could not find x value in '{transform}'"))?
+            .parse()?;
+
+        let y: f64 = translate_args.get(1).unwrap_or(&"0.0").parse()?;
+
+        Ok(Translation { x, y })
+    }
+}
+
+impl Add for Translation {
+    type Output = Translation;
+    fn add(self, other: Self) -> Translation {
+        Translation {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+
+impl Default for Translation {
+    fn default() -> Self {
+        Translation { x: 0.0, y: 0.0 }
+    }
+}
+
+impl Sub for Translation {
+    type Output = Translation;
+    fn sub(self, other: Self) -> Translation {
+        Translation {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
+}
+
+/** How to handle a world being merged into a `Subsector` on a hex that is already occupied. */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MergeResolution {
+    /// Keep the existing world and discard the incoming one
+    Skip,
+    /// Replace the existing world with the incoming one
+    Overwrite,
+    /// Keep the existing world and place the incoming one in the closest empty hex
+    MoveToNearestEmpty,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum WorldAbundance {
+    Rift,
+    Sparse,
+    Nominal,
+    Dense,
+    Abundant,
+}
+
+impl WorldAbundance {
+    pub const WORLD_ABUNDANCE_VALUES: [WorldAbundance; 5] = [
+        Self::Rift,
+        Self::Sparse,
+        Self::Nominal,
+        Self::Dense,
+        Self::Abundant,
+    ];
+}
+
+impl From<WorldAbundance> for i16 {
+    fn from(world_abundance: WorldAbundance) -> Self {
+        match world_abundance {
+            WorldAbundance::Rift => -2,
+            WorldAbundance::Sparse => -1,
+            WorldAbundance::Nominal => 0,
+            WorldAbundance::Dense => 1,
+            WorldAbundance::Abundant => 2,
+        }
+    }
+}
+
+impl fmt::Display for WorldAbundance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Rift => "Rift",
+            Self::Sparse => "Sparse",
+            Self::Nominal => "Nominal",
+            Self::Dense => "Dense",
+            Self::Abundant => "Abundant",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Subsector {
+    name: String,
+    map: BTreeMap<Point, World>,
+    hooks: Vec<String>,
+    /// Dated session notes of the campaign set in this `Subsector`
+    journal: Vec<JournalEntry>,
+    /// Hexes starred to be found again quickly, in the order they were starred
+    bookmarks: Vec<Bookmark>,
+    rule_set: RuleSet,
+    polities: Vec<Polity>,
+    /// Links of the x-boat network between pairs of hub worlds
+    xboat_routes: Vec<(Point, Point)>,
+    /// Where the `Subsector` is within a sector, used for the hex codes of Traveller Map exports
+    location: Option<SectorLocation>,
+    /// Lines, arrows, circles, and notes drawn over the map, in the order they were drawn
+    annotations: Vec<Annotation>,
+    /// Date the campaign set in this `Subsector` has reached, if its clock has been started
+    campaign_date: Option<ImperialDate>,
+}
+
+impl Subsector {
+    pub const COLUMNS: usize = 8;
+    pub const ROWS: usize = 10;
+
+    pub fn empty() -> Self {
+        Subsector {
+            name: String::from("Subsector"),
+            map: BTreeMap::new(),
+            hooks: Vec::new(),
+            journal: Vec::new(),
+            bookmarks: Vec::new(),
+            rule_set: RuleSet::default(),
+            polities: Vec::new(),
+            xboat_routes: Vec::new(),
+            location: None,
+            annotations: Vec::new(),
+            campaign_date: None,
+        }
+    }
+
+    pub fn hooks(&self) -> &[String] {
+        &self.hooks[..]
+    }
+
+    pub fn hooks_mut(&mut self) -> &mut Vec<String> {
+        &mut self.hooks
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name[..]
+    }
+
+    pub fn set_name(&mut self, new_name: String) {
+        self.name = new_name;
+    }
+
+    /** The [`RuleSet`] new worlds in the `Subsector` are generated with. */
+    pub fn rule_set(&self) -> RuleSet {
+        self.rule_set
+    }
+
+    /** Generate a new `Subsector` of random worlds.
+
+    `clustering` is a percentage from 0 to 100; at 0 every hex independently has the same chance of
+    holding a world, while higher values follow a random density field more closely so that
+    clusters, chains, and rifts of worlds emerge.
+    */
+    pub fn new(world_abundance_dm: i16, clustering: u8, rule_set: RuleSet) -> Self {
+        let mut subsector = Self::empty();
+        let mut names = random_names(Subsector::COLUMNS * Subsector::ROWS).into_iter();
+        subsector.name = random_subsector_name(NameStyle::default());
+        subsector.rule_set = rule_set;
+
+        let density = density_noise();
+        let clustering = clustering.min(100) as f64 / 100.0;
+
+        for x in 1..=Subsector::COLUMNS {
+            for y in 1..=Subsector::ROWS {
+                // At full clustering the densest and sparsest hexes are all but certain
+                let cluster_dm =
+                    (clustering * CLUSTER_MAX_DM * density[x - 1][y - 1]).round() as i16;
+
+                // Fifty-fifty chance with no modifiers
+                let roll = dice::roll_1d(6) + world_abundance_dm + cluster_dm;
+                if roll >= 4 {
+                    let point = Point {
+                        x: x as i32,
+                        y: y as i32,
+                    };
+
+                    let name = names.next().unwrap();
+                    let world = World::with_rule_set(name, rule_set);
+                    subsector
+                        .insert_world(&point, world)
+                        .expect("All new subsector world's should be valid");
+                }
+            }
+        }
+        subsector.generate_polities();
+        subsector.generate_xboat_routes();
+        subsector.generate_hooks();
+        subsector
+    }
+
+    /** Generate `count` new `Subsector`s of random worlds like [`Subsector::new`], in parallel.
+
+    The subsectors are split evenly between one thread per available core, or generated one at a
+    time in the browser. Every thread rolls with its own RNG, so the subsectors are as independent
+    as ones generated one at a time.
+    */
+    pub fn new_batch(
+        count: usize,
+        world_abundance_dm: i16,
+        clustering: u8,
+        rule_set: RuleSet,
+    ) -> Vec<Self> {
+        // There are no threads to split the work between in the browser
+        if cfg!(target_arch = "wasm32") {
+            return (0..count)
+                .map(|_| Self::new(world_abundance_dm, clustering, rule_set))
+                .collect();
+        }
+
+        let threads = thread::available_parallelism()
+            .map_or(1, usize::from)
+            .clamp(1, count.max(1));
+        thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|index| {
+                    let share = count / threads + usize::from(index < count % threads);
+                    scope.spawn(move || {
+                        (0..share)
+                            .map(|_| Self::new(world_abundance_dm, clustering, rule_set))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .flat_map(|worker| {
+                    worker
+                        .join()
+                        .expect("Subsector generation threads should not panic")
+                })
+                .collect()
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn show(&self) {
+        const HEX_GRID: &str = include_str!("../resources/hex_grid.txt");
+        let mut hex_grid = HEX_GRID.to_string();
+        for x in 1..=Subsector::COLUMNS {
+            for y in 1..=Subsector::ROWS {
+                let marker = format!(".{}", 100 * x + y);
+                let point = Point {
+                    x: x as i32,
+                    y: y as i32,
+                };
+
+                if let Some(world) = &self.map.get(&point) {
+                    if world.has_gas_giant() {
+                        hex_grid = hex_grid.replace(&marker, "G   ")
+                    } else {
+                        hex_grid = hex_grid.replace(&marker, "*   ");
+                    }
+                } else {
+                    hex_grid = hex_grid.replace(&marker, "    ");
+                }
+            }
+        }
+
+        println!("{}\n", hex_grid);
+    }
+
+    pub fn to_json(&self) -> String {
+        JsonableSubsector::from(self).to_string()
+    }
+
+    /** Load a `Subsector` from `json`, upgrading it first if it was saved by an older version.
+
+    # Returns
+    - `Ok(Subsector)` if `json` could be read
+    - `Err` with a [`SchemaError`] if `json` was saved by a newer version or couldn't be upgraded,
+      or any other error from reading it
+    */
+    pub fn try_from_json(json: &str) -> Result<Self, Box<dyn Error>> {
+        let jsonable = JsonableSubsector::try_from_json(json)?;
+        let subsector = Self::try_from(jsonable)?;
+        Ok(subsector)
+    }
+
+    pub fn to_t5_table(&self) -> String {
+        T5Table::from(self).to_string()
+    }
+
+    /** Get Traveller Map sector metadata XML with the `Subsector`'s polities and x-boat network. */
+    pub fn to_sector_metadata(&self) -> String {
+        SectorMetadata::from(self).to_string()
+    }
+
+    pub fn to_csv(&self) -> String {
+        CsvTable::from(self).to_string()
+    }
+
+    /** Create a copy of the `Subsector` whose worlds are replaced by the ones in `csv`.
+
+    Data that isn't in the CSV table, like factions, is kept for worlds that are still at the same
+    hex.
+    */
+    pub fn with_csv(&self, csv: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(CsvTable::try_from_csv(csv)?.apply_to(self))
+    }
+
+    /** Create an empty `Subsector` with the same rules as this one holding only the worlds in
+    `csv`, e.g. to [`merge`](Subsector::merge) them into this one.
+    */
+    pub fn worlds_from_csv(&self, csv: &str) -> Result<Self, Box<dyn Error>> {
+        let empty = Subsector {
+            rule_set: self.rule_set,
+            ..Subsector::empty()
+        };
+        Ok(CsvTable::try_from_csv(csv)?.apply_to(&empty))
+    }
+
+    /** Generate an SVG image of the full `Subsector` map for export to disk using `template`.
+
+    Only the layers enabled in `options` are drawn; `route` is the list of stops drawn when the
+    routes layer is enabled. The map is arranged in the layout of `options` on a single page, so a
+    [`MapLayout::Poster`] comes out as one page twice the size of the template's. `template` is
+    ignored if the map is drawn by [`MapRenderer::Standalone`], see
+    [`MapOptions::effective_renderer`].
+
+    # Returns
+    - `Ok(String)` with the SVG image
+    - `Err(MapRenderError)` if the template is malformed or is missing a hex something is drawn in
+    */
+    pub fn generate_svg(
+        &self,
+        template: &MapTemplate,
+        options: &MapOptions,
+        route: &[Point],
+    ) -> Result<String, MapRenderError> {
+        self.generate_svg_page(template, options, route, None)
+    }
+
+    /** Generate an SVG image of each printed page of the `Subsector` map using `template`.
+
+    This is the same as [`Subsector::generate_svg`] except that a tiled layout like
+    [`MapLayout::Poster`] is cut into a separate image for each of its pages, in reading order.
+    */
+    pub fn generate_svg_pages(
+        &self,
+        template: &MapTemplate,
+        options: &MapOptions,
+        route: &[Point],
+    ) -> Result<Vec<String>, MapRenderError> {
+        let transform = match options.effective_renderer() {
+            MapRenderer::Template => template.layout_transform(options.layout),
+            MapRenderer::Standalone => standalone_map::layout_transform(options.layout),
+        };
+        match transform {
+            Some(transform) if transform.pages.len() > 1 => transform
+                .pages
+                .iter()
+                .map(|page| self.generate_svg_page(template, options, route, Some(*page)))
+                .collect(),
+            _ => Ok(vec![self.generate_svg(template, options, route)?]),
+        }
+    }
+
+    /** Generate an SVG image of the area `page` of the map's layout, or the whole layout if `None`.
+
+    The template's contents and the generated layer are wrapped in a group that moves them into
+    place on the layout's canvas, so world elements are still positioned with the template's own
+    center markers.
+    */
+    fn generate_svg_page(
+        &self,
+        template: &MapTemplate,
+        options: &MapOptions,
+        route: &[Point],
+        page: Option<[f64; 4]>,
+    ) -> Result<String, MapRenderError> {
+        if options.effective_renderer() == MapRenderer::Standalone {
+            return self.generate_standalone_svg_page(options, route, page);
+        }
+
+        let transform = template.layout_transform(options.layout);
+        let mut reader = quick_xml::Reader::from_str(template.svg());
+        let mut writer = quick_xml::Writer::new_with_indent(io::Cursor::new(Vec::new()), b' ', 2);
+        loop {
+            match reader.read_event() {
+                Err(e) => {
+                    return Err(MapRenderError::Xml(format!(
+                        "Error at position {}: {}",
+                        reader.buffer_position(),
+                        e
+                    )))
+                }
+                Ok(Event::Eof) => break,
+                Ok(Event::Comment(_)) => (),
+
+                Ok(Event::Start(element)) if element.name().as_ref() == b"svg" => {
+                    let transform = match &transform {
+                        Some(transform) => transform,
+                        None => {
+                            writer.write_event(Event::Start(element))?;
+                            continue;
+                        }
+                    };
+
+                    let [x, y, width, height] =
+                        page.unwrap_or([0.0, 0.0, transform.canvas[0], transform.canvas[1]]);
+                    let view_box = format!("{} {} {} {}", x, y, width, height);
+                    let page_width = transform.physical_length(width);
+                    let page_height = transform.physical_length(height);
+
+                    let attributes = element
+                        .attributes()
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(|e| MapRenderError::Xml(e.to_string()))?;
+                    let mut svg = BytesStart::new("svg");
+                    svg.extend_attributes(attributes.into_iter().filter(|attr| {
+                        !matches!(attr.key.as_ref(), b"viewBox" | b"width" | b"height")
+                    }));
+                    svg.extend_attributes(vec![
+                        ("viewBox", &view_box[..]),
+                        ("width", &page_width[..]),
+                        ("height", &page_height[..]),
+                    ]);
+                    writer.write_event(Event::Start(svg))?;
+
+                    let mut layout = BytesStart::new("g");
+                    let svg_transform = transform.svg_transform();
+                    layout.extend_attributes(vec![
+                        ("id", "MapLayout"),
+                        ("transform", &svg_transform[..]),
+                    ]);
+                    writer.write_event(Event::Start(layout))?;
+                }
+
+                Ok(Event::Start(element)) => {
+                    let is_hex_number = matches!(
+                        element.try_get_attribute("class"),
+                        Ok(Some(class)) if class.value.as_ref() == b"text-hex-coord"
+                    );
+                    if is_hex_number && !options.hex_numbers {
+                        reader.read_to_end(element.to_end().name())?;
+                    } else if let Ok(Some(id_attr)) = element.try_get_attribute("id") {
+                        let id = str::from_utf8(&id_attr.value)
+                            .map_err(|e| MapRenderError::Xml(e.to_string()))?;
+                        if id == "CenterMarkers" {
+                            // Skip past all the center markers; they're invisible so we don't want
+                            // the svg rasterizer to waste time with them
+                            reader.read_to_end(element.to_end().name())?;
+                        } else {
+                            writer.write_event(Event::Start(element))?;
+                        }
+                    } else {
+                        writer.write_event(Event::Start(element))?;
+                    }
+                }
+
+                Ok(Event::End(element)) => {
+                    if element.name().as_ref() == b"svg" {
+                        let mut layer = BytesStart::new("g");
+                        layer.extend_attributes(vec![
+                            ("inkscape:groupmode", "layer"),
+                            ("id", "layer6"),
+                            ("inkscape:label", "Generated"),
+                        ]);
+                        writer.write_indent()?;
+                        writer.write_event(Event::Start(layer))?;
+                        // Comes after the template's own stylesheet, so its classes win
+                        writer
+                            .create_element("style")
+                            .write_text_content(BytesText::new(&options.style.css()))?;
+                        self.process_layers_to_svg_elements(
+                            &mut writer,
+                            options,
+                            route,
+                            &template.center_markers,
+                            Some(template),
+                        )?;
+                        // End of layer
+                        writer.write_event(Event::End(BytesEnd::new("g")))?;
+                        if transform.is_some() {
+                            // End of layout group
+                            writer.write_event(Event::End(BytesEnd::new("g")))?;
+                        }
+                    }
+                    // Close svg section
+                    writer.write_event(Event::End(element))?;
+                }
+
+                Ok(Event::Empty(element)) => {
+                    if !options.polity_colors {
+                        writer.write_event(Event::Empty(element))?;
+                        continue;
+                    }
+
+                    let element = if let Ok(Some(id_attr)) = element.try_get_attribute("id") {
+                        let id = str::from_utf8(&id_attr.value)
+                            .map_err(|e| MapRenderError::Xml(e.to_string()))?;
+                        let point = match id.strip_prefix("HexPath-") {
+                            Some(point_str) => Some(Point::try_from(point_str).map_err(|_| {
+                                MapRenderError::InvalidTemplate(format!("Unparsable point in {id}"))
+                            })?),
+                            None => None,
+                        };
+                        let polity_index = point.and_then(|point| self.polity_index(&point));
+
+                        if let Some(polity_index) = polity_index {
+                            let color_index = polity_index % PolityColor::ALL_VALUES.len();
+                            let class = PolityColor::ALL_VALUES[color_index].class();
+
+                            let attributes = element
+                                .attributes()
+                                .collect::<Result<Vec<_>, _>>()
+                                .map_err(|e| MapRenderError::Xml(e.to_string()))?;
+                            let mut hex = BytesStart::new("path");
+                            hex.extend_attributes(attributes.into_iter().map(|attr| {
+                                if attr.key.as_ref() == b"class" {
+                                    ("class", &class[..]).into()
+                                } else {
+                                    attr
+                                }
+                            }));
+
+                            hex
+                        } else {
+                            element
+                        }
+                    } else {
+                        element
+                    };
+                    writer.write_event(Event::Empty(element))?;
+                }
+
+                Ok(Event::Text(text)) => {
+                    let t: &[u8] = text.as_ref();
+                    if t == b"Subsector Name" {
+                        let map_title = format!("{} Subsector", self.name());
+                        let subsector_name = BytesText::new(&map_title);
+                        writer.write_event(Event::Text(subsector_name))?;
+                    } else {
+                        writer.write_event(Event::Text(text))?;
+                    }
+                }
+
+                Ok(event) => writer.write_event(event)?,
+            }
+        }
+
+        String::from_utf8(writer.into_inner().into_inner())
+            .map_err(|e| MapRenderError::Xml(e.to_string()))
+    }
+
+    /** Generate SVG of the subsector map grid without worlds, with the number of each hex in it
+    if `hex_numbers` is set.
+
+    Primarily intended to be layered with an image of the `Subsector`'s worlds.
+
+    # Returns
+    - `Ok(String)` with the grid from the built-in template
+    - `Err(MapRenderError)` if the built-in template could not be parsed
+    */
+    pub fn generate_grid_svg(&self, hex_numbers: bool) -> Result<String, MapRenderError> {
+        if hex_numbers {
+            SUBSECTOR_GRID_SVG.clone()
+        } else {
+            SUBSECTOR_GRID_SVG_WITHOUT_HEX_NUMBERS.clone()
+        }
+    }
+
+    /** Generate SVG of a bare hex grid drawn from scratch, titled with the `Subsector`'s name.
+
+    This is the fallback for when a map can't be drawn from its template, so it doesn't draw any of
+    the worlds or routes that could have been the problem. The hexes are in the same places as in
+    the built-in template, and are numbered if `hex_numbers` is set.
+    */
+    pub fn generate_fallback_grid_svg(&self, hex_numbers: bool) -> String {
+        let mut grid = Subsector::empty();
+        grid.set_name(self.name().to_string());
+        let options = MapOptions {
+            hex_numbers,
+            renderer: MapRenderer::Standalone,
+            ..MapOptions::default()
+        };
+        grid.generate_standalone_svg_page(&options, &[], None)
+            .expect("Standalone map of an empty subsector should always render")
+    }
+
+    /** Generate SVG of a bare hex grid drawn from scratch without a title, the same for every
+    `Subsector`.
+
+    This is the fallback for when the grid layered with an image of a `Subsector`'s worlds can't be
+    taken from the built-in template, so nothing about the subsector ends up in the grid's image.
+    */
+    pub fn generate_blank_grid_svg(hex_numbers: bool) -> String {
+        let nameless = Subsector {
+            name: String::new(),
+            ..Subsector::empty()
+        };
+        nameless.generate_fallback_grid_svg(hex_numbers)
+    }
+
+    pub fn get_map(&self) -> &BTreeMap<Point, World> {
+        &self.map
+    }
+
+    /** Returns a reference to the `World` at `point` or `None` if there isn't one. */
+    pub fn get_world(&self, point: &Point) -> Option<&World> {
+        self.map.get(point)
+    }
+
+    /** Find the worlds matching `query`, ignoring case and surrounding whitespace.
+
+    A world matches if its name or UWP contains `query`, or if `query` is its hex location.
+
+    # Returns
+    The locations of the matching worlds in order, or nothing if `query` is blank.
+    */
+    pub fn search_worlds(&self, query: &str) -> Vec<Point> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+
+        self.map
+            .iter()
+            .filter(|(point, world)| {
+                world.name.to_lowercase().contains(&query)
+                    || world.profile_str().to_lowercase().contains(&query)
+                    || point.to_string() == query
+            })
+            .map(|(point, _)| *point)
+            .collect()
+    }
+
+    /** Fix every [`Inconsistency`] of every [`World`] in the `Subsector`. */
+    pub fn fix_all_inconsistencies(&mut self) {
+        for world in self.map.values_mut() {
+            world.fix_all_inconsistencies();
+        }
+    }
+
+    /** Find all of the [`World`]s that violate the generation rules and how they violate them. */
+    pub fn inconsistencies(&self) -> BTreeMap<Point, Vec<Inconsistency>> {
+        self.map
+            .iter()
+            .map(|(point, world)| (*point, world.inconsistencies()))
+            .filter(|(_, inconsistencies)| !inconsistencies.is_empty())
+            .collect()
+    }
+
+    pub fn point_is_inbounds(point: &Point) -> bool {
+        point.x > 0
+            && point.x as usize <= Self::COLUMNS
+            && point.y > 0
+            && point.y as usize <= Self::ROWS
+    }
+
+    /** Inserts `world` at `point`, replacing any other [`World`] that was there previously.
+
+    # Returns
+    - `Ok(Some(world))` with the `World` that was already at `point` if there was one,
+    - `Ok(None)` if the was inserted into an empty location,
+    - `Err(msg)` if `point` was out of bounds and the insertion failed
+    */
+    pub fn insert_world(&mut self, point: &Point, world: World) -> Result<Option<World>, String> {
+        if Self::point_is_inbounds(point) {
+            Ok(self.map.insert(*point, world))
+        } else {
+            Err("Can not insert a world at an out of bounds point".to_string())
+        }
+    }
+
+    /** Inserts a random [`World`] at `point`, replacing any [`World`] there.
+
+    # Returns
+    - `Ok(Some(World))` containing the displaced world if there was one,
+    - `Ok(None)` if the world was inserted into an empty location,
+    - `Err(msg)` if `point` was out of bounds and the insertion failed
+    */
+    pub fn insert_random_world(&mut self, point: &Point) -> Result<Option<World>, String> {
+        let mut names = random_names(Subsector::COLUMNS * Subsector::ROWS + 1).into_iter();
+        let name = names.next().unwrap();
+        self.insert_world(point, World::with_rule_set(name, self.rule_set))
+    }
+
+    /** Inserts a random [`World`] at `point` that is biased toward its neighbors within Jump-2.
+
+    `weighting` ranges from `0.0`, a fully independent world, to `1.0`, where the tech level is the
+    average of the neighbors' and the most common neighboring allegiance is always adopted. Between
+    those, the tech level is pulled part of the way toward the average and the allegiance is adopted
+    with a chance of `weighting`. A world with no neighbors is fully independent.
+
+    # Returns
+    - `Ok(Some(World))` containing the displaced world if there was one,
+    - `Ok(None)` if the world was inserted into an empty location,
+    - `Err(msg)` if `point` was out of bounds and the insertion failed
+    */
+    pub fn insert_contextual_world(
+        &mut self,
+        point: &Point,
+        weighting: f64,
+    ) -> Result<Option<World>, String> {
+        let weighting = weighting.clamp(0.0, 1.0);
+        let mut names = random_names(Subsector::COLUMNS * Subsector::ROWS + 1).into_iter();
+        let mut world = World::with_rule_set(names.next().unwrap(), self.rule_set);
+
+        let neighbors: Vec<&World> = self
+            .map
+            .iter()
+            .filter(|(other, _)| *other != point && other.distance(point) <= 2)
+            .map(|(_, neighbor)| neighbor)
+            .collect();
+
+        if !neighbors.is_empty() && weighting > 0.0 {
+            let average_tech_level = neighbors
+                .iter()
+                .map(|neighbor| neighbor.tech_level.code as f64)
+                .sum::<f64>()
+                / neighbors.len() as f64;
+            let tech_level = world.tech_level.code as f64;
+            let tech_level = tech_level + (average_tech_level - tech_level) * weighting;
+            let max_code = (TABLES.tech_level_table.len() - 1) as f64;
+            world.tech_level =
+                TABLES.tech_level_table[tech_level.round().clamp(0.0, max_code) as usize].clone();
+            // Starport facilities depend on the tech level
+            world.generate_starport_details();
+
+            let mut allegiances: BTreeMap<&str, usize> = BTreeMap::new();
+            for neighbor in neighbors.iter().filter(|n| !n.allegiance.is_empty()) {
+                *allegiances.entry(&neighbor.allegiance).or_default() += 1;
+            }
+            // Ties go to the alphabetically first allegiance so the result is deterministic
+            let majority = allegiances
+                .into_iter()
+                .rev()
+                .max_by_key(|(_, count)| *count)
+                .map(|(allegiance, _)| allegiance.to_string());
+            if let Some(allegiance) = majority {
+                if rand::thread_rng().gen_bool(weighting) {
+                    world.allegiance = allegiance;
+                }
+            }
+
+            world.normalize_data();
+        }
+
+        self.insert_world(point, world)
+    }
+
+    /** Merge all of the worlds from `other` into this `Subsector`.
+
+    Worlds from `other` that land on an occupied hex are handled according to `resolution`. Any
+    world that can't be placed, such as when there are no empty hexes left, is skipped.
+
+    # Returns
+    The number of worlds from `other` that were merged in.
+    */
+    pub fn merge(&mut self, other: Subsector, resolution: MergeResolution) -> usize {
+        let mut merged = 0;
+        for (point, world) in other.map {
+            if !Self::point_is_inbounds(&point) {
+                continue;
+            }
+
+            let destination = if !self.map.contains_key(&point) {
+                Some(point)
+            } else {
+                match resolution {
+                    MergeResolution::Skip => None,
+                    MergeResolution::Overwrite => Some(point),
+                    MergeResolution::MoveToNearestEmpty => self.nearest_empty_point(&point),
+                }
+            };
+
+            if let Some(destination) = destination {
+                self.map.insert(destination, world);
+                merged += 1;
+            }
+        }
+        merged
+    }
+
+    /** Iterate over every hex of the `Subsector` with no `World` in it, column by column. */
+    fn empty_points(&self) -> impl Iterator<Item = Point> + '_ {
+        (1..=Self::COLUMNS as i32)
+            .flat_map(|x| (1..=Self::ROWS as i32).map(move |y| Point { x, y }))
+            .filter(|candidate| !self.map.contains_key(candidate))
+    }
+
+    /** Find the empty hex closest to `point`, if there are any empty hexes left. */
+    pub fn nearest_empty_point(&self, point: &Point) -> Option<Point> {
+        self.empty_points()
+            .min_by_key(|candidate| candidate.distance(point))
+    }
+
+    /** Pick one of the empty hexes at random, if there are any empty hexes left. */
+    pub fn random_empty_point(&self) -> Option<Point> {
+        let empty_points: Vec<Point> = self.empty_points().collect();
+        empty_points.choose(&mut rand::thread_rng()).copied()
+    }
+
+    /** Points in this `Subsector` that are occupied in both it and `other`. */
+    pub fn conflicting_points(&self, other: &Subsector) -> Vec<Point> {
+        other
+            .map
+            .keys()
+            .filter(|point| self.map.contains_key(point))
+            .copied()
+            .collect()
+    }
+
+    /** Remove any [`World`] at `point` and return it if there was one.
+
+    # Returns
+    - `Ok(Some(World))` containing the removed world if there was one,
+    - `Ok(None)` if there was no world to remove,
+    - `Err(msg)` if `point` is out of bounds and the removal failed
+    */
+    pub fn remove_world(&mut self, point: &Point) -> Result<Option<World>, String> {
+        if Self::point_is_inbounds(point) {
+            let world = self.map.remove(point);
+            self.prune_xboat_routes();
+            self.prune_owners();
+            Ok(world)
+        } else {
+            Err("Can not remove a world from an out of bounds point".to_string())
+        }
+    }
+
+    /** Move any [`World`] at `source` to `destination`, replacing any [`World`] there.
+
+    # Returns
+    - `Ok(Some(World))` containing the displaced world that at `destination` if the world moved
+      successfully
+    - `Ok(None)` if the world moved successfully to an empty location, or
+    - `Err(msg)` if the world could not be moved for one of the following reasons:
+        - `source` was out of bounds
+        - `destination` was out of bounds
+        - There was no world to move at `source`
+    */
+    pub fn move_world(
+        &mut self,
+        source: &Point,
+        destination: &Point,
+    ) -> Result<Option<World>, String> {
+        let xboat_routes = self.xboat_routes.clone();
+        let owners = self.owners();
+        if let Some(world) = self.remove_world(source)? {
+            match self.insert_world(destination, world.clone()) {
+                Err(msg) => {
+                    self.insert_world(source, world)
+                        .expect("World should insert back into same location with no problems");
+                    self.xboat_routes = xboat_routes;
+                    self.set_owners(owners);
+                    Err(msg)
+                }
+                ok => {
+                    // X-boat links follow the world to its new hex
+                    let moved = |point: Point| {
+                        if &point == source {
+                            *destination
+                        } else {
+                            point
+                        }
+                    };
+                    self.xboat_routes = xboat_routes
+                        .into_iter()
+                        .map(|(from, to)| (moved(from), moved(to)))
+                        .filter(|(from, to)| from != to)
+                        .collect();
+
+                    // So do ownership links, both to and from the moved world, while any links of
+                    // a world displaced from `destination` go with it
+                    self.set_owners(
+                        owners
+                            .into_iter()
+                            .filter(|(owned, owner)| {
+                                source == destination
+                                    || (owned != destination && owner != destination)
+                            })
+                            .map(|(owned, owner)| (moved(owned), moved(owner)))
+                            .collect(),
+                    );
+                    ok
+                }
+            }
+        } else {
+            Err(format!("No world to move at {}", source))
+        }
+    }
+
+    /** Attempts to produce a "player-safe" copy of the `Subsector`.
+
+    To do so, for each `World` it defaults all of the fields that are likely to have spoilers to the
+    zeroth index of their respective roll tables or completely blanks them where possible.
+    These likely fields are:
+
+    1. Factions
+    2. Culture
+    3. World Tags
+    4. Secret notes
+    5. History
+
+    All of the `Subsector`'s adventure hooks are removed as well.
+
+    This is intended to work alongside a player-safe version of the GUI that has the defaulted
+    fields removed; this is more to prevent overly-clever players from mining the JSON for spoilers.
+    */
+    pub fn copy_player_safe(&self) -> Self {
+        let mut player_safe_subsector = self.clone();
+        player_safe_subsector.make_player_safe();
+        player_safe_subsector
+    }
+
+    /** Attempts to mutate the `Subsector` to a "player-safe" state.
+
+    To do so, for each `World` it defaults all of the fields that are likely to have spoilers to the
+    zeroth index of their respective roll tables or completely blanks them where possible.
+    These likely fields are:
+
+    1. Factions
+    2. Culture
+    3. World Tags
+    4. Secret notes
+    5. History
+
+    All of the `Subsector`'s adventure hooks are removed as well.
+
+    This is intended to work alongside a player-safe version of the GUI that has the defaulted
+    fields removed; this is more to prevent overly-clever players from mining the JSON for spoilers.
+    */
+    pub fn make_player_safe(&mut self) {
+        for (_point, world) in self.map.iter_mut() {
+            world.make_player_safe();
+        }
+        self.hooks.clear();
+    }
+
+    /** Remove the history of every `World` in the `Subsector`. */
+    pub fn clear_world_history(&mut self) {
+        for world in self.map.values_mut() {
+            world.history.clear();
+        }
+    }
+}
+
+impl Default for Subsector {
+    fn default() -> Self {
+        Subsector::new(0, 0, RuleSet::default())
+    }
+}
+
+impl Subsector {
+    /** Draw the world, route, and legend layers enabled in `options` onto a map whose hexes are
+    centered at `centers`.
+
+    World and gas giant symbols are referenced from `symbols` if there is a template to take them
+    from, or else drawn in place.
+    */
+    fn process_layers_to_svg_elements<W: std::io::Write>(
+        &self,
+        writer: &mut quick_xml::Writer<W>,
+        options: &MapOptions,
+        route: &[Point],
+        centers: &BTreeMap<Point, Translation>,
+        symbols: Option<&MapTemplate>,
+    ) -> Result<(), MapRenderError> {
+        if options.xboat_routes {
+            process_xboat_routes_to_svg_elements(writer, centers, &self.xboat_routes)?;
+        }
+        if options.routes {
+            process_route_to_svg_elements(writer, centers, route)?;
+        }
+        if options.annotations {
+            annotations::process_annotations_to_svg_elements(writer, centers, &self.annotations)?;
+        }
+        if options.legend {
+            legend::process_legend_to_svg_elements(
+                writer,
+                &self.map_legend(options, route),
+                symbols.is_none(),
+            )?;
+        }
+        for (point, world) in &self.map {
+            let center = hex_center(centers, point)?;
+            process_world_to_svg_elements(writer, symbols, options, point, world, center)?;
+        }
+        Ok(())
+    }
+}
+
+/** Draw a line between the centers of each consecutive pair of hexes in `route`. */
+fn process_route_to_svg_elements<W: std::io::Write>(
+    writer: &mut quick_xml::Writer<W>,
+    centers: &BTreeMap<Point, Translation>,
+    route: &[Point],
+) -> Result<(), MapRenderError> {
+    for (index, pair) in route.windows(2).enumerate() {
+        let from = hex_center(centers, &pair[0])?;
+        let to = hex_center(centers, &pair[1])?;
+        writer
+            .create_element("line")
+            .with_attributes(vec![
+                ("class", ROUTE_CLASS),
+                ("x1", &from.x.to_string()),
+                ("y1", &from.y.to_string()),
+                ("x2", &to.x.to_string()),
+                ("y2", &to.y.to_string()),
+                ("id", &format!("RouteLine{}", index)),
+            ])
+            .write_empty()?;
+    }
+    Ok(())
+}
+
+/** Draw a dashed line between the centers of the two hubs of each x-boat link in `links`. */
+fn process_xboat_routes_to_svg_elements<W: std::io::Write>(
+    writer: &mut quick_xml::Writer<W>,
+    centers: &BTreeMap<Point, Translation>,
+    links: &[(Point, Point)],
+) -> Result<(), MapRenderError> {
+    for (index, (from, to)) in links.iter().enumerate() {
+        let from = hex_center(centers, from)?;
+        let to = hex_center(centers, to)?;
+        writer
+            .create_element("line")
+            .with_attributes(vec![
+                ("class", XBOAT_ROUTE_CLASS),
+                ("x1", &from.x.to_string()),
+                ("y1", &from.y.to_string()),
+                ("x2", &to.x.to_string()),
+                ("y2", &to.y.to_string()),
+                ("id", &format!("XboatLine{}", index)),
+            ])
+            .write_empty()?;
+    }
+    Ok(())
+}
+
+/** Get the center of the hex at `point`, or an error if the map has no center marker for it. */
+fn hex_center(
+    centers: &BTreeMap<Point, Translation>,
+    point: &Point,
+) -> Result<Translation, MapRenderError> {
+    centers
+        .get(point)
+        .copied()
+        .ok_or(MapRenderError::MissingCenterMarker(*point))
+}
+
+/** Draw the layers of `world` enabled in `options` around the center of its hex.
+
+World and gas giant symbols are referenced from `symbols` if there is a template to take them from,
+or else drawn in place.
+*/
+fn process_world_to_svg_elements<W: std::io::Write>(
+    writer: &mut quick_xml::Writer<W>,
+    symbols: Option<&MapTemplate>,
+    options: &MapOptions,
+    point: &Point,
+    world: &World,
+    center: Translation,
+) -> Result<(), MapRenderError> {
+    let point_str = point.to_string();
+    let marker_translation = &center;
+
+    // Circle amber and red zone worlds
+    let zone_class = match world.travel_code {
+        TravelCode::Safe => None,
+        TravelCode::Amber => Some(AMBER_ZONE_CLASS),
+        TravelCode::Red => Some(RED_ZONE_CLASS),
+    };
+    if let (true, Some(class)) = (options.travel_zones, zone_class) {
+        writer
+            .create_element("circle")
+            .with_attributes(vec![
+                ("class", class),
+                ("cx", &marker_translation.x.to_string()),
+                ("cy", &marker_translation.y.to_string()),
+                ("r", &TRAVEL_ZONE_RADIUS.to_string()),
+                ("id", &format!("{}TravelZone", point_str)),
+            ])
+            .write_empty()?;
+    }
+
+    let symbol_scale = options.style.clamped_symbol_scale();
+
+    // Place gas giant symbol
+    if world.has_gas_giant() {
+        let offset = Translation { x: 0.0, y: -6.0 };
+        let id = format!("{:02}{:02}GasGiantSymbol", point.x, point.y);
+        match symbols {
+            Some(template) => {
+                let transform = symbol_transform(
+                    *marker_translation + offset,
+                    template.gas_giant_trans,
+                    symbol_scale,
+                );
+                writer
+                    .create_element("use")
+                    .with_attributes(vec![
+                        ("href", "#GasGiantSymbol"),
+                        ("id", &id[..]),
+                        ("transform", &transform[..]),
+                    ])
+                    .write_empty()?;
+            }
+            None => standalone_map::write_gas_giant_symbol(
+                writer,
+                *marker_translation + offset,
+                symbol_scale,
+                &id,
+            )?,
+        }
+    }
+
+    // Place world name, fit to the hex
+    if options.world_names {
+        name_layout::write_world_name(
+            writer,
+            *marker_translation,
+            &format!("{}NameText", point_str),
+            &world.name,
+        )?;
+    }
+
+    // Place dry/world symbol
+    let symbol_id = if world.is_wet_world() {
+        "WetWorldSymbol"
+    } else {
+        "DryWorldSymbol"
+    };
+    let offset = Translation { x: -5.0, y: 4.0 };
+    let id = format!("{}{}", point_str, symbol_id);
+    match symbols {
+        Some(template) => {
+            let world_trans = if world.is_wet_world() {
+                template.wet_world_trans
+            } else {
+                template.dry_world_trans
+            };
+            let transform =
+                symbol_transform(*marker_translation + offset, world_trans, symbol_scale);
+            writer
+                .create_element("use")
+                .with_attributes(vec![
+                    ("href", &format!("#{}", symbol_id)[..]),
+                    ("id", &id[..]),
+                    ("transform", &transform[..]),
+                ])
+                .write_empty()?;
+        }
+        None => standalone_map::write_world_symbol(
+            writer,
+            *marker_translation + offset,
+            world.is_wet_world(),
+            symbol_scale,
+            &id,
+        )?,
+    }
+    standalone_map::write_subtype_symbol(
+        writer,
+        *marker_translation + offset,
+        world.subtype,
+        symbol_scale,
+        &format!("{}SubtypeSymbol", point_str),
+    )?;
+
+    // Add `StarportClass-TL` text to hex
+    if options.starport_tl {
+        let offset = Translation { x: 5.0, y: 5.0 };
+        let trans = *marker_translation + offset;
+        let starport_tl = world.starport_tl_str();
+        writer
+            .create_element("text")
+            .with_attributes(vec![
+                ("xml:space", "preserve"),
+                ("class", STARPORT_TL_CLASS),
+                ("x", &trans.x.to_string()),
+                ("y", &trans.y.to_string()),
+                ("id", &format!("{}StarportTlText", point_str)),
+            ])
+            .write_text_content(BytesText::new(&starport_tl))?;
+    }
+
+    // Add base codes to the upper left of the hex
+    let bases = world.base_str();
+    if options.bases && bases != "-" {
+        let offset = Translation { x: -6.0, y: -3.0 };
+        let trans = *marker_translation + offset;
+        writer
+            .create_element("text")
+            .with_attributes(vec![
+                ("xml:space", "preserve"),
+                ("class", STARPORT_TL_CLASS),
+                ("x", &trans.x.to_string()),
+                ("y", &trans.y.to_string()),
+                ("id", &format!("{}BasesText", point_str)),
+            ])
+            .write_text_content(BytesText::new(&bases))?;
+    }
+
+    // Place world profile code, as large as a name and in its place if there isn't one
+    if options.uwps {
+        let (offset, class) = if options.world_names {
+            (Translation { x: 0.0, y: 10.0 }, WORLD_PROFILE_CLASS)
+        } else {
+            (Translation { x: 0.0, y: 0.0 }, WORLD_NAME_CLASS)
+        };
+        let trans = *marker_translation + offset;
+        writer
+            .create_element("text")
+            .with_attributes(vec![
+                ("xml:space", "preserve"),
+                ("class", class),
+                ("x", &format!("{}", trans.x)),
+                ("y", &format!("{}", trans.y)),
+                ("id", &format!("{}WorldProfileText", point_str)),
+            ])
+            .write_text_content(BytesText::new(&world.profile_str()))?;
+    }
+    Ok(())
+}
+
+/** Get the `transform` of a `use` of a template symbol centered at `origin` that moves it to
+`center` and scales it by `scale` around its center.
+*/
+fn symbol_transform(center: Translation, origin: Translation, scale: f64) -> String {
+    if scale == 1.0 {
+        let trans = center - origin;
+        format!("translate({},{})", trans.x, trans.y)
+    } else {
+        format!(
+            "translate({},{}) scale({}) translate({},{})",
+            center.x, center.y, scale, -origin.x, -origin.y
+        )
+    }
+}
+
+/** Generate a smooth random field over the hexes of a `Subsector` with values from -1 to 1.
+
+Random values are placed on a coarse lattice every `CLUSTER_SPACING` hexes and smoothly
+interpolated in between, i.e. value noise. The field is indexed as `[x - 1][y - 1]`.
+*/
+fn density_noise() -> Vec<Vec<f64>> {
+    let lattice_columns = Subsector::COLUMNS / CLUSTER_SPACING + 2;
+    let lattice_rows = Subsector::ROWS / CLUSTER_SPACING + 2;
+
+    let mut rng = rand::thread_rng();
+    let lattice: Vec<Vec<f64>> = (0..lattice_columns)
+        .map(|_| {
+            (0..lattice_rows)
+                .map(|_| rng.gen_range(-1.0..=1.0))
+                .collect()
+        })
+        .collect();
+
+    let smoothstep = |t: f64| t * t * (3.0 - 2.0 * t);
+    let lerp = |a: f64, b: f64, t: f64| a + (b - a) * t;
+
+    (0..Subsector::COLUMNS)
+        .map(|x| {
+            (0..Subsector::ROWS)
+                .map(|y| {
+                    // Odd columns sit half a hex higher than even ones
+                    let fx = x as f64 / CLUSTER_SPACING as f64;
+                    let fy =
+                        (y as f64 + if x % 2 == 0 { 0.0 } else { 0.5 }) / CLUSTER_SPACING as f64;
+                    let (ix, iy) = (fx as usize, fy as usize);
+                    let (tx, ty) = (smoothstep(fx.fract()), smoothstep(fy.fract()));
+
+                    let top = lerp(lattice[ix][iy], lattice[ix + 1][iy], tx);
+                    let bottom = lerp(lattice[ix][iy + 1], lattice[ix + 1][iy + 1], tx);
+                    lerp(top, bottom, ty)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn subsector_grid_svg(hex_numbers: bool) -> Result<String, MapRenderError> {
+    let mut reader = quick_xml::Reader::from_str(SUBSECTOR_TEMPLATE_SVG);
+    let mut writer = quick_xml::Writer::new(io::Cursor::new(Vec::new()));
+    loop {
+        match reader.read_event() {
+            Err(e) => {
+                return Err(MapRenderError::Xml(format!(
+                    "Error at position {}: {}",
+                    reader.buffer_position(),
+                    e
+                )))
+            }
+            Ok(Event::Eof) => break,
+            Ok(Event::Comment(_)) => (),
+
+            Ok(Event::Start(element)) => {
+                let is_hex_number = matches!(
+                    element.try_get_attribute("class"),
+                    Ok(Some(class)) if class.value.as_ref() == b"text-hex-coord"
+                );
+                if is_hex_number && !hex_numbers {
+                    reader.read_to_end(element.to_end().name())?;
+                } else if let Ok(Some(id_attr)) = element.try_get_attribute("id") {
+                    let id = str::from_utf8(&id_attr.value)
+                        .map_err(|e| MapRenderError::Xml(e.to_string()))?;
+                    match id {
+                        "SubsectorName" => {
+                            reader.read_to_end(element.to_end().name())?;
+                        }
+                        _ => writer.write_event(Event::Start(element))?,
+                    }
+                } else {
+                    writer.write_event(Event::Start(element))?;
+                }
+            }
+
+            Ok(Event::End(element)) => writer.write_event(Event::End(element))?,
+            Ok(Event::Empty(element)) => writer.write_event(Event::Empty(element))?,
+            Ok(Event::Text(text)) => writer.write_event(Event::Text(text))?,
+            Ok(Event::Decl(element)) => writer.write_event(Event::Decl(element))?,
+            Ok(event) => {
+                return Err(MapRenderError::InvalidTemplate(format!(
+                    "Unexpected element in template svg: {:?}",
+                    event
+                )))
+            }
+        }
+    }
+    writer.write_event(Event::Eof)?;
+
+    String::from_utf8(writer.into_inner().into_inner())
+        .map_err(|e| MapRenderError::Xml(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contextual_world() {
+        let point = |x, y| Point { x, y };
+        let mut subsector = Subsector::empty();
+        for (neighbor, tech_level, allegiance) in [
+            (point(2, 2), 8, "ImDd"),
+            (point(2, 4), 12, "ImDd"),
+            (point(4, 3), 10, "CsIm"),
+            // Too far away to count as a neighbor
+            (point(8, 8), 0, "NaHu"),
+        ] {
+            let mut world = World::empty();
+            world.tech_level = TABLES.tech_level_table[tech_level].clone();
+            world.allegiance = allegiance.to_string();
+            subsector.insert_world(&neighbor, world).unwrap();
+        }
+
+        const ATTEMPTS: usize = 100;
+        for _ in 0..ATTEMPTS {
+            subsector
+                .insert_contextual_world(&point(2, 3), 1.0)
+                .unwrap();
+            let world = subsector.get_world(&point(2, 3)).unwrap();
+            assert_eq!(world.tech_level.code, 10);
+            assert_eq!(world.allegiance, "ImDd");
+        }
+
+        assert!(subsector
+            .insert_contextual_world(&point(0, 0), 0.5)
+            .is_err());
+    }
+
+    #[test]
+    fn point_distance() {
+        let point = |x, y| Point { x, y };
+        assert_eq!(point(1, 1).distance(&point(1, 1)), 0);
+
+        // Neighbors of an odd and even column respectively
+        for neighbor in [(1, 2), (1, 4), (2, 2), (2, 3)] {
+            assert_eq!(point(1, 3).distance(&point(neighbor.0, neighbor.1)), 1);
+        }
+        for neighbor in [(2, 2), (1, 3), (1, 4), (3, 3), (3, 4), (2, 4)] {
+            assert_eq!(point(2, 3).distance(&point(neighbor.0, neighbor.1)), 1);
+        }
+
+        assert_eq!(point(1, 1).distance(&point(2, 2)), 2);
+        assert_eq!(point(1, 1).distance(&point(8, 10)), 13);
+        assert_eq!(point(8, 10).distance(&point(1, 1)), 13);
+    }
+
+    #[test]
+    fn random_empty_point() {
+        let mut subsector = Subsector::empty();
+        let point = subsector.random_empty_point().unwrap();
+        assert!(Subsector::point_is_inbounds(&point));
+
+        for x in 1..=Subsector::COLUMNS as i32 {
+            for y in 1..=Subsector::ROWS as i32 {
+                if (x, y) != (3, 4) {
+                    subsector
+                        .insert_world(&Point { x, y }, World::empty())
+                        .unwrap();
+                }
+            }
+        }
+        assert_eq!(subsector.random_empty_point(), Some(Point { x: 3, y: 4 }));
+
+        subsector
+            .insert_world(&Point { x: 3, y: 4 }, World::empty())
+            .unwrap();
+        assert_eq!(subsector.random_empty_point(), None);
+    }
+
+    #[test]
+    fn search_worlds() {
+        let mut subsector = Subsector::empty();
+        for (point, name) in [((1, 1), "Regina"), ((2, 1), "Efate"), ((3, 1), "Ruie")] {
+            let mut world = World::empty();
+            world.name = name.to_string();
+            let point = Point {
+                x: point.0,
+                y: point.1,
+            };
+            subsector.insert_world(&point, world).unwrap();
+        }
+
+        assert!(subsector.search_worlds("  ").is_empty());
+        assert_eq!(
+            subsector.search_worlds(" A "),
+            vec![Point { x: 1, y: 1 }, Point { x: 2, y: 1 }]
+        );
+        assert_eq!(subsector.search_worlds("0201"), vec![Point { x: 2, y: 1 }]);
+        assert!(subsector.search_worlds("Vland").is_empty());
+    }
+
+    #[test]
+    fn subsector_clustering() {
+        const ATTEMPTS: usize = 100;
+        for _ in 0..ATTEMPTS {
+            let density = density_noise();
+            assert_eq!(density.len(), Subsector::COLUMNS);
+            for column in density {
+                assert_eq!(column.len(), Subsector::ROWS);
+                assert!(column.iter().all(|value| (-1.0..=1.0).contains(value)));
+            }
+
+            Subsector::new(0, 100, RuleSet::default());
+        }
+    }
+
+    #[test]
+    fn subsector_batch() {
+        for count in [0, 1, 7] {
+            let batch = Subsector::new_batch(count, 0, 0, RuleSet::default());
+            assert_eq!(batch.len(), count);
+        }
+
+        let batch = Subsector::new_batch(4, 0, 0, RuleSet::default());
+        for (index, subsector) in batch.iter().enumerate() {
+            assert!(batch[index + 1..]
+                .iter()
+                .all(|other| other.get_map() != subsector.get_map()));
+        }
+    }
+
+    #[test]
+    fn subsector_creation() {
+        const ATTEMPTS: usize = 1000;
+        for _ in 0..ATTEMPTS {
+            Subsector::default();
+        }
+    }
+
+    #[test]
+    fn subsector_csv_round_trip() {
+        const ATTEMPTS: usize = 100;
+        for _ in 0..ATTEMPTS {
+            let subsector = Subsector::default();
+            let csv = subsector.to_csv();
+            let imported = subsector.with_csv(&csv).unwrap();
+            assert_eq!(imported, subsector);
+        }
+
+        let mut subsector = Subsector::empty();
+        let point = Point { x: 3, y: 4 };
+        let mut world = World::new("Spreadsheet".to_string());
+        world.subtype = WorldSubtype::Outpost;
+        subsector.insert_world(&point, world).unwrap();
+        let csv = subsector.to_csv().replace("Spreadsheet", "Edited");
+        assert!(csv.starts_with("hex,name,subtype,"));
+        let imported = Subsector::empty().with_csv(&csv).unwrap();
+        let world = imported.get_world(&point).unwrap();
+        assert_eq!(world.name, "Edited");
+        assert_eq!(world.subtype, WorldSubtype::Outpost);
+        assert_eq!(
+            world.profile_str(),
+            subsector.get_world(&point).unwrap().profile_str()
+        );
+        assert_eq!(
+            world.pbg_str(),
+            subsector.get_world(&point).unwrap().pbg_str()
+        );
+
+        // Only the worlds in the table are kept when they're read to be merged
+        let worlds = Subsector::default().worlds_from_csv(&csv).unwrap();
+        assert_eq!(worlds.get_map().len(), 1);
+        assert_eq!(worlds.get_world(&point).unwrap().name, "Edited");
+    }
+
+    #[test]
+    fn subsector_csv_malformed_rows() {
+        let mut subsector = Subsector::empty();
+        for (x, name) in [(1, "First"), (2, "Second")] {
+            let mut world = World::empty();
+            world.name = name.to_string();
+            subsector.insert_world(&Point { x, y: 1 }, world).unwrap();
+        }
+        let csv = subsector.to_csv();
+        assert!(Subsector::empty().with_csv(&csv).is_ok());
+
+        // Replace the field in `column` of the second row with `value`
+        let edit_second_row = |column: usize, value: &str| {
+            let mut lines: Vec<String> = csv.lines().map(str::to_string).collect();
+            let mut fields: Vec<&str> = lines[2].split(',').collect();
+            fields[column] = value;
+            let row = fields.join(",");
+            lines[2] = row;
+            lines.join("\n")
+        };
+        let import_error = |csv: String| Subsector::empty().with_csv(&csv).unwrap_err().to_string();
+
+        assert_eq!(
+            import_error(edit_second_row(7, "16")),
+            "Row 2: Atmosphere code 16 of the world at 0201 is out of range"
+        );
+        assert!(import_error(edit_second_row(7, "A")).starts_with("Row 2: "));
+        assert_eq!(
+            import_error(edit_second_row(0, "0911")),
+            "Row 2: Hex 0911 is outside of the subsector"
+        );
+        assert_eq!(
+            import_error(edit_second_row(0, "0101")),
+            "Row 2: Hex 0101 has more than one world"
+        );
+    }
+
+    #[test]
+    fn subsector_hooks() {
+        let mut subsector = Subsector::empty();
+        assert_eq!(subsector.generate_hook(), None);
+
+        let mut world = World::new("Hookworld".to_string());
+        world.travel_code = TravelCode::Red;
+        subsector
+            .insert_world(&Point { x: 1, y: 1 }, world)
+            .unwrap();
+
+        subsector.generate_hooks();
+        assert!(!subsector.hooks().is_empty());
+        assert!(subsector.hooks().len() <= Subsector::NUM_HOOKS);
+        assert!(subsector
+            .hooks()
+            .iter()
+            .all(|hook| hook.contains("Hookworld (0101)")));
+
+        let hook = subsector.generate_hook().unwrap();
+        assert!(hook.contains("Hookworld (0101)"));
+
+        let mut world = subsector.get_world(&Point { x: 1, y: 1 }).unwrap().clone();
+        world.travel_code = TravelCode::Safe;
+        world.world_tags = [
+            TABLES.world_tag_table[0].clone(),
+            TABLES.world_tag_table[1].clone(),
+        ];
+        world.factions = vec![Faction::random()];
+        world.factions[0].name = "Loyalist".to_string();
+        subsector
+            .insert_world(&Point { x: 1, y: 1 }, world)
+            .unwrap();
+
+        // Either world tag or the faction can be picked, so give the faction enough chances
+        const ATTEMPTS: usize = 100;
+        let faction_hook = "The Loyalist faction on Hookworld (0101) is quietly recruiting \
+            off-world help against its rivals.";
+        assert!((0..ATTEMPTS).any(|_| subsector.generate_hook().unwrap() == faction_hook));
+
+        assert!(subsector.copy_player_safe().hooks().is_empty());
+    }
+
+    #[test]
+    fn faction_religions_from_json() {
+        let mut subsector = Subsector::empty();
+        let mut world = World::empty();
+        world.factions = vec![Faction::random()];
+        world.factions[0].religion = Religion::default();
+        let point = Point { x: 1, y: 1 };
+        subsector.insert_world(&point, world).unwrap();
+
+        // A religion left blank on purpose stays blank
+        let json = subsector.to_json();
+        let loaded = Subsector::try_from_json(&json).unwrap();
+        assert!(loaded.get_world(&point).unwrap().factions[0]
+            .religion
+            .is_empty());
+
+        // Factions saved before they had religions get one rolled
+        let mut json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        json["schema_version"] = 3.into();
+        let loaded = Subsector::try_from_json(&json.to_string()).unwrap();
+        assert!(!loaded.get_world(&point).unwrap().factions[0]
+            .religion
+            .is_empty());
+    }
+
+    #[test]
+    fn subsector_json_serde() {
+        const ATTEMPTS: usize = 100;
+        for _ in 0..ATTEMPTS {
+            let subsector = Subsector::default();
+            let json = subsector.to_json();
+            let deserialized = Subsector::try_from_json(&json[..]).unwrap();
+            assert_eq!(deserialized, subsector);
+        }
+    }
+
+    #[test]
+    fn subsector_merge() {
+        let occupied = Point { x: 4, y: 5 };
+        let free = Point { x: 6, y: 6 };
+
+        let mut base = Subsector::empty();
+        base.insert_world(&occupied, World::new("Base".to_string()))
+            .unwrap();
+
+        let mut other = Subsector::empty();
+        other
+            .insert_world(&occupied, World::new("Conflict".to_string()))
+            .unwrap();
+        other
+            .insert_world(&free, World::new("Free".to_string()))
+            .unwrap();
+        assert_eq!(base.conflicting_points(&other), vec![occupied]);
+
+        let mut merged = base.clone();
+        assert_eq!(merged.merge(other.clone(), MergeResolution::Skip), 1);
+        assert_eq!(merged.get_world(&occupied).unwrap().name, "Base");
+        assert_eq!(merged.get_world(&free).unwrap().name, "Free");
+
+        let mut merged = base.clone();
+        assert_eq!(merged.merge(other.clone(), MergeResolution::Overwrite), 2);
+        assert_eq!(merged.get_world(&occupied).unwrap().name, "Conflict");
+
+        let mut merged = base.clone();
+        assert_eq!(merged.merge(other, MergeResolution::MoveToNearestEmpty), 2);
+        assert_eq!(merged.get_world(&occupied).unwrap().name, "Base");
+        let moved = merged
+            .map
+            .iter()
+            .find(|(_, world)| world.name == "Conflict")
+            .map(|(point, _)| *point)
+            .unwrap();
+        assert_eq!(moved.distance(&occupied), 1);
+    }
+
+    #[test]
+    fn subsector_sector_metadata() {
+        let mut subsector = Subsector::empty();
+        subsector.set_name("Spinward & Marches".to_string());
+        subsector.polities.push(Polity {
+            name: "Regina Republic".to_string(),
+            code: "Re".to_string(),
+            capital: Point { x: 1, y: 10 },
+            summary: String::new(),
+        });
+        subsector
+            .xboat_routes
+            .push((Point { x: 1, y: 10 }, Point { x: 3, y: 8 }));
+
+        let xml = subsector.to_sector_metadata();
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"utf-8\"?>"));
+        assert!(xml.contains("<Name>Spinward &amp; Marches</Name>"));
+        assert!(xml.contains("<Subsector Index=\"A\">Spinward &amp; Marches</Subsector>"));
+        assert!(xml.contains("<Allegiance Code=\"Re\">Regina Republic</Allegiance>"));
+        assert!(xml.contains("<Route Start=\"0110\" End=\"0308\" Type=\"Xboat\"/>"));
+    }
+
+    #[test]
+    fn subsector_svg() {
+        const ATTEMPTS: usize = 100;
+        for _ in 0..ATTEMPTS {
+            let subsector = Subsector::default();
+            let _svg = subsector
+                .generate_svg(&DEFAULT_MAP_TEMPLATE, &MapOptions::default(), &[])
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn map_render_errors() {
+        let subsector = Subsector::default();
+        let off_map = Point { x: 0, y: 0 };
+        let route = [off_map, Point { x: 1, y: 1 }];
+        for renderer in MapRenderer::ALL_VALUES {
+            let options = MapOptions {
+                renderer,
+                ..MapOptions::default()
+            };
+            assert_eq!(
+                subsector.generate_svg(&DEFAULT_MAP_TEMPLATE, &options, &route),
+                Err(MapRenderError::MissingCenterMarker(off_map))
+            );
+        }
+
+        let grid = subsector.generate_fallback_grid_svg(true);
+        assert_eq!(
+            grid.matches("<polygon").count(),
+            Subsector::COLUMNS * Subsector::ROWS
+        );
+        assert!(grid.contains(&format!(">{} Subsector</text>", subsector.name())));
+        assert!(!grid.contains("NameText"));
+        assert!(grid.contains(">0101</text>"));
+        assert!(!subsector
+            .generate_fallback_grid_svg(false)
+            .contains(">0101</text>"));
+
+        let blank = Subsector::generate_blank_grid_svg(true);
+        assert_eq!(
+            blank.matches("<polygon").count(),
+            Subsector::COLUMNS * Subsector::ROWS
+        );
+        assert!(!blank.contains("SubsectorName"));
+        assert!(blank.contains(">0101</text>"));
+
+        let grid = subsector.generate_grid_svg(true).unwrap();
+        assert!(grid.contains(">0101</text>"));
+        let grid = subsector.generate_grid_svg(false).unwrap();
+        assert!(!grid.contains(">0101</text>"));
+        assert!(grid.contains("hex-blank"));
+    }
+
+    #[test]
+    fn subsector_svg_layers() {
+        let subsector = Subsector::default();
+        let route: Vec<Point> = subsector.map.keys().take(3).cloned().collect();
+        let svg = subsector
+            .generate_svg(&DEFAULT_MAP_TEMPLATE, &MapOptions::default(), &route)
+            .unwrap();
+        assert!(svg.contains("NameText"));
+        assert!(svg.contains("WorldProfileText"));
+        assert!(svg.contains("class=\"text-hex-coord\""));
+        assert_eq!(
+            svg.contains("class=\"hex-color-"),
+            !subsector.polities().is_empty()
+        );
+        assert_eq!(
+            svg.matches("RouteLine").count(),
+            route.len().saturating_sub(1)
+        );
+        assert_eq!(
+            svg.matches("XboatLine").count(),
+            subsector.xboat_routes().len()
+        );
+        assert!(!svg.contains("LegendEntry"));
+
+        let legend = MapOptions {
+            legend: true,
+            ..MapOptions::default()
+        };
+        let svg = subsector
+            .generate_svg(&DEFAULT_MAP_TEMPLATE, &legend, &route)
+            .unwrap();
+        if route.len() > 1 {
+            assert!(svg.contains(">Travel Route</text>"));
+        }
+
+        let no_layers = MapOptions {
+            annotations: false,
+            bases: false,
+            grid_headers: false,
+            hex_numbers: false,
+            hex_orientation: HexOrientation::FlatTop,
+            layout: MapLayout::Portrait,
+            legend: false,
+            polity_colors: false,
+            renderer: MapRenderer::Template,
+            routes: false,
+            starport_tl: false,
+            style: MapStyle::default(),
+            travel_zones: false,
+            uwps: false,
+            world_names: false,
+            xboat_routes: false,
+        };
+        let svg = subsector
+            .generate_svg(&DEFAULT_MAP_TEMPLATE, &no_layers, &route)
+            .unwrap();
+        for id in [
+            "NameText",
+            "WorldProfileText",
+            "StarportTlText",
+            "BasesText",
+            "TravelZone",
+            "RouteLine",
+            "XboatLine",
+            "class=\"text-hex-coord\"",
+            "class=\"hex-color-",
+        ] {
+            assert!(!svg.contains(id), "{} should not be drawn", id);
+        }
+    }
+
+    #[test]
+    fn subsector_svg_world_labels() {
+        let mut subsector = Subsector::empty();
+        let mut world = World::empty();
+        world.name = "Efate".to_string();
+        let profile = world.profile_str();
+        subsector
+            .insert_world(&Point { x: 4, y: 5 }, world)
+            .unwrap();
+
+        let mut options = MapOptions::default();
+        assert_eq!(options.world_labels(), Some(WorldLabels::NameAndUwp));
+        for labels in WorldLabels::ALL_VALUES {
+            options.set_world_labels(labels);
+            assert_eq!(options.world_labels(), Some(labels));
+
+            let svg = subsector
+                .generate_svg(&DEFAULT_MAP_TEMPLATE, &options, &[])
+                .unwrap();
+            assert_eq!(svg.contains(">Efate</text>"), labels.names());
+            assert_eq!(svg.contains(&format!(">{}</text>", profile)), labels.uwps());
+        }
+
+        // Without names the profile is drawn in their place, at their size
+        let svg = subsector
+            .generate_svg(&DEFAULT_MAP_TEMPLATE, &options, &[])
+            .unwrap();
+        let profile_id = svg.find("0405WorldProfileText").unwrap();
+        let element_start = svg[..profile_id].rfind("<text").unwrap();
+        assert!(svg[element_start..profile_id].contains(WORLD_NAME_CLASS));
+    }
+
+    #[test]
+    fn subsector_svg_style() {
+        let subsector = Subsector::default();
+        let options = MapOptions {
+            style: MapStyle {
+                font_family: "Georgia, serif".to_string(),
+                name_font_size: 5.0,
+                zone_line_weight: 1.25,
+                ..MapStyle::default()
+            },
+            ..MapOptions::default()
+        };
+        for renderer in MapRenderer::ALL_VALUES {
+            let options = MapOptions {
+                renderer,
+                ..options.clone()
+            };
+            let svg = subsector
+                .generate_svg(&DEFAULT_MAP_TEMPLATE, &options, &[])
+                .unwrap();
+            assert!(svg.contains(".text-world-name { font-family: Georgia, serif; font-size: 5px"));
+            assert!(svg.contains("stroke: #d40000; stroke-width: 1.25;"));
+            assert!(!svg.contains("stroke:#d40000"));
+        }
+
+        // Nothing can break out of the stylesheet
+        let options = MapOptions {
+            style: MapStyle {
+                font_family: "serif; } svg { display: none".to_string(),
+                ..MapStyle::default()
+            },
+            ..MapOptions::default()
+        };
+        let svg = subsector
+            .generate_svg(&DEFAULT_MAP_TEMPLATE, &options, &[])
+            .unwrap();
+        assert!(!svg.contains("svg { display"));
+    }
+
+    #[test]
+    fn subsector_svg_layouts() {
+        let subsector = Subsector::default();
+        let portrait = MapOptions::default();
+        let pages = subsector
+            .generate_svg_pages(&DEFAULT_MAP_TEMPLATE, &portrait, &[])
+            .unwrap();
+        assert_eq!(pages.len(), 1);
+        assert!(pages[0].contains("width=\"8.5in\""));
+        assert!(!pages[0].contains("MapLayout"));
+
+        let landscape = MapOptions {
+            layout: MapLayout::Landscape,
+            ..MapOptions::default()
+        };
+        let svg = subsector
+            .generate_svg(&DEFAULT_MAP_TEMPLATE, &landscape, &[])
+            .unwrap();
+        assert!(svg.contains("width=\"11in\""));
+        assert!(svg.contains("height=\"8.5in\""));
+        assert!(svg.contains("id=\"MapLayout\""));
+        assert_eq!(svg.matches("<svg").count(), 1);
+        assert_eq!(svg.matches("<g").count(), svg.matches("</g>").count());
+
+        let poster = MapOptions {
+            layout: MapLayout::Poster,
+            ..MapOptions::default()
+        };
+        let svg = subsector
+            .generate_svg(&DEFAULT_MAP_TEMPLATE, &poster, &[])
+            .unwrap();
+        assert!(svg.contains("width=\"17in\""));
+        let pages = subsector
+            .generate_svg_pages(&DEFAULT_MAP_TEMPLATE, &poster, &[])
+            .unwrap();
+        assert_eq!(pages.len(), 4);
+        assert!(pages[3].contains("viewBox=\"215.9 279.4 215.9 279.4\""));
+        assert!(pages.iter().all(|page| page.contains("width=\"8.5in\"")));
+    }
+}
@@ -0,0 +1,305 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use quick_xml::events::BytesText;
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::{
+    map_template::{ANNOTATION_CLASS, ANNOTATION_TEXT_CLASS},
+    MapRenderError, Point, Subsector, Translation, CENTER_MARKERS,
+};
+
+/// Length of the barbs of an arrow's head in map units
+const ARROWHEAD_LENGTH: f64 = 3.0;
+/// Angle in radians between an arrow's shaft and each barb of its head
+const ARROWHEAD_ANGLE: f64 = 0.45;
+
+/** Color an `Annotation` is drawn in. */
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum AnnotationColor {
+    #[default]
+    Red,
+    Blue,
+    Green,
+    Purple,
+    Black,
+}
+
+impl AnnotationColor {
+    pub const ALL_VALUES: [AnnotationColor; 5] = [
+        Self::Red,
+        Self::Blue,
+        Self::Green,
+        Self::Purple,
+        Self::Black,
+    ];
+
+    /** Get the red, green, and blue components of the color. */
+    pub fn rgb(&self) -> [u8; 3] {
+        match self {
+            Self::Red => [0xd4, 0x00, 0x00],
+            Self::Blue => [0x2a, 0x5a, 0xc8],
+            Self::Green => [0x2e, 0x8b, 0x57],
+            Self::Purple => [0x80, 0x40, 0xa0],
+            Self::Black => [0x20, 0x20, 0x20],
+        }
+    }
+
+    /** Get the color as a CSS hex color, e.g. "#d40000". */
+    pub fn hex(&self) -> String {
+        let [r, g, b] = self.rgb();
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    }
+}
+
+impl fmt::Display for AnnotationColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Red => "Red",
+            Self::Blue => "Blue",
+            Self::Green => "Green",
+            Self::Purple => "Purple",
+            Self::Black => "Black",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** What an `Annotation` draws, positioned in the map units of the built-in map template. */
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "kind")]
+pub enum AnnotationShape {
+    /// Line through each of `points` in order, as drawn by hand
+    Freehand {
+        points: Vec<Translation>,
+    },
+    /// Straight line with a head pointing at `to`
+    Arrow {
+        from: Translation,
+        to: Translation,
+    },
+    Circle {
+        center: Translation,
+        radius: f64,
+    },
+    /// Note written on the map, centered on `position`
+    Text {
+        position: Translation,
+        text: String,
+    },
+}
+
+impl AnnotationShape {
+    /** Get the ends of the two barbs of the head of an arrow, or `None` if this isn't one. */
+    pub fn arrowhead(&self) -> Option<[Translation; 2]> {
+        let (from, to) = match self {
+            Self::Arrow { from, to } => (*from, *to),
+            _ => return None,
+        };
+        let angle = (from.y - to.y).atan2(from.x - to.x);
+        Some(
+            [angle - ARROWHEAD_ANGLE, angle + ARROWHEAD_ANGLE].map(|angle| Translation {
+                x: to.x + ARROWHEAD_LENGTH * angle.cos(),
+                y: to.y + ARROWHEAD_LENGTH * angle.sin(),
+            }),
+        )
+    }
+
+    /** The same shape with every position moved by `place`. */
+    fn placed(&self, place: impl Fn(Translation) -> Translation) -> Self {
+        match self {
+            Self::Freehand { points } => Self::Freehand {
+                points: points.iter().map(|point| place(*point)).collect(),
+            },
+            Self::Arrow { from, to } => Self::Arrow {
+                from: place(*from),
+                to: place(*to),
+            },
+            Self::Circle { center, radius } => Self::Circle {
+                center: place(*center),
+                radius: *radius,
+            },
+            Self::Text { position, text } => Self::Text {
+                position: place(*position),
+                text: text.clone(),
+            },
+        }
+    }
+}
+
+/** A mark drawn over a `Subsector`'s map, like a campaign frontline or a patrol zone. */
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Annotation {
+    pub shape: AnnotationShape,
+    #[serde(default)]
+    pub color: AnnotationColor,
+}
+
+impl Subsector {
+    /** Get the annotations drawn over the map, in the order they were drawn. */
+    pub fn annotations(&self) -> &[Annotation] {
+        &self.annotations
+    }
+
+    pub fn add_annotation(&mut self, annotation: Annotation) {
+        self.annotations.push(annotation);
+    }
+
+    /** Remove the most recently drawn annotation.
+
+    # Returns
+    The removed annotation, or `None` if there weren't any.
+    */
+    pub fn remove_last_annotation(&mut self) -> Option<Annotation> {
+        self.annotations.pop()
+    }
+
+    pub fn clear_annotations(&mut self) {
+        self.annotations.clear();
+    }
+}
+
+/** Draw each of `annotations` on a map whose hexes are centered on `centers`.
+
+Each position is kept where it is relative to the center of the nearest hex of the built-in
+template, so annotations follow the hexes they were drawn over on templates with other geometry.
+*/
+pub(super) fn process_annotations_to_svg_elements<W: std::io::Write>(
+    writer: &mut quick_xml::Writer<W>,
+    centers: &BTreeMap<Point, Translation>,
+    annotations: &[Annotation],
+) -> Result<(), MapRenderError> {
+    let place = |position: Translation| {
+        CENTER_MARKERS
+            .iter()
+            .filter_map(|(point, default)| Some((*default, *centers.get(point)?)))
+            .min_by(|(a, _), (b, _)| {
+                distance(*a, position)
+                    .partial_cmp(&distance(*b, position))
+                    .unwrap()
+            })
+            .map_or(position, |(default, center)| center + (position - default))
+    };
+
+    for (index, annotation) in annotations.iter().enumerate() {
+        let id = format!("Annotation{}", index);
+        let color = annotation.color.hex();
+        let shape = annotation.shape.placed(place);
+        match &shape {
+            AnnotationShape::Freehand { points } => {
+                let points: Vec<String> = points
+                    .iter()
+                    .map(|point| format!("{},{}", point.x, point.y))
+                    .collect();
+                writer
+                    .create_element("polyline")
+                    .with_attributes(vec![
+                        ("class", ANNOTATION_CLASS),
+                        ("stroke", &color[..]),
+                        ("points", &points.join(" ")),
+                        ("id", &id),
+                    ])
+                    .write_empty()?;
+            }
+            AnnotationShape::Arrow { from, to } => {
+                let [left, right] = shape.arrowhead().unwrap();
+                let points = format!(
+                    "{},{} {},{} {},{} {},{} {},{}",
+                    from.x, from.y, to.x, to.y, left.x, left.y, to.x, to.y, right.x, right.y
+                );
+                writer
+                    .create_element("polyline")
+                    .with_attributes(vec![
+                        ("class", ANNOTATION_CLASS),
+                        ("stroke", &color[..]),
+                        ("points", &points[..]),
+                        ("id", &id),
+                    ])
+                    .write_empty()?;
+            }
+            AnnotationShape::Circle { center, radius } => {
+                writer
+                    .create_element("circle")
+                    .with_attributes(vec![
+                        ("class", ANNOTATION_CLASS),
+                        ("stroke", &color[..]),
+                        ("cx", &center.x.to_string()),
+                        ("cy", &center.y.to_string()),
+                        ("r", &radius.to_string()),
+                        ("id", &id),
+                    ])
+                    .write_empty()?;
+            }
+            AnnotationShape::Text { position, text } => {
+                writer
+                    .create_element("text")
+                    .with_attributes(vec![
+                        ("class", ANNOTATION_TEXT_CLASS),
+                        ("fill", &color[..]),
+                        ("x", &position.x.to_string()),
+                        ("y", &position.y.to_string()),
+                        ("id", &id),
+                    ])
+                    .write_text_content(BytesText::new(text))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn distance(a: Translation, b: Translation) -> f64 {
+    (a.x - b.x).hypot(a.y - b.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::{MapOptions, DEFAULT_MAP_TEMPLATE};
+
+    #[test]
+    fn annotations() {
+        let mut subsector = Subsector::empty();
+        let center = CENTER_MARKERS[&Point { x: 2, y: 3 }];
+        subsector.add_annotation(Annotation {
+            shape: AnnotationShape::Arrow {
+                from: center,
+                to: center + Translation { x: 20.0, y: 0.0 },
+            },
+            color: AnnotationColor::Blue,
+        });
+        subsector.add_annotation(Annotation {
+            shape: AnnotationShape::Text {
+                position: center,
+                text: "Front <line>".to_string(),
+            },
+            color: AnnotationColor::Red,
+        });
+
+        let json = subsector.to_json();
+        let deserialized = Subsector::try_from_json(&json).unwrap();
+        assert_eq!(deserialized.annotations(), subsector.annotations());
+
+        let template = &DEFAULT_MAP_TEMPLATE;
+        let svg = subsector
+            .generate_svg(template, &MapOptions::default(), &[])
+            .unwrap();
+        assert!(svg.contains("id=\"Annotation0\""));
+        assert!(svg.contains("Front &lt;line&gt;"));
+        let hidden = MapOptions {
+            annotations: false,
+            ..MapOptions::default()
+        };
+        let svg = subsector.generate_svg(template, &hidden, &[]).unwrap();
+        assert!(!svg.contains("id=\"Annotation0\""));
+
+        // The head points back along the shaft from its tip
+        let [left, right] = subsector.annotations()[0].shape.arrowhead().unwrap();
+        assert!(left.x < center.x + 20.0 && right.x < center.x + 20.0);
+        assert!((left.y - center.y) * (right.y - center.y) < 0.0);
+
+        assert!(subsector.remove_last_annotation().is_some());
+        subsector.clear_annotations();
+        assert!(subsector.annotations().is_empty());
+        assert!(subsector.remove_last_annotation().is_none());
+    }
+}
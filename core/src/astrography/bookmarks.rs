@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::{Point, Subsector};
+
+/** A hex of a `Subsector` starred so it can be found again quickly, like a world the campaign keeps
+coming back to.
+*/
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Bookmark {
+    pub point: Point,
+    /// What the hex is bookmarked for, e.g. "Patron's homeworld"; empty if it has no label
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub label: String,
+}
+
+impl Subsector {
+    pub fn bookmarks(&self) -> &[Bookmark] {
+        &self.bookmarks
+    }
+
+    pub fn bookmarks_mut(&mut self) -> &mut Vec<Bookmark> {
+        &mut self.bookmarks
+    }
+
+    /** Whether the hex at `point` is bookmarked. */
+    pub fn is_bookmarked(&self, point: &Point) -> bool {
+        self.bookmarks
+            .iter()
+            .any(|bookmark| bookmark.point == *point)
+    }
+
+    /** Bookmark the hex at `point` without a label, or remove its bookmark if it already has one.
+
+    # Returns
+    Whether the hex is bookmarked afterward.
+    */
+    pub fn toggle_bookmark(&mut self, point: &Point) -> bool {
+        if self.is_bookmarked(point) {
+            self.bookmarks.retain(|bookmark| bookmark.point != *point);
+            false
+        } else {
+            self.bookmarks.push(Bookmark {
+                point: *point,
+                label: String::new(),
+            });
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggle_bookmarks() {
+        let mut subsector = Subsector::empty();
+        let regina = Point { x: 1, y: 1 };
+        let efate = Point { x: 3, y: 5 };
+        assert!(subsector.toggle_bookmark(&regina));
+        assert!(subsector.toggle_bookmark(&efate));
+        subsector.bookmarks_mut()[1].label = "Patron's homeworld".to_string();
+        assert!(subsector.is_bookmarked(&regina));
+
+        let json = subsector.to_json();
+        let deserialized = Subsector::try_from_json(&json).unwrap();
+        assert_eq!(deserialized.bookmarks(), subsector.bookmarks());
+
+        assert!(!subsector.toggle_bookmark(&regina));
+        assert!(!subsector.is_bookmarked(&regina));
+        assert_eq!(subsector.bookmarks()[0].point, efate);
+    }
+}
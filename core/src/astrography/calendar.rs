@@ -0,0 +1,192 @@
+use std::error::Error;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::Subsector;
+
+/// Days in a year of the Imperial calendar, which has no leap years
+pub const DAYS_PER_YEAR: u16 = 365;
+/// Days in a week, the span over which starport markets change
+const DAYS_PER_WEEK: i64 = 7;
+
+/** Date in the Imperial calendar, written as a three digit day of the year and the year, e.g.
+"032-1105".
+*/
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ImperialDate {
+    // The year comes first so that dates are ordered chronologically
+    year: i32,
+    /// Day of the year from 1 to `DAYS_PER_YEAR`
+    day: u16,
+}
+
+impl ImperialDate {
+    /** Make the date of `day` of `year`, or `None` if `day` isn't a day of the year. */
+    pub fn new(day: u16, year: i32) -> Option<Self> {
+        (1..=DAYS_PER_YEAR)
+            .contains(&day)
+            .then_some(Self { year, day })
+    }
+
+    pub fn day(&self) -> u16 {
+        self.day
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /** Get the date `days` after this one. */
+    pub fn plus_days(&self, days: u32) -> Self {
+        Self::from_ordinal(self.ordinal() + days as i64)
+    }
+
+    /** Get the date once `hours` have passed since the start of this one, rounding up to whole
+    days, e.g. to find when a trip of that long arrives.
+    */
+    pub fn plus_hours(&self, hours: f64) -> Self {
+        self.plus_days((hours.max(0.0) / 24.0).ceil() as u32)
+    }
+
+    /** Get the number of days from `earlier` to this date, negative if `earlier` is later. */
+    pub fn days_since(&self, earlier: &ImperialDate) -> i64 {
+        self.ordinal() - earlier.ordinal()
+    }
+
+    /** Number of days since day 1 of year 0. */
+    fn ordinal(&self) -> i64 {
+        self.year as i64 * DAYS_PER_YEAR as i64 + (self.day as i64 - 1)
+    }
+
+    fn from_ordinal(ordinal: i64) -> Self {
+        Self {
+            year: ordinal.div_euclid(DAYS_PER_YEAR as i64) as i32,
+            day: ordinal.rem_euclid(DAYS_PER_YEAR as i64) as u16 + 1,
+        }
+    }
+}
+
+impl Default for ImperialDate {
+    /** Day 1 of 1105, when the classic Traveller setting begins. */
+    fn default() -> Self {
+        Self { year: 1105, day: 1 }
+    }
+}
+
+impl fmt::Display for ImperialDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:03}-{}", self.day, self.year)
+    }
+}
+
+impl TryFrom<&str> for ImperialDate {
+    type Error = Box<dyn Error>;
+    fn try_from(string: &str) -> Result<Self, Self::Error> {
+        let (day, year) = string
+            .trim()
+            .split_once('-')
+            .ok_or("Imperial date should be a day and year like 032-1105")?;
+        let day: u16 = day.parse()?;
+        let year: i32 = year.parse()?;
+        Self::new(day, year)
+            .ok_or_else(|| format!("Day {} is not between 1 and {}", day, DAYS_PER_YEAR).into())
+    }
+}
+
+impl TryFrom<String> for ImperialDate {
+    type Error = Box<dyn Error>;
+    fn try_from(string: String) -> Result<Self, Self::Error> {
+        Self::try_from(&string[..])
+    }
+}
+
+impl From<ImperialDate> for String {
+    fn from(date: ImperialDate) -> Self {
+        date.to_string()
+    }
+}
+
+impl Subsector {
+    /** Get the date the campaign set in the `Subsector` has reached, if its clock was started. */
+    pub fn campaign_date(&self) -> Option<ImperialDate> {
+        self.campaign_date
+    }
+
+    /** Set the campaign's date, or stop keeping track of it with `None`. */
+    pub fn set_campaign_date(&mut self, date: Option<ImperialDate>) {
+        self.campaign_date = date;
+    }
+
+    /** Move the campaign's date forward by `days`, ticking the fuel market of every `World` if a
+    new week has started.
+
+    # Returns
+    Whether the fuel markets ticked; never if the campaign's clock hasn't been started.
+    */
+    pub fn advance_campaign_date(&mut self, days: u32) -> bool {
+        let date = match self.campaign_date {
+            Some(date) => date,
+            None => return false,
+        };
+        let new_date = date.plus_days(days);
+        self.campaign_date = Some(new_date);
+
+        let new_week =
+            new_date.ordinal().div_euclid(DAYS_PER_WEEK) > date.ordinal().div_euclid(DAYS_PER_WEEK);
+        if new_week {
+            self.fuel_market_tick();
+        }
+        new_week
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::{Point, World, DEFAULT_JOURNAL_DATE};
+
+    #[test]
+    fn imperial_date() {
+        let date = ImperialDate::try_from(DEFAULT_JOURNAL_DATE).unwrap();
+        assert_eq!(date, ImperialDate::default());
+        assert_eq!(date.to_string(), DEFAULT_JOURNAL_DATE);
+
+        let date = ImperialDate::new(360, 1105).unwrap();
+        assert_eq!(date.plus_days(10).to_string(), "005-1106");
+        assert_eq!(date.plus_days(10).days_since(&date), 10);
+        assert!(date.plus_days(10) > date);
+        assert_eq!(date.plus_hours(168.0 + 1.0), date.plus_days(8));
+        assert_eq!(date.plus_hours(0.0), date);
+
+        assert!(ImperialDate::new(0, 1105).is_none());
+        assert!(ImperialDate::try_from("366-1105").is_err());
+        assert!(ImperialDate::try_from("1105").is_err());
+
+        let json = serde_json::to_string(&date).unwrap();
+        assert_eq!(json, "\"360-1105\"");
+        assert_eq!(serde_json::from_str::<ImperialDate>(&json).unwrap(), date);
+    }
+
+    #[test]
+    fn advance_campaign_date() {
+        let mut subsector = Subsector::empty();
+        assert!(!subsector.advance_campaign_date(7));
+        assert_eq!(subsector.campaign_date(), None);
+
+        let point = Point { x: 1, y: 1 };
+        subsector.insert_world(&point, World::empty()).unwrap();
+        subsector.set_campaign_date(Some(ImperialDate::default()));
+
+        // Markets only change once a new week starts
+        let ticked = subsector.advance_campaign_date(1);
+        assert_eq!(
+            ticked,
+            subsector.get_world(&point).unwrap().fuel_market.is_some()
+        );
+        assert!(subsector.advance_campaign_date(7));
+        assert!(subsector.get_world(&point).unwrap().fuel_market.is_some());
+        assert_eq!(subsector.campaign_date().unwrap().to_string(), "009-1105");
+    }
+}
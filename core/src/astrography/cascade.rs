@@ -0,0 +1,168 @@
+use std::fmt;
+
+use crate::astrography::World;
+
+/** Field of a `World` that other fields are rolled from, or are rolled with DMs from other fields.
+
+Values are listed in the order they are generated in [`World::with_rule_set`], so a field is only
+ever rolled from fields listed before it.
+*/
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum WorldField {
+    Size,
+    Atmosphere,
+    Temperature,
+    Hydrographics,
+    Population,
+    Government,
+    LawLevel,
+    Starport,
+    TechLevel,
+}
+
+impl WorldField {
+    pub const ALL_VALUES: [WorldField; 9] = [
+        Self::Size,
+        Self::Atmosphere,
+        Self::Temperature,
+        Self::Hydrographics,
+        Self::Population,
+        Self::Government,
+        Self::LawLevel,
+        Self::Starport,
+        Self::TechLevel,
+    ];
+
+    /** Fields whose values the field is rolled with, under any of the rule sets. */
+    fn rolled_from(&self) -> &'static [WorldField] {
+        match self {
+            Self::Size => &[],
+            Self::Atmosphere => &[Self::Size],
+            Self::Temperature => &[Self::Atmosphere],
+            Self::Hydrographics => &[Self::Size, Self::Atmosphere, Self::Temperature],
+            Self::Population => &[Self::Size, Self::Atmosphere, Self::Hydrographics],
+            Self::Government => &[Self::Population],
+            Self::LawLevel => &[Self::Government],
+            Self::Starport => &[Self::Population],
+            Self::TechLevel => &[
+                Self::Size,
+                Self::Atmosphere,
+                Self::Hydrographics,
+                Self::Population,
+                Self::Government,
+                Self::Starport,
+            ],
+        }
+    }
+
+    /** Get every field rolled from this one, directly or through other fields, in generation
+    order.
+    */
+    pub fn dependents(&self) -> Vec<WorldField> {
+        let mut dependents = Vec::new();
+        for field in Self::ALL_VALUES {
+            let depends = field
+                .rolled_from()
+                .iter()
+                .any(|upstream| upstream == self || dependents.contains(upstream));
+            if depends {
+                dependents.push(field);
+            }
+        }
+        dependents
+    }
+}
+
+impl fmt::Display for WorldField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Size => "Size",
+            Self::Atmosphere => "Atmosphere",
+            Self::Temperature => "Temperature",
+            Self::Hydrographics => "Hydrographics",
+            Self::Population => "Population",
+            Self::Government => "Government",
+            Self::LawLevel => "Law Level",
+            Self::Starport => "Starport",
+            Self::TechLevel => "Tech Level",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl World {
+    /** Get a copy of the `World` with every field rolled from `field` rolled again, in generation
+    order, so they are statistically consistent with its current value.
+
+    Trade codes are resolved again afterward, as is the travel code unless it was set by hand.
+    */
+    pub fn with_dependents_rerolled(&self, field: WorldField) -> World {
+        let mut world = self.clone();
+        for dependent in field.dependents() {
+            match dependent {
+                WorldField::Size => world.generate_size(),
+                WorldField::Atmosphere => world.generate_atmosphere(),
+                WorldField::Temperature => world.generate_temperature(),
+                WorldField::Hydrographics => world.generate_hydrographics(),
+                WorldField::Population => world.generate_population(),
+                WorldField::Government => world.generate_government(),
+                WorldField::LawLevel => world.generate_law_level(),
+                WorldField::Starport => {
+                    world.generate_starport();
+                    world.generate_starport_details();
+                    world.generate_fuel_market();
+                }
+                WorldField::TechLevel => world.generate_tech_level(),
+            }
+        }
+
+        world.resolve_trade_codes();
+        if !world.travel_code_overridden {
+            world.resolve_travel_code();
+        }
+        world
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::TABLES;
+
+    #[test]
+    fn field_dependents() {
+        assert_eq!(
+            WorldField::Size.dependents(),
+            WorldField::ALL_VALUES[1..].to_vec()
+        );
+        assert_eq!(
+            WorldField::Population.dependents(),
+            [
+                WorldField::Government,
+                WorldField::LawLevel,
+                WorldField::Starport,
+                WorldField::TechLevel
+            ]
+        );
+        assert_eq!(
+            WorldField::Government.dependents(),
+            [WorldField::LawLevel, WorldField::TechLevel]
+        );
+        assert!(WorldField::LawLevel.dependents().is_empty());
+        assert!(WorldField::TechLevel.dependents().is_empty());
+    }
+
+    #[test]
+    fn dependents_rerolled() {
+        let mut world = World::new("Regina".to_string());
+        world.population = TABLES.pop_table[0].clone();
+        let rerolled = world.with_dependents_rerolled(WorldField::Population);
+
+        // Nothing upstream of the edited field changes, and an empty world has no government
+        assert_eq!(rerolled.size, world.size);
+        assert_eq!(rerolled.atmosphere, world.atmosphere);
+        assert_eq!(rerolled.population, world.population);
+        assert_eq!(rerolled.government.code, 0);
+        assert_eq!(rerolled.law_level.code, 0);
+    }
+}
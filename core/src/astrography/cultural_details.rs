@@ -0,0 +1,161 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::World;
+use crate::dice;
+
+const DRESS_TABLE: [&str; 6] = [
+    "Plain, practical work clothes in muted colors, the same for every class",
+    "Flowing robes layered to show rank, with colors reserved for the elite",
+    "Body paint and very little else, redone each morning to suit the day",
+    "Uniforms for every trade and office, worn even off duty",
+    "Elaborate masks worn in public, only removed among family",
+    "Rapidly changing fashions that leave offworlders looking years out of date",
+];
+
+const TABOO_TABLE: [&str; 6] = [
+    "Speaking the name of the dead aloud",
+    "Eating in front of anyone who isn't kin",
+    "Showing the soles of the feet or the palms of the hands",
+    "Touching another person's head, even by accident",
+    "Refusing a gift, however unwanted",
+    "Wasting water, punished as harshly as theft",
+];
+
+const GREETING_TABLE: [&str; 6] = [
+    "A firm clasp of both forearms",
+    "A deep bow held until the higher ranked person straightens",
+    "An exchange of small tokens, kept by both sides as a record of the meeting",
+    "Reciting one's lineage back three generations",
+    "A kiss on each cheek, or three times for close friends",
+    "Averted eyes and silence until the host speaks first",
+];
+
+const CUISINE_TABLE: [&str; 6] = [
+    "Vat-grown protein flavored with imported spices worth more than the meal",
+    "Fermented everything, the stronger smelling the more prized",
+    "Raw seafood and native plants served in many small courses",
+    "Fiery stews eaten communally from one great pot",
+    "Bland, carefully portioned rations eaten in silence",
+    "Roasted meat from native beasts, hunted by the diners themselves",
+];
+
+/** Aspect of a `World`'s culture beyond its cultural difference, rolled on a table of its own. */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CulturalFacet {
+    Dress,
+    Taboos,
+    Greetings,
+    Cuisine,
+}
+
+impl CulturalFacet {
+    pub const ALL_VALUES: [CulturalFacet; 4] =
+        [Self::Dress, Self::Taboos, Self::Greetings, Self::Cuisine];
+
+    /** Entries of the facet's table, rolled on with 1D. */
+    fn table(&self) -> &'static [&'static str; 6] {
+        match self {
+            Self::Dress => &DRESS_TABLE,
+            Self::Taboos => &TABOO_TABLE,
+            Self::Greetings => &GREETING_TABLE,
+            Self::Cuisine => &CUISINE_TABLE,
+        }
+    }
+}
+
+impl fmt::Display for CulturalFacet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Dress => "Dress",
+            Self::Taboos => "Taboos",
+            Self::Greetings => "Greetings",
+            Self::Cuisine => "Cuisine",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** Dress, taboos, greeting customs, and cuisine of a `World`'s culture; empty if never rolled. */
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct CulturalDetails {
+    pub dress: String,
+    pub taboos: String,
+    pub greetings: String,
+    pub cuisine: String,
+}
+
+impl CulturalDetails {
+    pub fn get(&self, facet: CulturalFacet) -> &String {
+        match facet {
+            CulturalFacet::Dress => &self.dress,
+            CulturalFacet::Taboos => &self.taboos,
+            CulturalFacet::Greetings => &self.greetings,
+            CulturalFacet::Cuisine => &self.cuisine,
+        }
+    }
+
+    pub fn get_mut(&mut self, facet: CulturalFacet) -> &mut String {
+        match facet {
+            CulturalFacet::Dress => &mut self.dress,
+            CulturalFacet::Taboos => &mut self.taboos,
+            CulturalFacet::Greetings => &mut self.greetings,
+            CulturalFacet::Cuisine => &mut self.cuisine,
+        }
+    }
+
+    /** Whether none of the facets have been filled in. */
+    pub fn is_empty(&self) -> bool {
+        CulturalFacet::ALL_VALUES
+            .iter()
+            .all(|facet| self.get(*facet).is_empty())
+    }
+}
+
+impl World {
+    /** Roll `facet` of the `World`'s culture on its table, replacing whatever it was. */
+    pub fn generate_cultural_detail(&mut self, facet: CulturalFacet) {
+        let roll: i32 = dice::roll_1d(6);
+        *self.cultural_details.get_mut(facet) = facet.table()[roll as usize - 1].to_string();
+    }
+
+    /** Roll every facet of the `World`'s culture on its table, or clear them all if it has no
+    society of its own.
+    */
+    pub fn generate_cultural_details(&mut self) {
+        if !self.subtype.has_society() {
+            self.cultural_details = CulturalDetails::default();
+            return;
+        }
+        for facet in CulturalFacet::ALL_VALUES {
+            self.generate_cultural_detail(facet);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_cultural_details() {
+        let mut world = World::empty();
+        assert!(world.cultural_details.is_empty());
+
+        world.generate_cultural_details();
+        assert!(!world.cultural_details.is_empty());
+        for facet in CulturalFacet::ALL_VALUES {
+            let detail = world.cultural_details.get(facet);
+            assert!(facet.table().contains(&&detail[..]), "{} not rolled", facet);
+        }
+
+        world.cultural_details.cuisine = "Edited".to_string();
+        world.generate_cultural_detail(CulturalFacet::Dress);
+        assert_eq!(world.cultural_details.cuisine, "Edited");
+
+        world.make_player_safe();
+        assert!(world.cultural_details.is_empty());
+    }
+}
@@ -0,0 +1,247 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::{world_sheet::sheet_section_lines, StarportClass, TravelCode, World};
+use crate::dice;
+
+/// Number of entries in an encounter table, one for each face of a d6
+pub const ENCOUNTER_TABLE_SIZE: usize = 6;
+
+const PATROL_ENCOUNTERS: [&str; 5] = [
+    "System defense boat hails the ship and asks for its flight plan",
+    "Naval corvette shadows the ship all the way to the jump point",
+    "Scout courier asks for news from the last system visited",
+    "Planetary navy cutter orders the ship to hold position for inspection",
+    "Patrol frigate broadcasts a warning about a recent pirate attack",
+];
+
+const PIRATE_ENCOUNTERS: [&str; 5] = [
+    "Corsair poses as a merchant in distress to lure the ship in",
+    "Pair of armed fighters demands cargo in exchange for safe passage",
+    "Modified free trader runs without a transponder near the gas giant",
+    "Pirates are sighted looting a disabled freighter",
+    "Raider lies in wait at the edge of the jump limit",
+];
+
+const TRADER_ENCOUNTERS: [&str; 5] = [
+    "Free trader offers speculative cargo at a discount",
+    "Subsidized merchant is looking to hire a crew member",
+    "Bulk freighter asks for an escort to the next system",
+    "Far trader carrying passengers signals a medical emergency",
+    "Yacht owner invites the crew aboard for a party",
+];
+
+const CUSTOMS_ENCOUNTERS: [&str; 5] = [
+    "Customs officers board the ship to search the cargo hold",
+    "Health inspectors hold the ship in quarantine pending a screening",
+    "Port authority audits the ship's registration and papers",
+    "Customs cutter demands a fee for an undeclared weapon",
+    "Inspectors seize a passenger's luggage as contraband",
+];
+
+/** Kind of ship or official the crew runs into around a `World`. */
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum EncounterKind {
+    Patrol,
+    Pirates,
+    Traders,
+    Customs,
+}
+
+impl EncounterKind {
+    pub const ALL_VALUES: [EncounterKind; 4] =
+        [Self::Patrol, Self::Pirates, Self::Traders, Self::Customs];
+
+    /** Descriptions of encounters of this kind to pick from. */
+    fn descriptions(&self) -> &'static [&'static str] {
+        match self {
+            Self::Patrol => &PATROL_ENCOUNTERS,
+            Self::Pirates => &PIRATE_ENCOUNTERS,
+            Self::Traders => &TRADER_ENCOUNTERS,
+            Self::Customs => &CUSTOMS_ENCOUNTERS,
+        }
+    }
+}
+
+impl fmt::Display for EncounterKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Patrol => "Patrol",
+            Self::Pirates => "Pirates",
+            Self::Traders => "Traders",
+            Self::Customs => "Customs",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** An entry of a `World`'s random encounter table. */
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Encounter {
+    pub kind: EncounterKind,
+    pub description: String,
+}
+
+impl World {
+    /** Get the encounter table as a section of the `World`'s data sheet, or an empty string if
+    there isn't one.
+    */
+    pub(super) fn encounter_sheet_section(&self, markdown: bool) -> String {
+        if self.encounters.is_empty() {
+            return String::new();
+        }
+
+        let mut lines = sheet_section_lines("Encounters (1D)", markdown);
+        if markdown {
+            lines.push("| 1D | Kind | Encounter |".to_string());
+            lines.push("| --- | --- | --- |".to_string());
+        }
+
+        for (index, encounter) in self.encounters.iter().enumerate() {
+            lines.push(if markdown {
+                format!(
+                    "| {} | {} | {} |",
+                    index + 1,
+                    encounter.kind,
+                    encounter.description.replace('|', "\\|")
+                )
+            } else {
+                format!(
+                    "{}: {} - {}",
+                    index + 1,
+                    encounter.kind,
+                    encounter.description
+                )
+            });
+        }
+        lines.join("\n")
+    }
+
+    /** How likely each kind of encounter is around the `World`.
+
+    Naval and scout bases and strict or interdicted worlds draw patrols, pirate bases and lawless
+    or dangerous worlds draw pirates, good starports and big populations draw traders, and high law
+    levels at worlds with a starport mean customs checks.
+    */
+    fn encounter_weights(&self) -> [(EncounterKind, u32); 4] {
+        let law_level = self.law_level.code as u32;
+        let travel_code_dm = match self.travel_code {
+            TravelCode::Safe => 0,
+            TravelCode::Amber => 1,
+            TravelCode::Red => 2,
+        };
+
+        let mut patrol = 1 + travel_code_dm;
+        if self.has_naval_base {
+            patrol += 3;
+        }
+        if self.has_scout_base {
+            patrol += 1;
+        }
+        if law_level >= 7 {
+            patrol += 1;
+        }
+
+        let mut pirates = 1 + travel_code_dm;
+        if self.has_pirate_base {
+            pirates += 3;
+        }
+        if law_level <= 3 {
+            pirates += 1;
+        }
+
+        let (mut traders, customs_dm) = match self.starport.class {
+            StarportClass::A => (4, 1),
+            StarportClass::B => (3, 1),
+            StarportClass::C => (2, 0),
+            StarportClass::D | StarportClass::E => (1, 0),
+            StarportClass::X => (0, 0),
+        };
+        if matches!(self.starport.class, StarportClass::E | StarportClass::X) {
+            pirates += 1;
+        }
+        if traders > 0 && self.population.code >= 9 {
+            traders += 1;
+        }
+
+        let customs = match self.starport.class {
+            StarportClass::X => 0,
+            _ => law_level / 3 + customs_dm,
+        };
+
+        [
+            (EncounterKind::Patrol, patrol),
+            (EncounterKind::Pirates, pirates),
+            (EncounterKind::Traders, traders),
+            (EncounterKind::Customs, customs),
+        ]
+    }
+
+    /** Generate a single random encounter weighted by the `World`'s law level, starport, travel
+    code, and bases.
+    */
+    pub fn generate_encounter(&self) -> Encounter {
+        let weights = self.encounter_weights();
+        let total: u32 = weights.iter().map(|(_, weight)| weight).sum();
+        let mut roll = dice::roll_range(0..total);
+
+        let mut kind = EncounterKind::Patrol;
+        for (candidate, weight) in weights {
+            if roll < weight {
+                kind = candidate;
+                break;
+            }
+            roll -= weight;
+        }
+
+        let descriptions = kind.descriptions();
+        Encounter {
+            kind,
+            description: descriptions[dice::roll_range(0..descriptions.len())].to_string(),
+        }
+    }
+
+    /** Generate a new random encounter table for the `World`, replacing the old one. */
+    pub fn generate_encounters(&mut self) {
+        self.encounters = (0..ENCOUNTER_TABLE_SIZE)
+            .map(|_| self.generate_encounter())
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::TABLES;
+
+    #[test]
+    fn generate_encounters() {
+        let mut world = World::empty();
+        world.starport = TABLES.starport_table[0].clone();
+        world.law_level = TABLES.law_table[0].clone();
+        world.generate_encounters();
+        assert_eq!(world.encounters.len(), ENCOUNTER_TABLE_SIZE);
+
+        // Neither traders nor customs officials bother with worlds without a starport
+        for encounter in world.encounters.iter() {
+            assert!(matches!(
+                encounter.kind,
+                EncounterKind::Patrol | EncounterKind::Pirates
+            ));
+            assert!(encounter
+                .kind
+                .descriptions()
+                .contains(&&encounter.description[..]));
+        }
+
+        let markdown = world.encounter_sheet_section(true);
+        assert!(markdown.starts_with("\n\n#### Encounters (1D)\n\n| 1D | Kind | Encounter |"));
+        assert_eq!(markdown.lines().count(), 6 + ENCOUNTER_TABLE_SIZE);
+        let text = world.encounter_sheet_section(false);
+        assert!(text.contains(&format!("\n1: {} - ", world.encounters[0].kind)));
+
+        world.encounters.clear();
+        assert_eq!(world.encounter_sheet_section(false), "");
+    }
+}
@@ -0,0 +1,166 @@
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::World;
+use crate::dice;
+
+/// Digits of Traveller's extended hexadecimal notation; `I` and `O` are skipped to avoid confusion
+const EHEX_DIGITS: &[u8] = b"0123456789ABCDEFGHJKLMNPQRSTUVWXYZ";
+
+/** Dice rolled for the T5 economic and cultural extensions of a `World`.
+
+Only the rolls are kept; the extensions themselves are calculated from these and the current state
+of the `World` so that they stay consistent as the `World` is edited.
+*/
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ExtensionRolls {
+    /// 2D roll for resources
+    resources: i32,
+    /// Two 1D rolls for infrastructure; how many are used depends on population
+    infrastructure: [i32; 2],
+    /// Flux roll for efficiency
+    efficiency: i32,
+    /// Flux roll for heterogeneity
+    heterogeneity: i32,
+    /// Flux roll for strangeness
+    strangeness: i32,
+    /// Flux roll for symbols
+    symbols: i32,
+}
+
+impl ExtensionRolls {
+    pub fn random() -> Self {
+        Self {
+            resources: dice::roll_2d(6),
+            infrastructure: [dice::roll_1d(6), dice::roll_1d(6)],
+            efficiency: dice::flux(),
+            heterogeneity: dice::flux(),
+            strangeness: dice::flux(),
+            symbols: dice::flux(),
+        }
+    }
+}
+
+impl World {
+    /** Get the T5 cultural extension of the `World`, e.g. "[9C5B]".
+
+    Its digits are heterogeneity, acceptance, strangeness, and symbols. Uninhabited worlds have no
+    culture, so all of their digits are zero.
+    */
+    pub fn cultural_extension(&self) -> String {
+        let population = self.population.code as i32;
+        if population == 0 {
+            return "[0000]".to_string();
+        }
+
+        let rolls = &self.extension_rolls;
+        let heterogeneity = (population + rolls.heterogeneity).max(1);
+        let acceptance = (population + self.importance()).max(1);
+        let strangeness = (rolls.strangeness + 5).max(1);
+        let symbols = (rolls.symbols + self.tech_level.code as i32).max(1);
+
+        format!(
+            "[{}{}{}{}]",
+            ehex(heterogeneity),
+            ehex(acceptance),
+            ehex(strangeness),
+            ehex(symbols)
+        )
+    }
+
+    /** Get the T5 economic extension of the `World`, e.g. "(A46+2)".
+
+    Its digits are resources, labor, infrastructure, and efficiency.
+    */
+    pub fn economic_extension(&self) -> String {
+        let population = self.population.code as i32;
+        let rolls = &self.extension_rolls;
+
+        let mut resources = rolls.resources;
+        if self.tech_level.code >= 8 {
+            resources += self.gas_giants + self.planetoid_belts.unwrap_or(0);
+        }
+
+        let labor = (population - 1).max(0);
+
+        let infrastructure = match population {
+            0 => 0,
+            1..=3 => self.importance(),
+            4..=6 => rolls.infrastructure[0] + self.importance(),
+            _ => rolls.infrastructure.iter().sum::<i32>() + self.importance(),
+        }
+        .max(0);
+
+        let efficiency = if population == 0 { 0 } else { rolls.efficiency };
+
+        format!(
+            "({}{}{}{:+})",
+            ehex(resources),
+            ehex(labor),
+            ehex(infrastructure),
+            efficiency
+        )
+    }
+}
+
+/** Convert `value` to a single extended hexadecimal digit, clamping it to the range 0 to 33. */
+fn ehex(value: i32) -> char {
+    let index = value.clamp(0, EHEX_DIGITS.len() as i32 - 1) as usize;
+    EHEX_DIGITS[index] as char
+}
+
+/** Convert a single extended hexadecimal digit to its value, if it is one. */
+pub(super) fn ehex_value(digit: char) -> Option<u16> {
+    let digit = digit.to_ascii_uppercase();
+    EHEX_DIGITS
+        .iter()
+        .position(|&ehex| ehex as char == digit)
+        .map(|value| value as u16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::TABLES;
+
+    #[test]
+    fn extensions() {
+        let mut world = World::empty();
+        world.extension_rolls = ExtensionRolls {
+            resources: 7,
+            infrastructure: [3, 4],
+            efficiency: -2,
+            heterogeneity: 1,
+            strangeness: 0,
+            symbols: -1,
+        };
+
+        // Uninhabited worlds have no culture, labor, or infrastructure
+        assert_eq!(world.economic_extension(), "(700+0)");
+        assert_eq!(world.cultural_extension(), "[0000]");
+
+        world.population = TABLES.pop_table[8].clone();
+        world.tech_level = TABLES.tech_level_table[12].clone();
+        world.gas_giants = 2;
+        world.planetoid_belts = Some(1);
+        let importance = world.importance();
+
+        let infrastructure = ehex(7 + importance);
+        assert_eq!(
+            world.economic_extension(),
+            format!("(A7{}-2)", infrastructure)
+        );
+
+        let acceptance = ehex(8 + importance);
+        assert_eq!(world.cultural_extension(), format!("[9{}5B]", acceptance));
+    }
+
+    #[test]
+    fn ehex_digits() {
+        assert_eq!(ehex(-1), '0');
+        assert_eq!(ehex(9), '9');
+        assert_eq!(ehex(17), 'H');
+        assert_eq!(ehex(18), 'J');
+        assert_eq!(ehex(23), 'P');
+        assert_eq!(ehex(99), 'Z');
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::{Point, StarportClass, Subsector, TravelCode, World, WorldChange, TABLES};
+use crate::dice;
+
+/** Chances, in percent per decade, that each part of a `World` changes while fast-forwarding.
+
+Only inhabited worlds change; uninhabited worlds stay that way, and inhabited worlds are never
+completely abandoned.
+*/
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct GrowthRules {
+    /// Chance that the population code rises or falls by one
+    pub population_change: u8,
+    /// Chance that the tech level rises by one, up to `max_tech_level`
+    pub tech_advance: u8,
+    /// Chance that the tech level falls by one
+    pub tech_regress: u8,
+    /// Chance that the starport is upgraded or downgraded by one class
+    pub starport_change: u8,
+    /// Chance that the travel zone is reassessed by the Travellers' Aid Society
+    pub zone_change: u8,
+    /// Highest tech level worlds can advance to
+    pub max_tech_level: u16,
+}
+
+impl Default for GrowthRules {
+    fn default() -> Self {
+        Self {
+            population_change: 15,
+            tech_advance: 25,
+            tech_regress: 5,
+            starport_change: 10,
+            zone_change: 10,
+            max_tech_level: 15,
+        }
+    }
+}
+
+impl Subsector {
+    /** Advance every `World` of the `Subsector` by `decades` decades according to `rules`.
+
+    The changes are recorded in each `World`'s history.
+
+    # Returns
+    The changes to each `World` that changed at all, keyed by location.
+    */
+    pub fn fast_forward(
+        &mut self,
+        decades: u32,
+        rules: &GrowthRules,
+    ) -> BTreeMap<Point, Vec<WorldChange>> {
+        let mut summary = BTreeMap::new();
+        for (point, world) in self.map.iter_mut() {
+            let old = world.clone();
+            for _ in 0..decades {
+                world.advance_decade(rules);
+            }
+            world.resolve_trade_codes();
+
+            let changes = world.changes_from(&old);
+            if !changes.is_empty() {
+                world.history.extend(changes.iter().cloned());
+                summary.insert(*point, changes);
+            }
+        }
+        summary
+    }
+}
+
+impl World {
+    /** Randomly evolve the population, tech level, starport, and travel zone by one decade. */
+    fn advance_decade(&mut self, rules: &GrowthRules) {
+        if self.population.code == 0 {
+            return;
+        }
+
+        if chance(rules.population_change) {
+            let max_code = TABLES.pop_table.len() as u16 - 1;
+            let code = if dice::roll_2d(6) + self.growth_dm() >= 8 {
+                (self.population.code + 1).min(max_code)
+            } else {
+                (self.population.code - 1).max(1)
+            };
+            self.population = TABLES.pop_table[code as usize].clone();
+        }
+
+        let max_tech_level = (TABLES.tech_level_table.len() as u16 - 1).min(rules.max_tech_level);
+        if chance(rules.tech_advance) && self.tech_level.code < max_tech_level {
+            self.tech_level = TABLES.tech_level_table[self.tech_level.code as usize + 1].clone();
+        } else if chance(rules.tech_regress) && self.tech_level.code > 0 {
+            self.tech_level = TABLES.tech_level_table[self.tech_level.code as usize - 1].clone();
+        }
+
+        if chance(rules.starport_change) {
+            let upgrade = dice::roll_2d(6) + self.growth_dm() >= 8;
+            self.shift_starport_class(upgrade);
+        }
+
+        if chance(rules.zone_change) {
+            self.reassess_travel_code();
+        }
+    }
+
+    /** Modifier to rolls for growth for how prosperous and stable the `World` is. */
+    fn growth_dm(&self) -> i32 {
+        let mut dm = 0;
+        if self.tech_level.code >= 9 {
+            dm += 1;
+        }
+        if matches!(self.starport.class, StarportClass::A | StarportClass::B) {
+            dm += 1;
+        }
+        if self.population.code >= 9 {
+            dm -= 1;
+        }
+        if self.travel_code != TravelCode::Safe {
+            dm -= 1;
+        }
+        dm
+    }
+
+    /** Move the starport to the next better class if `upgrade` or the next worse one otherwise.
+
+    A starport that is already as good or as bad as it can be stays as it is.
+    */
+    fn shift_starport_class(&mut self, upgrade: bool) {
+        let code = self.starport.code as usize;
+        let class = &self.starport.class;
+        let shifted = if upgrade {
+            TABLES.starport_table[code..]
+                .iter()
+                .find(|record| &record.class != class)
+        } else {
+            TABLES.starport_table[..code]
+                .iter()
+                .rev()
+                .find(|record| &record.class != class)
+        };
+
+        if let Some(record) = shifted {
+            self.starport = record.clone();
+            self.generate_berthing_cost();
+            self.generate_starport_details();
+            self.generate_fuel_market();
+        }
+    }
+
+    /** Move the travel zone one step toward or away from safety.
+
+    Safe worlds rarely become amber zones, amber zones usually stay as they are, and red zones are
+    eventually relaxed. The code counts as overridden if it no longer follows from the UWP.
+    */
+    fn reassess_travel_code(&mut self) {
+        let roll = dice::roll_2d(6);
+        let travel_code = match self.travel_code {
+            TravelCode::Safe if roll >= 11 => TravelCode::Amber,
+            TravelCode::Amber if roll <= 5 => TravelCode::Safe,
+            TravelCode::Amber if roll >= 12 => TravelCode::Red,
+            TravelCode::Red if roll >= 8 => TravelCode::Amber,
+            travel_code => travel_code,
+        };
+
+        self.resolve_travel_code();
+        self.travel_code_overridden = self.travel_code != travel_code;
+        self.travel_code = travel_code;
+    }
+}
+
+/** Roll for something with a `percent` chance of happening. */
+fn chance(percent: u8) -> bool {
+    dice::roll_range(1..=100) <= percent as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fast_forward() {
+        let mut subsector = Subsector::default();
+        let original = subsector.clone();
+
+        // Nothing changes without any decades or any chance of change
+        assert!(subsector
+            .fast_forward(0, &GrowthRules::default())
+            .is_empty());
+        let still = GrowthRules {
+            population_change: 0,
+            tech_advance: 0,
+            tech_regress: 0,
+            starport_change: 0,
+            zone_change: 0,
+            ..Default::default()
+        };
+        assert!(subsector.fast_forward(10, &still).is_empty());
+        assert_eq!(subsector, original);
+
+        let always_advance = GrowthRules {
+            tech_advance: 100,
+            max_tech_level: 12,
+            ..still
+        };
+        let summary = subsector.fast_forward(20, &always_advance);
+        for (point, world) in subsector.get_map() {
+            let before = original.get_world(point).unwrap();
+            if before.population.code == 0 {
+                assert_eq!(world.tech_level, before.tech_level);
+            } else {
+                assert!(world.tech_level.code >= before.tech_level.code.min(12));
+                assert!(world.tech_level.code <= before.tech_level.code.max(12));
+            }
+            assert_eq!(world.population, before.population);
+            assert_eq!(summary.contains_key(point), world != before);
+            assert_eq!(
+                world.history.len() - before.history.len(),
+                summary.get(point).map_or(0, Vec::len)
+            );
+        }
+    }
+
+    #[test]
+    fn shift_starport_class() {
+        let mut world = World::empty();
+        world.starport = TABLES.starport_table[7].clone();
+        world.shift_starport_class(true);
+        assert_eq!(world.starport.class, StarportClass::B);
+        world.shift_starport_class(false);
+        world.shift_starport_class(false);
+        assert_eq!(world.starport.class, StarportClass::D);
+
+        world.starport = TABLES.starport_table[12].clone();
+        world.shift_starport_class(true);
+        assert_eq!(world.starport.class, StarportClass::A);
+        world.starport = TABLES.starport_table[0].clone();
+        world.shift_starport_class(false);
+        assert_eq!(world.starport.class, StarportClass::X);
+    }
+}
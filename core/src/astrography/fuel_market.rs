@@ -0,0 +1,251 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::{StarportClass, Subsector, TradeCode, World};
+use crate::dice;
+
+/// Usual price in credits of a ton of refined fuel
+const REFINED_FUEL_PRICE: u32 = 500;
+/// Usual price in credits of a ton of unrefined fuel
+const UNREFINED_FUEL_PRICE: u32 = 100;
+
+/** Grade of starship fuel sold at a starport. */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FuelKind {
+    Refined,
+    Unrefined,
+}
+
+impl FuelKind {
+    pub const ALL_VALUES: [FuelKind; 2] = [Self::Refined, Self::Unrefined];
+
+    /** Usual price in credits of a ton of the fuel, before shortages and surpluses. */
+    fn base_price(&self) -> u32 {
+        match self {
+            Self::Refined => REFINED_FUEL_PRICE,
+            Self::Unrefined => UNREFINED_FUEL_PRICE,
+        }
+    }
+}
+
+impl fmt::Display for FuelKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Refined => "Refined",
+            Self::Unrefined => "Unrefined",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** How easily fuel of some grade can be bought at a starport right now. */
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum FuelAvailability {
+    /// The starport doesn't sell the fuel at all
+    Unavailable,
+    /// Fuel is rationed and sold at a premium
+    Shortage,
+    Available,
+    /// More fuel than the traffic needs, sold at a discount
+    Surplus,
+}
+
+impl FuelAvailability {
+    pub const ALL_VALUES: [FuelAvailability; 4] = [
+        Self::Unavailable,
+        Self::Shortage,
+        Self::Available,
+        Self::Surplus,
+    ];
+
+    /** Percentage of the usual price that fuel sells for, before any day to day variation. */
+    fn price_percent(&self) -> i32 {
+        match self {
+            Self::Unavailable => 0,
+            Self::Shortage => 150,
+            Self::Available => 100,
+            Self::Surplus => 80,
+        }
+    }
+}
+
+impl fmt::Display for FuelAvailability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Unavailable => "Unavailable",
+            Self::Shortage => "Shortage",
+            Self::Available => "Available",
+            Self::Surplus => "Surplus",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** Availability and price of one grade of fuel at a starport. */
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct FuelPrice {
+    pub availability: FuelAvailability,
+    /// Credits per ton; 0 if the fuel is unavailable
+    pub price: u32,
+}
+
+impl FuelPrice {
+    const UNAVAILABLE: FuelPrice = FuelPrice {
+        availability: FuelAvailability::Unavailable,
+        price: 0,
+    };
+}
+
+impl fmt::Display for FuelPrice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.availability {
+            FuelAvailability::Unavailable => write!(f, "unavailable"),
+            FuelAvailability::Available => write!(f, "Cr{}/ton", self.price),
+            availability => write!(
+                f,
+                "Cr{}/ton ({})",
+                self.price,
+                availability.to_string().to_lowercase()
+            ),
+        }
+    }
+}
+
+/** Prices and availability of refined and unrefined fuel at a `World`'s starport.
+
+Markets change over time, so they can be rerolled with a market tick whenever the campaign moves on.
+*/
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct FuelMarket {
+    pub refined: FuelPrice,
+    pub unrefined: FuelPrice,
+}
+
+impl FuelMarket {
+    pub fn get(&self, kind: FuelKind) -> &FuelPrice {
+        match kind {
+            FuelKind::Refined => &self.refined,
+            FuelKind::Unrefined => &self.unrefined,
+        }
+    }
+
+    pub fn get_mut(&mut self, kind: FuelKind) -> &mut FuelPrice {
+        match kind {
+            FuelKind::Refined => &mut self.refined,
+            FuelKind::Unrefined => &mut self.unrefined,
+        }
+    }
+}
+
+impl fmt::Display for FuelMarket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Refined {}, Unrefined {}", self.refined, self.unrefined)
+    }
+}
+
+impl World {
+    /** Roll the prices and availability of fuel at the `World`'s starport.
+
+    Class A and B starports sell both grades of fuel, class C and D only unrefined fuel, and class E
+    and X none at all. Each grade sold is in short supply on a 2D roll of 4- and in surplus on a
+    10+, with +1 for industrial, high tech, and water or fluid ocean worlds and -1 for
+    non-industrial, poor, and desert worlds. Shortages raise the price by half and surpluses lower
+    it by a fifth, and it varies by up to a quarter either way from day to day.
+    */
+    pub fn generate_fuel_market(&mut self) {
+        let sold = match self.starport.class {
+            StarportClass::A | StarportClass::B => [true, true],
+            StarportClass::C | StarportClass::D => [false, true],
+            StarportClass::E | StarportClass::X => [false, false],
+        };
+        let dm: i32 = self
+            .trade_codes
+            .iter()
+            .map(|trade_code| match trade_code {
+                TradeCode::In | TradeCode::Ht | TradeCode::Wa | TradeCode::Fl => 1,
+                TradeCode::Ni | TradeCode::Po | TradeCode::De => -1,
+                _ => 0,
+            })
+            .sum();
+
+        let mut market = FuelMarket {
+            refined: FuelPrice::UNAVAILABLE,
+            unrefined: FuelPrice::UNAVAILABLE,
+        };
+        for (kind, sold) in FuelKind::ALL_VALUES.into_iter().zip(sold) {
+            if !sold {
+                continue;
+            }
+            let availability = match dice::roll_2d(6) + dm {
+                i32::MIN..=4 => FuelAvailability::Shortage,
+                10.. => FuelAvailability::Surplus,
+                _ => FuelAvailability::Available,
+            };
+            let percent = availability.price_percent() + dice::flux() * 5;
+            *market.get_mut(kind) = FuelPrice {
+                availability,
+                price: kind.base_price() * percent as u32 / 100,
+            };
+        }
+        self.fuel_market = Some(market);
+    }
+}
+
+impl Subsector {
+    /** Roll the fuel market of every `World` again, as though time has passed since the last. */
+    pub fn fuel_market_tick(&mut self) {
+        for world in self.map.values_mut() {
+            world.generate_fuel_market();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::TABLES;
+
+    #[test]
+    fn generate_fuel_market() {
+        let mut world = World::empty();
+        for starport in TABLES.starport_table.iter() {
+            world.starport = starport.clone();
+            world.generate_fuel_market();
+            let market = world.fuel_market.unwrap();
+            let refined = market.get(FuelKind::Refined);
+            let unrefined = market.get(FuelKind::Unrefined);
+            match starport.class {
+                StarportClass::A | StarportClass::B => {
+                    assert_ne!(refined.availability, FuelAvailability::Unavailable);
+                    assert!((275..=875).contains(&refined.price), "{}", refined);
+                }
+                StarportClass::C | StarportClass::D => {
+                    assert_eq!(*refined, FuelPrice::UNAVAILABLE);
+                    assert!((55..=175).contains(&unrefined.price), "{}", unrefined);
+                }
+                StarportClass::E | StarportClass::X => {
+                    assert_eq!(
+                        market.to_string(),
+                        "Refined unavailable, Unrefined unavailable"
+                    );
+                }
+            }
+        }
+
+        let market = FuelMarket {
+            refined: FuelPrice {
+                availability: FuelAvailability::Shortage,
+                price: 750,
+            },
+            unrefined: FuelPrice {
+                availability: FuelAvailability::Available,
+                price: 100,
+            },
+        };
+        assert_eq!(
+            market.to_string(),
+            "Refined Cr750/ton (shortage), Unrefined Cr100/ton"
+        );
+    }
+}
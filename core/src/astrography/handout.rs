@@ -0,0 +1,348 @@
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::{NumberFormat, Point, Subsector, World};
+
+/** Which spoiler-prone fields of each `World` the players may learn from a survey handout.
+
+Fields the players may learn are rewritten as in-universe survey reports in the handout itself,
+the rest are left to the GM appendix at the end of it.
+*/
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct HandoutOptions {
+    /// Describe the local culture
+    pub culture: bool,
+    /// Name the factions and their strength, but never their government
+    pub factions: bool,
+    /// Jump rating to add a table of the worlds reachable from each world for, if any
+    pub jump_table: Option<u32>,
+    /// Post the travel advisory, which may name the factions
+    pub travel_advisory: bool,
+    /// Pass the world tags on as rumors, without their descriptions
+    pub world_tags: bool,
+}
+
+impl Default for HandoutOptions {
+    fn default() -> Self {
+        Self {
+            culture: true,
+            factions: false,
+            jump_table: None,
+            travel_advisory: false,
+            world_tags: false,
+        }
+    }
+}
+
+impl Subsector {
+    /** Produce a Markdown player handout of the `Subsector`, writing numbers with `numbers`.
+
+    Every `World` gets a survey entry with its public data and whichever spoiler-prone fields
+    `options` reveals, written as survey reports. The hidden fields are collected in a GM appendix
+    at the end, which should be cut off before the handout is given to the players. If `options`
+    has a jump table, it comes right before the appendix.
+    */
+    pub fn to_handout(&self, options: &HandoutOptions, numbers: &NumberFormat) -> String {
+        let mut lines = vec![format!("# {} Subsector Survey", self.name()), String::new()];
+        for (point, world) in self.map.iter() {
+            lines.extend(world.survey_entry(point, options, numbers));
+            lines.push(String::new());
+        }
+        if let Some(jump_rating) = options.jump_table {
+            lines.extend(self.jump_table(jump_rating));
+            lines.push(String::new());
+        }
+
+        let appendix: Vec<String> = self
+            .map
+            .iter()
+            .flat_map(|(point, world)| world.appendix_entry(point, options))
+            .collect();
+        if !appendix.is_empty() {
+            lines.push("---".to_string());
+            lines.push(String::new());
+            lines.push("# GM Appendix".to_string());
+            lines.push(String::new());
+            lines.extend(appendix);
+        }
+
+        lines.join("\n")
+    }
+
+    /** Get the lines of a Markdown table of which worlds can be reached from each `World` by a ship
+    with `jump_rating`.
+    */
+    fn jump_table(&self, jump_rating: u32) -> Vec<String> {
+        let mut lines = vec![
+            format!("## Jump-{} Reachability", jump_rating),
+            String::new(),
+            "| World | Hex | Within One Jump | Reachable Worlds | Most Jumps |".to_string(),
+            "| --- | --- | --- | --- | --- |".to_string(),
+        ];
+        for (point, world) in self.map.iter() {
+            let reachable = self.jump_reachability(point, jump_rating, self.map.len() as u32);
+            let neighbors: Vec<String> = reachable
+                .iter()
+                .filter(|(_, &jumps)| jumps == 1)
+                .map(|(point, _)| format!("{} ({})", self.map[point].name, point))
+                .collect();
+            lines.push(format!(
+                "| {} | {} | {} | {} | {} |",
+                world.name,
+                point,
+                if neighbors.is_empty() {
+                    "None".to_string()
+                } else {
+                    neighbors.join(", ")
+                },
+                reachable.len(),
+                reachable.values().max().unwrap_or(&0)
+            ));
+        }
+        lines
+    }
+}
+
+impl World {
+    /** Get the lines of the `World`'s entry in the player section of a handout. */
+    fn survey_entry(
+        &self,
+        point: &Point,
+        options: &HandoutOptions,
+        numbers: &NumberFormat,
+    ) -> Vec<String> {
+        let trade_codes = self.trade_code_long_str();
+        let mut lines = vec![
+            format!("## {} ({})", self.name, point),
+            String::new(),
+            format!("- UWP: {}", self.profile_str()),
+            format!(
+                "- Trade Codes: {}",
+                if trade_codes.is_empty() {
+                    "None"
+                } else {
+                    &trade_codes
+                }
+            ),
+            format!("- Bases: {}", self.base_str()),
+            format!("- Travel Code: {}", self.travel_code_str()),
+            format!(
+                "- Berthing: Cr{}",
+                numbers.count(self.starport.berthing_cost as u64)
+            ),
+        ];
+        if !self.pronunciation.is_empty() {
+            lines.insert(2, format!("- Pronounced: {}", self.pronunciation));
+        }
+
+        let mut reports = Vec::new();
+        if options.travel_advisory && !self.travel_advisory.is_empty() {
+            reports.push(format!("Travel advisory: {}", self.travel_advisory));
+        }
+        if options.culture && self.population.code > 0 {
+            reports.push(format!(
+                "Survey teams describe the local culture as {}. {}",
+                self.culture.cultural_difference.to_lowercase(),
+                self.culture.description
+            ));
+        }
+        if options.factions && !self.factions.is_empty() {
+            let factions: Vec<String> = self
+                .factions
+                .iter()
+                .map(|faction| format!("{} ({})", faction.name, faction.strength.to_lowercase()))
+                .collect();
+            reports.push(format!(
+                "Local news services report on the activities of {}.",
+                list_str(&factions)
+            ));
+        }
+        if options.world_tags {
+            let tags: Vec<String> = self
+                .world_tags
+                .iter()
+                .map(|tag| tag.tag.to_lowercase())
+                .collect();
+            reports.push(format!(
+                "Travellers returning from {} speak of {}.",
+                self.name,
+                list_str(&tags)
+            ));
+        }
+        if !self.player_notes.is_empty() {
+            reports.push(format!("Surveyor's remarks: {}", self.player_notes));
+        }
+
+        for report in reports {
+            lines.push(String::new());
+            lines.push(format!("> {}", report));
+        }
+        lines
+    }
+
+    /** Get the lines of the `World`'s entry in the GM appendix of a handout, if it has any. */
+    fn appendix_entry(&self, point: &Point, options: &HandoutOptions) -> Vec<String> {
+        let mut lines = Vec::new();
+        if !options.world_tags {
+            lines.push("**World Tags**".to_string());
+            lines.push(String::new());
+            lines.extend(
+                self.world_tags
+                    .iter()
+                    .map(|tag| format!("- {}: {}", tag.tag, tag.description)),
+            );
+            lines.push(String::new());
+        }
+        if !self.factions.is_empty() {
+            lines.push("**Factions**".to_string());
+            lines.push(String::new());
+            lines.extend(self.factions.iter().map(|faction| {
+                format!(
+                    "- {}: {}, {}",
+                    faction.name, faction.strength, faction.government.kind
+                )
+            }));
+            lines.push(String::new());
+        }
+        if !self.hazards.is_empty() {
+            lines.push("**Hazards**".to_string());
+            lines.push(String::new());
+            lines.extend(self.hazards.iter().map(|hazard| {
+                format!(
+                    "- {} ({}): {}",
+                    hazard.kind, hazard.severity, hazard.description
+                )
+            }));
+            lines.push(String::new());
+        }
+        if !options.culture && self.population.code > 0 {
+            lines.push(format!(
+                "**Culture**: {}. {}",
+                self.culture.cultural_difference, self.culture.description
+            ));
+            lines.push(String::new());
+        }
+        if !options.travel_advisory && !self.travel_advisory.is_empty() {
+            lines.push(format!("**Travel Advisory**: {}", self.travel_advisory));
+            lines.push(String::new());
+        }
+        if !self.secret_notes.is_empty() {
+            lines.push("**Secret Notes**".to_string());
+            lines.push(String::new());
+            lines.push(self.secret_notes.clone());
+            lines.push(String::new());
+        }
+
+        if !lines.is_empty() {
+            lines.insert(0, format!("## {} ({})", self.name, point));
+            lines.insert(1, String::new());
+        }
+        lines
+    }
+}
+
+/** Join `items` into an English list, e.g. "a, b, and c". */
+fn list_str(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [item] => item.clone(),
+        [first, second] => format!("{} and {}", first, second),
+        [rest @ .., last] => format!("{}, and {}", rest.join(", "), last),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::{Faction, Hazard, HazardKind, HazardSeverity};
+
+    #[test]
+    fn handout_spoilers() {
+        let mut subsector = Subsector::empty();
+        subsector.set_name("Regina".to_string());
+        let mut world = World::new("Efate".to_string());
+        world.population.code = 5;
+        world.player_notes = "The duke rules from a floating palace".to_string();
+        world.secret_notes = "The duke is a Zhodani agent".to_string();
+        world.factions = vec![Faction::random()];
+        world.factions[0].name = "Loyalists".to_string();
+        world.hazards = vec![Hazard {
+            kind: HazardKind::SolarFlares,
+            severity: HazardSeverity::Severe,
+            description: "Flares scorch the day side".to_string(),
+        }];
+        world.travel_advisory = "Amber Zone: unrest among the Loyalists".to_string();
+        let tag = world.world_tags[0].clone();
+        subsector
+            .insert_world(&Point { x: 1, y: 1 }, world.clone())
+            .unwrap();
+
+        let handout = subsector.to_handout(&HandoutOptions::default(), &NumberFormat::default());
+        let (players, gm) = handout.split_once("# GM Appendix").unwrap();
+        assert!(players.contains("## Efate (0101)"));
+        assert!(players.contains("- Pronounced: EH-faht"));
+        assert!(players.contains(&world.culture.description));
+        assert!(!players.contains("speak of"));
+        assert!(!players.contains("Loyalists"));
+        assert!(players.contains("Surveyor's remarks: The duke rules from a floating palace"));
+        assert!(!players.contains("Zhodani"));
+        assert!(gm.contains(&tag.description));
+        assert!(gm.contains("Loyalists"));
+        assert!(gm.contains("Zhodani"));
+        assert!(!players.contains("Flares scorch"));
+        assert!(gm.contains("- Solar Flares (Severe): Flares scorch the day side"));
+        assert!(gm.contains("**Travel Advisory**: Amber Zone: unrest among the Loyalists"));
+
+        let options = HandoutOptions {
+            culture: false,
+            factions: true,
+            jump_table: None,
+            travel_advisory: true,
+            world_tags: true,
+        };
+        let handout = subsector.to_handout(&options, &NumberFormat::default());
+        let (players, gm) = handout.split_once("# GM Appendix").unwrap();
+        assert!(players.contains(&format!("speak of {}", tag.tag.to_lowercase())));
+        assert!(players.contains("Loyalists"));
+        // Secret notes are never revealed
+        assert!(!players.contains("Zhodani"));
+        assert!(gm.contains("**Secret Notes**\n\nThe duke is a Zhodani agent"));
+        assert!(!players.contains(&tag.description));
+        // Faction governments stay a secret even when the factions are known
+        assert!(gm.contains(&world.factions[0].government.kind));
+        assert!(gm.contains(&world.culture.description));
+        assert!(players.contains("> Travel advisory: Amber Zone: unrest among the Loyalists"));
+        assert!(!gm.contains("**Travel Advisory**"));
+    }
+
+    #[test]
+    fn handout_jump_table() {
+        let mut subsector = Subsector::empty();
+        for (x, y, name) in [(1, 1, "Efate"), (1, 3, "Alell"), (8, 10, "Yori")] {
+            subsector
+                .insert_world(&Point { x, y }, World::new(name.to_string()))
+                .unwrap();
+        }
+        let numbers = NumberFormat::default();
+        let handout = subsector.to_handout(&HandoutOptions::default(), &numbers);
+        assert!(!handout.contains("Reachability"));
+
+        let options = HandoutOptions {
+            jump_table: Some(2),
+            ..Default::default()
+        };
+        let handout = subsector.to_handout(&options, &numbers);
+        let players = handout.split("# GM Appendix").next().unwrap();
+        assert!(players.contains("## Jump-2 Reachability"));
+        assert!(players.contains("| Efate | 0101 | Alell (0103) | 1 | 1 |"));
+        assert!(players.contains("| Yori | 0810 | None | 0 | 0 |"));
+    }
+
+    #[test]
+    fn list_strs() {
+        let items: Vec<String> = ["a", "b", "c"].map(String::from).to_vec();
+        assert_eq!(list_str(&items[..1]), "a");
+        assert_eq!(list_str(&items[..2]), "a and b");
+        assert_eq!(list_str(&items), "a, b, and c");
+    }
+}
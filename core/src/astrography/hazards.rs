@@ -0,0 +1,240 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::World;
+use crate::dice;
+
+/// Lowest 2D roll plus DMs that gives a system a hazard of some kind
+const HAZARD_TARGET: i32 = 10;
+
+const SOLAR_FLARE_DESCRIPTIONS: [&str; 3] = [
+    "Occasional flares disrupt comms for a few hours at a time",
+    "Frequent flares force ships to shelter behind the world or its moons",
+    "Violent flares can cook an unshielded hull in open space",
+];
+
+const RADIATION_BELT_DESCRIPTIONS: [&str; 3] = [
+    "Weak radiation belts around the gas giant shorten safe refueling passes",
+    "Radiation belts make skimming the gas giant a risk without extra shielding",
+    "Intense radiation belts fry unhardened electronics on any close approach",
+];
+
+const ION_STORM_DESCRIPTIONS: [&str; 3] = [
+    "Ion storms now and then scramble sensors near the jump point",
+    "Regular ion storms between the stars delay traffic for days",
+    "Ion storms between the stars cut the system off for weeks at a time",
+];
+
+const DEBRIS_FIELD_DESCRIPTIONS: [&str; 3] = [
+    "Scattered debris in the belt calls for careful piloting",
+    "Dense debris in the belt has holed more than one careless ship",
+    "Collisions in the belt keep the inner system full of fast debris",
+];
+
+/** Kind of stellar or orbital hazard found in a `World`'s system. */
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum HazardKind {
+    SolarFlares,
+    RadiationBelts,
+    IonStorms,
+    DebrisField,
+}
+
+impl HazardKind {
+    pub const ALL_VALUES: [HazardKind; 4] = [
+        Self::SolarFlares,
+        Self::RadiationBelts,
+        Self::IonStorms,
+        Self::DebrisField,
+    ];
+
+    /** Descriptions of the hazard for each severity, from least to most severe. */
+    fn descriptions(&self) -> &'static [&'static str; 3] {
+        match self {
+            Self::SolarFlares => &SOLAR_FLARE_DESCRIPTIONS,
+            Self::RadiationBelts => &RADIATION_BELT_DESCRIPTIONS,
+            Self::IonStorms => &ION_STORM_DESCRIPTIONS,
+            Self::DebrisField => &DEBRIS_FIELD_DESCRIPTIONS,
+        }
+    }
+}
+
+impl fmt::Display for HazardKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::SolarFlares => "Solar Flares",
+            Self::RadiationBelts => "Radiation Belts",
+            Self::IonStorms => "Ion Storms",
+            Self::DebrisField => "Debris Field",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** How dangerous a `Hazard` is to ships and travellers. */
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum HazardSeverity {
+    Minor,
+    Moderate,
+    Severe,
+}
+
+impl HazardSeverity {
+    pub const ALL_VALUES: [HazardSeverity; 3] = [Self::Minor, Self::Moderate, Self::Severe];
+
+    /** Get the severity of a hazard rolled `margin` over the target number. */
+    fn from_margin(margin: i32) -> Self {
+        match margin {
+            i32::MIN..=1 => Self::Minor,
+            2..=3 => Self::Moderate,
+            _ => Self::Severe,
+        }
+    }
+}
+
+impl fmt::Display for HazardSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Minor => "Minor",
+            Self::Moderate => "Moderate",
+            Self::Severe => "Severe",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** A stellar or orbital hazard in a `World`'s system. */
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Hazard {
+    pub kind: HazardKind,
+    pub severity: HazardSeverity,
+    pub description: String,
+}
+
+impl World {
+    /** Get the DM to the roll for each kind of hazard in the `World`'s system.
+
+    Red dwarfs flare and giant stars are unstable, gas giants have radiation belts that are worse
+    without an atmosphere to shield the world, companion stars stir up ion storms that are worse
+    with a white dwarf, and planetoid belts hold debris. Kinds of hazard the system can't have are
+    left out.
+    */
+    fn hazard_dms(&self) -> Vec<(HazardKind, i32)> {
+        let stars: Vec<&str> = self.stellar.split(' ').collect();
+        let primary = stars.first().copied().unwrap_or_default();
+        let companions = stars.iter().skip(2).filter(|star| **star != "V").count() as i32;
+        let has_white_dwarf = stars.contains(&"D");
+
+        let mut dms = Vec::new();
+
+        let mut flare_dm = 0;
+        if primary.starts_with('M') {
+            flare_dm += 3;
+        } else if primary.starts_with('B') || primary.starts_with('A') {
+            flare_dm += 1;
+        }
+        if stars.get(1).is_some_and(|size| *size != "V") {
+            flare_dm += 2;
+        }
+        dms.push((HazardKind::SolarFlares, flare_dm));
+
+        if self.gas_giants > 0 {
+            let mut radiation_dm = 1;
+            if self.atmosphere.code <= 1 {
+                radiation_dm += 1;
+            }
+            if has_white_dwarf {
+                radiation_dm += 1;
+            }
+            dms.push((HazardKind::RadiationBelts, radiation_dm));
+        }
+
+        if companions > 0 {
+            let mut storm_dm = companions;
+            if has_white_dwarf {
+                storm_dm += 2;
+            }
+            dms.push((HazardKind::IonStorms, storm_dm));
+        }
+
+        if let Some(belts) = self.planetoid_belts {
+            if belts > 0 {
+                dms.push((HazardKind::DebrisField, belts));
+            }
+        }
+
+        dms
+    }
+
+    /** Roll the stellar and orbital hazards of the `World`'s system, replacing the old ones.
+
+    Each kind of hazard the system can have is rolled for on 2D plus its DM, and the further the
+    roll is over the target the more severe the hazard. Most systems have no hazards at all.
+    */
+    pub fn generate_hazards(&mut self) {
+        self.hazards = self
+            .hazard_dms()
+            .into_iter()
+            .filter_map(|(kind, dm)| {
+                let margin = dice::roll_2d(6) + dm - HAZARD_TARGET;
+                if margin < 0 {
+                    return None;
+                }
+
+                let severity = HazardSeverity::from_margin(margin);
+                Some(Hazard {
+                    kind,
+                    severity,
+                    description: kind.descriptions()[severity as usize].to_string(),
+                })
+            })
+            .collect();
+    }
+
+    /** Get the severity of the worst hazard in the `World`'s system, if it has any. */
+    pub fn worst_hazard(&self) -> Option<HazardSeverity> {
+        self.hazards.iter().map(|hazard| hazard.severity).max()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_hazards() {
+        const ATTEMPTS: usize = 200;
+        let mut world = World::empty();
+        world.stellar = "G2 V".to_string();
+        world.gas_giants = 0;
+        world.planetoid_belts = Some(0);
+        for _ in 0..ATTEMPTS {
+            world.generate_hazards();
+            // Only flares from the primary are possible, and never severe ones around a lone G star
+            for hazard in world.hazards.iter() {
+                assert_eq!(hazard.kind, HazardKind::SolarFlares);
+                assert!(hazard.severity <= HazardSeverity::Moderate);
+                assert!(hazard
+                    .kind
+                    .descriptions()
+                    .contains(&&hazard.description[..]));
+            }
+        }
+
+        world.stellar = "M5 III D".to_string();
+        world.gas_giants = 2;
+        world.planetoid_belts = Some(2);
+        let mut kinds_seen = Vec::new();
+        for _ in 0..ATTEMPTS {
+            world.generate_hazards();
+            kinds_seen.extend(world.hazards.iter().map(|hazard| hazard.kind));
+        }
+        for kind in HazardKind::ALL_VALUES {
+            assert!(kinds_seen.contains(&kind), "{} never generated", kind);
+        }
+
+        world.hazards.clear();
+        assert_eq!(world.worst_hazard(), None);
+    }
+}
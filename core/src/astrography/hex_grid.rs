@@ -0,0 +1,256 @@
+use std::{collections::BTreeMap, fmt};
+
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::{Point, Subsector, Translation};
+
+/// Distance from the outside of the hexes to the middle of the column and row headers
+const HEADER_GAP: f64 = 4.0;
+/// Distance from the middle of a header down to its baseline, about a third of its font size
+const HEADER_BASELINE_SHIFT: f64 = 1.2;
+
+/** Which way up the hexes of a subsector map are drawn. */
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum HexOrientation {
+    /// Flat edges at the top and bottom of each hex, with the columns running down the map, as in
+    /// the built-in template
+    #[default]
+    FlatTop,
+    /// Corners at the top and bottom of each hex, with the map turned on its side so that the
+    /// columns run across it
+    PointyTop,
+}
+
+impl HexOrientation {
+    pub const ALL_VALUES: [HexOrientation; 2] = [Self::FlatTop, Self::PointyTop];
+}
+
+impl fmt::Display for HexOrientation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::FlatTop => "Flat-Top",
+            Self::PointyTop => "Pointy-Top",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** Geometry of the hex grid of a subsector map drawn from scratch.
+
+Every hex has the same neighbors whichever way up the hexes are. Flat-topped hexes are laid out in
+columns running down the map with every even column half a hex lower, and pointy-topped ones are
+laid out as if that grid were turned on its side, with the columns running across the map and every
+even one half a hex further right.
+*/
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct HexGrid {
+    pub(super) orientation: HexOrientation,
+    /// Distance from the center of each hex to its corners
+    pub(super) radius: f64,
+    /// Top left corner of the smallest rectangle around every hex
+    pub(super) origin: Translation,
+}
+
+impl HexGrid {
+    /** Get the width and height of the smallest rectangle around every hex. */
+    pub(super) fn size(&self) -> [f64; 2] {
+        let along_columns = (Subsector::ROWS as f64 + 0.5) * self.hex_spacing();
+        let across_columns = ((Subsector::COLUMNS - 1) as f64 * 1.5 + 2.0) * self.radius;
+        match self.orientation {
+            HexOrientation::FlatTop => [across_columns, along_columns],
+            HexOrientation::PointyTop => [along_columns, across_columns],
+        }
+    }
+
+    /** Get the center of the hex at `point`, even if it's off the map. */
+    pub(super) fn center(&self, point: &Point) -> Translation {
+        let column_offset = if point.x.rem_euclid(2) == 0 {
+            self.hex_spacing() / 2.0
+        } else {
+            0.0
+        };
+        let along = self.hex_spacing() / 2.0 + (point.y - 1) as f64 * self.hex_spacing();
+        let across = self.radius + (point.x - 1) as f64 * 1.5 * self.radius;
+        match self.orientation {
+            HexOrientation::FlatTop => Translation {
+                x: self.origin.x + across,
+                y: self.origin.y + along + column_offset,
+            },
+            HexOrientation::PointyTop => Translation {
+                x: self.origin.x + along + column_offset,
+                y: self.origin.y + across,
+            },
+        }
+    }
+
+    /** Get the center of every hex on the map. */
+    pub(super) fn centers(&self) -> BTreeMap<Point, Translation> {
+        let mut centers = BTreeMap::new();
+        for x in 1..=Subsector::COLUMNS as i32 {
+            for y in 1..=Subsector::ROWS as i32 {
+                let point = Point { x, y };
+                centers.insert(point, self.center(&point));
+            }
+        }
+        centers
+    }
+
+    /** Get the `points` attribute of a hex `polygon` centered at `center`. */
+    pub(super) fn hex_points(&self, center: Translation) -> String {
+        let first_corner = match self.orientation {
+            HexOrientation::FlatTop => 0.0,
+            HexOrientation::PointyTop => 30.0,
+        };
+        (0..6)
+            .map(|corner| {
+                let angle = (first_corner + 60.0 * corner as f64).to_radians();
+                format!(
+                    "{:.3},{:.3}",
+                    center.x + self.radius * angle.cos(),
+                    center.y + self.radius * angle.sin()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /** Get the text and baseline of the number of each column and row, e.g. "03", outside the
+    top and left edges of the grid.
+    */
+    pub(super) fn headers(&self) -> Vec<(String, Translation)> {
+        let top = self.origin.y - HEADER_GAP + HEADER_BASELINE_SHIFT;
+        let left = self.origin.x - HEADER_GAP;
+        let column_header = |x: i32| {
+            let center = self.center(&Point { x, y: 1 });
+            let position = match self.orientation {
+                HexOrientation::FlatTop => Translation {
+                    x: center.x,
+                    y: top,
+                },
+                HexOrientation::PointyTop => Translation {
+                    x: left,
+                    y: center.y + HEADER_BASELINE_SHIFT,
+                },
+            };
+            (format!("{:02}", x), position)
+        };
+        // Rows zigzag, so their headers line up with the odd columns
+        let row_header = |y: i32| {
+            let center = self.center(&Point { x: 1, y });
+            let position = match self.orientation {
+                HexOrientation::FlatTop => Translation {
+                    x: left,
+                    y: center.y + HEADER_BASELINE_SHIFT,
+                },
+                HexOrientation::PointyTop => Translation {
+                    x: center.x,
+                    y: top,
+                },
+            };
+            (format!("{:02}", y), position)
+        };
+
+        (1..=Subsector::COLUMNS as i32)
+            .map(column_header)
+            .chain((1..=Subsector::ROWS as i32).map(row_header))
+            .collect()
+    }
+
+    /** Get the SVG transform that shrinks the grid, along with its headers if there are any, to
+    fit inside `area` and centers it there, or `None` if it already fits.
+
+    `area` is the `[x, y, width, height]` of a rectangle in the same units as the grid.
+    */
+    pub(super) fn fit_transform(&self, headers: bool, area: [f64; 4]) -> Option<String> {
+        let margin = if headers { 2.0 * HEADER_GAP } else { 0.0 };
+        let [width, height] = self.size();
+        let [x, y, width, height] = [
+            self.origin.x - margin,
+            self.origin.y - margin,
+            width + margin,
+            height + margin,
+        ];
+        let [area_x, area_y, area_width, area_height] = area;
+        if x >= area_x
+            && y >= area_y
+            && x + width <= area_x + area_width
+            && y + height <= area_y + area_height
+        {
+            return None;
+        }
+
+        let scale = (area_width / width).min(area_height / height).min(1.0);
+        let dx = area_x + (area_width - width * scale) / 2.0 - x * scale;
+        let dy = area_y + (area_height - height * scale) / 2.0 - y * scale;
+        Some(format!(
+            "translate({:.3} {:.3}) scale({:.4})",
+            dx, dy, scale
+        ))
+    }
+
+    /** Distance between the centers of neighboring hexes in the same column. */
+    fn hex_spacing(&self) -> f64 {
+        3f64.sqrt() * self.radius
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_grid() {
+        let mut grid = HexGrid {
+            orientation: HexOrientation::FlatTop,
+            radius: 10.0,
+            origin: Translation { x: 0.0, y: 0.0 },
+        };
+        let spacing = 3f64.sqrt() * 10.0;
+        assert_eq!(grid.size(), [125.0, 10.5 * spacing]);
+        assert_eq!(
+            grid.center(&Point { x: 1, y: 1 }),
+            Translation {
+                x: 10.0,
+                y: spacing / 2.0
+            }
+        );
+        assert_eq!(
+            grid.center(&Point { x: 2, y: 1 }),
+            Translation {
+                x: 25.0,
+                y: spacing
+            }
+        );
+        assert!(grid
+            .hex_points(grid.center(&Point { x: 1, y: 1 }))
+            .starts_with("20.000,8.660"));
+        assert_eq!(grid.fit_transform(false, [0.0, 0.0, 125.0, 200.0]), None);
+        assert_eq!(
+            grid.fit_transform(false, [0.0, 0.0, 62.5, 200.0]).unwrap(),
+            "translate(0.000 54.534) scale(0.5000)"
+        );
+
+        // Turned on its side, columns run across the map
+        grid.orientation = HexOrientation::PointyTop;
+        assert_eq!(grid.size(), [10.5 * spacing, 125.0]);
+        assert_eq!(
+            grid.center(&Point { x: 2, y: 1 }),
+            Translation {
+                x: spacing,
+                y: 25.0
+            }
+        );
+        assert!(grid
+            .hex_points(grid.center(&Point { x: 1, y: 1 }))
+            .starts_with("17.321,15.000"));
+
+        let headers = grid.headers();
+        assert_eq!(headers.len(), Subsector::COLUMNS + Subsector::ROWS);
+        assert_eq!(headers[0].0, "01");
+        assert!(headers[0].1.x < 0.0);
+        assert_eq!(headers[Subsector::COLUMNS + 9].0, "10");
+        assert!(headers[Subsector::COLUMNS + 9].1.y < 0.0);
+        // Headers need room of their own
+        assert!(grid.fit_transform(true, [0.0, 0.0, 200.0, 200.0]).is_some());
+    }
+}
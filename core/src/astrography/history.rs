@@ -0,0 +1,305 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::{CulturalFacet, Facility, Sophont, World};
+
+/** A single edit of a field of a `World`, recorded when the edit was applied. */
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct WorldChange {
+    /// Seconds since the Unix epoch at which the change was applied
+    pub timestamp: u64,
+    pub field: String,
+    pub old_value: String,
+    pub new_value: String,
+}
+
+impl WorldChange {
+    /** Get the time of the change as a UTC date and time, e.g. "2022-06-01 13:45 UTC". */
+    pub fn timestamp_str(&self) -> String {
+        let (year, month, day) = civil_date(self.timestamp);
+        let minutes = (self.timestamp % 86_400) / 60;
+        format!(
+            "{:04}-{:02}-{:02} {:02}:{:02} UTC",
+            year,
+            month,
+            day,
+            minutes / 60,
+            minutes % 60
+        )
+    }
+}
+
+/** Write `timestamp`, in seconds since the Unix epoch, as a UTC date and time that can be used in
+file names and sorts in chronological order, e.g. "2022-06-01_134500".
+*/
+pub fn file_timestamp_str(timestamp: u64) -> String {
+    let (year, month, day) = civil_date(timestamp);
+    let seconds = timestamp % 86_400;
+    format!(
+        "{:04}-{:02}-{:02}_{:02}{:02}{:02}",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/** Get the current time in seconds since the Unix epoch. */
+#[cfg(not(target_arch = "wasm32"))]
+pub fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+/** Get the current time in seconds since the Unix epoch.
+
+`SystemTime` can't tell the time in the browser, so the time is asked of JavaScript instead.
+*/
+#[cfg(target_arch = "wasm32")]
+pub fn current_timestamp() -> u64 {
+    (js_sys::Date::now() / 1000.0) as u64
+}
+
+/** Get the year, month, and day of `timestamp`, in seconds since the Unix epoch, in UTC. */
+fn civil_date(timestamp: u64) -> (i64, i64, i64) {
+    // Convert days since the epoch to a civil date in the proleptic Gregorian calendar
+    let days = (timestamp / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Function that gets the displayed value of a field of a `World`
+type ValueOf = fn(&World) -> String;
+
+/** Fields tracked in a `World`'s history along with how to display their values. */
+const TRACKED_FIELDS: &[(&str, ValueOf)] = &[
+    ("Name", |world| world.name.clone()),
+    ("Subtype", |world| world.subtype.to_string()),
+    ("Starport", |world| world.starport.class.to_string()),
+    ("Berthing Cost", |world| {
+        world.starport.berthing_cost.to_string()
+    }),
+    ("Size", |world| world.size.to_string()),
+    ("Diameter", |world| world.diameter.to_string()),
+    ("Atmosphere", |world| {
+        format!(
+            "{}: {}",
+            world.atmosphere.code, world.atmosphere.composition
+        )
+    }),
+    ("Temperature", |world| {
+        format!("{}: {}", world.temperature.code, world.temperature.kind)
+    }),
+    ("Hydrographics", |world| {
+        format!(
+            "{}: {}",
+            world.hydrographics.code, world.hydrographics.description
+        )
+    }),
+    ("Population", |world| {
+        format!(
+            "{}: {}",
+            world.population.code, world.population.inhabitants
+        )
+    }),
+    ("Government", |world| {
+        format!("{}: {}", world.government.code, world.government.kind)
+    }),
+    ("Law Level", |world| world.law_level.code.to_string()),
+    ("Law Categories", |world| {
+        world
+            .law_categories
+            .map(|categories| categories.to_string())
+            .unwrap_or_default()
+    }),
+    ("Tech Level", |world| world.tech_level.code.to_string()),
+    ("Culture", |world| {
+        format!(
+            "{}: {}",
+            world.culture.code, world.culture.cultural_difference
+        )
+    }),
+    ("Cultural Details", |world| {
+        CulturalFacet::ALL_VALUES
+            .iter()
+            .map(|facet| world.cultural_details.get(*facet).clone())
+            .collect::<Vec<_>>()
+            .join("; ")
+    }),
+    ("Religion", |world| world.religion.to_string()),
+    ("World Tags", |world| {
+        world
+            .world_tags
+            .iter()
+            .map(|tag| tag.tag.clone())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }),
+    ("Factions", |world| {
+        world
+            .factions
+            .iter()
+            .map(|faction| faction.name.clone())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }),
+    ("Sophonts", |world| {
+        world
+            .sophonts
+            .iter()
+            .map(Sophont::remark)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }),
+    ("Orbital Facilities", |world| {
+        world
+            .orbital_facilities
+            .iter()
+            .map(Facility::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }),
+    ("Allegiance", |world| world.allegiance.clone()),
+    ("Bases", World::base_str),
+    ("Travel Code", World::travel_code_str),
+    ("Travel Advisory", |world| world.travel_advisory.clone()),
+    ("Gas Giants", |world| world.gas_giants.to_string()),
+    ("Planetoid Belts", |world| {
+        world.planetoid_belts.unwrap_or(0).to_string()
+    }),
+    ("Stellar", |world| world.stellar.clone()),
+    ("Nobility", |world| world.nobility.clone()),
+    ("Player Notes", |world| world.player_notes.clone()),
+    ("Secret Notes", |world| world.secret_notes.clone()),
+    ("Image", |world| match &world.image {
+        Some(_) => "Attached".to_string(),
+        None => "None".to_string(),
+    }),
+];
+
+impl World {
+    /** Append a `WorldChange` to the `World`'s history for every tracked field that differs from
+    `old`, all stamped with the current time.
+
+    # Returns
+    The number of changes recorded.
+    */
+    pub fn record_changes_from(&mut self, old: &World) -> usize {
+        let changes = self.changes_from(old);
+        let count = changes.len();
+        self.history.extend(changes);
+        count
+    }
+
+    /** Get a `WorldChange` for every tracked field that differs from `old`, all stamped with the
+    current time, without recording them.
+    */
+    pub fn changes_from(&self, old: &World) -> Vec<WorldChange> {
+        let timestamp = current_timestamp();
+
+        TRACKED_FIELDS
+            .iter()
+            .filter_map(|(field, value_of)| {
+                let old_value = value_of(old);
+                let new_value = value_of(self);
+                (old_value != new_value).then(|| WorldChange {
+                    timestamp,
+                    field: field.to_string(),
+                    old_value,
+                    new_value,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::{FacilityKind, FacilitySize, LawCategories, TABLES};
+
+    #[test]
+    fn record_world_changes() {
+        let old = World::empty();
+        let mut world = old.clone();
+        assert_eq!(world.record_changes_from(&old), 0);
+
+        world.name = "Regina".to_string();
+        world.population = TABLES.pop_table[8].clone();
+        world.has_naval_base = true;
+        assert_eq!(world.record_changes_from(&old), 3);
+
+        let fields: Vec<&str> = world.history.iter().map(|c| &c.field[..]).collect();
+        assert_eq!(fields, ["Name", "Population", "Bases"]);
+        assert_eq!(world.history[0].old_value, "");
+        assert_eq!(world.history[0].new_value, "Regina");
+        assert_eq!(world.history[2].new_value, "N");
+    }
+
+    /** Get the fields whose changes are recorded once `edit` is applied to an empty `World`. */
+    fn recorded_fields(edit: impl FnOnce(&mut World)) -> Vec<String> {
+        let old = World::empty();
+        let mut world = old.clone();
+        edit(&mut world);
+        world.record_changes_from(&old);
+        world
+            .history
+            .into_iter()
+            .map(|change| change.field)
+            .collect()
+    }
+
+    #[test]
+    fn record_added_field_changes() {
+        let fields = recorded_fields(|world| world.nobility = "BC".to_string());
+        assert_eq!(fields, ["Nobility"]);
+
+        let fields = recorded_fields(|world| {
+            world.orbital_facilities =
+                vec![Facility::new(FacilityKind::Highport, FacilitySize::Large)]
+        });
+        assert_eq!(fields, ["Orbital Facilities"]);
+
+        let fields = recorded_fields(|world| world.cultural_details.dress = "Robes".to_string());
+        assert_eq!(fields, ["Cultural Details"]);
+
+        let fields = recorded_fields(|world| world.law_categories = Some(LawCategories::default()));
+        assert_eq!(fields, ["Law Categories"]);
+
+        let fields = recorded_fields(|world| world.religion.scope = "Edited".to_string());
+        assert_eq!(fields, ["Religion"]);
+
+        let fields = recorded_fields(|world| world.travel_advisory = "Red Zone".to_string());
+        assert_eq!(fields, ["Travel Advisory"]);
+    }
+
+    #[test]
+    fn world_change_timestamp() {
+        let change = WorldChange {
+            timestamp: 1_654_091_100,
+            field: String::new(),
+            old_value: String::new(),
+            new_value: String::new(),
+        };
+        assert_eq!(change.timestamp_str(), "2022-06-01 13:45 UTC");
+        assert_eq!(file_timestamp_str(change.timestamp), "2022-06-01_134500");
+    }
+}
@@ -0,0 +1,172 @@
+use rand::seq::SliceRandom;
+
+use crate::astrography::{
+    world_sheet::sheet_section_lines, Point, Subsector, TradeCode, TravelCode, World,
+};
+
+/// Farthest apart, in parsecs, two worlds can be for a trade imbalance between them to be a hook
+const TRADE_HOOK_MAX_PARSECS: u32 = 2;
+
+const TAG_TEMPLATES: [&str; 4] = [
+    "A patron is funding an expedition to investigate the {tag} of {world} ({point}).",
+    "Rumors about the {tag} of {world} ({point}) have spread across the subsector; someone wants \
+    to know how much of it is true.",
+    "An old acquaintance on {world} ({point}) sends a desperate message about the {tag} there.",
+    "A noble house is quietly hiring travellers to exploit the {tag} of {world} ({point}).",
+];
+
+/** Pairs of trade codes where the first has goods that the second is short of. */
+const TRADE_IMBALANCES: [(TradeCode, TradeCode, &str); 3] = [
+    (
+        TradeCode::Ag,
+        TradeCode::Na,
+        "Crop failures have left {buyer} desperate for food shipments from {seller}, {parsecs} \
+        parsec(s) away.",
+    ),
+    (
+        TradeCode::In,
+        TradeCode::Ni,
+        "Brokers on {buyer} will pay a premium for manufactured goods from {seller}, {parsecs} \
+        parsec(s) away.",
+    ),
+    (
+        TradeCode::Ht,
+        TradeCode::Lt,
+        "A faction on {buyer} will pay anything for advanced technology smuggled in from {seller}, \
+        {parsecs} parsec(s) away.",
+    ),
+];
+
+impl Subsector {
+    /** Number of hooks generated for a brand new `Subsector`. */
+    pub const NUM_HOOKS: usize = 6;
+
+    /** Generate a random adventure hook from the worlds of the `Subsector`.
+
+    Hooks that are already in the `Subsector`'s list are avoided where possible.
+
+    # Returns
+    - `Some(hook)` with the new hook,
+    - `None` if the `Subsector` has no worlds to build a hook from
+    */
+    pub fn generate_hook(&self) -> Option<String> {
+        let candidates = self.hook_candidates();
+        let unused: Vec<_> = candidates
+            .iter()
+            .filter(|hook| !self.hooks.contains(hook))
+            .collect();
+
+        let mut rng = rand::thread_rng();
+        match unused.choose(&mut rng) {
+            Some(hook) => Some(hook.to_string()),
+            None => candidates.choose(&mut rng).cloned(),
+        }
+    }
+
+    /** Replace all of the `Subsector`'s hooks with `NUM_HOOKS` freshly generated ones. */
+    pub fn generate_hooks(&mut self) {
+        let mut candidates = self.hook_candidates();
+        candidates.shuffle(&mut rand::thread_rng());
+        candidates.truncate(Self::NUM_HOOKS);
+        self.hooks = candidates;
+    }
+
+    /** Every adventure hook that can be built from the worlds in the `Subsector`.
+
+    Hooks are drawn from each world's tags, factions, and travel code, as well as from trade
+    imbalances between nearby worlds.
+    */
+    fn hook_candidates(&self) -> Vec<String> {
+        let mut candidates = Vec::new();
+        for (point, world) in self.map.iter() {
+            candidates.extend(world_hooks(point, world));
+
+            for (other_point, other_world) in self.map.iter() {
+                let parsecs = point.distance(other_point);
+                if parsecs == 0 || parsecs > TRADE_HOOK_MAX_PARSECS {
+                    continue;
+                }
+
+                for (supply, demand, template) in TRADE_IMBALANCES.iter() {
+                    if world.trade_codes.contains(supply)
+                        && other_world.trade_codes.contains(demand)
+                    {
+                        candidates.push(
+                            template
+                                .replace("{buyer}", &located_name(other_point, other_world))
+                                .replace("{seller}", &located_name(point, world))
+                                .replace("{parsecs}", &parsecs.to_string()),
+                        );
+                    }
+                }
+            }
+        }
+        candidates
+    }
+}
+
+impl World {
+    /** Get the adventure hooks among `hooks` that involve the `World` at `point` as a section of
+    its data sheet, or an empty string if there aren't any.
+    */
+    pub(super) fn hook_sheet_section(
+        &self,
+        point: &Point,
+        hooks: &[String],
+        markdown: bool,
+    ) -> String {
+        let name = located_name(point, self);
+        let hooks: Vec<_> = hooks.iter().filter(|hook| hook.contains(&name)).collect();
+        if hooks.is_empty() {
+            return String::new();
+        }
+
+        let mut lines = sheet_section_lines("Adventure Hooks", markdown);
+        lines.extend(hooks.iter().map(|hook| format!("- {}", hook)));
+        lines.join("\n")
+    }
+}
+
+/** Name of `world` followed by its location, e.g. "Regina (1910)". */
+fn located_name(point: &Point, world: &World) -> String {
+    format!("{} ({})", world.name, point)
+}
+
+/** Adventure hooks built only from the data of `world`. */
+fn world_hooks(point: &Point, world: &World) -> Vec<String> {
+    let mut hooks = Vec::new();
+
+    let mut rng = rand::thread_rng();
+    for world_tag in world.world_tags.iter() {
+        let template = TAG_TEMPLATES.choose(&mut rng).unwrap();
+        hooks.push(
+            template
+                .replace("{tag}", &world_tag.tag)
+                .replace("{world}", &world.name)
+                .replace("{point}", &point.to_string()),
+        );
+    }
+
+    for faction in world.factions.iter() {
+        hooks.push(format!(
+            "The {} faction on {} is quietly recruiting off-world help against its rivals.",
+            faction.name,
+            located_name(point, world)
+        ));
+    }
+
+    match world.travel_code {
+        TravelCode::Safe => (),
+        TravelCode::Amber => hooks.push(format!(
+            "{} has been declared an Amber Zone, and a patron wants someone discreet to find out \
+            why.",
+            located_name(point, world)
+        )),
+        TravelCode::Red => hooks.push(format!(
+            "Someone is offering a fortune for passage past the interdiction of {}.",
+            located_name(point, world)
+        )),
+    }
+
+    hooks
+}
@@ -0,0 +1,127 @@
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::{Point, Subsector};
+
+/// Date given to the first entry of a journal, in the Imperial "day-year" format
+pub const DEFAULT_JOURNAL_DATE: &str = "001-1105";
+
+/** A dated entry of the campaign journal kept with a `Subsector`. */
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct JournalEntry {
+    /// Free-form date of the session, e.g. "001-1105"
+    pub date: String,
+    pub text: String,
+}
+
+impl Subsector {
+    pub fn journal(&self) -> &[JournalEntry] {
+        &self.journal
+    }
+
+    pub fn journal_mut(&mut self) -> &mut Vec<JournalEntry> {
+        &mut self.journal
+    }
+
+    /** Add an empty entry to the end of the journal and return its index.
+
+    The new entry is dated with the campaign's date if its clock has been started. Otherwise it has
+    the same date as the last one so that consecutive notes from one session don't need to be
+    redated, or `DEFAULT_JOURNAL_DATE` if it is the first.
+    */
+    pub fn add_journal_entry(&mut self) -> usize {
+        let date = match (self.campaign_date, self.journal.last()) {
+            (Some(date), _) => date.to_string(),
+            (None, Some(entry)) => entry.date.clone(),
+            (None, None) => DEFAULT_JOURNAL_DATE.to_string(),
+        };
+        self.journal.push(JournalEntry {
+            date,
+            text: String::new(),
+        });
+        self.journal.len() - 1
+    }
+}
+
+/** Find the hex codes in `text` that refer to locations inside a `Subsector`.
+
+A hex code is exactly four digits, e.g. "0504", that aren't part of a longer word or number.
+
+# Returns
+The byte range of each hex code in `text` paired with the location it refers to, in order.
+*/
+pub fn journal_links(text: &str) -> Vec<(Range<usize>, Point)> {
+    let is_word_byte = |byte: u8| byte.is_ascii_alphanumeric() || byte == b'_';
+    let bytes = text.as_bytes();
+
+    let mut links = Vec::new();
+    let mut start = 0;
+    while start < bytes.len() {
+        if !bytes[start].is_ascii_digit() || (start > 0 && is_word_byte(bytes[start - 1])) {
+            start += 1;
+            continue;
+        }
+
+        let end = bytes[start..]
+            .iter()
+            .position(|&byte| !is_word_byte(byte))
+            .map_or(bytes.len(), |len| start + len);
+        if end - start == 4 && bytes[start..end].iter().all(u8::is_ascii_digit) {
+            if let Ok(point) = Point::try_from(&text[start..end]) {
+                if Subsector::point_is_inbounds(&point) {
+                    links.push((start..end, point));
+                }
+            }
+        }
+        start = end;
+    }
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::ImperialDate;
+
+    #[test]
+    fn journal_entries() {
+        let mut subsector = Subsector::empty();
+        assert_eq!(subsector.add_journal_entry(), 0);
+        assert_eq!(subsector.journal()[0].date, DEFAULT_JOURNAL_DATE);
+
+        subsector.journal_mut()[0].date = "032-1105".to_string();
+        assert_eq!(subsector.add_journal_entry(), 1);
+        assert_eq!(subsector.journal()[1].date, "032-1105");
+
+        subsector.journal_mut()[1].text = "Jumped to 0504.".to_string();
+        let json = subsector.to_json();
+        let deserialized = Subsector::try_from_json(&json[..]).unwrap();
+        assert_eq!(deserialized.journal(), subsector.journal());
+
+        // Once the campaign's clock is started, new entries are dated by it
+        let date = ImperialDate::new(100, 1105).unwrap();
+        subsector.set_campaign_date(Some(date));
+        assert_eq!(subsector.add_journal_entry(), 2);
+        assert_eq!(subsector.journal()[2].date, "100-1105");
+        let deserialized = Subsector::try_from_json(&subsector.to_json()).unwrap();
+        assert_eq!(deserialized.campaign_date(), Some(date));
+    }
+
+    #[test]
+    fn hex_code_links() {
+        let text = "Left 0504 for 0810; 12345, x0101, 0911 and 0000 are not links (0102).";
+        let links = journal_links(text);
+        let points: Vec<Point> = links.iter().map(|(_, point)| *point).collect();
+        assert_eq!(
+            points,
+            vec![
+                Point { x: 5, y: 4 },
+                Point { x: 8, y: 10 },
+                Point { x: 1, y: 2 }
+            ]
+        );
+        assert_eq!(&text[links[0].0.clone()], "0504");
+        assert!(journal_links("").is_empty());
+    }
+}
@@ -0,0 +1,209 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::{World, TABLES};
+use crate::dice;
+
+const DRUG_BANS: [&str; 10] = [
+    "None",
+    "Highly addictive and dangerous narcotics",
+    "Highly addictive narcotics",
+    "Combat drugs",
+    "Addictive narcotics",
+    "Anagathics",
+    "Fast and slow drugs",
+    "All alcohol and stimulants",
+    "All medicinal drugs without a prescription",
+    "All drugs",
+];
+
+const TECHNOLOGY_BANS: [&str; 10] = [
+    "None",
+    "Dangerous technologies such as nanotechnology",
+    "Alien technology",
+    "TL 15+ items",
+    "TL 13+ items",
+    "TL 11+ items",
+    "TL 9+ items",
+    "TL 7+ items",
+    "TL 5+ items",
+    "TL 3+ items",
+];
+
+const PSIONICS_BANS: [&str; 10] = [
+    "None",
+    "Dangerous talents must be registered",
+    "All talents must be registered",
+    "Telepathy outside of government service",
+    "Teleportation and clairvoyance",
+    "All talents outside of government service",
+    "Psionic drugs",
+    "All use of psionics",
+    "Psionic technology",
+    "All psionics, including being psionic",
+];
+
+/** Kind of goods or activity restricted by a `World`'s laws, each with a law level of its own. */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LawCategory {
+    Weapons,
+    Armor,
+    Drugs,
+    Technology,
+    Psionics,
+}
+
+impl LawCategory {
+    pub const ALL_VALUES: [LawCategory; 5] = [
+        Self::Weapons,
+        Self::Armor,
+        Self::Drugs,
+        Self::Technology,
+        Self::Psionics,
+    ];
+
+    /** Get the most restrictive ban of the category at law level `level`, e.g. "Combat drugs".
+
+    Weapon and armor bans come from the law level table, where a level with no new armor banned
+    keeps the ban of the level below it.
+    */
+    pub fn banned(&self, level: u16) -> String {
+        let table: &[&str] = match self {
+            Self::Weapons | Self::Armor => {
+                let law_table = &TABLES.law_table;
+                let level = (level as usize).min(law_table.len() - 1);
+                return law_table[..=level]
+                    .iter()
+                    .rev()
+                    .map(|law| match self {
+                        Self::Weapons => &law.banned_weapons,
+                        _ => &law.banned_armor,
+                    })
+                    .find(|banned| !banned.is_empty())
+                    .cloned()
+                    .unwrap_or_default();
+            }
+            Self::Drugs => &DRUG_BANS,
+            Self::Technology => &TECHNOLOGY_BANS,
+            Self::Psionics => &PSIONICS_BANS,
+        };
+        table[(level as usize).min(table.len() - 1)].to_string()
+    }
+}
+
+impl fmt::Display for LawCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Weapons => "Weapons",
+            Self::Armor => "Armor",
+            Self::Drugs => "Drugs",
+            Self::Technology => "Technology",
+            Self::Psionics => "Psionics",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** Law level of each `LawCategory` on a `World`, varying a little around its overall law level. */
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct LawCategories {
+    pub weapons: u16,
+    pub armor: u16,
+    pub drugs: u16,
+    pub technology: u16,
+    pub psionics: u16,
+}
+
+impl LawCategories {
+    pub fn get(&self, category: LawCategory) -> u16 {
+        match category {
+            LawCategory::Weapons => self.weapons,
+            LawCategory::Armor => self.armor,
+            LawCategory::Drugs => self.drugs,
+            LawCategory::Technology => self.technology,
+            LawCategory::Psionics => self.psionics,
+        }
+    }
+
+    pub fn get_mut(&mut self, category: LawCategory) -> &mut u16 {
+        match category {
+            LawCategory::Weapons => &mut self.weapons,
+            LawCategory::Armor => &mut self.armor,
+            LawCategory::Drugs => &mut self.drugs,
+            LawCategory::Technology => &mut self.technology,
+            LawCategory::Psionics => &mut self.psionics,
+        }
+    }
+}
+
+impl fmt::Display for LawCategories {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let levels: Vec<String> = LawCategory::ALL_VALUES
+            .iter()
+            .map(|category| format!("{} {}", category, self.get(*category)))
+            .collect();
+        write!(f, "{}", levels.join(", "))
+    }
+}
+
+impl World {
+    /** Get the law level of `category`, which is the overall law level if the categories haven't
+    been rolled.
+    */
+    pub fn law_category_level(&self, category: LawCategory) -> u16 {
+        match &self.law_categories {
+            Some(categories) => categories.get(category),
+            None => self.law_level.code,
+        }
+    }
+
+    /** Roll the law level of each category a step or so either side of the overall law level.
+
+    Worlds with a law level of 0 have no laws to vary, so every category is 0 as well.
+    */
+    pub fn generate_law_categories(&mut self) {
+        let law_level = self.law_level.code as i32;
+        let max_code = TABLES.law_table.len() as i32 - 1;
+        let mut categories = LawCategories::default();
+        if law_level > 0 {
+            for category in LawCategory::ALL_VALUES {
+                let level = (law_level + dice::flux() / 3).clamp(0, max_code);
+                *categories.get_mut(category) = level as u16;
+            }
+        }
+        self.law_categories = Some(categories);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_law_categories() {
+        let mut world = World::empty();
+        world.law_level = TABLES.law_table[6].clone();
+        assert_eq!(world.law_category_level(LawCategory::Drugs), 6);
+
+        world.generate_law_categories();
+        for category in LawCategory::ALL_VALUES {
+            let level = world.law_category_level(category);
+            assert!((4..=8).contains(&level), "{} law level {}", category, level);
+        }
+
+        world.law_level = TABLES.law_table[0].clone();
+        world.generate_law_categories();
+        assert_eq!(world.law_categories, Some(LawCategories::default()));
+    }
+
+    #[test]
+    fn banned() {
+        assert_eq!(LawCategory::Drugs.banned(3), "Combat drugs");
+        assert_eq!(LawCategory::Weapons.banned(0), "None");
+        // Armor bans carry over levels that don't ban any more armor
+        assert_eq!(LawCategory::Armor.banned(7), LawCategory::Armor.banned(5));
+        assert_eq!(LawCategory::Technology.banned(20), "TL 3+ items");
+    }
+}
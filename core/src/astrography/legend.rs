@@ -0,0 +1,355 @@
+use quick_xml::events::BytesText;
+
+use crate::astrography::{
+    map_template::{
+        AMBER_ZONE_CLASS, RED_ZONE_CLASS, ROUTE_CLASS, STARPORT_TL_CLASS, XBOAT_ROUTE_CLASS,
+    },
+    standalone_map, MapOptions, MapRenderError, Point, PolityColor, Subsector, Translation,
+    TravelCode, WorldSubtype,
+};
+
+/// Position of the first legend entry on exported maps, in the free part of the bottom margin
+const SVG_LEGEND_ORIGIN: (f64, f64) = (117.747, 256.0);
+/// Position of the first legend entry on standalone maps, which have the whole bottom margin free
+const STANDALONE_SVG_LEGEND_ORIGIN: (f64, f64) = (39.515, 256.0);
+/// Vertical distance between legend entries on exported maps
+const SVG_LEGEND_ROW_HEIGHT: f64 = 3.6;
+/// Number of legend entries in each column on exported maps
+const SVG_LEGEND_ROWS: usize = 6;
+/// Horizontal distance between the columns of legend entries on exported maps
+const SVG_LEGEND_COLUMN_WIDTH: f64 = 24.5;
+
+/// Base codes in the order they are written on the map, along with what each one stands for
+const BASE_NAMES: [(&str, &str); 5] = [
+    ("N", "Naval Base"),
+    ("R", "Research Base"),
+    ("S", "Scout Base"),
+    ("T", "TAS Hostel"),
+    ("P", "Pirate Base"),
+];
+
+/** A symbol drawn on the subsector map that the map legend explains. */
+#[derive(Clone, Debug, PartialEq)]
+pub enum LegendSymbol {
+    DryWorld,
+    WetWorld,
+    GasGiant,
+    /// Outline around the world symbol of a world that isn't a mainworld
+    Subtype(WorldSubtype),
+    /// Starport class and tech level, e.g. "B-12"
+    StarportTl,
+    /// Base code written beside a world, e.g. "N"
+    Base(&'static str),
+    AmberZone,
+    RedZone,
+    XboatRoute,
+    TravelRoute,
+    /// Hex fill of a polity as an RGB color
+    Polity([u8; 3]),
+}
+
+impl LegendSymbol {
+    /** Whether the built-in map template's own legend already explains the symbol. */
+    fn in_template_legend(&self) -> bool {
+        matches!(
+            self,
+            Self::DryWorld | Self::WetWorld | Self::GasGiant | Self::StarportTl
+        )
+    }
+}
+
+/** A symbol on the subsector map along with a short explanation of it. */
+#[derive(Clone, Debug, PartialEq)]
+pub struct LegendEntry {
+    pub symbol: LegendSymbol,
+    pub label: String,
+}
+
+impl LegendEntry {
+    fn new(symbol: LegendSymbol, label: &str) -> Self {
+        Self {
+            symbol,
+            label: label.to_string(),
+        }
+    }
+}
+
+impl Subsector {
+    /** Build the legend of a map drawn with the layers in `options` and the travel `route`.
+
+    Only symbols that are actually drawn are listed: the layer showing them has to be enabled and
+    at least one world on the map has to have them. Each polity with members gets an entry of its
+    own in the color its hexes are filled with.
+    */
+    pub fn map_legend(&self, options: &MapOptions, route: &[Point]) -> Vec<LegendEntry> {
+        let worlds = || self.map.values();
+        let mut legend = Vec::new();
+
+        if worlds().any(|world| !world.is_wet_world()) {
+            legend.push(LegendEntry::new(LegendSymbol::DryWorld, "Dry World"));
+        }
+        if worlds().any(|world| world.is_wet_world()) {
+            legend.push(LegendEntry::new(LegendSymbol::WetWorld, "Wet World"));
+        }
+        if worlds().any(|world| world.has_gas_giant()) {
+            legend.push(LegendEntry::new(LegendSymbol::GasGiant, "Gas Giant"));
+        }
+        for subtype in WorldSubtype::ALL_VALUES {
+            if !subtype.is_mainworld() && worlds().any(|world| world.subtype == subtype) {
+                legend.push(LegendEntry::new(
+                    LegendSymbol::Subtype(subtype),
+                    &subtype.to_string(),
+                ));
+            }
+        }
+        if options.starport_tl && !self.map.is_empty() {
+            legend.push(LegendEntry::new(LegendSymbol::StarportTl, "Starport-TL"));
+        }
+
+        if options.bases {
+            for (code, name) in BASE_NAMES {
+                if worlds().any(|world| world.base_str().contains(code)) {
+                    legend.push(LegendEntry::new(LegendSymbol::Base(code), name));
+                }
+            }
+        }
+
+        if options.travel_zones {
+            if worlds().any(|world| world.travel_code == TravelCode::Amber) {
+                legend.push(LegendEntry::new(LegendSymbol::AmberZone, "Amber Zone"));
+            }
+            if worlds().any(|world| world.travel_code == TravelCode::Red) {
+                legend.push(LegendEntry::new(LegendSymbol::RedZone, "Red Zone"));
+            }
+        }
+
+        if options.xboat_routes && !self.xboat_routes.is_empty() {
+            legend.push(LegendEntry::new(LegendSymbol::XboatRoute, "X-Boat Route"));
+        }
+        if options.routes && route.len() > 1 {
+            legend.push(LegendEntry::new(LegendSymbol::TravelRoute, "Travel Route"));
+        }
+
+        if options.polity_colors {
+            for (index, polity) in self.polities.iter().enumerate() {
+                if self.polity_member_count(index) == 0 {
+                    continue;
+                }
+                let color = &PolityColor::ALL_VALUES[index % PolityColor::ALL_VALUES.len()];
+                legend.push(LegendEntry::new(
+                    LegendSymbol::Polity(color.rgb()),
+                    &polity.name,
+                ));
+            }
+        }
+
+        legend
+    }
+}
+
+/** Draw `legend` in columns in the bottom margin of an exported map.
+
+Unless the map is `standalone`, symbols the template's own legend already explains are left out so
+they aren't listed twice.
+*/
+pub(super) fn process_legend_to_svg_elements<W: std::io::Write>(
+    writer: &mut quick_xml::Writer<W>,
+    legend: &[LegendEntry],
+    standalone: bool,
+) -> Result<(), MapRenderError> {
+    let entries = legend
+        .iter()
+        .filter(|entry| standalone || !entry.symbol.in_template_legend());
+    for (index, entry) in entries.enumerate() {
+        let (x0, y0) = if standalone {
+            STANDALONE_SVG_LEGEND_ORIGIN
+        } else {
+            SVG_LEGEND_ORIGIN
+        };
+        let x = x0 + (index / SVG_LEGEND_ROWS) as f64 * SVG_LEGEND_COLUMN_WIDTH;
+        let y = y0 + (index % SVG_LEGEND_ROWS) as f64 * SVG_LEGEND_ROW_HEIGHT;
+        let id = format!("LegendEntry{}", index);
+
+        let symbol = match &entry.symbol {
+            LegendSymbol::DryWorld | LegendSymbol::WetWorld => {
+                standalone_map::write_world_symbol(
+                    writer,
+                    Translation { x, y },
+                    entry.symbol == LegendSymbol::WetWorld,
+                    1.0,
+                    &format!("{}Symbol", id),
+                )?;
+                Ok(&mut *writer)
+            }
+            LegendSymbol::GasGiant => {
+                standalone_map::write_gas_giant_symbol(
+                    writer,
+                    Translation { x, y },
+                    1.0,
+                    &format!("{}Symbol", id),
+                )?;
+                Ok(&mut *writer)
+            }
+            LegendSymbol::Subtype(subtype) => {
+                standalone_map::write_subtype_symbol(
+                    writer,
+                    Translation { x, y },
+                    *subtype,
+                    0.5,
+                    &format!("{}Symbol", id),
+                )?;
+                Ok(&mut *writer)
+            }
+            LegendSymbol::StarportTl => writer
+                .create_element("text")
+                .with_attributes(vec![
+                    ("xml:space", "preserve"),
+                    ("class", STARPORT_TL_CLASS),
+                    ("x", &x.to_string()),
+                    ("y", &(y + 1.2).to_string()),
+                    ("id", &format!("{}Symbol", id)),
+                ])
+                .write_text_content(BytesText::new("B-6")),
+            LegendSymbol::Base(code) => writer
+                .create_element("text")
+                .with_attributes(vec![
+                    ("xml:space", "preserve"),
+                    ("class", STARPORT_TL_CLASS),
+                    ("x", &x.to_string()),
+                    ("y", &(y + 1.2).to_string()),
+                    ("id", &format!("{}Symbol", id)),
+                ])
+                .write_text_content(BytesText::new(code)),
+            LegendSymbol::AmberZone | LegendSymbol::RedZone => {
+                let class = match entry.symbol {
+                    LegendSymbol::AmberZone => AMBER_ZONE_CLASS,
+                    _ => RED_ZONE_CLASS,
+                };
+                writer
+                    .create_element("circle")
+                    .with_attributes(vec![
+                        ("class", class),
+                        ("cx", &x.to_string()),
+                        ("cy", &y.to_string()),
+                        ("r", "1.4"),
+                        ("id", &format!("{}Symbol", id)),
+                    ])
+                    .write_empty()
+            }
+            LegendSymbol::XboatRoute | LegendSymbol::TravelRoute => {
+                let class = match entry.symbol {
+                    LegendSymbol::XboatRoute => XBOAT_ROUTE_CLASS,
+                    _ => ROUTE_CLASS,
+                };
+                writer
+                    .create_element("line")
+                    .with_attributes(vec![
+                        ("class", class),
+                        ("x1", &(x - 2.0).to_string()),
+                        ("y1", &y.to_string()),
+                        ("x2", &(x + 2.0).to_string()),
+                        ("y2", &y.to_string()),
+                        ("id", &format!("{}Symbol", id)),
+                    ])
+                    .write_empty()
+            }
+            LegendSymbol::Polity([r, g, b]) => writer
+                .create_element("rect")
+                .with_attributes(vec![
+                    (
+                        "style",
+                        &format!(
+                            "fill:#{:02x}{:02x}{:02x};stroke:#000000;stroke-width:0.254",
+                            r, g, b
+                        )[..],
+                    ),
+                    ("x", &(x - 1.8).to_string()),
+                    ("y", &(y - 1.2).to_string()),
+                    ("width", "3.6"),
+                    ("height", "2.4"),
+                    ("id", &format!("{}Symbol", id)),
+                ])
+                .write_empty(),
+        };
+        symbol?;
+
+        writer
+            .create_element("text")
+            .with_attributes(vec![
+                ("xml:space", "preserve"),
+                ("class", "text-legend-label"),
+                ("style", "text-align:start;text-anchor:start"),
+                ("x", &(x + 3.5).to_string()),
+                ("y", &(y + 1.0).to_string()),
+                ("id", &format!("{}Label", id)),
+            ])
+            .write_text_content(BytesText::new(&entry.label))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::{Polity, World, TABLES};
+
+    #[test]
+    fn map_legend() {
+        let point = |x, y| Point { x, y };
+        let mut subsector = Subsector::empty();
+        let mut world = World::empty();
+        world.hydrographics = TABLES.hydro_table[0].clone();
+        world.has_naval_base = true;
+        world.travel_code = TravelCode::Amber;
+        world.allegiance = "ImDd".to_string();
+        subsector.insert_world(&point(1, 1), world).unwrap();
+        subsector.polities.push(Polity {
+            name: "Domain of Deneb".to_string(),
+            code: "ImDd".to_string(),
+            capital: point(1, 1),
+            summary: String::new(),
+        });
+
+        let options = MapOptions::default();
+        let symbols = |legend: Vec<LegendEntry>| -> Vec<LegendSymbol> {
+            legend.into_iter().map(|entry| entry.symbol).collect()
+        };
+        let color = PolityColor::ALL_VALUES[0].rgb();
+        assert_eq!(
+            symbols(subsector.map_legend(&options, &[])),
+            vec![
+                LegendSymbol::DryWorld,
+                LegendSymbol::StarportTl,
+                LegendSymbol::Base("N"),
+                LegendSymbol::AmberZone,
+                LegendSymbol::Polity(color),
+            ]
+        );
+
+        // Symbols of disabled layers are left out
+        let options = MapOptions {
+            bases: false,
+            polity_colors: false,
+            starport_tl: false,
+            travel_zones: false,
+            ..Default::default()
+        };
+        let route = [point(1, 1), point(2, 1)];
+        assert_eq!(
+            symbols(subsector.map_legend(&options, &route)),
+            vec![LegendSymbol::DryWorld, LegendSymbol::TravelRoute]
+        );
+
+        // Worlds that aren't mainworlds are marked whatever layers are drawn
+        let mut outpost = World::empty();
+        outpost.subtype = WorldSubtype::Outpost;
+        subsector.insert_world(&point(2, 1), outpost).unwrap();
+        assert_eq!(
+            symbols(subsector.map_legend(&options, &[])),
+            vec![
+                LegendSymbol::DryWorld,
+                LegendSymbol::Subtype(WorldSubtype::Outpost)
+            ]
+        );
+    }
+}
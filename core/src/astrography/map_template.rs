@@ -0,0 +1,833 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    error::Error,
+    fmt, str,
+};
+
+use quick_xml::events::{BytesStart, Event};
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::{HexOrientation, Point, Subsector, Translation, SUBSECTOR_TEMPLATE_SVG};
+
+/// Class of the lines, arrows, and circles of map annotations, colored by each annotation
+pub(super) const ANNOTATION_CLASS: &str = "annotation";
+/// Class of the notes written on the map as annotations
+pub(super) const ANNOTATION_TEXT_CLASS: &str = "text-annotation";
+/// Class of the circle drawn around amber zone worlds
+pub(super) const AMBER_ZONE_CLASS: &str = "travel-zone-amber";
+/// Class of the circle drawn around red zone worlds
+pub(super) const RED_ZONE_CLASS: &str = "travel-zone-red";
+/// Class of the lines drawn between the stops of a travel route
+pub(super) const ROUTE_CLASS: &str = "travel-route";
+/// Class of starport-TL and base code text, the same as in the built-in template
+pub(super) const STARPORT_TL_CLASS: &str = "text-starport-tl";
+/// Class of world name text, the same as in the built-in template
+pub(super) const WORLD_NAME_CLASS: &str = "text-world-name";
+/// Class of UWP text, the same as in the built-in template
+pub(super) const WORLD_PROFILE_CLASS: &str = "text-world-profile";
+/// Class of the dashed lines drawn between the hubs of the x-boat network
+pub(super) const XBOAT_ROUTE_CLASS: &str = "xboat-route";
+
+/** Ids of legend symbols that generated world elements are positioned relative to. */
+const LEGEND_SYMBOL_IDS: [&str; 3] = ["GasGiantCircle", "DryWorldSymbol", "WetWorldSymbol"];
+
+/** Ids of elements that generated world elements reference with `href`. */
+const REFERENCED_SYMBOL_IDS: [&str; 3] = ["GasGiantSymbol", "DryWorldSymbol", "WetWorldSymbol"];
+
+/** File format of an exported subsector map. */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MapFormat {
+    Png,
+    Svg,
+}
+
+impl MapFormat {
+    pub const ALL_VALUES: [MapFormat; 2] = [Self::Svg, Self::Png];
+
+    /** The file extension of the format. */
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Svg => "svg",
+        }
+    }
+}
+
+impl fmt::Display for MapFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Png => "PNG",
+            Self::Svg => "SVG",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** How an exported subsector map is arranged on the printed page. */
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum MapLayout {
+    /// The template's own page, as it was designed
+    #[default]
+    Portrait,
+    /// The template's page turned sideways, with the map scaled down to fit its height
+    Landscape,
+    /// The map at twice its size, tiled across four pages that are taped together after printing
+    Poster,
+    /// The map scaled down to fit on half of the template's page
+    Compact,
+}
+
+impl MapLayout {
+    pub const ALL_VALUES: [MapLayout; 4] =
+        [Self::Portrait, Self::Landscape, Self::Poster, Self::Compact];
+}
+
+impl fmt::Display for MapLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Portrait => "Portrait",
+            Self::Landscape => "Landscape",
+            Self::Poster => "Poster (2x2)",
+            Self::Compact => "Compact (Half Page)",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** How an exported subsector map is drawn. */
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum MapRenderer {
+    /// Filled into the `MapTemplate`, keeping everything the template draws itself
+    #[default]
+    Template,
+    /// Drawn entirely from scratch as plain SVG, without the template or any editor-specific
+    /// markup, so it shows the same in browsers and other vector tools
+    Standalone,
+}
+
+impl MapRenderer {
+    pub const ALL_VALUES: [MapRenderer; 2] = [Self::Template, Self::Standalone];
+}
+
+impl fmt::Display for MapRenderer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Template => "Map Template",
+            Self::Standalone => "Standalone SVG",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** Which labels are drawn next to each world on a subsector map.
+
+Dropping the names lets the UWP take their place at a larger size, which keeps dense maps legible
+when they are printed small.
+*/
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum WorldLabels {
+    #[default]
+    NameAndUwp,
+    NameOnly,
+    UwpOnly,
+}
+
+impl WorldLabels {
+    pub const ALL_VALUES: [WorldLabels; 3] = [Self::NameAndUwp, Self::NameOnly, Self::UwpOnly];
+
+    /** Whether world names are drawn. */
+    pub fn names(&self) -> bool {
+        *self != Self::UwpOnly
+    }
+
+    /** Whether world profiles are drawn. */
+    pub fn uwps(&self) -> bool {
+        *self != Self::NameOnly
+    }
+}
+
+impl fmt::Display for WorldLabels {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::NameAndUwp => "Name + UWP",
+            Self::NameOnly => "Name Only",
+            Self::UwpOnly => "UWP Only",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** Where the contents of a `MapTemplate` are drawn in a `MapLayout` and how they are paged.
+
+The template is scaled and moved onto a canvas the size of the whole layout, which is then cut into
+one or more pages.
+*/
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayoutTransform {
+    /// Width and height of the whole layout in the template's user units
+    pub canvas: [f64; 2],
+    /// Where the template's origin ends up on the canvas
+    pub offset: Translation,
+    /// Area of the canvas shown on each page as `[x, y, width, height]`, in reading order
+    pub pages: Vec<[f64; 4]>,
+    pub scale: f64,
+    /// Physical size of one user unit, in `unit`s
+    unit_size: f64,
+    /// Physical unit of the template's width and height, e.g. "in"
+    unit: String,
+}
+
+impl LayoutTransform {
+    /** Find where `translation` in the template's coordinates ends up on the canvas. */
+    pub fn apply(&self, translation: Translation) -> Translation {
+        Translation {
+            x: self.offset.x + self.scale * translation.x,
+            y: self.offset.y + self.scale * translation.y,
+        }
+    }
+
+    /** Get the printed size of `length` user units, e.g. "8.5in". */
+    pub fn physical_length(&self, length: f64) -> String {
+        let length = format!("{:.3}", length * self.unit_size);
+        let length = length.trim_end_matches('0').trim_end_matches('.');
+        format!("{}{}", length, self.unit)
+    }
+
+    /** The SVG `transform` attribute that moves the template's contents onto the canvas. */
+    pub fn svg_transform(&self) -> String {
+        format!(
+            "translate({} {}) scale({})",
+            self.offset.x, self.offset.y, self.scale
+        )
+    }
+}
+
+/** Size of the page of a template, parsed out of its root `svg` element. */
+#[derive(Clone, Debug, PartialEq)]
+struct PageGeometry {
+    /// The `viewBox` of the template as `[x, y, width, height]`
+    view_box: [f64; 4],
+    /// Physical width of the page in `unit`s
+    width: f64,
+    unit: String,
+}
+
+impl PageGeometry {
+    /** Work out where a drawing of the page is placed in `layout`, or `None` for
+    [`MapLayout::Portrait`].
+    */
+    fn layout_transform(&self, layout: MapLayout) -> Option<LayoutTransform> {
+        let [x, y, width, height] = self.view_box;
+        let (canvas, scale) = match layout {
+            MapLayout::Portrait => return None,
+            MapLayout::Landscape => ([height, width], (height / width).min(width / height)),
+            MapLayout::Poster => ([2.0 * width, 2.0 * height], 2.0),
+            MapLayout::Compact => (
+                [height / 2.0, width],
+                (height / 2.0 / width).min(width / height),
+            ),
+        };
+
+        let pages = match layout {
+            MapLayout::Poster => (0..4)
+                .map(|i| {
+                    let column = (i % 2) as f64;
+                    let row = (i / 2) as f64;
+                    [column * width, row * height, width, height]
+                })
+                .collect(),
+            _ => vec![[0.0, 0.0, canvas[0], canvas[1]]],
+        };
+
+        // Center the scaled page on the canvas
+        let offset = Translation {
+            x: (canvas[0] - width * scale) / 2.0 - x * scale,
+            y: (canvas[1] - height * scale) / 2.0 - y * scale,
+        };
+
+        Some(LayoutTransform {
+            canvas,
+            offset,
+            pages,
+            scale,
+            unit_size: self.width / width,
+            unit: self.unit.clone(),
+        })
+    }
+
+    /** Try to read the page size of a template from the attributes of its root `svg` element.
+
+    # Returns
+    `None` unless the element has a `viewBox` and a `width` with a physical unit.
+    */
+    fn from_attributes(attributes: &BTreeMap<String, String>) -> Option<Self> {
+        let view_box: Vec<f64> = attributes
+            .get("viewBox")?
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse().ok())
+            .collect::<Option<_>>()?;
+        let view_box: [f64; 4] = view_box.try_into().ok()?;
+        if view_box[2] <= 0.0 || view_box[3] <= 0.0 {
+            return None;
+        }
+
+        let width = attributes.get("width")?.trim();
+        let unit_start = width
+            .find(|c: char| c.is_ascii_alphabetic() || c == '%')
+            .unwrap_or(width.len());
+        let (width, unit) = width.split_at(unit_start);
+        if unit == "%" {
+            return None;
+        }
+
+        Some(Self {
+            view_box,
+            width: width.parse().ok()?,
+            unit: unit.to_string(),
+        })
+    }
+}
+
+/** Problem that kept a subsector map from being parsed or drawn. */
+#[derive(Clone, Debug, PartialEq)]
+pub enum MapRenderError {
+    /// The map template couldn't be read or written as XML
+    Xml(String),
+    /// The map template is missing an element the map needs, described by the message
+    InvalidTemplate(String),
+    /// A world, route stop, or x-boat hub is at a hex with no center marker to place it by
+    MissingCenterMarker(Point),
+    /// The map template is missing a legend symbol that world symbols are placed relative to
+    MissingLegendSymbol(String),
+}
+
+impl fmt::Display for MapRenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Xml(message) => write!(f, "Invalid SVG: {}", message),
+            Self::InvalidTemplate(message) => write!(f, "Invalid map template: {}", message),
+            Self::MissingCenterMarker(point) => write!(f, "Could not find CenterMark-{}", point),
+            Self::MissingLegendSymbol(id) => {
+                write!(f, "Could not find required legend symbol {}", id)
+            }
+        }
+    }
+}
+
+impl Error for MapRenderError {}
+
+impl From<quick_xml::Error> for MapRenderError {
+    fn from(e: quick_xml::Error) -> Self {
+        Self::Xml(e.to_string())
+    }
+}
+
+/** Which layers are drawn on an exported subsector map, and how. */
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct MapOptions {
+    /// Lines, arrows, circles, and notes drawn over the map
+    pub annotations: bool,
+    /// Base codes of each world, e.g. "NS"
+    pub bases: bool,
+    /// Column and row numbers around the edges of the hex grid
+    pub grid_headers: bool,
+    /// Hex numbers printed in the template, e.g. "0101"
+    pub hex_numbers: bool,
+    /// Which way up the hexes are drawn
+    pub hex_orientation: HexOrientation,
+    /// Arrangement of the map on the printed page
+    pub layout: MapLayout,
+    /// Key to the symbols drawn by the other layers, in the bottom margin
+    pub legend: bool,
+    /// Hexes filled with the color of the polity they belong to
+    pub polity_colors: bool,
+    /// Whether the map is filled into the template or drawn from scratch
+    pub renderer: MapRenderer,
+    /// Lines between the stops of the planned travel route
+    pub routes: bool,
+    /// Starport class and tech level of each world, e.g. "B-12"
+    pub starport_tl: bool,
+    /// Fonts, sizes, and line weights of what is drawn
+    pub style: MapStyle,
+    /// Circles around amber and red zone worlds
+    pub travel_zones: bool,
+    /// Universal world profile of each world, drawn in place of the name if names are off
+    pub uwps: bool,
+    pub world_names: bool,
+    /// Dashed lines between the hubs of the x-boat network
+    pub xboat_routes: bool,
+}
+
+impl Default for MapOptions {
+    fn default() -> Self {
+        Self {
+            annotations: true,
+            bases: true,
+            grid_headers: false,
+            hex_numbers: true,
+            hex_orientation: HexOrientation::default(),
+            layout: MapLayout::default(),
+            legend: false,
+            polity_colors: true,
+            renderer: MapRenderer::default(),
+            routes: true,
+            starport_tl: true,
+            style: MapStyle::default(),
+            travel_zones: true,
+            uwps: true,
+            world_names: true,
+            xboat_routes: true,
+        }
+    }
+}
+
+impl MapOptions {
+    /** Get what the map is actually drawn with.
+
+    A template can only fill in its own hex grid, so maps with grid headers or pointy-topped hexes
+    are drawn from scratch whichever renderer was picked.
+    */
+    pub fn effective_renderer(&self) -> MapRenderer {
+        if self.grid_headers || self.hex_orientation != HexOrientation::FlatTop {
+            MapRenderer::Standalone
+        } else {
+            self.renderer
+        }
+    }
+
+    /** The world labels drawn by the `world_names` and `uwps` layers; `None` if neither is. */
+    pub fn world_labels(&self) -> Option<WorldLabels> {
+        match (self.world_names, self.uwps) {
+            (true, true) => Some(WorldLabels::NameAndUwp),
+            (true, false) => Some(WorldLabels::NameOnly),
+            (false, true) => Some(WorldLabels::UwpOnly),
+            (false, false) => None,
+        }
+    }
+
+    /** Turn the `world_names` and `uwps` layers on or off to draw `labels`. */
+    pub fn set_world_labels(&mut self, labels: WorldLabels) {
+        self.world_names = labels.names();
+        self.uwps = labels.uwps();
+    }
+}
+
+/** Fonts, sizes, and line weights of the text and symbols drawn on an exported subsector map.
+
+Sizes are in the map's user units, which are millimeters on the built-in template. The style is
+written into the map as CSS classes, which take precedence over the template's own classes of the
+same names.
+*/
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct MapStyle {
+    /// CSS font family of world names, UWPs, and starport-TL and base codes, e.g. "Georgia, serif"
+    pub font_family: String,
+    /// Font size of starport-TL and base codes
+    pub label_font_size: f64,
+    pub name_font_size: f64,
+    /// Width of the dashed lines of x-boat routes and the line of the travel route
+    pub route_line_weight: f64,
+    /// Size of world and gas giant symbols relative to their size in the template
+    pub symbol_scale: f64,
+    pub uwp_font_size: f64,
+    /// Width of the circles around amber and red zone worlds
+    pub zone_line_weight: f64,
+}
+
+impl MapStyle {
+    /// Smallest allowed font size
+    pub const MIN_FONT_SIZE: f64 = 0.5;
+    /// Largest allowed font size
+    pub const MAX_FONT_SIZE: f64 = 10.0;
+    /// Smallest allowed `MapStyle::symbol_scale`
+    pub const MIN_SYMBOL_SCALE: f64 = 0.25;
+    /// Largest allowed `MapStyle::symbol_scale`
+    pub const MAX_SYMBOL_SCALE: f64 = 3.0;
+    /// Smallest allowed line weight
+    pub const MIN_LINE_WEIGHT: f64 = 0.05;
+    /// Largest allowed line weight
+    pub const MAX_LINE_WEIGHT: f64 = 3.0;
+
+    /** Get the CSS rules of the classes of generated map elements in this style. */
+    pub(super) fn css(&self) -> String {
+        // Anything that could end the declaration or rule early is dropped from the font family
+        let font_family: String = self
+            .font_family
+            .chars()
+            .filter(|c| !matches!(c, ';' | '{' | '}' | '<' | '>'))
+            .collect();
+        let font_family = match font_family.trim() {
+            "" => "sans-serif",
+            font_family => font_family,
+        };
+        let font_rule = |class: &str, size: f64| {
+            format!(
+                "    .{} {{ font-family: {}; font-size: {}px; }}\n",
+                class,
+                font_family,
+                size.clamp(Self::MIN_FONT_SIZE, Self::MAX_FONT_SIZE)
+            )
+        };
+        let zone_weight = self
+            .zone_line_weight
+            .clamp(Self::MIN_LINE_WEIGHT, Self::MAX_LINE_WEIGHT);
+        let route_weight = self
+            .route_line_weight
+            .clamp(Self::MIN_LINE_WEIGHT, Self::MAX_LINE_WEIGHT);
+
+        let mut css = String::from("\n");
+        css += &font_rule(WORLD_NAME_CLASS, self.name_font_size);
+        css += &font_rule(WORLD_PROFILE_CLASS, self.uwp_font_size);
+        css += &font_rule(STARPORT_TL_CLASS, self.label_font_size);
+        css += &font_rule(ANNOTATION_TEXT_CLASS, self.name_font_size);
+        css += &format!(
+            "    .{} {{ fill: none; stroke: #ffbf00; stroke-width: {}; stroke-dasharray: 1.5,1; \
+            }}\n",
+            AMBER_ZONE_CLASS, zone_weight
+        );
+        css += &format!(
+            "    .{} {{ fill: none; stroke: #d40000; stroke-width: {}; }}\n",
+            RED_ZONE_CLASS, zone_weight
+        );
+        css += &format!(
+            "    .{} {{ fill: none; stroke: #2a7fff; stroke-width: {}; stroke-linecap: round; }}\n",
+            ROUTE_CLASS, route_weight
+        );
+        css += &format!(
+            "    .{} {{ fill: none; stroke: #a05a2c; stroke-width: {}; stroke-dasharray: 2,1.5; \
+            stroke-linecap: round; }}\n",
+            XBOAT_ROUTE_CLASS, route_weight
+        );
+        css += &format!(
+            "    .{} {{ text-anchor: middle; dominant-baseline: middle; }}\n",
+            ANNOTATION_TEXT_CLASS
+        );
+        css += &format!(
+            "    .{} {{ fill: none; stroke-width: {}; stroke-linecap: round; \
+            stroke-linejoin: round; }}\n",
+            ANNOTATION_CLASS, route_weight
+        );
+        css
+    }
+
+    /** `MapStyle::symbol_scale` kept within its allowed range. */
+    pub(super) fn clamped_symbol_scale(&self) -> f64 {
+        self.symbol_scale
+            .clamp(Self::MIN_SYMBOL_SCALE, Self::MAX_SYMBOL_SCALE)
+    }
+}
+
+impl Default for MapStyle {
+    fn default() -> Self {
+        Self {
+            font_family: "sans-serif".to_string(),
+            label_font_size: 3.52777,
+            name_font_size: 3.52777,
+            route_line_weight: 0.6,
+            symbol_scale: 1.0,
+            uwp_font_size: 2.8,
+            zone_line_weight: 0.5,
+        }
+    }
+}
+
+/** An SVG subsector map template along with the positioning data parsed out of it.
+
+The built-in template is used by default, but any SVG can be used as long as it contains:
+- A `circle` with id `CenterMark-XXYY` for every hex in the subsector, optionally nested in a `g`
+  with id `CenterMarkerColumn-X` whose `transform` translates the whole column
+- Legend symbols with ids `GasGiantCircle`, `DryWorldSymbol`, and `WetWorldSymbol`, each with `cx`
+  and `cy` attributes
+- An element with id `GasGiantSymbol`, which is copied into every hex with a gas giant
+
+Everything else in the template (fonts, colors, logos, etc.) is passed through to the exported map
+as-is. If the template contains a text element that reads "Subsector Name", it is replaced with the
+name of the subsector.
+
+Layouts other than [`MapLayout::Portrait`] need the root `svg` to have a `viewBox` and a `width`
+in physical units; templates without them are always exported as designed.
+*/
+#[derive(Clone, Debug)]
+pub struct MapTemplate {
+    pub center_markers: BTreeMap<Point, Translation>,
+    pub dry_world_trans: Translation,
+    pub gas_giant_trans: Translation,
+    page: Option<PageGeometry>,
+    svg: String,
+    pub wet_world_trans: Translation,
+}
+
+impl MapTemplate {
+    pub fn svg(&self) -> &str {
+        &self.svg
+    }
+
+    /** Work out where the template is drawn in `layout`.
+
+    # Returns
+    - `Some(LayoutTransform)` if `layout` moves or pages the template
+    - `None` if the template is drawn as designed, either because `layout` is
+      [`MapLayout::Portrait`] or because the template's page size is unknown
+    */
+    pub fn layout_transform(&self, layout: MapLayout) -> Option<LayoutTransform> {
+        self.page.as_ref()?.layout_transform(layout)
+    }
+
+    /** Try to parse and validate `svg` as a subsector map template.
+
+    # Returns
+    - `Ok(MapTemplate)` if `svg` is valid XML containing all of the required elements
+    - `Err` describing the first problem found otherwise
+    */
+    pub fn try_from_svg(svg: String) -> Result<Self, Box<dyn Error>> {
+        let mut reader = quick_xml::Reader::from_str(&svg);
+        let mut column_translations: [Option<Translation>; Subsector::COLUMNS] =
+            [None; Subsector::COLUMNS];
+        let mut circle_translations: BTreeMap<Point, Translation> = BTreeMap::new();
+        let mut legend_translations: BTreeMap<String, Translation> = BTreeMap::new();
+        let mut ids = BTreeSet::new();
+        let mut page = None;
+
+        loop {
+            let element = match reader.read_event() {
+                Err(e) => {
+                    return Err(format!(
+                        "Error parsing template at position {}: {}",
+                        reader.buffer_position(),
+                        e
+                    )
+                    .into())
+                }
+                Ok(Event::Eof) => break,
+                Ok(Event::Start(element)) | Ok(Event::Empty(element)) => element,
+                _ => continue,
+            };
+
+            let attributes = attribute_map(&element)?;
+            if element.name().as_ref() == b"svg" && page.is_none() {
+                page = PageGeometry::from_attributes(&attributes);
+            }
+            let id = match attributes.get("id") {
+                Some(id) => id,
+                None => continue,
+            };
+            ids.insert(id.clone());
+
+            if let Some(column_num) = id.strip_prefix("CenterMarkerColumn-") {
+                // If the element is a center marker column, get the column offset
+                let column_num: usize = column_num
+                    .parse()
+                    .map_err(|_| format!("Unparsable column number in {id}"))?;
+                if !(1..=Subsector::COLUMNS).contains(&column_num) {
+                    return Err(format!("Out of bounds column number while parsing {id}").into());
+                }
+
+                let column_idx = column_num - 1;
+                if column_translations[column_idx].is_some() {
+                    return Err(
+                        format!("Found double definition of CenterMarkerColumn {id}").into(),
+                    );
+                }
+
+                column_translations[column_idx] = match attributes.get("transform") {
+                    Some(transform) => Some(Translation::try_from_transform_str(transform)?),
+                    None => Some(Translation::default()),
+                };
+            } else if let Some(point_str) = id.strip_prefix("CenterMark-") {
+                // If the element is a center mark circle itself, get the center coordinates
+                let point = Point::try_from(point_str)
+                    .map_err(|_| format!("Unparsable point in CenterMark {id}"))?;
+                if circle_translations.contains_key(&point) {
+                    return Err(format!("Found double definition of CenterMark {id}").into());
+                }
+                if !Subsector::point_is_inbounds(&point) {
+                    return Err(format!("Found out-of-bounds CenterMark {id}").into());
+                }
+
+                circle_translations.insert(point, center_translation(id, &attributes)?);
+            } else if LEGEND_SYMBOL_IDS.contains(&&id[..]) {
+                legend_translations.insert(id.clone(), center_translation(id, &attributes)?);
+            }
+        }
+
+        let center_markers = center_markers(&column_translations, &circle_translations)?;
+
+        for id in REFERENCED_SYMBOL_IDS {
+            if !ids.contains(id) {
+                return Err(format!("Could not find required symbol {id}").into());
+            }
+        }
+
+        Ok(Self {
+            center_markers,
+            dry_world_trans: map_legend_translation(&legend_translations, "DryWorldSymbol")?,
+            gas_giant_trans: map_legend_translation(&legend_translations, "GasGiantCircle")?,
+            page,
+            svg,
+            wet_world_trans: map_legend_translation(&legend_translations, "WetWorldSymbol")?,
+        })
+    }
+}
+
+impl Default for MapTemplate {
+    fn default() -> Self {
+        Self::try_from_svg(SUBSECTOR_TEMPLATE_SVG.to_string())
+            .expect("Built-in subsector map template should be valid")
+    }
+}
+
+/** Work out where a drawing of a page `width` by `height` `unit`s is placed in `layout`, or `None`
+for [`MapLayout::Portrait`].
+*/
+pub(super) fn page_layout_transform(
+    width: f64,
+    height: f64,
+    unit: &str,
+    layout: MapLayout,
+) -> Option<LayoutTransform> {
+    PageGeometry {
+        view_box: [0.0, 0.0, width, height],
+        width,
+        unit: unit.to_string(),
+    }
+    .layout_transform(layout)
+}
+
+/** Work out the center of every hex from the template's center mark circles, each moved by the
+translation of the column group it is in.
+
+# Returns
+- `Ok(BTreeMap)` with the center of every in-bounds hex
+- `Err(MapRenderError::MissingCenterMarker)` with the first hex that has no center mark
+*/
+fn center_markers(
+    column_translations: &[Option<Translation>],
+    circle_translations: &BTreeMap<Point, Translation>,
+) -> Result<BTreeMap<Point, Translation>, MapRenderError> {
+    let mut center_markers = BTreeMap::new();
+    for x in 1..=Subsector::COLUMNS {
+        let column_translation = column_translations
+            .get(x - 1)
+            .copied()
+            .flatten()
+            .unwrap_or_default();
+        for y in 1..=Subsector::ROWS {
+            let point = Point {
+                x: x as i32,
+                y: y as i32,
+            };
+
+            let center_mark = *circle_translations
+                .get(&point)
+                .ok_or(MapRenderError::MissingCenterMarker(point))?
+                + column_translation;
+            center_markers.insert(point, center_mark);
+        }
+    }
+    Ok(center_markers)
+}
+
+/** Get the center of the legend symbol `id` that generated world symbols are placed relative to. */
+fn map_legend_translation(
+    legend_translations: &BTreeMap<String, Translation>,
+    id: &str,
+) -> Result<Translation, MapRenderError> {
+    legend_translations
+        .get(id)
+        .copied()
+        .ok_or_else(|| MapRenderError::MissingLegendSymbol(id.to_string()))
+}
+
+fn attribute_map(element: &BytesStart) -> Result<BTreeMap<String, String>, Box<dyn Error>> {
+    let mut attributes = BTreeMap::new();
+    for attribute in element.attributes() {
+        let attribute = attribute?;
+        attributes.insert(
+            str::from_utf8(attribute.key.as_ref())?.to_string(),
+            str::from_utf8(attribute.value.as_ref())?.to_string(),
+        );
+    }
+    Ok(attributes)
+}
+
+fn center_translation(
+    id: &str,
+    attributes: &BTreeMap<String, String>,
+) -> Result<Translation, Box<dyn Error>> {
+    let x = attributes
+        .get("cx")
+        .ok_or_else(|| format!("Could not find cx attr while parsing {id}"))?
+        .parse()
+        .map_err(|_| format!("Unparsable cx attr in {id}"))?;
+    let y = attributes
+        .get("cy")
+        .ok_or_else(|| format!("Could not find cy attr while parsing {id}"))?
+        .parse()
+        .map_err(|_| format!("Unparsable cy attr in {id}"))?;
+    Ok(Translation { x, y })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_template_is_valid() {
+        let template = MapTemplate::default();
+        assert_eq!(
+            template.center_markers.len(),
+            Subsector::COLUMNS * Subsector::ROWS
+        );
+    }
+
+    #[test]
+    fn template_missing_required_ids() {
+        let svg = SUBSECTOR_TEMPLATE_SVG.replace("id=\"CenterMark-0504\"", "id=\"Removed\"");
+        let e = MapTemplate::try_from_svg(svg).unwrap_err();
+        assert_eq!(
+            e.downcast_ref::<MapRenderError>(),
+            Some(&MapRenderError::MissingCenterMarker(Point { x: 5, y: 4 }))
+        );
+
+        let svg = SUBSECTOR_TEMPLATE_SVG.replace("id=\"WetWorldSymbol\"", "id=\"Removed\"");
+        assert!(MapTemplate::try_from_svg(svg).is_err());
+
+        let svg = SUBSECTOR_TEMPLATE_SVG.replace("id=\"GasGiantSymbol\"", "id=\"Removed\"");
+        assert!(MapTemplate::try_from_svg(svg).is_err());
+
+        assert!(MapTemplate::try_from_svg("<svg><g></svg>".to_string()).is_err());
+    }
+
+    #[test]
+    fn layout_transforms() {
+        let template = MapTemplate::default();
+        assert_eq!(template.layout_transform(MapLayout::Portrait), None);
+
+        let landscape = template.layout_transform(MapLayout::Landscape).unwrap();
+        assert!(landscape.canvas[0] > landscape.canvas[1]);
+        assert_eq!(landscape.physical_length(landscape.canvas[0]), "11in");
+        assert_eq!(landscape.physical_length(landscape.canvas[1]), "8.5in");
+
+        let poster = template.layout_transform(MapLayout::Poster).unwrap();
+        assert_eq!(poster.pages.len(), 4);
+        assert_eq!(poster.physical_length(poster.pages[3][2]), "8.5in");
+
+        let compact = template.layout_transform(MapLayout::Compact).unwrap();
+        assert_eq!(compact.physical_length(compact.canvas[0]), "5.5in");
+
+        for transform in [landscape, poster, compact] {
+            for center in template.center_markers.values() {
+                let moved = transform.apply(*center);
+                assert!((0.0..=transform.canvas[0]).contains(&moved.x));
+                assert!((0.0..=transform.canvas[1]).contains(&moved.y));
+            }
+        }
+
+        let svg = SUBSECTOR_TEMPLATE_SVG.replace("viewBox=\"0 0 215.9 279.4\"", "");
+        let template = MapTemplate::try_from_svg(svg).unwrap();
+        assert_eq!(template.layout_transform(MapLayout::Landscape), None);
+    }
+}
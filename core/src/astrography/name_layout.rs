@@ -0,0 +1,181 @@
+use quick_xml::events::BytesText;
+
+use crate::astrography::{map_template::WORLD_NAME_CLASS, MapRenderError, Translation};
+
+/// Most characters of a name that fit across a hex at the full name font size
+const FULL_SIZE_CHARS: usize = 12;
+/// Smallest fraction of the full font size a name is shrunk to before it's wrapped or cut short
+const MIN_SCALE: f64 = 0.75;
+/// Ellipsis ending a name that was cut short
+const ELLIPSIS: char = '…';
+
+/** How a `World`'s name is laid out to fit across its hex on the map.
+
+Maps don't know the widths of the glyphs they're drawn with, so names are fit by how many
+characters they have: short names are drawn as they are, slightly long ones are shrunk, longer ones
+are wrapped onto two lines at a space or hyphen, and any that still don't fit are cut short with an
+ellipsis.
+*/
+#[derive(Clone, Debug, PartialEq)]
+pub struct NameLayout {
+    /// One or two lines the name is drawn on, top to bottom
+    pub lines: Vec<String>,
+    /// Fraction of the full name font size the lines are drawn at
+    pub scale: f64,
+}
+
+impl NameLayout {
+    pub fn fit(name: &str) -> Self {
+        let max_chars = Self::max_chars();
+        let len = name.chars().count();
+        if len <= max_chars {
+            return Self::shrunk_to_fit(vec![name.to_string()]);
+        }
+        if let Some(lines) = Self::wrap(name, max_chars) {
+            return Self::shrunk_to_fit(lines);
+        }
+
+        let mut abbreviated: String = name.chars().take(max_chars - 1).collect();
+        abbreviated.truncate(abbreviated.trim_end().len());
+        abbreviated.push(ELLIPSIS);
+        Self::shrunk_to_fit(vec![abbreviated])
+    }
+
+    /** Whether the name is drawn as it is, on one line at the full font size. */
+    pub fn is_unchanged(&self) -> bool {
+        self.lines.len() == 1 && self.scale == 1.0
+    }
+
+    /** Most characters that fit on a line at the smallest font size. */
+    fn max_chars() -> usize {
+        (FULL_SIZE_CHARS as f64 / MIN_SCALE) as usize
+    }
+
+    fn shrunk_to_fit(lines: Vec<String>) -> Self {
+        let longest = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or_default();
+        let scale = (FULL_SIZE_CHARS as f64 / longest.max(1) as f64).min(1.0);
+        Self { lines, scale }
+    }
+
+    /** Split `name` in two at the space or hyphen that leaves the longer line shortest, keeping
+    hyphens on the first line, if both lines have no more than `max_chars` characters.
+    */
+    fn wrap(name: &str, max_chars: usize) -> Option<Vec<String>> {
+        name.char_indices()
+            .filter_map(|(index, c)| match c {
+                ' ' => Some((&name[..index], &name[index + 1..])),
+                '-' => Some((&name[..index + 1], &name[index + 1..])),
+                _ => None,
+            })
+            .map(|(first, second)| (first.trim_end(), second.trim_start()))
+            .filter(|(first, second)| !first.is_empty() && !second.is_empty())
+            .min_by_key(|(first, second)| first.chars().count().max(second.chars().count()))
+            .filter(|(first, second)| {
+                first.chars().count() <= max_chars && second.chars().count() <= max_chars
+            })
+            .map(|(first, second)| vec![first.to_string(), second.to_string()])
+    }
+}
+
+/** Write `name` as a world name text element centered on `translation`, fit to its hex.
+
+Names that fit as they are are written as plain text. Otherwise each line is a `tspan` sized in
+`em`s relative to the name font size of the map's style.
+*/
+pub(super) fn write_world_name<W: std::io::Write>(
+    writer: &mut quick_xml::Writer<W>,
+    translation: Translation,
+    id: &str,
+    name: &str,
+) -> Result<(), MapRenderError> {
+    let x = translation.x.to_string();
+    let element = writer.create_element("text").with_attributes(vec![
+        ("xml:space", "preserve"),
+        ("class", WORLD_NAME_CLASS),
+        ("x", &x[..]),
+        ("y", &translation.y.to_string()),
+        ("id", id),
+    ]);
+
+    let layout = NameLayout::fit(name);
+    if layout.is_unchanged() {
+        element.write_text_content(BytesText::new(name))?;
+        return Ok(());
+    }
+
+    let font_size = format!("{}em", layout.scale);
+    // Lines are centered on the baseline a single line would have been drawn on
+    let first_dy = format!("{}em", (1.0 - layout.lines.len() as f64) / 2.0);
+    element.write_inner_content(|writer| {
+        for (index, line) in layout.lines.iter().enumerate() {
+            let dy = if index == 0 { &first_dy[..] } else { "1em" };
+            writer
+                .create_element("tspan")
+                .with_attributes(vec![
+                    ("x", &x[..]),
+                    ("dy", dy),
+                    ("font-size", &font_size[..]),
+                ])
+                .write_text_content(BytesText::new(line))?;
+        }
+        Ok(())
+    })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit() {
+        let layout = NameLayout::fit("Regina");
+        assert!(layout.is_unchanged());
+        assert_eq!(layout.lines, ["Regina"]);
+
+        // Slightly long names are only shrunk
+        let layout = NameLayout::fit("Shionthy Prime");
+        assert_eq!(layout.lines, ["Shionthy Prime"]);
+        assert!(layout.scale < 1.0 && layout.scale >= MIN_SCALE);
+
+        // Longer ones are wrapped at the space or hyphen nearest their middle
+        let layout = NameLayout::fit("New Hope of the Marches");
+        assert_eq!(layout.lines, ["New Hope of", "the Marches"]);
+        assert_eq!(layout.scale, 1.0);
+        let layout = NameLayout::fit("Kaggushus-Ashmakhaga");
+        assert_eq!(layout.lines, ["Kaggushus-", "Ashmakhaga"]);
+
+        // Names that can't be wrapped are cut short
+        let layout = NameLayout::fit("Iiramulumaashukaagir");
+        assert_eq!(layout.lines, ["Iiramulumaashuk…"]);
+        assert_eq!(layout.scale, MIN_SCALE);
+
+        let layout = NameLayout::fit("");
+        assert_eq!(layout.lines, [""]);
+        assert_eq!(layout.scale, 1.0);
+    }
+
+    #[test]
+    fn write_world_name() {
+        let mut writer = quick_xml::Writer::new(Vec::new());
+        let translation = Translation { x: 10.0, y: 20.0 };
+        super::write_world_name(&mut writer, translation, "0101NameText", "Regina").unwrap();
+        super::write_world_name(
+            &mut writer,
+            translation,
+            "0102NameText",
+            "New Hope of the Marches",
+        )
+        .unwrap();
+        let svg = String::from_utf8(writer.into_inner()).unwrap();
+
+        assert!(svg.contains("id=\"0101NameText\">Regina</text>"));
+        let first = "<tspan x=\"10\" dy=\"-0.5em\" font-size=\"1em\">New Hope of</tspan>";
+        let second = "<tspan x=\"10\" dy=\"1em\" font-size=\"1em\">the Marches</tspan>";
+        assert!(svg.contains(&format!("{}{}", first, second)));
+    }
+}
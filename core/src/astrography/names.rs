@@ -0,0 +1,797 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    astrography::{Point, Subsector},
+    dice,
+};
+
+/// Roots of classical names, reminiscent of Latin and Greek constellations and provinces
+const CLASSICAL_ROOTS: [&str; 24] = [
+    "Aquil", "Aur", "Cass", "Cor", "Delph", "Drac", "Heli", "Hyper", "Lyr", "Magn", "Nov", "Orph",
+    "Pell", "Reg", "Sabin", "Ser", "Sol", "Tauri", "Theb", "Val", "Vel", "Ves", "Vind", "Xanth",
+];
+
+/// Endings of classical names
+const CLASSICAL_ENDINGS: [&str; 16] = [
+    "a", "ae", "ania", "ara", "eia", "elia", "ene", "ia", "ica", "idon", "ina", "ion", "is", "on",
+    "onia", "us",
+];
+
+/// Places and people that colonists name their new homes after
+const COLONIAL_NAMES: [&str; 24] = [
+    "Albion",
+    "Avalon",
+    "Bristol",
+    "Caledonia",
+    "Carver",
+    "Cortez",
+    "Dalton",
+    "Drake",
+    "Eden",
+    "Esperanza",
+    "Hadley",
+    "Harmony",
+    "Hudson",
+    "Kepler",
+    "Lisbon",
+    "Magellan",
+    "Marlowe",
+    "Providence",
+    "Sinclair",
+    "Tasman",
+    "Vance",
+    "Verity",
+    "Winslow",
+    "York",
+];
+
+/// Patterns of colonial names; `{}` is replaced with one of `COLONIAL_NAMES`
+const COLONIAL_PATTERNS: [&str; 10] = [
+    "{}",
+    "New {}",
+    "Port {}",
+    "{}'s Reach",
+    "{}'s Landing",
+    "{} Marches",
+    "{} Frontier",
+    "{} Expanse",
+    "Greater {}",
+    "{} Drift",
+];
+
+/// Consonant clusters that start syllables of alien names
+const ALIEN_ONSETS: [&str; 18] = [
+    "g", "gh", "k", "kh", "kr", "q", "r", "sk", "sr", "t", "th", "tl", "ts", "v", "vr", "x", "z",
+    "zh",
+];
+
+/// Vowels of syllables of alien names
+const ALIEN_VOWELS: [&str; 9] = ["a", "aa", "e", "i", "ii", "o", "u", "uu", "y"];
+
+/// Consonant clusters that end syllables of alien names; empty for open syllables
+const ALIEN_CODAS: [&str; 10] = ["", "", "", "k", "n", "r", "rr", "ss", "th", "x"];
+
+/// Syllable components random world names are built from, in the order they're indexed by
+/// `NAME_PATTERNS`: consonants, vowels, consonant clusters, vowel clusters, consonant-led endings,
+/// and vowel-led endings
+const NAME_COMPONENTS: [&[&str]; 6] = [
+    &[
+        "b", "c", "d", "f", "g", "h", "i", "j", "k", "l", "m", "n", "p", "q", "r", "s", "t", "v",
+        "w", "x", "y", "z",
+    ],
+    &["a", "e", "o", "u"],
+    &[
+        "br", "cr", "dr", "fr", "gr", "pr", "str", "tr", "bl", "cl", "fl", "gl", "pl", "sl", "sc",
+        "sk", "sm", "sn", "sp", "st", "sw", "ch", "sh", "th", "wh",
+    ],
+    &[
+        "ae", "ai", "ao", "au", "a", "ay", "ea", "ei", "eo", "eu", "e", "ey", "ua", "ue", "ui",
+        "uo", "u", "uy", "ia", "ie", "iu", "io", "iy", "oa", "oe", "ou", "oi", "o", "oy",
+    ],
+    &[
+        "turn", "ter", "nus", "rus", "tania", "hiri", "hines", "gawa", "nides", "carro", "rilia",
+        "stea", "lia", "lea", "ria", "nov", "phus", "mia", "nerth", "wei", "ruta", "tov", "zuno",
+        "vis", "lara", "nia", "liv", "tera", "gantu", "yama", "tune", "ter", "nus", "cury", "bos",
+        "pra", "thea", "nope", "tis", "clite",
+    ],
+    &[
+        "una", "ion", "iea", "iri", "illes", "ides", "agua", "olla", "inda", "eshan", "oria",
+        "ilia", "erth", "arth", "orth", "oth", "illon", "ichi", "ov", "arvis", "ara", "ars", "yke",
+        "yria", "onoe", "ippe", "osie", "one", "ore", "ade", "adus", "urn", "ypso", "ora", "iuq",
+        "orix", "apus", "ion", "eon", "eron", "ao", "omia",
+    ],
+];
+
+/// Sequences of one-based `NAME_COMPONENTS` indices that random world names follow in turn
+const NAME_PATTERNS: [&[usize]; 10] = [
+    &[1, 2, 5],
+    &[2, 3, 6],
+    &[3, 4, 5],
+    &[4, 3, 6],
+    &[3, 4, 2, 5],
+    &[2, 1, 3, 6],
+    &[3, 4, 2, 5],
+    &[4, 3, 1, 6],
+    &[3, 4, 1, 4, 5],
+    &[4, 1, 4, 3, 6],
+];
+
+/// Consonant pairs spelled as a single sound, along with how that sound is written phonetically
+const CONSONANT_DIGRAPHS: [(&str, &str); 10] = [
+    ("ch", "ch"),
+    ("ck", "k"),
+    ("gh", "g"),
+    ("kh", "kh"),
+    ("ph", "f"),
+    ("qu", "kw"),
+    ("sh", "sh"),
+    ("th", "th"),
+    ("wh", "w"),
+    ("zh", "zh"),
+];
+
+/// Vowel pairs pronounced as a single syllable, along with how they are written phonetically;
+/// other pairs of vowels, like the "ia" of "Tania", are pronounced as two syllables
+const VOWEL_DIGRAPHS: [(&str, &str); 16] = [
+    ("aa", "ah"),
+    ("ae", "ay"),
+    ("ai", "eye"),
+    ("ao", "ow"),
+    ("au", "aw"),
+    ("ay", "ay"),
+    ("ee", "ee"),
+    ("ei", "ay"),
+    ("eu", "yoo"),
+    ("ey", "ay"),
+    ("ii", "ee"),
+    ("oi", "oy"),
+    ("oo", "oo"),
+    ("ou", "ow"),
+    ("oy", "oy"),
+    ("uu", "oo"),
+];
+
+/// Words political parties are named after
+const PARTY_WORDS: [&str; 12] = [
+    "Conservative",
+    "Democratic",
+    "Federalist",
+    "Free",
+    "Labour",
+    "Liberty",
+    "Loyalist",
+    "National",
+    "Popular",
+    "Progressive",
+    "Reform",
+    "Unity",
+];
+
+/// Patterns of political party names; `{}` is replaced with one of `PARTY_WORDS`
+const PARTY_PATTERNS: [&str; 8] = [
+    "{} Party",
+    "{} Front",
+    "{} Alliance",
+    "{} League",
+    "{} Union",
+    "{} Coalition",
+    "{} Movement",
+    "{} Caucus",
+];
+
+/// Words corporations are named after
+const CORPORATE_WORDS: [&str; 12] = [
+    "Apex", "Argent", "Crest", "Helix", "Meridian", "Nova", "Orion", "Stellar", "Summit", "Titan",
+    "Vanguard", "Zenith",
+];
+
+/// Patterns of corporate names; `{}` is replaced with one of `CORPORATE_WORDS`
+const CORPORATE_PATTERNS: [&str; 8] = [
+    "{} Industries",
+    "{} Holdings",
+    "{} Consolidated",
+    "{} Mining",
+    "{} Shipping",
+    "{} Biotech",
+    "{} Dynamics",
+    "{} Trading Company",
+];
+
+/// Things religious movements are devoted to
+const RELIGIOUS_WORDS: [&str; 10] = [
+    "Ascension",
+    "Burning Sun",
+    "Eternal Light",
+    "Final Jump",
+    "First Word",
+    "Hidden Path",
+    "Long Night",
+    "Open Hand",
+    "Silent Star",
+    "Void",
+];
+
+/// Patterns of religious movement names; `{}` is replaced with one of `RELIGIOUS_WORDS`
+const RELIGIOUS_PATTERNS: [&str; 6] = [
+    "Church of the {}",
+    "Order of the {}",
+    "Children of the {}",
+    "Temple of the {}",
+    "Followers of the {}",
+    "Brotherhood of the {}",
+];
+
+/// Words criminal cartels are named after
+const CRIMINAL_WORDS: [&str; 12] = [
+    "Black", "Crimson", "Ghost", "Iron", "Jade", "Obsidian", "Red", "Sable", "Scorpion", "Serpent",
+    "Vega", "Viper",
+];
+
+/// Patterns of criminal cartel names; `{}` is replaced with one of `CRIMINAL_WORDS`
+const CRIMINAL_PATTERNS: [&str; 6] = [
+    "The {} Syndicate",
+    "{} Cartel",
+    "The {} Hand",
+    "{} Family",
+    "{} Combine",
+    "{} Circle",
+];
+
+/// Relative chances of each `FactionKind`, in the order of `FactionKind::ALL_VALUES`, of a faction
+/// led by each of the governments of the government table, indexed by government code
+const FACTION_KIND_WEIGHTS: [[u32; 4]; 16] = [
+    [1, 1, 2, 4], // None
+    [1, 6, 1, 2], // Company/Corporation
+    [6, 2, 1, 1], // Participating Democracy
+    [2, 3, 1, 2], // Self-Perpetuating Oligarchy
+    [6, 2, 1, 1], // Representative Democracy
+    [2, 4, 1, 1], // Feudal Technocracy
+    [4, 2, 1, 2], // Captive Government
+    [3, 1, 2, 4], // Balkanization
+    [4, 3, 1, 1], // Civil Service Bureaucracy
+    [3, 3, 1, 2], // Impersonal Bureaucracy
+    [3, 1, 3, 2], // Charismatic Dictator
+    [4, 2, 1, 2], // Non-Charismatic Leader
+    [3, 2, 3, 1], // Charismatic Oligarchy
+    [1, 1, 6, 2], // Religious Dictatorship
+    [1, 1, 6, 2], // Religious Autocracy
+    [2, 2, 1, 4], // Totalitarian Oligarchy
+];
+
+/** Kind of organization a generated faction name belongs to. */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum FactionKind {
+    PoliticalParty,
+    Corporation,
+    ReligiousMovement,
+    CriminalCartel,
+}
+
+impl FactionKind {
+    const ALL_VALUES: [FactionKind; 4] = [
+        Self::PoliticalParty,
+        Self::Corporation,
+        Self::ReligiousMovement,
+        Self::CriminalCartel,
+    ];
+
+    /** The words and patterns names of the kind are built from. */
+    fn name_parts(&self) -> (&'static [&'static str], &'static [&'static str]) {
+        match self {
+            Self::PoliticalParty => (&PARTY_WORDS, &PARTY_PATTERNS),
+            Self::Corporation => (&CORPORATE_WORDS, &CORPORATE_PATTERNS),
+            Self::ReligiousMovement => (&RELIGIOUS_WORDS, &RELIGIOUS_PATTERNS),
+            Self::CriminalCartel => (&CRIMINAL_WORDS, &CRIMINAL_PATTERNS),
+        }
+    }
+}
+
+/** Style of a generated subsector name. */
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum NameStyle {
+    /// Latin and Greek sounding names, e.g. "Aurelia"
+    #[default]
+    Classical,
+    /// Names of human colonies, e.g. "Drake's Reach"
+    Colonial,
+    /// Harsh, unfamiliar names, e.g. "Khaar'tlix"
+    Alien,
+}
+
+impl NameStyle {
+    pub const ALL_VALUES: [NameStyle; 3] = [Self::Classical, Self::Colonial, Self::Alien];
+}
+
+impl fmt::Display for NameStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Classical => "Classical",
+            Self::Colonial => "Colonial",
+            Self::Alien => "Alien",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** Generate a random subsector name in the given `style`. */
+pub fn random_subsector_name(style: NameStyle) -> String {
+    let mut rng = rand::thread_rng();
+    match style {
+        NameStyle::Classical => {
+            let root = CLASSICAL_ROOTS.choose(&mut rng).unwrap();
+            let ending = CLASSICAL_ENDINGS.choose(&mut rng).unwrap();
+            format!("{}{}", root, ending)
+        }
+
+        NameStyle::Colonial => {
+            let name = COLONIAL_NAMES.choose(&mut rng).unwrap();
+            COLONIAL_PATTERNS
+                .choose(&mut rng)
+                .unwrap()
+                .replace("{}", name)
+        }
+
+        NameStyle::Alien => {
+            let syllable_count = dice::roll_range(2..=3);
+            let syllables: Vec<String> = (0..syllable_count)
+                .map(|_| {
+                    format!(
+                        "{}{}{}",
+                        ALIEN_ONSETS.choose(&mut rng).unwrap(),
+                        ALIEN_VOWELS.choose(&mut rng).unwrap(),
+                        ALIEN_CODAS.choose(&mut rng).unwrap()
+                    )
+                })
+                .collect();
+
+            // Glottal stops between some of the syllables
+            let mut name = syllables[0].clone();
+            for syllable in &syllables[1..] {
+                if dice::roll_1d(6) >= 4 {
+                    name.push('\'');
+                }
+                name.push_str(syllable);
+            }
+
+            let mut chars = name.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+                None => String::new(),
+            }
+        }
+    }
+}
+
+/** Generate a random name for a faction led by the government with the code `government_code`.
+
+The kind of organization the name is for is chosen by the weights in `FACTION_KIND_WEIGHTS`, so
+e.g. factions led by corporations are usually named like companies and theocracies like churches.
+Governments outside the table have an equal chance of every kind.
+*/
+pub fn random_faction_name(government_code: u16) -> String {
+    let mut rng = rand::thread_rng();
+    let weights = FACTION_KIND_WEIGHTS
+        .get(government_code as usize)
+        .unwrap_or(&[1; 4]);
+    let kind = FactionKind::ALL_VALUES
+        .choose_weighted(&mut rng, |kind| weights[*kind as usize])
+        .unwrap();
+
+    let (words, patterns) = kind.name_parts();
+    let word = words.choose(&mut rng).unwrap();
+    patterns.choose(&mut rng).unwrap().replace("{}", word)
+}
+
+/** Generate `count` random world names from the syllable components in `NAME_COMPONENTS`. */
+pub(super) fn random_names(count: usize) -> Vec<String> {
+    let mut rng = rand::thread_rng();
+    (0..count)
+        .map(|c| {
+            let name: String = NAME_PATTERNS[c % NAME_PATTERNS.len()]
+                .iter()
+                .map(|&index| {
+                    let components = NAME_COMPONENTS[index - 1];
+                    components[rng.gen_range(0..components.len())]
+                })
+                .collect();
+
+            // Capitalize name
+            let mut chars = name.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/** One sound of a name, spelled phonetically. */
+#[derive(Debug)]
+enum Sound {
+    Consonant(String),
+    /// Spelling of the vowel in open syllables, then in syllables closed by a consonant
+    Vowel(&'static str, &'static str),
+}
+
+/** Spell out how `name` is pronounced, e.g. "REH-jee-nuh" for "Regina".
+
+Names are split into the same consonants, vowels, and clusters of them that random world names are
+built from, then grouped into syllables with the first syllable of each word stressed. Any name
+works, but invented ones come out best; English names with silent letters may come out oddly.
+*/
+pub fn phonetic_spelling(name: &str) -> String {
+    name.split(|c: char| c.is_whitespace() || c == '-')
+        .map(phonetic_word)
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/** Spell out how a single word is pronounced, syllables separated by hyphens. */
+fn phonetic_word(word: &str) -> String {
+    let letters: Vec<char> = word
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphabetic())
+        .collect();
+    let sounds = word_sounds(&letters);
+
+    // Each vowel starts a new syllable, taking the consonants before it that can start a syllable
+    // together as its onset and leaving any others to close the syllable before
+    let mut syllables: Vec<(String, Option<Sound>, String)> = Vec::new();
+    let mut consonants: Vec<String> = Vec::new();
+    for sound in sounds {
+        match sound {
+            Sound::Consonant(spelling) => consonants.push(spelling),
+            vowel => {
+                let onset = if syllables.is_empty() {
+                    consonants.concat()
+                } else {
+                    let onset_len = (1..=consonants.len())
+                        .rev()
+                        .find(|&len| {
+                            len == 1 || is_onset_cluster(&consonants[consonants.len() - len..])
+                        })
+                        .unwrap_or(0);
+                    let onset = consonants.split_off(consonants.len() - onset_len).concat();
+                    syllables
+                        .last_mut()
+                        .unwrap()
+                        .2
+                        .push_str(&consonants.concat());
+                    onset
+                };
+                consonants.clear();
+                syllables.push((onset, Some(vowel), String::new()));
+            }
+        }
+    }
+    match syllables.last_mut() {
+        Some(last) => last.2.push_str(&consonants.concat()),
+        None if consonants.is_empty() => return String::new(),
+        None => syllables.push((consonants.concat(), None, String::new())),
+    }
+
+    let syllable_count = syllables.len();
+    syllables
+        .into_iter()
+        .enumerate()
+        .map(|(i, (onset, vowel, coda))| {
+            let vowel = match vowel {
+                // A trailing "a" is unstressed, as in "Regina"
+                Some(Sound::Vowel("ah", _))
+                    if i > 0 && i == syllable_count - 1 && coda.is_empty() =>
+                {
+                    "uh"
+                }
+                Some(Sound::Vowel(open, _)) if coda.is_empty() => open,
+                Some(Sound::Vowel(_, closed)) => closed,
+                _ => "",
+            };
+            let syllable = format!("{}{}{}", onset, vowel, coda);
+            if i == 0 {
+                syllable.to_uppercase()
+            } else {
+                syllable
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/** Whether the phonetic `consonants` together are one of the consonant clusters that start
+syllables of random world names, e.g. "str".
+*/
+fn is_onset_cluster(consonants: &[String]) -> bool {
+    let spelling = consonants.concat();
+    NAME_COMPONENTS[2]
+        .iter()
+        .any(|cluster| cluster.replace('c', "k") == spelling)
+}
+
+/** Split the lowercase `letters` of a word into the sounds they're pronounced as. */
+fn word_sounds(letters: &[char]) -> Vec<Sound> {
+    let is_vowel = |i: usize| match letters.get(i) {
+        Some('a' | 'e' | 'i' | 'o' | 'u') => true,
+        // "y" is only a consonant at the start of a word or right before a vowel, as in "Yama"
+        Some('y') => i > 0 && !matches!(letters.get(i + 1), Some('a' | 'e' | 'i' | 'o' | 'u')),
+        _ => false,
+    };
+    let softens = |i: usize| matches!(letters.get(i), Some('e' | 'i' | 'y'));
+
+    let mut sounds = Vec::new();
+    let mut i = 0;
+    while i < letters.len() {
+        let pair: String = letters[i..letters.len().min(i + 2)].iter().collect();
+
+        if is_vowel(i) {
+            // A final "e" after a single consonant is silent, as in "Tune"
+            let silent_e = letters[i] == 'e'
+                && i == letters.len() - 1
+                && i >= 2
+                && !is_vowel(i - 1)
+                && (0..i - 1).any(is_vowel);
+            if silent_e {
+                // ...but lengthens the vowel before it
+                if let Some(Sound::Vowel(open, closed)) = sounds
+                    .iter_mut()
+                    .rev()
+                    .find(|sound| matches!(sound, Sound::Vowel(..)))
+                {
+                    *closed = *open;
+                }
+                break;
+            }
+
+            match VOWEL_DIGRAPHS.iter().find(|(digraph, _)| *digraph == pair) {
+                Some((_, spelling)) => {
+                    sounds.push(Sound::Vowel(spelling, spelling));
+                    i += 2;
+                }
+                None => {
+                    let (open, closed) = match letters[i] {
+                        'a' => ("ah", "a"),
+                        'e' => ("eh", "eh"),
+                        'o' => ("oh", "o"),
+                        'u' => ("oo", "uh"),
+                        _ => ("ee", "ih"),
+                    };
+                    sounds.push(Sound::Vowel(open, closed));
+                    i += 1;
+                }
+            }
+            continue;
+        }
+
+        if let Some((_, spelling)) = CONSONANT_DIGRAPHS
+            .iter()
+            .find(|(digraph, _)| *digraph == pair)
+        {
+            sounds.push(Sound::Consonant(spelling.to_string()));
+            i += 2;
+            continue;
+        }
+
+        let spelling = match letters[i] {
+            'c' if softens(i + 1) => "s".to_string(),
+            'c' | 'q' => "k".to_string(),
+            'g' if softens(i + 1) => "j".to_string(),
+            'x' if i == 0 => "z".to_string(),
+            'x' => "ks".to_string(),
+            c => c.to_string(),
+        };
+        sounds.push(Sound::Consonant(spelling));
+
+        // Doubled consonants are pronounced once, as in "Carro"
+        let mut next = i + 1;
+        while next < letters.len() && letters[next] == letters[i] {
+            next += 1;
+        }
+        i = next;
+    }
+    sounds
+}
+
+impl Subsector {
+    /** Find every name shared by more than one `World`, ignoring case and surrounding whitespace.
+
+    Generated names can collide, and imported subsectors often contain copies of the same world.
+
+    # Returns
+    Each duplicated name as it is spelled on its first `World`, along with the locations of every
+    `World` sharing it, in order.
+    */
+    pub fn duplicate_names(&self) -> Vec<(String, Vec<Point>)> {
+        let mut names: BTreeMap<String, (String, Vec<Point>)> = BTreeMap::new();
+        for (point, world) in self.map.iter() {
+            let name = world.name.trim();
+            if name.is_empty() {
+                continue;
+            }
+            names
+                .entry(name.to_lowercase())
+                .or_insert_with(|| (name.to_string(), Vec::new()))
+                .1
+                .push(*point);
+        }
+
+        let mut duplicates: Vec<(String, Vec<Point>)> = names
+            .into_values()
+            .filter(|(_, points)| points.len() > 1)
+            .collect();
+        duplicates.sort_by_key(|(_, points)| points[0]);
+        duplicates
+    }
+
+    /** Give a new random name to every `World` but the first sharing each of the duplicate names.
+
+    The new names are unique within the subsector.
+
+    # Returns
+    The locations of the renamed worlds, in order.
+    */
+    pub fn rename_duplicates(&mut self) -> Vec<Point> {
+        let mut points: Vec<Point> = self
+            .duplicate_names()
+            .into_iter()
+            .flat_map(|(_, points)| points.into_iter().skip(1))
+            .collect();
+        points.sort();
+
+        let mut taken: BTreeSet<String> = self
+            .map
+            .values()
+            .map(|world| world.name.trim().to_lowercase())
+            .collect();
+        let mut names = Vec::<String>::new().into_iter();
+        for point in points.iter() {
+            let name = loop {
+                match names.next() {
+                    Some(name) if taken.insert(name.to_lowercase()) => break name,
+                    Some(_) => (),
+                    None => names = random_names(Subsector::COLUMNS * Subsector::ROWS).into_iter(),
+                }
+            };
+            if let Some(world) = self.map.get_mut(point) {
+                world.name = name;
+                world.generate_pronunciation();
+            }
+        }
+        points
+    }
+
+    /** Give a random name to every unnamed `Faction` of every `World`, suited to its government.
+
+    # Returns
+    The locations of the worlds that had factions named, in order.
+    */
+    pub fn name_unnamed_factions(&mut self) -> Vec<Point> {
+        let mut points = Vec::new();
+        for (point, world) in self.map.iter_mut() {
+            let mut named = false;
+            for faction in world.factions.iter_mut().filter(|f| f.is_unnamed()) {
+                faction.name = random_faction_name(faction.government.code);
+                named = true;
+            }
+            if named {
+                points.push(*point);
+            }
+        }
+        points
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::World;
+
+    #[test]
+    fn rename_duplicates() {
+        let mut subsector = Subsector::empty();
+        let names = ["Regina", "Efate", " regina", "Pixie", "Efate", "REGINA"];
+        let points: Vec<Point> = (1..=names.len() as i32)
+            .map(|y| Point { x: 1, y })
+            .collect();
+        for (point, name) in points.iter().zip(names) {
+            let mut world = World::empty();
+            world.name = name.to_string();
+            subsector.insert_world(point, world).unwrap();
+        }
+
+        assert_eq!(
+            subsector.duplicate_names(),
+            vec![
+                ("Regina".to_string(), vec![points[0], points[2], points[5]]),
+                ("Efate".to_string(), vec![points[1], points[4]]),
+            ]
+        );
+
+        assert_eq!(
+            subsector.rename_duplicates(),
+            vec![points[2], points[4], points[5]]
+        );
+        assert!(subsector.duplicate_names().is_empty());
+        assert_eq!(subsector.get_world(&points[0]).unwrap().name, "Regina");
+        assert_eq!(subsector.get_world(&points[1]).unwrap().name, "Efate");
+        assert!(subsector.rename_duplicates().is_empty());
+    }
+
+    #[test]
+    fn faction_names() {
+        for government_code in 0..20 {
+            let name = random_faction_name(government_code);
+            assert!(!name.is_empty());
+            assert!(!name.contains("{}"), "{}", name);
+        }
+
+        // Factions of theocracies are mostly named like religious movements
+        let (words, _) = FactionKind::ReligiousMovement.name_parts();
+        assert!((0..100).any(|_| {
+            let name = random_faction_name(13);
+            words.iter().any(|word| name.ends_with(word))
+        }));
+
+        let mut subsector = Subsector::empty();
+        let points = [Point { x: 1, y: 1 }, Point { x: 2, y: 2 }];
+        for point in points {
+            subsector.insert_world(&point, World::empty()).unwrap();
+        }
+        let world = subsector.map.get_mut(&points[1]).unwrap();
+        world.add_faction();
+        world.add_faction();
+        world.factions[1].name = "Ine Givar".to_string();
+
+        assert_eq!(subsector.name_unnamed_factions(), vec![points[1]]);
+        let factions = &subsector.get_world(&points[1]).unwrap().factions;
+        assert!(factions.iter().all(|faction| !faction.is_unnamed()));
+        assert_eq!(factions[1].name, "Ine Givar");
+        assert!(subsector.name_unnamed_factions().is_empty());
+    }
+
+    #[test]
+    fn phonetic_spellings() {
+        assert_eq!(phonetic_spelling("Regina"), "REH-jee-nuh");
+        assert_eq!(phonetic_spelling("Carro"), "KAH-roh");
+        assert_eq!(phonetic_spelling("Astra"), "AH-struh");
+        assert_eq!(phonetic_spelling("Zuno Tune"), "ZOO-noh TOON");
+        assert_eq!(phonetic_spelling("Khaar'tlix"), "KHAHRT-lihks");
+        assert_eq!(phonetic_spelling(" - "), "");
+
+        for name in random_names(100) {
+            let spelling = phonetic_spelling(&name);
+            assert!(
+                !spelling.is_empty()
+                    && spelling
+                        .chars()
+                        .all(|c| c.is_ascii_alphabetic() || c == '-'),
+                "'{}' should have a phonetic spelling, not '{}'",
+                name,
+                spelling
+            );
+        }
+    }
+
+    #[test]
+    fn subsector_names() {
+        const ATTEMPTS: usize = 100;
+        for _ in 0..ATTEMPTS {
+            for style in NameStyle::ALL_VALUES {
+                let name = random_subsector_name(style);
+                assert!(!name.contains("{}"));
+                assert!(
+                    name.starts_with(char::is_uppercase),
+                    "{} name '{}' should be capitalized",
+                    style,
+                    name
+                );
+            }
+        }
+    }
+}
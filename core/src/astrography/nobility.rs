@@ -0,0 +1,112 @@
+use crate::astrography::{TradeCode, World};
+
+/// Importance a world needs to be the seat of a duke
+const DUKE_IMPORTANCE: i32 = 4;
+
+/** T5 noble ranks by their nobility code, from lowest to highest. */
+pub const NOBLE_RANKS: [(char, &str); 8] = [
+    ('B', "Knight"),
+    ('c', "Baronet"),
+    ('C', "Baron"),
+    ('D', "Marquis"),
+    ('e', "Viscount"),
+    ('E', "Count"),
+    ('f', "Duke"),
+    ('F', "Subsector Duke"),
+];
+
+impl World {
+    /** Work out the T5 nobility codes of the nobles resident on the `World`, e.g. "BCf".
+
+    Every populated world has a knight (B). Worlds about to become agricultural or rich have a
+    baronet (c), and those that are have a baron (C). Worlds about to industrialize have a marquis
+    (D), those about to reach a high population a viscount (e), and industrial or high population
+    worlds a count (E). Worlds with an importance of 4 or more are the seat of a duke (f).
+
+    Subsector dukes (F) sit on the subsector's capital, so they are left to whatever knows which
+    world that is. Uninhabited worlds have no nobility at all.
+    */
+    pub fn generate_nobility(&mut self) {
+        self.nobility.clear();
+        if self.population.code == 0 {
+            return;
+        }
+
+        let has_code =
+            |codes: &[TradeCode]| codes.iter().any(|code| self.trade_codes.contains(code));
+        let atmosphere = self.atmosphere.code;
+        let hydrographics = self.hydrographics.code;
+        let population = self.population.code;
+
+        // The T5 "pre" trade codes, for worlds just short of earning a full trade code
+        let pre_agricultural = (4..=9).contains(&atmosphere)
+            && (4..=8).contains(&hydrographics)
+            && matches!(population, 4 | 8);
+        let pre_rich = matches!(atmosphere, 6 | 8) && matches!(population, 5 | 9);
+        let pre_industrial = matches!(atmosphere, 0..=2 | 4 | 7 | 9) && matches!(population, 7 | 8);
+        let pre_high = population == 8;
+
+        let mut nobility = "B".to_string();
+        if pre_agricultural || pre_rich {
+            nobility.push('c');
+        }
+        if has_code(&[TradeCode::Ag, TradeCode::Ri]) {
+            nobility.push('C');
+        }
+        if pre_industrial {
+            nobility.push('D');
+        }
+        if pre_high {
+            nobility.push('e');
+        }
+        if has_code(&[TradeCode::Hi, TradeCode::In]) {
+            nobility.push('E');
+        }
+        if self.importance() >= DUKE_IMPORTANCE {
+            nobility.push('f');
+        }
+        self.nobility = nobility;
+    }
+
+    /** Get the names of the noble ranks in the `World`'s nobility codes, ignoring unknown codes. */
+    pub fn noble_ranks(&self) -> Vec<&'static str> {
+        self.nobility
+            .chars()
+            .filter_map(|code| {
+                NOBLE_RANKS
+                    .iter()
+                    .find(|(rank_code, _)| *rank_code == code)
+                    .map(|(_, rank)| *rank)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::TABLES;
+
+    #[test]
+    fn generate_nobility() {
+        let mut world = World::empty();
+        world.generate_nobility();
+        assert_eq!(world.nobility, "");
+        assert!(world.noble_ranks().is_empty());
+
+        world.atmosphere = TABLES.atmo_table[6].clone();
+        world.hydrographics = TABLES.hydro_table[5].clone();
+        world.population = TABLES.pop_table[8].clone();
+        world.trade_codes.clear();
+        world.trade_codes.insert(TradeCode::Ri);
+        world.generate_nobility();
+        assert_eq!(world.nobility, "BcCe");
+        assert_eq!(
+            world.noble_ranks(),
+            ["Knight", "Baronet", "Baron", "Viscount"]
+        );
+
+        world.nobility = "BxF".to_string();
+        assert_eq!(world.noble_ranks(), ["Knight", "Subsector Duke"]);
+    }
+}
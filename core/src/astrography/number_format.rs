@@ -0,0 +1,192 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::World;
+
+/// Kilometers in one statute mile
+const KM_PER_MILE: f64 = 1.609_344;
+
+/// SI prefixes of abbreviated counts, each a thousand times the last
+const SI_SUFFIXES: [&str; 5] = ["", "k", "M", "G", "T"];
+
+/** Unit that lengths like world diameters are displayed in. */
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum LengthUnit {
+    #[default]
+    Kilometers,
+    Miles,
+}
+
+impl LengthUnit {
+    pub const ALL_VALUES: [LengthUnit; 2] = [Self::Kilometers, Self::Miles];
+
+    /** The abbreviation written after a length in the unit, e.g. "km". */
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            Self::Kilometers => "km",
+            Self::Miles => "mi",
+        }
+    }
+}
+
+impl fmt::Display for LengthUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Kilometers => "Kilometers",
+            Self::Miles => "Miles",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** How large numbers like populations are written. */
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum CountStyle {
+    /// Digits only, e.g. "1234567"
+    #[default]
+    Plain,
+    /// Digits with thousands separators, e.g. "1,234,567"
+    Separated,
+    /// Rounded with an SI suffix, e.g. "1.2M"
+    Abbreviated,
+}
+
+impl CountStyle {
+    pub const ALL_VALUES: [CountStyle; 3] = [Self::Plain, Self::Separated, Self::Abbreviated];
+}
+
+impl fmt::Display for CountStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Plain => "Plain (1234567)",
+            Self::Separated => "Separated (1,234,567)",
+            Self::Abbreviated => "SI Suffix (1.2M)",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** How numbers are displayed in the GUI and written in exports.
+
+The default matches how numbers were always written: plain digits and lengths in kilometers.
+*/
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct NumberFormat {
+    pub count_style: CountStyle,
+    pub length_unit: LengthUnit,
+}
+
+impl NumberFormat {
+    /** Write `count` in the count style, e.g. "1,234,567" or "1.2M". */
+    pub fn count(&self, count: u64) -> String {
+        match self.count_style {
+            CountStyle::Plain => count.to_string(),
+            CountStyle::Separated => separated(count),
+            CountStyle::Abbreviated => abbreviated(count),
+        }
+    }
+
+    /** Write a length of `km` kilometers in the length unit, e.g. "12742 km" or "7,918 mi".
+
+    Lengths are never abbreviated; they are written with thousands separators instead.
+    */
+    pub fn length(&self, km: u32) -> String {
+        let length = match self.length_unit {
+            LengthUnit::Kilometers => km as u64,
+            LengthUnit::Miles => (km as f64 / KM_PER_MILE).round() as u64,
+        };
+        let digits = match self.count_style {
+            CountStyle::Plain => length.to_string(),
+            CountStyle::Separated | CountStyle::Abbreviated => separated(length),
+        };
+        format!("{} {}", digits, self.length_unit.abbreviation())
+    }
+}
+
+impl World {
+    /** Estimate the number of inhabitants of the `World` from its population code.
+
+    Every world is assumed to have a population multiplier of 1, as in its PBG, so this is 10 to the
+    power of the code, or 0 for an uninhabited world.
+    */
+    pub fn population_count(&self) -> u64 {
+        match self.population.code {
+            0 => 0,
+            code => 10u64.saturating_pow(code as u32),
+        }
+    }
+}
+
+/** Write `value` with a comma between each group of three digits. */
+fn separated(value: u64) -> String {
+    let digits = value.to_string();
+    let mut result = String::new();
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            result.push(',');
+        }
+        result.push(digit);
+    }
+    result
+}
+
+/** Write `value` with an SI suffix, e.g. "12k", with one decimal place if it is less than 10. */
+fn abbreviated(value: u64) -> String {
+    let mut scaled = value as f64;
+    let mut suffix = 0;
+    // Rounding may carry up to the next suffix, e.g. 999,999 is "1M" rather than "1000k"
+    while scaled >= 999.5 && suffix + 1 < SI_SUFFIXES.len() {
+        scaled /= 1000.0;
+        suffix += 1;
+    }
+
+    let digits = if suffix > 0 && scaled < 9.95 {
+        let digits = format!("{:.1}", scaled);
+        digits.trim_end_matches(".0").to_string()
+    } else {
+        format!("{:.0}", scaled)
+    };
+    format!("{}{}", digits, SI_SUFFIXES[suffix])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::TABLES;
+
+    #[test]
+    fn number_formats() {
+        let mut numbers = NumberFormat::default();
+        assert_eq!(numbers.count(1_234_567), "1234567");
+        assert_eq!(numbers.length(12_742), "12742 km");
+
+        numbers.count_style = CountStyle::Separated;
+        assert_eq!(numbers.count(1_234_567), "1,234,567");
+        assert_eq!(numbers.count(123), "123");
+        assert_eq!(numbers.count(100_000), "100,000");
+
+        numbers.length_unit = LengthUnit::Miles;
+        assert_eq!(numbers.length(12_742), "7,918 mi");
+
+        numbers.count_style = CountStyle::Abbreviated;
+        assert_eq!(numbers.length(12_742), "7,918 mi");
+        assert_eq!(numbers.count(0), "0");
+        assert_eq!(numbers.count(999), "999");
+        assert_eq!(numbers.count(1_234_567), "1.2M");
+        assert_eq!(numbers.count(12_345), "12k");
+        assert_eq!(numbers.count(999_999), "1M");
+        assert_eq!(numbers.count(1_000_000_000_000), "1T");
+    }
+
+    #[test]
+    fn population_counts() {
+        let mut world = World::empty();
+        world.population = TABLES.pop_table[0].clone();
+        assert_eq!(world.population_count(), 0);
+
+        world.population = TABLES.pop_table[6].clone();
+        assert_eq!(world.population_count(), 1_000_000);
+    }
+}
@@ -0,0 +1,250 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::{Shipyard, StarportClass, World};
+
+const HIGHPORT_DESCRIPTIONS: [&str; 3] = [
+    "A single station with a handful of berths",
+    "A ring station with berths for dozens of ships",
+    "A sprawling orbital city with berths for hundreds of ships",
+];
+
+const SHIPYARD_DESCRIPTIONS: [&str; 3] = [
+    "Slips for building and refitting small craft",
+    "Slips for building starships up to a few thousand tons",
+    "Slips large enough to lay down capital ships",
+];
+
+const FUEL_DEPOT_DESCRIPTIONS: [&str; 3] = [
+    "A few tanks for refueling passing ships",
+    "Orbital tanks sized for regular traffic",
+    "Huge fuel reserves able to supply a fleet",
+];
+
+const NAVAL_STATION_DESCRIPTIONS: [&str; 3] = [
+    "A picket station with a few patrol ships",
+    "A naval station with a squadron on call",
+    "A fleet anchorage with dry docks and capital ships",
+];
+
+/** Kind of installation orbiting a `World`. */
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum FacilityKind {
+    Highport,
+    Shipyard,
+    FuelDepot,
+    NavalStation,
+}
+
+impl FacilityKind {
+    pub const ALL_VALUES: [FacilityKind; 4] = [
+        Self::Highport,
+        Self::Shipyard,
+        Self::FuelDepot,
+        Self::NavalStation,
+    ];
+
+    /** Descriptions of the facility for each size, from smallest to largest. */
+    fn descriptions(&self) -> &'static [&'static str; 3] {
+        match self {
+            Self::Highport => &HIGHPORT_DESCRIPTIONS,
+            Self::Shipyard => &SHIPYARD_DESCRIPTIONS,
+            Self::FuelDepot => &FUEL_DEPOT_DESCRIPTIONS,
+            Self::NavalStation => &NAVAL_STATION_DESCRIPTIONS,
+        }
+    }
+}
+
+impl fmt::Display for FacilityKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Highport => "Highport",
+            Self::Shipyard => "Shipyard",
+            Self::FuelDepot => "Fuel Depot",
+            Self::NavalStation => "Naval Station",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** How large a `Facility` is, and so how many ships it can handle at once. */
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum FacilitySize {
+    Small,
+    Medium,
+    Large,
+}
+
+impl FacilitySize {
+    pub const ALL_VALUES: [FacilitySize; 3] = [Self::Small, Self::Medium, Self::Large];
+
+    /** Get the size for a `score` of 0 or less for small up to 2 or more for large. */
+    fn from_score(score: i32) -> Self {
+        match score {
+            i32::MIN..=0 => Self::Small,
+            1 => Self::Medium,
+            _ => Self::Large,
+        }
+    }
+}
+
+impl fmt::Display for FacilitySize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Small => "Small",
+            Self::Medium => "Medium",
+            Self::Large => "Large",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** An installation in orbit around a `World`, like a highport or a naval station. */
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Facility {
+    pub kind: FacilityKind,
+    pub size: FacilitySize,
+    pub description: String,
+}
+
+impl Facility {
+    /** Create a `Facility` with the stock description of its `kind` and `size`. */
+    pub fn new(kind: FacilityKind, size: FacilitySize) -> Self {
+        Self {
+            kind,
+            size,
+            description: kind.descriptions()[size as usize].to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Facility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.size, self.kind.to_string().to_lowercase())
+    }
+}
+
+impl World {
+    /** Add a small highport to the `World`'s orbital facilities, to be edited into whatever is
+    wanted.
+
+    # Returns
+    The index of the new facility.
+    */
+    pub fn add_orbital_facility(&mut self) -> usize {
+        self.orbital_facilities
+            .push(Facility::new(FacilityKind::Highport, FacilitySize::Small));
+        self.orbital_facilities.len() - 1
+    }
+
+    /** Work out the installations orbiting the `World`, replacing the old ones.
+
+    Class A to D starports have a fuel depot, along with a highport and a shipyard if their
+    starport details say so, and naval bases have a naval station. Highports, fuel depots, and
+    naval stations are larger at better starports, larger still on worlds with a population of 9+,
+    and smaller on worlds with a population of 5-. Shipyards are sized by the largest ships they
+    can build.
+    */
+    pub fn generate_orbital_facilities(&mut self) {
+        self.orbital_facilities.clear();
+
+        let class_score = match self.starport.class {
+            StarportClass::A => Some(2),
+            StarportClass::B => Some(1),
+            StarportClass::C | StarportClass::D => Some(0),
+            StarportClass::E | StarportClass::X => None,
+        };
+        let population_dm = match self.population.code {
+            0..=5 => -1,
+            9.. => 1,
+            _ => 0,
+        };
+        let sized =
+            |kind, score: i32| Facility::new(kind, FacilitySize::from_score(score + population_dm));
+
+        if let Some(score) = class_score {
+            let (highport, shipyard) = match &self.starport.details {
+                Some(details) => (details.highport, details.shipyard),
+                None => (false, Shipyard::None),
+            };
+            if highport {
+                self.orbital_facilities
+                    .push(sized(FacilityKind::Highport, score));
+            }
+            let shipyard_size = match shipyard {
+                Shipyard::None => None,
+                Shipyard::SmallCraft => Some(FacilitySize::Small),
+                Shipyard::Starships => Some(FacilitySize::Medium),
+                Shipyard::CapitalShips => Some(FacilitySize::Large),
+            };
+            if let Some(size) = shipyard_size {
+                self.orbital_facilities
+                    .push(Facility::new(FacilityKind::Shipyard, size));
+            }
+            self.orbital_facilities
+                .push(sized(FacilityKind::FuelDepot, score));
+        }
+
+        if self.has_naval_base {
+            self.orbital_facilities
+                .push(sized(FacilityKind::NavalStation, class_score.unwrap_or(0)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::{Repairs, StarportDetails, TABLES};
+
+    #[test]
+    fn generate_orbital_facilities() {
+        let mut world = World::empty();
+        world.starport = TABLES.starport_table[12].clone();
+        assert_eq!(world.starport.class, StarportClass::A);
+        world.starport.details = Some(StarportDetails {
+            highport: true,
+            shipyard: Shipyard::Starships,
+            repairs: Repairs::Full,
+        });
+        world.population = TABLES.pop_table[9].clone();
+        world.has_naval_base = true;
+        world.generate_orbital_facilities();
+
+        let facilities: Vec<String> = world
+            .orbital_facilities
+            .iter()
+            .map(Facility::to_string)
+            .collect();
+        assert_eq!(
+            facilities,
+            [
+                "Large highport",
+                "Medium shipyard",
+                "Large fuel depot",
+                "Large naval station"
+            ]
+        );
+        assert_eq!(
+            world.orbital_facilities[0].description,
+            HIGHPORT_DESCRIPTIONS[2]
+        );
+
+        world.starport = TABLES.starport_table[0].clone();
+        world.population = TABLES.pop_table[3].clone();
+        world.generate_orbital_facilities();
+        assert_eq!(
+            world.orbital_facilities,
+            [Facility::new(
+                FacilityKind::NavalStation,
+                FacilitySize::Small
+            )]
+        );
+
+        world.has_naval_base = false;
+        world.generate_orbital_facilities();
+        assert!(world.orbital_facilities.is_empty());
+        assert_eq!(world.add_orbital_facility(), 0);
+    }
+}
@@ -0,0 +1,83 @@
+use crate::astrography::{Point, Subsector};
+
+impl Subsector {
+    /** Get the colonies and possessions of the `World` at `point`, in order. */
+    pub fn colonies_of(&self, point: &Point) -> Vec<Point> {
+        self.owners()
+            .into_iter()
+            .filter(|(_, owner)| owner == point)
+            .map(|(owned, _)| owned)
+            .collect()
+    }
+
+    /** Get a pair of each owned `World`'s location and the location of its owner. */
+    pub fn owners(&self) -> Vec<(Point, Point)> {
+        self.map
+            .iter()
+            .filter_map(|(point, world)| world.owner.map(|owner| (*point, owner)))
+            .collect()
+    }
+
+    /** Clear the owner of any `World` that is owned by itself or by an empty hex. */
+    pub fn prune_owners(&mut self) {
+        let occupied: Vec<Point> = self.map.keys().copied().collect();
+        for (point, world) in self.map.iter_mut() {
+            if let Some(owner) = world.owner {
+                if owner == *point || !occupied.contains(&owner) {
+                    world.owner = None;
+                }
+            }
+        }
+    }
+
+    /** Replace every ownership link with `owners`, pairs of an owned `World` and its owner. */
+    pub(super) fn set_owners(&mut self, owners: Vec<(Point, Point)>) {
+        for world in self.map.values_mut() {
+            world.owner = None;
+        }
+        for (owned, owner) in owners {
+            if let Some(world) = self.map.get_mut(&owned) {
+                world.owner = Some(owner);
+            }
+        }
+        self.prune_owners();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::World;
+
+    #[test]
+    fn ownership_follows_worlds() {
+        let point = |x, y| Point { x, y };
+        let mut subsector = Subsector::empty();
+        for x in 1..=3 {
+            subsector
+                .insert_world(&point(x, 1), World::empty())
+                .unwrap();
+        }
+        subsector.map.get_mut(&point(2, 1)).unwrap().owner = Some(point(1, 1));
+        subsector.map.get_mut(&point(3, 1)).unwrap().owner = Some(point(1, 1));
+        assert_eq!(
+            subsector.colonies_of(&point(1, 1)),
+            vec![point(2, 1), point(3, 1)]
+        );
+
+        // Moving the owner takes its colonies' links with it
+        subsector.move_world(&point(1, 1), &point(5, 5)).unwrap();
+        assert_eq!(
+            subsector.owners(),
+            vec![(point(2, 1), point(5, 5)), (point(3, 1), point(5, 5))]
+        );
+
+        // Moving a colony onto another one replaces it and its link
+        subsector.move_world(&point(2, 1), &point(3, 1)).unwrap();
+        assert_eq!(subsector.owners(), vec![(point(3, 1), point(5, 5))]);
+
+        // Colonies of a removed world are set free
+        subsector.remove_world(&point(5, 5)).unwrap();
+        assert!(subsector.owners().is_empty());
+    }
+}
@@ -0,0 +1,247 @@
+use std::fmt;
+
+use crate::astrography::{Faction, TravelCode, World, TABLES};
+use crate::dice;
+
+/** Kind of political crisis that can befall the government of a `World`. */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PoliticalEvent {
+    /// A faction seizes power and the old regime is driven into opposition
+    Coup,
+    /// A faction breaks away from the government and declares its own territory
+    Secession,
+    /// Power passes to a new leader, which may shift the character of the government
+    Succession,
+}
+
+impl PoliticalEvent {
+    pub const ALL_VALUES: [PoliticalEvent; 3] = [Self::Coup, Self::Secession, Self::Succession];
+
+    /** Roll a random kind of crisis for `world`; only worlds with factions can suffer a secession. */
+    pub fn random(world: &World) -> Self {
+        match dice::roll_1d(6) {
+            1..=3 => Self::Succession,
+            4..=5 => Self::Coup,
+            _ if world.factions.is_empty() => Self::Coup,
+            _ => Self::Secession,
+        }
+    }
+}
+
+impl fmt::Display for PoliticalEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Coup => "Coup",
+            Self::Secession => "Secession",
+            Self::Succession => "Succession",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl World {
+    /** Put the `World` through a political crisis of the kind `event` and apply its consequences.
+
+    Crises shift the government code, law level, and faction strengths, and the travel code follows
+    along unless it is overridden. A summary of what happened is appended to the `World`'s secret
+    notes so it can be read out between sessions.
+
+    # Returns
+    - `Some(String)` with the summary of the crisis,
+    - `None` if the `World` is uninhabited, or `event` is a secession and it has no factions
+    */
+    pub fn political_crisis(&mut self, event: PoliticalEvent) -> Option<String> {
+        if self.population.code == 0 {
+            return None;
+        }
+
+        let summary = match event {
+            PoliticalEvent::Coup => self.coup(),
+            PoliticalEvent::Secession => self.secession()?,
+            PoliticalEvent::Succession => self.succession(),
+        };
+        if !self.travel_code_overridden {
+            self.resolve_travel_code();
+        }
+
+        let entry = format!("{}: {}", event, summary);
+        if !self.secret_notes.is_empty() {
+            self.secret_notes.push_str("\n\n");
+        }
+        self.secret_notes.push_str(&entry);
+        Some(entry)
+    }
+
+    /** The strongest faction takes over the government, or a new one if there are none. */
+    fn coup(&mut self) -> String {
+        let usurper = match (0..self.factions.len()).max_by_key(|&i| self.factions[i].code) {
+            Some(index) => self.factions.remove(index),
+            None => {
+                let mut faction = Faction::random();
+                faction.name = "Revolutionary Council".to_string();
+                faction
+            }
+        };
+
+        let old_government = std::mem::replace(&mut self.government, usurper.government);
+        let mut loyalists = Faction::random();
+        loyalists.name = format!("{} Loyalists", old_government.kind);
+        loyalists.government = old_government;
+        set_strength(&mut loyalists, dice::roll_2d(6) - 4);
+        self.factions.push(loyalists);
+
+        self.shift_law_level(dice::roll_1d(3));
+        format!(
+            "The {} seized power, establishing a {}. Supporters of the old regime fight on as the \
+            {}, and the new rulers have cracked down with law level {}.",
+            usurper.name,
+            self.government.kind,
+            self.factions.last().unwrap().name,
+            self.law_level.code
+        )
+    }
+
+    /** A random faction breaks away, leaving the government to clamp down on the rest. */
+    fn secession(&mut self) -> Option<String> {
+        if self.factions.is_empty() {
+            return None;
+        }
+
+        let index = dice::roll_range(0..self.factions.len());
+        let separatists = self.factions.remove(index);
+        for faction in self.factions.iter_mut() {
+            let code = faction.code as i32 + dice::roll_range(0..=1);
+            set_strength(faction, code);
+        }
+        self.shift_law_level(1);
+        if self.travel_code == TravelCode::Safe {
+            self.travel_code = TravelCode::Amber;
+            self.detect_travel_code_override();
+        }
+
+        Some(format!(
+            "The {} declared independence and took their territory with them. The government \
+            answered with emergency laws, raising the law level to {}, and the remaining factions \
+            are emboldened.",
+            separatists.name, self.law_level.code
+        ))
+    }
+
+    /** Power changes hands, nudging the government, law level, and factions one way or another. */
+    fn succession(&mut self) -> String {
+        let max_code = TABLES.gov_table.len() as i32 - 1;
+        let contested = dice::roll_2d(6) >= 9;
+        if contested {
+            let shift = if dice::roll_1d(2) == 1 { -1 } else { 1 };
+            let code = (self.government.code as i32 + shift).clamp(1, max_code);
+            self.government
+                .safe_mutate(&TABLES.gov_table[code as usize]);
+        }
+        self.shift_law_level(dice::flux() / 3);
+        for faction in self.factions.iter_mut() {
+            let code = faction.code as i32 + dice::roll_range(-1..=1);
+            set_strength(faction, code);
+        }
+
+        if contested {
+            format!(
+                "A contested succession reshaped the government into a {}, with the law level now \
+                at {}. The factions jockey for position under the new leadership.",
+                self.government.kind, self.law_level.code
+            )
+        } else {
+            format!(
+                "Power passed to a new leader of the {} in an orderly succession, leaving the law \
+                level at {}. The factions jockey for position under the new leadership.",
+                self.government.kind, self.law_level.code
+            )
+        }
+    }
+
+    /** Raise or lower the law level by `shift`, staying within the law table, and roll the law
+    level of each category around the new one.
+    */
+    fn shift_law_level(&mut self, shift: i32) {
+        let max_code = TABLES.law_table.len() as i32 - 1;
+        let code = (self.law_level.code as i32 + shift).clamp(0, max_code);
+        self.law_level = TABLES.law_table[code as usize].clone();
+        self.generate_law_categories();
+    }
+}
+
+/** Set the strength of `faction` to the faction table entry `code`, clamped to the table. */
+fn set_strength(faction: &mut Faction, code: i32) {
+    let max_code = TABLES.faction_table.len() as i32 - 1;
+    let record = &TABLES.faction_table[code.clamp(0, max_code) as usize];
+    faction.code = record.code;
+    faction.strength = record.strength.clone();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inhabited_world() -> World {
+        let mut world = World::new("Efate".to_string());
+        world.population = TABLES.pop_table[6].clone();
+        world.secret_notes = "Capital of the duchy".to_string();
+        world
+    }
+
+    #[test]
+    fn coup() {
+        let mut world = inhabited_world();
+        let old_government = world.government.clone();
+        let mut usurper = Faction::random();
+        usurper.name = "Navy".to_string();
+        usurper.government = TABLES.gov_table[10].clone();
+        set_strength(&mut usurper, 12);
+        world.factions = vec![usurper];
+
+        let summary = world.political_crisis(PoliticalEvent::Coup).unwrap();
+        assert!(summary.contains("Navy"));
+        assert_eq!(world.government.code, 10);
+        assert_eq!(world.factions.len(), 1);
+        assert_eq!(world.factions[0].government, old_government);
+        assert!(world
+            .secret_notes
+            .starts_with("Capital of the duchy\n\nCoup: "));
+    }
+
+    #[test]
+    fn crises() {
+        const ATTEMPTS: usize = 200;
+        for _ in 0..ATTEMPTS {
+            let mut world = inhabited_world();
+            let event = PoliticalEvent::random(&world);
+            assert!(world.political_crisis(event).is_some());
+            assert!((world.law_level.code as usize) < TABLES.law_table.len());
+            for faction in world.factions.iter() {
+                assert_eq!(
+                    faction.strength,
+                    TABLES.faction_table[faction.code as usize].strength
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn secession() {
+        let mut world = inhabited_world();
+        world.factions.clear();
+        assert!(world.political_crisis(PoliticalEvent::Secession).is_none());
+        assert_eq!(world.secret_notes, "Capital of the duchy");
+
+        world.factions = vec![Faction::random(), Faction::random()];
+        world.factions[0].name = "Separatists".to_string();
+        world.factions[1].name = "Separatists".to_string();
+        world.travel_code = TravelCode::Safe;
+        let summary = world.political_crisis(PoliticalEvent::Secession).unwrap();
+        assert!(summary.contains("Separatists"));
+        assert_eq!(world.factions.len(), 1);
+        assert_eq!(world.travel_code, TravelCode::Amber);
+
+        world.population = TABLES.pop_table[0].clone();
+        assert!(world.political_crisis(PoliticalEvent::Coup).is_none());
+    }
+}
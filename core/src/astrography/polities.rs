@@ -0,0 +1,303 @@
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::{Point, Subsector, World};
+use crate::dice;
+
+/// Fewest polities generated when there are enough inhabited worlds
+const MIN_POLITIES: usize = 2;
+/// Most polities generated in a `Subsector`
+const MAX_POLITIES: usize = 5;
+/// Capitals closer than this many parsecs to each other are avoided where possible
+const MIN_CAPITAL_SEPARATION: u32 = 3;
+/// Parsecs a polity reaches from its capital before tech level is taken into account
+const BASE_REACH: u32 = 2;
+
+/// Name patterns for polities based on the capital's government code; `{}` is the capital's name
+const POLITY_NAME_PATTERNS: [&str; 16] = [
+    "{} Compact",
+    "{} Company Holdings",
+    "{} Republic",
+    "{} Oligarchy",
+    "{} Federation",
+    "{} Technate",
+    "{} Protectorate",
+    "{} League",
+    "{} Directorate",
+    "{} Administration",
+    "{} Dominion",
+    "{} Regency",
+    "{} Council",
+    "{} Theocracy",
+    "{} Covenant",
+    "{} Hegemony",
+];
+
+/** A political entity that some of the worlds of a `Subsector` belong to.
+
+Worlds belong to a `Polity` through their `allegiance`, which matches the `Polity`'s `code`.
+*/
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Polity {
+    pub name: String,
+    /// Short allegiance code, e.g. "Re", used in T5 exports and to link worlds to the `Polity`
+    pub code: String,
+    pub capital: Point,
+    pub summary: String,
+}
+
+impl Subsector {
+    pub fn polities(&self) -> &[Polity] {
+        &self.polities
+    }
+
+    pub fn polities_mut(&mut self) -> &mut Vec<Polity> {
+        &mut self.polities
+    }
+
+    /** Count the worlds, including the capital, that belong to the `Polity` at `index`. */
+    pub fn polity_member_count(&self, index: usize) -> usize {
+        let code = &self.polities[index].code;
+        self.map
+            .values()
+            .filter(|world| &world.allegiance == code)
+            .count()
+    }
+
+    /** Get the locations of the worlds whose allegiance is `code`, in order. */
+    pub fn polity_members(&self, code: &str) -> Vec<Point> {
+        self.map
+            .iter()
+            .filter(|(_, world)| !code.is_empty() && world.allegiance == code)
+            .map(|(point, _)| *point)
+            .collect()
+    }
+
+    /** Get the index of the `Polity` the `World` at `point` belongs to, if any. */
+    pub fn polity_index(&self, point: &Point) -> Option<usize> {
+        let world = self.map.get(point)?;
+        self.polities
+            .iter()
+            .position(|polity| !world.allegiance.is_empty() && polity.code == world.allegiance)
+    }
+
+    /** Replace the `Subsector`'s polities with freshly generated ones and reassign allegiances.
+
+    Between `MIN_POLITIES` and `MAX_POLITIES` capitals are chosen from the most populous, advanced,
+    and important worlds, spread out across the map where possible. Every other world joins the
+    polity that exerts the most influence on it, accounting for distance and the capital's
+    strength, or stays non-aligned if no capital reaches it.
+    */
+    pub fn generate_polities(&mut self) {
+        self.polities.clear();
+        for world in self.map.values_mut() {
+            world.allegiance.clear();
+        }
+
+        // Strongest candidates first, with random tie breaks rolled once per candidate
+        let mut candidates: Vec<(Point, i32, i32)> = self
+            .map
+            .iter()
+            .filter(|(_, world)| world.population.code > 0)
+            .map(|(point, world)| (*point, capital_strength(world), dice::roll_1d(6)))
+            .collect();
+        if candidates.len() < MIN_POLITIES {
+            return;
+        }
+        candidates.sort_by_key(|(_, strength, tie_break)| (-strength, -tie_break));
+        let candidates: Vec<(Point, i32)> = candidates
+            .into_iter()
+            .map(|(point, strength, _)| (point, strength))
+            .collect();
+
+        let count = dice::roll_range(MIN_POLITIES..=MAX_POLITIES).min(candidates.len());
+        let mut capitals: Vec<(Point, i32)> = Vec::new();
+        for candidate in &candidates {
+            let far_enough = capitals
+                .iter()
+                .all(|(capital, _)| capital.distance(&candidate.0) >= MIN_CAPITAL_SEPARATION);
+            if far_enough && capitals.len() < count {
+                capitals.push(*candidate);
+            }
+        }
+        // Fall back on capitals that are close together if the map is too crowded
+        for candidate in &candidates {
+            if capitals.len() >= count {
+                break;
+            }
+            if !capitals.contains(candidate) {
+                capitals.push(*candidate);
+            }
+        }
+
+        for (capital, _) in &capitals {
+            let world = &self.map[capital];
+            let code = self.unique_polity_code(&world.name);
+            let name = POLITY_NAME_PATTERNS
+                [(world.government.code as usize).min(POLITY_NAME_PATTERNS.len() - 1)]
+            .replace("{}", &world.name);
+            self.polities.push(Polity {
+                name,
+                code,
+                capital: *capital,
+                summary: String::new(),
+            });
+        }
+
+        // Each world joins the capital with the most influence over it, if any reach it at all
+        let assignments: Vec<(Point, usize)> = self
+            .map
+            .keys()
+            .filter_map(|point| {
+                if let Some(index) = capitals.iter().position(|(capital, _)| capital == point) {
+                    return Some((*point, index));
+                }
+
+                capitals
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, (capital, strength))| {
+                        let distance = capital.distance(point);
+                        let reach = BASE_REACH + self.map[capital].tech_level.code as u32 / 4;
+                        (distance <= reach).then(|| (index, strength - 2 * distance as i32))
+                    })
+                    .max_by_key(|(_, influence)| *influence)
+                    .map(|(index, _)| (*point, index))
+            })
+            .collect();
+
+        for (point, index) in assignments {
+            let code = self.polities[index].code.clone();
+            if let Some(world) = self.map.get_mut(&point) {
+                world.allegiance = code;
+            }
+        }
+
+        for index in 0..self.polities.len() {
+            self.polities[index].summary = self.polity_summary(index);
+        }
+    }
+
+    /** Write a short description of the `Polity` at `index` from its capital and member worlds. */
+    fn polity_summary(&self, index: usize) -> String {
+        let polity = &self.polities[index];
+        let capital = &self.map[&polity.capital];
+        let members: Vec<&World> = self
+            .map
+            .values()
+            .filter(|world| world.allegiance == polity.code)
+            .collect();
+
+        let min_tech = members.iter().map(|w| w.tech_level.code).min().unwrap_or(0);
+        let max_tech = members.iter().map(|w| w.tech_level.code).max().unwrap_or(0);
+        let inhabited = members.iter().filter(|w| w.population.code > 0).count();
+
+        let reach = match members.len() {
+            1 => "claims no worlds beyond its capital".to_string(),
+            n => format!(
+                "holds {} worlds, {} of them inhabited, ranging from TL {} to TL {}",
+                n, inhabited, min_tech, max_tech
+            ),
+        };
+
+        let government = match capital.government.code {
+            0 => "no formal government".to_string(),
+            _ => format!("a {}", capital.government.kind.to_lowercase()),
+        };
+
+        format!(
+            "The {} is ruled from {} ({}, population {}) under {}. It {}.",
+            polity.name,
+            capital.name,
+            polity.capital,
+            capital.population.inhabitants.to_lowercase(),
+            government,
+            reach
+        )
+    }
+
+    /** Make a two letter allegiance code from `name` that no existing `Polity` uses. */
+    fn unique_polity_code(&self, name: &str) -> String {
+        let letters: Vec<char> = name.chars().filter(|c| c.is_alphabetic()).collect();
+        let first = letters
+            .first()
+            .map(|c| c.to_ascii_uppercase())
+            .unwrap_or('X');
+
+        let is_unused = |code: &String| self.polities.iter().all(|p| &p.code != code);
+        letters
+            .iter()
+            .skip(1)
+            .map(|c| format!("{}{}", first, c.to_ascii_lowercase()))
+            .chain(('a'..='z').map(|c| format!("{}{}", first, c)))
+            .find(is_unused)
+            .unwrap_or_else(|| first.to_string())
+    }
+}
+
+/** How strong a capital `world` makes, from its population, tech level, and importance. */
+fn capital_strength(world: &World) -> i32 {
+    world.population.code as i32 + world.tech_level.code as i32 / 2 + world.importance()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::RuleSet;
+
+    #[test]
+    fn generate_polities() {
+        const ATTEMPTS: usize = 100;
+        for _ in 0..ATTEMPTS {
+            let mut subsector = Subsector::new(0, 0, RuleSet::default());
+            subsector.generate_polities();
+
+            let polities = subsector.polities().to_vec();
+            assert!(polities.len() <= MAX_POLITIES);
+
+            for polity in &polities {
+                // Capitals always belong to their own polity
+                let capital = subsector.get_world(&polity.capital).unwrap();
+                assert_eq!(capital.allegiance, polity.code);
+                assert!(polity.summary.contains(&capital.name));
+            }
+
+            let aligned = subsector
+                .get_map()
+                .values()
+                .filter(|world| !world.allegiance.is_empty())
+                .count();
+            let members: usize = (0..polities.len())
+                .map(|index| subsector.polity_member_count(index))
+                .sum();
+            assert_eq!(members, aligned);
+
+            let codes: Vec<_> = polities.iter().map(|polity| &polity.code).collect();
+            for (point, world) in subsector.get_map().clone() {
+                assert!(world.allegiance.is_empty() || codes.contains(&&world.allegiance));
+                assert_eq!(
+                    subsector.polity_index(&point).is_some(),
+                    !world.allegiance.is_empty()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn unique_polity_codes() {
+        let mut subsector = Subsector::empty();
+        let mut polity = |name: &str| {
+            let code = subsector.unique_polity_code(name);
+            subsector.polities.push(Polity {
+                name: name.to_string(),
+                code: code.clone(),
+                capital: Point { x: 1, y: 1 },
+                summary: String::new(),
+            });
+            code
+        };
+
+        assert_eq!(polity("Regina"), "Re");
+        assert_eq!(polity("Reacher"), "Ra");
+        assert_eq!(polity("Rhylanor"), "Rh");
+    }
+}
@@ -0,0 +1,980 @@
+use std::{collections::BTreeMap, error::Error, fmt, ops::Deref, path::Path, sync::RwLock};
+
+use lazy_static::lazy_static;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::astrography::{StarportDetails, TradeCode};
+use crate::dice;
+
+const ATMO_TABLE_CSV: &str = include_str!("../../resources/tables/atmospheres.csv");
+const TEMP_TABLE_CSV: &str = include_str!("../../resources/tables/temperatures.csv");
+const HYDRO_TABLE_CSV: &str = include_str!("../../resources/tables/hydrographics.csv");
+const POP_TABLE_CSV: &str = include_str!("../../resources/tables/populations.csv");
+const GOV_TABLE_CSV: &str = include_str!("../../resources/tables/governments.csv");
+const FACTION_TABLE_CSV: &str = include_str!("../../resources/tables/factions.csv");
+const CULTURE_TABLE_CSV: &str = include_str!("../../resources/tables/cultural_differences.csv");
+const WORLD_TAG_TABLE_CSV: &str = include_str!("../../resources/tables/world_tags.csv");
+const LAW_TABLE_CSV: &str = include_str!("../../resources/tables/law_levels.csv");
+const STARPORT_TABLE_CSV: &str = include_str!("../../resources/tables/starports.csv");
+const TECH_LEVEL_CSV: &str = include_str!("../../resources/tables/tech_levels.csv");
+const TRADE_CODE_CSV: &str = include_str!("../../resources/tables/trade_codes.csv");
+
+/// Translated tables loaded by `set_table_language`; `None` while the built-in English tables are
+/// in use
+static TRANSLATED_TABLES: RwLock<Option<&'static RandomizationTableCollection>> = RwLock::new(None);
+/// Row overrides set by `set_table_overrides`, merged over the translated or built-in tables
+static TABLE_OVERRIDES: RwLock<Option<TableOverrides>> = RwLock::new(None);
+/// Tables currently in use, with any overrides merged in; `None` while the built-in tables are in
+/// use as they are
+static ACTIVE_TABLES: RwLock<Option<&'static RandomizationTableCollection>> = RwLock::new(None);
+
+/** Trait representing a record or row in a table. */
+trait Record {
+    /** Get the `code` of this `Record`; i.e. its index in the table.
+
+    This *must* match the physical row index of the `Record` in the table.
+    */
+    fn code(&self) -> u16;
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AtmoRecord {
+    pub code: u16,
+    pub composition: String,
+}
+
+impl Record for AtmoRecord {
+    fn code(&self) -> u16 {
+        self.code
+    }
+}
+type AtmoTable = Vec<AtmoRecord>;
+
+#[derive(Clone, Debug, Deserialize, Eq, Serialize)]
+pub struct TempRecord {
+    pub code: u16,
+    pub kind: String,
+    pub description: String,
+}
+
+impl PartialEq for TempRecord {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind && self.description == other.description
+    }
+}
+
+impl Record for TempRecord {
+    fn code(&self) -> u16 {
+        self.code
+    }
+}
+type TempTable = Vec<TempRecord>;
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct HydroRecord {
+    pub code: u16,
+    pub description: String,
+}
+
+impl Record for HydroRecord {
+    fn code(&self) -> u16 {
+        self.code
+    }
+}
+type HydroTable = Vec<HydroRecord>;
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PopRecord {
+    pub code: u16,
+    pub inhabitants: String,
+}
+
+impl Record for PopRecord {
+    fn code(&self) -> u16 {
+        self.code
+    }
+}
+type PopTable = Vec<PopRecord>;
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct GovRecord {
+    pub code: u16,
+    pub kind: String,
+    pub description: String,
+    pub contraband: String,
+}
+
+impl GovRecord {
+    /** Mutate `self` into `other`, but retain non-default `description` and `contraband` fields. */
+    pub fn safe_mutate(&mut self, other: &Self) {
+        let Self {
+            code: new_code,
+            kind: new_kind,
+            description: new_desc,
+            contraband: new_contra,
+        } = other;
+
+        let code = self.code as usize;
+        if self.description == TABLES.gov_table[code].description
+            || self.description == BUILTIN_TABLES.gov_table[code].description
+        {
+            self.description = new_desc.clone();
+        }
+        if self.contraband == TABLES.gov_table[code].contraband
+            || self.contraband == BUILTIN_TABLES.gov_table[code].contraband
+        {
+            self.contraband = new_contra.clone();
+        }
+
+        self.code = *new_code;
+        self.kind = new_kind.clone();
+    }
+}
+
+impl Record for GovRecord {
+    fn code(&self) -> u16 {
+        self.code
+    }
+}
+type GovTable = Vec<GovRecord>;
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct FactionStrengthRecord {
+    pub code: u16,
+    pub strength: String,
+}
+
+impl Record for FactionStrengthRecord {
+    fn code(&self) -> u16 {
+        self.code
+    }
+}
+type FactionTable = Vec<FactionStrengthRecord>;
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CulturalDiffRecord {
+    pub code: u16,
+    pub cultural_difference: String,
+    pub description: String,
+}
+
+impl CulturalDiffRecord {
+    /** Mutate `self` into `other`, but retain non-default `description` fields. */
+    pub fn safe_mutate(&mut self, other: &Self) {
+        let Self {
+            code: new_code,
+            cultural_difference: new_culture,
+            description: new_desc,
+        } = other;
+
+        let code = self.code as usize;
+        if self.description == TABLES.culture_table[code].description
+            || self.description == BUILTIN_TABLES.culture_table[code].description
+        {
+            self.description = new_desc.clone();
+        }
+
+        self.code = *new_code;
+        self.cultural_difference = new_culture.clone();
+    }
+}
+
+impl Record for CulturalDiffRecord {
+    fn code(&self) -> u16 {
+        self.code
+    }
+}
+type CulturalDiffTable = Vec<CulturalDiffRecord>;
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct WorldTagRecord {
+    pub code: u16,
+    pub tag: String,
+    pub description: String,
+}
+
+impl WorldTagRecord {
+    /** Mutate `self` into `other`, but retain non-default `description` fields. */
+    pub fn safe_mutate(&mut self, other: &Self) {
+        let Self {
+            code: new_code,
+            tag: new_tag,
+            description: new_desc,
+        } = other;
+
+        let code = self.code as usize;
+        if self.description == TABLES.world_tag_table[code].description
+            || self.description == BUILTIN_TABLES.world_tag_table[code].description
+        {
+            self.description = new_desc.clone();
+        }
+
+        self.code = *new_code;
+        self.tag = new_tag.clone();
+    }
+}
+
+impl Record for WorldTagRecord {
+    fn code(&self) -> u16 {
+        self.code
+    }
+}
+type WorldTagTable = Vec<WorldTagRecord>;
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct LawRecord {
+    pub code: u16,
+    pub banned_weapons: String,
+    pub banned_armor: String,
+}
+
+impl Record for LawRecord {
+    fn code(&self) -> u16 {
+        self.code
+    }
+}
+type LawTable = Vec<LawRecord>;
+
+#[derive(Clone, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum StarportClass {
+    A,
+    B,
+    C,
+    D,
+    E,
+    X,
+}
+
+impl fmt::Display for StarportClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::A => "A",
+            Self::B => "B",
+            Self::C => "C",
+            Self::D => "D",
+            Self::E => "E",
+            Self::X => "X",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, Serialize)]
+pub struct StarportRecord {
+    pub code: u16,
+    pub class: StarportClass,
+    pub berthing_cost: u32,
+    pub fuel: String,
+    pub facilities: String,
+    /// Details of a particular world's starport; never set in the starport table itself
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<StarportDetails>,
+}
+
+impl PartialEq for StarportRecord {
+    fn eq(&self, other: &Self) -> bool {
+        self.class == other.class
+            && self.berthing_cost == other.berthing_cost
+            && self.fuel == other.fuel
+            && self.facilities == other.facilities
+            && self.details == other.details
+    }
+}
+
+impl Record for StarportRecord {
+    fn code(&self) -> u16 {
+        self.code
+    }
+}
+type StarportTable = Vec<StarportRecord>;
+
+#[derive(Clone, Debug, Deserialize, Eq, Serialize)]
+pub struct TechLevelRecord {
+    pub code: u16,
+    pub description: String,
+}
+
+impl PartialEq for TechLevelRecord {
+    fn eq(&self, other: &Self) -> bool {
+        self.code == other.code
+    }
+}
+
+impl Record for TechLevelRecord {
+    fn code(&self) -> u16 {
+        self.code
+    }
+}
+type TechLevelTable = Vec<TechLevelRecord>;
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TradeCodeRecord {
+    pub code: u16,
+    pub trade_code: TradeCode,
+    pub name: String,
+}
+
+impl Record for TradeCodeRecord {
+    fn code(&self) -> u16 {
+        self.code
+    }
+}
+type TradeCodeTable = Vec<TradeCodeRecord>;
+
+pub trait Table<T> {
+    /** Get a reference to an item within the `Table` using a straight "1d6" roll. */
+    fn roll_1d6(&self, modifier: i32) -> &T;
+
+    /** Get a reference to an item within the `Table` using a "2d6" normal distribution. */
+    fn roll_normal_2d6(&self, modifier: i32) -> &T;
+
+    /** Get a reference to an item within the `Table` using a uniform distribution. */
+    fn roll_uniform(&self) -> &T;
+}
+
+impl<T, U> Table<T> for U
+where
+    U: Deref<Target = [T]>,
+{
+    /** Get a reference to an item within the `Table` using a straight "1d6" roll.
+
+    The value of `modifier` is added to the result of the 1d6 roll, however all rolls are
+    clamped to be in-bounds for the `Table`.
+
+    # Panics
+    Panics if the `Table` is empty.
+    */
+    fn roll_1d6(&self, modifier: i32) -> &T {
+        assert!(!self.is_empty(), "Cannot roll on an empty table");
+        let roll = dice::roll_1d(6);
+        let modified_roll = roll + modifier;
+
+        let low = 0;
+        let high = (self.len() - 1) as i32;
+        let index = (modified_roll).clamp(low, high) as usize;
+        dice::record_table_row(modifier, index);
+        &self[index]
+    }
+
+    /** Get a reference to an item within the `Table` using a "2d6" normal distribution.
+
+    The value of `modifier` is added to the result of the 2d6 roll, however all rolls are
+    clamped to be in-bounds for the `Table`.
+    Because of this, double-peaks in the outcome of these rolls will tend to appear at the top or
+    bottom of the table's domain when `modifier` is significantly greater than or less than zero,
+    respectively.
+
+    # Panics
+    Panics if the `Table` is empty.
+    */
+    fn roll_normal_2d6(&self, modifier: i32) -> &T {
+        assert!(!self.is_empty(), "Cannot roll on an empty table");
+        let roll = dice::roll_2d(6);
+        let modified_roll = roll + modifier;
+
+        let low = 0;
+        let high = (self.len() - 1) as i32;
+        let index = (modified_roll).clamp(low, high) as usize;
+        dice::record_table_row(modifier, index);
+        &self[index]
+    }
+
+    /** Get a reference to an item within the `Table` using a uniform distribution.
+
+    # Panics
+    Panics if the `Table` is empty.
+    */
+    fn roll_uniform(&self) -> &T {
+        assert!(!self.is_empty(), "Cannot roll on an empty table");
+        // Rolled as a die with a side for each row so the roll can be recorded
+        let index = dice::roll_1d(self.len()) - 1;
+        dice::record_table_row(0, index);
+        &self[index]
+    }
+}
+
+fn load_table<T: for<'de> Deserialize<'de> + Record>(table_csv: &str) -> Vec<T> {
+    try_load_table(table_csv).expect("Randomization csv's should always be valid")
+}
+
+fn try_load_table<T: for<'de> Deserialize<'de> + Record>(
+    table_csv: &str,
+) -> Result<Vec<T>, Box<dyn Error>> {
+    let mut table = Vec::new();
+    let mut reader = csv::ReaderBuilder::new()
+        .comment(Some(b'#'))
+        .from_reader(table_csv.as_bytes());
+    for (index, result) in reader.deserialize().enumerate() {
+        let record: T = result?;
+        if record.code() != index as u16 {
+            return Err(format!(
+                "The code field in row {} must match its zero-indexed position in the table",
+                index
+            )
+            .into());
+        }
+        table.push(record);
+    }
+    Ok(table)
+}
+
+/** Load the translation of a table from `file_name` in `directory`, if there is one.
+
+# Returns
+- `Ok(table)` with the translated table, or a copy of `builtin` if there is no translation
+- `Err` if the translation could not be read, is invalid, or has a different number of rows than
+  `builtin`
+*/
+fn load_translated_table<T>(
+    directory: &Path,
+    file_name: &str,
+    builtin: &[T],
+) -> Result<Vec<T>, Box<dyn Error>>
+where
+    T: Clone + for<'de> Deserialize<'de> + Record,
+{
+    let path = directory.join(file_name);
+    if !path.exists() {
+        return Ok(builtin.to_vec());
+    }
+
+    let table: Vec<T> = try_load_table(&std::fs::read_to_string(&path)?)
+        .map_err(|e| format!("{}: {}", file_name, e))?;
+    if table.len() != builtin.len() {
+        return Err(format!(
+            "{} has {} rows, but it should have {}",
+            file_name,
+            table.len(),
+            builtin.len()
+        )
+        .into());
+    }
+    Ok(table)
+}
+
+/** Get the columns of `record` as they are written in its table's CSV file, as `(column, value)`
+pairs.
+*/
+fn record_fields<T: Serialize>(record: &T) -> Vec<(String, String)> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer
+        .serialize(record)
+        .expect("Table records should always serialize");
+    let csv = writer
+        .into_inner()
+        .expect("Writing to a Vec should not fail");
+
+    let mut reader = csv::Reader::from_reader(&csv[..]);
+    let headers = reader.headers().unwrap().clone();
+    let values = reader.records().next().unwrap().unwrap();
+    headers
+        .iter()
+        .zip(values.iter())
+        .map(|(column, value)| (column.to_string(), value.to_string()))
+        .collect()
+}
+
+/** Override the columns of row `code` of `table` with `fields`, which map column names to values.
+
+# Returns
+`Err` if there is no such row, a column doesn't exist or is the row's code, or a value can't be
+parsed for its column.
+*/
+fn override_row<T: Serialize + DeserializeOwned>(
+    table: &mut [T],
+    code: u16,
+    fields: &BTreeMap<String, String>,
+) -> Result<(), Box<dyn Error>> {
+    let record = table
+        .get_mut(code as usize)
+        .ok_or_else(|| format!("There is no row {}", code))?;
+
+    let mut row = record_fields(&*record);
+    for (column, value) in fields {
+        match row.iter_mut().find(|(name, _)| name == column) {
+            Some((name, old)) if name != "code" => *old = value.clone(),
+            _ => {
+                let message = format!("Row {} has no column \"{}\" to override", code, column);
+                return Err(message.into());
+            }
+        }
+    }
+
+    let headers: csv::StringRecord = row.iter().map(|(column, _)| column).collect();
+    let values: csv::StringRecord = row.iter().map(|(_, value)| value).collect();
+    *record = values
+        .deserialize(Some(&headers))
+        .map_err(|e| format!("Row {}: {}", code, e))?;
+    Ok(())
+}
+
+#[allow(dead_code)]
+fn test_table(file_path: &str) {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_path(file_path)
+        .unwrap();
+
+    for (index, result) in reader.records().enumerate() {
+        let record = result.unwrap();
+        println!("record[{}]: {:?}", index, record);
+    }
+}
+
+/** One of the tables of a [`RandomizationTableCollection`]. */
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum TableKind {
+    Atmospheres,
+    Temperatures,
+    Hydrographics,
+    Populations,
+    Governments,
+    Factions,
+    CulturalDifferences,
+    WorldTags,
+    LawLevels,
+    Starports,
+    TechLevels,
+    TradeCodes,
+}
+
+impl TableKind {
+    pub const ALL_VALUES: [TableKind; 12] = [
+        Self::Atmospheres,
+        Self::Temperatures,
+        Self::Hydrographics,
+        Self::Populations,
+        Self::Governments,
+        Self::Factions,
+        Self::CulturalDifferences,
+        Self::WorldTags,
+        Self::LawLevels,
+        Self::Starports,
+        Self::TechLevels,
+        Self::TradeCodes,
+    ];
+}
+
+impl fmt::Display for TableKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Atmospheres => "Atmospheres",
+            Self::Temperatures => "Temperatures",
+            Self::Hydrographics => "Hydrographics",
+            Self::Populations => "Populations",
+            Self::Governments => "Governments",
+            Self::Factions => "Factions",
+            Self::CulturalDifferences => "Cultural Differences",
+            Self::WorldTags => "World Tags",
+            Self::LawLevels => "Law Levels",
+            Self::Starports => "Starports",
+            Self::TechLevels => "Tech Levels",
+            Self::TradeCodes => "Trade Codes",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** Errata and house-rule changes to individual rows of the tables, merged over the built-in or
+translated tables with [`set_table_overrides`].
+
+Each overridden row only lists the columns that are changed, by the same names as the columns of the
+table's CSV file. Codes can't be overridden.
+*/
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct TableOverrides {
+    rows: BTreeMap<TableKind, BTreeMap<u16, BTreeMap<String, String>>>,
+}
+
+impl TableOverrides {
+    pub fn try_from_json(json: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /** Get the overridden columns of row `code` of `table`, if it has any. */
+    pub fn row(&self, table: TableKind, code: u16) -> Option<&BTreeMap<String, String>> {
+        self.rows.get(&table)?.get(&code)
+    }
+
+    /** Override the columns of row `code` of `table` with `fields`, replacing any earlier overrides
+    of the row; an empty `fields` removes them.
+    */
+    pub fn set_row(&mut self, table: TableKind, code: u16, fields: BTreeMap<String, String>) {
+        if fields.is_empty() {
+            if let Some(rows) = self.rows.get_mut(&table) {
+                rows.remove(&code);
+                if rows.is_empty() {
+                    self.rows.remove(&table);
+                }
+            }
+        } else {
+            self.rows.entry(table).or_default().insert(code, fields);
+        }
+    }
+}
+
+/** Every table that text shown to the user is drawn from.
+
+The text of the tables can be translated by placing CSV files with the same names and columns as the
+built-in ones in a directory and loading it with [`set_table_language`]. Only the text columns
+should be translated; codes and numbers must stay the same. Tables without a translated file stay in
+English.
+*/
+#[derive(Clone, Debug)]
+pub struct RandomizationTableCollection {
+    pub atmo_table: AtmoTable,
+    pub temp_table: TempTable,
+    pub hydro_table: HydroTable,
+    pub pop_table: PopTable,
+    pub gov_table: GovTable,
+    pub faction_table: FactionTable,
+    pub culture_table: CulturalDiffTable,
+    pub world_tag_table: WorldTagTable,
+    pub law_table: LawTable,
+    pub starport_table: StarportTable,
+    pub tech_level_table: TechLevelTable,
+    pub trade_code_table: TradeCodeTable,
+}
+
+impl RandomizationTableCollection {
+    fn new() -> RandomizationTableCollection {
+        RandomizationTableCollection {
+            atmo_table: load_table(ATMO_TABLE_CSV),
+            temp_table: load_table(TEMP_TABLE_CSV),
+            hydro_table: load_table(HYDRO_TABLE_CSV),
+            pop_table: load_table(POP_TABLE_CSV),
+            gov_table: load_table(GOV_TABLE_CSV),
+            faction_table: load_table(FACTION_TABLE_CSV),
+            culture_table: load_table(CULTURE_TABLE_CSV),
+            world_tag_table: load_table(WORLD_TAG_TABLE_CSV),
+            law_table: load_table(LAW_TABLE_CSV),
+            starport_table: load_table(STARPORT_TABLE_CSV),
+            tech_level_table: load_table(TECH_LEVEL_CSV),
+            trade_code_table: load_table(TRADE_CODE_CSV),
+        }
+    }
+
+    /** Load the built-in tables, replacing any that have a translation in `directory`. */
+    fn load_translation(directory: &Path) -> Result<Self, Box<dyn Error>> {
+        let builtin = &*BUILTIN_TABLES;
+        Ok(Self {
+            atmo_table: load_translated_table(directory, "atmospheres.csv", &builtin.atmo_table)?,
+            temp_table: load_translated_table(directory, "temperatures.csv", &builtin.temp_table)?,
+            hydro_table: load_translated_table(
+                directory,
+                "hydrographics.csv",
+                &builtin.hydro_table,
+            )?,
+            pop_table: load_translated_table(directory, "populations.csv", &builtin.pop_table)?,
+            gov_table: load_translated_table(directory, "governments.csv", &builtin.gov_table)?,
+            faction_table: load_translated_table(
+                directory,
+                "factions.csv",
+                &builtin.faction_table,
+            )?,
+            culture_table: load_translated_table(
+                directory,
+                "cultural_differences.csv",
+                &builtin.culture_table,
+            )?,
+            world_tag_table: load_translated_table(
+                directory,
+                "world_tags.csv",
+                &builtin.world_tag_table,
+            )?,
+            law_table: load_translated_table(directory, "law_levels.csv", &builtin.law_table)?,
+            starport_table: load_translated_table(
+                directory,
+                "starports.csv",
+                &builtin.starport_table,
+            )?,
+            tech_level_table: load_translated_table(
+                directory,
+                "tech_levels.csv",
+                &builtin.tech_level_table,
+            )?,
+            trade_code_table: load_translated_table(
+                directory,
+                "trade_codes.csv",
+                &builtin.trade_code_table,
+            )?,
+        })
+    }
+}
+
+impl RandomizationTableCollection {
+    /** Get the number of rows in `table`. */
+    pub fn table_len(&self, table: TableKind) -> usize {
+        match table {
+            TableKind::Atmospheres => self.atmo_table.len(),
+            TableKind::Temperatures => self.temp_table.len(),
+            TableKind::Hydrographics => self.hydro_table.len(),
+            TableKind::Populations => self.pop_table.len(),
+            TableKind::Governments => self.gov_table.len(),
+            TableKind::Factions => self.faction_table.len(),
+            TableKind::CulturalDifferences => self.culture_table.len(),
+            TableKind::WorldTags => self.world_tag_table.len(),
+            TableKind::LawLevels => self.law_table.len(),
+            TableKind::Starports => self.starport_table.len(),
+            TableKind::TechLevels => self.tech_level_table.len(),
+            TableKind::TradeCodes => self.trade_code_table.len(),
+        }
+    }
+
+    /** Get every column of row `code` of `table` except its code, as `(column, value)` pairs.
+
+    # Returns
+    `None` if `table` has no row `code`.
+    */
+    pub fn row_fields(&self, table: TableKind, code: u16) -> Option<Vec<(String, String)>> {
+        let index = code as usize;
+        let fields = match table {
+            TableKind::Atmospheres => record_fields(self.atmo_table.get(index)?),
+            TableKind::Temperatures => record_fields(self.temp_table.get(index)?),
+            TableKind::Hydrographics => record_fields(self.hydro_table.get(index)?),
+            TableKind::Populations => record_fields(self.pop_table.get(index)?),
+            TableKind::Governments => record_fields(self.gov_table.get(index)?),
+            TableKind::Factions => record_fields(self.faction_table.get(index)?),
+            TableKind::CulturalDifferences => record_fields(self.culture_table.get(index)?),
+            TableKind::WorldTags => record_fields(self.world_tag_table.get(index)?),
+            TableKind::LawLevels => record_fields(self.law_table.get(index)?),
+            TableKind::Starports => record_fields(self.starport_table.get(index)?),
+            TableKind::TechLevels => record_fields(self.tech_level_table.get(index)?),
+            TableKind::TradeCodes => record_fields(self.trade_code_table.get(index)?),
+        };
+        Some(
+            fields
+                .into_iter()
+                .filter(|(column, _)| column != "code")
+                .collect(),
+        )
+    }
+
+    /** Create a copy of the tables with `overrides` merged over them.
+
+    # Returns
+    `Err` naming the table and row of the first override that can't be applied.
+    */
+    fn with_overrides(&self, overrides: &TableOverrides) -> Result<Self, Box<dyn Error>> {
+        let mut tables = self.clone();
+        for (table, rows) in &overrides.rows {
+            for (code, fields) in rows {
+                let code = *code;
+                match table {
+                    TableKind::Atmospheres => override_row(&mut tables.atmo_table, code, fields),
+                    TableKind::Temperatures => override_row(&mut tables.temp_table, code, fields),
+                    TableKind::Hydrographics => override_row(&mut tables.hydro_table, code, fields),
+                    TableKind::Populations => override_row(&mut tables.pop_table, code, fields),
+                    TableKind::Governments => override_row(&mut tables.gov_table, code, fields),
+                    TableKind::Factions => override_row(&mut tables.faction_table, code, fields),
+                    TableKind::CulturalDifferences => {
+                        override_row(&mut tables.culture_table, code, fields)
+                    }
+                    TableKind::WorldTags => override_row(&mut tables.world_tag_table, code, fields),
+                    TableKind::LawLevels => override_row(&mut tables.law_table, code, fields),
+                    TableKind::Starports => override_row(&mut tables.starport_table, code, fields),
+                    TableKind::TechLevels => {
+                        override_row(&mut tables.tech_level_table, code, fields)
+                    }
+                    TableKind::TradeCodes => {
+                        override_row(&mut tables.trade_code_table, code, fields)
+                    }
+                }
+                .map_err(|e| format!("{} table: {}", table, e))?;
+            }
+        }
+        Ok(tables)
+    }
+}
+
+/** Handle to the tables in use, which are the built-in ones unless a translation or overrides have
+been loaded.
+*/
+pub struct ActiveTables;
+
+impl Deref for ActiveTables {
+    type Target = RandomizationTableCollection;
+
+    fn deref(&self) -> &Self::Target {
+        let active = *ACTIVE_TABLES.read().unwrap();
+        active.unwrap_or(&BUILTIN_TABLES)
+    }
+}
+
+pub static TABLES: ActiveTables = ActiveTables;
+
+lazy_static! {
+    /// The English tables built into the app
+    pub static ref BUILTIN_TABLES: RandomizationTableCollection =
+        RandomizationTableCollection::new();
+}
+
+/** Use the translated tables in `directory` for all newly generated text, or the built-in English
+tables if `directory` is `None`.
+
+Text already copied into existing worlds is not changed. The tables of previous translations are
+never freed since references to them may still be held; they are small and switching languages is
+rare, so this is not a concern in practice.
+
+# Returns
+`Err` without changing the tables in use if any translated table is invalid.
+*/
+pub fn set_table_language(directory: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    let translated = match directory {
+        Some(directory) => {
+            let tables = RandomizationTableCollection::load_translation(directory)?;
+            Some(&*Box::leak(Box::new(tables)))
+        }
+        None => None,
+    };
+
+    let overrides = TABLE_OVERRIDES.read().unwrap().clone();
+    set_active_tables(translated, overrides.as_ref())?;
+    *TRANSLATED_TABLES.write().unwrap() = translated;
+    Ok(())
+}
+
+/** Merge `overrides` over the translated or built-in tables for all newly generated text, or stop
+overriding them if `overrides` is `None`.
+
+As with [`set_table_language`], text already copied into existing worlds is not changed.
+
+# Returns
+`Err` without changing the tables in use if any override can't be applied.
+*/
+pub fn set_table_overrides(overrides: Option<TableOverrides>) -> Result<(), Box<dyn Error>> {
+    let translated = *TRANSLATED_TABLES.read().unwrap();
+    set_active_tables(translated, overrides.as_ref())?;
+    *TABLE_OVERRIDES.write().unwrap() = overrides;
+    Ok(())
+}
+
+/** Get the tables in use without any overrides merged in: the translated tables if there are any, or
+else the built-in ones.
+*/
+pub fn tables_without_overrides() -> &'static RandomizationTableCollection {
+    let translated = *TRANSLATED_TABLES.read().unwrap();
+    translated.unwrap_or(&BUILTIN_TABLES)
+}
+
+/** Make the tables in use `translated`, or the built-in tables if `None`, with `overrides` merged
+over them.
+*/
+fn set_active_tables(
+    translated: Option<&'static RandomizationTableCollection>,
+    overrides: Option<&TableOverrides>,
+) -> Result<(), Box<dyn Error>> {
+    let active = match overrides {
+        Some(overrides) if !overrides.is_empty() => {
+            let base = translated.unwrap_or(&BUILTIN_TABLES);
+            let tables = base.with_overrides(overrides)?;
+            Some(&*Box::leak(Box::new(tables)))
+        }
+        _ => translated,
+    };
+    *ACTIVE_TABLES.write().unwrap() = active;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_all_tables() {
+        // No easy way to check the contents, just make sure they all load without panic
+        RandomizationTableCollection::new();
+    }
+
+    #[test]
+    fn load_translated_tables() {
+        let directory =
+            std::env::temp_dir().join(format!("swt-gen-translation-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+
+        // Only the tables with a file are translated
+        let mut governments = String::from("code,kind,description,contraband\n");
+        for record in BUILTIN_TABLES.gov_table.iter() {
+            governments += &format!(
+                "{},\"Gouvernement {}\",\"-\",\"-\"\n",
+                record.code, record.code
+            );
+        }
+        std::fs::write(directory.join("governments.csv"), governments).unwrap();
+
+        let tables = RandomizationTableCollection::load_translation(&directory).unwrap();
+        assert_eq!(tables.gov_table[1].kind, "Gouvernement 1");
+        assert_eq!(tables.atmo_table, BUILTIN_TABLES.atmo_table);
+        assert_eq!(tables.trade_code_table, BUILTIN_TABLES.trade_code_table);
+
+        // Translations must have exactly as many rows as the built-in tables
+        std::fs::write(
+            directory.join("tech_levels.csv"),
+            "code,description\n0,\"Âge de pierre\"\n",
+        )
+        .unwrap();
+        assert!(RandomizationTableCollection::load_translation(&directory).is_err());
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn table_overrides() {
+        let mut overrides = TableOverrides::default();
+        let fields = |pairs: &[(&str, &str)]| -> BTreeMap<String, String> {
+            pairs
+                .iter()
+                .map(|(column, value)| (column.to_string(), value.to_string()))
+                .collect()
+        };
+        overrides.set_row(
+            TableKind::Governments,
+            7,
+            fields(&[("description", "Errata: many small nations")]),
+        );
+        overrides.set_row(TableKind::Starports, 1, fields(&[("berthing_cost", "750")]));
+
+        let overrides = TableOverrides::try_from_json(&overrides.to_json()).unwrap();
+        let tables = BUILTIN_TABLES.with_overrides(&overrides).unwrap();
+        assert_eq!(
+            tables.gov_table[7].description,
+            "Errata: many small nations"
+        );
+        assert_eq!(tables.gov_table[7].kind, BUILTIN_TABLES.gov_table[7].kind);
+        assert_eq!(tables.starport_table[1].berthing_cost, 750);
+        assert_eq!(tables.atmo_table, BUILTIN_TABLES.atmo_table);
+        assert!(tables
+            .row_fields(TableKind::Governments, 7)
+            .unwrap()
+            .contains(&(
+                "description".to_string(),
+                "Errata: many small nations".to_string()
+            )));
+
+        // Codes, unknown columns, unparsable values, and missing rows can't be overridden
+        for (table, code, pairs) in [
+            (TableKind::Governments, 7, [("code", "8")]),
+            (TableKind::Governments, 7, [("flavor", "text")]),
+            (TableKind::Starports, 1, [("berthing_cost", "lots")]),
+            (TableKind::Atmospheres, 99, [("composition", "Thin")]),
+        ] {
+            let mut invalid = TableOverrides::default();
+            invalid.set_row(table, code, fields(&pairs));
+            assert!(BUILTIN_TABLES.with_overrides(&invalid).is_err());
+        }
+
+        let mut overrides = overrides;
+        overrides.set_row(TableKind::Governments, 7, BTreeMap::new());
+        overrides.set_row(TableKind::Starports, 1, BTreeMap::new());
+        assert!(overrides.is_empty());
+    }
+}
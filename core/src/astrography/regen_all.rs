@@ -0,0 +1,135 @@
+use std::fmt;
+
+use crate::astrography::{Subsector, World};
+
+/** Attribute of a `World` that can be rolled again on its own, without rolling anything else. */
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum WorldAttribute {
+    Culture,
+    Encounters,
+    GasGiants,
+    Government,
+    Hazards,
+    PlanetoidBelts,
+    Stellar,
+    Temperature,
+    WorldTags,
+}
+
+impl WorldAttribute {
+    pub const ALL_VALUES: [WorldAttribute; 9] = [
+        Self::Culture,
+        Self::Encounters,
+        Self::GasGiants,
+        Self::Government,
+        Self::Hazards,
+        Self::PlanetoidBelts,
+        Self::Stellar,
+        Self::Temperature,
+        Self::WorldTags,
+    ];
+}
+
+impl fmt::Display for WorldAttribute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Culture => "Culture",
+            Self::Encounters => "Encounters",
+            Self::GasGiants => "Gas Giants",
+            Self::Government => "Government",
+            Self::Hazards => "Hazards",
+            Self::PlanetoidBelts => "Planetoid Belts",
+            Self::Stellar => "Stellar Data",
+            Self::Temperature => "Temperature",
+            Self::WorldTags => "World Tags",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl World {
+    /** Roll `attribute` again, keeping any descriptions the user has written for it.
+
+    Trade codes are resolved again afterward, as is the travel code unless it was set by hand.
+    */
+    pub fn regenerate_attribute(&mut self, attribute: WorldAttribute) {
+        match attribute {
+            WorldAttribute::Culture => {
+                let mut old_culture = self.culture.clone();
+                self.generate_culture();
+                old_culture.safe_mutate(&self.culture);
+                self.culture = old_culture;
+            }
+            WorldAttribute::Encounters => self.generate_encounters(),
+            WorldAttribute::GasGiants => self.generate_gas_giants(),
+            WorldAttribute::Government => {
+                let mut old_gov = self.government.clone();
+                self.generate_government();
+                old_gov.safe_mutate(&self.government);
+                self.government = old_gov;
+            }
+            WorldAttribute::Hazards => self.generate_hazards(),
+            WorldAttribute::PlanetoidBelts => self.generate_planetoid_belts(),
+            WorldAttribute::Stellar => self.generate_stellar(),
+            WorldAttribute::Temperature => self.generate_temperature(),
+            WorldAttribute::WorldTags => {
+                for index in 0..self.world_tags.len() {
+                    if let Some(mut old_tag) = self.generate_world_tag(index) {
+                        old_tag.safe_mutate(&self.world_tags[index]);
+                        self.world_tags[index] = old_tag;
+                    }
+                }
+            }
+        }
+
+        self.resolve_trade_codes();
+        if !self.travel_code_overridden {
+            self.resolve_travel_code();
+        }
+    }
+}
+
+impl Subsector {
+    /** Roll `attribute` again for every `World` in the subsector, keeping any descriptions the
+    user has written for it.
+
+    # Returns
+    The number of worlds that were rerolled.
+    */
+    pub fn regenerate_attribute(&mut self, attribute: WorldAttribute) -> usize {
+        for world in self.map.values_mut() {
+            world.regenerate_attribute(attribute);
+        }
+        self.map.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::{Point, TABLES};
+
+    #[test]
+    fn regenerate_attribute() {
+        let mut subsector = Subsector::empty();
+        for y in 1..=3 {
+            let mut world = World::empty();
+            world.culture = TABLES.culture_table[1].clone();
+            world.culture.description = format!("Edited description {}", y);
+            subsector.insert_world(&Point { x: 1, y }, world).unwrap();
+        }
+
+        assert_eq!(subsector.regenerate_attribute(WorldAttribute::Culture), 3);
+        for (point, world) in subsector.get_map() {
+            // User edited descriptions survive the reroll, whatever the new culture is
+            assert_eq!(
+                world.culture.description,
+                format!("Edited description {}", point.y)
+            );
+        }
+        assert_eq!(
+            Subsector::empty().regenerate_attribute(WorldAttribute::WorldTags),
+            0
+        );
+    }
+}
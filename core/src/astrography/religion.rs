@@ -0,0 +1,187 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::World;
+use crate::dice;
+
+const ORGANIZATION_TABLE: [&str; 6] = [
+    "No clergy at all; every household keeps its own shrine and rites",
+    "Loose network of wandering preachers who answer to no one",
+    "Local temples, each run by its own council of elders",
+    "Strict hierarchy of priests under a single high prelate",
+    "Hereditary priesthood whose families also hold secular power",
+    "Secretive orders whose true leaders are known only to initiates",
+];
+
+const SCOPE_TABLE: [&str; 6] = [
+    "A handful of devoted adherents in a single city",
+    "A minority faith, tolerated but kept at arm's length",
+    "One of several faiths with large followings and rival claims",
+    "The faith of most of the population, if not all of its devotion",
+    "The state religion, observed in every public ceremony",
+    "Spread across several worlds, with this one only a distant parish",
+];
+
+const ATTITUDE_TABLE: [&str; 6] = [
+    "Welcomes offworlders and eagerly seeks converts among them",
+    "Indifferent to outsiders so long as they keep out of the temples",
+    "Polite but wary; offworlders are watched for signs of blasphemy",
+    "Requires visitors to observe its rites while on the world",
+    "Sees outsiders as unclean and bars them from holy places and cities",
+    "Openly hostile, preaching that offworlders bring ruin with them",
+];
+
+/** Aspect of a belief system, rolled on a table of its own. */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReligionAspect {
+    Organization,
+    Scope,
+    Attitude,
+}
+
+impl ReligionAspect {
+    pub const ALL_VALUES: [ReligionAspect; 3] = [Self::Organization, Self::Scope, Self::Attitude];
+
+    /** Entries of the aspect's table, rolled on with 1D. */
+    fn table(&self) -> &'static [&'static str; 6] {
+        match self {
+            Self::Organization => &ORGANIZATION_TABLE,
+            Self::Scope => &SCOPE_TABLE,
+            Self::Attitude => &ATTITUDE_TABLE,
+        }
+    }
+}
+
+impl fmt::Display for ReligionAspect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Organization => "Organization",
+            Self::Scope => "Scope",
+            Self::Attitude => "Attitude Toward Outsiders",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** Organization, scope, and attitude toward outsiders of the dominant belief system of a `World`
+or `Faction`; empty if never rolled.
+*/
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Religion {
+    pub organization: String,
+    pub scope: String,
+    pub attitude: String,
+}
+
+impl Religion {
+    /** Make a `Religion` with every aspect rolled on its table. */
+    pub fn random() -> Self {
+        let mut religion = Self::default();
+        for aspect in ReligionAspect::ALL_VALUES {
+            religion.generate_aspect(aspect);
+        }
+        religion
+    }
+
+    pub fn get(&self, aspect: ReligionAspect) -> &String {
+        match aspect {
+            ReligionAspect::Organization => &self.organization,
+            ReligionAspect::Scope => &self.scope,
+            ReligionAspect::Attitude => &self.attitude,
+        }
+    }
+
+    pub fn get_mut(&mut self, aspect: ReligionAspect) -> &mut String {
+        match aspect {
+            ReligionAspect::Organization => &mut self.organization,
+            ReligionAspect::Scope => &mut self.scope,
+            ReligionAspect::Attitude => &mut self.attitude,
+        }
+    }
+
+    /** Roll `aspect` on its table, replacing whatever it was. */
+    pub fn generate_aspect(&mut self, aspect: ReligionAspect) {
+        let roll: i32 = dice::roll_1d(6);
+        *self.get_mut(aspect) = aspect.table()[roll as usize - 1].to_string();
+    }
+
+    /** Whether none of the aspects have been filled in. */
+    pub fn is_empty(&self) -> bool {
+        ReligionAspect::ALL_VALUES
+            .iter()
+            .all(|aspect| self.get(*aspect).is_empty())
+    }
+}
+
+impl fmt::Display for Religion {
+    /** Every aspect that's been filled in, separated by semicolons. */
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let aspects: Vec<&str> = ReligionAspect::ALL_VALUES
+            .iter()
+            .map(|aspect| &self.get(*aspect)[..])
+            .filter(|text| !text.is_empty())
+            .collect();
+        write!(f, "{}", aspects.join("; "))
+    }
+}
+
+impl World {
+    /** Roll the `World`'s religion, or clear it if it has no society of its own. */
+    pub fn generate_religion(&mut self) {
+        if !self.subtype.has_society() {
+            self.religion = Religion::default();
+            return;
+        }
+        self.religion = Religion::random();
+    }
+
+    /** Roll a religion for each of the `World`'s factions that has none, as factions saved before
+    they had religions do.
+
+    Only done when loading older files, since a faction's religion may be left blank on purpose.
+    */
+    pub(crate) fn generate_missing_faction_religions(&mut self) {
+        for faction in self.factions.iter_mut() {
+            if faction.religion.is_empty() {
+                faction.religion = Religion::random();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::{Faction, WorldSubtype};
+
+    #[test]
+    fn generate_religion() {
+        let mut world = World::empty();
+        assert!(world.religion.is_empty());
+        assert_eq!(world.religion.to_string(), "");
+
+        world.generate_religion();
+        for aspect in ReligionAspect::ALL_VALUES {
+            let text = world.religion.get(aspect);
+            assert!(aspect.table().contains(&&text[..]), "{} not rolled", aspect);
+        }
+
+        world.religion.scope = "Edited".to_string();
+        world.religion.generate_aspect(ReligionAspect::Attitude);
+        assert_eq!(world.religion.scope, "Edited");
+        assert!(world.religion.to_string().contains("; Edited; "));
+
+        let faction = Faction::random();
+        assert!(!faction.religion.is_empty());
+        world.factions.push(faction);
+        world.make_player_safe();
+        assert!(world.religion.is_empty());
+        assert!(world.factions.is_empty());
+
+        world.generate_religion();
+        world.set_subtype(WorldSubtype::Outpost);
+        assert!(world.religion.is_empty());
+    }
+}
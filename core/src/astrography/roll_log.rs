@@ -0,0 +1,105 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::{TableKind, World};
+use crate::dice::{self, RecordedRoll};
+
+/// Whether newly generated worlds keep a log of the dice rolled to generate them
+static ROLL_LOGGING: AtomicBool = AtomicBool::new(false);
+
+/** Dice rolled while generating one field of a `World`, kept to show how the field was generated. */
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct RollLogEntry {
+    /// Field that was generated, e.g. "Atmosphere"
+    pub field: String,
+    /// Table the rolls were looked up on; `None` if they weren't, or were looked up on several
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub table: Option<TableKind>,
+    pub rolls: Vec<RecordedRoll>,
+}
+
+/** Keep a log of the dice rolled to generate every new `World` in its `roll_log`, or stop. */
+pub fn set_roll_logging(enabled: bool) {
+    ROLL_LOGGING.store(enabled, Ordering::Relaxed);
+}
+
+/** Whether newly generated worlds keep a log of the dice rolled to generate them. */
+pub fn roll_logging() -> bool {
+    ROLL_LOGGING.load(Ordering::Relaxed)
+}
+
+impl World {
+    /** Run `generate` on the `World`, adding the dice it rolls to the roll log as the rolls of
+    `field` if rolls are being logged and it rolled any.
+    */
+    pub(super) fn generate_logged(
+        &mut self,
+        field: &str,
+        table: Option<TableKind>,
+        generate: impl FnOnce(&mut World),
+    ) {
+        if !roll_logging() {
+            generate(self);
+            return;
+        }
+
+        let ((), rolls) = dice::record_rolls(|| generate(self));
+        if !rolls.is_empty() {
+            self.roll_log.push(RollLogEntry {
+                field: field.to_string(),
+                table,
+                rolls,
+            });
+        }
+    }
+
+    /** Get the roll log as plain text, one roll per line, or an empty string if there isn't one. */
+    pub fn roll_log_text(&self) -> String {
+        let mut lines = Vec::new();
+        for entry in self.roll_log.iter() {
+            let label = match entry.table {
+                Some(table) => format!("{} ({} table)", entry.field, table),
+                None => entry.field.clone(),
+            };
+            lines.extend(
+                entry
+                    .rolls
+                    .iter()
+                    .map(|roll| format!("{}: {}", label, roll)),
+            );
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn world_roll_log() {
+        set_roll_logging(true);
+        let world = World::new("Regina".to_string());
+        set_roll_logging(false);
+
+        let size = &world.roll_log[0];
+        assert_eq!(size.field, "Size");
+        assert_eq!(size.rolls[0].results.len(), 2);
+        let population = world
+            .roll_log
+            .iter()
+            .find(|entry| entry.field == "Population")
+            .unwrap();
+        assert_eq!(population.table, Some(TableKind::Populations));
+        assert_eq!(
+            population.rolls[0].row,
+            Some(world.population.code as usize)
+        );
+
+        let text = world.roll_log_text();
+        assert!(text.starts_with("Size: 2D6 ["));
+        assert!(text.contains("\nPopulation (Populations table): 2D6 ["));
+        assert!(World::new("Efate".to_string()).roll_log.is_empty());
+    }
+}
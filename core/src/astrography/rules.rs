@@ -0,0 +1,240 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::{StarportClass, World};
+
+/** Die modifiers for the rolls that differ between rule sets when generating a `World`.
+
+Each modifier is added to a roll on the matching randomization table.
+*/
+pub trait GenerationRules {
+    /** Modifier for the government roll. */
+    fn government_dm(&self, world: &World) -> i32;
+
+    /** Modifier for the hydrographics roll; only used for worlds larger than size 1. */
+    fn hydrographics_dm(&self, world: &World) -> i32;
+
+    /** Modifier for the population roll. */
+    fn population_dm(&self, world: &World) -> i32;
+
+    /** Modifier for the starport roll. */
+    fn starport_dm(&self, world: &World) -> i32;
+
+    /** Modifier for the tech level roll. */
+    fn tech_level_dm(&self, world: &World) -> i32;
+}
+
+/** Which set of rules a `World` is generated with. */
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum RuleSet {
+    #[default]
+    Cepheus,
+    Mongoose,
+}
+
+impl RuleSet {
+    pub const ALL_VALUES: [RuleSet; 2] = [Self::Cepheus, Self::Mongoose];
+
+    pub fn rules(&self) -> &'static dyn GenerationRules {
+        match self {
+            Self::Cepheus => &CepheusRules,
+            Self::Mongoose => &MongooseRules,
+        }
+    }
+}
+
+impl fmt::Display for RuleSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Cepheus => "Cepheus Engine",
+            Self::Mongoose => "Mongoose 2e",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** Cepheus Engine rules, with a few of this generator's own deviations. */
+struct CepheusRules;
+
+impl CepheusRules {
+    /** Modifier to population for how habitable the world is. */
+    fn habitability_dm(world: &World) -> i32 {
+        let size_mod: i32 = match world.size {
+            0..=2 => -1,
+            _ => 0,
+        };
+
+        let atmo_mod: i32 = match world.atmosphere.code {
+            10..=u16::MAX => -2,
+            6 => 3,
+            5 | 8 => 1,
+            _ => 0,
+        };
+
+        let hydro_mod: i32 = if world.hydrographics.code == 0 && world.atmosphere.code < 3 {
+            -2
+        } else {
+            0
+        };
+
+        size_mod + atmo_mod + hydro_mod
+    }
+}
+
+impl GenerationRules for CepheusRules {
+    fn government_dm(&self, world: &World) -> i32 {
+        // To keep governments less tyrannical, we deviate from the Cepheus Engine slightly and
+        // don't include the "habitability" modifiers of the world when rolling for the government
+        let unmodified_population = world.population.code as i32 - Self::habitability_dm(world);
+        unmodified_population - 7
+    }
+
+    fn hydrographics_dm(&self, world: &World) -> i32 {
+        match world.atmosphere.code {
+            0 | 1 | 10 | 11 | 12 => -4,
+            14 => -2,
+            _ => 0,
+        }
+    }
+
+    fn population_dm(&self, world: &World) -> i32 {
+        Self::habitability_dm(world) - 2
+    }
+
+    fn starport_dm(&self, world: &World) -> i32 {
+        world.population.code as i32 - 7
+    }
+
+    fn tech_level_dm(&self, world: &World) -> i32 {
+        let pop_mod = match world.population.code {
+            1..=5 => 1,
+            9 => 1,
+            10 => 2,
+            11 => 3,
+            12 => 4,
+            _ => 0,
+        };
+
+        common_tech_level_dm(world) + pop_mod
+    }
+}
+
+/** Mongoose Traveller 2nd Edition rules. */
+struct MongooseRules;
+
+impl GenerationRules for MongooseRules {
+    fn government_dm(&self, world: &World) -> i32 {
+        world.population.code as i32 - 7
+    }
+
+    fn hydrographics_dm(&self, world: &World) -> i32 {
+        let atmo_mod = match world.atmosphere.code {
+            0 | 1 | 10 | 11 | 12 => -4,
+            _ => 0,
+        };
+
+        let temp_mod = match &world.temperature.kind[..] {
+            "Hot" => -2,
+            "Boiling" => -6,
+            _ => 0,
+        };
+
+        world.atmosphere.code as i32 - 7 + atmo_mod + temp_mod
+    }
+
+    fn population_dm(&self, _world: &World) -> i32 {
+        -2
+    }
+
+    fn starport_dm(&self, world: &World) -> i32 {
+        match world.population.code {
+            0..=2 => -2,
+            3..=4 => -1,
+            5..=7 => 0,
+            8..=9 => 1,
+            _ => 2,
+        }
+    }
+
+    fn tech_level_dm(&self, world: &World) -> i32 {
+        let pop_mod = match world.population.code {
+            1..=5 => 1,
+            8 => 1,
+            9 => 2,
+            10.. => 4,
+            _ => 0,
+        };
+
+        common_tech_level_dm(world) + pop_mod
+    }
+}
+
+/** Tech level modifiers for everything except population, which every rule set shares. */
+fn common_tech_level_dm(world: &World) -> i32 {
+    let size_mod = match world.size {
+        0..=1 => 2,
+        2..=4 => 1,
+        _ => 0,
+    };
+
+    let atmo_mod = match world.atmosphere.code {
+        0..=3 => 1,
+        10..=15 => 1,
+        _ => 0,
+    };
+
+    let hydro_mod = match world.hydrographics.code {
+        0 => 1,
+        9 => 1,
+        10 => 2,
+        _ => 0,
+    };
+
+    let gov_mod = match world.government.code {
+        0 => 1,
+        5 => 1,
+        7 => 2,
+        13..=14 => -2,
+        _ => 0,
+    };
+
+    let starport_mod = match world.starport.class {
+        StarportClass::A => 6,
+        StarportClass::B => 4,
+        StarportClass::C => 2,
+        StarportClass::X => -4,
+        _ => 0,
+    };
+
+    size_mod + atmo_mod + hydro_mod + gov_mod + starport_mod
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::TABLES;
+
+    #[test]
+    fn rule_set_dms() {
+        let mut world = World::empty();
+        world.size = 8;
+        world.atmosphere = TABLES.atmo_table[6].clone();
+        world.population = TABLES.pop_table[9].clone();
+
+        let cepheus = RuleSet::Cepheus.rules();
+        let mongoose = RuleSet::Mongoose.rules();
+
+        // Only Cepheus rules account for habitability when rolling population
+        assert_eq!(cepheus.population_dm(&world), 1);
+        assert_eq!(mongoose.population_dm(&world), -2);
+
+        assert_eq!(cepheus.government_dm(&world), -1);
+        assert_eq!(mongoose.government_dm(&world), 2);
+
+        assert_eq!(cepheus.starport_dm(&world), 2);
+        assert_eq!(mongoose.starport_dm(&world), 1);
+
+        assert_eq!(mongoose.hydrographics_dm(&world), -1);
+    }
+}
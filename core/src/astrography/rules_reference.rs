@@ -0,0 +1,157 @@
+use std::fmt;
+
+use lazy_static::lazy_static;
+use serde::Deserialize;
+
+use crate::astrography::{trade::TradeCondition, TRADE_GOODS_TABLE};
+
+const RULES_REFERENCE_CSV: &str = include_str!("../../resources/rules_reference.csv");
+
+lazy_static! {
+    pub static ref RULES_REFERENCE: Vec<RuleEntry> = load_rules_reference();
+}
+
+/** Kind of world code a [`RuleEntry`] explains. */
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq)]
+pub enum RuleTopic {
+    StarportClass,
+    TradeCode,
+    TravelCode,
+}
+
+impl RuleTopic {
+    pub const ALL_VALUES: [RuleTopic; 3] = [Self::StarportClass, Self::TradeCode, Self::TravelCode];
+}
+
+impl fmt::Display for RuleTopic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::StarportClass => "Starport Classes",
+            Self::TradeCode => "Trade Codes",
+            Self::TravelCode => "Travel Codes",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** Rules text explaining a single starport class, trade code, or travel code. */
+#[derive(Clone, Debug, Deserialize)]
+pub struct RuleEntry {
+    pub topic: RuleTopic,
+    /// Code as it appears in a world's profile, e.g. "A", "Ag", or "Amber"
+    pub key: String,
+    pub title: String,
+    pub text: String,
+}
+
+impl RuleEntry {
+    /** Whether `query` is part of the entry's key, title, or text, ignoring case. */
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        self.key.to_lowercase() == query
+            || self.title.to_lowercase().contains(&query)
+            || self.text.to_lowercase().contains(&query)
+    }
+
+    /** Get the purchase and sale DMs the entry's trade or travel code gives each trade good.
+
+    Only goods with at least one DM from the code are listed, in trade goods table order.
+    */
+    pub fn trade_dms(&self) -> Vec<(&'static str, Option<i32>, Option<i32>)> {
+        if self.topic == RuleTopic::StarportClass {
+            return Vec::new();
+        }
+
+        TRADE_GOODS_TABLE
+            .iter()
+            .filter_map(|record| {
+                let purchase_dm = condition_dm(&record.purchase_dms, &self.key);
+                let sale_dm = condition_dm(&record.sale_dms, &self.key);
+                if purchase_dm.is_some() || sale_dm.is_some() {
+                    Some((&record.good[..], purchase_dm, sale_dm))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+/** Look up the rules text for the code `key` of the given `topic`. */
+pub fn rule_entry(topic: RuleTopic, key: &str) -> Option<&'static RuleEntry> {
+    RULES_REFERENCE
+        .iter()
+        .find(|entry| entry.topic == topic && entry.key == key)
+}
+
+/** Find every rules entry that mentions `query`; every entry if `query` is blank. */
+pub fn search_rules(query: &str) -> Vec<&'static RuleEntry> {
+    if query.trim().is_empty() {
+        return RULES_REFERENCE.iter().collect();
+    }
+    RULES_REFERENCE
+        .iter()
+        .filter(|entry| entry.matches(query))
+        .collect()
+}
+
+/** Get the DM in `dms` whose condition is the code `key`, if there is one. */
+fn condition_dm(dms: &[(TradeCondition, i32)], key: &str) -> Option<i32> {
+    dms.iter()
+        .find(|(condition, _)| condition.to_string() == key)
+        .map(|(_, dm)| *dm)
+}
+
+fn load_rules_reference() -> Vec<RuleEntry> {
+    let mut reader = csv::Reader::from_reader(RULES_REFERENCE_CSV.as_bytes());
+    reader
+        .deserialize()
+        .map(|result| result.expect("Rules reference csv should always be valid"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::TABLES;
+
+    #[test]
+    fn every_code_has_rules() {
+        for record in TABLES.trade_code_table.iter() {
+            let key = format!("{:?}", record.trade_code);
+            assert!(rule_entry(RuleTopic::TradeCode, &key).is_some(), "{}", key);
+        }
+        for record in TABLES.starport_table.iter() {
+            let key = record.class.to_string();
+            assert!(
+                rule_entry(RuleTopic::StarportClass, &key).is_some(),
+                "{}",
+                key
+            );
+        }
+        for key in ["Safe", "Amber", "Red"] {
+            assert!(rule_entry(RuleTopic::TravelCode, key).is_some(), "{}", key);
+        }
+    }
+
+    #[test]
+    fn rules_search() {
+        assert_eq!(search_rules("").len(), RULES_REFERENCE.len());
+        assert!(search_rules(" ag ").iter().any(|entry| entry.key == "Ag"));
+        assert!(search_rules("berthing")
+            .iter()
+            .all(|entry| entry.topic == RuleTopic::StarportClass));
+        assert!(search_rules("no such rule").is_empty());
+    }
+
+    #[test]
+    fn rule_trade_dms() {
+        let red = rule_entry(RuleTopic::TravelCode, "Red").unwrap();
+        assert!(red
+            .trade_dms()
+            .contains(&("Advanced Weapons", None, Some(4))));
+
+        let class_a = rule_entry(RuleTopic::StarportClass, "A").unwrap();
+        assert!(class_a.trade_dms().is_empty());
+    }
+}
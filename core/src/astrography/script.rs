@@ -0,0 +1,697 @@
+use std::{error::Error, fmt, path::Path};
+
+use serde::de::{value, DeserializeOwned, IntoDeserializer};
+
+use crate::astrography::{StarportClass, Subsector, TradeCode, TravelCode, World, TABLES};
+
+/// Most operations a Rhai script may run on a single `World`, so an endless loop can't hang
+#[cfg(feature = "rhai")]
+const RHAI_MAX_OPERATIONS: u64 = 1_000_000;
+
+/** Error found while parsing or running a `Script`, along with the line it was found on if it can
+be pinned to one.
+*/
+#[derive(Debug, PartialEq)]
+pub struct ScriptError {
+    line: Option<usize>,
+    message: String,
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "Line {}: {}", line, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl Error for ScriptError {}
+
+/** Field of a `World` that can be read and written by a `Script`.
+
+Fields are named the same way as the columns of the spreadsheet CSV export.
+*/
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Field {
+    Atmosphere,
+    Culture,
+    GasGiants,
+    Government,
+    Hydrographics,
+    LawLevel,
+    NavalBase,
+    PirateBase,
+    PlanetoidBelts,
+    Population,
+    ResearchBase,
+    ScoutBase,
+    Size,
+    Starport,
+    Tas,
+    TechLevel,
+    Temperature,
+    TravelCode,
+}
+
+impl Field {
+    const ALL_VALUES: [Field; 18] = [
+        Self::Atmosphere,
+        Self::Culture,
+        Self::GasGiants,
+        Self::Government,
+        Self::Hydrographics,
+        Self::LawLevel,
+        Self::NavalBase,
+        Self::PirateBase,
+        Self::PlanetoidBelts,
+        Self::Population,
+        Self::ResearchBase,
+        Self::ScoutBase,
+        Self::Size,
+        Self::Starport,
+        Self::Tas,
+        Self::TechLevel,
+        Self::Temperature,
+        Self::TravelCode,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Atmosphere => "atmosphere",
+            Self::Culture => "culture",
+            Self::GasGiants => "gas_giants",
+            Self::Government => "government",
+            Self::Hydrographics => "hydrographics",
+            Self::LawLevel => "law_level",
+            Self::NavalBase => "naval_base",
+            Self::PirateBase => "pirate_base",
+            Self::PlanetoidBelts => "planetoid_belts",
+            Self::Population => "population",
+            Self::ResearchBase => "research_base",
+            Self::ScoutBase => "scout_base",
+            Self::Size => "size",
+            Self::Starport => "starport",
+            Self::Tas => "tas",
+            Self::TechLevel => "tech_level",
+            Self::Temperature => "temperature",
+            Self::TravelCode => "travel_code",
+        }
+    }
+
+    fn parse(name: &str) -> Result<Self, String> {
+        Self::ALL_VALUES
+            .into_iter()
+            .find(|field| field.name() == name)
+            .ok_or_else(|| format!("Unknown field \"{}\"", name))
+    }
+
+    /** Largest value the field can be set to, if it is a numeric field. */
+    fn max_value(&self) -> Option<i32> {
+        let max = match self {
+            Self::Atmosphere => TABLES.atmo_table.len() - 1,
+            Self::Culture => TABLES.culture_table.len() - 1,
+            Self::GasGiants | Self::PlanetoidBelts => i32::MAX as usize,
+            Self::Government => TABLES.gov_table.len() - 1,
+            Self::Hydrographics => TABLES.hydro_table.len() - 1,
+            Self::LawLevel => TABLES.law_table.len() - 1,
+            Self::Population => TABLES.pop_table.len() - 1,
+            Self::Size => World::SIZE_MAX as usize,
+            Self::TechLevel => TABLES.tech_level_table.len() - 1,
+            Self::Temperature => TABLES.temp_table.len() - 1,
+            _ => return None,
+        };
+        Some(max as i32)
+    }
+
+    /** Parse `token` as a value this field can hold. */
+    fn parse_value(&self, token: &str) -> Result<Value, String> {
+        match self {
+            Self::NavalBase
+            | Self::PirateBase
+            | Self::ResearchBase
+            | Self::ScoutBase
+            | Self::Tas => match token {
+                "true" => Ok(Value::Bool(true)),
+                "false" => Ok(Value::Bool(false)),
+                _ => Err(format!("Expected true or false but found \"{}\"", token)),
+            },
+            Self::Starport => parse_variant(token)
+                .map(Value::Starport)
+                .ok_or_else(|| format!("Unknown starport class \"{}\"", token)),
+            Self::TravelCode => parse_variant(token)
+                .map(Value::TravelCode)
+                .ok_or_else(|| format!("Unknown travel code \"{}\"", token)),
+            _ => {
+                let max = self
+                    .max_value()
+                    .expect("Every other field should be numeric");
+                match token.parse::<i32>() {
+                    Ok(value) if (0..=max).contains(&value) => Ok(Value::Int(value)),
+                    _ => Err(format!(
+                        "Expected a number from 0 to {} but found \"{}\"",
+                        max, token
+                    )),
+                }
+            }
+        }
+    }
+
+    fn get(&self, world: &World) -> Value {
+        match self {
+            Self::Atmosphere => Value::Int(world.atmosphere.code as i32),
+            Self::Culture => Value::Int(world.culture.code as i32),
+            Self::GasGiants => Value::Int(world.gas_giants),
+            Self::Government => Value::Int(world.government.code as i32),
+            Self::Hydrographics => Value::Int(world.hydrographics.code as i32),
+            Self::LawLevel => Value::Int(world.law_level.code as i32),
+            Self::NavalBase => Value::Bool(world.has_naval_base),
+            Self::PirateBase => Value::Bool(world.has_pirate_base),
+            Self::PlanetoidBelts => Value::Int(world.planetoid_belts.unwrap_or(0)),
+            Self::Population => Value::Int(world.population.code as i32),
+            Self::ResearchBase => Value::Bool(world.has_research_base),
+            Self::ScoutBase => Value::Bool(world.has_scout_base),
+            Self::Size => Value::Int(world.size as i32),
+            Self::Starport => Value::Starport(world.starport.class.clone()),
+            Self::Tas => Value::Bool(world.has_tas),
+            Self::TechLevel => Value::Int(world.tech_level.code as i32),
+            Self::Temperature => Value::Int(world.temperature.code as i32),
+            Self::TravelCode => Value::TravelCode(world.travel_code),
+        }
+    }
+
+    /** Set the field of `world` to `value`; `value` must have been parsed by this field. */
+    fn set(&self, world: &mut World, value: &Value) {
+        match (self, value) {
+            (Self::Atmosphere, Value::Int(code)) => {
+                world.atmosphere = TABLES.atmo_table[*code as usize].clone()
+            }
+            (Self::Culture, Value::Int(code)) => world
+                .culture
+                .safe_mutate(&TABLES.culture_table[*code as usize]),
+            (Self::GasGiants, Value::Int(count)) => world.gas_giants = *count,
+            (Self::Government, Value::Int(code)) => world
+                .government
+                .safe_mutate(&TABLES.gov_table[*code as usize]),
+            (Self::Hydrographics, Value::Int(code)) => {
+                world.hydrographics = TABLES.hydro_table[*code as usize].clone()
+            }
+            (Self::LawLevel, Value::Int(code)) => {
+                world.law_level = TABLES.law_table[*code as usize].clone();
+                world.generate_law_categories();
+            }
+            (Self::NavalBase, Value::Bool(b)) => world.has_naval_base = *b,
+            (Self::PirateBase, Value::Bool(b)) => world.has_pirate_base = *b,
+            (Self::PlanetoidBelts, Value::Int(count)) => world.planetoid_belts = Some(*count),
+            (Self::Population, Value::Int(code)) => {
+                world.population = TABLES.pop_table[*code as usize].clone()
+            }
+            (Self::ResearchBase, Value::Bool(b)) => world.has_research_base = *b,
+            (Self::ScoutBase, Value::Bool(b)) => world.has_scout_base = *b,
+            (Self::Size, Value::Int(size)) => world.size = *size as u16,
+            (Self::Starport, Value::Starport(class)) => {
+                if world.starport.class != *class {
+                    world.starport = TABLES
+                        .starport_table
+                        .iter()
+                        .find(|starport| starport.class == *class)
+                        .expect("Every starport class should be in the starport table")
+                        .clone();
+                    world.generate_fuel_market();
+                }
+            }
+            (Self::Tas, Value::Bool(b)) => world.has_tas = *b,
+            (Self::TechLevel, Value::Int(code)) => {
+                world.tech_level = TABLES.tech_level_table[*code as usize].clone()
+            }
+            (Self::Temperature, Value::Int(code)) => {
+                world.temperature = TABLES.temp_table[*code as usize].clone()
+            }
+            (Self::TravelCode, Value::TravelCode(travel_code)) => world.travel_code = *travel_code,
+            _ => unreachable!("Values should only ever be parsed by the field they are set to"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    Bool(bool),
+    Int(i32),
+    Starport(StarportClass),
+    TravelCode(TravelCode),
+}
+
+#[cfg(feature = "rhai")]
+impl Value {
+    /** The value as a Rhai value; starport classes and travel codes are strings such as "A". */
+    fn to_dynamic(&self) -> rhai::Dynamic {
+        match self {
+            Self::Bool(b) => rhai::Dynamic::from(*b),
+            Self::Int(value) => rhai::Dynamic::from(*value as rhai::INT),
+            Self::Starport(class) => rhai::Dynamic::from(format!("{:?}", class)),
+            Self::TravelCode(travel_code) => rhai::Dynamic::from(format!("{:?}", travel_code)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Operator {
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+}
+
+impl Operator {
+    fn parse(token: &str) -> Result<Self, String> {
+        let operator = match token {
+            "==" => Self::Equal,
+            "!=" => Self::NotEqual,
+            "<" => Self::Less,
+            "<=" => Self::LessEqual,
+            ">" => Self::Greater,
+            ">=" => Self::GreaterEqual,
+            _ => return Err(format!("Unknown comparison \"{}\"", token)),
+        };
+        Ok(operator)
+    }
+
+    fn compare(&self, lhs: &Value, rhs: &Value) -> bool {
+        match (self, lhs, rhs) {
+            (Self::Equal, _, _) => lhs == rhs,
+            (Self::NotEqual, _, _) => lhs != rhs,
+            (Self::Less, Value::Int(lhs), Value::Int(rhs)) => lhs < rhs,
+            (Self::LessEqual, Value::Int(lhs), Value::Int(rhs)) => lhs <= rhs,
+            (Self::Greater, Value::Int(lhs), Value::Int(rhs)) => lhs > rhs,
+            (Self::GreaterEqual, Value::Int(lhs), Value::Int(rhs)) => lhs >= rhs,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Condition {
+    Compare(Field, Operator, Value),
+    HasTradeCode(TradeCode),
+}
+
+impl Condition {
+    fn parse(condition: &str) -> Result<Self, String> {
+        let tokens: Vec<&str> = condition.split_whitespace().collect();
+        match tokens[..] {
+            ["has", trade_code] => parse_variant(trade_code)
+                .map(Self::HasTradeCode)
+                .ok_or_else(|| format!("Unknown trade code \"{}\"", trade_code)),
+            [field, operator, value] => {
+                let field = Field::parse(field)?;
+                let operator = Operator::parse(operator)?;
+                let value = field.parse_value(value)?;
+                if field.max_value().is_none()
+                    && !matches!(operator, Operator::Equal | Operator::NotEqual)
+                {
+                    return Err("Only numeric fields can be compared with < or >".to_string());
+                }
+                Ok(Self::Compare(field, operator, value))
+            }
+            _ => Err(format!(
+                "Expected a condition like \"tech_level > 12\" or \"has Ag\" but found \"{}\"",
+                condition.trim()
+            )),
+        }
+    }
+
+    fn applies_to(&self, world: &World) -> bool {
+        match self {
+            Self::Compare(field, operator, value) => operator.compare(&field.get(world), value),
+            Self::HasTradeCode(trade_code) => world.trade_codes.contains(trade_code),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Rule {
+    conditions: Vec<Condition>,
+    assignments: Vec<(Field, Value)>,
+}
+
+impl Rule {
+    fn parse(line: &str) -> Result<Self, String> {
+        let (conditions, assignments) = match line.strip_prefix("if ") {
+            Some(rest) => match rest.split_once(" then ") {
+                Some((conditions, assignments)) => (
+                    conditions
+                        .split(" and ")
+                        .map(Condition::parse)
+                        .collect::<Result<Vec<_>, _>>()?,
+                    assignments,
+                ),
+                None => return Err("Expected \"then\" after the conditions".to_string()),
+            },
+            None => (Vec::new(), line),
+        };
+
+        let assignments = assignments
+            .split(',')
+            .map(|assignment| {
+                let tokens: Vec<&str> = assignment.split_whitespace().collect();
+                match tokens[..] {
+                    [field, "=", value] => {
+                        let field = Field::parse(field)?;
+                        let value = field.parse_value(value)?;
+                        Ok((field, value))
+                    }
+                    _ => Err(format!(
+                        "Expected an assignment like \"research_base = true\" but found \"{}\"",
+                        assignment.trim()
+                    )),
+                }
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(Self {
+            conditions,
+            assignments,
+        })
+    }
+}
+
+/** Script that modifies the `World`s of a `Subsector`, written either in the built-in rule
+language or, if built with the `rhai` feature, in [Rhai](https://rhai.rs).
+
+# Rule language
+Each non-empty line is a rule that is applied to every `World` in order, and everything after a `#`
+is a comment. A rule either sets fields unconditionally or only when all of its conditions are met:
+
+```text
+# Advanced worlds attract researchers
+if tech_level > 12 then research_base = true
+if has In and population >= 9 then travel_code = Amber, law_level = 9
+gas_giants = 1
+```
+
+The full grammar, with `FIELD` one of the field names below and `CODE` a trade code like `Ag`:
+
+```text
+rule       = [ "if" condition { "and" condition } "then" ] assignment { "," assignment }
+condition  = FIELD ( "==" | "!=" | "<" | "<=" | ">" | ">=" ) value | "has" CODE
+assignment = FIELD "=" value
+```
+
+Words are separated by whitespace. Only numeric fields can be compared with `<` and `>`.
+
+# Rhai
+Rhai scripts are run once for every `World`, with each field in a variable of the same name that
+the script can change, along with the `World`'s `name` and an array of its `trade_codes`:
+
+```text
+if tech_level > 12 && trade_codes.contains("Hi") {
+    research_base = true;
+}
+```
+
+# Fields
+Fields are named like the columns of the spreadsheet CSV export: `atmosphere`, `culture`,
+`gas_giants`, `government`, `hydrographics`, `law_level`, `naval_base`, `pirate_base`,
+`planetoid_belts`, `population`, `research_base`, `scout_base`, `size`, `starport`, `tas`,
+`tech_level`, `temperature`, and `travel_code`. Fields drawn from the randomization tables are set
+by their code, bases are `true` or `false`, starports are set by their class, e.g. `A`, and travel
+codes are one of `Safe`, `Amber`, or `Red`.
+*/
+#[derive(Debug)]
+pub struct Script {
+    program: Program,
+}
+
+#[derive(Debug)]
+enum Program {
+    Rules(Vec<Rule>),
+    #[cfg(feature = "rhai")]
+    Rhai(rhai::AST),
+}
+
+impl Script {
+    /** Extensions of the script files that can be loaded with [`Script::from_file`]. */
+    #[cfg(not(feature = "rhai"))]
+    pub const EXTENSIONS: &'static [&'static str] = &["txt"];
+    /** Extensions of the script files that can be loaded with [`Script::from_file`]. */
+    #[cfg(feature = "rhai")]
+    pub const EXTENSIONS: &'static [&'static str] = &["txt", "rhai"];
+
+    /** Parse a `Script` in the rule language from its source text. */
+    pub fn parse(source: &str) -> Result<Self, ScriptError> {
+        let mut rules = Vec::new();
+        for (index, line) in source.lines().enumerate() {
+            let line = match line.split_once('#') {
+                Some((code, _)) => code,
+                None => line,
+            }
+            .trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let rule = Rule::parse(line).map_err(|message| ScriptError {
+                line: Some(index + 1),
+                message,
+            })?;
+            rules.push(rule);
+        }
+        Ok(Self {
+            program: Program::Rules(rules),
+        })
+    }
+
+    /** Compile a `Script` written in Rhai from its source text. */
+    #[cfg(feature = "rhai")]
+    pub fn parse_rhai(source: &str) -> Result<Self, ScriptError> {
+        let ast = rhai::Engine::new()
+            .compile(source)
+            .map_err(|e| ScriptError {
+                line: e.position().line(),
+                message: e.err_type().to_string(),
+            })?;
+        Ok(Self {
+            program: Program::Rhai(ast),
+        })
+    }
+
+    /** Parse the `Script` in `source` read from the file at `path`, as Rhai if the file's extension
+    is "rhai" and in the rule language otherwise.
+    */
+    pub fn from_file(path: &Path, source: &str) -> Result<Self, ScriptError> {
+        let is_rhai = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("rhai"));
+        if !is_rhai {
+            return Self::parse(source);
+        }
+
+        #[cfg(feature = "rhai")]
+        return Self::parse_rhai(source);
+        #[cfg(not(feature = "rhai"))]
+        Err(ScriptError {
+            line: None,
+            message: "Rhai scripts are not supported by this build".to_string(),
+        })
+    }
+}
+
+impl Subsector {
+    /** Run `script` on every `World` and return how many `World`s were changed.
+
+    Trade codes of changed `World`s are resolved again afterwards. If the script fails on any
+    `World`, none of them are changed.
+    */
+    pub fn run_script(&mut self, script: &Script) -> Result<usize, ScriptError> {
+        #[cfg(feature = "rhai")]
+        let mut engine = rhai::Engine::new();
+        #[cfg(feature = "rhai")]
+        engine.set_max_operations(RHAI_MAX_OPERATIONS);
+
+        let mut map = self.map.clone();
+        let mut changed = 0;
+        for world in map.values_mut() {
+            let original = world.clone();
+            match &script.program {
+                Program::Rules(rules) => {
+                    for rule in rules.iter() {
+                        if rule.conditions.iter().all(|c| c.applies_to(world)) {
+                            for (field, value) in rule.assignments.iter() {
+                                if *value != field.get(world) {
+                                    field.set(world, value);
+                                }
+                            }
+                        }
+                    }
+                }
+                #[cfg(feature = "rhai")]
+                Program::Rhai(ast) => run_rhai(&engine, ast, world)?,
+            }
+
+            if *world != original {
+                world.resolve_trade_codes();
+                changed += 1;
+            }
+        }
+        self.map = map;
+        Ok(changed)
+    }
+}
+
+/** Run the Rhai script `ast` on `world`, then set any fields the script changed. */
+#[cfg(feature = "rhai")]
+fn run_rhai(engine: &rhai::Engine, ast: &rhai::AST, world: &mut World) -> Result<(), ScriptError> {
+    let mut scope = rhai::Scope::new();
+    scope.push_constant("name", world.name.clone());
+    let trade_codes: rhai::Array = world
+        .trade_codes
+        .iter()
+        .map(|trade_code| rhai::Dynamic::from(format!("{:?}", trade_code)))
+        .collect();
+    scope.push_constant("trade_codes", trade_codes);
+    for field in Field::ALL_VALUES {
+        scope.push_dynamic(field.name(), field.get(world).to_dynamic());
+    }
+
+    engine
+        .run_ast_with_scope(&mut scope, ast)
+        .map_err(|mut e| ScriptError {
+            line: e.take_position().line(),
+            message: e.to_string(),
+        })?;
+
+    for field in Field::ALL_VALUES {
+        let token = scope
+            .get_value::<rhai::Dynamic>(field.name())
+            .map(|value| value.to_string())
+            .unwrap_or_default();
+        let value = field.parse_value(&token).map_err(|message| ScriptError {
+            line: None,
+            message: format!("{} of {}: {}", field.name(), world.name, message),
+        })?;
+        // Only set changed fields, since setting some of them rerolls the data that depends on them
+        if value != field.get(world) {
+            field.set(world, &value);
+        }
+    }
+    Ok(())
+}
+
+fn parse_variant<T: DeserializeOwned>(name: &str) -> Option<T> {
+    let deserializer: value::StrDeserializer<value::Error> = name.into_deserializer();
+    T::deserialize(deserializer).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::{Point, RuleSet};
+
+    #[test]
+    fn run_script() {
+        let script = Script::parse(
+            "# Advanced worlds attract researchers\n\
+            if tech_level > 12 then research_base = true\n\
+            \n\
+            if starport == X then gas_giants = 0, travel_code = Red # Nobody goes there\n",
+        )
+        .unwrap();
+
+        let mut subsector = Subsector::new(0, 0, RuleSet::default());
+        let mut world = World::empty();
+        world.tech_level = TABLES.tech_level_table[13].clone();
+        world.starport.class = StarportClass::A;
+        let point = Point { x: 1, y: 1 };
+        subsector.insert_world(&point, world).unwrap();
+
+        assert!(subsector.run_script(&script).unwrap() >= 1);
+        for world in subsector.get_map().values() {
+            assert!(world.has_research_base || world.tech_level.code <= 12);
+            if world.starport.class == StarportClass::X {
+                assert_eq!(world.gas_giants, 0);
+                assert_eq!(world.travel_code, TravelCode::Red);
+            }
+        }
+        assert!(subsector.get_world(&point).unwrap().has_research_base);
+
+        // Running the same script again shouldn't change anything else
+        assert_eq!(subsector.run_script(&script), Ok(0));
+
+        // Setting a field to the value it already has doesn't reroll the data that depends on it
+        let law_level = subsector.get_world(&point).unwrap().law_level.code;
+        let rule = format!("if law_level == {0} then law_level = {0}", law_level);
+        let script = Script::parse(&rule).unwrap();
+        assert_eq!(subsector.run_script(&script), Ok(0));
+    }
+
+    #[test]
+    fn script_errors() {
+        let error = Script::parse("size = 1\nif size > 3 research_base = true").unwrap_err();
+        assert_eq!(error.line, Some(2));
+        assert!(error.to_string().starts_with("Line 2: "));
+
+        assert!(Script::parse("size = 11").is_err());
+        assert!(Script::parse("starport > B").is_err());
+        assert!(Script::parse("if starport > B then tas = true").is_err());
+        assert!(Script::parse("if has Zz then tas = true").is_err());
+        assert!(Script::parse("if has Ag then tas = maybe").is_err());
+        assert!(Script::parse("warp_drive = 1").is_err());
+    }
+
+    #[test]
+    fn script_from_file() {
+        let script = Script::from_file(Path::new("rules.txt"), "size = 1").unwrap();
+        assert!(matches!(script.program, Program::Rules(_)));
+        assert!(Script::from_file(Path::new("rules.txt"), "size = 100").is_err());
+
+        let rhai = Script::from_file(Path::new("rules.rhai"), "if size > 1 { size = 1; }");
+        #[cfg(not(feature = "rhai"))]
+        assert!(rhai.is_err());
+        #[cfg(feature = "rhai")]
+        assert!(matches!(rhai.unwrap().program, Program::Rhai(_)));
+    }
+
+    #[cfg(feature = "rhai")]
+    #[test]
+    fn run_rhai_script() {
+        let script = Script::parse_rhai(
+            "if tech_level > 12 && !trade_codes.contains(\"Lo\") {\n\
+                research_base = true;\n\
+                starport = \"A\";\n\
+            }",
+        )
+        .unwrap();
+
+        let mut subsector = Subsector::empty();
+        let mut world = World::empty();
+        world.name = "Research".to_string();
+        world.tech_level = TABLES.tech_level_table[13].clone();
+        let point = Point { x: 1, y: 1 };
+        subsector.insert_world(&point, world).unwrap();
+        subsector
+            .insert_world(&Point { x: 2, y: 2 }, World::empty())
+            .unwrap();
+
+        assert_eq!(subsector.run_script(&script), Ok(1));
+        let world = subsector.get_world(&point).unwrap();
+        assert!(world.has_research_base);
+        assert_eq!(world.starport.class, StarportClass::A);
+        assert_eq!(subsector.run_script(&script), Ok(0));
+
+        // Bad values are caught after the script runs, and nothing is changed if they are
+        let script = Script::parse_rhai("tech_level = 99;").unwrap();
+        let before = subsector.clone();
+        let error = subsector.run_script(&script).unwrap_err();
+        assert!(error.to_string().starts_with("tech_level of "));
+        assert_eq!(subsector, before);
+
+        assert_eq!(Script::parse_rhai("if size > {").unwrap_err().line, Some(1));
+        let error = subsector
+            .run_script(&Script::parse_rhai("\nlet x = warp_drive;").unwrap())
+            .unwrap_err();
+        assert_eq!(error.line, Some(2));
+    }
+}
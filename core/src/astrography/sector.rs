@@ -0,0 +1,122 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::{Point, Subsector};
+
+/// Number of subsectors across a sector
+const SECTOR_COLUMNS: i32 = 4;
+/// Number of subsectors down a sector
+const SECTOR_ROWS: i32 = 4;
+
+/** Where a `Subsector` sits within a larger sector, e.g. subsector C of the Spinward Marches.
+
+Sectors are four subsectors across and four down, lettered "A" through "P" in reading order. Hexes
+within a sector are numbered from "0101" to "3240".
+*/
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SectorLocation {
+    pub sector: String,
+    /// Letter of the subsector within the sector, from 'A' to 'P'
+    pub index: char,
+}
+
+impl fmt::Display for SectorLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.sector, self.index)
+    }
+}
+
+impl SectorLocation {
+    /** Find the location of the subsector containing `sector_hex` and the hex's `Point` in it.
+
+    # Returns
+    - `Some((index, point))` with the subsector's letter and the `Subsector` `Point` of the hex,
+    - `None` if `sector_hex` is outside of a sector
+    */
+    pub fn split_sector_hex(sector_hex: &Point) -> Option<(char, Point)> {
+        let columns = Subsector::COLUMNS as i32;
+        let rows = Subsector::ROWS as i32;
+        if !(1..=columns * SECTOR_COLUMNS).contains(&sector_hex.x)
+            || !(1..=rows * SECTOR_ROWS).contains(&sector_hex.y)
+        {
+            return None;
+        }
+
+        let column = (sector_hex.x - 1) / columns;
+        let row = (sector_hex.y - 1) / rows;
+        let index = (b'A' + (row * SECTOR_COLUMNS + column) as u8) as char;
+        let point = Point {
+            x: (sector_hex.x - 1) % columns + 1,
+            y: (sector_hex.y - 1) % rows + 1,
+        };
+        Some((index, point))
+    }
+
+    /** Convert `point` within this subsector to the hex it is in the whole sector. */
+    pub fn sector_hex(&self, point: &Point) -> Point {
+        let position = (self.index as u8).saturating_sub(b'A') as i32;
+        Point {
+            x: (position % SECTOR_COLUMNS) * Subsector::COLUMNS as i32 + point.x,
+            y: (position / SECTOR_COLUMNS) * Subsector::ROWS as i32 + point.y,
+        }
+    }
+}
+
+impl Subsector {
+    /** Get where the `Subsector` is within a sector, if it is known. */
+    pub fn location(&self) -> Option<&SectorLocation> {
+        self.location.as_ref()
+    }
+
+    pub fn set_location(&mut self, location: Option<SectorLocation>) {
+        self.location = location;
+    }
+
+    /** Get the hex code of `point` as it appears in exports, relative to the sector if known. */
+    pub fn export_hex(&self, point: &Point) -> String {
+        match &self.location {
+            Some(location) => location.sector_hex(point).to_string(),
+            None => point.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sector_hexes() {
+        let regina = Point { x: 19, y: 10 };
+        let (index, point) = SectorLocation::split_sector_hex(&regina).unwrap();
+        assert_eq!(index, 'C');
+        assert_eq!(point, Point { x: 3, y: 10 });
+
+        let location = SectorLocation {
+            sector: "Spinward Marches".to_string(),
+            index,
+        };
+        assert_eq!(location.sector_hex(&point), regina);
+        assert_eq!(location.to_string(), "Spinward Marches C");
+
+        let corner = Point { x: 32, y: 40 };
+        assert_eq!(
+            SectorLocation::split_sector_hex(&corner),
+            Some(('P', Point { x: 8, y: 10 }))
+        );
+        assert_eq!(
+            SectorLocation::split_sector_hex(&Point { x: 33, y: 1 }),
+            None
+        );
+        assert_eq!(
+            SectorLocation::split_sector_hex(&Point { x: 1, y: 0 }),
+            None
+        );
+
+        let mut subsector = Subsector::empty();
+        assert_eq!(subsector.export_hex(&point), "0310");
+        subsector.set_location(Some(location));
+        assert_eq!(subsector.export_hex(&point), "1910");
+    }
+}
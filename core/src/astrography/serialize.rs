@@ -0,0 +1,15 @@
+mod csv_table;
+mod json;
+mod migration;
+mod sector_metadata;
+mod t5_table;
+mod traveller_map;
+mod world_list;
+
+pub use csv_table::CsvTable;
+pub use json::JsonableSubsector;
+pub use migration::{SchemaError, SCHEMA_VERSION};
+pub use sector_metadata::SectorMetadata;
+pub use t5_table::T5Table;
+pub use traveller_map::TravellerMapTable;
+pub use world_list::WorldList;
@@ -0,0 +1,304 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::{
+    Point, StarportClass, Subsector, TravelCode, World, WorldSubtype, TABLES,
+};
+
+/** One row of a spreadsheet-friendly CSV table of a `Subsector`'s worlds.
+
+Table fields are stored by their code, except for the starport which is stored by its class.
+*/
+#[derive(Debug, Deserialize, Serialize)]
+struct CsvRecord {
+    hex: String,
+    name: String,
+    /// Missing from tables exported before worlds had subtypes
+    #[serde(default)]
+    subtype: WorldSubtype,
+    starport: StarportClass,
+    berthing_cost: u32,
+    size: u16,
+    diameter: u32,
+    atmosphere: u16,
+    temperature: u16,
+    hydrographics: u16,
+    population: u16,
+    government: u16,
+    law_level: u16,
+    tech_level: u16,
+    culture: u16,
+    world_tag_1: u16,
+    world_tag_2: u16,
+    naval_base: bool,
+    scout_base: bool,
+    research_base: bool,
+    tas: bool,
+    pirate_base: bool,
+    travel_code: TravelCode,
+    planetoid_belts: i32,
+    gas_giants: i32,
+    /// Missing from tables exported before worlds had player notes
+    #[serde(default)]
+    player_notes: String,
+    #[serde(alias = "notes")]
+    secret_notes: String,
+}
+
+impl From<(&World, &Point)> for CsvRecord {
+    fn from(value: (&World, &Point)) -> Self {
+        let (world, point) = value;
+        Self {
+            hex: point.to_string(),
+            name: world.name.clone(),
+            subtype: world.subtype,
+            starport: world.starport.class.clone(),
+            berthing_cost: world.starport.berthing_cost,
+            size: world.size,
+            diameter: world.diameter,
+            atmosphere: world.atmosphere.code,
+            temperature: world.temperature.code,
+            hydrographics: world.hydrographics.code,
+            population: world.population.code,
+            government: world.government.code,
+            law_level: world.law_level.code,
+            tech_level: world.tech_level.code,
+            culture: world.culture.code,
+            world_tag_1: world.world_tags[0].code,
+            world_tag_2: world.world_tags[1].code,
+            naval_base: world.has_naval_base,
+            scout_base: world.has_scout_base,
+            research_base: world.has_research_base,
+            tas: world.has_tas,
+            pirate_base: world.has_pirate_base,
+            travel_code: world.travel_code,
+            planetoid_belts: world.planetoid_belts.unwrap_or(0),
+            gas_giants: world.gas_giants,
+            player_notes: world.player_notes.clone(),
+            secret_notes: world.secret_notes.clone(),
+        }
+    }
+}
+
+impl CsvRecord {
+    /** Check that the record describes a world that can be placed in a `Subsector`.
+
+    # Returns
+    - `Ok(Point)` with the hex of the world,
+    - `Err(String)` describing the first problem with the record
+    */
+    fn validate(&self) -> Result<Point, String> {
+        let point =
+            Point::try_from(&self.hex[..]).map_err(|e| format!("Hex {}: {}", self.hex, e))?;
+        if !Subsector::point_is_inbounds(&point) {
+            return Err(format!("Hex {} is outside of the subsector", self.hex));
+        }
+
+        if self.size > World::SIZE_MAX {
+            return Err(format!(
+                "Size {} of the world at {} is out of range",
+                self.size, self.hex
+            ));
+        }
+
+        let codes = [
+            ("Atmosphere", TABLES.atmo_table.len(), self.atmosphere),
+            ("Temperature", TABLES.temp_table.len(), self.temperature),
+            (
+                "Hydrographics",
+                TABLES.hydro_table.len(),
+                self.hydrographics,
+            ),
+            ("Population", TABLES.pop_table.len(), self.population),
+            ("Government", TABLES.gov_table.len(), self.government),
+            ("Law level", TABLES.law_table.len(), self.law_level),
+            ("Tech level", TABLES.tech_level_table.len(), self.tech_level),
+            ("Culture", TABLES.culture_table.len(), self.culture),
+            ("World tag", TABLES.world_tag_table.len(), self.world_tag_1),
+            ("World tag", TABLES.world_tag_table.len(), self.world_tag_2),
+        ];
+        for (table_name, len, code) in codes {
+            if code as usize >= len {
+                return Err(format!(
+                    "{} code {} of the world at {} is out of range",
+                    table_name, code, self.hex
+                ));
+            }
+        }
+        Ok(point)
+    }
+
+    /** Overwrite the fields of `world` covered by the CSV table with the data of this record, which
+    must have been validated with [`CsvRecord::validate`].
+
+    Table fields whose code hasn't changed are left alone so that any custom descriptions survive.
+    */
+    fn apply_to(self, world: &mut World) {
+        let atmosphere = self.atmosphere as usize;
+        let temperature = self.temperature as usize;
+        let hydrographics = self.hydrographics as usize;
+        let population = self.population as usize;
+        let government = self.government as usize;
+        let law_level = self.law_level as usize;
+        let tech_level = self.tech_level as usize;
+        let culture = self.culture as usize;
+        let world_tags = [self.world_tag_1 as usize, self.world_tag_2 as usize];
+
+        world.name = self.name;
+        world.subtype = self.subtype;
+        world.size = self.size;
+        world.diameter = self.diameter;
+
+        let starport_changed = world.starport.class != self.starport;
+        if starport_changed {
+            world.starport = TABLES
+                .starport_table
+                .iter()
+                .find(|starport| starport.class == self.starport)
+                .expect("Every starport class should be in the starport table")
+                .clone();
+        }
+        world.starport.berthing_cost = self.berthing_cost;
+
+        if world.atmosphere.code as usize != atmosphere {
+            world.atmosphere = TABLES.atmo_table[atmosphere].clone();
+        }
+        if world.temperature.code as usize != temperature {
+            world.temperature = TABLES.temp_table[temperature].clone();
+        }
+        if world.hydrographics.code as usize != hydrographics {
+            world.hydrographics = TABLES.hydro_table[hydrographics].clone();
+        }
+        if world.population.code as usize != population {
+            world.population = TABLES.pop_table[population].clone();
+        }
+        if world.government.code as usize != government {
+            world.government.safe_mutate(&TABLES.gov_table[government]);
+        }
+        if world.law_level.code as usize != law_level {
+            world.law_level = TABLES.law_table[law_level].clone();
+            world.generate_law_categories();
+        }
+        if world.tech_level.code as usize != tech_level {
+            world.tech_level = TABLES.tech_level_table[tech_level].clone();
+        }
+        if world.culture.code as usize != culture {
+            world.culture.safe_mutate(&TABLES.culture_table[culture]);
+        }
+        for (world_tag, code) in world.world_tags.iter_mut().zip(world_tags) {
+            if world_tag.code as usize != code {
+                world_tag.safe_mutate(&TABLES.world_tag_table[code]);
+            }
+        }
+
+        world.has_naval_base = self.naval_base;
+        world.has_scout_base = self.scout_base;
+        world.has_research_base = self.research_base;
+        world.has_tas = self.tas;
+        world.has_pirate_base = self.pirate_base;
+        world.travel_code = self.travel_code;
+        world.detect_travel_code_override();
+        world.planetoid_belts = Some(self.planetoid_belts);
+        world.gas_giants = self.gas_giants;
+        world.player_notes = self.player_notes;
+        world.secret_notes = self.secret_notes;
+
+        world.resolve_trade_codes();
+        if starport_changed || world.fuel_market.is_none() {
+            world.generate_fuel_market();
+        }
+    }
+}
+
+/** Representation of a `Subsector` as a CSV table with one world per row.
+
+Only the fields that make sense to edit in a spreadsheet are included; factions, images, and the
+like are kept from the `Subsector` the table is applied to.
+*/
+pub struct CsvTable {
+    rows: Vec<(Point, CsvRecord)>,
+}
+
+impl CsvTable {
+    /** Parse a `CsvTable` from the contents of a CSV file.
+
+    Every row is checked for a hex inside of the subsector that no other row is at and for codes
+    that are in their tables. Errors name the row they were found in, counting from 1 after the
+    header.
+    */
+    pub fn try_from_csv(csv: &str) -> Result<Self, Box<dyn Error>> {
+        let mut reader = csv::Reader::from_reader(csv.as_bytes());
+        let mut rows: Vec<(Point, CsvRecord)> = Vec::new();
+        for (index, result) in reader.deserialize().enumerate() {
+            let row = index + 1;
+            let record: CsvRecord = result.map_err(|e| match e.kind() {
+                csv::ErrorKind::Deserialize { err, .. } => format!("Row {}: {}", row, err),
+                _ => format!("Row {}: {}", row, e),
+            })?;
+
+            let point = record
+                .validate()
+                .map_err(|e| format!("Row {}: {}", row, e))?;
+            if rows.iter().any(|(other, _)| *other == point) {
+                return Err(
+                    format!("Row {}: Hex {} has more than one world", row, record.hex).into(),
+                );
+            }
+            rows.push((point, record));
+        }
+        Ok(Self { rows })
+    }
+
+    /** Create a copy of `subsector` whose worlds are exactly the ones in this table.
+
+    Worlds already at a row's hex are updated with the data of that row, so any data not in the
+    table is kept. Rows at empty hexes create new worlds and worlds without a row are removed.
+    */
+    pub fn apply_to(self, subsector: &Subsector) -> Subsector {
+        let mut map = BTreeMap::new();
+        for (point, row) in self.rows {
+            let mut world = subsector.map.get(&point).cloned().unwrap_or_else(|| World {
+                rule_set: subsector.rule_set,
+                ..World::empty()
+            });
+            row.apply_to(&mut world);
+            if world.trade_goods.is_empty() {
+                world.generate_trade_goods();
+            }
+            map.insert(point, world);
+        }
+
+        let mut subsector = Subsector {
+            map,
+            ..subsector.clone()
+        };
+        subsector.prune_xboat_routes();
+        subsector.prune_owners();
+        subsector
+    }
+}
+
+impl fmt::Display for CsvTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        for (_, row) in self.rows.iter() {
+            writer.serialize(row).map_err(|_| fmt::Error)?;
+        }
+        let bytes = writer.into_inner().map_err(|_| fmt::Error)?;
+        write!(f, "{}", String::from_utf8_lossy(&bytes))
+    }
+}
+
+impl From<&Subsector> for CsvTable {
+    fn from(subsector: &Subsector) -> Self {
+        let rows = subsector
+            .map
+            .iter()
+            .map(|(point, world)| (*point, CsvRecord::from((world, point))))
+            .collect();
+        Self { rows }
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::{
+    serialize::migration::{self, SchemaError, SCHEMA_VERSION},
+    Annotation, Bookmark, ImperialDate, JournalEntry, Point, Polity, RuleSet, SectorLocation,
+    Subsector, World,
+};
+
+/** Representation of a `Subsector` that can be easily serialized to JSON.
+
+Specifically, `serde_json` requires all maps use `String` keys, so to accomodate this we create this
+representation using the result of `Point::to_string` as the key for `Subsector::map`.
+*/
+#[derive(Debug, Deserialize, Serialize)]
+pub struct JsonableSubsector {
+    /// Version of the schema the file was saved with; 0 for files saved before there was one
+    #[serde(default)]
+    schema_version: u32,
+    name: String,
+    map: BTreeMap<String, World>,
+    #[serde(default)]
+    hooks: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    journal: Vec<JournalEntry>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    bookmarks: Vec<Bookmark>,
+    #[serde(default)]
+    rule_set: RuleSet,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    polities: Vec<Polity>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    xboat_routes: Vec<(String, String)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    location: Option<SectorLocation>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    annotations: Vec<Annotation>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    campaign_date: Option<ImperialDate>,
+}
+
+impl fmt::Display for JsonableSubsector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(self).unwrap())
+    }
+}
+
+impl JsonableSubsector {
+    /** Read a `JsonableSubsector` from `json`, upgrading it from whatever schema it was saved with
+    first.
+    */
+    pub fn try_from_json(json: &str) -> Result<Self, Box<dyn Error>> {
+        let mut json: serde_json::Value = serde_json::from_str(json)?;
+        migration::migrate(&mut json)?;
+        Ok(serde_json::from_value(json)?)
+    }
+}
+
+impl From<&Subsector> for JsonableSubsector {
+    fn from(subsector: &Subsector) -> Self {
+        let mut map: BTreeMap<String, World> = BTreeMap::new();
+        for (point, world) in subsector.map.iter() {
+            map.insert(point.to_string(), world.clone());
+        }
+
+        Self {
+            schema_version: SCHEMA_VERSION,
+            name: subsector.name.clone(),
+            map,
+            hooks: subsector.hooks.clone(),
+            journal: subsector.journal.clone(),
+            bookmarks: subsector.bookmarks.clone(),
+            rule_set: subsector.rule_set,
+            polities: subsector.polities.clone(),
+            xboat_routes: subsector
+                .xboat_routes
+                .iter()
+                .map(|(from, to)| (from.to_string(), to.to_string()))
+                .collect(),
+            location: subsector.location.clone(),
+            annotations: subsector.annotations.clone(),
+            campaign_date: subsector.campaign_date,
+        }
+    }
+}
+
+impl TryFrom<JsonableSubsector> for Subsector {
+    type Error = Box<dyn Error>;
+    fn try_from(jsonable: JsonableSubsector) -> Result<Self, Self::Error> {
+        let JsonableSubsector {
+            schema_version,
+            name,
+            map,
+            hooks,
+            journal,
+            bookmarks,
+            rule_set,
+            polities,
+            xboat_routes,
+            location,
+            annotations,
+            campaign_date,
+        } = jsonable;
+        if schema_version > SCHEMA_VERSION {
+            return Err(SchemaError::TooNew(schema_version).into());
+        }
+
+        let mut point_map: BTreeMap<Point, World> = BTreeMap::new();
+        for (point_str, mut world) in map {
+            let point = Point::try_from(&point_str[..])?;
+            world.detect_travel_code_override();
+            if schema_version < SCHEMA_VERSION {
+                // Fill in whatever the older schema didn't save, like planetoid belts
+                world.normalize_data();
+                world.generate_missing_faction_religions();
+            } else {
+                world.resolve_trade_codes();
+                if !world.travel_code_overridden {
+                    world.resolve_travel_code();
+                }
+            }
+            point_map.insert(point, world);
+        }
+
+        let xboat_routes = xboat_routes
+            .iter()
+            .map(|(from, to)| Ok((Point::try_from(&from[..])?, Point::try_from(&to[..])?)))
+            .collect::<Result<_, Box<dyn Error>>>()?;
+
+        let mut subsector = Self {
+            name,
+            map: point_map,
+            hooks,
+            journal,
+            bookmarks,
+            rule_set,
+            polities,
+            xboat_routes,
+            location,
+            annotations,
+            campaign_date,
+        };
+        subsector.prune_xboat_routes();
+        subsector.prune_owners();
+        Ok(subsector)
+    }
+}
@@ -0,0 +1,167 @@
+use std::error::Error;
+use std::fmt;
+
+use serde_json::{Map, Value};
+
+use crate::astrography::Point;
+
+/// Version of the JSON save format written by this version of the app
+pub const SCHEMA_VERSION: u32 = 4;
+
+/** Step that upgrades a save from the schema version at its index to the next one. */
+type Migration = fn(&mut Map<String, Value>) -> Result<(), String>;
+
+const MIGRATIONS: [Migration; SCHEMA_VERSION as usize] =
+    [migrate_v0, migrate_v1, migrate_v2, migrate_v3];
+
+/** Problem that kept a saved subsector from being upgraded to the current schema. */
+#[derive(Clone, Debug, PartialEq)]
+pub enum SchemaError {
+    /// The file isn't a JSON object, or its schema version isn't a number
+    Malformed(String),
+    /// The file was saved by a newer version of the app than this one
+    TooNew(u32),
+    /// Upgrading from the given schema version failed, for the reason in the message
+    Migration(u32, String),
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed(message) => write!(f, "Invalid subsector file: {}", message),
+            Self::TooNew(version) => write!(
+                f,
+                "File was saved with schema version {}, but this version of the app only \
+                supports up to version {}; update the app to open it",
+                version, SCHEMA_VERSION
+            ),
+            Self::Migration(version, message) => write!(
+                f,
+                "Could not upgrade file from schema version {}: {}",
+                version, message
+            ),
+        }
+    }
+}
+
+impl Error for SchemaError {}
+
+/** Upgrade the saved subsector in `json` to the current schema one version at a time.
+
+Files saved before there was a schema version are version 0. The `schema_version` in `json` is left
+as it was so that whatever loads it knows which data the older version didn't save.
+
+# Returns
+- `Ok(u32)` with the schema version `json` was saved with
+- `Err(SchemaError)` if `json` is newer than the app supports or couldn't be upgraded
+*/
+pub(crate) fn migrate(json: &mut Value) -> Result<u32, SchemaError> {
+    let object = json
+        .as_object_mut()
+        .ok_or_else(|| SchemaError::Malformed("expected a JSON object".to_string()))?;
+    let version = match object.get("schema_version") {
+        None => 0,
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(|| SchemaError::Malformed(format!("bad schema version {}", version)))?,
+    };
+    if version > SCHEMA_VERSION {
+        return Err(SchemaError::TooNew(version));
+    }
+
+    for (from, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        migration(object).map_err(|message| SchemaError::Migration(from as u32, message))?;
+    }
+    Ok(version)
+}
+
+/** Rewrite a hex saved with a legacy `'` or `_` prefix as a plain one, e.g. "_0101" as "0101". */
+fn strip_point_prefix(point_str: &str) -> Result<String, String> {
+    Point::try_from(point_str)
+        .map(|point| point.to_string())
+        .map_err(|e| format!("bad hex '{}': {}", point_str, e))
+}
+
+/** Upgrade an unversioned save, whose hexes may have legacy prefixes. */
+fn migrate_v0(object: &mut Map<String, Value>) -> Result<(), String> {
+    if let Some(map) = object.get_mut("map") {
+        let worlds = map
+            .as_object_mut()
+            .ok_or("expected the map to be a JSON object")?;
+        let mut migrated = Map::new();
+        for (point_str, world) in std::mem::take(worlds) {
+            let point_str = strip_point_prefix(&point_str)?;
+            if migrated.insert(point_str.clone(), world).is_some() {
+                return Err(format!("more than one world at hex {}", point_str));
+            }
+        }
+        *worlds = migrated;
+    }
+
+    if let Some(Value::Array(routes)) = object.get_mut("xboat_routes") {
+        for point in routes.iter_mut().filter_map(Value::as_array_mut).flatten() {
+            if let Some(point_str) = point.as_str() {
+                *point = Value::String(strip_point_prefix(point_str)?);
+            }
+        }
+    }
+    Ok(())
+}
+
+/** Upgrade a save from before worlds had a law level for each law category.
+
+Nothing in the file itself changes; the categories are rolled when its worlds are loaded, like any
+other data an older schema didn't save.
+*/
+fn migrate_v1(_object: &mut Map<String, Value>) -> Result<(), String> {
+    Ok(())
+}
+
+/** Upgrade a save from before starports had fuel markets, which are rolled when it's loaded. */
+fn migrate_v2(_object: &mut Map<String, Value>) -> Result<(), String> {
+    Ok(())
+}
+
+/** Upgrade a save from before factions had religions, which are rolled when it's loaded. */
+fn migrate_v3(_object: &mut Map<String, Value>) -> Result<(), String> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn migrate_legacy_points() {
+        let mut json = json!({
+            "name": "Old",
+            "map": { "'0101": {}, "_0102": {}, "0103": {} },
+            "xboat_routes": [["_0101", "'0102"]],
+        });
+        assert_eq!(migrate(&mut json), Ok(0));
+        let keys: Vec<&String> = json["map"].as_object().unwrap().keys().collect();
+        assert_eq!(keys, ["0101", "0102", "0103"]);
+        assert_eq!(json["xboat_routes"], json!([["0101", "0102"]]));
+        assert!(json.get("schema_version").is_none());
+
+        let mut json = json!({ "map": { "'0101": {}, "_0101": {} } });
+        assert!(matches!(
+            migrate(&mut json),
+            Err(SchemaError::Migration(0, _))
+        ));
+
+        let mut json = json!({ "map": {}, "schema_version": SCHEMA_VERSION });
+        assert_eq!(migrate(&mut json), Ok(SCHEMA_VERSION));
+        let mut json = json!({ "map": {}, "schema_version": SCHEMA_VERSION + 1 });
+        assert_eq!(
+            migrate(&mut json),
+            Err(SchemaError::TooNew(SCHEMA_VERSION + 1))
+        );
+        assert!(matches!(
+            migrate(&mut json!([])),
+            Err(SchemaError::Malformed(_))
+        ));
+    }
+}
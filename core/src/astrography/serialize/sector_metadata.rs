@@ -0,0 +1,113 @@
+use std::fmt;
+use std::io;
+
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+
+use crate::astrography::{Polity, Subsector};
+
+/** Traveller Map sector metadata describing a `Subsector`, written as XML.
+
+The `Subsector` is placed at its location within its sector if it has one, or as subsector A of a
+sector of the same name otherwise. Its polities are listed as allegiances and its x-boat network as
+routes, so that the metadata can be uploaded to Traveller Map alongside the column delimited table.
+*/
+pub struct SectorMetadata {
+    name: String,
+    sector: String,
+    /// Letter of the `Subsector` within the sector
+    index: char,
+    allegiances: Vec<Polity>,
+    /// Hex codes of the ends of each x-boat link, relative to the sector
+    xboat_routes: Vec<(String, String)>,
+}
+
+impl fmt::Display for SectorMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut writer = quick_xml::Writer::new_with_indent(io::Cursor::new(Vec::new()), b' ', 2);
+        let xml_error = |_| fmt::Error;
+
+        writer
+            .write_event(Event::Decl(BytesDecl::new("1.0", Some("utf-8"), None)))
+            .map_err(xml_error)?;
+        writer
+            .write_event(Event::Start(BytesStart::new("Sector")))
+            .map_err(xml_error)?;
+
+        writer
+            .create_element("Name")
+            .write_text_content(BytesText::new(&self.sector))
+            .map_err(xml_error)?;
+
+        writer
+            .create_element("Subsectors")
+            .write_inner_content(|writer| {
+                writer
+                    .create_element("Subsector")
+                    .with_attribute(("Index", &self.index.to_string()[..]))
+                    .write_text_content(BytesText::new(&self.name))?;
+                Ok(())
+            })
+            .map_err(xml_error)?;
+
+        if !self.allegiances.is_empty() {
+            writer
+                .create_element("Allegiances")
+                .write_inner_content(|writer| {
+                    for polity in &self.allegiances {
+                        writer
+                            .create_element("Allegiance")
+                            .with_attribute(("Code", &polity.code[..]))
+                            .write_text_content(BytesText::new(&polity.name))?;
+                    }
+                    Ok(())
+                })
+                .map_err(xml_error)?;
+        }
+
+        if !self.xboat_routes.is_empty() {
+            writer
+                .create_element("Routes")
+                .write_inner_content(|writer| {
+                    for (start, end) in &self.xboat_routes {
+                        writer
+                            .create_element("Route")
+                            .with_attributes(vec![
+                                ("Start", &start[..]),
+                                ("End", &end[..]),
+                                ("Type", "Xboat"),
+                            ])
+                            .write_empty()?;
+                    }
+                    Ok(())
+                })
+                .map_err(xml_error)?;
+        }
+
+        writer
+            .write_event(Event::End(BytesEnd::new("Sector")))
+            .map_err(xml_error)?;
+
+        let xml = String::from_utf8(writer.into_inner().into_inner()).map_err(|_| fmt::Error)?;
+        writeln!(f, "{}", xml)
+    }
+}
+
+impl From<&Subsector> for SectorMetadata {
+    fn from(subsector: &Subsector) -> Self {
+        let (sector, index) = match &subsector.location {
+            Some(location) => (location.sector.clone(), location.index),
+            None => (subsector.name.clone(), 'A'),
+        };
+        Self {
+            name: subsector.name.clone(),
+            sector,
+            index,
+            allegiances: subsector.polities.clone(),
+            xboat_routes: subsector
+                .xboat_routes
+                .iter()
+                .map(|(start, end)| (subsector.export_hex(start), subsector.export_hex(end)))
+                .collect(),
+        }
+    }
+}
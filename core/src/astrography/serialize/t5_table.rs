@@ -0,0 +1,336 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::astrography::{Point, Sophont, Subsector, World};
+
+const UWP_REFERENCE: &str = r"# UWP Reference Diagram:
+#
+#     CA6A643-9
+#     ||||||| `- Tech Level
+#     |||||| `- Law Level
+#     ||||| `- Government
+#     |||| `- Population
+#     ||| `- Hydrographics
+#     || `- Atmosphere
+#     | `- Size
+#      `- Starport";
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum Header {
+    Hex,
+    Name,
+    UniversalWorldProfile,
+    Remarks,
+    ImportanceExtension,
+    EconomicExtension,
+    CulturalExtension,
+    Nobility,
+    Bases,
+    Zone,
+    PopModBeltsGasGiants,
+    Worlds,
+    Allegiance,
+    Stellar,
+}
+
+impl Header {
+    const ALL_VALUES: [Header; 14] = [
+        Header::Hex,
+        Header::Name,
+        Header::UniversalWorldProfile,
+        Header::Remarks,
+        Header::ImportanceExtension,
+        Header::EconomicExtension,
+        Header::CulturalExtension,
+        Header::Nobility,
+        Header::Bases,
+        Header::Zone,
+        Header::PopModBeltsGasGiants,
+        Header::Worlds,
+        Header::Allegiance,
+        Header::Stellar,
+    ];
+}
+
+impl fmt::Display for Header {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Header::Hex => "Hex",
+            Header::Name => "Name",
+            Header::UniversalWorldProfile => "UWP",
+            Header::Remarks => "Remarks",
+            Header::Bases => "B",
+            Header::Zone => "Z",
+            Header::Allegiance => "A",
+            Header::ImportanceExtension => "{Ix}",
+            Header::EconomicExtension => "(Ex)",
+            Header::CulturalExtension => "[Cx]",
+            Header::Nobility => "N",
+            Header::PopModBeltsGasGiants => "PBG",
+            Header::Worlds => "W",
+            Header::Stellar => "Stellar",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+struct T5Record {
+    columns: HashMap<Header, String>,
+}
+
+impl T5Record {
+    /** Create the record of `world` with `hex` as the text of its hex code, e.g. "0101".
+
+    `owner_hex` converts the locations of owner worlds to hex codes in the same way, and `capital`
+    marks the world as the capital of a polity.
+    */
+    fn new(
+        world: &World,
+        hex: String,
+        owner_hex: impl Fn(&Point) -> String,
+        capital: bool,
+    ) -> Self {
+        let mut columns = HashMap::new();
+        for header in Header::ALL_VALUES {
+            let _ = match header {
+                Header::Hex => columns.insert(header, hex.clone()),
+                Header::Name => columns.insert(header, world.name.clone()),
+                Header::UniversalWorldProfile => columns.insert(header, world.profile_str()),
+                Header::Remarks => {
+                    let mut remarks = Vec::new();
+                    if !world.trade_codes.is_empty() {
+                        remarks.push(world.trade_code_str());
+                    }
+                    remarks.extend(world.sophonts.iter().map(Sophont::remark));
+                    if capital {
+                        remarks.push("Cp".to_string());
+                    }
+                    if let Some(owner) = world.owner {
+                        remarks.push(format!("O:{}", owner_hex(&owner)));
+                    }
+                    if remarks.is_empty() {
+                        remarks.push("-".to_string());
+                    }
+                    columns.insert(header, remarks.join(" "))
+                }
+                Header::Bases => columns.insert(header, world.base_str()),
+                Header::Zone => columns.insert(header, world.travel_code.as_short_string()),
+                Header::Allegiance => {
+                    let allegiance = if world.allegiance.is_empty() {
+                        "Na".to_string()
+                    } else {
+                        world.allegiance.clone()
+                    };
+                    columns.insert(header, allegiance)
+                }
+                Header::ImportanceExtension => columns.insert(header, world.importance_extension()),
+                Header::EconomicExtension => columns.insert(header, world.economic_extension()),
+                Header::CulturalExtension => columns.insert(header, world.cultural_extension()),
+                Header::Nobility => columns.insert(header, nobility(world, capital)),
+                Header::PopModBeltsGasGiants => columns.insert(header, world.pbg_str()),
+                Header::Worlds => {
+                    let worlds = 1 + world.planetoid_belts.unwrap_or(0) + world.gas_giants;
+                    columns.insert(header, worlds.to_string())
+                }
+                Header::Stellar => {
+                    let stellar = if world.stellar.is_empty() {
+                        "-".to_string()
+                    } else {
+                        world.stellar.clone()
+                    };
+                    columns.insert(header, stellar)
+                }
+            };
+        }
+
+        Self { columns }
+    }
+}
+
+/** The T5 nobility codes of the nobles resident on `world`, with a subsector duke (F) added if it
+is a capital.
+*/
+fn nobility(world: &World, capital: bool) -> String {
+    let mut nobility = world.nobility.clone();
+    if capital && !nobility.contains('F') {
+        nobility.push('F');
+    }
+    if nobility.is_empty() {
+        nobility.push('-');
+    }
+    nobility
+}
+
+pub struct T5Table {
+    rows: Vec<T5Record>,
+}
+
+impl fmt::Display for T5Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Find the minimum width of each column
+        let mut widths = HashMap::new();
+        for header in Header::ALL_VALUES {
+            // Find the width of the longest value in either the headers or the values
+            let width = header.to_string().len();
+            let width = self.rows.iter().fold(width, |acc, row| {
+                let len = row.columns[&header].len();
+                if len > acc {
+                    len
+                } else {
+                    acc
+                }
+            });
+
+            widths.insert(header, width);
+        }
+
+        // Write out the header and separator rows
+        let width = widths[&Header::ALL_VALUES[0]];
+        let mut header_row = format!("{:width$}", Header::ALL_VALUES[0].to_string());
+        let mut separator_row = format!("{:-<width$}", "");
+        for header in &Header::ALL_VALUES[1..] {
+            let width = widths[header];
+            header_row += &format!("  {:width$}", header.to_string());
+            separator_row += &format!("  {:-<width$}", "");
+        }
+        writeln!(f, "{}", header_row)?;
+        writeln!(f, "{}", separator_row)?;
+
+        for row in self.rows.iter() {
+            let header = &Header::ALL_VALUES[0];
+            let width = widths[header];
+            let mut row_str = format!("{:width$}", row.columns[header]);
+            for header in &Header::ALL_VALUES[1..] {
+                let width = widths[header];
+                row_str += &format!("  {:width$}", row.columns[header]);
+            }
+            let trimmed = row_str.trim();
+
+            writeln!(f, "{}", trimmed)?;
+        }
+
+        write!(f, "\n{}", UWP_REFERENCE)
+    }
+}
+
+impl From<&Subsector> for T5Table {
+    fn from(value: &Subsector) -> Self {
+        let mut rows = Vec::new();
+        for (point, world) in value.map.iter() {
+            let hex = value.export_hex(point);
+            let capital = value
+                .polities()
+                .iter()
+                .any(|polity| polity.capital == *point);
+            rows.push(T5Record::new(
+                world,
+                hex,
+                |owner| value.export_hex(owner),
+                capital,
+            ));
+        }
+
+        Self { rows }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::TradeCode;
+
+    #[test]
+    fn capital_nobility() {
+        let mut world = World::empty();
+        world.trade_codes.clear();
+        let record = T5Record::new(&world, "0101".to_string(), Point::to_string, false);
+        assert_eq!(record.columns[&Header::Nobility], "-");
+
+        world.population.code = 9;
+        world.trade_codes.insert(TradeCode::Hi);
+        world.trade_codes.insert(TradeCode::Ri);
+        world.generate_nobility();
+        let record = T5Record::new(&world, "0101".to_string(), Point::to_string, true);
+        assert!(record.columns[&Header::Nobility].starts_with("BCE"));
+        assert!(record.columns[&Header::Nobility].ends_with('F'));
+        assert_eq!(record.columns[&Header::Remarks], "Hi Ri Cp");
+
+        // Nobility edited by hand is exported as it is
+        world.nobility = "BD".to_string();
+        let record = T5Record::new(&world, "0101".to_string(), Point::to_string, false);
+        assert_eq!(record.columns[&Header::Nobility], "BD");
+    }
+
+    #[test]
+    fn column_alignment() {
+        let mut world = World::empty();
+        world.name = "Regina".to_string();
+        world.gas_giants = 2;
+        world.planetoid_belts = Some(1);
+        world.stellar = "F7 V BD M3 V".to_string();
+        let table = T5Table {
+            rows: vec![T5Record::new(
+                &world,
+                "0101".to_string(),
+                Point::to_string,
+                false,
+            )],
+        };
+        let output = table.to_string();
+        let mut lines = output.lines();
+        let headers: Vec<&str> = lines.next().unwrap().split_whitespace().collect();
+        assert_eq!(
+            headers,
+            [
+                "Hex", "Name", "UWP", "Remarks", "{Ix}", "(Ex)", "[Cx]", "N", "B", "Z", "PBG", "W",
+                "A", "Stellar"
+            ]
+        );
+
+        // Every value starts under its header
+        let header_row = output.lines().next().unwrap();
+        let separator_row = lines.next().unwrap();
+        let row = lines.next().unwrap();
+        let worlds_column = header_row.find(" W ").unwrap() + 1;
+        assert!(row[worlds_column..].starts_with("4 "));
+        assert_eq!(header_row.find("Stellar"), row.find("F7 V BD M3 V"));
+        assert!(row.len() <= separator_row.len());
+    }
+
+    #[test]
+    fn owner_remarks() {
+        let point = Point { x: 2, y: 3 };
+        let mut world = World::empty();
+        world.trade_codes.clear();
+        world.owner = Some(Point { x: 1, y: 10 });
+        let record = T5Record::new(&world, point.to_string(), Point::to_string, false);
+        assert_eq!(record.columns[&Header::Hex], "0203");
+        assert_eq!(record.columns[&Header::Remarks], "O:0110");
+
+        world.resolve_trade_codes();
+        let record = T5Record::new(&world, point.to_string(), Point::to_string, false);
+        assert_eq!(
+            record.columns[&Header::Remarks],
+            format!("{} O:0110", world.trade_code_str())
+        );
+    }
+
+    #[test]
+    fn sophont_remarks() {
+        let mut world = World::empty();
+        world.trade_codes.clear();
+        world.sophonts.push(Sophont {
+            name: "Amindii".to_string(),
+            tenths: 2,
+            native: true,
+        });
+        world.sophonts.push(Sophont {
+            name: "Vargr".to_string(),
+            tenths: 0,
+            native: false,
+        });
+        world.owner = Some(Point { x: 1, y: 10 });
+        let record = T5Record::new(&world, "0203".to_string(), Point::to_string, false);
+        assert_eq!(record.columns[&Header::Remarks], "(Amindii)2 Varg0 O:0110");
+    }
+}
@@ -0,0 +1,327 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+
+use crate::astrography::{
+    extensions::ehex_value, Point, RuleSet, SectorLocation, Subsector, TravelCode, World, TABLES,
+};
+
+/// Sector name used when the table doesn't have a "Sector" column
+const UNKNOWN_SECTOR: &str = "Unknown Sector";
+
+/** One world of a Traveller Map tab delimited sector file. */
+#[derive(Debug, Default)]
+pub(super) struct TravellerMapRecord {
+    /// Location of the world within the whole sector
+    pub(super) sector_hex: Point,
+    pub(super) name: String,
+    pub(super) uwp: String,
+    pub(super) bases: String,
+    pub(super) remarks: String,
+    pub(super) zone: String,
+    pub(super) pbg: String,
+    pub(super) allegiance: String,
+    pub(super) stellar: String,
+}
+
+impl TravellerMapRecord {
+    /** Create a random `World` using `rule_set` and overwrite it with the canon data of the record.
+
+    Data that Traveller Map doesn't have, like factions and temperature, stays random but is rolled
+    again where it depends on the canon data. Codes beyond the end of this app's tables are clamped
+    to the last entry of the table.
+    */
+    pub(super) fn to_world(&self, rule_set: RuleSet) -> Result<World, Box<dyn Error>> {
+        let hex = self.sector_hex;
+        let uwp: Vec<char> = self.uwp.chars().collect();
+        if uwp.len() != 9 || uwp[7] != '-' {
+            return Err(format!("UWP '{}' of the world at {} is malformed", self.uwp, hex).into());
+        }
+        let code = |index: usize, max: usize| {
+            ehex_value(uwp[index])
+                .map(|code| (code as usize).min(max))
+                .ok_or_else(|| {
+                    format!(
+                        "UWP '{}' of the world at {} has an invalid digit '{}'",
+                        self.uwp, hex, uwp[index]
+                    )
+                })
+        };
+
+        let mut world = World::with_rule_set(self.name.clone(), rule_set);
+
+        let class = uwp[0].to_ascii_uppercase().to_string();
+        world.starport = TABLES
+            .starport_table
+            .iter()
+            .find(|starport| starport.class.to_string() == class)
+            .ok_or_else(|| {
+                format!(
+                    "UWP '{}' of the world at {} has an invalid starport '{}'",
+                    self.uwp, hex, uwp[0]
+                )
+            })?
+            .clone();
+        world.generate_berthing_cost();
+
+        world.size = code(1, World::SIZE_MAX as usize)? as u16;
+        world.generate_diameter();
+        world.atmosphere = TABLES.atmo_table[code(2, TABLES.atmo_table.len() - 1)?].clone();
+        world.generate_temperature();
+        world.hydrographics = TABLES.hydro_table[code(3, TABLES.hydro_table.len() - 1)?].clone();
+        world.population = TABLES.pop_table[code(4, TABLES.pop_table.len() - 1)?].clone();
+        let government = code(5, TABLES.gov_table.len() - 1)?;
+        if world.government.code as usize != government {
+            world.government.safe_mutate(&TABLES.gov_table[government]);
+        }
+        world.law_level = TABLES.law_table[code(6, TABLES.law_table.len() - 1)?].clone();
+        world.tech_level =
+            TABLES.tech_level_table[code(8, TABLES.tech_level_table.len() - 1)?].clone();
+        world.generate_starport_details();
+
+        let bases = self.bases.to_ascii_uppercase();
+        let has_any = |codes: &str| bases.chars().any(|base| codes.contains(base));
+        world.has_naval_base = has_any("NKD");
+        world.has_scout_base = has_any("SWV");
+        world.has_research_base = has_any("R");
+        world.has_tas = has_any("T");
+        world.has_pirate_base = has_any("PC");
+
+        world.travel_code = match self.zone.trim() {
+            "A" | "a" => TravelCode::Amber,
+            "R" | "r" => TravelCode::Red,
+            _ => TravelCode::Safe,
+        };
+        world.detect_travel_code_override();
+
+        let pbg: Vec<u16> = self.pbg.chars().filter_map(ehex_value).collect();
+        if let [_, belts, gas_giants] = pbg[..] {
+            world.planetoid_belts = Some(belts as i32);
+            world.gas_giants = gas_giants as i32;
+        }
+
+        world.allegiance = match self.allegiance.trim() {
+            // Traveller Map's codes for non-aligned worlds
+            allegiance if allegiance.starts_with("Na") || allegiance.starts_with("--") => {
+                String::new()
+            }
+            allegiance => allegiance.to_string(),
+        };
+        if !self.stellar.is_empty() {
+            world.stellar = self.stellar.clone();
+        }
+
+        world.resolve_trade_codes();
+        world.generate_trade_goods();
+        world.generate_fuel_market();
+        Ok(world)
+    }
+
+    /** Find the sector hex of the world that owns this one according to its remarks, if any. */
+    fn owner_hex(&self) -> Option<Point> {
+        self.remarks
+            .split_whitespace()
+            .filter_map(|remark| remark.strip_prefix("O:"))
+            .find_map(|owner| Point::try_from(owner).ok())
+    }
+}
+
+/** A Traveller Map tab delimited sector file, as downloaded from the site or its API.
+
+Only the "Hex", "Name", and "UWP" columns are required. A file may hold any number of the
+subsectors of a single sector; each can be converted into a `Subsector` on its own.
+*/
+pub struct TravellerMapTable {
+    sector: String,
+    rows: Vec<TravellerMapRecord>,
+}
+
+impl TravellerMapTable {
+    /** Parse a `TravellerMapTable` from the contents of a tab delimited file. */
+    pub fn try_from_tsv(tsv: &str) -> Result<Self, Box<dyn Error>> {
+        let mut lines = tsv
+            .lines()
+            .filter(|line| !line.trim().is_empty() && !line.starts_with('#'));
+        let headers: Vec<&str> = lines
+            .next()
+            .ok_or("The file is empty")?
+            .split('\t')
+            .map(str::trim)
+            .collect();
+        let column = |name: &str| headers.iter().position(|header| *header == name);
+        let required =
+            |name: &str| column(name).ok_or_else(|| format!("The file has no '{}' column", name));
+
+        let hex_column = required("Hex")?;
+        let name_column = required("Name")?;
+        let uwp_column = required("UWP")?;
+        let sector_column = column("Sector");
+        let other_columns = ["Bases", "Remarks", "Zone", "PBG", "Allegiance"].map(column);
+        // Traveller Map calls the stellar data "Stars" in tab delimited files
+        let stellar_column = column("Stars").or_else(|| column("Stellar"));
+
+        let mut sector = None;
+        let mut rows = Vec::new();
+        for line in lines {
+            let fields: Vec<&str> = line.split('\t').map(str::trim).collect();
+            let field = |index: Option<usize>| {
+                index
+                    .and_then(|index| fields.get(index))
+                    .map_or_else(String::new, |field| field.to_string())
+            };
+
+            let hex = field(Some(hex_column));
+            let sector_hex = Point::try_from(&hex[..])
+                .map_err(|e| format!("Hex '{}' could not be read: {}", hex, e))?;
+            if sector.is_none() {
+                sector = Some(field(sector_column)).filter(|sector| !sector.is_empty());
+            }
+
+            let [bases, remarks, zone, pbg, allegiance] = other_columns.map(field);
+            rows.push(TravellerMapRecord {
+                sector_hex,
+                name: field(Some(name_column)),
+                uwp: field(Some(uwp_column)),
+                bases,
+                remarks,
+                zone,
+                pbg,
+                allegiance,
+                stellar: field(stellar_column),
+            });
+        }
+
+        Ok(Self {
+            sector: sector.unwrap_or_else(|| UNKNOWN_SECTOR.to_string()),
+            rows,
+        })
+    }
+
+    /** Get the letters of the subsectors that have at least one world in the table, in order. */
+    pub fn subsector_indices(&self) -> Vec<char> {
+        self.rows
+            .iter()
+            .filter_map(|row| SectorLocation::split_sector_hex(&row.sector_hex))
+            .map(|(index, _)| index)
+            .collect::<BTreeSet<char>>()
+            .into_iter()
+            .collect()
+    }
+
+    /** Create a new `Subsector` from the worlds of the table in the subsector lettered `index`.
+
+    Worlds that are owned by another world of the same subsector keep their owner. Traveller Map
+    files don't name their subsectors, so the `Subsector` is named after its location, which it also
+    remembers for exports.
+    */
+    pub fn to_subsector(
+        &self,
+        index: char,
+        rule_set: RuleSet,
+    ) -> Result<Subsector, Box<dyn Error>> {
+        let location = SectorLocation {
+            sector: self.sector.clone(),
+            index,
+        };
+        let mut subsector = Subsector::empty();
+        subsector.name = location.to_string();
+        subsector.rule_set = rule_set;
+
+        let mut owners = BTreeMap::new();
+        for row in &self.rows {
+            match SectorLocation::split_sector_hex(&row.sector_hex) {
+                Some((row_index, point)) if row_index == index => {
+                    if subsector.map.contains_key(&point) {
+                        return Err(
+                            format!("Hex {} has more than one world", row.sector_hex).into()
+                        );
+                    }
+                    subsector.map.insert(point, row.to_world(rule_set)?);
+                    if let Some(owner) = row.owner_hex() {
+                        owners.insert(point, owner);
+                    }
+                }
+                _ => continue,
+            }
+        }
+
+        if subsector.map.is_empty() {
+            return Err(format!("There are no worlds in subsector {} of the file", index).into());
+        }
+
+        for (point, owner) in owners {
+            if let Some((owner_index, owner)) = SectorLocation::split_sector_hex(&owner) {
+                if owner_index == index {
+                    subsector.map.get_mut(&point).unwrap().owner = Some(owner);
+                }
+            }
+        }
+        subsector.prune_owners();
+        subsector.location = Some(location);
+        Ok(subsector)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::{StarportClass, TradeCode};
+
+    const SPINWARD_MARCHES_C: &str = "Sector\tSS\tHex\tName\tUWP\tBases\tRemarks\tZone\tPBG\tAllegiance\tStars\n\
+        Spin\tC\t1910\tRegina\tA788899-C\tNS\tRi Pa Ph An Cp (Amindii)2 Varg0 Asla0 Sa\t\t703\tImDd\tF7 V BD M3 V\n\
+        Spin\tC\t2007\tRoup\tC777977-8\tW\tHi In\tA\t904\tImDd\tF0 V\n\
+        Spin\tC\t1705\tEfate\tB564500-B\tNS\tAg Ni O:1910\t\t600\tImDd\tM2 V\n\
+        Spin\tD\t2506\tLanth\tA434934-F\tNS\tHi In\t\t105\tImDd\tK2 V\n";
+
+    #[test]
+    fn traveller_map_import() {
+        let table = TravellerMapTable::try_from_tsv(SPINWARD_MARCHES_C).unwrap();
+        assert_eq!(table.subsector_indices(), vec!['C', 'D']);
+
+        let subsector = table.to_subsector('C', RuleSet::default()).unwrap();
+        assert_eq!(subsector.map.len(), 3);
+        assert_eq!(subsector.name, "Spin C");
+        assert_eq!(
+            subsector.location(),
+            Some(&SectorLocation {
+                sector: "Spin".to_string(),
+                index: 'C'
+            })
+        );
+
+        let regina = subsector.get_world(&Point { x: 3, y: 10 }).unwrap();
+        assert_eq!(regina.name, "Regina");
+        assert_eq!(regina.starport.class, StarportClass::A);
+        assert_eq!(regina.size, 7);
+        assert_eq!(regina.atmosphere.code, 8);
+        assert_eq!(regina.population.code, 8);
+        assert_eq!(regina.law_level.code, 9);
+        assert_eq!(regina.tech_level.code, 12);
+        assert!(regina.has_naval_base && regina.has_scout_base);
+        assert!(!regina.has_research_base);
+        assert_eq!(regina.planetoid_belts, Some(0));
+        assert_eq!(regina.gas_giants, 3);
+        assert_eq!(regina.allegiance, "ImDd");
+        assert_eq!(regina.stellar, "F7 V BD M3 V");
+
+        let roup = subsector.get_world(&Point { x: 4, y: 7 }).unwrap();
+        assert_eq!(roup.travel_code, TravelCode::Amber);
+        assert!(roup.has_scout_base);
+        assert!(roup.trade_codes.contains(&TradeCode::Hi));
+
+        let efate = subsector.get_world(&Point { x: 1, y: 5 }).unwrap();
+        assert_eq!(efate.owner, Some(Point { x: 3, y: 10 }));
+        assert_eq!(subsector.export_hex(&Point { x: 1, y: 5 }), "1705");
+        assert!(subsector.to_t5_table().contains("\n1705  Efate"));
+        assert!(subsector
+            .to_sector_metadata()
+            .contains("<Subsector Index=\"C\">Spin C</Subsector>"));
+
+        assert!(table.to_subsector('A', RuleSet::default()).is_err());
+        assert!(TravellerMapTable::try_from_tsv("Hex\tName\n0101\tNowhere\n").is_err());
+        let malformed = TravellerMapTable::try_from_tsv("Hex\tName\tUWP\n0101\tNowhere\tA7\n");
+        assert!(malformed
+            .unwrap()
+            .to_subsector('A', RuleSet::default())
+            .is_err());
+    }
+}
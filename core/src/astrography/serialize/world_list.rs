@@ -0,0 +1,163 @@
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+use crate::astrography::{
+    serialize::traveller_map::TravellerMapRecord, Point, RuleSet, SectorLocation, Subsector,
+};
+
+/** Worlds read from a block of plain text, like a UWP list or a SEC file pasted from elsewhere.
+
+Each line holds one world as a hex, a name, and a UWP. The name may come before or after the hex,
+so both "0101 Regina A788899-C" and SEC lines like "Regina 1910 A788899-C N Ri Pa" can be read;
+anything after the UWP is ignored. Sector hexes like "1910" are moved to the same hex of their
+subsector. Blank lines, comments starting with '#', and lines without any digits, like column
+headers and separators, are skipped.
+*/
+#[derive(Debug)]
+pub struct WorldList {
+    /// Worlds that were read, at the hexes they were listed in
+    subsector: Subsector,
+    /// One-based number of each line that couldn't be read, along with why
+    errors: Vec<(usize, String)>,
+}
+
+impl WorldList {
+    /** Read every line of `text`, creating worlds with `rule_set` for the data UWPs don't have. */
+    pub fn parse(text: &str, rule_set: RuleSet) -> Self {
+        let mut subsector = Subsector::empty();
+        subsector.rule_set = rule_set;
+        let mut lines_read: BTreeMap<Point, usize> = BTreeMap::new();
+        let mut errors = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || !line.chars().any(|c| c.is_ascii_digit())
+            {
+                continue;
+            }
+
+            let result = parse_line(line).and_then(|record| {
+                let point = record.sector_hex;
+                if let Some(other_line) = lines_read.get(&point) {
+                    return Err(format!(
+                        "Hex {} already has a world on line {}",
+                        point, other_line
+                    ));
+                }
+                record
+                    .to_world(rule_set)
+                    .map(|world| (point, world))
+                    .map_err(|e| e.to_string())
+            });
+
+            match result {
+                Ok((point, world)) => {
+                    lines_read.insert(point, line_number);
+                    subsector.map.insert(point, world);
+                }
+                Err(e) => errors.push((line_number, e)),
+            }
+        }
+
+        Self { subsector, errors }
+    }
+
+    /** Get the lines that couldn't be read as worlds, by one-based line number. */
+    pub fn errors(&self) -> &[(usize, String)] {
+        &self.errors
+    }
+
+    /** Get the number of worlds that were read. */
+    pub fn len(&self) -> usize {
+        self.subsector.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.subsector.map.is_empty()
+    }
+
+    /** Get a `Subsector` holding every world that was read, to be merged into another. */
+    pub fn to_subsector(&self) -> Subsector {
+        self.subsector.clone()
+    }
+}
+
+/** Read the hex, name, and UWP of a single line of a world list. */
+fn parse_line(line: &str) -> Result<TravellerMapRecord, String> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let is_uwp = |token: &&str| token.len() == 9 && token.as_bytes()[7] == b'-';
+    let uwp_index = tokens
+        .iter()
+        .position(is_uwp)
+        .ok_or("No UWP like 'A788899-C' was found")?;
+
+    let is_hex = |token: &&str| token.len() == 4 && token.chars().all(|c| c.is_ascii_digit());
+    let hex_index = tokens[..uwp_index]
+        .iter()
+        .position(is_hex)
+        .ok_or("No hex like '0101' was found before the UWP")?;
+    let hex = Point::try_from(tokens[hex_index])
+        .map_err(|e| format!("Hex '{}' could not be read: {}", tokens[hex_index], e))?;
+
+    let point = if (1..=Subsector::COLUMNS as i32).contains(&hex.x)
+        && (1..=Subsector::ROWS as i32).contains(&hex.y)
+    {
+        hex
+    } else {
+        SectorLocation::split_sector_hex(&hex)
+            .map(|(_, point)| point)
+            .ok_or_else(|| format!("Hex {} is outside of a sector", hex))?
+    };
+
+    let name = tokens[..uwp_index]
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| *index != hex_index)
+        .map(|(_, token)| *token)
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Ok(TravellerMapRecord {
+        sector_hex: point,
+        name,
+        uwp: tokens[uwp_index].to_string(),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn world_list_parsing() {
+        let text = "\
+            # Pasted from a forum post\n\
+            Hex  Name          UWP\n\
+            ---- ------------- ---------\n\
+            0101 Regina        A788899-C\n\
+            New Rome 1912 B564500-B N Ag Ni\n\
+            \n\
+            0203 Broken        A7889-C\n\
+            0304 Nowhere\n\
+            Twin 0101 C430698-9\n\
+            4141 Far Out X000000-0\n";
+        let list = WorldList::parse(text, RuleSet::default());
+
+        assert_eq!(list.len(), 2);
+        let subsector = list.to_subsector();
+        let regina = subsector.get_world(&Point { x: 1, y: 1 }).unwrap();
+        assert_eq!(regina.name, "Regina");
+        assert_eq!(regina.profile_str(), "A788899-C");
+        let new_rome = subsector.get_world(&Point { x: 3, y: 2 }).unwrap();
+        assert_eq!(new_rome.name, "New Rome");
+        assert_eq!(new_rome.profile_str(), "B564500-B");
+
+        let error_lines: Vec<usize> = list.errors().iter().map(|(line, _)| *line).collect();
+        assert_eq!(error_lines, [7, 8, 9, 10]);
+        assert!(list.errors()[2].1.contains("line 4"));
+
+        assert!(WorldList::parse("", RuleSet::default()).is_empty());
+    }
+}
@@ -0,0 +1,183 @@
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::{random_subsector_name, NameStyle, World};
+use crate::dice;
+
+/// Share of the population given in tenths that means the whole population
+const WHOLE_POPULATION: u8 = 10;
+
+/** A non-human species living on a `World` and its share of the population.
+
+Humans aren't listed; they make up whatever share of the population is left over.
+*/
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Sophont {
+    pub name: String,
+    /// Share of the population in tenths, from 0 for a handful up to 10 for everyone
+    pub tenths: u8,
+    /// Whether the species evolved on the world, making the world its homeworld
+    pub native: bool,
+}
+
+impl Sophont {
+    /** Get the T5 remark for the species, e.g. "(Amindii)2" if it is native or "Asla0" otherwise.
+
+    Species that aren't native are abbreviated to the first four letters of their name, and a share
+    of the whole population is written as "W".
+    */
+    pub fn remark(&self) -> String {
+        let share = if self.tenths >= WHOLE_POPULATION {
+            "W".to_string()
+        } else {
+            self.tenths.to_string()
+        };
+
+        if self.native {
+            format!("({}){}", self.name, share)
+        } else {
+            let code: String = self
+                .name
+                .chars()
+                .filter(|c| c.is_alphanumeric())
+                .take(4)
+                .collect();
+            format!("{}{}", code, share)
+        }
+    }
+}
+
+impl World {
+    /** Add a minor species with a random name and a small share of the population.
+
+    # Returns
+    The index of the new `Sophont`.
+    */
+    pub fn add_sophont(&mut self) -> usize {
+        self.sophonts.push(Sophont {
+            name: random_subsector_name(NameStyle::Alien),
+            tenths: 0,
+            native: false,
+        });
+        self.sophonts.len() - 1
+    }
+
+    /** Get the share of the population that is human, in tenths. */
+    pub fn human_tenths(&self) -> u8 {
+        let others = self
+            .sophonts
+            .iter()
+            .fold(0u8, |sum, sophont| sum.saturating_add(sophont.tenths));
+        WHOLE_POPULATION.saturating_sub(others)
+    }
+
+    /** Randomly generate the species living on the `World`, replacing any that are there already.
+
+    Worlds with a comfortable atmosphere and some surface water are the most likely to have given
+    rise to native intelligent life, which may make up any share of the population. Otherwise, and
+    alongside any natives, humans are the majority and up to two minor species have settled among
+    them. Uninhabited worlds have no sophonts.
+    */
+    pub fn generate_sophonts(&mut self) {
+        self.sophonts.clear();
+        if self.population.code == 0 {
+            return;
+        }
+
+        if dice::roll_2d(6) + self.native_life_dm() >= 12 {
+            self.sophonts.push(Sophont {
+                name: random_subsector_name(NameStyle::Alien),
+                tenths: dice::roll_range(1..=WHOLE_POPULATION),
+                native: true,
+            });
+        }
+
+        for target in [10, 12] {
+            // Minor species never take the majority from humans
+            if dice::roll_2d(6) < target || self.human_tenths() <= 5 {
+                break;
+            }
+            let tenths = dice::roll_range(0..=2).min(self.human_tenths() - 6);
+            self.sophonts.push(Sophont {
+                name: random_subsector_name(NameStyle::Alien),
+                tenths,
+                native: false,
+            });
+        }
+    }
+
+    /** Modifier to the roll for native intelligent life for how hospitable the `World` is. */
+    fn native_life_dm(&self) -> i32 {
+        let atmo_mod = match self.atmosphere.code {
+            5 | 6 | 8 => 2,
+            4 | 7 | 9 => 1,
+            2 | 3 => 0,
+            _ => -4,
+        };
+
+        let hydro_mod = match self.hydrographics.code {
+            3..=8 => 1,
+            0 => -2,
+            _ => 0,
+        };
+
+        atmo_mod + hydro_mod
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::TABLES;
+
+    #[test]
+    fn sophont_remarks() {
+        let mut sophont = Sophont {
+            name: "Amindii".to_string(),
+            tenths: 2,
+            native: true,
+        };
+        assert_eq!(sophont.remark(), "(Amindii)2");
+
+        sophont.tenths = 10;
+        assert_eq!(sophont.remark(), "(Amindii)W");
+
+        sophont.native = false;
+        sophont.name = "K'kree".to_string();
+        sophont.tenths = 0;
+        assert_eq!(sophont.remark(), "Kkre0");
+    }
+
+    #[test]
+    fn generate_sophonts() {
+        const ATTEMPTS: usize = 1000;
+        let mut world = World::empty();
+        world.atmosphere = TABLES.atmo_table[6].clone();
+        world.hydrographics = TABLES.hydro_table[7].clone();
+        world.population = TABLES.pop_table[7].clone();
+
+        let mut found_natives = false;
+        for _ in 0..ATTEMPTS {
+            world.generate_sophonts();
+            let natives = world
+                .sophonts
+                .iter()
+                .filter(|sophont| sophont.native)
+                .count();
+            assert!(natives <= 1);
+            found_natives |= natives == 1;
+            if natives == 0 {
+                assert!(world.human_tenths() > 5);
+            }
+        }
+        assert!(found_natives);
+
+        world.population = TABLES.pop_table[0].clone();
+        world.generate_sophonts();
+        assert!(world.sophonts.is_empty());
+        assert_eq!(world.human_tenths(), 10);
+
+        assert_eq!(world.add_sophont(), 0);
+        world.sophonts[0].tenths = 12;
+        assert_eq!(world.human_tenths(), 0);
+    }
+}
@@ -0,0 +1,513 @@
+use std::{collections::BTreeMap, io};
+
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+
+use crate::astrography::{
+    hex_grid::HexGrid, legend, map_template, LayoutTransform, MapLayout, MapOptions,
+    MapRenderError, Point, PolityColor, Subsector, Translation, WorldSubtype,
+};
+
+/// Width of the page of standalone maps in mm, the same US Letter page as the built-in template's
+const PAGE_WIDTH: f64 = 215.9;
+/// Height of the page of standalone maps in mm
+const PAGE_HEIGHT: f64 = 279.4;
+/// Distance from the center of a hex to each of its corners
+const HEX_RADIUS: f64 = 13.115;
+/// Top left corner of the hexes of the built-in template, around hex 0101 at (39.336, 24.058)
+const GRID_ORIGIN: Translation = Translation {
+    x: 26.221,
+    y: 12.700,
+};
+/// Area between the title and the legend that grids turned on their side or with headers are
+/// shrunk to fit, as tall as the built-in template's grid and nearly as wide as the page
+const GRID_AREA: [f64; 4] = [8.0, 12.45, 199.9, 239.1];
+/// Distance from the center of a hex up to the baseline of its hex number
+const HEX_NUMBER_OFFSET: f64 = 8.33;
+/// Margin between the outermost hexes and the border drawn around the grid
+const GRID_BORDER_MARGIN: f64 = 0.25;
+/// Baseline of the subsector name at the top of the page
+const TITLE_POSITION: Translation = Translation { x: 107.95, y: 9.13 };
+
+/// Styles of the hexes and text of standalone maps, under the same class names as in the built-in
+/// template; the polity hex colors and the `MapStyle` of the map are added to these
+const STYLE: &str = "
+    .hex-blank { fill: #ffffff; stroke: #000000; stroke-width: 0.254; }
+    .text-subsector-name { font-size: 8.46667px; font-family: sans-serif; text-anchor: middle; }
+    .text-hex-coord { font-size: 3.52778px; font-family: sans-serif; text-anchor: middle; }
+    .text-legend-label { font-size: 2.82222px; font-family: sans-serif; text-anchor: end; }
+    .text-world-name {
+      font-size: 3.52777px; font-family: sans-serif; font-stretch: condensed;
+      text-anchor: middle;
+    }
+    .text-world-profile {
+      font-size: 2.8px; font-style: italic; font-family: sans-serif; font-stretch: condensed;
+      text-anchor: middle;
+    }
+    .text-starport-tl {
+      font-size: 3.52777px; font-style: italic; font-family: sans-serif;
+      font-stretch: condensed; text-anchor: middle;
+    }
+";
+/// Inline style of the outlines of world and gas giant symbols
+const SYMBOL_STROKE_STYLE: &str = "stroke:#000000;stroke-width:0.2794";
+
+/** Work out where a standalone map is placed in `layout`, or `None` for [`MapLayout::Portrait`]. */
+pub(super) fn layout_transform(layout: MapLayout) -> Option<LayoutTransform> {
+    map_template::page_layout_transform(PAGE_WIDTH, PAGE_HEIGHT, "mm", layout)
+}
+
+/** Get the hex grid of a standalone map whose hexes are drawn the way `options` says.
+
+Flat-topped hexes are placed the same as in the built-in template. Other grids are drawn the same
+size from the same corner, then moved and shrunk to fit the page by `HexGrid::fit_transform`.
+*/
+fn hex_grid(options: &MapOptions) -> HexGrid {
+    HexGrid {
+        orientation: options.hex_orientation,
+        radius: HEX_RADIUS,
+        origin: GRID_ORIGIN,
+    }
+}
+
+/** Draw a gas giant symbol, a ringed dot, centered at `center` and `scale` times its usual size. */
+pub(super) fn write_gas_giant_symbol<W: io::Write>(
+    writer: &mut quick_xml::Writer<W>,
+    center: Translation,
+    scale: f64,
+    id: &str,
+) -> Result<(), MapRenderError> {
+    let (x, y) = (center.x.to_string(), center.y.to_string());
+    writer
+        .create_element("g")
+        .with_attribute(("id", id))
+        .write_inner_content(|writer| {
+            writer
+                .create_element("circle")
+                .with_attributes(vec![
+                    (
+                        "style",
+                        &format!("fill:#000000;{}", SYMBOL_STROKE_STYLE)[..],
+                    ),
+                    ("cx", &x[..]),
+                    ("cy", &y[..]),
+                    ("r", &(0.766 * scale).to_string()[..]),
+                ])
+                .write_empty()?;
+            writer
+                .create_element("ellipse")
+                .with_attributes(vec![
+                    ("style", &format!("fill:none;{}", SYMBOL_STROKE_STYLE)[..]),
+                    ("cx", &x[..]),
+                    ("cy", &y[..]),
+                    ("rx", &(1.76 * scale).to_string()[..]),
+                    ("ry", &(0.412 * scale).to_string()[..]),
+                    ("transform", &format!("rotate(-21.5 {} {})", x, y)[..]),
+                ])
+                .write_empty()?;
+            Ok(())
+        })?;
+    Ok(())
+}
+
+/** Draw a world symbol centered at `center` and `scale` times its usual size: a filled circle for
+wet worlds, or else an empty one.
+*/
+pub(super) fn write_world_symbol<W: io::Write>(
+    writer: &mut quick_xml::Writer<W>,
+    center: Translation,
+    is_wet_world: bool,
+    scale: f64,
+    id: &str,
+) -> Result<(), MapRenderError> {
+    let fill = if is_wet_world { "#000000" } else { "none" };
+    writer
+        .create_element("circle")
+        .with_attributes(vec![
+            (
+                "style",
+                &format!("fill:{};{}", fill, SYMBOL_STROKE_STYLE)[..],
+            ),
+            ("cx", &center.x.to_string()[..]),
+            ("cy", &center.y.to_string()[..]),
+            ("r", &(1.27 * scale).to_string()[..]),
+            ("id", id),
+        ])
+        .write_empty()?;
+    Ok(())
+}
+
+/** Draw the outline marking a world of `subtype` around its world symbol centered at `center`, at
+`scale` times its usual size: a dashed ring for colonies, a triangle for outposts, and a square for
+research stations. Mainworlds aren't marked.
+*/
+pub(super) fn write_subtype_symbol<W: io::Write>(
+    writer: &mut quick_xml::Writer<W>,
+    center: Translation,
+    subtype: WorldSubtype,
+    scale: f64,
+    id: &str,
+) -> Result<(), MapRenderError> {
+    let style = format!("fill:none;{}", SYMBOL_STROKE_STYLE);
+    let (cx, cy) = (center.x.to_string(), center.y.to_string());
+    let points = |corners: &[(f64, f64)]| {
+        corners
+            .iter()
+            .map(|(x, y)| format!("{},{}", center.x + x * scale, center.y + y * scale))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    match subtype {
+        WorldSubtype::Mainworld => return Ok(()),
+        WorldSubtype::Colony => writer
+            .create_element("circle")
+            .with_attributes(vec![
+                ("style", &format!("{};stroke-dasharray:0.6,0.4", style)[..]),
+                ("cx", &cx[..]),
+                ("cy", &cy[..]),
+                ("r", &(2.2 * scale).to_string()[..]),
+                ("id", id),
+            ])
+            .write_empty()?,
+        WorldSubtype::Outpost => writer
+            .create_element("polygon")
+            .with_attributes(vec![
+                ("style", &style[..]),
+                (
+                    "points",
+                    &points(&[(0.0, -2.9), (2.51, 1.45), (-2.51, 1.45)])[..],
+                ),
+                ("id", id),
+            ])
+            .write_empty()?,
+        WorldSubtype::ResearchStation => writer
+            .create_element("polygon")
+            .with_attributes(vec![
+                ("style", &style[..]),
+                (
+                    "points",
+                    &points(&[(-1.7, -1.7), (1.7, -1.7), (1.7, 1.7), (-1.7, 1.7)])[..],
+                ),
+                ("id", id),
+            ])
+            .write_empty()?,
+    };
+    Ok(())
+}
+
+impl Subsector {
+    /** Generate an SVG image of the area `page` of the map's layout, or the whole layout if `None`,
+    without using a `MapTemplate`.
+
+    Every part of the map is drawn from scratch using only standard SVG elements and CSS, so it
+    doesn't rely on any editor-specific markup or on the ids of elements in a template. The grid is
+    laid out by a `HexGrid`, so unlike a template's it can have headers or pointy-topped hexes.
+    Subsectors without a name are drawn without a title.
+    */
+    pub(super) fn generate_standalone_svg_page(
+        &self,
+        options: &MapOptions,
+        route: &[Point],
+        page: Option<[f64; 4]>,
+    ) -> Result<String, MapRenderError> {
+        let grid = hex_grid(options);
+        let centers = grid.centers();
+        let transform = layout_transform(options.layout);
+        let (view_box, width, height) = match &transform {
+            Some(transform) => {
+                let [x, y, width, height] =
+                    page.unwrap_or([0.0, 0.0, transform.canvas[0], transform.canvas[1]]);
+                (
+                    format!("{} {} {} {}", x, y, width, height),
+                    transform.physical_length(width),
+                    transform.physical_length(height),
+                )
+            }
+            None => (
+                format!("0 0 {} {}", PAGE_WIDTH, PAGE_HEIGHT),
+                format!("{}mm", PAGE_WIDTH),
+                format!("{}mm", PAGE_HEIGHT),
+            ),
+        };
+
+        let mut writer = quick_xml::Writer::new_with_indent(io::Cursor::new(Vec::new()), b' ', 2);
+        writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+
+        let mut svg = BytesStart::new("svg");
+        svg.extend_attributes(vec![
+            ("xmlns", "http://www.w3.org/2000/svg"),
+            ("version", "1.1"),
+            ("viewBox", &view_box[..]),
+            ("width", &width[..]),
+            ("height", &height[..]),
+        ]);
+        writer.write_event(Event::Start(svg)).unwrap();
+
+        let map_title = format!("{} Subsector", self.name());
+        writer
+            .create_element("title")
+            .write_text_content(BytesText::new(&map_title))?;
+
+        let mut style = STYLE.to_string();
+        style.push_str(&options.style.css());
+        for color in PolityColor::ALL_VALUES {
+            let [r, g, b] = color.rgb();
+            style.push_str(&format!(
+                "    .{} {{ fill: #{:02x}{:02x}{:02x}; stroke: #000000; stroke-width: 0.254; }}\n",
+                color.class(),
+                r,
+                g,
+                b
+            ));
+        }
+        writer
+            .create_element("style")
+            .write_text_content(BytesText::new(&style))?;
+
+        if let Some(transform) = &transform {
+            let mut layout = BytesStart::new("g");
+            let svg_transform = transform.svg_transform();
+            layout.extend_attributes(vec![("id", "MapLayout"), ("transform", &svg_transform[..])]);
+            writer.write_event(Event::Start(layout)).unwrap();
+        }
+
+        writer
+            .create_element("rect")
+            .with_attributes(vec![
+                ("style", "fill:#ffffff"),
+                ("x", "0"),
+                ("y", "0"),
+                ("width", &PAGE_WIDTH.to_string()[..]),
+                ("height", &PAGE_HEIGHT.to_string()[..]),
+                ("id", "Page"),
+            ])
+            .write_empty()?;
+
+        if !self.name().is_empty() {
+            writer
+                .create_element("text")
+                .with_attributes(vec![
+                    ("class", "text-subsector-name"),
+                    ("x", &TITLE_POSITION.x.to_string()[..]),
+                    ("y", &TITLE_POSITION.y.to_string()[..]),
+                    ("id", "SubsectorName"),
+                ])
+                .write_text_content(BytesText::new(&map_title))?;
+        }
+
+        // Everything drawn in the hexes is shrunk along with them, but the legend stays put
+        let grid_transform = grid.fit_transform(options.grid_headers, GRID_AREA);
+        if let Some(grid_transform) = &grid_transform {
+            let mut grid_layout = BytesStart::new("g");
+            grid_layout.extend_attributes(vec![
+                ("id", "GridLayout"),
+                ("transform", &grid_transform[..]),
+            ]);
+            writer.write_event(Event::Start(grid_layout))?;
+        }
+
+        self.process_hexes_to_svg_elements(&mut writer, options, &grid, &centers)?;
+
+        writer.write_event(Event::Start(
+            BytesStart::new("g").with_attributes(vec![("id", "Generated")]),
+        ))?;
+        let layers = MapOptions {
+            legend: options.legend && grid_transform.is_none(),
+            ..options.clone()
+        };
+        self.process_layers_to_svg_elements(&mut writer, &layers, route, &centers, None)?;
+        writer.write_event(Event::End(BytesEnd::new("g"))).unwrap();
+
+        if grid_transform.is_some() {
+            // End of grid layout group
+            writer.write_event(Event::End(BytesEnd::new("g")))?;
+            if options.legend {
+                legend::process_legend_to_svg_elements(
+                    &mut writer,
+                    &self.map_legend(options, route),
+                    true,
+                )?;
+            }
+        }
+
+        if transform.is_some() {
+            // End of layout group
+            writer.write_event(Event::End(BytesEnd::new("g"))).unwrap();
+        }
+        writer.write_event(Event::End(BytesEnd::new("svg")))?;
+
+        String::from_utf8(writer.into_inner().into_inner())
+            .map_err(|e| MapRenderError::Xml(e.to_string()))
+    }
+
+    /** Draw the hex grid with the border around it, filling hexes with the color of their polity
+    and numbering them and the grid's columns and rows if `options` enables those layers.
+    */
+    fn process_hexes_to_svg_elements<W: io::Write>(
+        &self,
+        writer: &mut quick_xml::Writer<W>,
+        options: &MapOptions,
+        grid: &HexGrid,
+        centers: &BTreeMap<Point, Translation>,
+    ) -> Result<(), MapRenderError> {
+        let [width, height] = grid.size();
+        let border_x = grid.origin.x - GRID_BORDER_MARGIN;
+        let border_y = grid.origin.y - GRID_BORDER_MARGIN;
+        let border_width = width + 2.0 * GRID_BORDER_MARGIN;
+        let border_height = height + 2.0 * GRID_BORDER_MARGIN;
+
+        writer
+            .create_element("g")
+            .with_attribute(("id", "HexGrid"))
+            .write_inner_content(|writer| {
+                writer
+                    .create_element("rect")
+                    .with_attributes(vec![
+                        ("style", "fill:none;stroke:#000000;stroke-width:0.244"),
+                        ("x", &border_x.to_string()[..]),
+                        ("y", &border_y.to_string()[..]),
+                        ("width", &border_width.to_string()[..]),
+                        ("height", &border_height.to_string()[..]),
+                        ("id", "GridBorder"),
+                    ])
+                    .write_empty()?;
+
+                for (point, center) in centers {
+                    let polity_index = if options.polity_colors {
+                        self.polity_index(point)
+                    } else {
+                        None
+                    };
+                    let class = match polity_index {
+                        Some(index) => {
+                            PolityColor::ALL_VALUES[index % PolityColor::ALL_VALUES.len()].class()
+                        }
+                        None => "hex-blank".to_string(),
+                    };
+                    writer
+                        .create_element("polygon")
+                        .with_attributes(vec![
+                            ("class", &class[..]),
+                            ("points", &grid.hex_points(*center)[..]),
+                            ("id", &format!("HexPath-{}", point)[..]),
+                        ])
+                        .write_empty()?;
+
+                    if options.hex_numbers {
+                        writer
+                            .create_element("text")
+                            .with_attributes(vec![
+                                ("class", "text-hex-coord"),
+                                ("x", &center.x.to_string()[..]),
+                                ("y", &(center.y - HEX_NUMBER_OFFSET).to_string()[..]),
+                            ])
+                            .write_text_content(BytesText::new(&point.to_string()))?;
+                    }
+                }
+
+                if options.grid_headers {
+                    for (index, (header, position)) in grid.headers().into_iter().enumerate() {
+                        writer
+                            .create_element("text")
+                            .with_attributes(vec![
+                                ("class", "text-hex-coord"),
+                                ("x", &position.x.to_string()[..]),
+                                ("y", &position.y.to_string()[..]),
+                                ("id", &format!("GridHeader{}", index)[..]),
+                            ])
+                            .write_text_content(BytesText::new(&header))?;
+                    }
+                }
+                Ok(())
+            })?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::{HexOrientation, MapRenderer, CENTER_MARKERS, DEFAULT_MAP_TEMPLATE};
+
+    #[test]
+    fn hex_centers_match_template() {
+        // Close enough that worlds are placed the same as they are on the built-in template
+        for (point, center) in hex_grid(&MapOptions::default()).centers() {
+            let marker = CENTER_MARKERS[&point];
+            assert!(
+                (center.x - marker.x).abs() < 0.5,
+                "{} is out of place",
+                point
+            );
+            assert!(
+                (center.y - marker.y).abs() < 0.5,
+                "{} is out of place",
+                point
+            );
+        }
+    }
+
+    #[test]
+    fn standalone_svg() {
+        let subsector = Subsector::default();
+        let route: Vec<Point> = subsector.get_map().keys().take(3).cloned().collect();
+        let options = MapOptions {
+            legend: true,
+            renderer: MapRenderer::Standalone,
+            ..MapOptions::default()
+        };
+        let svg = subsector
+            .generate_svg(&DEFAULT_MAP_TEMPLATE, &options, &route)
+            .unwrap();
+
+        for markup in ["inkscape", "sodipodi", "href", "<use"] {
+            assert!(!svg.contains(markup), "{} should not be in the map", markup);
+        }
+        assert!(svg.contains("xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains("width=\"215.9mm\""));
+        assert_eq!(
+            svg.matches("<polygon").count(),
+            Subsector::COLUMNS * Subsector::ROWS
+        );
+        assert_eq!(
+            svg.matches("WorldProfileText").count(),
+            subsector.get_map().len()
+        );
+        assert!(svg.contains(&format!(">{} Subsector</text>", subsector.name())));
+        // Symbols the built-in template's own legend would explain are in the legend as well
+        assert!(svg.contains(">Starport-TL</text>"));
+        assert_eq!(svg.matches("<g").count(), svg.matches("</g>").count());
+        assert!(!svg.contains("GridLayout"));
+
+        // Even when asked to use the template, only a grid drawn from scratch can be turned
+        let turned = MapOptions {
+            grid_headers: true,
+            hex_orientation: HexOrientation::PointyTop,
+            renderer: MapRenderer::Template,
+            ..options.clone()
+        };
+        let svg = subsector
+            .generate_svg(&DEFAULT_MAP_TEMPLATE, &turned, &route)
+            .unwrap();
+        assert!(!svg.contains("inkscape"));
+        assert!(svg.contains("id=\"GridLayout\""));
+        assert_eq!(
+            svg.matches("GridHeader").count(),
+            Subsector::COLUMNS + Subsector::ROWS
+        );
+        assert!(svg.contains(">08</text>"));
+        assert!(svg.contains(">Starport-TL</text>"));
+        assert_eq!(svg.matches("<g").count(), svg.matches("</g>").count());
+
+        let poster = MapOptions {
+            layout: MapLayout::Poster,
+            ..options
+        };
+        let pages = subsector
+            .generate_svg_pages(&DEFAULT_MAP_TEMPLATE, &poster, &route)
+            .unwrap();
+        assert_eq!(pages.len(), 4);
+        assert!(pages[3].contains("viewBox=\"215.9 279.4 215.9 279.4\""));
+        assert!(pages.iter().all(|page| page.contains("width=\"215.9mm\"")));
+    }
+}
@@ -0,0 +1,344 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::{StarportClass, TradeCode, World};
+use crate::dice;
+
+/** Largest kind of ship a starport's shipyard can build. */
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Shipyard {
+    None,
+    SmallCraft,
+    Starships,
+    CapitalShips,
+}
+
+impl Shipyard {
+    pub const ALL_VALUES: [Shipyard; 4] = [
+        Self::None,
+        Self::SmallCraft,
+        Self::Starships,
+        Self::CapitalShips,
+    ];
+}
+
+impl fmt::Display for Shipyard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::None => "None",
+            Self::SmallCraft => "Small Craft",
+            Self::Starships => "Starships",
+            Self::CapitalShips => "Capital Ships",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** Extent of the repairs a starport can make to visiting ships. */
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Repairs {
+    None,
+    Limited,
+    Major,
+    Full,
+}
+
+impl Repairs {
+    pub const ALL_VALUES: [Repairs; 4] = [Self::None, Self::Limited, Self::Major, Self::Full];
+}
+
+impl fmt::Display for Repairs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::None => "None",
+            Self::Limited => "Limited",
+            Self::Major => "Major",
+            Self::Full => "Full",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** Facilities of a particular starport beyond what every starport of its class has.
+
+Shipyards and repairs follow from the class of the starport, while the highport is rolled for as
+per the Mongoose rules.
+*/
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StarportDetails {
+    /// Whether the starport has an orbital highport in addition to its downport
+    pub highport: bool,
+    pub shipyard: Shipyard,
+    pub repairs: Repairs,
+}
+
+impl StarportDetails {
+    /** Describe which parts of the starport are on the ground and which are in orbit. */
+    pub fn layout_str(&self, class: &StarportClass) -> String {
+        match (class, self.highport) {
+            (StarportClass::X, _) => "None".to_string(),
+            (StarportClass::E, false) => "Landing area only".to_string(),
+            (_, false) => "Downport only".to_string(),
+            (_, true) => "Downport and highport".to_string(),
+        }
+    }
+}
+
+impl fmt::Display for StarportDetails {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut details = Vec::new();
+        if self.highport {
+            details.push("highport".to_string());
+        }
+        if self.shipyard != Shipyard::None {
+            details.push(format!("{} shipyard", self.shipyard).to_lowercase());
+        }
+        if self.repairs != Repairs::None {
+            details.push(format!("{} repairs", self.repairs).to_lowercase());
+        }
+
+        if details.is_empty() {
+            write!(f, "no facilities")
+        } else {
+            write!(f, "{}", details.join(", "))
+        }
+    }
+}
+
+/** Size range of the ships arriving at a starport. */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TonnageClass {
+    /// Under 100 tons: shuttles, launches, fighters, and other craft without jump drives
+    SmallCraft,
+    /// 100 to 999 tons: free traders, scouts, yachts, and other small starships
+    Light,
+    /// 1,000 to 4,999 tons: liners, bulk freighters, and warships up to cruisers
+    Medium,
+    /// 5,000 tons and up: capital ships and the largest merchant hulls
+    Heavy,
+}
+
+impl TonnageClass {
+    pub const ALL_VALUES: [TonnageClass; 4] =
+        [Self::SmallCraft, Self::Light, Self::Medium, Self::Heavy];
+}
+
+impl fmt::Display for TonnageClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::SmallCraft => "Small Craft (<100 t)",
+            Self::Light => "Light (100-999 t)",
+            Self::Medium => "Medium (1,000-4,999 t)",
+            Self::Heavy => "Heavy (5,000+ t)",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** Estimated number of ships arriving at a starport in a typical week. */
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct WeeklyTraffic {
+    /// Arrivals of each `TonnageClass`, in the order of `TonnageClass::ALL_VALUES`
+    pub arrivals: [u32; 4],
+}
+
+impl WeeklyTraffic {
+    /** Number of arrivals of ships of `class`. */
+    pub fn arrivals_of(&self, class: TonnageClass) -> u32 {
+        self.arrivals[class as usize]
+    }
+
+    /** Average number of hours between arrivals, or `None` if nothing arrives in a week.
+
+    Useful for pacing encounters with other ships around the starport.
+    */
+    pub fn hours_between_arrivals(&self) -> Option<f64> {
+        match self.total() {
+            0 => None,
+            total => Some(168.0 / total as f64),
+        }
+    }
+
+    /** Total number of arrivals of ships of every class. */
+    pub fn total(&self) -> u32 {
+        self.arrivals.iter().sum()
+    }
+}
+
+impl fmt::Display for WeeklyTraffic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.total() == 0 {
+            return write!(f, "no regular traffic");
+        }
+
+        let classes: Vec<String> = TonnageClass::ALL_VALUES
+            .iter()
+            .filter(|class| self.arrivals_of(**class) > 0)
+            .map(|class| format!("{} {}", self.arrivals_of(*class), class))
+            .collect();
+        write!(f, "{} ships/week: {}", self.total(), classes.join(", "))
+    }
+}
+
+impl World {
+    /** Generate the details of the `World`'s starport from its class, population, and tech level.
+
+    A highport is found on a roll of 6+ for class A, 8+ for B, 10+ for C, and 12+ for D starports,
+    with +1 for tech level 9-11, +2 for tech level 12+, +1 for population 9+, and -1 for
+    population 6-.
+    */
+    pub fn generate_starport_details(&mut self) {
+        let (target, shipyard, repairs) = match self.starport.class {
+            StarportClass::A => (Some(6), Shipyard::CapitalShips, Repairs::Full),
+            StarportClass::B => (Some(8), Shipyard::Starships, Repairs::Major),
+            StarportClass::C => (Some(10), Shipyard::SmallCraft, Repairs::Major),
+            StarportClass::D => (Some(12), Shipyard::None, Repairs::Limited),
+            StarportClass::E | StarportClass::X => (None, Shipyard::None, Repairs::None),
+        };
+
+        let highport = match target {
+            Some(target) => dice::roll_2d(6) + self.highport_dm() >= target,
+            None => false,
+        };
+
+        self.starport.details = Some(StarportDetails {
+            highport,
+            shipyard,
+            repairs,
+        });
+    }
+
+    fn highport_dm(&self) -> i32 {
+        let tech_level_dm = match self.tech_level.code {
+            9..=11 => 1,
+            12.. => 2,
+            _ => 0,
+        };
+        let population_dm = match self.population.code {
+            9.. => 1,
+            0..=6 => -1,
+            _ => 0,
+        };
+        tech_level_dm + population_dm
+    }
+
+    /** Estimate the ships arriving at the `World`'s starport each week.
+
+    The total follows from the starport class, scaled by population and by trade codes that draw
+    or drive away merchants. Better starports see a larger share of bigger ships, while small craft
+    make up most of the traffic at frontier ports.
+    */
+    pub fn weekly_traffic(&self) -> WeeklyTraffic {
+        // Base arrivals per week and the percentage of them in each tonnage class
+        let (base, shares): (f64, [u32; 4]) = match self.starport.class {
+            StarportClass::A => (40.0, [40, 35, 18, 7]),
+            StarportClass::B => (20.0, [45, 40, 12, 3]),
+            StarportClass::C => (8.0, [50, 45, 5, 0]),
+            StarportClass::D => (3.0, [60, 40, 0, 0]),
+            StarportClass::E => (1.0, [80, 20, 0, 0]),
+            StarportClass::X => return WeeklyTraffic::default(),
+        };
+
+        let population_multiplier = match self.population.code {
+            0 => 0.1,
+            1..=3 => 0.5,
+            4..=6 => 1.0,
+            7..=8 => 1.5,
+            _ => 2.0,
+        };
+        let trade_multiplier: f64 = self
+            .trade_codes
+            .iter()
+            .map(|trade_code| match trade_code {
+                TradeCode::In | TradeCode::Ri => 1.25,
+                TradeCode::Ag | TradeCode::Ht => 1.1,
+                TradeCode::Ba | TradeCode::Po => 0.75,
+                _ => 1.0,
+            })
+            .product();
+
+        let total = base * population_multiplier * trade_multiplier;
+        let mut arrivals = [0; 4];
+        for (arrivals, share) in arrivals.iter_mut().zip(shares) {
+            *arrivals = (total * share as f64 / 100.0).round() as u32;
+        }
+        WeeklyTraffic { arrivals }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::TABLES;
+
+    #[test]
+    fn generate_starport_details() {
+        for starport in TABLES.starport_table.iter() {
+            let mut world = World::empty();
+            world.starport = starport.clone();
+            world.generate_starport_details();
+
+            let details = world.starport.details.clone().unwrap();
+            match starport.class {
+                StarportClass::A => {
+                    assert_eq!(details.shipyard, Shipyard::CapitalShips);
+                    assert_eq!(details.repairs, Repairs::Full);
+                }
+                StarportClass::B => assert_eq!(details.repairs, Repairs::Major),
+                StarportClass::C => {
+                    assert_eq!(details.shipyard, Shipyard::SmallCraft);
+                    assert_eq!(details.repairs, Repairs::Major);
+                }
+                StarportClass::D => assert_eq!(details.repairs, Repairs::Limited),
+                StarportClass::E | StarportClass::X => {
+                    assert!(!details.highport);
+                    assert_eq!(details.shipyard, Shipyard::None);
+                    assert_eq!(details.repairs, Repairs::None);
+                }
+            }
+        }
+
+        let details = StarportDetails {
+            highport: true,
+            shipyard: Shipyard::Starships,
+            repairs: Repairs::Full,
+        };
+        assert_eq!(
+            details.to_string(),
+            "highport, starships shipyard, full repairs"
+        );
+        assert_eq!(
+            details.layout_str(&StarportClass::B),
+            "Downport and highport"
+        );
+    }
+
+    #[test]
+    fn weekly_traffic() {
+        let mut world = World::empty();
+        world.starport = TABLES.starport_table[12].clone();
+        world.population = TABLES.pop_table[8].clone();
+        world.trade_codes.clear();
+
+        let traffic = world.weekly_traffic();
+        assert_eq!(traffic.arrivals, [24, 21, 11, 4]);
+        assert_eq!(traffic.arrivals_of(TonnageClass::Medium), 11);
+        assert_eq!(traffic.total(), 60);
+        assert_eq!(traffic.hours_between_arrivals(), Some(2.8));
+        assert_eq!(
+            traffic.to_string(),
+            "60 ships/week: 24 Small Craft (<100 t), 21 Light (100-999 t), \
+             11 Medium (1,000-4,999 t), 4 Heavy (5,000+ t)"
+        );
+
+        world.trade_codes.insert(TradeCode::Ri);
+        assert_eq!(world.weekly_traffic().total(), 75);
+
+        world.starport = TABLES.starport_table[0].clone();
+        let traffic = world.weekly_traffic();
+        assert_eq!(traffic.total(), 0);
+        assert_eq!(traffic.hours_between_arrivals(), None);
+        assert_eq!(traffic.to_string(), "no regular traffic");
+    }
+}
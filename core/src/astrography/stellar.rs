@@ -0,0 +1,78 @@
+use crate::astrography::World;
+use crate::dice;
+
+/// Spectral types from hottest to coolest
+const SPECTRAL_TYPES: [char; 6] = ['B', 'A', 'F', 'G', 'K', 'M'];
+/// Most companion stars a system can have
+const MAX_COMPANIONS: usize = 2;
+
+impl World {
+    /** Roll the T5 stellar data of the `World`'s system, e.g. "G2 V M5 V".
+
+    This is a simplified version of the T5 rules: the primary star's spectral type and size are
+    rolled with flux, and each companion is cooler than the primary or is a white dwarf.
+    */
+    pub fn generate_stellar(&mut self) {
+        let primary_type = match dice::flux() {
+            -6 => 0,
+            -5..=-4 => 1,
+            -3..=-2 => 2,
+            -1..=0 => 3,
+            1..=2 => 4,
+            _ => 5,
+        };
+        let primary_size = match dice::flux() {
+            -6..=-5 => "III",
+            -4 => "IV",
+            _ => "V",
+        };
+        let mut stars = vec![format!(
+            "{}{} {}",
+            SPECTRAL_TYPES[primary_type],
+            dice::roll_range(0..=9),
+            primary_size
+        )];
+
+        for _ in 0..MAX_COMPANIONS {
+            if dice::flux() < 3 {
+                continue;
+            }
+            let roll = dice::roll_1d(6);
+            if roll == 6 {
+                stars.push("D".to_string());
+            } else {
+                let companion_type =
+                    (primary_type + roll as usize - 1).min(SPECTRAL_TYPES.len() - 1);
+                stars.push(format!(
+                    "{}{} V",
+                    SPECTRAL_TYPES[companion_type],
+                    dice::roll_range(0..=9)
+                ));
+            }
+        }
+
+        self.stellar = stars.join(" ");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_stellar() {
+        const ATTEMPTS: usize = 200;
+        let mut world = World::empty();
+        for _ in 0..ATTEMPTS {
+            world.generate_stellar();
+            let stars: Vec<&str> = world.stellar.split(' ').collect();
+            assert!(
+                (2..=2 + 2 * MAX_COMPANIONS).contains(&stars.len()),
+                "'{}' has the wrong number of parts",
+                world.stellar
+            );
+            assert!(SPECTRAL_TYPES.contains(&stars[0].chars().next().unwrap()));
+            assert!(["III", "IV", "V"].contains(&stars[1]));
+        }
+    }
+}
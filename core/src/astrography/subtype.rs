@@ -0,0 +1,72 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/** Role a `World` plays in its system, which limits how much of it is generated. */
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum WorldSubtype {
+    /// The main world of its system, generated without limits
+    #[default]
+    Mainworld,
+    /// A settlement founded from another world that hasn't grown past a modest population
+    Colony,
+    /// A small staffed installation without a culture or factions of its own
+    Outpost,
+    /// An outpost given over to research, which always has a research base
+    ResearchStation,
+}
+
+impl WorldSubtype {
+    pub const ALL_VALUES: [WorldSubtype; 4] = [
+        Self::Mainworld,
+        Self::Colony,
+        Self::Outpost,
+        Self::ResearchStation,
+    ];
+
+    /** Short code of the subtype used in exported tables, e.g. "Out". */
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Mainworld => "Main",
+            Self::Colony => "Col",
+            Self::Outpost => "Out",
+            Self::ResearchStation => "Res",
+        }
+    }
+
+    /** Whether the subtype always has a research base. */
+    pub fn has_research_base(&self) -> bool {
+        *self == Self::ResearchStation
+    }
+
+    /** Whether worlds of the subtype have a culture and factions of their own. */
+    pub fn has_society(&self) -> bool {
+        matches!(self, Self::Mainworld | Self::Colony)
+    }
+
+    pub fn is_mainworld(&self) -> bool {
+        *self == Self::Mainworld
+    }
+
+    /** Highest population code worlds of the subtype are generated with. */
+    pub fn max_population(&self) -> u16 {
+        match self {
+            Self::Mainworld => u16::MAX,
+            Self::Colony => 6,
+            Self::Outpost => 4,
+            Self::ResearchStation => 3,
+        }
+    }
+}
+
+impl fmt::Display for WorldSubtype {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Mainworld => "Mainworld",
+            Self::Colony => "Colony",
+            Self::Outpost => "Outpost",
+            Self::ResearchStation => "Research Station",
+        };
+        write!(f, "{}", s)
+    }
+}
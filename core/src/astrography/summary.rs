@@ -0,0 +1,278 @@
+use std::fmt;
+
+use crate::astrography::{LawCategory, NumberFormat, Point, World};
+
+/** Format of a `World` summary meant to be pasted into chat or notes. */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SummaryFormat {
+    /// Single line with the hex, name, UWP, bases, trade codes, and travel zone
+    UwpLine,
+    /// One labeled field per line
+    PlainText,
+    /// Heading followed by a table of fields
+    Markdown,
+}
+
+impl SummaryFormat {
+    pub const ALL_VALUES: [SummaryFormat; 3] = [Self::UwpLine, Self::PlainText, Self::Markdown];
+}
+
+impl fmt::Display for SummaryFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::UwpLine => "UWP Line",
+            Self::PlainText => "Plain Text Summary",
+            Self::Markdown => "Markdown Summary",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl World {
+    /** Summarize the `World` at `point` in the given `format`, writing numbers with `numbers`. */
+    pub fn summary(&self, point: &Point, format: SummaryFormat, numbers: &NumberFormat) -> String {
+        match format {
+            SummaryFormat::UwpLine => format!(
+                "{} {} {} {} {} {}",
+                point,
+                self.name,
+                self.profile_str(),
+                self.base_str(),
+                self.trade_code_str(),
+                self.travel_code.as_short_string()
+            ),
+
+            SummaryFormat::PlainText => {
+                let mut lines = vec![format!("{} ({})", self.name, point)];
+                lines.extend(
+                    self.summary_fields(numbers)
+                        .into_iter()
+                        .map(|(field, value)| format!("{}: {}", field, value)),
+                );
+                lines.extend(self.summary_notes(false));
+                lines.join("\n")
+            }
+
+            SummaryFormat::Markdown => {
+                let mut lines = vec![
+                    format!("### {} ({})", self.name, point),
+                    String::new(),
+                    "| Field | Value |".to_string(),
+                    "| --- | --- |".to_string(),
+                ];
+                lines.extend(
+                    self.summary_fields(numbers)
+                        .into_iter()
+                        .map(|(field, value)| {
+                            format!("| {} | {} |", field, value.replace('|', "\\|"))
+                        }),
+                );
+                lines.extend(self.summary_notes(true));
+                lines.join("\n")
+            }
+        }
+    }
+
+    /** Get the lines of the player and secret notes that end the plain text or markdown
+    summaries, each set apart by a blank line and the secret notes under their own heading.
+    */
+    fn summary_notes(&self, markdown: bool) -> Vec<String> {
+        let mut lines = Vec::new();
+        if !self.player_notes.is_empty() {
+            lines.push(String::new());
+            lines.push(self.player_notes.clone());
+        }
+        if !self.secret_notes.is_empty() {
+            lines.push(String::new());
+            if markdown {
+                lines.push("#### Secret Notes".to_string());
+                lines.push(String::new());
+            } else {
+                lines.push("Secret Notes:".to_string());
+            }
+            lines.push(self.secret_notes.clone());
+        }
+        lines
+    }
+
+    /** Get the labeled fields shared by the plain text and markdown summaries. */
+    fn summary_fields(&self, numbers: &NumberFormat) -> Vec<(&'static str, String)> {
+        let list_or_none = |items: Vec<String>| {
+            if items.is_empty() {
+                "None".to_string()
+            } else {
+                items.join(", ")
+            }
+        };
+        let text_or_none = |text: &String| {
+            if text.is_empty() {
+                "None".to_string()
+            } else {
+                text.clone()
+            }
+        };
+
+        let trade_codes = self.trade_code_long_str();
+        let mut starport = format!(
+            "{:?}, berthing Cr{}",
+            self.starport.class,
+            numbers.count(self.starport.berthing_cost as u64)
+        );
+        let population = match self.population_count() {
+            0 => self.population.inhabitants.clone(),
+            count => format!(
+                "{} (~{})",
+                self.population.inhabitants,
+                numbers.count(count)
+            ),
+        };
+        if let Some(details) = &self.starport.details {
+            starport += &format!(", {}", details);
+        }
+        vec![
+            ("UWP", self.profile_str()),
+            (
+                "Trade Codes",
+                if trade_codes.is_empty() {
+                    "None".to_string()
+                } else {
+                    trade_codes
+                },
+            ),
+            ("Bases", self.base_str()),
+            ("Travel Code", self.travel_code_str()),
+            ("Travel Advisory", text_or_none(&self.travel_advisory)),
+            ("Starport", starport),
+            ("Traffic", self.weekly_traffic().to_string()),
+            (
+                "Fuel",
+                self.fuel_market
+                    .map_or("None".to_string(), |market| market.to_string()),
+            ),
+            (
+                "Orbital Facilities",
+                list_or_none(
+                    self.orbital_facilities
+                        .iter()
+                        .map(|facility| facility.to_string())
+                        .collect(),
+                ),
+            ),
+            (
+                "Size",
+                format!("{} ({})", self.size, numbers.length(self.diameter)),
+            ),
+            (
+                "Planetoid Belts",
+                self.planetoid_belts.unwrap_or(0).to_string(),
+            ),
+            ("Gas Giants", self.gas_giants.to_string()),
+            ("Atmosphere", self.atmosphere.composition.clone()),
+            ("Temperature", self.temperature.kind.clone()),
+            ("Hydrographics", self.hydrographics.description.clone()),
+            ("Population", population),
+            ("Government", self.government.kind.clone()),
+            ("Law Level", self.law_level.code.to_string()),
+            (
+                "Law Categories",
+                LawCategory::ALL_VALUES
+                    .iter()
+                    .map(|category| format!("{} {}", category, self.law_category_level(*category)))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+            ("Tech Level", self.tech_level.code.to_string()),
+            ("Culture", self.culture.cultural_difference.clone()),
+            ("Dress", text_or_none(&self.cultural_details.dress)),
+            ("Taboos", text_or_none(&self.cultural_details.taboos)),
+            ("Greetings", text_or_none(&self.cultural_details.greetings)),
+            ("Cuisine", text_or_none(&self.cultural_details.cuisine)),
+            ("Religion", text_or_none(&self.religion.to_string())),
+            (
+                "World Tags",
+                list_or_none(self.world_tags.iter().map(|tag| tag.tag.clone()).collect()),
+            ),
+            (
+                "Factions",
+                list_or_none(
+                    self.factions
+                        .iter()
+                        .map(|faction| faction.name.clone())
+                        .collect(),
+                ),
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::{
+        CountStyle, Facility, FacilityKind, FacilitySize, LengthUnit, TABLES,
+    };
+
+    #[test]
+    fn world_summaries() {
+        let mut world = World::empty();
+        world.name = "Regina".to_string();
+        world.population = TABLES.pop_table[8].clone();
+        world.has_naval_base = true;
+        world.planetoid_belts = Some(1);
+        world.gas_giants = 2;
+        world.player_notes = "Capital of the | subsector".to_string();
+        let point = Point { x: 1, y: 10 };
+        let mut numbers = NumberFormat::default();
+
+        let line = world.summary(&point, SummaryFormat::UwpLine, &numbers);
+        assert_eq!(
+            line,
+            format!(
+                "0110 Regina {} N {} -",
+                world.profile_str(),
+                world.trade_code_str()
+            )
+        );
+
+        let text = world.summary(&point, SummaryFormat::PlainText, &numbers);
+        assert!(text.starts_with("Regina (0110)\nUWP: "));
+        assert!(text.contains("\nBases: N\n"));
+        assert!(text.contains(&format!("\nTraffic: {}\n", world.weekly_traffic())));
+        assert!(text.contains(&format!("({} km)", world.diameter)));
+        assert!(text.contains("(~100000000)\n"));
+        assert!(text
+            .contains("\nLaw Categories: Weapons 0, Armor 0, Drugs 0, Technology 0, Psionics 0\n"));
+        assert!(text.ends_with("\n\nCapital of the | subsector"));
+
+        let markdown = world.summary(&point, SummaryFormat::Markdown, &numbers);
+        assert!(markdown.starts_with("### Regina (0110)\n\n| Field | Value |\n| --- | --- |\n"));
+        assert!(markdown.contains("| Bases | N |"));
+        assert!(markdown.contains("| Planetoid Belts | 1 |\n| Gas Giants | 2 |"));
+        for (label, _) in world.summary_fields(&numbers) {
+            let row = format!("\n| {} |", label);
+            assert_eq!(markdown.matches(&row).count(), 1, "{}", label);
+        }
+        assert!(markdown.contains("| Factions | None |"));
+        assert!(markdown.contains("| Orbital Facilities | None |"));
+        assert!(markdown.contains("| Fuel | None |"));
+        assert!(markdown.contains("| Cuisine | None |"));
+        assert!(markdown.contains("| Religion | None |"));
+        assert!(markdown.contains("| Travel Code | Safe |\n| Travel Advisory | None |"));
+        assert!(markdown.ends_with("|\n\nCapital of the | subsector"));
+
+        world.orbital_facilities = vec![Facility::new(FacilityKind::Highport, FacilitySize::Large)];
+        let text = world.summary(&point, SummaryFormat::PlainText, &numbers);
+        assert!(text.contains("\nOrbital Facilities: Large highport\n"));
+
+        world.secret_notes = "Seat of the sector duke".to_string();
+        let markdown = world.summary(&point, SummaryFormat::Markdown, &numbers);
+        assert!(markdown.ends_with("subsector\n\n#### Secret Notes\n\nSeat of the sector duke"));
+
+        numbers.count_style = CountStyle::Abbreviated;
+        numbers.length_unit = LengthUnit::Miles;
+        let text = world.summary(&point, SummaryFormat::PlainText, &numbers);
+        assert!(text.contains(&format!("({})", numbers.length(world.diameter))));
+        assert!(text.contains(" mi)"));
+        assert!(text.contains("(~100M)\n"));
+    }
+}
@@ -0,0 +1,415 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    f64::consts::{PI, TAU},
+    hash::{Hash, Hasher},
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::astrography::World;
+
+/// Width and height of the square view box a `World` thumbnail is drawn in
+const THUMBNAIL_VIEW_BOX_SIZE: f64 = 100.0;
+/// Center of a thumbnail in both directions
+const CENTER: f64 = THUMBNAIL_VIEW_BOX_SIZE / 2.0;
+/// Width and height in pixels of a thumbnail SVG
+const THUMBNAIL_PIXELS: u32 = 160;
+/// Constant for approximating a quarter of an ellipse with a cubic Bézier curve
+const BEZIER_KAPPA: f64 = 0.552_284_75;
+
+type Rgb = [u8; 3];
+
+const ROCK_COLOR: Rgb = [0x8a, 0x84, 0x7c];
+const ICE_COLOR: Rgb = [0xf2, 0xf6, 0xfa];
+
+/** Filled ellipse drawn as part of a thumbnail. */
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Blob {
+    cx: f64,
+    cy: f64,
+    rx: f64,
+    ry: f64,
+    color: Rgb,
+}
+
+/** Procedural picture of the surface of a `World`, in the coordinates of a view box
+`THUMBNAIL_VIEW_BOX_SIZE` wide with the y axis pointing down.
+*/
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct Thumbnail {
+    /// Radius of the world's disc; `0.0` for a planetoid belt, which is only a scattering of rocks
+    radius: f64,
+    /// Color of the whole disc before anything is drawn on it
+    base: Rgb,
+    /// Continents, islands, and ice caps, cut off at the edge of the disc
+    blobs: Vec<Blob>,
+    /// Color and width of the atmosphere's glow around the disc, if there is one
+    halo: Option<(Rgb, f64)>,
+}
+
+/** Broad temperature band of a `World` by its temperature code. */
+#[derive(Clone, Copy, PartialEq)]
+enum Climate {
+    Frozen,
+    Cold,
+    Temperate,
+    Hot,
+    Boiling,
+}
+
+impl Climate {
+    fn new(code: u16) -> Self {
+        match code {
+            0..=2 => Self::Frozen,
+            3..=4 => Self::Cold,
+            5..=9 => Self::Temperate,
+            10..=11 => Self::Hot,
+            _ => Self::Boiling,
+        }
+    }
+
+    fn land_color(&self) -> Rgb {
+        match self {
+            Self::Frozen => [0xc8, 0xd0, 0xd8],
+            Self::Cold => [0x7d, 0x8c, 0x6e],
+            Self::Temperate => [0x4c, 0x86, 0x3a],
+            Self::Hot => [0xb3, 0x8b, 0x4f],
+            Self::Boiling => [0x8c, 0x45, 0x22],
+        }
+    }
+
+    fn water_color(&self) -> Rgb {
+        match self {
+            Self::Frozen | Self::Cold => [0x3b, 0x6e, 0x9e],
+            Self::Temperate => [0x27, 0x5d, 0xa8],
+            Self::Hot | Self::Boiling => [0x2f, 0x6f, 0x8f],
+        }
+    }
+
+    /** Height of each ice cap as a fraction of the world's radius. */
+    fn ice_cap_fraction(&self) -> f64 {
+        match self {
+            Self::Frozen => 0.45,
+            Self::Cold => 0.28,
+            Self::Temperate => 0.14,
+            Self::Hot => 0.05,
+            Self::Boiling => 0.0,
+        }
+    }
+}
+
+impl World {
+    /** Draw a simple procedural picture of the `World`'s surface as a square SVG image.
+
+    Ice caps, oceans, continents, and the glow of the atmosphere follow from the `World`'s size,
+    hydrographics, atmosphere, and temperature. The same `World` always gets the same picture.
+    */
+    pub fn thumbnail_svg(&self) -> String {
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{pixels}\" height=\"{pixels}\" \
+             viewBox=\"0 0 {size} {size}\">\n{}</svg>\n",
+            self.thumbnail().svg_elements(),
+            pixels = THUMBNAIL_PIXELS,
+            size = THUMBNAIL_VIEW_BOX_SIZE,
+        )
+    }
+
+    pub(super) fn thumbnail(&self) -> Thumbnail {
+        let mut rng = StdRng::seed_from_u64(self.thumbnail_seed());
+
+        if self.size == 0 {
+            // A ring of rocks around where the world would be
+            let blobs = (0..16)
+                .map(|_| {
+                    let angle = rng.gen_range(0.0..TAU);
+                    let distance = rng.gen_range(26.0..44.0);
+                    let radius = rng.gen_range(1.5..4.5);
+                    Blob {
+                        cx: CENTER + distance * angle.cos(),
+                        cy: CENTER + distance * angle.sin(),
+                        rx: radius,
+                        ry: radius * rng.gen_range(0.6..1.0),
+                        color: ROCK_COLOR,
+                    }
+                })
+                .collect();
+            return Thumbnail {
+                radius: 0.0,
+                base: ROCK_COLOR,
+                blobs,
+                halo: None,
+            };
+        }
+
+        let radius = 22.0 + 2.6 * self.size.min(10) as f64;
+        let climate = Climate::new(self.temperature.code);
+        let hydrographics = self.hydrographics.code.min(10);
+        let has_water = hydrographics > 0;
+
+        let mut blobs = Vec::new();
+        if has_water {
+            // Even water worlds have a few islands
+            let land_fraction = ((10 - hydrographics) as f64 / 10.0).max(0.02);
+            let land_area = land_fraction * PI * radius * radius;
+            let continents = rng.gen_range(2..=4);
+            const LOBES: usize = 4;
+            // Lobes overlap, so each is drawn bigger than its share of the area
+            let lobe_area = 1.6 * land_area / (continents * LOBES) as f64;
+
+            for _ in 0..continents {
+                let angle = rng.gen_range(0.0..TAU);
+                let distance = rng.gen_range(0.0..0.75) * radius;
+                let (cx, cy) = (
+                    CENTER + distance * angle.cos(),
+                    CENTER + distance * angle.sin(),
+                );
+                for _ in 0..LOBES {
+                    let rx = (lobe_area / PI).sqrt() * rng.gen_range(0.8..1.3);
+                    let ry = lobe_area / (PI * rx);
+                    blobs.push(Blob {
+                        cx: cx + rng.gen_range(-1.0..1.0) * rx,
+                        cy: cy + rng.gen_range(-1.0..1.0) * ry,
+                        rx,
+                        ry,
+                        color: climate.land_color(),
+                    });
+                }
+            }
+
+            let cap = climate.ice_cap_fraction() * radius;
+            if cap > 0.0 {
+                for pole in [-1.0, 1.0] {
+                    blobs.push(Blob {
+                        cx: CENTER + rng.gen_range(-0.1..0.1) * radius,
+                        cy: CENTER + pole * radius,
+                        rx: radius * rng.gen_range(0.8..1.0),
+                        ry: cap * rng.gen_range(0.8..1.2),
+                        color: ICE_COLOR,
+                    });
+                }
+            }
+        }
+
+        let halo = match self.atmosphere.code {
+            0 => None,
+            1 => Some(([0xb0, 0xb0, 0xb0], 1.0)),
+            code @ 2..=9 => Some(([0x8e, 0xc3, 0xff], 1.0 + 0.3 * code as f64)),
+            _ => Some(([0xc9, 0xd8, 0x6b], 3.0)),
+        };
+
+        Thumbnail {
+            radius,
+            base: if has_water {
+                climate.water_color()
+            } else {
+                climate.land_color()
+            },
+            blobs,
+            halo,
+        }
+    }
+
+    /** Seed for the thumbnail's random shapes, which only changes with the data it is drawn from. */
+    fn thumbnail_seed(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.name.hash(&mut hasher);
+        self.size.hash(&mut hasher);
+        self.atmosphere.code.hash(&mut hasher);
+        self.hydrographics.code.hash(&mut hasher);
+        self.temperature.code.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+impl Thumbnail {
+    /** Get the SVG elements that draw the thumbnail, to be placed in a view box
+    `THUMBNAIL_VIEW_BOX_SIZE` wide.
+    */
+    pub(super) fn svg_elements(&self) -> String {
+        let blobs: String = self
+            .blobs
+            .iter()
+            .map(|blob| {
+                format!(
+                    "<ellipse cx=\"{:.2}\" cy=\"{:.2}\" rx=\"{:.2}\" ry=\"{:.2}\" fill=\"{}\"/>\n",
+                    blob.cx,
+                    blob.cy,
+                    blob.rx,
+                    blob.ry,
+                    svg_color(blob.color)
+                )
+            })
+            .collect();
+        if self.radius == 0.0 {
+            return blobs;
+        }
+
+        let mut svg = format!(
+            "<defs>\n<clipPath id=\"thumbnail-disc\"><circle cx=\"{center}\" cy=\"{center}\" \
+             r=\"{radius:.2}\"/></clipPath>\n\
+             <radialGradient id=\"thumbnail-shade\" cx=\"0.35\" cy=\"0.35\" r=\"0.75\">\
+             <stop offset=\"0.5\" stop-color=\"black\" stop-opacity=\"0\"/>\
+             <stop offset=\"1\" stop-color=\"black\" stop-opacity=\"0.5\"/></radialGradient>\n\
+             </defs>\n",
+            center = CENTER,
+            radius = self.radius,
+        );
+        if let Some((color, width)) = self.halo {
+            svg += &format!(
+                "<circle cx=\"{center}\" cy=\"{center}\" r=\"{:.2}\" fill=\"none\" stroke=\"{}\" \
+                 stroke-width=\"{:.2}\" stroke-opacity=\"0.7\"/>\n",
+                self.radius + width / 2.0,
+                svg_color(color),
+                width,
+                center = CENTER,
+            );
+        }
+        svg += &format!(
+            "<g clip-path=\"url(#thumbnail-disc)\">\n\
+             <circle cx=\"{center}\" cy=\"{center}\" r=\"{radius:.2}\" fill=\"{}\"/>\n\
+             {blobs}\
+             <circle cx=\"{center}\" cy=\"{center}\" r=\"{radius:.2}\" \
+             fill=\"url(#thumbnail-shade)\"/>\n</g>\n",
+            svg_color(self.base),
+            center = CENTER,
+            radius = self.radius,
+            blobs = blobs,
+        );
+        svg
+    }
+
+    /** Get the PDF content stream operators that draw the thumbnail.
+
+    The operators draw in the thumbnail's own coordinates, so they need to be preceded by a
+    transformation that places the view box on the page and flips its y axis.
+    */
+    pub(super) fn pdf_operators(&self) -> String {
+        let fill = |blob: &Blob| {
+            format!(
+                "{} rg\n{}f\n",
+                pdf_color(blob.color),
+                pdf_ellipse(blob.cx, blob.cy, blob.rx, blob.ry)
+            )
+        };
+        if self.radius == 0.0 {
+            return self.blobs.iter().map(fill).collect();
+        }
+
+        let mut operators = String::new();
+        if let Some((color, width)) = self.halo {
+            let radius = self.radius + width / 2.0;
+            operators += &format!(
+                "{} RG\n{:.2} w\n{}S\n",
+                pdf_color(color),
+                width,
+                pdf_ellipse(CENTER, CENTER, radius, radius)
+            );
+        }
+
+        // Everything else is cut off at the edge of the disc
+        let disc = pdf_ellipse(CENTER, CENTER, self.radius, self.radius);
+        operators += &format!("q\n{}W n\n", disc);
+        operators += &format!("{} rg\n{}f\n", pdf_color(self.base), disc);
+        operators.extend(self.blobs.iter().map(fill));
+        operators += "Q\n";
+        operators
+    }
+}
+
+fn svg_color(color: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", color[0], color[1], color[2])
+}
+
+fn pdf_color(color: Rgb) -> String {
+    format!(
+        "{:.3} {:.3} {:.3}",
+        color[0] as f64 / 255.0,
+        color[1] as f64 / 255.0,
+        color[2] as f64 / 255.0
+    )
+}
+
+/** Get the PDF path operators of an ellipse, made of four Bézier curves. */
+fn pdf_ellipse(cx: f64, cy: f64, rx: f64, ry: f64) -> String {
+    let (kx, ky) = (rx * BEZIER_KAPPA, ry * BEZIER_KAPPA);
+    format!(
+        "{:.2} {:.2} m\n\
+         {:.2} {:.2} {:.2} {:.2} {:.2} {:.2} c\n\
+         {:.2} {:.2} {:.2} {:.2} {:.2} {:.2} c\n\
+         {:.2} {:.2} {:.2} {:.2} {:.2} {:.2} c\n\
+         {:.2} {:.2} {:.2} {:.2} {:.2} {:.2} c\nh\n",
+        cx + rx,
+        cy,
+        cx + rx,
+        cy + ky,
+        cx + kx,
+        cy + ry,
+        cx,
+        cy + ry,
+        cx - kx,
+        cy + ry,
+        cx - rx,
+        cy + ky,
+        cx - rx,
+        cy,
+        cx - rx,
+        cy - ky,
+        cx - kx,
+        cy - ry,
+        cx,
+        cy - ry,
+        cx + kx,
+        cy - ry,
+        cx + rx,
+        cy - ky,
+        cx + rx,
+        cy
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::TABLES;
+
+    #[test]
+    fn thumbnails() {
+        let mut world = World::empty();
+        world.name = "Regina".to_string();
+        world.size = 8;
+        world.atmosphere = TABLES.atmo_table[6].clone();
+        world.hydrographics = TABLES.hydro_table[7].clone();
+        world.temperature = TABLES.temp_table[3].clone();
+
+        // The same world always gets the same picture
+        let thumbnail = world.thumbnail();
+        assert_eq!(thumbnail, world.thumbnail());
+        assert_eq!(thumbnail.base, Climate::Cold.water_color());
+        assert!(thumbnail.halo.is_some());
+        let ice_caps = thumbnail
+            .blobs
+            .iter()
+            .filter(|blob| blob.color == ICE_COLOR)
+            .count();
+        assert_eq!(ice_caps, 2);
+
+        let svg = world.thumbnail_svg();
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.contains("clip-path=\"url(#thumbnail-disc)\""));
+
+        // Dry and airless worlds are all land with no glow
+        world.atmosphere = TABLES.atmo_table[0].clone();
+        world.hydrographics = TABLES.hydro_table[0].clone();
+        let thumbnail = world.thumbnail();
+        assert_eq!(thumbnail.base, Climate::Cold.land_color());
+        assert!(thumbnail.blobs.is_empty());
+        assert_eq!(thumbnail.halo, None);
+
+        // Planetoid belts have no disc at all
+        world.size = 0;
+        let thumbnail = world.thumbnail();
+        assert_eq!(thumbnail.radius, 0.0);
+        assert!(!thumbnail.svg_elements().contains("thumbnail-disc"));
+        assert!(!thumbnail.pdf_operators().contains("W n"));
+    }
+}
@@ -0,0 +1,335 @@
+use std::fmt;
+
+use lazy_static::lazy_static;
+use serde::{
+    de::{value, IntoDeserializer},
+    Deserialize, Serialize,
+};
+
+use crate::astrography::{world_sheet::sheet_section_lines, TradeCode, TravelCode, World};
+use crate::dice;
+
+const TRADE_GOODS_TABLE_CSV: &str = include_str!("../../resources/tables/trade_goods.csv");
+
+lazy_static! {
+    pub static ref TRADE_GOODS_TABLE: Vec<TradeGoodRecord> = load_trade_goods_table();
+}
+
+/** World condition that modifies the price of a trade good. */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TradeCondition {
+    TradeCode(TradeCode),
+    TravelCode(TravelCode),
+}
+
+impl TradeCondition {
+    fn applies_to(&self, world: &World) -> bool {
+        match self {
+            Self::TradeCode(trade_code) => world.trade_codes.contains(trade_code),
+            Self::TravelCode(travel_code) => world.travel_code == *travel_code,
+        }
+    }
+}
+
+impl fmt::Display for TradeCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TradeCode(trade_code) => write!(f, "{:?}", trade_code),
+            Self::TravelCode(travel_code) => write!(f, "{:?}", travel_code),
+        }
+    }
+}
+
+/** A row of the trade goods table.
+
+Based on the Mongoose Traveller 2nd Edition trade goods table.
+*/
+#[derive(Clone, Debug)]
+pub struct TradeGoodRecord {
+    pub code: u16,
+    pub good: String,
+    /// Trade codes of worlds where the good is available; `None` if it is available everywhere
+    pub availability: Option<Vec<TradeCode>>,
+    /// Number of d6 rolled for the tonnage of a lot
+    pub tons_dice: u32,
+    /// Multiplier applied to the roll for the tonnage of a lot
+    pub tons_multiplier: u32,
+    /// Price in credits of a single ton
+    pub base_price: u32,
+    pub purchase_dms: Vec<(TradeCondition, i32)>,
+    pub sale_dms: Vec<(TradeCondition, i32)>,
+    pub illegal: bool,
+}
+
+impl TradeGoodRecord {
+    /** Whether the good is commonly available to buy on `world`. */
+    pub fn is_available_on(&self, world: &World) -> bool {
+        match &self.availability {
+            Some(trade_codes) => trade_codes
+                .iter()
+                .any(|trade_code| world.trade_codes.contains(trade_code)),
+            None => true,
+        }
+    }
+
+    /** Highest purchase DM that applies to buying the good on `world`. */
+    pub fn purchase_dm(&self, world: &World) -> i32 {
+        highest_dm(&self.purchase_dms, world)
+    }
+
+    /** Highest sale DM that applies to selling the good on `world`. */
+    pub fn sale_dm(&self, world: &World) -> i32 {
+        highest_dm(&self.sale_dms, world)
+    }
+
+    /** Roll the tonnage of a single lot of the good. */
+    fn roll_tons(&self) -> u32 {
+        dice::roll(self.tons_dice, 6) * self.tons_multiplier
+    }
+}
+
+/** A lot of a trade good available for purchase on a `World`. */
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TradeGood {
+    pub code: u16,
+    pub tons: u32,
+}
+
+impl World {
+    /** Generate the lots of trade goods available for purchase on the `World`.
+
+    Every good that is commonly available given the `World`'s trade codes gets a lot, then a
+    number of additional random lots are added; more populous worlds get more random lots.
+    Illegal goods are only ever found on worlds with a pirate base or an Amber or Red travel code.
+    */
+    pub fn generate_trade_goods(&mut self) {
+        let black_market = self.has_pirate_base || self.travel_code != TravelCode::Safe;
+        let allowed = |record: &&TradeGoodRecord| !record.illegal || black_market;
+
+        let mut goods: Vec<TradeGood> = TRADE_GOODS_TABLE
+            .iter()
+            .filter(allowed)
+            .filter(|record| record.is_available_on(self))
+            .map(|record| TradeGood {
+                code: record.code,
+                tons: record.roll_tons(),
+            })
+            .collect();
+
+        let population_dm = match self.population.code {
+            0..=3 => -3,
+            4..=8 => 0,
+            _ => 3,
+        };
+        let random_lots = (dice::roll_1d(6) + population_dm).max(0);
+        let candidates: Vec<&TradeGoodRecord> = TRADE_GOODS_TABLE.iter().filter(allowed).collect();
+
+        for _ in 0..random_lots {
+            let record = candidates[dice::roll_range(0..candidates.len())];
+            let tons = record.roll_tons();
+            match goods.iter_mut().find(|good| good.code == record.code) {
+                Some(good) => good.tons += tons,
+                None => goods.push(TradeGood {
+                    code: record.code,
+                    tons,
+                }),
+            }
+        }
+
+        goods.sort_by_key(|good| good.code);
+        self.trade_goods = goods;
+    }
+
+    /** Get the trade goods table as a section of the `World`'s data sheet, with the tons of each
+    good for sale and the DMs to buy and sell it on the `World`.
+    */
+    pub(super) fn trade_goods_sheet_section(&self, markdown: bool) -> String {
+        let heading = if markdown {
+            "Trade Goods"
+        } else {
+            "Trade Goods (Tons, Base Price, Purchase DM, Sale DM)"
+        };
+        let mut lines = sheet_section_lines(heading, markdown);
+        if markdown {
+            lines.push("| Good | Tons | Base Price | Purchase DM | Sale DM |".to_string());
+            lines.push("| --- | --- | --- | --- | --- |".to_string());
+        }
+
+        for record in TRADE_GOODS_TABLE.iter() {
+            let good = if record.illegal {
+                format!("{} (illegal)", record.good)
+            } else {
+                record.good.clone()
+            };
+            let tons = self
+                .trade_goods
+                .iter()
+                .find(|good| good.code == record.code)
+                .map(|good| good.tons.to_string())
+                .unwrap_or_else(|| "-".to_string());
+            let purchase_dm = record.purchase_dm(self);
+            let sale_dm = record.sale_dm(self);
+            lines.push(if markdown {
+                format!(
+                    "| {} | {} | Cr{} | {:+} | {:+} |",
+                    good, tons, record.base_price, purchase_dm, sale_dm
+                )
+            } else {
+                format!(
+                    "{}: {}, Cr{}, {:+}, {:+}",
+                    good, tons, record.base_price, purchase_dm, sale_dm
+                )
+            });
+        }
+        lines.join("\n")
+    }
+}
+
+fn highest_dm(dms: &[(TradeCondition, i32)], world: &World) -> i32 {
+    dms.iter()
+        .filter(|(condition, _)| condition.applies_to(world))
+        .map(|(_, dm)| *dm)
+        .max()
+        .unwrap_or(0)
+}
+
+fn load_trade_goods_table() -> Vec<TradeGoodRecord> {
+    #[derive(Deserialize)]
+    struct CsvRecord {
+        code: u16,
+        good: String,
+        availability: String,
+        tons_dice: u32,
+        tons_multiplier: u32,
+        base_price: u32,
+        purchase_dms: String,
+        sale_dms: String,
+        illegal: bool,
+    }
+
+    let mut table = Vec::new();
+    let mut reader = csv::Reader::from_reader(TRADE_GOODS_TABLE_CSV.as_bytes());
+    for (index, result) in reader.deserialize().enumerate() {
+        let record: CsvRecord = result.expect("Trade goods csv should always be valid");
+        assert_eq!(
+            record.code, index as u16,
+            "The code field in each row must match its zero-indexed position in the table"
+        );
+
+        let availability = match &record.availability[..] {
+            "All" => None,
+            codes => Some(codes.split_whitespace().map(parse_trade_code).collect()),
+        };
+
+        table.push(TradeGoodRecord {
+            code: record.code,
+            good: record.good,
+            availability,
+            tons_dice: record.tons_dice,
+            tons_multiplier: record.tons_multiplier,
+            base_price: record.base_price,
+            purchase_dms: parse_dms(&record.purchase_dms),
+            sale_dms: parse_dms(&record.sale_dms),
+            illegal: record.illegal,
+        });
+    }
+    table
+}
+
+/** Parse a list of DMs such as "Ag+2 Ri-1 Amber+4". */
+fn parse_dms(dms: &str) -> Vec<(TradeCondition, i32)> {
+    dms.split_whitespace()
+        .map(|dm| {
+            let split = dm
+                .find(['+', '-'])
+                .expect("Trade good DMs should have a sign");
+            let (condition, value) = dm.split_at(split);
+            let value: i32 = value
+                .trim_start_matches('+')
+                .parse()
+                .expect("Trade good DMs should be integers");
+
+            let condition = match condition {
+                "Amber" => TradeCondition::TravelCode(TravelCode::Amber),
+                "Red" => TradeCondition::TravelCode(TravelCode::Red),
+                code => TradeCondition::TradeCode(parse_trade_code(code)),
+            };
+            (condition, value)
+        })
+        .collect()
+}
+
+fn parse_trade_code(code: &str) -> TradeCode {
+    let deserializer: value::StrDeserializer<value::Error> = code.into_deserializer();
+    TradeCode::deserialize(deserializer).expect("Trade goods should only use valid trade codes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_trade_goods() {
+        assert!(!TRADE_GOODS_TABLE.is_empty());
+
+        let radioactives = TRADE_GOODS_TABLE
+            .iter()
+            .find(|record| record.good == "Radioactives")
+            .unwrap();
+        assert!(radioactives
+            .sale_dms
+            .contains(&(TradeCondition::TradeCode(TradeCode::Ag), -3)));
+    }
+
+    #[test]
+    fn trade_good_dms() {
+        let mut world = World::empty();
+        world.trade_codes.insert(TradeCode::In);
+        world.trade_codes.insert(TradeCode::Ht);
+        world.travel_code = TravelCode::Red;
+
+        let weapons = &TRADE_GOODS_TABLE[9];
+        assert_eq!(weapons.good, "Advanced Weapons");
+        assert!(weapons.is_available_on(&world));
+        assert_eq!(weapons.purchase_dm(&world), 2);
+        assert_eq!(weapons.sale_dm(&world), 4);
+
+        world.generate_trade_goods();
+        assert!(world
+            .trade_goods
+            .iter()
+            .any(|good| good.code == weapons.code));
+    }
+
+    #[test]
+    fn trade_goods_sheet_section() {
+        let mut world = World::empty();
+        world.trade_codes.insert(TradeCode::In);
+        world.travel_code = TravelCode::Red;
+        world.trade_goods = vec![TradeGood { code: 9, tons: 5 }];
+
+        let markdown = world.trade_goods_sheet_section(true);
+        assert!(markdown.starts_with(
+            "\n\n#### Trade Goods\n\n| Good | Tons | Base Price | Purchase DM | Sale DM |\n"
+        ));
+        assert_eq!(
+            markdown.lines().count(),
+            TRADE_GOODS_TABLE.len() + 6,
+            "Every good in the table gets a row"
+        );
+        let weapons = &TRADE_GOODS_TABLE[9];
+        assert!(markdown.contains(&format!(
+            "\n| Advanced Weapons | 5 | Cr{} | {:+} | {:+} |",
+            weapons.base_price,
+            weapons.purchase_dm(&world),
+            weapons.sale_dm(&world)
+        )));
+
+        let text = world.trade_goods_sheet_section(false);
+        assert!(text.contains(&format!(
+            "\nAdvanced Weapons: 5, Cr{}, ",
+            weapons.base_price
+        )));
+        assert!(text.contains(&format!("\n{}: -, ", TRADE_GOODS_TABLE[0].good)));
+    }
+}
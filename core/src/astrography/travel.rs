@@ -0,0 +1,332 @@
+use std::{collections::BTreeMap, fmt};
+
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::{Point, StarportClass, Subsector, World};
+
+/// Standard gravity in m/s^2
+const STANDARD_GRAVITY: f64 = 9.81;
+
+/// Safe jump distance from a world, in multiples of its diameter
+const JUMP_LIMIT_DIAMETERS: f64 = 100.0;
+
+/// Hours spent in jump space for any jump, regardless of distance
+pub const JUMP_HOURS: f64 = 168.0;
+
+/** The performance characteristics of a ship relevant to planning a route. */
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ShipSpec {
+    /// Tonnage of fuel the ship can carry
+    pub fuel_capacity: u32,
+    /// Displacement tonnage of the ship's hull
+    pub hull_tonnage: u32,
+    /// Maximum number of parsecs the ship can cover in a single jump
+    pub jump_rating: u32,
+    /// Thrust of the ship's maneuver drive in G's
+    pub thrust: u32,
+}
+
+impl ShipSpec {
+    /** Tonnage of fuel required to make a jump of `parsecs`. */
+    pub fn jump_fuel(&self, parsecs: u32) -> u32 {
+        // Each parsec jumped takes fuel equal to 10% of the ship's hull, rounded up
+        (self.hull_tonnage * parsecs).div_ceil(10)
+    }
+
+    /** Hours needed to travel from the surface of a world with `diameter` to its jump limit.
+
+    Assumes constant acceleration the whole way, since there is no need to slow down before jumping.
+    The same time is taken to travel from the jump limit to the surface at the other end.
+    */
+    pub fn transit_hours(&self, diameter: u32) -> f64 {
+        if self.thrust == 0 {
+            return f64::INFINITY;
+        }
+
+        let distance = diameter as f64 * 1000.0 * JUMP_LIMIT_DIAMETERS;
+        let acceleration = self.thrust as f64 * STANDARD_GRAVITY;
+        (2.0 * distance / acceleration).sqrt() / 3600.0
+    }
+}
+
+impl Subsector {
+    /** Find the fewest jumps a ship with `jump_rating` needs to reach each `World` from `origin`.
+
+    Every jump must end at a `World`, since a ship can't refuel in an empty hex, so worlds beyond
+    the first jump are found by hopping from world to world. `origin` itself needn't have a `World`
+    in it, and is never included.
+
+    # Returns
+    The number of jumps to each `World` that can be reached in at most `max_jumps` jumps
+    */
+    pub fn jump_reachability(
+        &self,
+        origin: &Point,
+        jump_rating: u32,
+        max_jumps: u32,
+    ) -> BTreeMap<Point, u32> {
+        let mut reached = BTreeMap::new();
+        let mut frontier = vec![*origin];
+        for jumps in 1..=max_jumps {
+            let next: Vec<Point> = self
+                .map
+                .keys()
+                .filter(|point| *point != origin && !reached.contains_key(*point))
+                .filter(|point| {
+                    frontier
+                        .iter()
+                        .any(|from| from.distance(point) <= jump_rating)
+                })
+                .copied()
+                .collect();
+            if next.is_empty() {
+                break;
+            }
+
+            reached.extend(next.iter().map(|point| (*point, jumps)));
+            frontier = next;
+        }
+        reached
+    }
+}
+
+impl Default for ShipSpec {
+    fn default() -> Self {
+        // A standard 200-ton Free Trader
+        Self {
+            fuel_capacity: 21,
+            hull_tonnage: 200,
+            jump_rating: 1,
+            thrust: 1,
+        }
+    }
+}
+
+/** The source of fuel available at a stop along a route. */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Refueling {
+    /// Refined fuel can be bought from the starport
+    Refined,
+    /// Unrefined fuel can be bought from the starport
+    Unrefined,
+    /// No starport fuel, but fuel can be skimmed from a gas giant or collected from surface water
+    Wilderness,
+    /// No fuel is available at all
+    None,
+}
+
+impl Refueling {
+    fn at(world: Option<&World>) -> Self {
+        let world = match world {
+            Some(world) => world,
+            None => return Self::None,
+        };
+
+        match world.starport.class {
+            StarportClass::A | StarportClass::B => Self::Refined,
+            StarportClass::C | StarportClass::D => Self::Unrefined,
+            StarportClass::E | StarportClass::X => {
+                if world.has_gas_giant() || world.hydrographics.code > 0 {
+                    Self::Wilderness
+                } else {
+                    Self::None
+                }
+            }
+        }
+    }
+
+    pub fn is_available(&self) -> bool {
+        !matches!(self, Self::None)
+    }
+}
+
+impl fmt::Display for Refueling {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Refined => "Refined",
+            Self::Unrefined => "Unrefined",
+            Self::Wilderness => "Wilderness",
+            Self::None => "None",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** A problem that prevents a ship from completing a leg of a route. */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LegProblem {
+    /// The distance of the leg is beyond the ship's jump rating
+    BeyondJumpRating,
+    /// The ship's tanks can't hold enough fuel for the jump, even when full
+    InsufficientCapacity,
+    /// The ship won't have enough fuel left in its tanks to make the jump
+    OutOfFuel,
+}
+
+impl fmt::Display for LegProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::BeyondJumpRating => "Beyond jump rating",
+            Self::InsufficientCapacity => "Fuel capacity too small",
+            Self::OutOfFuel => "Not enough fuel",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** A single jump between two stops along a route. */
+#[derive(Clone, Debug, PartialEq)]
+pub struct RouteLeg {
+    pub from: Point,
+    pub to: Point,
+    pub parsecs: u32,
+    /// Tonnage of fuel needed for the jump
+    pub fuel_required: u32,
+    /// Tonnage of fuel in the ship's tanks after the jump, before any refueling
+    pub fuel_remaining: u32,
+    /// Hours spent in normal space departing `from` and arriving at `to`
+    pub transit_hours: f64,
+    /// Fuel available at the destination
+    pub refueling: Refueling,
+    pub problem: Option<LegProblem>,
+}
+
+impl RouteLeg {
+    pub fn total_hours(&self) -> f64 {
+        self.transit_hours + JUMP_HOURS
+    }
+}
+
+/** Plan out a route through `subsector` that visits each of `stops` in order using `ship`.
+
+The ship is assumed to start with full tanks and to refuel to full at every stop where fuel is
+available.
+Stops don't need to have a `World` in them, but there is no way to refuel at such stops.
+*/
+pub fn plan_route(subsector: &Subsector, stops: &[Point], ship: &ShipSpec) -> Vec<RouteLeg> {
+    let mut legs = Vec::new();
+    let mut fuel = ship.fuel_capacity;
+    for pair in stops.windows(2) {
+        let (from, to) = (pair[0], pair[1]);
+        let from_world = subsector.get_world(&from);
+        let to_world = subsector.get_world(&to);
+
+        if Refueling::at(from_world).is_available() {
+            fuel = ship.fuel_capacity;
+        }
+
+        let parsecs = from.distance(&to);
+        let fuel_required = ship.jump_fuel(parsecs);
+        let problem = if parsecs > ship.jump_rating {
+            Some(LegProblem::BeyondJumpRating)
+        } else if fuel_required > ship.fuel_capacity {
+            Some(LegProblem::InsufficientCapacity)
+        } else if fuel_required > fuel {
+            Some(LegProblem::OutOfFuel)
+        } else {
+            None
+        };
+
+        // Assume the problem is resolved somehow so the rest of the route can still be planned
+        fuel = fuel.saturating_sub(fuel_required);
+
+        let diameter = |world: Option<&World>| world.map(|world| world.diameter).unwrap_or(0);
+        legs.push(RouteLeg {
+            from,
+            to,
+            parsecs,
+            fuel_required,
+            fuel_remaining: fuel,
+            transit_hours: ship.transit_hours(diameter(from_world))
+                + ship.transit_hours(diameter(to_world)),
+            refueling: Refueling::at(to_world),
+            problem,
+        });
+    }
+    legs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::TABLES;
+
+    fn world_with_starport(class_code: usize) -> World {
+        let mut world = World::empty();
+        world.starport = TABLES.starport_table[class_code].clone();
+        world
+    }
+
+    #[test]
+    fn jump_reachability() {
+        let mut subsector = Subsector::empty();
+        let origin = Point { x: 1, y: 1 };
+        let near = Point { x: 1, y: 3 };
+        let far = Point { x: 1, y: 5 };
+        let isolated = Point { x: 8, y: 10 };
+        for point in [origin, near, far, isolated] {
+            subsector.insert_world(&point, World::empty()).unwrap();
+        }
+
+        let reachable = subsector.jump_reachability(&origin, 2, 1);
+        assert_eq!(reachable, BTreeMap::from([(near, 1)]));
+
+        let reachable = subsector.jump_reachability(&origin, 2, 6);
+        assert_eq!(reachable, BTreeMap::from([(near, 1), (far, 2)]));
+
+        // Starting from an empty hex
+        let reachable = subsector.jump_reachability(&Point { x: 1, y: 7 }, 2, 6);
+        assert_eq!(
+            reachable,
+            BTreeMap::from([(far, 1), (near, 2), (origin, 3)])
+        );
+        assert!(subsector.jump_reachability(&origin, 1, 6).is_empty());
+    }
+
+    #[test]
+    fn route_fuel() {
+        let mut subsector = Subsector::empty();
+        let refined = Point { x: 1, y: 1 };
+        let no_fuel = Point { x: 1, y: 2 };
+        let far = Point { x: 1, y: 4 };
+        subsector
+            .insert_world(&refined, world_with_starport(11))
+            .unwrap();
+        subsector
+            .insert_world(&no_fuel, world_with_starport(0))
+            .unwrap();
+        subsector
+            .insert_world(&far, world_with_starport(0))
+            .unwrap();
+
+        let ship = ShipSpec {
+            fuel_capacity: 20,
+            hull_tonnage: 200,
+            jump_rating: 2,
+            thrust: 1,
+        };
+
+        let legs = plan_route(&subsector, &[refined, no_fuel, far], &ship);
+        assert_eq!(legs.len(), 2);
+
+        assert_eq!(legs[0].parsecs, 1);
+        assert_eq!(legs[0].fuel_required, 20);
+        assert_eq!(legs[0].fuel_remaining, 0);
+        assert_eq!(legs[0].refueling, Refueling::None);
+        assert_eq!(legs[0].problem, None);
+
+        assert_eq!(legs[1].parsecs, 2);
+        assert_eq!(legs[1].problem, Some(LegProblem::InsufficientCapacity));
+
+        let ship = ShipSpec {
+            fuel_capacity: 40,
+            ..ship
+        };
+        let legs = plan_route(&subsector, &[refined, no_fuel, far], &ship);
+        assert_eq!(legs[1].problem, Some(LegProblem::OutOfFuel));
+
+        let legs = plan_route(&subsector, &[refined, no_fuel, refined, far], &ship);
+        assert_eq!(legs[1].problem, None);
+        assert_eq!(legs[2].problem, Some(LegProblem::BeyondJumpRating));
+    }
+}
@@ -0,0 +1,170 @@
+use crate::astrography::{Faction, StarportClass, TravelCode, World, TABLES};
+
+/// Strength code from which factions are strong enough to unsettle a world, i.e. "Significant"
+const STRONG_FACTION_CODE: usize = 10;
+/// Government code of a balkanized world, whose factions are already at odds
+const BALKANIZATION_CODE: u16 = 7;
+
+impl World {
+    /** Get a short travel advisory for the `World` as a travel agency would post it, e.g. "Amber
+    Zone: ongoing civil conflict between the Loyalists and Separatists; customs inspections common
+    under law level 9".
+
+    Each concern is taken from the world's atmosphere, government, factions, law level, starport,
+    and bases, most dangerous first.
+    */
+    pub fn travel_advisory_text(&self) -> String {
+        let zone = match self.travel_code {
+            TravelCode::Safe => "Green Zone",
+            TravelCode::Amber => "Amber Zone",
+            TravelCode::Red => "Red Zone",
+        };
+
+        let mut concerns = Vec::new();
+        match self.atmosphere.code {
+            0 | 1 => {
+                concerns.push("vacuum suits required outside of pressurized areas".to_string())
+            }
+            2 | 4 | 7 | 9 => {
+                concerns.push("filter masks required against a tainted atmosphere".to_string())
+            }
+            10 => concerns.push("air supply required in an exotic atmosphere".to_string()),
+            11 | 12 => concerns
+                .push("protective suits required against a corrosive atmosphere".to_string()),
+            _ => (),
+        }
+        if let Some(conflict) = self.faction_conflict() {
+            concerns.push(conflict);
+        }
+        if self.government.code == 0 {
+            concerns.push("no government to appeal to in case of trouble".to_string());
+        }
+        match self.law_level.code {
+            0 if self.population.code > 0 => concerns.push(
+                "no law enforcement to speak of; visitors are advised to go armed".to_string(),
+            ),
+            9.. => concerns.push(format!(
+                "customs inspections common under law level {}",
+                self.law_level.code
+            )),
+            _ => (),
+        }
+        match self.starport.class {
+            StarportClass::E => {
+                concerns.push("frontier starport with no fuel or repairs".to_string())
+            }
+            StarportClass::X => {
+                concerns.push("no starport; ships land at their own risk".to_string())
+            }
+            _ => (),
+        }
+        if self.has_pirate_base {
+            concerns.push("pirate activity reported in the system".to_string());
+        }
+
+        if concerns.is_empty() {
+            format!("{}: no particular dangers to travellers reported", zone)
+        } else {
+            format!("{}: {}", zone, concerns.join("; "))
+        }
+    }
+
+    /** Write a new travel advisory for the `World` from its current data. */
+    pub fn generate_travel_advisory(&mut self) {
+        self.travel_advisory = self.travel_advisory_text();
+    }
+
+    /** Describe the strife between the two strongest factions, if the government is balkanized or
+    they're strong enough to be at odds with it.
+    */
+    fn faction_conflict(&self) -> Option<String> {
+        let mut factions: Vec<_> = self.factions.iter().collect();
+        factions.sort_by_key(|faction| std::cmp::Reverse(strength_code(faction)));
+        let balkanized = self.government.code == BALKANIZATION_CODE;
+        let strong = factions
+            .first()
+            .is_some_and(|faction| strength_code(faction) >= STRONG_FACTION_CODE);
+        if factions.len() < 2 || !(balkanized || strong) {
+            return balkanized.then(|| "ongoing civil conflict between rival factions".to_string());
+        }
+
+        let parties = if factions[..2].iter().any(|faction| faction.is_unnamed()) {
+            "rival factions".to_string()
+        } else {
+            format!(
+                "the {} and {}",
+                factions[0].name.trim(),
+                factions[1].name.trim()
+            )
+        };
+        Some(if balkanized {
+            format!("ongoing civil conflict between {}", parties)
+        } else {
+            format!("political unrest between {}", parties)
+        })
+    }
+}
+
+/** Get the lowest code of the faction table with the strength of `faction`, since the code it was
+rolled with isn't saved.
+*/
+fn strength_code(faction: &Faction) -> usize {
+    TABLES
+        .faction_table
+        .iter()
+        .position(|record| record.strength == faction.strength)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn travel_advisory() {
+        let mut world = World::empty();
+        world.population.code = 6;
+        world.government = TABLES.gov_table[4].clone();
+        world.law_level = TABLES.law_table[4].clone();
+        world.atmosphere = TABLES.atmo_table[6].clone();
+        world.starport.class = StarportClass::B;
+        world.travel_code = TravelCode::Safe;
+        assert_eq!(
+            world.travel_advisory_text(),
+            "Green Zone: no particular dangers to travellers reported"
+        );
+
+        world.government = TABLES.gov_table[BALKANIZATION_CODE as usize].clone();
+        world.law_level = TABLES.law_table[9].clone();
+        world.travel_code = TravelCode::Amber;
+        assert_eq!(
+            world.travel_advisory_text(),
+            "Amber Zone: ongoing civil conflict between rival factions; \
+            customs inspections common under law level 9"
+        );
+
+        for (name, code) in [("Separatists", 6), ("Loyalists", 11)] {
+            let mut faction = Faction::random();
+            faction.name = name.to_string();
+            faction.strength = TABLES.faction_table[code].strength.clone();
+            world.factions.push(faction);
+        }
+        world.has_pirate_base = true;
+        world.generate_travel_advisory();
+        assert_eq!(
+            world.travel_advisory,
+            "Amber Zone: ongoing civil conflict between the Loyalists and Separatists; \
+            customs inspections common under law level 9; pirate activity reported in the system"
+        );
+
+        // Strong factions unsettle even a stable government
+        world.government = TABLES.gov_table[4].clone();
+        assert!(world
+            .travel_advisory_text()
+            .contains("political unrest between the Loyalists and Separatists"));
+        for faction in world.factions.iter_mut() {
+            faction.strength = TABLES.faction_table[2].strength.clone();
+        }
+        assert!(!world.travel_advisory_text().contains("Loyalists"));
+    }
+}
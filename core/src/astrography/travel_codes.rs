@@ -0,0 +1,156 @@
+use std::fmt;
+
+use crate::astrography::{Point, Subsector, TravelCode, World};
+
+/** Which worlds of a `Subsector` a `TravelCodePolicy` applies to. */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TravelCodeCondition {
+    /// Every world
+    All,
+    /// Worlds with at least this law level
+    LawLevelAtLeast(u16),
+    /// Worlds with a pirate base
+    PirateBase,
+    /// Worlds that currently have this travel code
+    TravelCode(TravelCode),
+}
+
+impl TravelCodeCondition {
+    /** Check if `world` meets the condition. */
+    pub fn is_met(&self, world: &World) -> bool {
+        match self {
+            Self::All => true,
+            Self::LawLevelAtLeast(law_level) => world.law_level.code >= *law_level,
+            Self::PirateBase => world.has_pirate_base,
+            Self::TravelCode(travel_code) => world.travel_code == *travel_code,
+        }
+    }
+}
+
+impl fmt::Display for TravelCodeCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::All => write!(f, "all worlds"),
+            Self::LawLevelAtLeast(law_level) => write!(f, "worlds with law level {}+", law_level),
+            Self::PirateBase => write!(f, "worlds with a pirate base"),
+            Self::TravelCode(travel_code) => write!(f, "{:?} worlds", travel_code),
+        }
+    }
+}
+
+/** Rule for setting the travel code of many worlds of a `Subsector` at once, e.g. "mark worlds with
+law level 9+ Amber" or "clear all Red zones".
+*/
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TravelCodePolicy {
+    pub condition: TravelCodeCondition,
+    /// Travel code given to the worlds meeting `condition`
+    pub travel_code: TravelCode,
+}
+
+impl fmt::Display for TravelCodePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Mark {} {:?}", self.condition, self.travel_code)
+    }
+}
+
+impl Subsector {
+    /** Apply `policy` to every `World` it would change.
+
+    The travel codes it sets are marked as overridden so they aren't recalculated from the worlds'
+    data afterward.
+
+    # Returns
+    The locations of the changed worlds, in order.
+    */
+    pub fn apply_travel_code_policy(&mut self, policy: &TravelCodePolicy) -> Vec<Point> {
+        let points = self.travel_code_policy_targets(policy);
+        for point in points.iter() {
+            if let Some(world) = self.map.get_mut(point) {
+                world.travel_code = policy.travel_code;
+                world.travel_code_overridden = true;
+            }
+        }
+        points
+    }
+
+    /** Find the worlds that meet the condition of `policy` but don't already have its travel code.
+
+    # Returns
+    The locations of the worlds that applying `policy` would change, in order.
+    */
+    pub fn travel_code_policy_targets(&self, policy: &TravelCodePolicy) -> Vec<Point> {
+        self.map
+            .iter()
+            .filter(|(_, world)| {
+                policy.condition.is_met(world) && world.travel_code != policy.travel_code
+            })
+            .map(|(point, _)| *point)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_travel_code_policy() {
+        let mut subsector = Subsector::empty();
+        let points: Vec<Point> = (1..=3).map(|y| Point { x: 1, y }).collect();
+        for (index, point) in points.iter().enumerate() {
+            let mut world = World::empty();
+            world.law_level.code = index as u16 * 4;
+            world.has_pirate_base = index == 0;
+            world.travel_code = if index == 2 {
+                TravelCode::Red
+            } else {
+                TravelCode::Safe
+            };
+            subsector.insert_world(point, world).unwrap();
+        }
+
+        let amber_high_law = TravelCodePolicy {
+            condition: TravelCodeCondition::LawLevelAtLeast(4),
+            travel_code: TravelCode::Amber,
+        };
+        assert_eq!(
+            amber_high_law.to_string(),
+            "Mark worlds with law level 4+ Amber"
+        );
+        assert_eq!(
+            subsector.travel_code_policy_targets(&amber_high_law),
+            &points[1..]
+        );
+
+        let red_pirates = TravelCodePolicy {
+            condition: TravelCodeCondition::PirateBase,
+            travel_code: TravelCode::Red,
+        };
+        assert_eq!(
+            subsector.apply_travel_code_policy(&red_pirates),
+            &points[..1]
+        );
+        let world = subsector.get_world(&points[0]).unwrap();
+        assert_eq!(world.travel_code, TravelCode::Red);
+        assert!(world.travel_code_overridden);
+
+        // Worlds that already have the travel code are left alone
+        assert!(subsector
+            .travel_code_policy_targets(&red_pirates)
+            .is_empty());
+
+        let clear_red = TravelCodePolicy {
+            condition: TravelCodeCondition::TravelCode(TravelCode::Red),
+            travel_code: TravelCode::Safe,
+        };
+        assert_eq!(
+            subsector.apply_travel_code_policy(&clear_red),
+            [points[0], points[2]]
+        );
+        assert!(subsector
+            .get_map()
+            .values()
+            .all(|world| world.travel_code == TravelCode::Safe));
+    }
+}
@@ -0,0 +1,345 @@
+use std::{fmt, ops::RangeInclusive};
+
+use crate::astrography::{StarportClass, World, TABLES};
+
+/** A combination of [`World`] data that could never have been produced by the generation rules.
+
+These usually show up after manual edits or when importing hand-edited JSON; e.g. bumping a world's
+population down to 0 without also touching its government.
+*/
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Inconsistency {
+    /// Size 0 worlds are planetoids and can not hold onto an atmosphere
+    AtmosphereOnPlanetoid,
+    /// Worlds of size 0 or 1 are too small to have any hydrographics
+    HydrographicsOnTinyWorld,
+    /// Unpopulated worlds can't have a government
+    GovernmentWithoutPopulation,
+    /// Unpopulated worlds can't have factions
+    FactionsWithoutPopulation,
+    /// Worlds without a government can't have a law level
+    LawWithoutGovernment,
+    /// Tech level is outside of the range that the 1d6 tech level roll could produce
+    TechLevelOutOfRange,
+    /// The world has bases that its starport class can not support
+    UnsupportedBases,
+}
+
+impl Inconsistency {
+    /// Every kind of `Inconsistency` in the order they should be checked and fixed
+    pub const ALL_VALUES: [Inconsistency; 7] = [
+        Self::AtmosphereOnPlanetoid,
+        Self::HydrographicsOnTinyWorld,
+        Self::GovernmentWithoutPopulation,
+        Self::FactionsWithoutPopulation,
+        Self::LawWithoutGovernment,
+        Self::TechLevelOutOfRange,
+        Self::UnsupportedBases,
+    ];
+
+    /** Short description of what will be changed if this `Inconsistency` is fixed. */
+    pub fn fix_description(&self) -> &'static str {
+        match self {
+            Self::AtmosphereOnPlanetoid => "Set atmosphere to 0",
+            Self::HydrographicsOnTinyWorld => "Set hydrographics to 0",
+            Self::GovernmentWithoutPopulation => "Set government and law level to 0",
+            Self::FactionsWithoutPopulation => "Remove all factions",
+            Self::LawWithoutGovernment => "Set law level to 0",
+            Self::TechLevelOutOfRange => "Reroll tech level",
+            Self::UnsupportedBases => "Remove unsupported bases",
+        }
+    }
+
+    /** Check if `world` exhibits this `Inconsistency`. */
+    fn is_present(&self, world: &World) -> bool {
+        match self {
+            Self::AtmosphereOnPlanetoid => world.size == 0 && world.atmosphere.code != 0,
+            Self::HydrographicsOnTinyWorld => world.size <= 1 && world.hydrographics.code != 0,
+            Self::GovernmentWithoutPopulation => {
+                world.population.code == 0 && world.government.code != 0
+            }
+            Self::FactionsWithoutPopulation => {
+                world.population.code == 0 && !world.factions.is_empty()
+            }
+            Self::LawWithoutGovernment => world.government.code == 0 && world.law_level.code != 0,
+            Self::TechLevelOutOfRange => {
+                let (low, high) = tech_level_range(world);
+                !(low..=high).contains(&world.tech_level.code)
+            }
+            Self::UnsupportedBases => {
+                let unsupported = unsupported_bases(world);
+                (world.has_naval_base && unsupported.naval)
+                    || (world.has_scout_base && unsupported.scout)
+                    || (world.has_research_base && unsupported.research)
+                    || (world.has_tas && unsupported.tas)
+                    || (world.has_pirate_base && unsupported.pirate)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Inconsistency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::AtmosphereOnPlanetoid => "Size 0 worlds can not have an atmosphere",
+            Self::HydrographicsOnTinyWorld => "Size 0 and 1 worlds can not have hydrographics",
+            Self::GovernmentWithoutPopulation => "Unpopulated worlds can not have a government",
+            Self::FactionsWithoutPopulation => "Unpopulated worlds can not have factions",
+            Self::LawWithoutGovernment => "Worlds without a government can not have a law level",
+            Self::TechLevelOutOfRange => "Tech level is impossible for this world's profile",
+            Self::UnsupportedBases => "Starport class can not support all of this world's bases",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Which bases are impossible for a world to have due to its starport class or other bases
+struct UnsupportedBases {
+    naval: bool,
+    scout: bool,
+    research: bool,
+    tas: bool,
+    pirate: bool,
+}
+
+impl World {
+    /** Get the berthing cost the `World`'s starport class is based on; legal costs are multiples. */
+    pub fn base_berthing_cost(&self) -> u32 {
+        TABLES.starport_table[self.starport.code as usize].berthing_cost
+    }
+
+    /** Get the range of berthing costs the starport class could have rolled: 1 to 6 times its base
+    cost in steps of the base cost.
+    */
+    pub fn berthing_cost_range(&self) -> RangeInclusive<u32> {
+        let base = self.base_berthing_cost();
+        base..=6 * base
+    }
+
+    /** Describe how the berthing cost is outside of the starport class's legal costs, if it is. */
+    pub fn berthing_cost_warning(&self) -> Option<String> {
+        let cost = self.starport.berthing_cost;
+        if cost == self.snapped_berthing_cost(cost) {
+            return None;
+        }
+
+        let range = self.berthing_cost_range();
+        Some(format!(
+            "Class {} starports charge a multiple of Cr{} from Cr{} to Cr{}",
+            self.starport.class,
+            self.base_berthing_cost(),
+            range.start(),
+            range.end()
+        ))
+    }
+
+    /** Get the range of diameters in km that fit the `World`'s size. */
+    pub fn diameter_range(&self) -> RangeInclusive<u32> {
+        let median: u32 = match self.size {
+            0 => 800,
+            _ => (1600 * self.size).into(),
+        };
+        median - 200..=median + 200
+    }
+
+    /** Describe how the diameter doesn't fit the `World`'s size, if it doesn't. */
+    pub fn diameter_warning(&self) -> Option<String> {
+        let range = self.diameter_range();
+        if range.contains(&self.diameter) {
+            None
+        } else {
+            Some(format!(
+                "Size {} worlds are {} to {} km in diameter",
+                self.size,
+                range.start(),
+                range.end()
+            ))
+        }
+    }
+
+    /** Get the legal berthing cost closest to `cost` for the `World`'s starport class. */
+    pub fn snapped_berthing_cost(&self, cost: u32) -> u32 {
+        let base = self.base_berthing_cost();
+        if base == 0 {
+            return 0;
+        }
+        let multiple = ((cost as f64 / base as f64).round() as u32).clamp(1, 6);
+        multiple * base
+    }
+
+    /** Get the diameter closest to `diameter` that fits the `World`'s size. */
+    pub fn snapped_diameter(&self, diameter: u32) -> u32 {
+        let range = self.diameter_range();
+        diameter.clamp(*range.start(), *range.end())
+    }
+
+    /** Fix `inconsistency` by re-deriving the offending field and any fields that depend on it.
+
+    Does nothing if the `World` does not actually exhibit `inconsistency`.
+    */
+    pub fn fix_inconsistency(&mut self, inconsistency: Inconsistency) {
+        if !inconsistency.is_present(self) {
+            return;
+        }
+
+        match inconsistency {
+            Inconsistency::AtmosphereOnPlanetoid => self.generate_atmosphere(),
+            Inconsistency::HydrographicsOnTinyWorld => self.generate_hydrographics(),
+            Inconsistency::GovernmentWithoutPopulation => {
+                let mut old_gov = self.government.clone();
+                self.generate_government();
+                old_gov.safe_mutate(&self.government);
+                self.government = old_gov;
+                self.generate_law_level();
+            }
+            Inconsistency::FactionsWithoutPopulation => self.factions.clear(),
+            Inconsistency::LawWithoutGovernment => self.generate_law_level(),
+            Inconsistency::TechLevelOutOfRange => self.generate_tech_level(),
+            Inconsistency::UnsupportedBases => {
+                let unsupported = unsupported_bases(self);
+                self.has_naval_base &= !unsupported.naval;
+                self.has_scout_base &= !unsupported.scout;
+                self.has_research_base &= !unsupported.research;
+                self.has_tas &= !unsupported.tas;
+                self.has_pirate_base &= !unsupported.pirate;
+            }
+        }
+        self.normalize_data();
+    }
+
+    /** Repeatedly fix all of the `World`'s inconsistencies until there are none left.
+
+    Fixing one [`Inconsistency`] can introduce another (e.g. zeroing out a government changes the
+    tech level modifiers), so this keeps going until the `World` settles.
+    */
+    pub fn fix_all_inconsistencies(&mut self) {
+        // Each pass fixes inconsistencies in generation order, so it should never take more passes
+        // than there are kinds of inconsistencies; the limit is just a guard against a reroll loop
+        for _ in 0..Inconsistency::ALL_VALUES.len() {
+            let inconsistencies = self.inconsistencies();
+            if inconsistencies.is_empty() {
+                break;
+            }
+
+            for inconsistency in inconsistencies {
+                self.fix_inconsistency(inconsistency);
+            }
+        }
+    }
+
+    /** Check the `World` against the generation rules and return any violations found. */
+    pub fn inconsistencies(&self) -> Vec<Inconsistency> {
+        Inconsistency::ALL_VALUES
+            .into_iter()
+            .filter(|inconsistency| inconsistency.is_present(self))
+            .collect()
+    }
+}
+
+/** Get the inclusive range of tech level codes that `world` could have rolled. */
+fn tech_level_range(world: &World) -> (u16, u16) {
+    let modifier = world.tech_level_modifier();
+    let max_code = (TABLES.tech_level_table.len() - 1) as i32;
+    let low = (1 + modifier).clamp(0, max_code) as u16;
+    let high = (6 + modifier).clamp(0, max_code) as u16;
+    (low, high)
+}
+
+fn unsupported_bases(world: &World) -> UnsupportedBases {
+    use StarportClass::*;
+    let class = &world.starport.class;
+    UnsupportedBases {
+        naval: !matches!(class, A | B),
+        scout: !matches!(class, A | B | C | D),
+        research: !matches!(class, A | B | C),
+        tas: !matches!(class, A | B | C),
+        pirate: *class == A || world.has_naval_base,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_worlds_are_consistent() {
+        const ATTEMPTS: usize = 1000;
+        for _ in 0..ATTEMPTS {
+            let world = World::new("Consistent".to_string());
+            assert_eq!(world.inconsistencies(), Vec::new(), "{:?}", world);
+        }
+    }
+
+    #[test]
+    fn legal_ranges() {
+        const ATTEMPTS: usize = 1000;
+        for _ in 0..ATTEMPTS {
+            let world = World::new("Legal".to_string());
+            assert_eq!(world.diameter_warning(), None, "{:?}", world);
+            assert_eq!(world.berthing_cost_warning(), None, "{:?}", world);
+        }
+
+        let mut world = World::new("Snapped".to_string());
+        world.size = 5;
+        assert_eq!(world.diameter_range(), 7800..=8200);
+        assert_eq!(world.snapped_diameter(10_000), 8200);
+        assert_eq!(world.snapped_diameter(8000), 8000);
+        world.diameter = 100;
+        assert!(world.diameter_warning().is_some());
+
+        let base = world.base_berthing_cost();
+        if base > 0 {
+            assert_eq!(world.snapped_berthing_cost(0), base);
+            assert_eq!(world.snapped_berthing_cost(base * 3 + base / 3), base * 3);
+            assert_eq!(world.snapped_berthing_cost(base * 100), base * 6);
+            world.starport.berthing_cost = base * 3 + 1;
+            assert!(world.berthing_cost_warning().is_some());
+        }
+    }
+
+    #[test]
+    fn fix_unpopulated_world() {
+        let mut world = World::new("Unpopulated".to_string());
+        world.population = TABLES.pop_table[0].clone();
+        world.government = TABLES.gov_table[5].clone();
+        world.law_level = TABLES.law_table[5].clone();
+        world.add_faction();
+
+        let inconsistencies = world.inconsistencies();
+        assert!(inconsistencies.contains(&Inconsistency::GovernmentWithoutPopulation));
+        assert!(inconsistencies.contains(&Inconsistency::FactionsWithoutPopulation));
+        assert!(!inconsistencies.contains(&Inconsistency::LawWithoutGovernment));
+
+        // Fixing the government should also re-derive the dependent law level
+        world.fix_inconsistency(Inconsistency::GovernmentWithoutPopulation);
+        assert_eq!(world.government.code, 0);
+        assert_eq!(world.law_level.code, 0);
+
+        world.fix_all_inconsistencies();
+        assert!(world.factions.is_empty());
+        assert_eq!(world.inconsistencies(), Vec::new());
+    }
+
+    #[test]
+    fn fix_unsupported_bases() {
+        let mut world = World::new("Bases".to_string());
+        world.starport = TABLES
+            .starport_table
+            .iter()
+            .find(|starport| starport.class == StarportClass::X)
+            .unwrap()
+            .clone();
+        world.has_naval_base = true;
+        world.has_tas = true;
+        assert!(world
+            .inconsistencies()
+            .contains(&Inconsistency::UnsupportedBases));
+
+        world.fix_inconsistency(Inconsistency::UnsupportedBases);
+        assert!(!world.has_naval_base);
+        assert!(!world.has_tas);
+        assert!(!world
+            .inconsistencies()
+            .contains(&Inconsistency::UnsupportedBases));
+    }
+}
@@ -0,0 +1,1269 @@
+use std::{
+    collections::BTreeSet,
+    error::Error,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::astrography::{
+    phonetic_spelling, AtmoRecord, CulturalDetails, CulturalDiffRecord, Encounter, ExtensionRolls,
+    Facility, FuelMarket, GovRecord, Hazard, HydroRecord, LawCategories, LawRecord, Point,
+    PopRecord, Religion, RollLogEntry, RuleSet, Sophont, StarportClass, StarportRecord, Table,
+    TableKind, TechLevelRecord, TempRecord, TradeGood, WorldChange, WorldSubtype, WorldTagRecord,
+    TABLES,
+};
+use crate::dice;
+use crate::histogram::Histogram;
+
+#[derive(Clone, Debug, Deserialize, Eq, Serialize)]
+pub struct Faction {
+    pub name: String,
+    pub code: u16,
+    pub strength: String,
+    pub government: GovRecord,
+    /// Beliefs the faction's members hold in common, if any
+    #[serde(default, skip_serializing_if = "Religion::is_empty")]
+    pub religion: Religion,
+}
+
+impl Faction {
+    /// Name given to new factions until they're named
+    pub const UNNAMED: &'static str = "Unnamed";
+
+    /** Whether the faction still has the name it was generated with, or no name at all. */
+    pub fn is_unnamed(&self) -> bool {
+        let name = self.name.trim();
+        name.is_empty() || name == Self::UNNAMED
+    }
+
+    pub fn random() -> Faction {
+        let faction_info = TABLES.faction_table.roll_normal_2d6(0);
+
+        Faction {
+            name: String::from(Self::UNNAMED),
+            code: faction_info.code,
+            strength: faction_info.strength.clone(),
+            government: TABLES.gov_table.roll_normal_2d6(0).clone(),
+            religion: Religion::random(),
+        }
+    }
+}
+
+impl PartialEq for Faction {
+    fn eq(&self, other: &Self) -> bool {
+        // We ignore the `code` field because it's lost during serialization
+        self.name == other.name
+            && self.strength == other.strength
+            && self.government == other.government
+            && self.religion == other.religion
+    }
+}
+
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum TravelCode {
+    Safe,
+    Amber,
+    Red,
+}
+
+impl TravelCode {
+    pub fn as_short_string(&self) -> String {
+        match self {
+            TravelCode::Safe => "-".to_string(),
+            TravelCode::Amber => "A".to_string(),
+            TravelCode::Red => "R".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum TradeCode {
+    /// Agricultural
+    Ag,
+    /// Asteroid
+    As,
+    /// Barren
+    Ba,
+    /// Desert
+    De,
+    /// Fluid Oceans
+    Fl,
+    /// Garden
+    Ga,
+    /// High Population
+    Hi,
+    /// High Tech
+    Ht,
+    /// Ice-Capped
+    Ic,
+    /// Industrial
+    In,
+    /// Low Population
+    Lo,
+    /// Low Tech
+    Lt,
+    /// Non-Agricultural
+    Na,
+    /// Non-Industrial
+    Ni,
+    /// Poor
+    Po,
+    /// Rich
+    Ri,
+    /// Vacuum
+    Va,
+    /// Water World
+    Wa,
+}
+
+impl TradeCode {
+    /** Get the full name of the trade code from the trade code table, e.g. "Agricultural". */
+    fn to_long_str(&self) -> String {
+        TABLES
+            .trade_code_table
+            .iter()
+            .find(|record| &record.trade_code == self)
+            .map(|record| record.name.clone())
+            .unwrap_or_else(|| format!("{:?}", self))
+    }
+}
+
+/** An image attached to a `World`, such as planet art or a map. */
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum WorldImage {
+    /// Path to an image file; relative paths are relative to the directory of the subsector file
+    Linked(PathBuf),
+    /// Base64 encoded contents of an image file
+    Embedded(String),
+}
+
+impl WorldImage {
+    /** Read the image file at `path` and embed its contents. */
+    pub fn embed<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+        let bytes = std::fs::read(path)?;
+        Ok(Self::from_bytes(&bytes))
+    }
+
+    /** Embed the contents of an image file that has already been read. */
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        Self::Embedded(base64::encode(bytes))
+    }
+
+    /** Link to the image file at `path`, relative to `directory` if `path` is inside of it. */
+    pub fn link(path: &Path, directory: &Path) -> Self {
+        let path = path.strip_prefix(directory).unwrap_or(path);
+        Self::Linked(path.to_path_buf())
+    }
+
+    /** Get the raw bytes of the image file, resolving relative linked paths against `directory`. */
+    pub fn load_bytes<P: AsRef<Path>>(&self, directory: P) -> Result<Vec<u8>, Box<dyn Error>> {
+        match self {
+            Self::Linked(path) => Ok(std::fs::read(directory.as_ref().join(path))?),
+            Self::Embedded(data) => Ok(base64::decode(data)?),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, Serialize)]
+pub struct World {
+    pub name: String,
+    /// Phonetic spelling of `name`, e.g. "REH-jee-nuh"; empty if none has been made
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub pronunciation: String,
+    pub gas_giants: i32,
+    pub size: u16,
+    pub diameter: u32,
+    pub atmosphere: AtmoRecord,
+    pub temperature: TempRecord,
+    pub hydrographics: HydroRecord,
+    pub population: PopRecord,
+    pub government: GovRecord,
+    pub law_level: LawRecord,
+    /// Law level of each category of goods and activity; `None` if they haven't been rolled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub law_categories: Option<LawCategories>,
+    pub factions: Vec<Faction>,
+    pub culture: CulturalDiffRecord,
+    /// Dress, taboos, greeting customs, and cuisine of the `World`'s culture
+    #[serde(default, skip_serializing_if = "CulturalDetails::is_empty")]
+    pub cultural_details: CulturalDetails,
+    /// Organization, scope, and attitude toward outsiders of the `World`'s dominant religion
+    #[serde(default, skip_serializing_if = "Religion::is_empty")]
+    pub religion: Religion,
+    pub world_tags: [WorldTagRecord; Self::NUM_TAGS],
+    pub starport: StarportRecord,
+    /// Prices and availability of fuel at the starport; `None` if they haven't been rolled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fuel_market: Option<FuelMarket>,
+    pub tech_level: TechLevelRecord,
+    pub has_naval_base: bool,
+    pub has_scout_base: bool,
+    pub has_research_base: bool,
+    pub has_tas: bool,
+    pub has_pirate_base: bool,
+    pub travel_code: TravelCode,
+    /// Whether `travel_code` was set by hand instead of being kept in line with the world's data
+    #[serde(default)]
+    pub travel_code_overridden: bool,
+    /// Short prose warning to visitors, e.g. "Amber Zone: customs inspections common under law
+    /// level 9"; empty if none has been written
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub travel_advisory: String,
+    pub trade_codes: BTreeSet<TradeCode>,
+    #[serde(default)]
+    pub trade_goods: Vec<TradeGood>,
+    /// Notes the players may see, which are kept when the `World` is made player-safe
+    #[serde(default)]
+    pub player_notes: String,
+    /// Notes only the GM may see; saved as just `notes` before there were player notes
+    #[serde(default, alias = "notes")]
+    pub secret_notes: String,
+
+    pub planetoid_belts: Option<i32>,
+    pub image: Option<WorldImage>,
+    #[serde(default)]
+    pub rule_set: RuleSet,
+    #[serde(default)]
+    pub extension_rolls: ExtensionRolls,
+    /// Changes applied to the `World` in the order they were applied
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<WorldChange>,
+    /// Code of the `Polity` the `World` belongs to; empty if it is non-aligned
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub allegiance: String,
+    /// Location of the `World` that holds this one as a colony or possession, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<Point>,
+    /// Non-human species living on the `World`; humans make up the rest of the population
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sophonts: Vec<Sophont>,
+    /// Random encounter table for the space around the `World`, rolled on with 1D
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub encounters: Vec<Encounter>,
+    /// T5 stellar data of the `World`'s system, e.g. "G2 V M5 V"
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub stellar: String,
+    /// T5 nobility codes of the nobles resident on the `World`, e.g. "BCf"; empty if it has none
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub nobility: String,
+    /// Stellar and orbital hazards of the `World`'s system; most systems have none
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hazards: Vec<Hazard>,
+    /// Installations in orbit around the `World`, like highports, shipyards, and naval stations
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub orbital_facilities: Vec<Facility>,
+    /// Role of the `World` in its system, which limits how much of it is generated
+    #[serde(default, skip_serializing_if = "WorldSubtype::is_mainworld")]
+    pub subtype: WorldSubtype,
+    /// Dice rolled to generate the `World`, in the order they were rolled; empty unless rolls were
+    /// being logged when it was generated
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub roll_log: Vec<RollLogEntry>,
+}
+
+impl World {
+    pub const SIZE_MIN: u16 = 0;
+    pub const SIZE_MAX: u16 = 10;
+    pub const NUM_TAGS: usize = 2;
+
+    /** Add a randomized faction and return its index. */
+    pub fn add_faction(&mut self) -> usize {
+        self.factions.push(Faction::random());
+        self.factions.len() - 1
+    }
+
+    pub fn base_str(&self) -> String {
+        let mut bases = Vec::new();
+        if self.has_naval_base {
+            bases.push(String::from("N"));
+        }
+        if self.has_research_base {
+            bases.push(String::from("R"));
+        }
+        if self.has_scout_base {
+            bases.push(String::from("S"));
+        }
+        if self.has_tas {
+            bases.push(String::from("T"));
+        }
+        if self.has_pirate_base {
+            bases.push(String::from("P"));
+        }
+        let s = bases.join("");
+
+        if !s.is_empty() {
+            s
+        } else {
+            "-".to_string()
+        }
+    }
+
+    pub fn empty() -> Self {
+        World {
+            name: String::from(""),
+            pronunciation: String::new(),
+            gas_giants: 0,
+            size: 0,
+            diameter: 0,
+            atmosphere: TABLES.atmo_table[0].clone(),
+            temperature: TABLES.temp_table[0].clone(),
+            hydrographics: TABLES.hydro_table[0].clone(),
+            population: TABLES.pop_table[0].clone(),
+            government: TABLES.gov_table[0].clone(),
+            factions: Vec::new(),
+            culture: TABLES.culture_table[0].clone(),
+            cultural_details: CulturalDetails::default(),
+            religion: Religion::default(),
+            world_tags: [
+                TABLES.world_tag_table[0].clone(),
+                TABLES.world_tag_table[0].clone(),
+            ],
+            law_level: TABLES.law_table[0].clone(),
+            law_categories: None,
+            starport: TABLES.starport_table[0].clone(),
+            fuel_market: None,
+            tech_level: TABLES.tech_level_table[0].clone(),
+            has_naval_base: false,
+            has_scout_base: false,
+            has_research_base: false,
+            has_tas: false,
+            has_pirate_base: false,
+            travel_code: TravelCode::Safe,
+            travel_code_overridden: false,
+            travel_advisory: String::new(),
+            trade_codes: BTreeSet::new(),
+            trade_goods: Vec::new(),
+            player_notes: String::new(),
+            secret_notes: String::new(),
+            planetoid_belts: Some(0),
+            image: None,
+            rule_set: RuleSet::default(),
+            extension_rolls: ExtensionRolls::default(),
+            history: Vec::new(),
+            allegiance: String::new(),
+            owner: None,
+            sophonts: Vec::new(),
+            stellar: String::new(),
+            nobility: String::new(),
+            hazards: Vec::new(),
+            orbital_facilities: Vec::new(),
+            encounters: Vec::new(),
+            subtype: WorldSubtype::default(),
+            roll_log: Vec::new(),
+        }
+    }
+
+    pub fn generate_atmosphere(&mut self) {
+        if self.size > 0 {
+            let modifier = self.size as i32 - 7;
+            self.atmosphere = TABLES.atmo_table.roll_normal_2d6(modifier).clone();
+        } else {
+            self.atmosphere = TABLES.atmo_table[0].clone();
+        }
+    }
+
+    fn generate_bases(&mut self) {
+        let naval_target;
+        let scout_target;
+        let research_target;
+        let tas_target;
+        let pirate_target = 12;
+        match self.starport.class {
+            StarportClass::A => {
+                naval_target = 8;
+                scout_target = 10;
+                research_target = 8;
+                tas_target = 0; // Guaranteed
+            }
+
+            StarportClass::B => {
+                naval_target = 8;
+                scout_target = 9;
+                research_target = 10;
+                tas_target = 0; // Guaranteed
+            }
+
+            StarportClass::C => {
+                naval_target = i32::MAX; // Impossible
+                scout_target = 8;
+                research_target = 10;
+                tas_target = 10;
+            }
+
+            StarportClass::D => {
+                naval_target = i32::MAX; // Impossible
+                scout_target = 7;
+                research_target = i32::MAX; // Impossible
+                tas_target = i32::MAX; // Impossible
+            }
+
+            _ => {
+                naval_target = i32::MAX; // Impossible
+                scout_target = i32::MAX; // Impossible
+                research_target = i32::MAX; // Impossible
+                tas_target = i32::MAX; // Impossible
+            }
+        }
+
+        self.has_naval_base = dice::roll_2d(6) >= naval_target;
+        self.has_scout_base = dice::roll_2d(6) >= scout_target;
+        self.has_research_base = dice::roll_2d(6) >= research_target;
+        self.has_tas = dice::roll_2d(6) >= tas_target;
+        self.has_pirate_base = !self.has_naval_base
+            && self.starport.class != StarportClass::A
+            && dice::roll_2d(6) >= pirate_target;
+        self.has_research_base |= self.subtype.has_research_base();
+    }
+
+    pub fn generate_berthing_cost(&mut self) {
+        let index = self.starport.code as usize;
+        self.starport.berthing_cost = dice::roll_1d(6) * TABLES.starport_table[index].berthing_cost;
+    }
+
+    pub fn generate_culture(&mut self) {
+        self.culture = if self.subtype.has_society() {
+            TABLES.culture_table.roll_uniform().clone()
+        } else {
+            TABLES.culture_table[0].clone()
+        };
+    }
+
+    /** Roll a diameter in km that fits the `World`'s size. */
+    pub fn generate_diameter(&mut self) {
+        self.diameter = dice::roll_range(self.diameter_range());
+    }
+
+    fn generate_factions(&mut self) {
+        if self.population.code == 0 || !self.subtype.has_society() {
+            return;
+        }
+
+        let faction_count = dice::roll_1d(3)
+            + match self.government.code {
+                0 | 7 => 1,
+                10.. => -1,
+                _ => 0,
+            };
+
+        for _ in 0..faction_count {
+            self.factions.push(Faction::random());
+        }
+    }
+
+    pub fn generate_gas_giants(&mut self) {
+        self.gas_giants = match dice::roll_2d(6) {
+            5..=12 => (dice::roll_1d(6) - 2).clamp(1, i32::MAX),
+            _ => 0,
+        }
+    }
+
+    pub fn generate_government(&mut self) {
+        if self.population.code == 0 {
+            self.government = TABLES.gov_table[0].clone();
+            return;
+        }
+        let modifier = self.rule_set.rules().government_dm(self);
+        self.government = TABLES.gov_table.roll_normal_2d6(modifier).clone();
+    }
+
+    pub fn generate_hydrographics(&mut self) {
+        if self.size <= 1 {
+            self.hydrographics = TABLES.hydro_table[0].clone();
+            return;
+        }
+
+        let modifier = self.rule_set.rules().hydrographics_dm(self);
+        self.hydrographics = TABLES.hydro_table.roll_normal_2d6(modifier).clone();
+    }
+
+    /** Roll the law level, along with the law level of each category around it. */
+    pub fn generate_law_level(&mut self) {
+        if self.government.code == 0 {
+            self.law_level = TABLES.law_table[0].clone();
+        } else {
+            let modifier = self.government.code as i32 - 7;
+            self.law_level = TABLES.law_table.roll_normal_2d6(modifier).clone();
+        }
+        self.generate_law_categories();
+    }
+
+    pub fn generate_planetoid_belts(&mut self) {
+        let has_belts = dice::roll_2d(6) >= 4;
+        let world_is_planetoid = self.size == 0;
+
+        // If the world has a size of 0, it is itself a planetoid so there's at least one belt
+        self.planetoid_belts = if has_belts || world_is_planetoid {
+            Some((dice::roll_1d(6) - 3).clamp(1, i32::MAX))
+        } else {
+            Some(0)
+        };
+    }
+
+    /** Spell out how the `World`'s name is pronounced. */
+    pub fn generate_pronunciation(&mut self) {
+        self.pronunciation = phonetic_spelling(&self.name);
+    }
+
+    pub fn generate_population(&mut self) {
+        let modifier = self.rule_set.rules().population_dm(self);
+        self.population = TABLES.pop_table.roll_normal_2d6(modifier).clone();
+
+        let max_population = self.subtype.max_population() as usize;
+        if self.population.code as usize > max_population {
+            self.population = TABLES.pop_table[max_population].clone();
+        }
+    }
+
+    pub fn generate_size(&mut self) {
+        self.size = (dice::roll_2d(6) - 2).clamp(Self::SIZE_MIN, Self::SIZE_MAX);
+        self.generate_diameter();
+    }
+
+    pub fn generate_starport(&mut self) {
+        let modifier = self.rule_set.rules().starport_dm(self);
+        self.starport = TABLES.starport_table.roll_normal_2d6(modifier).clone();
+        self.generate_berthing_cost();
+    }
+
+    pub fn generate_tech_level(&mut self) {
+        let modifier = self.tech_level_modifier();
+        self.tech_level = TABLES.tech_level_table.roll_1d6(modifier).clone();
+    }
+
+    pub fn generate_temperature(&mut self) {
+        let modifier: i32 = match self.atmosphere.code {
+            0 | 1 => 0,
+            2 | 3 => -2,
+            4 | 5 | 14 => -1,
+            6 | 7 => 0,
+            8 | 9 => 1,
+            10 | 13 | 15 => 2,
+            11 | 12 => 6,
+            _ => unreachable!("The atmosphere should always be in the range 0..=12"),
+        };
+        self.temperature = TABLES.temp_table.roll_normal_2d6(modifier).clone();
+    }
+
+    /** Mutate the world tag at `index` to a random one on the `world_tag_table`.
+
+    Currently each world only has two world tags, so the only valid indices are `0` and `1`.
+
+    # Returns
+    - `Some(world_tag)` with the old, displaced world tag if `index` is valid, or
+    - `None` otherwise
+    */
+    pub fn generate_world_tag(&mut self, index: usize) -> Option<WorldTagRecord> {
+        match self.world_tags.get_mut(index) {
+            Some(world_tag) => {
+                let old_tag = world_tag.clone();
+                *world_tag = TABLES.world_tag_table.roll_uniform().clone();
+                Some(old_tag)
+            }
+            None => None,
+        }
+    }
+
+    /** Regenerate all of the world's world tags. */
+    fn generate_world_tags(&mut self) {
+        for index in 0..self.world_tags.len() {
+            self.generate_world_tag(index);
+        }
+    }
+
+    pub fn gravity(&mut self) -> &str {
+        match self.size {
+            0 => "N/A",
+            1 => "0.05 G",
+            2 => "0.15 G",
+            3 => "0.25 G",
+            4 => "0.35 G",
+            5 => "0.45 G",
+            6 => "0.70 G",
+            7 => "0.90 G",
+            8 => "1.00 G",
+            9 => "1.25 G",
+            10 => "1.40 G",
+            _ => unreachable!("The size should always be in the range 0..=10"),
+        }
+    }
+
+    pub fn has_gas_giant(&self) -> bool {
+        self.gas_giants > 0
+    }
+
+    /** Get the T5 importance of the `World`, which is also used by its other extensions. */
+    pub fn importance(&self) -> i32 {
+        let mut importance = 0;
+        importance += match self.starport.class {
+            StarportClass::A | StarportClass::B => 1,
+            StarportClass::D | StarportClass::E | StarportClass::X => -1,
+            _ => 0,
+        };
+
+        if self.tech_level.code >= 16 {
+            importance += 1;
+        }
+        if self.tech_level.code >= 10 {
+            importance += 1;
+        }
+        if self.tech_level.code <= 8 {
+            importance -= 1;
+        }
+
+        const IMPORTANT_TRADE_CODES: [TradeCode; 4] =
+            [TradeCode::Ag, TradeCode::Hi, TradeCode::In, TradeCode::Ri];
+        for trade_code in IMPORTANT_TRADE_CODES {
+            if self.trade_codes.contains(&trade_code) {
+                importance += 1;
+            }
+        }
+
+        if self.population.code <= 6 {
+            importance -= 1;
+        }
+
+        if self.has_naval_base && self.has_scout_base {
+            importance += 1;
+        }
+
+        importance
+    }
+
+    pub fn importance_extension(&self) -> String {
+        format!("{{ {} }}", self.importance())
+    }
+
+    pub fn is_wet_world(&self) -> bool {
+        self.hydrographics.code > 3
+    }
+
+    /** Attempts to mutate the `World` into a "player-safe" state.
+
+    To do so, it defaults all of the fields that are likely to have spoilers to the zeroth index of
+    their respective roll tables or completely blanks them where possible.
+    These likely fields are:
+
+    1. Factions
+    2. Culture and its dress, taboos, greetings, cuisine, and religion
+    3. World Tags
+    4. Secret notes
+    5. History
+    6. Travel advisory, which may name the factions
+
+    This is intended to work alongside a player-safe version of the GUI that has the defaulted
+    fields removed; this is more to prevent overly-clever players from mining the JSON for spoilers.
+    */
+    pub fn make_player_safe(&mut self) {
+        self.factions.clear();
+        self.culture = TABLES.culture_table[0].clone();
+        self.cultural_details = CulturalDetails::default();
+        self.religion = Religion::default();
+        for world_tag in self.world_tags.iter_mut() {
+            *world_tag = TABLES.world_tag_table[0].clone();
+        }
+        self.secret_notes = String::new();
+        self.travel_advisory = String::new();
+        self.history.clear();
+        self.encounters.clear();
+        self.hazards.clear();
+        self.roll_log.clear();
+    }
+
+    /** Create a randomized `World` named `name` using the default [`RuleSet`]. */
+    pub fn new(name: String) -> Self {
+        Self::with_rule_set(name, RuleSet::default())
+    }
+
+    /** Create a randomized `World` named `name` using the rules of `rule_set`. */
+    pub fn with_rule_set(name: String, rule_set: RuleSet) -> Self {
+        let mut world = Self::empty();
+        world.name = name;
+        world.generate_pronunciation();
+        world.rule_set = rule_set;
+
+        // Generation *must* happen in this order, many fields depend on the value
+        // of other fields when making their rolls
+        world.generate_logged("Size", None, Self::generate_size);
+        let atmospheres = Some(TableKind::Atmospheres);
+        world.generate_logged("Atmosphere", atmospheres, Self::generate_atmosphere);
+        let temperatures = Some(TableKind::Temperatures);
+        world.generate_logged("Temperature", temperatures, Self::generate_temperature);
+        let hydrographics = Some(TableKind::Hydrographics);
+        world.generate_logged("Hydrographics", hydrographics, Self::generate_hydrographics);
+        let populations = Some(TableKind::Populations);
+        world.generate_logged("Population", populations, Self::generate_population);
+        let governments = Some(TableKind::Governments);
+        world.generate_logged("Government", governments, Self::generate_government);
+        let law_levels = Some(TableKind::LawLevels);
+        world.generate_logged("Law Level", law_levels, Self::generate_law_level);
+        world.generate_logged("Factions", None, Self::generate_factions);
+        let cultures = Some(TableKind::CulturalDifferences);
+        world.generate_logged("Culture", cultures, Self::generate_culture);
+        world.generate_logged("Cultural Details", None, Self::generate_cultural_details);
+        world.generate_logged("Religion", None, Self::generate_religion);
+        let world_tags = Some(TableKind::WorldTags);
+        world.generate_logged("World Tags", world_tags, Self::generate_world_tags);
+        let starports = Some(TableKind::Starports);
+        world.generate_logged("Starport", starports, Self::generate_starport);
+        let tech_levels = Some(TableKind::TechLevels);
+        world.generate_logged("Tech Level", tech_levels, Self::generate_tech_level);
+        world.generate_logged("Starport Details", None, Self::generate_starport_details);
+        world.generate_logged("Bases", None, Self::generate_bases);
+        world.generate_orbital_facilities();
+        world.resolve_travel_code();
+        world.resolve_trade_codes();
+        world.generate_logged("Fuel Market", None, Self::generate_fuel_market);
+        world.generate_logged("Planetoid Belts", None, Self::generate_planetoid_belts);
+        world.generate_logged("Gas Giants", None, Self::generate_gas_giants);
+        world.generate_logged("Trade Goods", None, Self::generate_trade_goods);
+        world.generate_logged("Encounters", None, Self::generate_encounters);
+        world.generate_logged("Stellar Data", None, Self::generate_stellar);
+        world.generate_logged("Hazards", None, Self::generate_hazards);
+        world.generate_logged("Extensions", None, |world| {
+            world.extension_rolls = ExtensionRolls::random()
+        });
+        world.generate_nobility();
+        world.generate_travel_advisory();
+
+        world
+    }
+
+    /** Resolve trade codes, ensure `Option` fields are not `None`, and recalculate extensions.
+
+    The travel code is also resolved again unless it has been overridden by hand. Worlds from files
+    saved before trade goods, encounter tables, T5 extensions, stellar data and hazards, starport
+    details, nobility, orbital facilities, cultural details, law categories, fuel markets,
+    religions, or travel advisories existed have them generated too.
+    */
+    pub fn normalize_data(&mut self) {
+        if self.planetoid_belts.is_none() {
+            self.generate_planetoid_belts();
+        }
+        if self.starport.details.is_none() {
+            self.generate_starport_details();
+        }
+        self.resolve_trade_codes();
+        if !self.travel_code_overridden {
+            self.resolve_travel_code();
+        }
+        if self.trade_goods.is_empty() {
+            self.generate_trade_goods();
+        }
+        if self.encounters.is_empty() {
+            self.generate_encounters();
+        }
+        if self.extension_rolls == ExtensionRolls::default() {
+            self.extension_rolls = ExtensionRolls::random();
+        }
+        if self.stellar.is_empty() {
+            self.generate_stellar();
+            self.generate_hazards();
+        }
+        if self.nobility.is_empty() {
+            self.generate_nobility();
+        }
+        if self.orbital_facilities.is_empty() {
+            self.generate_orbital_facilities();
+        }
+        if self.cultural_details.is_empty() {
+            self.generate_cultural_details();
+        }
+        if self.religion.is_empty() {
+            self.generate_religion();
+        }
+        if self.law_categories.is_none() {
+            self.generate_law_categories();
+        }
+        if self.fuel_market.is_none() {
+            self.generate_fuel_market();
+        }
+        if self.travel_advisory.is_empty() {
+            self.generate_travel_advisory();
+        }
+    }
+
+    /** Get the "Population Modifier/Belts/Gas Giants string" */
+    pub fn pbg_str(&self) -> String {
+        format!(
+            "1{}{}",
+            self.planetoid_belts
+                .expect("World planetoid belts should not be None"),
+            self.gas_giants
+        )
+    }
+
+    pub fn profile_str(&self) -> String {
+        format!(
+            "{starport:?}{size:X}{atmo:X}{hydro:X}{pop:X}{gov:X}{law:X}-{tech:X}",
+            starport = self.starport.class,
+            size = self.size,
+            atmo = self.atmosphere.code,
+            hydro = self.hydrographics.code,
+            pop = self.population.code,
+            gov = self.government.code,
+            law = self.law_level.code,
+            tech = self.tech_level.code,
+        )
+    }
+
+    /** Remove the [`Faction`] at `idx` and return the nearest valid index to `idx`.
+
+    Does nothing and returns 0 if `idx` is out of bounds.
+    */
+    pub fn remove_faction(&mut self, idx: usize) -> usize {
+        if idx >= self.factions.len() {
+            return 0;
+        }
+
+        self.factions.remove(idx);
+        if self.factions.is_empty() {
+            0
+        } else if idx >= self.factions.len() {
+            self.factions.len() - 1
+        } else {
+            idx
+        }
+    }
+
+    /** Change the `World`'s subtype, rerolling whatever exceeds the limits of the new one.
+
+    A population over the subtype's cap is rerolled along with the government, law level, and tech
+    level that depend on it. Subtypes without a society of their own lose their factions, culture,
+    cultural details, and religion.
+    */
+    pub fn set_subtype(&mut self, subtype: WorldSubtype) {
+        self.subtype = subtype;
+        if self.population.code > subtype.max_population() {
+            self.generate_population();
+            self.generate_government();
+            self.generate_law_level();
+            self.generate_tech_level();
+        }
+        if !subtype.has_society() {
+            self.factions.clear();
+            self.culture = TABLES.culture_table[0].clone();
+            self.cultural_details = CulturalDetails::default();
+            self.religion = Religion::default();
+        }
+        self.has_research_base |= subtype.has_research_base();
+
+        self.resolve_trade_codes();
+        if !self.travel_code_overridden {
+            self.resolve_travel_code();
+        }
+    }
+
+    pub fn resolve_trade_codes(&mut self) {
+        self.trade_codes.clear();
+
+        // Agricultural
+        if (4..=9).contains(&self.atmosphere.code)
+            && (4..=8).contains(&self.hydrographics.code)
+            && (5..=7).contains(&self.population.code)
+        {
+            self.trade_codes.insert(TradeCode::Ag);
+        }
+
+        // Asteroid
+        if self.size == 0 && self.atmosphere.code == 0 && self.hydrographics.code == 0 {
+            self.trade_codes.insert(TradeCode::As);
+        }
+
+        // Barren
+        if self.population.code == 0 && self.government.code == 0 && self.law_level.code == 0 {
+            self.trade_codes.insert(TradeCode::Ba);
+        }
+
+        // Desert
+        if self.atmosphere.code >= 2 && self.hydrographics.code == 0 {
+            self.trade_codes.insert(TradeCode::De);
+        }
+
+        // Fluid (non-water) oceans
+        if self.atmosphere.code >= 10 && self.hydrographics.code >= 1 {
+            self.trade_codes.insert(TradeCode::Fl);
+        }
+
+        // Garden
+        if [5, 6, 8].contains(&self.atmosphere.code)
+            && (4..=9).contains(&self.hydrographics.code)
+            && (4..=8).contains(&self.population.code)
+        {
+            self.trade_codes.insert(TradeCode::Ga);
+        }
+
+        // High population
+        if self.population.code >= 9 {
+            self.trade_codes.insert(TradeCode::Hi);
+        }
+
+        // High tech
+        if self.tech_level.code >= 12 {
+            self.trade_codes.insert(TradeCode::Ht);
+        }
+
+        // Ice-capped
+        if (0..=1).contains(&self.atmosphere.code) && self.hydrographics.code >= 1 {
+            self.trade_codes.insert(TradeCode::Ic);
+        }
+
+        // Industrial
+        if ((0..=2).contains(&self.atmosphere.code) || [4, 7, 9].contains(&self.atmosphere.code))
+            && self.population.code >= 9
+        {
+            self.trade_codes.insert(TradeCode::In);
+        }
+
+        // Low population
+        if self.population.code <= 3 {
+            self.trade_codes.insert(TradeCode::Lo);
+        }
+
+        // Low tech
+        if self.tech_level.code <= 5 {
+            self.trade_codes.insert(TradeCode::Lt);
+        }
+
+        // Non-agricultural
+        if (0..=3).contains(&self.atmosphere.code)
+            && (0..=3).contains(&self.hydrographics.code)
+            && self.population.code >= 6
+        {
+            self.trade_codes.insert(TradeCode::Na);
+        }
+
+        // Non-industrial
+        if (4..=6).contains(&self.population.code) {
+            self.trade_codes.insert(TradeCode::Ni);
+        }
+
+        // Poor
+        if (2..=5).contains(&self.atmosphere.code) && self.hydrographics.code <= 3 {
+            self.trade_codes.insert(TradeCode::Po);
+        }
+
+        // Rich
+        if [6, 8].contains(&self.atmosphere.code) && (6..=8).contains(&self.population.code) {
+            self.trade_codes.insert(TradeCode::Ri);
+        }
+
+        // Vacuum
+        if self.atmosphere.code == 0 {
+            self.trade_codes.insert(TradeCode::Va);
+        }
+
+        // Water world
+        if self.hydrographics.code >= 10 {
+            self.trade_codes.insert(TradeCode::Wa);
+        }
+    }
+
+    /** Mark the travel code as overridden if it differs from the one that would be resolved.
+
+    Used on worlds from files, which may have been saved before overrides were tracked, so that
+    their hand-picked travel codes, such as red zones, aren't replaced.
+    */
+    pub fn detect_travel_code_override(&mut self) {
+        let mut resolved = self.clone();
+        resolved.resolve_travel_code();
+        if resolved.travel_code != self.travel_code {
+            self.travel_code_overridden = true;
+        }
+    }
+
+    pub fn resolve_travel_code(&mut self) {
+        self.travel_code = TravelCode::Safe;
+
+        if self.atmosphere.code >= 10 {
+            self.travel_code = TravelCode::Amber
+        }
+
+        match self.government.code {
+            0 | 7 | 10 => self.travel_code = TravelCode::Amber,
+            _ => (),
+        }
+
+        match self.law_level.code {
+            0 => self.travel_code = TravelCode::Amber,
+            9.. => self.travel_code = TravelCode::Amber,
+            _ => (),
+        }
+    }
+
+    pub fn starport_tl_str(&self) -> String {
+        format!("{:?}-{}", self.starport.class, self.tech_level.code)
+    }
+
+    /** Get the total modifier applied to the 1d6 tech level roll from all other world data. */
+    pub fn tech_level_modifier(&self) -> i32 {
+        self.rule_set.rules().tech_level_dm(self)
+    }
+
+    pub fn trade_code_long_str(&self) -> String {
+        self.trade_codes
+            .iter()
+            .map(|code| code.to_long_str())
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    pub fn trade_code_str(&self) -> String {
+        let s = self
+            .trade_codes
+            .iter()
+            .map(|code| format!("{:?}", code))
+            .collect::<Vec<String>>()
+            .join(" ");
+        if !s.is_empty() {
+            s
+        } else {
+            "-".to_string()
+        }
+    }
+
+    pub fn travel_code_str(&self) -> String {
+        format!("{:?}", self.travel_code)
+    }
+}
+
+impl Default for World {
+    fn default() -> Self {
+        World::new("".to_string())
+    }
+}
+
+impl PartialEq for World {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.pronunciation == other.pronunciation
+            && self.gas_giants == other.gas_giants
+            && self.size == other.size
+            && self.diameter == other.diameter
+            && self.atmosphere == other.atmosphere
+            && self.temperature == other.temperature
+            && self.hydrographics == other.hydrographics
+            && self.population == other.population
+            && self.government == other.government
+            && self.law_level == other.law_level
+            && self.law_categories == other.law_categories
+            && self.factions == other.factions
+            && self.culture == other.culture
+            && self.cultural_details == other.cultural_details
+            && self.religion == other.religion
+            && self.world_tags == other.world_tags
+            && self.starport == other.starport
+            && self.fuel_market == other.fuel_market
+            && self.tech_level == other.tech_level
+            && self.has_naval_base == other.has_naval_base
+            && self.has_scout_base == other.has_scout_base
+            && self.has_research_base == other.has_research_base
+            && self.has_tas == other.has_tas
+            && self.travel_code == other.travel_code
+            && self.travel_code_overridden == other.travel_code_overridden
+            && self.travel_advisory == other.travel_advisory
+            && self.trade_codes == other.trade_codes
+            && self.trade_goods == other.trade_goods
+            && self.player_notes == other.player_notes
+            && self.secret_notes == other.secret_notes
+            && self.planetoid_belts == other.planetoid_belts
+            && self.image == other.image
+            && self.rule_set == other.rule_set
+            && self.extension_rolls == other.extension_rolls
+            && self.history == other.history
+            && self.allegiance == other.allegiance
+            && self.owner == other.owner
+            && self.sophonts == other.sophonts
+            && self.encounters == other.encounters
+            && self.stellar == other.stellar
+            && self.nobility == other.nobility
+            && self.hazards == other.hazards
+            && self.orbital_facilities == other.orbital_facilities
+            && self.subtype == other.subtype
+            && self.roll_log == other.roll_log
+    }
+}
+
+#[allow(dead_code)]
+pub fn histograms(n: usize) {
+    let mut gas_giant_hist = Histogram::with_domain("Gas Giant", 0..=4);
+    let mut size_hist = Histogram::with_domain("Size", 0..=10);
+    let mut atmo_hist =
+        Histogram::with_domain("Atmosphere", 0..=(TABLES.atmo_table.len() as u16 - 1));
+    let mut temp_hist =
+        Histogram::with_domain("Temperature", 0..=(TABLES.temp_table.len() as u16 - 1));
+    let mut hydro_hist =
+        Histogram::with_domain("Hydrographics", 0..=(TABLES.hydro_table.len() as u16 - 1));
+    let mut pop_hist =
+        Histogram::with_domain("Population", 0..=(TABLES.pop_table.len() as u16 - 1));
+    let mut gov_hist =
+        Histogram::with_domain("Government", 0..=(TABLES.gov_table.len() as u16 - 1));
+    let mut law_hist = Histogram::with_domain("Law Level", 0..=(TABLES.law_table.len() as u16 - 1));
+    let mut fac_strength_hist = Histogram::with_domain(
+        "Faction Strength",
+        2..=(TABLES.faction_table.len() as u16 - 1),
+    );
+    let mut fac_count_hist = Histogram::new("Faction Count");
+    let mut starport_hist = Histogram::new("Starport");
+    let mut tech_hist =
+        Histogram::with_domain("Tech Level", 0..=(TABLES.tech_level_table.len() as u16 - 1));
+    let mut trade_code_hist = Histogram::new("Trade Codes");
+    let mut culture_hist =
+        Histogram::with_domain("Cultures", 0..=(TABLES.culture_table.len() as u16 - 1));
+    let mut world_tag_hist =
+        Histogram::with_domain("World Tags", 0..=(TABLES.world_tag_table.len() as u16 - 1));
+
+    for _ in 0..n {
+        let world = World::new(String::from("0101"));
+
+        gas_giant_hist.inc(world.gas_giants);
+        size_hist.inc(world.size);
+        atmo_hist.inc(world.atmosphere.code);
+        temp_hist.inc(world.temperature.code);
+        hydro_hist.inc(world.hydrographics.code);
+        pop_hist.inc(world.population.code);
+        gov_hist.inc(world.government.code);
+        law_hist.inc(world.law_level.code);
+
+        for faction in &world.factions {
+            fac_strength_hist.inc(faction.code);
+        }
+        fac_count_hist.inc(world.factions.len());
+
+        starport_hist.inc(world.starport.class);
+        tech_hist.inc(world.tech_level.code);
+
+        for trade_code in world.trade_codes {
+            trade_code_hist.inc(trade_code);
+        }
+
+        culture_hist.inc(world.culture.code);
+
+        for world_tag in world.world_tags {
+            world_tag_hist.inc(world_tag.code);
+        }
+    }
+
+    gas_giant_hist.show_percent(n / 50);
+    size_hist.show_percent(n / 200);
+    atmo_hist.show_percent(n / 200);
+    temp_hist.show_percent(n / 200);
+    hydro_hist.show_percent(n / 200);
+    pop_hist.show_percent(n / 200);
+    gov_hist.show_percent(n / 200);
+    law_hist.show_percent(n / 200);
+    fac_strength_hist.show_percent(n / 200);
+    fac_count_hist.show_percent(n / 200);
+    starport_hist.show_percent(n / 200);
+    tech_hist.show_percent(n / 200);
+
+    // Percent doesn't work well for these
+    trade_code_hist.show(n / 100);
+    culture_hist.show(n / 500);
+    world_tag_hist.show(n / 1000);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn world_subtypes() {
+        for _ in 0..50 {
+            let mut outpost = World::new("Outpost".to_string());
+            outpost.set_subtype(WorldSubtype::Outpost);
+            assert!(outpost.population.code <= 4);
+            assert!(outpost.factions.is_empty());
+            for _ in 0..5 {
+                outpost.generate_population();
+                outpost.generate_factions();
+                assert!(outpost.population.code <= 4);
+                assert!(outpost.factions.is_empty());
+            }
+
+            let mut station = World::new("Station".to_string());
+            station.set_subtype(WorldSubtype::ResearchStation);
+            assert!(station.population.code <= 3);
+            assert!(station.has_research_base);
+            station.generate_bases();
+            assert!(station.has_research_base);
+        }
+
+        // Lowering the population limit of a world within it leaves its population alone
+        let mut colony = World::empty();
+        colony.population = TABLES.pop_table[5].clone();
+        colony.set_subtype(WorldSubtype::Colony);
+        assert_eq!(colony.population.code, 5);
+
+        // Mainworlds are left out of saved files, so older files load as mainworlds
+        let json = serde_json::to_string(&colony).unwrap();
+        assert!(json.contains("\"subtype\":\"Colony\""));
+        colony.subtype = WorldSubtype::Mainworld;
+        let json = serde_json::to_string(&colony).unwrap();
+        assert!(!json.contains("subtype"));
+        let loaded: World = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.subtype, WorldSubtype::Mainworld);
+    }
+
+    #[test]
+    fn player_and_secret_notes() {
+        let mut world = World::empty();
+        world.player_notes = "Famous for its floating markets".to_string();
+        world.secret_notes = "The markets launder pirate loot".to_string();
+
+        // Files saved before player notes were added load their notes as secret notes
+        let json = serde_json::to_string(&world)
+            .unwrap()
+            .replace("\"player_notes\":\"Famous for its floating markets\",", "")
+            .replace("secret_notes", "notes");
+        let loaded: World = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.player_notes, "");
+        assert_eq!(loaded.secret_notes, world.secret_notes);
+
+        world.make_player_safe();
+        assert_eq!(world.player_notes, "Famous for its floating markets");
+        assert!(world.secret_notes.is_empty());
+    }
+
+    #[test]
+    fn travel_code_audit() {
+        let mut world = World::empty();
+        world.government = TABLES.gov_table[4].clone();
+        world.law_level = TABLES.law_table[4].clone();
+        world.normalize_data();
+        assert_eq!(world.travel_code, TravelCode::Safe);
+
+        // Edits to the world's data are reflected in the travel code
+        world.law_level = TABLES.law_table[9].clone();
+        world.normalize_data();
+        assert_eq!(world.travel_code, TravelCode::Amber);
+
+        // Unless it has been overridden by hand
+        world.travel_code = TravelCode::Red;
+        world.travel_code_overridden = true;
+        world.law_level = TABLES.law_table[4].clone();
+        world.normalize_data();
+        assert_eq!(world.travel_code, TravelCode::Red);
+
+        // Worlds from files keep hand-picked travel codes
+        let mut loaded = world.clone();
+        loaded.travel_code_overridden = false;
+        loaded.detect_travel_code_override();
+        assert!(loaded.travel_code_overridden);
+
+        world.travel_code = TravelCode::Safe;
+        world.travel_code_overridden = false;
+        world.detect_travel_code_override();
+        assert!(!world.travel_code_overridden);
+    }
+
+    // TODO: this, and other statistical analysis functions, should probably be moved into a
+    // separate bin or something at some point
+    #[allow(dead_code)]
+    fn show_histograms() {
+        histograms(100_000);
+        // Purposefully fail get cargo test to show stdout and to make sure this doesn't get
+        // commited as a test
+        panic!();
+    }
+}
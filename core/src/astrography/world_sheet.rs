@@ -0,0 +1,506 @@
+use std::fmt;
+
+use crate::astrography::{
+    thumbnail::Thumbnail, NumberFormat, Point, SummaryFormat, World, WorldImage,
+};
+
+/// Most characters on one line of an SVG or PDF sheet before it is wrapped
+const SHEET_LINE_WIDTH: usize = 90;
+/// Most lines on one page of a PDF sheet
+const PDF_LINES_PER_PAGE: usize = 54;
+/// Left edge of the surface thumbnail on an SVG or PDF sheet, in points
+const SHEET_THUMBNAIL_X: usize = 476;
+/// Top edge of the surface thumbnail on an SVG or PDF sheet, in points from the top of the page
+const SHEET_THUMBNAIL_TOP: usize = 24;
+
+/** File format of an exported `World` data sheet. */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SheetFormat {
+    Markdown,
+    Svg,
+    Pdf,
+}
+
+impl SheetFormat {
+    pub const ALL_VALUES: [SheetFormat; 3] = [Self::Markdown, Self::Svg, Self::Pdf];
+
+    /** The file extension of the format. */
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Svg => "svg",
+            Self::Pdf => "pdf",
+        }
+    }
+}
+
+impl fmt::Display for SheetFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Markdown => "Markdown",
+            Self::Svg => "SVG",
+            Self::Pdf => "PDF",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl World {
+    /** Produce a data sheet of the `World` at `point` in the given `format`, writing numbers with
+    `numbers`.
+
+    The sheet is meant for the referee, so it ends with the `World`'s attached image, its trade
+    goods and encounter tables, and the adventure hooks among `hooks` that involve it. If
+    `thumbnail` is set, a picture of the `World`'s surface is included beside the heading.
+    */
+    pub fn sheet(
+        &self,
+        point: &Point,
+        format: SheetFormat,
+        numbers: &NumberFormat,
+        thumbnail: bool,
+        hooks: &[String],
+    ) -> Vec<u8> {
+        let thumbnail = thumbnail.then(|| self.thumbnail());
+        match format {
+            SheetFormat::Markdown => {
+                let mut summary = self.summary(point, SummaryFormat::Markdown, numbers);
+                if thumbnail.is_some() {
+                    // Embedded as a data URI so the sheet is still a single file
+                    let image = format!(
+                        "\n\n![Surface of {}](data:image/svg+xml;base64,{})",
+                        self.name,
+                        base64::encode(self.thumbnail_svg())
+                    );
+                    let heading_end = summary.find('\n').unwrap_or(summary.len());
+                    summary.insert_str(heading_end, &image);
+                }
+                summary.push_str(&self.image_sheet_section(true));
+                summary.push_str(&self.trade_goods_sheet_section(true));
+                summary.push_str(&self.encounter_sheet_section(true));
+                summary.push_str(&self.hook_sheet_section(point, hooks, true));
+                summary.into_bytes()
+            }
+            SheetFormat::Svg => {
+                svg_sheet(&self.sheet_lines(point, numbers, hooks), thumbnail.as_ref()).into_bytes()
+            }
+            SheetFormat::Pdf => {
+                pdf_sheet(&self.sheet_lines(point, numbers, hooks), thumbnail.as_ref())
+            }
+        }
+    }
+
+    /** Name of the file a data sheet of the `World` at `point` is saved as, e.g. "0101 Regina.md".
+
+    Characters that aren't allowed in file names on some platforms are replaced with underscores.
+    */
+    pub fn sheet_file_name(&self, point: &Point, format: SheetFormat) -> String {
+        let name: String = self
+            .name
+            .chars()
+            .map(|c| match c {
+                '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+                c if c.is_control() => '_',
+                c => c,
+            })
+            .collect();
+        format!("{} {}.{}", point, name.trim(), format.extension())
+    }
+
+    /** Get the lines of text on the SVG and PDF sheets, wrapped to `SHEET_LINE_WIDTH`. */
+    fn sheet_lines(&self, point: &Point, numbers: &NumberFormat, hooks: &[String]) -> Vec<String> {
+        let mut text = self.summary(point, SummaryFormat::PlainText, numbers);
+        text.push_str(&self.image_sheet_section(false));
+        text.push_str(&self.trade_goods_sheet_section(false));
+        text.push_str(&self.encounter_sheet_section(false));
+        text.push_str(&self.hook_sheet_section(point, hooks, false));
+        text.lines()
+            .flat_map(|line| wrap_line(line, SHEET_LINE_WIDTH))
+            .collect()
+    }
+
+    /** Get the image attached to the `World` as a section of its data sheet, or an empty string if
+    there isn't one.
+
+    Markdown sheets show embedded images inline as data URIs and linked ones by their path, while
+    SVG and PDF sheets only say where the image can be found.
+    */
+    fn image_sheet_section(&self, markdown: bool) -> String {
+        let image = match &self.image {
+            Some(image) => image,
+            None => return String::new(),
+        };
+
+        let mut lines = sheet_section_lines("Image", markdown);
+        if markdown {
+            let target = match image {
+                WorldImage::Linked(path) => format!("<{}>", path.display()),
+                WorldImage::Embedded(data) => {
+                    format!("data:{};base64,{}", image_media_type(data), data)
+                }
+            };
+            lines.push(format!("![Image of {}]({})", self.name, target));
+        } else {
+            lines.push(match image {
+                WorldImage::Linked(path) => path.display().to_string(),
+                WorldImage::Embedded(_) => "Embedded in the subsector file".to_string(),
+            });
+        }
+        lines.join("\n")
+    }
+}
+
+/** Start the lines of a section of a data sheet with its `heading`, set apart from the end of the
+previous section by a blank line.
+*/
+pub(super) fn sheet_section_lines(heading: &str, markdown: bool) -> Vec<String> {
+    let mut lines = vec![String::new(), String::new()];
+    if markdown {
+        lines.push(format!("#### {}", heading));
+        lines.push(String::new());
+    } else {
+        lines.push(heading.to_string());
+    }
+    lines
+}
+
+/** Guess the media type of an image from the start of its base64 encoded contents `data`. */
+fn image_media_type(data: &str) -> &'static str {
+    let header = base64::decode(data.get(..16).unwrap_or(data)).unwrap_or_default();
+    if header.starts_with(b"\x89PNG") {
+        "image/png"
+    } else if header.starts_with(&[0xFF, 0xD8]) {
+        "image/jpeg"
+    } else if header.starts_with(b"GIF8") {
+        "image/gif"
+    } else if header.starts_with(b"RIFF") && header.get(8..12) == Some(&b"WEBP"[..]) {
+        "image/webp"
+    } else if header.starts_with(b"BM") {
+        "image/bmp"
+    } else {
+        "image/svg+xml"
+    }
+}
+
+/** Split `line` into lines no longer than `width` characters, breaking at spaces where possible. */
+fn wrap_line(line: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in line.split(' ') {
+        let mut word: Vec<char> = word.chars().collect();
+        // Words that don't fit on a line of their own are split wherever they run out of room
+        while word.len() > width {
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+            }
+            lines.push(word.drain(..width).collect());
+        }
+        let word: String = word.into_iter().collect();
+
+        if current.is_empty() {
+            current = word;
+        } else if current.chars().count() + 1 + word.chars().count() <= width {
+            current.push(' ');
+            current.push_str(&word);
+        } else {
+            lines.push(std::mem::replace(&mut current, word));
+        }
+    }
+    lines.push(current);
+    lines
+}
+
+/** Lay out `lines` on a letter-sized SVG page, with the first line as a heading and `thumbnail`, if
+any, in the top right corner.
+*/
+fn svg_sheet(lines: &[String], thumbnail: Option<&Thumbnail>) -> String {
+    const LINE_HEIGHT: usize = 14;
+    let height = (lines.len() + 4) * LINE_HEIGHT;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"612\" height=\"{}\" \
+         viewBox=\"0 0 612 {}\">\n<rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n",
+        height.max(792),
+        height.max(792)
+    );
+    if let Some(thumbnail) = thumbnail {
+        svg += &format!(
+            "<g transform=\"translate({} {})\">\n{}</g>\n",
+            SHEET_THUMBNAIL_X,
+            SHEET_THUMBNAIL_TOP,
+            thumbnail.svg_elements()
+        );
+    }
+    for (index, line) in lines.iter().enumerate() {
+        let (size, weight) = if index == 0 {
+            (16, "bold")
+        } else {
+            (11, "normal")
+        };
+        svg += &format!(
+            "<text x=\"36\" y=\"{}\" font-family=\"sans-serif\" font-size=\"{}\" \
+             font-weight=\"{}\" xml:space=\"preserve\">{}</text>\n",
+            (index + 3) * LINE_HEIGHT,
+            size,
+            weight,
+            xml_escape(line)
+        );
+    }
+    svg += "</svg>\n";
+    svg
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/** Lay out `lines` on as many letter-sized PDF pages as needed, with the first line as a heading and
+`thumbnail`, if any, in the top right corner of the first page.
+
+The built-in Helvetica font is used, so characters outside of Latin-1 are replaced with `?`.
+*/
+fn pdf_sheet(lines: &[String], thumbnail: Option<&Thumbnail>) -> Vec<u8> {
+    let pages: Vec<&[String]> = lines.chunks(PDF_LINES_PER_PAGE).collect();
+
+    // Objects are numbered from 1: the catalog, the page tree, the fonts, then a page and its
+    // content stream for every page
+    let page_id = |index: usize| 5 + 2 * index;
+    let mut objects: Vec<Vec<u8>> = vec![
+        b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            (0..pages.len())
+                .map(|index| format!("{} 0 R", page_id(index)))
+                .collect::<Vec<_>>()
+                .join(" "),
+            pages.len()
+        )
+        .into_bytes(),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_vec(),
+        b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+            .to_vec(),
+    ];
+
+    for (page_index, page_lines) in pages.iter().enumerate() {
+        let mut content = Vec::new();
+        if let (0, Some(thumbnail)) = (page_index, thumbnail) {
+            // Flip the y axis so the thumbnail can be drawn in its own coordinates
+            content.extend(
+                format!(
+                    "q\n1 0 0 -1 {} {} cm\n{}Q\n",
+                    SHEET_THUMBNAIL_X,
+                    792 - SHEET_THUMBNAIL_TOP,
+                    thumbnail.pdf_operators()
+                )
+                .into_bytes(),
+            );
+        }
+        content.extend_from_slice(b"BT\n/F1 11 Tf\n14 TL\n36 750 Td\n");
+        for (line_index, line) in page_lines.iter().enumerate() {
+            let is_heading = page_index == 0 && line_index == 0;
+            if is_heading {
+                content.extend_from_slice(b"/F2 16 Tf\n");
+            }
+            content.push(b'(');
+            content.extend(pdf_string(line));
+            content.extend_from_slice(b") Tj T*\n");
+            if is_heading {
+                content.extend_from_slice(b"/F1 11 Tf\n");
+            }
+        }
+        content.extend_from_slice(b"ET");
+
+        objects.push(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] \
+                 /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                page_id(page_index) + 1
+            )
+            .into_bytes(),
+        );
+        let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+        stream.extend(content);
+        stream.extend_from_slice(b"\nendstream");
+        objects.push(stream);
+    }
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::new();
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend(format!("{} 0 obj\n", index + 1).into_bytes());
+        pdf.extend_from_slice(object);
+        pdf.extend_from_slice(b"\nendobj\n");
+    }
+
+    let xref_offset = pdf.len();
+    pdf.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).into_bytes());
+    for offset in offsets {
+        pdf.extend(format!("{:010} 00000 n \n", offset).into_bytes());
+    }
+    pdf.extend(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref_offset
+        )
+        .into_bytes(),
+    );
+    pdf
+}
+
+/** Encode `text` for a PDF string literal in the WinAnsi encoding, escaping special characters. */
+fn pdf_string(text: &str) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                bytes.push(b'\\');
+                bytes.push(c as u8);
+            }
+            c if (c as u32) < 0x20 => bytes.push(b' '),
+            c if (c as u32) < 0x100 => bytes.push(c as u8),
+            _ => bytes.push(b'?'),
+        }
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn world_sheets() {
+        let mut world = World::empty();
+        world.name = "Regina: (Capital)".to_string();
+        world.secret_notes = "A long note. ".repeat(300);
+        world.generate_encounters();
+        let point = Point { x: 1, y: 10 };
+        let numbers = NumberFormat::default();
+
+        assert_eq!(
+            world.sheet_file_name(&point, SheetFormat::Pdf),
+            "0110 Regina_ (Capital).pdf"
+        );
+
+        let markdown =
+            String::from_utf8(world.sheet(&point, SheetFormat::Markdown, &numbers, false, &[]))
+                .unwrap();
+        assert!(markdown.starts_with("### Regina: (Capital) (0110)"));
+        assert!(markdown.contains("\n\n#### Trade Goods\n"));
+        assert!(markdown.contains("\n\n#### Encounters (1D)\n"));
+
+        let svg =
+            String::from_utf8(world.sheet(&point, SheetFormat::Svg, &numbers, false, &[])).unwrap();
+        assert!(svg.contains(">Regina: (Capital) (0110)</text>"));
+
+        // Long notes are wrapped onto more than one page
+        let pdf = world.sheet(&point, SheetFormat::Pdf, &numbers, false, &[]);
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.starts_with("%PDF-1.4\n"));
+        assert!(text.ends_with("%%EOF\n"));
+        assert!(text.contains("(Regina: \\(Capital\\) \\(0110\\)) Tj"));
+        let lines = world.sheet_lines(&point, &numbers, &[]);
+        assert!(lines.len() > PDF_LINES_PER_PAGE);
+        assert!(text.contains(&format!(
+            "/Count {}",
+            lines.len().div_ceil(PDF_LINES_PER_PAGE)
+        )));
+
+        // The cross-reference table is where the trailer says it is
+        let startxref = text.rsplit("startxref\n").next().unwrap();
+        let offset: usize = startxref.lines().next().unwrap().parse().unwrap();
+        assert!(text[offset..].starts_with("xref\n"));
+        assert!(!text.contains(" cm\n"));
+
+        // The surface thumbnail goes beside the heading
+        let markdown =
+            String::from_utf8(world.sheet(&point, SheetFormat::Markdown, &numbers, true, &[]))
+                .unwrap();
+        assert!(markdown
+            .contains("(0110)\n\n![Surface of Regina: (Capital)](data:image/svg+xml;base64,"));
+        let svg =
+            String::from_utf8(world.sheet(&point, SheetFormat::Svg, &numbers, true, &[])).unwrap();
+        assert!(svg.contains("<g transform=\"translate(476 24)\">"));
+        let pdf = world.sheet(&point, SheetFormat::Pdf, &numbers, true, &[]);
+        assert!(String::from_utf8_lossy(&pdf).contains("q\n1 0 0 -1 476 768 cm\n"));
+    }
+
+    #[test]
+    fn world_sheet_hooks() {
+        let mut world = World::empty();
+        world.name = "Regina".to_string();
+        let point = Point { x: 1, y: 10 };
+        let numbers = NumberFormat::default();
+        let hooks = [
+            "Someone is offering a fortune for passage past the interdiction of Regina (0110)."
+                .to_string(),
+            "Efate (0109) has been declared an Amber Zone.".to_string(),
+        ];
+
+        // Only hooks involving the world make it onto its sheet
+        let markdown =
+            String::from_utf8(world.sheet(&point, SheetFormat::Markdown, &numbers, false, &hooks))
+                .unwrap();
+        assert!(markdown.ends_with(
+            "\n\n#### Adventure Hooks\n\n- Someone is offering a fortune for passage past the \
+            interdiction of Regina (0110)."
+        ));
+        assert!(!markdown.contains("Efate"));
+
+        let svg = String::from_utf8(world.sheet(&point, SheetFormat::Svg, &numbers, false, &hooks))
+            .unwrap();
+        assert!(svg.contains(">Adventure Hooks</text>"));
+
+        let markdown =
+            String::from_utf8(world.sheet(&point, SheetFormat::Markdown, &numbers, false, &[]))
+                .unwrap();
+        assert!(!markdown.contains("Adventure Hooks"));
+    }
+
+    #[test]
+    fn world_sheet_images() {
+        let mut world = World::empty();
+        world.name = "Regina".to_string();
+        let point = Point { x: 1, y: 10 };
+        let numbers = NumberFormat::default();
+        let markdown_of = |world: &World| {
+            String::from_utf8(world.sheet(&point, SheetFormat::Markdown, &numbers, false, &[]))
+                .unwrap()
+        };
+        assert!(!markdown_of(&world).contains("#### Image"));
+
+        world.image = Some(WorldImage::from_bytes(b"\x89PNG\r\n\x1a\nrest of the file"));
+        let data = base64::encode(b"\x89PNG\r\n\x1a\nrest of the file");
+        assert!(markdown_of(&world).contains(&format!(
+            "\n\n#### Image\n\n![Image of Regina](data:image/png;base64,{})\n",
+            data
+        )));
+        let pdf = world.sheet(&point, SheetFormat::Pdf, &numbers, false, &[]);
+        assert!(String::from_utf8_lossy(&pdf).contains("(Embedded in the subsector file) Tj"));
+
+        world.image = Some(WorldImage::Linked("art/regina map.jpg".into()));
+        assert!(markdown_of(&world).contains("\n![Image of Regina](<art/regina map.jpg>)\n"));
+        let svg =
+            String::from_utf8(world.sheet(&point, SheetFormat::Svg, &numbers, false, &[])).unwrap();
+        assert!(svg.contains(">art/regina map.jpg</text>"));
+
+        assert_eq!(
+            image_media_type(&base64::encode(b"\xFF\xD8\xFF")),
+            "image/jpeg"
+        );
+        assert_eq!(image_media_type(&base64::encode("<svg/>")), "image/svg+xml");
+    }
+
+    #[test]
+    fn wrapped_lines() {
+        assert_eq!(wrap_line("", 10), vec![""]);
+        assert_eq!(wrap_line("one two three", 7), vec!["one two", "three"]);
+        assert_eq!(wrap_line("abcdefghij k", 4), vec!["abcd", "efgh", "ij k"]);
+    }
+}
@@ -0,0 +1,109 @@
+use crate::astrography::{Point, StarportClass, Subsector, World};
+
+/// Longest jump, in parsecs, that an x-boat link can span
+const MAX_XBOAT_JUMP: u32 = 4;
+
+impl Subsector {
+    /** Get the links of the x-boat network, each a pair of hub worlds a single jump apart. */
+    pub fn xboat_routes(&self) -> &[(Point, Point)] {
+        &self.xboat_routes
+    }
+
+    /** Replace the `Subsector`'s x-boat network with a freshly generated one.
+
+    Hubs are the worlds with class A or B starports that are important enough to warrant express
+    mail service. Hubs are linked so that every hub reachable from another by jumps of at most
+    `MAX_XBOAT_JUMP` parsecs is on the network, using the shortest links possible and no more of
+    them than needed.
+    */
+    pub fn generate_xboat_routes(&mut self) {
+        let hubs: Vec<Point> = self
+            .map
+            .iter()
+            .filter(|(_, world)| is_xboat_hub(world))
+            .map(|(point, _)| *point)
+            .collect();
+
+        let mut links: Vec<(u32, usize, usize)> = Vec::new();
+        for (i, from) in hubs.iter().enumerate() {
+            for (j, to) in hubs.iter().enumerate().skip(i + 1) {
+                let distance = from.distance(to);
+                if distance <= MAX_XBOAT_JUMP {
+                    links.push((distance, i, j));
+                }
+            }
+        }
+        links.sort();
+
+        // Kruskal's algorithm; `networks[i]` is the index of a hub on the same network as hub `i`
+        let mut networks: Vec<usize> = (0..hubs.len()).collect();
+
+        self.xboat_routes.clear();
+        for (_, i, j) in links {
+            let (a, b) = (network_of(&mut networks, i), network_of(&mut networks, j));
+            if a != b {
+                networks[a] = b;
+                self.xboat_routes.push((hubs[i], hubs[j]));
+            }
+        }
+    }
+
+    /** Drop any x-boat links with an end that no longer has a `World`. */
+    pub fn prune_xboat_routes(&mut self) {
+        let map = &self.map;
+        self.xboat_routes
+            .retain(|(from, to)| map.contains_key(from) && map.contains_key(to));
+    }
+}
+
+/** Find the hub that identifies the network `hub` is on, flattening the chain of hubs on the way. */
+fn network_of(networks: &mut [usize], mut hub: usize) -> usize {
+    while networks[hub] != hub {
+        networks[hub] = networks[networks[hub]];
+        hub = networks[hub];
+    }
+    hub
+}
+
+/** Whether `world` has a good enough starport and is important enough to be an x-boat hub. */
+fn is_xboat_hub(world: &World) -> bool {
+    match world.starport.class {
+        StarportClass::A => true,
+        StarportClass::B => world.importance() >= 1,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astrography::RuleSet;
+
+    #[test]
+    fn generate_xboat_routes() {
+        const ATTEMPTS: usize = 100;
+        for _ in 0..ATTEMPTS {
+            let mut subsector = Subsector::new(0, 0, RuleSet::default());
+            subsector.generate_xboat_routes();
+
+            let hub_count = subsector.map.values().filter(|w| is_xboat_hub(w)).count();
+            // A spanning forest never has as many links as hubs
+            assert!(subsector.xboat_routes().len() < hub_count.max(1));
+
+            for (from, to) in subsector.xboat_routes() {
+                assert!(from.distance(to) <= MAX_XBOAT_JUMP);
+                assert!(is_xboat_hub(&subsector.map[from]));
+                assert!(is_xboat_hub(&subsector.map[to]));
+            }
+
+            if let Some((from, _)) = subsector.xboat_routes().first().cloned() {
+                subsector.remove_world(&from).unwrap();
+                subsector.prune_xboat_routes();
+                assert!(subsector
+                    .xboat_routes()
+                    .iter()
+                    .all(|(a, b)| a != &from && b != &from));
+            }
+        }
+    }
+}
@@ -0,0 +1,309 @@
+use rand::distributions::uniform::{SampleRange, SampleUniform};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::cmp::PartialOrd;
+use std::fmt;
+use std::ops::{Add, AddAssign, Div, Mul, Rem, Sub};
+
+thread_local! {
+    /// Dice rolled on this thread inside of `record_rolls`, or `None` outside of it
+    static RECORDED_ROLLS: RefCell<Option<Vec<RecordedRoll>>> = const { RefCell::new(None) };
+}
+
+/** Dice rolled by a single call to [`roll`] inside of [`record_rolls`]. */
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct RecordedRoll {
+    pub sides: usize,
+    /// Result of each die rolled
+    pub results: Vec<usize>,
+    /// DM added to the dice when they were rolled on a table
+    #[serde(default)]
+    pub modifier: i32,
+    /// Row of the table the modified roll landed on, after being clamped to the table's rows
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub row: Option<usize>,
+}
+
+impl RecordedRoll {
+    /** Get the sum of the dice plus the modifier. */
+    pub fn total(&self) -> i32 {
+        self.results.iter().sum::<usize>() as i32 + self.modifier
+    }
+}
+
+impl fmt::Display for RecordedRoll {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // e.g. "2D6 [3, 5] -2 = 6, row 6"
+        let results: Vec<String> = self.results.iter().map(usize::to_string).collect();
+        write!(
+            f,
+            "{}D{} [{}]",
+            self.results.len(),
+            self.sides,
+            results.join(", ")
+        )?;
+        if self.modifier != 0 {
+            write!(f, " {:+} = {}", self.modifier, self.total())?;
+        } else if self.results.len() > 1 {
+            write!(f, " = {}", self.total())?;
+        }
+        if let Some(row) = self.row {
+            write!(f, ", row {}", row)?;
+        }
+        Ok(())
+    }
+}
+
+/** Stand-in for "any integer"; any signed or unsigned primitive integer will satisfy this.
+
+If it walks like an integer and quacks like an integer, it's probably an integer.
+*/
+pub trait DuckInteger:
+    Add<Output = Self>
+    + AddAssign
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + PartialEq
+    + Eq
+    + PartialOrd
+    + Ord
+    + TryFrom<u8>
+    + TryInto<usize>
+    + SampleUniform
+    + Copy
+{
+}
+
+impl<T> DuckInteger for T where
+    T: Add<Output = Self>
+        + AddAssign
+        + Sub<Output = Self>
+        + Mul<Output = Self>
+        + Div<Output = Self>
+        + Rem<Output = Self>
+        + PartialEq
+        + Eq
+        + PartialOrd
+        + Ord
+        + TryFrom<u8>
+        + TryInto<usize>
+        + SampleUniform
+        + Copy
+{
+}
+
+/** Roll a number within `range` with a uniform distribution.
+
+# Panics
+Panics of `range` is empty.
+*/
+pub fn roll_range<T: DuckInteger, U: SampleRange<T>>(range: U) -> T {
+    assert!(!range.is_empty(), "Cannot roll within an empty range");
+    let mut rng = rand::thread_rng();
+    rng.gen_range(range)
+}
+
+/** Roll a `sides`-sided die `rolls` times and return the sum of all rolls.
+
+# Panics
+Panics if `rolls` or `sides` is less than 1.
+*/
+pub fn roll<T: DuckInteger>(rolls: T, sides: T) -> T {
+    let one = T::try_from(1).unwrap_or_else(|_| unreachable!());
+    assert!(rolls >= one, "Cannot roll zero or fewer dice");
+    assert!(sides >= one, "Dice must have at least one side");
+
+    let mut rng = rand::thread_rng();
+    let mut roll = T::try_from(0).unwrap_or_else(|_| unreachable!());
+
+    let rolls = rolls.try_into().unwrap_or_else(|_| unreachable!());
+    let mut results = Vec::with_capacity(rolls);
+    for _ in 1..=rolls {
+        let result = rng.gen_range(one..=sides);
+        roll += result;
+        results.push(result.try_into().unwrap_or_default());
+    }
+
+    RECORDED_ROLLS.with(|recorded| {
+        if let Some(recorded) = recorded.borrow_mut().as_mut() {
+            recorded.push(RecordedRoll {
+                sides: sides.try_into().unwrap_or_default(),
+                results,
+                ..Default::default()
+            });
+        }
+    });
+    roll
+}
+
+/** Run `f`, collecting every roll of the dice it makes on this thread.
+
+Rolls made with [`roll_range`] aren't dice rolls and so aren't collected. Calls may be nested, in
+which case the outer call collects the rolls of the inner one as well.
+
+# Returns
+The result of `f` and the dice it rolled, in the order they were rolled.
+*/
+pub fn record_rolls<R>(f: impl FnOnce() -> R) -> (R, Vec<RecordedRoll>) {
+    let outer = RECORDED_ROLLS.with(|recorded| recorded.replace(Some(Vec::new())));
+    let result = f();
+    let rolls = RECORDED_ROLLS
+        .with(|recorded| recorded.replace(outer))
+        .unwrap_or_default();
+
+    RECORDED_ROLLS.with(|recorded| {
+        if let Some(outer) = recorded.borrow_mut().as_mut() {
+            outer.extend(rolls.iter().cloned());
+        }
+    });
+    (result, rolls)
+}
+
+/** Note the DM and the table row of the last roll collected by [`record_rolls`], if any. */
+pub(crate) fn record_table_row(modifier: i32, row: usize) {
+    RECORDED_ROLLS.with(|recorded| {
+        if let Some(last) = recorded
+            .borrow_mut()
+            .as_mut()
+            .and_then(|recorded| recorded.last_mut())
+        {
+            last.modifier = modifier;
+            last.row = Some(row);
+        }
+    });
+}
+
+/** Wrapper for `dice::roll(1, sides)`. */
+pub fn roll_1d<T: DuckInteger>(sides: T) -> T {
+    let one = T::try_from(1).unwrap_or_else(|_| unreachable!());
+    roll(one, sides)
+}
+
+/** Wrapper for `dice::roll(2, sides)`. */
+pub fn roll_2d<T: DuckInteger>(sides: T) -> T {
+    let two = T::try_from(2).unwrap_or_else(|_| unreachable!());
+    roll(two, sides)
+}
+
+/** Roll two six-sided dice and subtract the second from the first, giving a result from -5 to 5. */
+pub fn flux() -> i32 {
+    roll_1d(6) - roll_1d(6)
+}
+
+/** Roll two six-sided dice, treating one as the 10's place in a two-digit number.
+
+For example,
+
+- `roll: 5 & 4 -> 54`,
+- `roll: 1 & 6 -> 16`,
+- `roll: 6 & 1 -> 61`,
+- etc...
+*/
+pub fn roll_d66() -> isize {
+    10 * roll_1d(6) + roll_1d(6)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    const ROLL_ATTEMPTS: usize = 10_000;
+
+    #[test]
+    fn test_roll() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..ROLL_ATTEMPTS {
+            let dice = rng.gen_range(1..=50);
+            let sides = rng.gen_range(1..=100);
+
+            let low = dice;
+            let high = dice * sides;
+            let range = low..=high;
+
+            let roll = roll(dice, sides);
+            assert!(range.contains(&roll));
+        }
+    }
+
+    #[test]
+    fn test_roll_1d() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..ROLL_ATTEMPTS {
+            let sides = rng.gen_range(3..=20);
+            let range = 1..=sides;
+            let roll = roll_1d(sides);
+            assert!(range.contains(&roll));
+        }
+    }
+
+    #[test]
+    fn test_roll_2d() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..ROLL_ATTEMPTS {
+            let sides = rng.gen_range(3..=20);
+            let range = 2..=(2 * sides);
+            let roll = roll_2d(sides);
+            assert!(range.contains(&roll));
+        }
+    }
+
+    #[test]
+    fn test_record_rolls() {
+        let (sum, rolls) = record_rolls(|| {
+            let (inner, inner_rolls) = record_rolls(|| roll_2d(6));
+            assert_eq!(inner_rolls.len(), 1);
+            record_table_row(-2, 3);
+            inner + roll_1d(20) + roll_range(1..=6)
+        });
+
+        assert_eq!(rolls.len(), 2);
+        assert_eq!(rolls[0].results.len(), 2);
+        assert_eq!(rolls[0].modifier, -2);
+        assert_eq!(rolls[0].row, Some(3));
+        assert_eq!(rolls[1].sides, 20);
+        let dice_sum: usize = rolls.iter().flat_map(|roll| roll.results.iter()).sum();
+        assert!(sum as usize > dice_sum);
+
+        // Nothing is collected outside of `record_rolls`
+        roll_2d(6);
+        let ((), rolls) = record_rolls(|| ());
+        assert!(rolls.is_empty());
+    }
+
+    #[test]
+    fn test_recorded_roll_display() {
+        let roll = RecordedRoll {
+            sides: 6,
+            results: vec![3, 5],
+            modifier: -2,
+            row: Some(6),
+        };
+        assert_eq!(roll.to_string(), "2D6 [3, 5] -2 = 6, row 6");
+
+        let roll = RecordedRoll {
+            sides: 20,
+            results: vec![17],
+            ..Default::default()
+        };
+        assert_eq!(roll.to_string(), "1D20 [17]");
+    }
+
+    #[test]
+    fn test_roll_d66() {
+        let mut possible_outcomes = HashSet::new();
+        for i in 1..=6 {
+            for j in 1..=6 {
+                possible_outcomes.insert(10 * i + j);
+            }
+        }
+
+        for _ in 0..ROLL_ATTEMPTS {
+            let roll = roll_d66();
+            assert!(possible_outcomes.contains(&roll));
+        }
+    }
+}
@@ -0,0 +1,26 @@
+/*! Generation, editing, and serialization of Traveller subsectors, without any GUI.
+
+A [`Subsector`] is a map of [`World`]s keyed by their [`Point`] on the hex grid. Subsectors can be
+generated at random, edited world by world, and saved to or loaded from JSON, as well as exported
+to a number of other formats such as SVG maps and T5 tab-delimited tables.
+
+```
+use swt_gen_core::{Point, RuleSet, Subsector, World};
+
+let mut subsector = Subsector::new(0, 0, RuleSet::default());
+let point = Point { x: 1, y: 1 };
+subsector
+    .insert_world(&point, World::new("Regina".to_string()))
+    .unwrap();
+
+let json = subsector.to_json();
+let loaded = Subsector::try_from_json(&json).unwrap();
+assert_eq!(loaded.get_world(&point).unwrap().name, "Regina");
+```
+*/
+#![warn(clippy::todo)]
+mod astrography;
+pub mod dice;
+mod histogram;
+
+pub use astrography::*;
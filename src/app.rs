@@ -1,124 +1,575 @@
+mod backup;
+mod document;
+mod file_io;
 mod gui;
+mod library;
+mod map_redraw;
+mod message_log;
 mod pipe;
+mod platform;
+mod settings;
 
 use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt, mem,
     path::{Path, PathBuf},
-    sync::mpsc,
-    thread,
+    sync::{mpsc, Arc},
+    time::{Duration, Instant, SystemTime},
 };
 
-use eframe::{App, Frame};
+use eframe::{App, CreationContext, Frame, Storage};
 use egui::{Context, Key, Modifiers};
 use egui_extras::RetainedImage;
-use native_dialog::{FileDialog, MessageDialog, MessageType};
-
-use crate::astrography::{Faction, Point, Subsector, World, TABLES};
+use swt_gen_core::{
+    random_faction_name, random_subsector_name, set_roll_logging, set_table_language,
+    set_table_overrides, Annotation, AnnotationColor, AnnotationShape, CulturalFacet, Faction,
+    GrowthRules, HandoutOptions, ImperialDate, Inconsistency, MapFormat, MapLayout, MapOptions,
+    MapRenderer, MapTemplate, MergeResolution, NameStyle, NumberFormat, Point, PoliticalEvent,
+    Religion, RuleSet, Script, SheetFormat, ShipSpec, Subsector, SummaryFormat, TableOverrides,
+    TravelCodePolicy, TravellerMapTable, World, WorldAttribute, WorldField, WorldImage,
+    WorldSubtype, DEFAULT_MAP_TEMPLATE, TABLES,
+};
 
+use document::Document;
+use file_io::{FileIo, PlatformFileIo};
 use gui::Popup;
+use library::{LibraryQuery, LibrarySearch};
+use map_redraw::MapRedraw;
+use message_log::MessageLog;
+use settings::{GenerationPreset, Settings, MAX_UI_SCALE, MIN_UI_SCALE};
 
-// TODO: calls to `Subsector::generate_svg` using this variable need to have their logic of when to
-// have the svg colored updated once proper svg coloring has been implemented. This `const` is just
-// part of the proof of concept commit; set to true to have the hexes of generated svg's be rainbow
-// colored. Make sure to commit only with this set to `false`.
-const COLORED: bool = false;
+/// Key that opens and closes the debug console while holding Ctrl and Shift
+const DEBUG_CONSOLE_KEY: Key = Key::D;
 
 const DEFAULT_DIRECTORY: &str = "~";
 
+/// Scale of exported PNG subsector maps relative to the 96 DPI size of the SVG
+const MAP_PNG_ZOOM: f32 = 3.0;
+
+/// Time between checks of the open save file for changes made outside of the app
+const SAVE_FILE_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Amount the GUI scale changes with each press of its hotkeys
+const UI_SCALE_STEP: f32 = 0.1;
+
 /** Set of messages respresenting all non-trivial GUI events.
 
 The definition of "non-trivial" is "not just a straightforward value change"; many widgets in `egui`
 are directly linked to a variable and update them directly. There would be no point in triggering a
 `Message` that just updates it again.
 */
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub(crate) enum Message {
+    AddAnnotation {
+        annotation: Annotation,
+    },
+    AddJournalEntry,
     AddNewFaction,
     AddNewWorld,
-    ApplyConfirmHexGridClicked { new_point: Point },
+    AddRandomWorld,
+    AddSubsectorHook,
+    AddTravelRouteStop,
+    AddWorldOrbitalFacility,
+    AddWorldSophont,
+    AdvanceCampaignDate {
+        days: u32,
+    },
+    ApplyConfirmHexGridClicked {
+        new_point: Point,
+    },
     ApplyWorldChanges,
+    AttachWorldImage {
+        embed: bool,
+    },
     CancelLocUpdate,
     CancelUnsavedExit,
+    ClearAnnotations,
+    ClearDeletedWorlds,
+    ClearTravelRoute,
+    CloseTab,
+    CompareWorld {
+        point: Point,
+    },
+    ConfigNewEmptySubsector,
     ConfigRegenSubsector,
-    ConfirmHexGridClicked { new_point: Point },
+    ConfirmCloseTab,
+    ConfirmEnterPlayerView {
+        passphrase: String,
+    },
+    ConfirmExportAllWorldSheets {
+        format: SheetFormat,
+        thumbnail: bool,
+    },
+    ConfirmExportPlayerHandout {
+        options: HandoutOptions,
+    },
+    ConfirmExportSubsectorMap {
+        format: MapFormat,
+        options: MapOptions,
+    },
+    ConfirmFastForward {
+        decades: u32,
+        rules: GrowthRules,
+    },
+    ConfirmFillEmptyHexes {
+        count: usize,
+    },
+    ConfirmGenerateBatch {
+        count: usize,
+        world_abundance_dm: i16,
+        clustering: u8,
+        name_style: NameStyle,
+        rule_set: RuleSet,
+    },
+    ConfirmHexGridClicked {
+        new_point: Point,
+    },
     ConfirmImportJson,
-    ConfirmLocUpdate { location: Point },
-    ConfirmRegenSubsector { world_abundance_dm: i16 },
-    ConfirmRegenWorld,
-    ConfirmRemoveWorld { point: Point },
-    ConfirmRenameSubsector { new_name: String },
+    ConfirmImportText {
+        subsector: Subsector,
+    },
+    ConfirmImportTravellerMap {
+        subsector: Subsector,
+    },
+    ConfirmImportWorlds {
+        subsector: Subsector,
+        resolution: MergeResolution,
+    },
+    ConfirmLeavePlayerView {
+        passphrase: String,
+    },
+    ConfirmLocUpdate {
+        location: Point,
+    },
+    ConfirmNewEmptySubsector {
+        name: String,
+    },
+    ConfirmPoliticalCrisis {
+        event: Option<PoliticalEvent>,
+    },
+    ConfirmRegenAttribute {
+        attribute: WorldAttribute,
+    },
+    ConfirmRegenSubsector {
+        world_abundance_dm: i16,
+        clustering: u8,
+        name_style: NameStyle,
+        rule_set: RuleSet,
+    },
+    ConfirmRegenWorld {
+        weighting: u8,
+    },
+    ConfirmRemoveWorld {
+        point: Point,
+    },
+    ConfirmRenameSubsector {
+        new_name: String,
+    },
+    ConfirmRerollDependents {
+        world: Box<World>,
+    },
+    ConfirmSetTravelCodes {
+        policy: TravelCodePolicy,
+    },
+    ConfirmTableOverrides {
+        overrides: TableOverrides,
+    },
     ConfirmUnsavedExit,
+    CopyRollLog,
+    CopyWorld,
+    CopyWorldSummary {
+        format: SummaryFormat,
+    },
+    DeleteGenerationPreset {
+        name: String,
+    },
+    DiscardBatch,
+    EditTableOverrides,
+    ExportAllWorldSheets,
     ExportColumnDelimitedTable,
+    ExportCsv,
+    ExportPlayerHandout,
     ExportPlayerSafeSubsectorJson,
-    ExportSubsectorMapSvg,
-    HexGridClicked { new_point: Point },
-    NewFactionGovSelected { new_code: u16 },
-    NewFactionStrengthSelected { new_code: u16 },
+    ExportSectorMetadata,
+    ExportSubsectorMap,
+    FastForwardSubsector,
+    FillEmptyHexes,
+    FixAllSubsectorInconsistencies,
+    FixWorldInconsistency {
+        inconsistency: Inconsistency,
+    },
+    FuelMarketTick,
+    GenerateBatch,
+    HexGridClicked {
+        new_point: Point,
+    },
+    ImportCsv,
+    ImportText,
+    ImportTravellerMap,
+    ImportWorlds,
+    MoveSelection {
+        dx: i32,
+        dy: i32,
+    },
+    NameUnnamedFactions,
+    NewEmptySubsector,
+    NewFactionGovSelected {
+        new_code: u16,
+    },
+    NewFactionStrengthSelected {
+        new_code: u16,
+    },
     NewStarportClassSelected,
-    NewWorldCultureSelected { new_code: u16 },
-    NewWorldGovSelected { new_code: u16 },
-    NewWorldTagSelected { index: usize, new_code: u16 },
+    NewTab,
+    NewWorldCultureSelected {
+        new_code: u16,
+    },
+    NewWorldGovSelected {
+        new_code: u16,
+    },
+    NewWorldSubtypeSelected {
+        subtype: WorldSubtype,
+    },
+    NewWorldTagSelected {
+        index: usize,
+        new_code: u16,
+    },
     NoOp,
+    OpenBatchCandidate {
+        index: usize,
+    },
     OpenJson,
+    OpenJsonInNewTab,
+    OpenLibraryMatch {
+        index: usize,
+    },
+    PasteWorld,
+    PoliticalCrisis,
+    RegenAttribute {
+        attribute: WorldAttribute,
+    },
+    RegenFactionName,
+    RegenFactionReligion,
+    RegenPolities,
     RegenSelectedFaction,
     RegenSelectedWorld,
     RegenSubsector,
+    RegenSubsectorHook {
+        index: usize,
+    },
+    RegenSubsectorHooks,
     RegenWorldAtmosphere,
+    RegenWorldCulturalDetail {
+        facet: CulturalFacet,
+    },
     RegenWorldCulture,
+    RegenWorldEncounter {
+        index: usize,
+    },
+    RegenWorldEncounters,
+    RegenWorldFuelMarket,
+    RegenWorldGasGiants,
     RegenWorldGovernment,
+    RegenWorldHazards,
     RegenWorldHydrographics,
+    RegenWorldLawCategories,
     RegenWorldLawLevel,
+    RegenWorldNobility,
+    RegenWorldOrbitalFacilities,
+    RegenWorldPlanetoidBelts,
     RegenWorldPopulation,
+    RegenWorldPronunciation,
+    RegenWorldReligion,
     RegenWorldSize,
+    RegenWorldSophonts,
     RegenWorldStarport,
-    RegenWorldTag { index: usize },
+    RegenWorldStarportDetails,
+    RegenWorldStellar,
+    RegenWorldTag {
+        index: usize,
+    },
     RegenWorldTechLevel,
     RegenWorldTemperature,
+    RegenWorldTradeGoods,
+    RegenWorldTravelAdvisory,
+    RegenXboatRoutes,
+    ReloadSaveFile,
+    RemoveBookmark {
+        index: usize,
+    },
+    RemoveJournalEntry {
+        index: usize,
+    },
+    RemoveLastAnnotation,
     RemoveSelectedFaction,
     RemoveSelectedWorld,
+    RemoveSubsectorHook {
+        index: usize,
+    },
+    RemoveTravelRouteStop {
+        index: usize,
+    },
+    RemoveWorldImage,
+    RemoveWorldOrbitalFacility {
+        index: usize,
+    },
+    RemoveWorldSophont {
+        index: usize,
+    },
+    RenameDuplicateWorlds,
     RenameSubsector,
+    ResetGenerationScript,
+    ResetLanguage,
+    ResetMapTemplate,
+    ResetTableOverrides,
+    RestoreDeletedWorld {
+        index: usize,
+        point: Point,
+    },
     RevertWorldChanges,
+    RunScript,
     Save,
     SaveAs,
+    SaveCloseTab,
+    SaveConfigNewEmptySubsector,
     SaveConfigRegenSubsector,
     SaveConfirmImportJson,
     SaveExit,
+    SaveGenerationPreset {
+        preset: GenerationPreset,
+    },
+    SearchLibrary,
+    SelectGenerationScript,
+    SelectLanguage,
+    SelectLibraryDirectory,
+    SelectMapTemplate,
+    SelectTab {
+        index: usize,
+    },
+    SelectTableOverrides,
+    SetCampaignDate {
+        date: Option<ImperialDate>,
+    },
+    SetTravelCodes,
+    SetUiScale {
+        scale: f32,
+    },
+    SubsectorModelUpdated,
+    ToggleBookmark,
+    TogglePlayerView,
+    ToggleViewerMode,
     WorldBerthingCostsUpdated,
     WorldDiameterUpdated,
+    WorldFieldEdited {
+        field: WorldField,
+    },
     WorldGasGiantsUpdated,
     WorldLocUpdated,
     WorldModelUpdated,
     WorldPlanetoidBeltsUpdated,
 }
 
+impl Message {
+    /** Whether the `Message` leaves the open subsectors as they are, so it can be handled in the
+    read-only viewer; i.e. it only navigates, searches, exports, or changes how things are shown.
+    */
+    fn is_read_only(&self) -> bool {
+        use Message::*;
+        matches!(
+            self,
+            AddTravelRouteStop
+                | CancelUnsavedExit
+                | ClearTravelRoute
+                | CloseTab
+                | CompareWorld { .. }
+                | ConfirmCloseTab
+                | ConfirmEnterPlayerView { .. }
+                | ConfirmExportAllWorldSheets { .. }
+                | ConfirmExportPlayerHandout { .. }
+                | ConfirmExportSubsectorMap { .. }
+                | ConfirmHexGridClicked { .. }
+                | ConfirmImportJson
+                | ConfirmLeavePlayerView { .. }
+                | ConfirmUnsavedExit
+                | CopyRollLog
+                | CopyWorld
+                | CopyWorldSummary { .. }
+                | ExportAllWorldSheets
+                | ExportColumnDelimitedTable
+                | ExportCsv
+                | ExportPlayerHandout
+                | ExportPlayerSafeSubsectorJson
+                | ExportSectorMetadata
+                | ExportSubsectorMap
+                | HexGridClicked { .. }
+                | MoveSelection { .. }
+                | NoOp
+                | OpenJson
+                | OpenJsonInNewTab
+                | OpenLibraryMatch { .. }
+                | ReloadSaveFile
+                | RemoveTravelRouteStop { .. }
+                | ResetLanguage
+                | ResetMapTemplate
+                | SearchLibrary
+                | SelectLanguage
+                | SelectLibraryDirectory
+                | SelectMapTemplate
+                | SelectTab { .. }
+                | SetUiScale { .. }
+                | TogglePlayerView
+                | ToggleViewerMode
+        )
+    }
+}
+
+/** How a world differs from the last time its [`Subsector`] was saved or loaded. */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum UnsavedChange {
+    Added,
+    Edited,
+    Removed,
+}
+
+impl fmt::Display for UnsavedChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Added => "Added",
+            Self::Edited => "Edited",
+            Self::Removed => "Removed",
+        };
+        write!(f, "{}", s)
+    }
+}
+
 pub struct GeneratorApp {
-    /// Buffor for `String` representation of the selected world's planetoid belt count
-    belt_str: String,
-    /// Buffer for `String` representation of the selected world's starport berthing cost
-    berthing_cost_str: String,
+    /// Color new annotations are drawn on the map in
+    annotation_color: AnnotationColor,
+    /// Annotation being drawn on the map, until the drag drawing it ends
+    annotation_draft: Option<AnnotationShape>,
+    /// Text written on the map by the text annotation tool
+    annotation_text: String,
+    /// Tool that dragging across or clicking on the map draws annotations with, if any
+    annotation_tool: Option<gui::AnnotationTool>,
+    /// Subsectors of the last generated batch that haven't been opened yet, with map previews
+    batch_candidates: Vec<(Subsector, RetainedImage)>,
+    /// Buffer for the selected world's planetoid belt count
+    belt_buffer: gui::BufferedField<i32>,
+    /// Buffer for the selected world's starport berthing cost
+    berthing_cost_buffer: gui::BufferedField<u32>,
     /// Flag used to ensure the program is not closed without a save prompt
     can_exit: bool,
-    /// Buffer for `String` representation of the selected world's diameter in km
-    diameter_str: String,
+    /// Second `World` selection displayed read-only in split view
+    compared_world: Option<gui::ComparedWorld>,
+    /// Text to be copied to the system clipboard at the end of the frame
+    copied_text: Option<String>,
+    /// [`World`] copied to be pasted into any tab
+    copied_world: Option<World>,
+    /// Worlds removed from the [`Subsector`] this session and the hexes they were removed from,
+    /// oldest first, so they can be restored
+    deleted_worlds: Vec<(Point, World)>,
+    /// Settings and log of the dice roller
+    dice_roller: gui::DiceRoller,
+    /// Buffer for the selected world's diameter in km
+    diameter_buffer: gui::BufferedField<u32>,
+    /// Subsector files open in every tab except the selected one, in tab order
+    documents: Vec<Document>,
     /// Index of selected [`Faction`]
     faction_idx: usize,
-    /// Buffer for `String` representation of the selected world's gas giant count
-    gas_giant_str: String,
+    /// Changes made by the last fast-forward, shown until the summary window is closed
+    fast_forward_summary: Option<gui::FastForwardSummary>,
+    /// File dialogs, file reads and writes, and error alerts, faked in tests
+    file_io: Arc<dyn FileIo>,
+    /// `Message` that opened a file dialog that finishes late, handled again once a file is picked
+    file_pick_message: Option<Message>,
+    /// Buffer for the selected world's gas giant count
+    gas_giant_buffer: gui::BufferedField<i32>,
+    /// Index of the journal entry being edited instead of displayed, if any
+    journal_editing: Option<usize>,
+    /// Most jumps the jump range window follows chains of worlds for
+    jump_range_jumps: u32,
+    /// Jump rating of the ship whose range is shown in the jump range window
+    jump_range_rating: u32,
+    /// What the library search window looks for in every subsector of the library directory
+    library_query: LibraryQuery,
+    /// Results of the last library search, if one has been run
+    library_search: Option<LibrarySearch>,
+    /// When the subsector map is due to be redrawn, and what it was last drawn from
+    map_redraw: MapRedraw,
+    /// Text that matching worlds are highlighted for on the map
+    map_search: String,
+    /// How far the map is zoomed in and which part of it is in view
+    map_view: gui::MapView,
+    /// Worker that rasterizes subsector map SVGs
+    map_worker: platform::Worker<String, RetainedImage>,
+    /// Whether dragging across the map measures the distance between hexes instead
+    measure_mode: bool,
+    /// Hexes at the ends of the ruler drawn over the map, until Escape is pressed
+    measurement: Option<(Point, Point)>,
+    /// Most recently handled messages, shown in the debug console
+    message_log: MessageLog,
     /// Receive internal and external messages
     message_rx: pipe::Receiver<Message>,
     /// Send internal and external messages; cloned by external GUI structs (e.g. [`Popups`]s)
     message_tx: pipe::Sender<Message>,
+    /// Whether GM-only tabs, tools, and fields are hidden so the app can be shown to players
+    player_view: bool,
+    /// Passphrase needed to leave the player view, if it was locked with one
+    player_view_passphrase: Option<String>,
     /// Currently selected [`Point`] on the hex grid
     point: Point,
     /// Whether a [`Point`] on the hex grid is currently selected or not
     point_selected: bool,
-    /// Buffer for `String` representation of the selected world's [`Point`] location
-    point_str: String,
+    /// Buffer for the selected world's [`Point`] location
+    point_buffer: gui::BufferedField<Point>,
+    /// Code of the [`Polity`] whose worlds are highlighted on the map, if any
+    polity_filter: Option<String>,
     /// List of blocking popups
     popup_queue: Vec<Box<dyn Popup>>,
+    /// Text the entries of the rules tab are filtered by
+    rules_search: String,
     /// Path to directory that was last saved to
     save_directory: String,
+    /// When the open save file was last checked for changes made outside of the app
+    save_file_checked: Option<Instant>,
+    /// Modification time of the open save file when it was last loaded, saved, or found changed
+    save_file_modified: Option<SystemTime>,
     /// Name of the file that was last saved to
     save_filename: String,
+    /// Worlds of the [`Subsector`] as of the last time it was saved or loaded
+    saved_worlds: BTreeMap<Point, World>,
+    /// User settings persisted between sessions
+    settings: Settings,
+    /// Ship used by the travel planner
+    ship_spec: ShipSpec,
+    /// Whether the bookmarks sidebar is open
+    show_bookmarks: bool,
+    /// Whether the debug console logging each handled message is open
+    show_debug_console: bool,
+    /// Whether the recently deleted worlds window is open
+    show_deleted_worlds: bool,
+    /// Whether the dice roller is open
+    show_dice_panel: bool,
+    /// Whether the window warning about worlds that share a name is open
+    show_duplicate_names: bool,
+    /// Whether worlds with stellar hazards are marked with warning icons on the map
+    show_hazards: bool,
+    /// Whether the adventure hooks window is open
+    show_hooks: bool,
+    /// Whether the campaign journal window is open
+    show_journal: bool,
+    /// Whether the jump range window is open and the selected hex's range is highlighted on the map
+    show_jump_range: bool,
+    /// Whether the library search window is open
+    show_library: bool,
+    /// Whether the polities window is open
+    show_polities: bool,
+    /// Whether the travel planner window is open
+    show_travel_planner: bool,
+    /// Whether the unsaved changes window is open and edited worlds are highlighted on the map
+    show_unsaved_worlds: bool,
+    /// Whether the selected and compared [`World`]s are shown side by side in place of the map
+    split_view: bool,
     subsector: Subsector,
     /// Whether the loaded [`Subsector`] has unsaved changes
     subsector_edited: bool,
@@ -126,20 +577,39 @@ pub struct GeneratorApp {
     subsector_grid_image: Option<RetainedImage>,
     /// Selected display [`TabLabel`]
     tab: gui::TabLabel,
-    /// `Receiver` for the subsector image worker thread
-    worker_rx: mpsc::Receiver<RetainedImage>,
-    /// `Sender` for the subsector image worker thread
-    worker_tx: mpsc::Sender<String>,
+    /// Index of the selected subsector tab, counting every open tab
+    tab_index: usize,
+    /// Stops of the route in the travel planner, in order
+    travel_route: Vec<Point>,
+    /// Whether the app is a read-only viewer, where the subsectors can only be navigated, searched,
+    /// and exported
+    viewer_mode: bool,
     /// Selected `World`
     world: World,
     /// Whether the selected [`World`] has unapplied changes
     world_edited: bool,
+    /// Thumbnail of the image attached to the selected [`World`], if it could be loaded
+    world_image: Option<RetainedImage>,
+    /// Procedural picture of the selected [`World`]'s surface, along with the SVG it was drawn from
+    world_thumbnail: Option<(String, RetainedImage)>,
     /// Whether a [`World`] is at the selected [`Point`] or not
     world_selected: bool,
 }
 
 type MessageResult = Result<Option<()>, String>;
 impl GeneratorApp {
+    fn add_annotation(&mut self, annotation: Annotation) -> MessageResult {
+        self.subsector.add_annotation(annotation);
+        self.subsector_model_updated()
+    }
+
+    fn add_journal_entry(&mut self) -> MessageResult {
+        let index = self.subsector.add_journal_entry();
+        self.journal_editing = Some(index);
+        self.subsector_model_updated()?;
+        Ok(Some(()))
+    }
+
     fn add_new_faction(&mut self) -> MessageResult {
         self.faction_idx = self.world.add_faction();
         self.world_model_updated()?;
@@ -157,6 +627,52 @@ impl GeneratorApp {
         }
     }
 
+    /** Add a random world to a random empty hex and select it. */
+    fn add_random_world(&mut self) -> MessageResult {
+        self.confirm_fill_empty_hexes(1)
+    }
+
+    fn add_subsector_hook(&mut self) -> MessageResult {
+        let hook = self.subsector.generate_hook().unwrap_or_default();
+        self.subsector.hooks_mut().push(hook);
+        self.subsector_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn add_travel_route_stop(&mut self) -> MessageResult {
+        if !self.point_selected {
+            return Ok(None);
+        }
+        self.travel_route.push(self.point);
+        Ok(Some(()))
+    }
+
+    fn add_world_orbital_facility(&mut self) -> MessageResult {
+        self.world.add_orbital_facility();
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn add_world_sophont(&mut self) -> MessageResult {
+        self.world.add_sophont();
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn advance_campaign_date(&mut self, days: u32) -> MessageResult {
+        if self.subsector.campaign_date().is_none() {
+            return Ok(None);
+        }
+
+        // The selected world's market may tick, so unapplied edits would otherwise be lost
+        self.apply_world_changes()?;
+        if self.subsector.advance_campaign_date(days) && self.world_selected {
+            let point = self.point;
+            self.load_world(&point)?;
+        }
+        self.subsector_model_updated()
+    }
+
     fn apply_confirm_hex_grid_clicked(&mut self, new_point: Point) -> MessageResult {
         self.apply_world_changes()?;
         self.confirm_hex_grid_clicked(new_point)?;
@@ -165,6 +681,10 @@ impl GeneratorApp {
 
     fn apply_world_changes(&mut self) -> MessageResult {
         if self.world_selected && self.world_edited {
+            if let Some(old_world) = self.subsector.get_world(&self.point) {
+                self.world.record_changes_from(old_world);
+            }
+
             match self.subsector.insert_world(&self.point, self.world.clone()) {
                 Ok(_) => {
                     self.subsector_model_updated()?;
@@ -177,8 +697,52 @@ impl GeneratorApp {
         }
     }
 
+    fn attach_world_image(&mut self, embed: bool) -> MessageResult {
+        let result = self.file_io.pick_open_file(
+            Path::new(&self.save_directory),
+            "Image",
+            &["png", "jpg", "jpeg"],
+        );
+
+        let path = match result {
+            Ok(Some(path)) => path,
+            Ok(None) => return Ok(None),
+            Err(e) => return Err(e.to_string()),
+        };
+
+        let image: Result<WorldImage, Box<dyn std::error::Error>> = if embed {
+            self.file_io
+                .read(&path)
+                .map(|bytes| WorldImage::from_bytes(&bytes))
+                .map_err(Box::from)
+        } else {
+            Ok(WorldImage::link(&path, Path::new(&self.save_directory)))
+        };
+
+        // Make sure the image can actually be displayed before attaching it
+        let result = image.and_then(|image| {
+            let bytes = image.load_bytes(&self.save_directory)?;
+            gui::decode_image("world_image", &bytes)?;
+            Ok(image)
+        });
+
+        match result {
+            Ok(image) => {
+                self.world.image = Some(image);
+                self.load_world_image();
+                self.world_model_updated()?;
+                Ok(Some(()))
+            }
+            Err(e) => {
+                self.file_io
+                    .show_error("Error: Failed to Attach Image", &e.to_string());
+                Err(e.to_string())
+            }
+        }
+    }
+
     fn cancel_loc_update(&mut self) -> MessageResult {
-        self.point_str = self.point.to_string();
+        self.point_buffer.set_text(&self.point);
         Ok(None)
     }
 
@@ -187,6 +751,37 @@ impl GeneratorApp {
         Ok(None)
     }
 
+    /** Prompt to reload the open save file if it was changed outside of the app since it was last
+    loaded, saved, or checked.
+    */
+    fn check_save_file(&mut self) {
+        if !platform::HAS_FILE_SYSTEM
+            || !self.settings.watch_save_file
+            || self.save_filename.is_empty()
+        {
+            return;
+        }
+        let now = Instant::now();
+        match self.save_file_checked {
+            Some(checked) if now.duration_since(checked) < SAVE_FILE_CHECK_INTERVAL => return,
+            _ => self.save_file_checked = Some(now),
+        }
+
+        // Files that were moved or deleted can't be reloaded, so they're left for saving to handle
+        let modified = match platform::file_modified(&self.save_path()) {
+            Some(modified) => modified,
+            None => return,
+        };
+        if self.save_file_modified != Some(modified) {
+            // Each outside change is only brought up once, whichever version is kept
+            let changed = self.save_file_modified.is_some();
+            self.save_file_modified = Some(modified);
+            if changed {
+                self.save_file_changed_popup();
+            }
+        }
+    }
+
     fn check_world_edited(&mut self) {
         self.world_edited = match self.subsector.get_world(&self.point) {
             Some(stored_world) => self.world != *stored_world,
@@ -194,1289 +789,4955 @@ impl GeneratorApp {
         };
     }
 
-    fn config_regen_subsector(&mut self) -> MessageResult {
-        self.subsector_regen_popup();
-        Ok(Some(()))
+    fn clear_annotations(&mut self) -> MessageResult {
+        if self.subsector.annotations().is_empty() {
+            return Ok(None);
+        }
+        self.subsector.clear_annotations();
+        self.subsector_model_updated()
     }
 
-    fn confirm_hex_grid_clicked(&mut self, new_point: Point) -> MessageResult {
-        self.point_selected = true;
-        self.point = new_point;
-        self.faction_idx = 0;
-
-        if self.subsector.get_world(&self.point).is_some() {
-            self.load_world(&new_point)?;
-        } else {
-            self.world_selected = false;
+    fn clear_deleted_worlds(&mut self) -> MessageResult {
+        if self.deleted_worlds.is_empty() {
+            return Ok(None);
         }
+        self.deleted_worlds.clear();
         Ok(Some(()))
     }
 
-    fn confirm_import_json(&mut self) -> MessageResult {
-        let result = load_file_to_string(&self.save_directory, "JSON", &["json"]);
-
-        let (path, json) = match result {
-            Ok(Some((path, json))) => (path, json),
-            Ok(None) => return Ok(None),
-            Err(e) => {
-                MessageDialog::new()
-                    .set_type(MessageType::Error)
-                    .set_title("Error: Failed to Read JSON")
-                    .set_text(&format!("{}", e)[..])
-                    .show_alert()
-                    .unwrap();
-                return Err(e.to_string());
-            }
-        };
-
-        let subsector = match Subsector::try_from_json(&json) {
-            Ok(subsector) => subsector,
-            Err(e) => {
-                MessageDialog::new()
-                    .set_type(MessageType::Error)
-                    .set_title("Error: Failed to Load Subsector from JSON")
-                    .set_text(&format!("{}", e)[..])
-                    .show_alert()
-                    .unwrap();
-                return Err(e.to_string());
-            }
-        };
-
-        let directory = path.parent().unwrap().to_str().unwrap().to_string();
-        let filename = path.file_name().unwrap().to_str().unwrap().to_string();
-        *self = Self {
-            save_directory: directory,
-            save_filename: filename,
-            ..Self::from(subsector)
-        };
+    fn clear_travel_route(&mut self) -> MessageResult {
+        self.travel_route.clear();
         Ok(Some(()))
     }
 
-    fn confirm_loc_update(&mut self, location: Point) -> MessageResult {
-        let result = match self.subsector.move_world(&self.point, &location) {
-            Ok(_) => {
-                self.point = location;
-                self.world_model_updated()?;
-                self.subsector_model_updated()?;
-                Ok(Some(()))
-            }
+    fn close_tab(&mut self) -> MessageResult {
+        if self.documents.is_empty() {
+            Ok(None)
+        } else if self.has_unsaved_changes() {
+            self.unsaved_close_tab_popup();
+            Ok(Some(()))
+        } else {
+            self.confirm_close_tab()
+        }
+    }
 
-            Err(e) => Err(e),
+    fn compare_world(&mut self, point: Point) -> MessageResult {
+        let world_image = match self.subsector.get_world(&point) {
+            Some(world) => self.load_image_of(world),
+            None => return Ok(None),
         };
-        self.point_str = self.point.to_string();
-        result
+        self.compared_world = Some(gui::ComparedWorld::new(self, point, world_image));
+        self.split_view = true;
+        Ok(Some(()))
     }
 
-    fn confirm_regen_subsector(&mut self, world_abundance_dm: i16) -> MessageResult {
-        let directory = self.save_directory.clone();
-        *self = Self {
-            save_directory: directory,
-            ..Self::with_world_abundance(world_abundance_dm)
-        };
+    fn config_new_empty_subsector(&mut self) -> MessageResult {
+        self.new_empty_subsector_popup();
         Ok(Some(()))
     }
 
-    fn confirm_regen_world(&mut self) -> MessageResult {
-        match self.subsector.insert_random_world(&self.point) {
-            Ok(_) => {
-                self.world_selected = false;
-                self.confirm_hex_grid_clicked(self.point)?;
-                self.subsector_model_updated()?;
-                Ok(Some(()))
-            }
-            Err(e) => Err(e),
-        }
+    fn config_regen_subsector(&mut self) -> MessageResult {
+        self.subsector_regen_popup();
+        Ok(Some(()))
     }
 
-    fn confirm_remove_world(&mut self, point: Point) -> MessageResult {
-        self.world_selected = false;
-        match self.subsector.remove_world(&point) {
-            Ok(Some(_)) => {
-                self.subsector_model_updated()?;
-                Ok(Some(()))
-            }
-            Ok(None) => Ok(None),
-            Err(e) => Err(e),
+    /** Close the selected tab, discarding any unsaved changes, and select the next tab. */
+    fn confirm_close_tab(&mut self) -> MessageResult {
+        if self.documents.is_empty() {
+            return Ok(None);
         }
-    }
 
-    fn confirm_rename_subsector(&mut self, new_name: String) -> MessageResult {
-        self.subsector.set_name(new_name);
-        self.subsector_model_updated()?;
+        // The tab after the closed one takes its place, unless the last tab was closed
+        let index = self.tab_index.min(self.documents.len() - 1);
+        let mut document = self.documents.remove(index);
+        self.swap_document(&mut document);
+        self.tab_index = index;
         Ok(Some(()))
     }
 
-    fn confirm_unsaved_exit(&mut self) -> MessageResult {
-        self.can_exit = true;
+    fn confirm_enter_player_view(&mut self, passphrase: String) -> MessageResult {
+        self.player_view = true;
+        self.player_view_passphrase = Some(passphrase).filter(|passphrase| !passphrase.is_empty());
+
+        // Close anything showing GM-only information
+        self.show_hooks = false;
+        self.show_polities = false;
+        self.polity_filter = None;
+        if !gui::TabLabel::PLAYER_VIEW_VALUES.contains(&self.tab) {
+            self.tab = gui::TabLabel::WorldSurvey;
+        }
         Ok(Some(()))
     }
 
-    fn empty() -> Self {
-        let subsector = Subsector::empty();
-        let (message_tx, message_rx) = pipe::channel();
+    fn confirm_export_all_world_sheets(
+        &mut self,
+        format: SheetFormat,
+        thumbnail: bool,
+    ) -> MessageResult {
+        // Make sure the sheet of the selected world includes any unapplied changes
+        self.apply_world_changes()?;
 
-        let (worker_tx, boss_rx) = mpsc::channel::<String>();
-        let (boss_tx, worker_rx) = mpsc::channel::<RetainedImage>();
+        let directory = match self.file_io.pick_directory(Path::new(&self.save_directory)) {
+            Ok(Some(directory)) => directory,
+            Ok(None) => return Ok(None),
+            Err(e) => return Err(e.to_string()),
+        };
 
-        // Spawn worker thread to process SVG asynchronously
-        thread::spawn(move || {
-            while let Ok(svg) = boss_rx.recv() {
-                match boss_tx.send(gui::rasterize_svg(svg)) {
-                    Ok(_) => (),
-                    Err(_) => break,
+        let worlds: Vec<(Point, World)> = self
+            .subsector
+            .get_map()
+            .iter()
+            .map(|(point, world)| {
+                let mut world = world.clone();
+                // Linked images are relative to the subsector file, not to where the sheets go
+                if let Some(WorldImage::Linked(path)) = &mut world.image {
+                    *path = Path::new(&self.save_directory).join(path.as_path());
                 }
-            }
+                (*point, world)
+            })
+            .collect();
+        let total = worlds.len();
+        let options = SheetOptions {
+            format,
+            numbers: self.settings.number_format,
+            thumbnail,
+            hooks: self.subsector.hooks().to_vec(),
+        };
+
+        // Write the sheets in the background so the GUI can show progress in the meantime
+        let (progress_tx, progress_rx) = mpsc::channel();
+        let file_io = Arc::clone(&self.file_io);
+        platform::spawn(move || {
+            write_world_sheets(
+                file_io.as_ref(),
+                &directory,
+                &worlds,
+                &options,
+                &progress_tx,
+            )
         });
 
-        Self {
-            belt_str: String::new(),
-            berthing_cost_str: String::new(),
-            can_exit: false,
-            diameter_str: String::new(),
-            faction_idx: 0,
-            gas_giant_str: String::new(),
-            message_rx,
-            message_tx,
-            point: Point::default(),
-            point_selected: false,
-            point_str: String::new(),
-            popup_queue: Vec::new(),
-            save_directory: DEFAULT_DIRECTORY.to_string(),
-            save_filename: String::new(),
-            subsector,
-            subsector_edited: false,
-            subsector_grid_image: None,
-            tab: gui::TabLabel::WorldSurvey,
-            worker_rx,
-            worker_tx,
-            world: World::empty(),
-            world_edited: false,
-            world_selected: false,
-        }
+        self.sheet_export_progress_popup(total, progress_rx);
+        Ok(Some(()))
     }
 
-    fn export_column_delimited_table(&self) -> MessageResult {
-        let filename = format!("{} Subsector Table.txt", self.subsector.name());
+    fn confirm_export_player_handout(&mut self, options: HandoutOptions) -> MessageResult {
+        self.settings.handout_options = options;
+        // Make sure the handout includes any unapplied changes to the selected world
+        self.apply_world_changes()?;
+
+        let filename = format!("{} Subsector Handout.md", self.subsector.name());
         let result = save_file_dialog(
+            self.file_io.as_ref(),
             &self.save_directory,
             &filename,
-            "Plain Text",
-            &["txt"],
-            self.subsector.to_t5_table(),
+            "Markdown",
+            &["md"],
+            self.subsector
+                .to_handout(&options, &self.settings.number_format),
         );
 
         match result {
             Ok(Some(_)) => Ok(Some(())),
             Ok(None) => Ok(None),
             Err(e) => {
-                MessageDialog::new()
-                    .set_type(MessageType::Error)
-                    .set_title("Error: Failed to Save Summary Table")
-                    .set_text(&format!("{}", e)[..])
-                    .show_alert()
-                    .unwrap();
+                self.file_io
+                    .show_error("Error: Failed to Save Player Handout", &e.to_string());
                 Err(e.to_string())
             }
         }
     }
 
-    fn export_player_safe_subsector_json(&mut self) -> MessageResult {
-        let filename = format!("{} Subsector Player-Safe.json", self.subsector.name());
-        let result = save_file_dialog(
-            &self.save_directory,
-            &filename,
-            "JSON",
-            &["json"],
-            self.subsector.copy_player_safe().to_json(),
-        );
+    fn confirm_export_subsector_map(
+        &mut self,
+        format: MapFormat,
+        options: MapOptions,
+    ) -> MessageResult {
+        self.settings.map_options = options.clone();
+
+        // Standalone maps don't use the template, so there's no need to load the user's
+        let template = match options.effective_renderer() {
+            MapRenderer::Template => self.settings.load_map_template(),
+            MapRenderer::Standalone => Ok(DEFAULT_MAP_TEMPLATE.clone()),
+        };
+        let template = match template {
+            Ok(template) => template,
+            Err(e) => {
+                self.file_io
+                    .show_error("Error: Failed to Load Map Template", &e.to_string());
+                return Err(e.to_string());
+            }
+        };
 
-        match result {
-            Ok(Some(_)) => Ok(Some(())),
-            Ok(None) => Ok(None),
+        let pages = match self
+            .subsector
+            .generate_svg_pages(&template, &options, &self.travel_route)
+        {
+            Ok(pages) => pages,
             Err(e) => {
-                MessageDialog::new()
-                    .set_type(MessageType::Error)
-                    .set_title("Error: Failed to Save Player Safe JSON")
-                    .set_text(&format!("{}", e)[..])
-                    .show_alert()
-                    .unwrap();
-                Err(e.to_string())
+                self.file_io
+                    .show_error("Error: Failed to Draw Map", &e.to_string());
+                return Err(e.to_string());
             }
+        };
+        let mut contents = Vec::new();
+        for svg in pages {
+            contents.push(match format {
+                MapFormat::Svg => svg.into_bytes(),
+                MapFormat::Png => match gui::svg_to_png(&svg, MAP_PNG_ZOOM) {
+                    Ok(png) => png,
+                    Err(e) => {
+                        self.file_io.show_error("Error: Failed to Render PNG", &e);
+                        return Err(e);
+                    }
+                },
+            });
         }
-    }
 
-    fn export_subsector_map_svg(&mut self) -> MessageResult {
-        let filename = format!("{} Subsector Map.svg", self.subsector.name());
-        let result = save_file_dialog(
-            &self.save_directory,
-            &filename,
-            "SVG",
-            &["svg"],
-            self.subsector.generate_svg(COLORED),
+        let filename = format!(
+            "{} Subsector Map.{}",
+            self.subsector.name(),
+            format.extension()
         );
+        let result = if contents.len() == 1 {
+            save_file_dialog(
+                self.file_io.as_ref(),
+                &self.save_directory,
+                &filename,
+                &format.to_string(),
+                &[format.extension()],
+                contents.remove(0),
+            )
+        } else {
+            save_tiles_dialog(
+                self.file_io.as_ref(),
+                &self.save_directory,
+                &filename,
+                &format.to_string(),
+                &[format.extension()],
+                &contents,
+            )
+        };
 
         match result {
             Ok(Some(_)) => Ok(Some(())),
             Ok(None) => Ok(None),
             Err(e) => {
-                MessageDialog::new()
-                    .set_type(MessageType::Error)
-                    .set_title("Error: Failed to Save SVG")
-                    .set_text(&format!("{}", e)[..])
-                    .show_alert()
-                    .unwrap();
+                self.file_io
+                    .show_error(&format!("Error: Failed to Save {}", format), &e.to_string());
                 Err(e.to_string())
             }
         }
     }
 
-    fn has_unsaved_changes(&self) -> bool {
-        self.subsector_edited || self.world_edited
-    }
+    /** Generate a batch of `count` subsectors in parallel and preview them for the user to pick from.
 
-    fn hex_grid_clicked(&mut self, new_point: Point) -> MessageResult {
-        if self.world_edited {
-            self.unapplied_world_popup(new_point);
-            Ok(Some(()))
-        } else {
-            self.confirm_hex_grid_clicked(new_point)?;
-            Ok(Some(()))
+    The subsectors are named in `name_style` and have the generation script run on them, just like
+    a single regenerated subsector. Their map previews are rasterized in parallel as well.
+    */
+    fn confirm_generate_batch(
+        &mut self,
+        count: usize,
+        world_abundance_dm: i16,
+        clustering: u8,
+        name_style: NameStyle,
+        rule_set: RuleSet,
+    ) -> MessageResult {
+        let script = self.generation_script()?;
+        let mut subsectors = Subsector::new_batch(count, world_abundance_dm, clustering, rule_set);
+        for subsector in subsectors.iter_mut() {
+            subsector.set_name(random_subsector_name(name_style));
+            if let Some(script) = &script {
+                if let Err(e) = subsector.run_script(script) {
+                    self.file_io
+                        .show_error("Error: Failed to Run Generation Script", &e.to_string());
+                    return Err(e.to_string());
+                }
+            }
         }
-    }
 
-    fn load_world(&mut self, new_world_loc: &Point) -> MessageResult {
-        if let Some(world) = self.subsector.get_world(new_world_loc) {
-            self.world_selected = true;
-            self.world = world.clone();
-            self.berthing_cost_str = self.world.starport.berthing_cost.to_string();
-            self.diameter_str = self.world.diameter.to_string();
-            self.point_str = self.point.to_string();
-            self.gas_giant_str = self.world.gas_giants.to_string();
-            self.belt_str = self
-                .world
-                .planetoid_belts
-                .expect("World planetoid belts should not be None")
-                .to_string();
-            Ok(Some(()))
-        } else {
-            Err(format!("Could not load world from point {}", new_world_loc))
-        }
+        let options = MapOptions {
+            layout: MapLayout::default(),
+            renderer: MapRenderer::Standalone,
+            ..self.settings.map_options.clone()
+        };
+        let previews = platform::map_parallel(&subsectors, |subsector| {
+            // A preview that can't be drawn shouldn't hide the rest of the batch
+            let svg = subsector
+                .generate_svg(&DEFAULT_MAP_TEMPLATE, &options, &[])
+                .unwrap_or_else(|_| subsector.generate_fallback_grid_svg(options.hex_numbers));
+            gui::rasterize_map_preview(&svg)
+        })
+        .into_iter()
+        .collect::<Result<Vec<_>, String>>()?;
+
+        self.batch_candidates = subsectors.into_iter().zip(previews).collect();
+        Ok(Some(()))
     }
 
-    /** Queue a message to be handled at the beginning of the next frame. */
-    fn message(&self, message: Message) {
-        self.message_tx.send(message);
+    /** Open a copy of the `Subsector` advanced by `decades` decades in a new tab.
+
+    The original is left as it is so that the new tab can serve as an alternate timeline. The
+    changes are summarized in a window until it is closed.
+    */
+    fn confirm_fast_forward(&mut self, decades: u32, rules: GrowthRules) -> MessageResult {
+        self.settings.growth_rules = rules;
+
+        let mut subsector = self.subsector.clone();
+        let changes = subsector.fast_forward(decades, &rules);
+        let summary = gui::FastForwardSummary::new(&subsector, decades, changes);
+        self.open_tab(subsector)?;
+        self.fast_forward_summary = Some(summary);
+        self.subsector_edited = true;
+        Ok(Some(()))
     }
 
-    /** Handle a `Message` generated by a GUI event immediately.
+    /** Add random worlds to `count` random empty hexes and select the last one added.
 
-    # Returns
-    - `Ok(Some(()))` if the message was handled successfully
-    - `Ok(None)` if no error occurred but the message was not handled; usually this means the user
-       cancelled the action before anything could result from it
-    - `Err(msg)` if an error occurred while handling the message
+    Stops early if the subsector fills up, and does nothing if it was already full.
     */
-    fn message_immediate(&mut self, message: Message) -> MessageResult {
-        use Message::*;
-        match message {
-            AddNewFaction => self.add_new_faction(),
-            AddNewWorld => self.add_new_world(),
-
-            ApplyConfirmHexGridClicked { new_point } => {
-                self.apply_confirm_hex_grid_clicked(new_point)
-            }
+    fn confirm_fill_empty_hexes(&mut self, count: usize) -> MessageResult {
+        self.apply_world_changes()?;
 
-            ApplyWorldChanges => self.apply_world_changes(),
-            CancelLocUpdate => self.cancel_loc_update(),
-            CancelUnsavedExit => self.cancel_unsaved_exit(),
-            ConfigRegenSubsector => self.config_regen_subsector(),
-            ConfirmHexGridClicked { new_point } => self.confirm_hex_grid_clicked(new_point),
-            ConfirmImportJson => self.confirm_import_json(),
-            ConfirmLocUpdate { location } => self.confirm_loc_update(location),
+        let mut last_point = None;
+        for _ in 0..count {
+            let point = match self.subsector.random_empty_point() {
+                Some(point) => point,
+                None => break,
+            };
+            self.subsector.insert_random_world(&point)?;
+            last_point = Some(point);
+        }
 
-            ConfirmRegenSubsector { world_abundance_dm } => {
-                self.confirm_regen_subsector(world_abundance_dm)
+        match last_point {
+            Some(point) => {
+                self.confirm_hex_grid_clicked(point)?;
+                self.subsector_model_updated()?;
+                Ok(Some(()))
             }
-
-            ConfirmRegenWorld => self.confirm_regen_world(),
-            ConfirmRemoveWorld { point } => self.confirm_remove_world(point),
-            ConfirmRenameSubsector { new_name } => self.confirm_rename_subsector(new_name),
-            ConfirmUnsavedExit => self.confirm_unsaved_exit(),
-            ExportColumnDelimitedTable => self.export_column_delimited_table(),
-            ExportPlayerSafeSubsectorJson => self.export_player_safe_subsector_json(),
-            ExportSubsectorMapSvg => self.export_subsector_map_svg(),
-            HexGridClicked { new_point } => self.hex_grid_clicked(new_point),
-            NewFactionGovSelected { new_code } => self.new_faction_gov_selected(new_code),
-            NewFactionStrengthSelected { new_code } => self.new_faction_strength_selected(new_code),
-            NewStarportClassSelected => self.new_starport_class_selected(),
-            NewWorldCultureSelected { new_code } => self.new_world_culture_selected(new_code),
-            NewWorldGovSelected { new_code } => self.new_world_gov_selected(new_code),
-            NewWorldTagSelected { index, new_code } => self.new_world_tag_selected(index, new_code),
-            NoOp => Ok(None),
-            OpenJson => self.open_json(),
-            RegenSelectedFaction => self.regen_selected_faction(),
-            RegenSelectedWorld => self.regen_selected_world(),
-            RegenSubsector => self.regen_subsector(),
-            RegenWorldAtmosphere => self.regen_world_atmosphere(),
-            RegenWorldCulture => self.regen_world_culture(),
-            RegenWorldGovernment => self.regen_world_government(),
-            RegenWorldHydrographics => self.regen_world_hydrographics(),
-            RegenWorldLawLevel => self.regen_world_law_level(),
-            RegenWorldPopulation => self.regen_world_population(),
-            RegenWorldSize => self.regen_world_size(),
-            RegenWorldStarport => self.regen_world_starport(),
-            RegenWorldTag { index } => self.regen_world_tag(index),
-            RegenWorldTechLevel => self.regen_world_tech_level(),
-            RegenWorldTemperature => self.regen_world_temperature(),
-            RemoveSelectedFaction => self.remove_selected_faction(),
-            RemoveSelectedWorld => self.remove_selected_world(),
-            RenameSubsector => self.rename_subsector(),
-            RevertWorldChanges => self.revert_world_changes(),
-            Save => self.save(),
-            SaveAs => self.save_as(),
-            SaveConfigRegenSubsector => self.save_config_regen_subsector(),
-            SaveConfirmImportJson => self.save_confirm_import_json(),
-            SaveExit => self.save_exit(),
-            WorldBerthingCostsUpdated => self.world_berthing_costs_updated(),
-            WorldDiameterUpdated => self.world_diameter_updated(),
-            WorldGasGiantsUpdated => self.world_gas_giants_updated(),
-            WorldLocUpdated => self.world_loc_updated(),
-            WorldModelUpdated => self.world_model_updated(),
-            WorldPlanetoidBeltsUpdated => self.world_planetoid_belts_updated(),
+            None => Ok(None),
         }
     }
 
-    fn new_faction_gov_selected(&mut self, new_code: u16) -> MessageResult {
-        if let Some(faction) = self.world.factions.get_mut(self.faction_idx) {
-            faction
-                .government
-                .safe_mutate(&TABLES.gov_table[new_code as usize]);
-            self.world_model_updated()?;
-            Ok(Some(()))
-        } else {
-            Ok(None)
-        }
-    }
+    fn confirm_hex_grid_clicked(&mut self, new_point: Point) -> MessageResult {
+        self.point_selected = true;
+        self.point = new_point;
+        self.faction_idx = 0;
 
-    fn new_faction_strength_selected(&mut self, new_code: u16) -> MessageResult {
-        if let Some(faction) = self.world.factions.get_mut(self.faction_idx) {
-            let faction_strength = &TABLES.faction_table[new_code as usize];
-            faction.code = faction_strength.code;
-            faction.strength = faction_strength.strength.clone();
-            self.world_model_updated()?;
-            Ok(Some(()))
+        if self.subsector.get_world(&self.point).is_some() {
+            self.load_world(&new_point)?;
         } else {
-            Ok(None)
+            self.world_selected = false;
         }
+        Ok(Some(()))
     }
 
-    fn new_starport_class_selected(&mut self) -> MessageResult {
-        let starport = TABLES
-            .starport_table
-            .iter()
-            .find(|starport| starport.class == self.world.starport.class)
-            .unwrap();
+    fn confirm_import_json(&mut self) -> MessageResult {
+        let result = load_file_to_string(
+            self.file_io.as_ref(),
+            &self.save_directory,
+            "JSON",
+            &["json"],
+        );
 
-        self.world.starport = starport.clone();
-        self.world.generate_berthing_cost();
-        self.berthing_cost_str = self.world.starport.berthing_cost.to_string();
-        self.world_model_updated()?;
-        Ok(Some(()))
-    }
+        let (path, json) = match result {
+            Ok(Some((path, json))) => (path, json),
+            Ok(None) => return Ok(None),
+            Err(e) => {
+                self.file_io
+                    .show_error("Error: Failed to Read JSON", &e.to_string());
+                return Err(e.to_string());
+            }
+        };
 
-    fn new_world_culture_selected(&mut self, new_code: u16) -> MessageResult {
-        self.world
-            .culture
-            .safe_mutate(&TABLES.culture_table[new_code as usize]);
-        self.world_model_updated()?;
-        Ok(Some(()))
+        self.load_json(&path, &json)
     }
 
-    fn new_world_gov_selected(&mut self, new_code: u16) -> MessageResult {
-        self.world
-            .government
-            .safe_mutate(&TABLES.gov_table[new_code as usize]);
-        self.world_model_updated()?;
-        Ok(Some(()))
+    fn confirm_import_text(&mut self, subsector: Subsector) -> MessageResult {
+        self.merge_imported_worlds(subsector)
     }
 
-    fn new_world_tag_selected(&mut self, index: usize, new_code: u16) -> MessageResult {
-        if let Some(tag) = self.world.world_tags.get_mut(index) {
-            tag.safe_mutate(&TABLES.world_tag_table[new_code as usize]);
-            self.world_model_updated()?;
-            Ok(Some(()))
-        } else {
-            Ok(None)
-        }
+    fn confirm_import_traveller_map(&mut self, subsector: Subsector) -> MessageResult {
+        self.open_tab(subsector)?;
+        self.flag_duplicate_names();
+        self.subsector_model_updated()
     }
 
-    fn open_json(&mut self) -> MessageResult {
-        if self.has_unsaved_changes() {
-            self.unsaved_subsector_reload_popup();
-            Ok(Some(()))
-        } else {
-            self.confirm_import_json()
+    fn confirm_import_worlds(
+        &mut self,
+        subsector: Subsector,
+        resolution: MergeResolution,
+    ) -> MessageResult {
+        if self.subsector.merge(subsector, resolution) == 0 {
+            return Ok(None);
         }
-    }
-
-    fn process_hotkeys(&mut self, ctx: &Context) {
-        let hotkeys = [
-            (Modifiers::CTRL, Key::N, Message::RenameSubsector),
-            (Modifiers::CTRL, Key::O, Message::OpenJson),
-            (Modifiers::CTRL, Key::S, Message::Save),
-            (Modifiers::CTRL | Modifiers::SHIFT, Key::S, Message::SaveAs),
-        ];
 
-        for (modifiers, key, message) in hotkeys {
-            if ctx.input_mut().consume_key(modifiers, key) {
-                self.message(message);
-            }
+        // Pick up any world that was merged into the selected hex
+        if self.point_selected && !self.world_edited {
+            self.confirm_hex_grid_clicked(self.point)?;
         }
+        self.flag_duplicate_names();
+        self.subsector_model_updated()?;
+        Ok(Some(()))
     }
 
-    /** Process all messages in the queue. */
-    fn process_message_queue(&mut self) {
-        while !self.message_rx.is_empty() {
-            let message = self.message_rx.receive().unwrap();
-            let _ = self.message_immediate(message);
+    fn confirm_leave_player_view(&mut self, passphrase: String) -> MessageResult {
+        if !self.player_view {
+            return Ok(None);
+        }
+        if self
+            .player_view_passphrase
+            .as_ref()
+            .is_some_and(|expected| *expected != passphrase)
+        {
+            return Err("Incorrect passphrase".to_string());
         }
-    }
 
-    // TODO: current unneeded but drawing the world allegiances might be done by changing the svg
-    #[allow(dead_code)]
-    fn redraw_subsector_grid(&mut self) -> MessageResult {
-        let svg = self.subsector.generate_grid_svg();
-        self.worker_tx
-            .send(svg)
-            .expect("Subsector map worker thread should never hang up.");
+        self.player_view = false;
+        self.player_view_passphrase = None;
         Ok(Some(()))
     }
 
-    fn regen_selected_faction(&mut self) -> MessageResult {
-        let index = self.faction_idx;
-        if let Some(faction) = self.world.factions.get_mut(index) {
-            let mut old_gov = faction.government.clone();
-            let name = faction.name.clone();
-            *faction = Faction::random();
+    fn confirm_loc_update(&mut self, location: Point) -> MessageResult {
+        let result = match self.subsector.move_world(&self.point, &location) {
+            Ok(_) => {
+                self.point = location;
+                self.world_model_updated()?;
+                self.subsector_model_updated()?;
+                Ok(Some(()))
+            }
 
-            faction.name = name;
-            old_gov.safe_mutate(&faction.government);
-            faction.government = old_gov;
-            self.world_model_updated()?;
-            Ok(Some(()))
-        } else {
-            Ok(None)
-        }
+            Err(e) => Err(e),
+        };
+        self.point_buffer.set_text(&self.point);
+        result
     }
 
-    fn regen_selected_world(&mut self) -> MessageResult {
-        self.regen_world_popup();
+    /** Replace the loaded subsector with an empty one called `name`, for placing worlds by hand. */
+    fn confirm_new_empty_subsector(&mut self, name: String) -> MessageResult {
+        let directory = self.save_directory.clone();
+        let mut subsector = Subsector::empty();
+        subsector.set_name(name);
+        *self = Self {
+            copied_world: self.copied_world.take(),
+            documents: mem::take(&mut self.documents),
+            file_io: Arc::clone(&self.file_io),
+            message_log: mem::take(&mut self.message_log),
+            save_directory: directory,
+            settings: self.settings.clone(),
+            ship_spec: self.ship_spec.clone(),
+            show_debug_console: self.show_debug_console,
+            tab_index: self.tab_index,
+            ..Self::from(subsector)
+        };
         Ok(Some(()))
     }
 
-    fn regen_subsector(&mut self) -> MessageResult {
-        if self.has_unsaved_changes() {
-            self.unsaved_subsector_regen_popup();
-            Ok(Some(()))
-        } else {
-            self.config_regen_subsector()?;
-            Ok(Some(()))
+    /** Put the selected world through a political crisis, rolling the kind of crisis if `None`. */
+    fn confirm_political_crisis(&mut self, event: Option<PoliticalEvent>) -> MessageResult {
+        if !self.world_selected {
+            return Ok(None);
         }
-    }
 
-    fn regen_world_atmosphere(&mut self) -> MessageResult {
-        self.world.generate_atmosphere();
-        self.world_model_updated()?;
-        Ok(Some(()))
+        let event = event.unwrap_or_else(|| PoliticalEvent::random(&self.world));
+        match self.world.political_crisis(event) {
+            Some(_) => {
+                self.world_model_updated()?;
+                Ok(Some(()))
+            }
+            None => Err(format!(
+                "{} can't have a {} crisis",
+                self.world.name,
+                event.to_string().to_lowercase()
+            )),
+        }
     }
 
-    fn regen_world_culture(&mut self) -> MessageResult {
-        let mut old_culture = self.world.culture.clone();
-        self.world.generate_culture();
-        old_culture.safe_mutate(&self.world.culture);
-        self.world.culture = old_culture;
-        self.world_model_updated()?;
-        Ok(Some(()))
-    }
+    fn confirm_regen_attribute(&mut self, attribute: WorldAttribute) -> MessageResult {
+        // The selected world is rerolled too, so unapplied edits would otherwise be lost
+        self.apply_world_changes()?;
+        if self.subsector.regenerate_attribute(attribute) == 0 {
+            return Ok(None);
+        }
 
-    fn regen_world_government(&mut self) -> MessageResult {
-        let mut old_gov = self.world.government.clone();
-        self.world.generate_government();
-        old_gov.safe_mutate(&self.world.government);
-        self.world.government = old_gov;
-        self.world_model_updated()?;
-        Ok(Some(()))
+        if self.world_selected {
+            let point = self.point;
+            self.load_world(&point)?;
+        }
+        self.subsector_model_updated()
     }
 
-    fn regen_world_hydrographics(&mut self) -> MessageResult {
-        self.world.generate_hydrographics();
-        self.world_model_updated()?;
+    fn confirm_regen_subsector(
+        &mut self,
+        world_abundance_dm: i16,
+        clustering: u8,
+        name_style: NameStyle,
+        rule_set: RuleSet,
+    ) -> MessageResult {
+        let directory = self.save_directory.clone();
+        *self = Self {
+            copied_world: self.copied_world.take(),
+            documents: mem::take(&mut self.documents),
+            file_io: Arc::clone(&self.file_io),
+            message_log: mem::take(&mut self.message_log),
+            save_directory: directory,
+            settings: self.settings.clone(),
+            ship_spec: self.ship_spec.clone(),
+            show_debug_console: self.show_debug_console,
+            tab_index: self.tab_index,
+            ..Self::with_world_abundance(world_abundance_dm, clustering, rule_set)
+        };
+        self.subsector.set_name(random_subsector_name(name_style));
+
+        if let Some(script) = self.generation_script()? {
+            if let Err(e) = self.subsector.run_script(&script) {
+                self.file_io
+                    .show_error("Error: Failed to Run Generation Script", &e.to_string());
+                return Err(e.to_string());
+            }
+            self.saved_worlds = self.subsector.get_map().clone();
+        }
+        self.flag_duplicate_names();
         Ok(Some(()))
     }
 
-    fn regen_world_law_level(&mut self) -> MessageResult {
-        self.world.generate_law_level();
-        self.world_model_updated()?;
-        Ok(Some(()))
+    fn confirm_regen_world(&mut self, weighting: u8) -> MessageResult {
+        let subtype = self.world.subtype;
+        let result = if weighting == 0 {
+            self.subsector.insert_random_world(&self.point)
+        } else {
+            self.subsector
+                .insert_contextual_world(&self.point, weighting as f64 / 100.0)
+        };
+        match result {
+            Ok(_) => {
+                // The new world is held to the limits of the old one's subtype
+                if let Some(mut world) = self.subsector.get_world(&self.point).cloned() {
+                    world.set_subtype(subtype);
+                    self.subsector.insert_world(&self.point, world)?;
+                }
+
+                self.world_selected = false;
+                self.confirm_hex_grid_clicked(self.point)?;
+                self.subsector_model_updated()?;
+                Ok(Some(()))
+            }
+            Err(e) => Err(e),
+        }
     }
 
-    fn regen_world_population(&mut self) -> MessageResult {
-        self.world.generate_population();
-        self.world_model_updated()?;
-        Ok(Some(()))
+    fn confirm_remove_world(&mut self, point: Point) -> MessageResult {
+        self.world_selected = false;
+        match self.subsector.remove_world(&point) {
+            Ok(Some(world)) => {
+                self.deleted_worlds.push((point, world));
+                self.subsector_model_updated()?;
+                Ok(Some(()))
+            }
+            Ok(None) => Ok(None),
+            Err(e) => Err(e),
+        }
     }
 
-    fn regen_world_size(&mut self) -> MessageResult {
-        self.world.generate_size();
-        self.diameter_str = self.world.diameter.to_string();
-        self.world_model_updated()?;
+    fn confirm_rename_subsector(&mut self, new_name: String) -> MessageResult {
+        self.subsector.set_name(new_name);
+        self.subsector_model_updated()?;
         Ok(Some(()))
     }
 
-    fn regen_world_starport(&mut self) -> MessageResult {
-        self.world.generate_starport();
-        self.berthing_cost_str = self.world.starport.berthing_cost.to_string();
+    fn confirm_reroll_dependents(&mut self, world: Box<World>) -> MessageResult {
+        self.world = *world;
+        self.berthing_cost_buffer
+            .set_text(&self.world.starport.berthing_cost);
         self.world_model_updated()?;
         Ok(Some(()))
     }
 
-    fn regen_world_tag(&mut self, index: usize) -> MessageResult {
-        match self.world.generate_world_tag(index) {
-            Some(mut old_tag) => {
-                old_tag.safe_mutate(&self.world.world_tags[index]);
-                self.world.world_tags[index] = old_tag;
-                self.world_model_updated()?;
-                Ok(Some(()))
-            }
-            None => Ok(None),
+    fn confirm_set_travel_codes(&mut self, policy: TravelCodePolicy) -> MessageResult {
+        if self.subsector.apply_travel_code_policy(&policy).is_empty() {
+            return Ok(None);
         }
-    }
 
-    fn regen_world_tech_level(&mut self) -> MessageResult {
-        self.world.generate_tech_level();
-        self.world_model_updated()?;
+        // Make sure the selected world reflects the new code, unless that would throw away user edits
+        if self.world_selected && !self.world_edited {
+            let point = self.point;
+            self.load_world(&point)?;
+        }
+        self.subsector_model_updated()?;
         Ok(Some(()))
     }
 
-    fn regen_world_temperature(&mut self) -> MessageResult {
-        self.world.generate_temperature();
-        self.world_model_updated()?;
+    fn confirm_table_overrides(&mut self, overrides: TableOverrides) -> MessageResult {
+        let path = match &self.settings.table_overrides_path {
+            Some(path) => path.clone(),
+            None => match save_path_dialog(
+                self.file_io.as_ref(),
+                &self.save_directory,
+                "table_overrides.json",
+                "JSON",
+                &["json"],
+            ) {
+                Ok(Some(path)) => path,
+                Ok(None) => return Ok(None),
+                Err(e) => return Err(e.to_string()),
+            },
+        };
+
+        // Only keep the overrides if every one of them can be applied
+        if let Err(e) = set_table_overrides(Some(overrides.clone())) {
+            self.file_io
+                .show_error("Error: Invalid Table Override", &e.to_string());
+            return Err(e.to_string());
+        }
+
+        if let Err(e) = self.file_io.write(&path, overrides.to_json().as_bytes()) {
+            self.file_io
+                .show_error("Error: Failed to Save Table Overrides", &e.to_string());
+            return Err(e.to_string());
+        }
+
+        self.settings.table_overrides_path = Some(path);
         Ok(Some(()))
     }
 
-    fn remove_selected_faction(&mut self) -> MessageResult {
-        self.faction_idx = self.world.remove_faction(self.faction_idx);
-        self.world_model_updated()?;
+    fn confirm_unsaved_exit(&mut self) -> MessageResult {
+        self.can_exit = true;
         Ok(Some(()))
     }
 
-    fn remove_selected_world(&mut self) -> MessageResult {
-        self.remove_world_popup();
-        Ok(Some(()))
+    fn copy_roll_log(&mut self) -> MessageResult {
+        if self.world_selected && !self.world.roll_log.is_empty() {
+            self.copied_text = Some(self.world.roll_log_text());
+            Ok(Some(()))
+        } else {
+            Ok(None)
+        }
     }
 
-    fn rename_subsector(&mut self) -> MessageResult {
-        self.subsector_rename_popup();
-        Ok(Some(()))
+    fn copy_world(&mut self) -> MessageResult {
+        if self.world_selected {
+            self.copied_world = Some(self.world.clone());
+            Ok(Some(()))
+        } else {
+            Ok(None)
+        }
     }
 
-    fn revert_world_changes(&mut self) -> MessageResult {
+    fn copy_world_summary(&mut self, format: SummaryFormat) -> MessageResult {
         if self.world_selected {
-            let point = self.point;
-            self.load_world(&point)?;
+            self.copied_text = Some(self.world.summary(
+                &self.point,
+                format,
+                &self.settings.number_format,
+            ));
             Ok(Some(()))
         } else {
-            unreachable!("Reverting a world without one selected should be impossible");
+            Ok(None)
         }
     }
 
-    fn save(&mut self) -> MessageResult {
-        // Make sure any unapplied changes the selected world are also saved
-        self.apply_world_changes()?;
+    fn delete_generation_preset(&mut self, name: String) -> MessageResult {
+        let count = self.settings.generation_presets.len();
+        self.settings
+            .generation_presets
+            .retain(|preset| preset.name != name);
+        if self.settings.generation_presets.len() < count {
+            Ok(Some(()))
+        } else {
+            Ok(None)
+        }
+    }
 
-        let directory: &Path = self.save_directory.as_ref();
-        let filename: &Path = self.save_filename.as_ref();
-        let path = directory.join(filename);
+    fn discard_batch(&mut self) -> MessageResult {
+        if self.batch_candidates.is_empty() {
+            return Ok(None);
+        }
+        self.batch_candidates.clear();
+        Ok(Some(()))
+    }
 
-        if self.save_filename.is_empty() || !path.exists() {
-            // This is our first time saving or the path has been invalidated underneath us
-            self.save_as()
-        } else {
-            let result = save_file(
-                &self.save_directory,
-                &self.save_filename,
-                self.subsector.to_json(),
-            );
-            match result {
-                Ok(()) => {
-                    self.subsector_edited = false;
-                    Ok(Some(()))
-                }
+    fn edit_table_overrides(&mut self) -> MessageResult {
+        let overrides = match &self.settings.table_overrides_path {
+            Some(path) => match load_table_overrides(self.file_io.as_ref(), path) {
+                Ok(overrides) => overrides,
                 Err(e) => {
-                    MessageDialog::new()
-                        .set_type(MessageType::Error)
-                        .set_title("Error: Failed to Save JSON")
-                        .set_text(&format!("{}", e)[..])
-                        .show_alert()
-                        .unwrap();
-                    Err(e.to_string())
+                    self.file_io
+                        .show_error("Error: Failed to Read Table Overrides", &e.to_string());
+                    return Err(e.to_string());
                 }
-            }
-        }
+            },
+            None => TableOverrides::default(),
+        };
+        self.table_overrides_popup(overrides);
+        Ok(Some(()))
     }
 
-    fn save_as(&mut self) -> MessageResult {
-        // Make sure any unapplied changes the selected world are also saved
-        self.apply_world_changes()?;
+    fn empty() -> Self {
+        let subsector = Subsector::empty();
+        let (message_tx, message_rx) = pipe::channel();
 
-        let default_filename = format!("{} Subsector.json", self.subsector.name());
-        let filename = if !self.save_filename.is_empty() {
-            &self.save_filename
-        } else {
-            // This is our first time saving
-            &default_filename
-        };
+        // Rasterize SVGs in the background where the platform allows it
+        let map_worker = platform::Worker::new(gui::rasterize_svg);
 
-        let default_directory = DEFAULT_DIRECTORY.to_string();
-        let directory = if <String as AsRef<Path>>::as_ref(&self.save_directory).is_dir() {
-            &self.save_directory
-        } else {
-            // The directory has been invalidated underneath us
-            &default_directory
-        };
+        Self {
+            annotation_color: AnnotationColor::default(),
+            annotation_draft: None,
+            annotation_text: String::new(),
+            annotation_tool: None,
+            batch_candidates: Vec::new(),
+            belt_buffer: gui::BufferedField::new(
+                Message::WorldPlanetoidBeltsUpdated,
+                "a whole number of planetoid belts",
+            )
+            .submit_on_enter(),
+            berthing_cost_buffer: gui::BufferedField::new(
+                Message::WorldBerthingCostsUpdated,
+                "a whole number of credits",
+            ),
+            can_exit: false,
+            compared_world: None,
+            copied_text: None,
+            copied_world: None,
+            deleted_worlds: Vec::new(),
+            dice_roller: gui::DiceRoller::default(),
+            diameter_buffer: gui::BufferedField::new(
+                Message::WorldDiameterUpdated,
+                "a whole number of km",
+            ),
+            documents: Vec::new(),
+            faction_idx: 0,
+            fast_forward_summary: None,
+            file_io: Arc::new(PlatformFileIo),
+            file_pick_message: None,
+            gas_giant_buffer: gui::BufferedField::new(
+                Message::WorldGasGiantsUpdated,
+                "a whole number of gas giants",
+            )
+            .submit_on_enter(),
+            journal_editing: None,
+            jump_range_jumps: 1,
+            jump_range_rating: 2,
+            library_query: LibraryQuery::default(),
+            library_search: None,
+            map_redraw: MapRedraw::default(),
+            map_search: String::new(),
+            map_view: gui::MapView::default(),
+            map_worker,
+            measure_mode: false,
+            measurement: None,
+            message_log: MessageLog::default(),
+            message_rx,
+            message_tx,
+            player_view: false,
+            player_view_passphrase: None,
+            point: Point::default(),
+            point_selected: false,
+            point_buffer: gui::BufferedField::new(Message::WorldLocUpdated, "a hex like 0101")
+                .submit_on_enter(),
+            polity_filter: None,
+            popup_queue: Vec::new(),
+            rules_search: String::new(),
+            save_directory: DEFAULT_DIRECTORY.to_string(),
+            save_file_checked: None,
+            save_file_modified: None,
+            save_filename: String::new(),
+            saved_worlds: BTreeMap::new(),
+            settings: Settings::default(),
+            ship_spec: ShipSpec::default(),
+            show_bookmarks: false,
+            show_debug_console: false,
+            show_deleted_worlds: false,
+            show_dice_panel: false,
+            show_duplicate_names: false,
+            show_hazards: false,
+            show_hooks: false,
+            show_journal: false,
+            show_jump_range: false,
+            show_library: false,
+            show_polities: false,
+            show_travel_planner: false,
+            show_unsaved_worlds: false,
+            split_view: false,
+            subsector,
+            subsector_edited: false,
+            subsector_grid_image: None,
+            tab: gui::TabLabel::WorldSurvey,
+            tab_index: 0,
+            travel_route: Vec::new(),
+            viewer_mode: false,
+            world: World::empty(),
+            world_edited: false,
+            world_image: None,
+            world_selected: false,
+            world_thumbnail: None,
+        }
+    }
 
+    fn export_all_world_sheets(&mut self) -> MessageResult {
+        self.world_sheet_export_popup();
+        Ok(Some(()))
+    }
+
+    fn export_column_delimited_table(&self) -> MessageResult {
+        let filename = format!("{} Subsector Table.txt", self.subsector.name());
         let result = save_file_dialog(
-            directory,
-            filename,
-            "JSON",
-            &["json"],
-            self.subsector.to_json(),
+            self.file_io.as_ref(),
+            &self.save_directory,
+            &filename,
+            "Plain Text",
+            &["txt"],
+            self.subsector.to_t5_table(),
         );
 
         match result {
-            Ok(Some(path)) => {
-                self.save_directory = path.parent().unwrap().to_str().unwrap().to_string();
-                self.save_filename = path.file_name().unwrap().to_str().unwrap().to_string();
-                self.subsector_edited = false;
-                Ok(Some(()))
-            }
+            Ok(Some(_)) => Ok(Some(())),
             Ok(None) => Ok(None),
             Err(e) => {
-                MessageDialog::new()
-                    .set_type(MessageType::Error)
-                    .set_title("Error: Failed to Save JSON")
-                    .set_text(&format!("{}", e)[..])
-                    .show_alert()
-                    .unwrap();
+                self.file_io
+                    .show_error("Error: Failed to Save Summary Table", &e.to_string());
                 Err(e.to_string())
             }
         }
     }
 
-    fn save_config_regen_subsector(&mut self) -> MessageResult {
-        match self.save() {
-            Ok(Some(())) => self.config_regen_subsector(),
+    fn export_csv(&self) -> MessageResult {
+        let filename = format!("{} Subsector.csv", self.subsector.name());
+        let result = save_file_dialog(
+            self.file_io.as_ref(),
+            &self.save_directory,
+            &filename,
+            "CSV",
+            &["csv"],
+            self.subsector.to_csv(),
+        );
+
+        match result {
+            Ok(Some(_)) => Ok(Some(())),
             Ok(None) => Ok(None),
-            Err(e) => Err(e),
+            Err(e) => {
+                self.file_io
+                    .show_error("Error: Failed to Save CSV", &e.to_string());
+                Err(e.to_string())
+            }
         }
     }
 
-    fn save_confirm_import_json(&mut self) -> MessageResult {
-        match self.save() {
-            Ok(Some(())) => self.confirm_import_json(),
+    fn export_player_handout(&mut self) -> MessageResult {
+        self.handout_export_popup();
+        Ok(Some(()))
+    }
+
+    fn export_player_safe_subsector_json(&mut self) -> MessageResult {
+        let filename = format!("{} Subsector Player-Safe.json", self.subsector.name());
+        let result = save_file_dialog(
+            self.file_io.as_ref(),
+            &self.save_directory,
+            &filename,
+            "JSON",
+            &["json"],
+            self.subsector.copy_player_safe().to_json(),
+        );
+
+        match result {
+            Ok(Some(_)) => Ok(Some(())),
             Ok(None) => Ok(None),
-            Err(e) => Err(e),
+            Err(e) => {
+                self.file_io
+                    .show_error("Error: Failed to Save Player Safe JSON", &e.to_string());
+                Err(e.to_string())
+            }
         }
     }
 
-    fn save_exit(&mut self) -> MessageResult {
-        match self.save() {
-            Ok(Some(())) => {
-                self.can_exit = true;
-                Ok(Some(()))
-            }
+    fn export_sector_metadata(&self) -> MessageResult {
+        let filename = format!("{} Sector Metadata.xml", self.subsector.name());
+        let result = save_file_dialog(
+            self.file_io.as_ref(),
+            &self.save_directory,
+            &filename,
+            "XML",
+            &["xml"],
+            self.subsector.to_sector_metadata(),
+        );
+
+        match result {
+            Ok(Some(_)) => Ok(Some(())),
             Ok(None) => Ok(None),
-            Err(e) => Err(e),
+            Err(e) => {
+                self.file_io
+                    .show_error("Error: Failed to Save Sector Metadata", &e.to_string());
+                Err(e.to_string())
+            }
         }
     }
 
-    fn subsector_model_updated(&mut self) -> MessageResult {
-        self.subsector_edited = true;
+    fn export_subsector_map(&mut self) -> MessageResult {
+        self.map_export_popup();
         Ok(Some(()))
     }
 
-    fn with_world_abundance(world_abundance_dm: i16) -> Self {
-        let subsector = Subsector::new(world_abundance_dm);
-        Self {
-            subsector,
-            ..Self::empty()
-        }
+    fn fast_forward_subsector(&mut self) -> MessageResult {
+        self.fast_forward_popup();
+        Ok(Some(()))
     }
 
-    fn world_berthing_costs_updated(&mut self) -> MessageResult {
-        match self.berthing_cost_str.parse::<u32>() {
-            Ok(berthing_cost) => {
-                self.world.starport.berthing_cost = berthing_cost;
-                self.world_model_updated()?;
-                Ok(Some(()))
-            }
-            Err(_) => {
-                self.berthing_cost_str = self.world.starport.berthing_cost.to_string();
-                Ok(None)
-            }
-        }
+    fn fill_empty_hexes(&mut self) -> MessageResult {
+        self.fill_empty_hexes_popup();
+        Ok(Some(()))
     }
 
-    fn world_diameter_updated(&mut self) -> MessageResult {
-        match self.diameter_str.parse::<u32>() {
-            Ok(diameter) => {
-                self.world.diameter = diameter;
-                self.world_model_updated()?;
-                Ok(Some(()))
-            }
-            Err(_) => {
-                self.diameter_str = self.world.diameter.to_string();
-                Ok(None)
-            }
+    fn fix_all_subsector_inconsistencies(&mut self) -> MessageResult {
+        if self.subsector.inconsistencies().is_empty() {
+            return Ok(None);
         }
-    }
 
-    fn world_gas_giants_updated(&mut self) -> MessageResult {
-        let result = self.gas_giant_str.parse();
-        match result {
-            Ok(gas_giants) => {
-                self.world.gas_giants = gas_giants;
-                self.gas_giant_str = self.world.gas_giants.to_string();
-                self.world_model_updated()?;
-                Ok(Some(()))
-            }
-            Err(_) => {
-                self.gas_giant_str = self.world.gas_giants.to_string();
-                Ok(None)
+        self.subsector.fix_all_inconsistencies();
+
+        // Make sure the selected world reflects the fixes, unless that would throw away user edits
+        if self.world_selected && !self.world_edited {
+            let point = self.point;
+            self.load_world(&point)?;
+        }
+        self.subsector_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn fix_world_inconsistency(&mut self, inconsistency: Inconsistency) -> MessageResult {
+        if !self.world.inconsistencies().contains(&inconsistency) {
+            return Ok(None);
+        }
+
+        self.world.fix_inconsistency(inconsistency);
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    /** Open the duplicate names window if any worlds of the [`Subsector`] share a name. */
+    fn flag_duplicate_names(&mut self) {
+        if !self.subsector.duplicate_names().is_empty() {
+            self.show_duplicate_names = true;
+        }
+    }
+
+    /** Roll every world's fuel market again, applying any changes to the selected world first. */
+    fn fuel_market_tick(&mut self) -> MessageResult {
+        self.apply_world_changes()?;
+        self.subsector.fuel_market_tick();
+        if self.world_selected {
+            let point = self.point;
+            self.load_world(&point)?;
+        }
+        self.subsector_model_updated()
+    }
+
+    fn generate_batch(&mut self) -> MessageResult {
+        self.subsector_batch_popup();
+        Ok(Some(()))
+    }
+
+    /** Load the user's generation script, if they have chosen one.
+
+    # Returns
+    - `Ok(Some(Script))` if the script was loaded,
+    - `Ok(None)` if no script is chosen,
+    - `Err(String)` if the script couldn't be loaded, after telling the user why
+    */
+    fn generation_script(&self) -> Result<Option<Script>, String> {
+        let path = match &self.settings.generation_script_path {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+        match load_script(self.file_io.as_ref(), path) {
+            Ok(script) => Ok(Some(script)),
+            Err(e) => {
+                self.file_io
+                    .show_error("Error: Failed to Run Generation Script", &e.to_string());
+                Err(e.to_string())
             }
         }
     }
 
-    fn world_loc_updated(&mut self) -> MessageResult {
-        match Point::try_from(&self.point_str[..]) {
-            Ok(location) => {
-                if location != self.point && Subsector::point_is_inbounds(&location) {
-                    match self.subsector.get_world(&location) {
-                        Some(world) => {
-                            self.occupied_hex_popup(world.name.clone(), location);
-                            Ok(Some(()))
-                        }
-                        None => {
-                            self.confirm_loc_update(location)?;
-                            Ok(Some(()))
-                        }
-                    }
-                } else {
-                    self.point_str = self.point.to_string();
-                    Ok(None)
-                }
+    fn has_unsaved_changes(&self) -> bool {
+        self.subsector_edited || self.world_edited
+    }
+
+    /** Check if GM-only tabs, tools, and fields should be hidden, either because this is the
+    player-safe build or because the player view is on.
+    */
+    fn hides_spoilers(&self) -> bool {
+        cfg!(feature = "player-safe-gui") || self.player_view
+    }
+
+    fn hex_grid_clicked(&mut self, new_point: Point) -> MessageResult {
+        if self.world_edited {
+            self.unapplied_world_popup(new_point);
+            Ok(Some(()))
+        } else {
+            self.confirm_hex_grid_clicked(new_point)?;
+            Ok(Some(()))
+        }
+    }
+
+    fn import_csv(&mut self) -> MessageResult {
+        let result =
+            load_file_to_string(self.file_io.as_ref(), &self.save_directory, "CSV", &["csv"]);
+
+        let csv = match result {
+            Ok(Some((_, csv))) => csv,
+            Ok(None) => return Ok(None),
+            Err(e) => {
+                self.file_io
+                    .show_error("Error: Failed to Read CSV", &e.to_string());
+                return Err(e.to_string());
             }
-            Err(_) => {
-                self.point_str = self.point.to_string();
-                Ok(None)
+        };
+
+        match self.subsector.with_csv(&csv) {
+            Ok(subsector) => self.subsector = subsector,
+            Err(e) => {
+                self.file_io
+                    .show_error("Error: Failed to Load Worlds from CSV", &e.to_string());
+                return Err(e.to_string());
             }
         }
+
+        // Pick up any changes to the world in the selected hex
+        if self.point_selected && !self.world_edited {
+            self.confirm_hex_grid_clicked(self.point)?;
+        }
+        self.flag_duplicate_names();
+        self.subsector_model_updated()?;
+        Ok(Some(()))
     }
 
-    fn world_model_updated(&mut self) -> MessageResult {
-        self.world.normalize_data();
+    fn import_text(&mut self) -> MessageResult {
+        self.text_import_popup();
         Ok(Some(()))
     }
 
-    fn world_planetoid_belts_updated(&mut self) -> MessageResult {
-        let result = self.belt_str.parse();
+    fn import_traveller_map(&mut self) -> MessageResult {
+        let result = load_file_to_string(
+            self.file_io.as_ref(),
+            &self.save_directory,
+            "Traveller Map Tab Delimited",
+            &["tab", "tsv", "txt"],
+        );
+
+        let tsv = match result {
+            Ok(Some((_, tsv))) => tsv,
+            Ok(None) => return Ok(None),
+            Err(e) => {
+                self.file_io
+                    .show_error("Error: Failed to Read Traveller Map File", &e.to_string());
+                return Err(e.to_string());
+            }
+        };
+
+        let subsectors = TravellerMapTable::try_from_tsv(&tsv).and_then(|table| {
+            table
+                .subsector_indices()
+                .into_iter()
+                .map(|index| table.to_subsector(index, self.subsector.rule_set()))
+                .collect::<Result<Vec<_>, _>>()
+        });
+        let mut subsectors = match subsectors {
+            Ok(subsectors) if !subsectors.is_empty() => subsectors,
+            result => {
+                let e = result
+                    .err()
+                    .map_or_else(|| "The file has no worlds".to_string(), |e| e.to_string());
+                self.file_io.show_error(
+                    "Error: Failed to Load Subsector from Traveller Map File",
+                    &e,
+                );
+                return Err(e);
+            }
+        };
+
+        if subsectors.len() == 1 {
+            self.confirm_import_traveller_map(subsectors.remove(0))
+        } else {
+            self.traveller_map_subsector_popup(subsectors);
+            Ok(Some(()))
+        }
+    }
+
+    fn import_worlds(&mut self) -> MessageResult {
+        let result = load_file_to_string(
+            self.file_io.as_ref(),
+            &self.save_directory,
+            "Subsector",
+            &["json", "csv"],
+        );
+
+        let (path, contents) = match result {
+            Ok(Some(loaded)) => loaded,
+            Ok(None) => return Ok(None),
+            Err(e) => {
+                self.file_io
+                    .show_error("Error: Failed to Read Subsector", &e.to_string());
+                return Err(e.to_string());
+            }
+        };
+
+        let is_csv = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("csv"));
+        let (result, error_title) = if is_csv {
+            (
+                self.subsector.worlds_from_csv(&contents),
+                "Error: Failed to Load Worlds from CSV",
+            )
+        } else {
+            (
+                Subsector::try_from_json(&contents),
+                "Error: Failed to Load Subsector from JSON",
+            )
+        };
+
         match result {
-            Ok(belts) => {
-                self.world.planetoid_belts = Some(belts);
-                self.belt_str = self
-                    .world
-                    .planetoid_belts
-                    .expect("World planetoid belts should not be None")
-                    .to_string();
-                self.world_model_updated()?;
-                Ok(Some(()))
+            Ok(subsector) => self.merge_imported_worlds(subsector),
+            Err(e) => {
+                self.file_io.show_error(error_title, &e.to_string());
+                Err(e.to_string())
+            }
+        }
+    }
+
+    /** Replace the selected tab's `Subsector` with the one in `json` read from `path`. */
+    fn load_json(&mut self, path: &Path, json: &str) -> MessageResult {
+        let subsector = match Subsector::try_from_json(json) {
+            Ok(subsector) => subsector,
+            Err(e) => {
+                self.file_io
+                    .show_error("Error: Failed to Load Subsector from JSON", &e.to_string());
+                return Err(e.to_string());
             }
-            Err(_) => {
-                self.belt_str = self
+        };
+
+        let directory = path.parent().unwrap().to_str().unwrap().to_string();
+        let filename = path.file_name().unwrap().to_str().unwrap().to_string();
+        *self = Self {
+            copied_world: self.copied_world.take(),
+            documents: mem::take(&mut self.documents),
+            file_io: Arc::clone(&self.file_io),
+            // Keep the library search around so more of its matches can be opened
+            library_query: mem::take(&mut self.library_query),
+            library_search: self.library_search.take(),
+            message_log: mem::take(&mut self.message_log),
+            save_directory: directory,
+            save_file_modified: platform::file_modified(path),
+            save_filename: filename,
+            settings: self.settings.clone(),
+            ship_spec: self.ship_spec.clone(),
+            show_debug_console: self.show_debug_console,
+            show_library: self.show_library,
+            tab_index: self.tab_index,
+            viewer_mode: self.viewer_mode,
+            ..Self::from(subsector)
+        };
+
+        let inconsistencies = self.subsector.inconsistencies();
+        if !inconsistencies.is_empty() {
+            self.inconsistent_import_popup(&inconsistencies);
+        }
+        self.flag_duplicate_names();
+        Ok(Some(()))
+    }
+
+    fn load_world(&mut self, new_world_loc: &Point) -> MessageResult {
+        if let Some(world) = self.subsector.get_world(new_world_loc) {
+            self.world_selected = true;
+            self.world = world.clone();
+            self.berthing_cost_buffer
+                .reset(&self.world.starport.berthing_cost);
+            self.diameter_buffer.reset(&self.world.diameter);
+            self.point_buffer.reset(&self.point);
+            self.gas_giant_buffer.reset(&self.world.gas_giants);
+            self.belt_buffer.reset(
+                &self
                     .world
                     .planetoid_belts
-                    .expect("World planetoid belts should not be None")
-                    .to_string();
-                Ok(None)
+                    .expect("World planetoid belts should not be None"),
+            );
+            self.load_world_image();
+            Ok(Some(()))
+        } else {
+            Err(format!("Could not load world from point {}", new_world_loc))
+        }
+    }
+
+    /** Load the thumbnail of the image attached to the selected `World`, if there is one. */
+    fn load_world_image(&mut self) {
+        self.world_image = self.load_image_of(&self.world);
+    }
+
+    /** Load the thumbnail of the image attached to `world`, if there is one. */
+    fn load_image_of(&self, world: &World) -> Option<RetainedImage> {
+        world.image.as_ref().and_then(|image| {
+            let bytes = image.load_bytes(&self.save_directory).ok()?;
+            gui::decode_image("world_image", &bytes).ok()
+        })
+    }
+
+    /** Merge the worlds of `subsector` into the selected `Subsector`, asking what to do first if
+    any of them are in occupied hexes, are outside of the subsector, or break the world generation
+    rules.
+    */
+    fn merge_imported_worlds(&mut self, subsector: Subsector) -> MessageResult {
+        let conflicts = self.subsector.conflicting_points(&subsector);
+        let inconsistencies = subsector.inconsistencies();
+        let all_inbounds = subsector.get_map().keys().all(Subsector::point_is_inbounds);
+        if conflicts.is_empty() && inconsistencies.is_empty() && all_inbounds {
+            self.confirm_import_worlds(subsector, MergeResolution::Skip)
+        } else {
+            self.merge_conflict_popup(subsector, &conflicts, &inconsistencies);
+            Ok(Some(()))
+        }
+    }
+
+    /** Queue a message to be handled at the beginning of the next frame. */
+    fn message(&self, message: Message) {
+        self.message_tx.send(message);
+    }
+
+    /** Handle a `Message` generated by a GUI event immediately.
+
+    # Returns
+    - `Ok(Some(()))` if the message was handled successfully
+    - `Ok(None)` if no error occurred but the message was not handled; usually this means the user
+      cancelled the action before anything could result from it
+    - `Err(msg)` if an error occurred while handling the message
+    */
+    fn message_immediate(&mut self, message: Message) -> MessageResult {
+        let description = message_log::describe(&message);
+        let stopwatch = platform::Stopwatch::start();
+        let result = self.handle_message(message);
+        self.message_log
+            .record(description, &result, stopwatch.elapsed());
+        result
+    }
+
+    /** Handle `message` for `message_immediate`, without logging it. */
+    fn handle_message(&mut self, message: Message) -> MessageResult {
+        if self.viewer_mode && !message.is_read_only() {
+            return Ok(None);
+        }
+
+        use Message::*;
+        match message {
+            AddAnnotation { annotation } => self.add_annotation(annotation),
+            AddJournalEntry => self.add_journal_entry(),
+            AddNewFaction => self.add_new_faction(),
+            AddNewWorld => self.add_new_world(),
+            AddRandomWorld => self.add_random_world(),
+            AddSubsectorHook => self.add_subsector_hook(),
+            AddTravelRouteStop => self.add_travel_route_stop(),
+            AddWorldOrbitalFacility => self.add_world_orbital_facility(),
+            AddWorldSophont => self.add_world_sophont(),
+            AdvanceCampaignDate { days } => self.advance_campaign_date(days),
+
+            ApplyConfirmHexGridClicked { new_point } => {
+                self.apply_confirm_hex_grid_clicked(new_point)
+            }
+
+            ApplyWorldChanges => self.apply_world_changes(),
+            AttachWorldImage { embed } => self.attach_world_image(embed),
+            CancelLocUpdate => self.cancel_loc_update(),
+            CancelUnsavedExit => self.cancel_unsaved_exit(),
+            ClearAnnotations => self.clear_annotations(),
+            ClearDeletedWorlds => self.clear_deleted_worlds(),
+            ClearTravelRoute => self.clear_travel_route(),
+            CloseTab => self.close_tab(),
+            CompareWorld { point } => self.compare_world(point),
+            ConfigNewEmptySubsector => self.config_new_empty_subsector(),
+            ConfigRegenSubsector => self.config_regen_subsector(),
+            ConfirmCloseTab => self.confirm_close_tab(),
+            ConfirmEnterPlayerView { passphrase } => self.confirm_enter_player_view(passphrase),
+            ConfirmExportAllWorldSheets { format, thumbnail } => {
+                self.confirm_export_all_world_sheets(format, thumbnail)
+            }
+            ConfirmExportPlayerHandout { options } => self.confirm_export_player_handout(options),
+            ConfirmExportSubsectorMap { format, options } => {
+                self.confirm_export_subsector_map(format, options)
+            }
+            ConfirmFastForward { decades, rules } => self.confirm_fast_forward(decades, rules),
+            ConfirmFillEmptyHexes { count } => self.confirm_fill_empty_hexes(count),
+            ConfirmGenerateBatch {
+                count,
+                world_abundance_dm,
+                clustering,
+                name_style,
+                rule_set,
+            } => self.confirm_generate_batch(
+                count,
+                world_abundance_dm,
+                clustering,
+                name_style,
+                rule_set,
+            ),
+            ConfirmHexGridClicked { new_point } => self.confirm_hex_grid_clicked(new_point),
+            ConfirmImportJson => self.confirm_import_json(),
+            ConfirmImportText { subsector } => self.confirm_import_text(subsector),
+            ConfirmImportTravellerMap { subsector } => self.confirm_import_traveller_map(subsector),
+            ConfirmImportWorlds {
+                subsector,
+                resolution,
+            } => self.confirm_import_worlds(subsector, resolution),
+            ConfirmLeavePlayerView { passphrase } => self.confirm_leave_player_view(passphrase),
+            ConfirmLocUpdate { location } => self.confirm_loc_update(location),
+            ConfirmNewEmptySubsector { name } => self.confirm_new_empty_subsector(name),
+            ConfirmPoliticalCrisis { event } => self.confirm_political_crisis(event),
+            ConfirmRegenAttribute { attribute } => self.confirm_regen_attribute(attribute),
+
+            ConfirmRegenSubsector {
+                world_abundance_dm,
+                clustering,
+                name_style,
+                rule_set,
+            } => self.confirm_regen_subsector(world_abundance_dm, clustering, name_style, rule_set),
+
+            ConfirmRegenWorld { weighting } => self.confirm_regen_world(weighting),
+            ConfirmRemoveWorld { point } => self.confirm_remove_world(point),
+            ConfirmRenameSubsector { new_name } => self.confirm_rename_subsector(new_name),
+            ConfirmRerollDependents { world } => self.confirm_reroll_dependents(world),
+            ConfirmSetTravelCodes { policy } => self.confirm_set_travel_codes(policy),
+            ConfirmTableOverrides { overrides } => self.confirm_table_overrides(overrides),
+            ConfirmUnsavedExit => self.confirm_unsaved_exit(),
+            CopyRollLog => self.copy_roll_log(),
+            CopyWorld => self.copy_world(),
+            CopyWorldSummary { format } => self.copy_world_summary(format),
+            DeleteGenerationPreset { name } => self.delete_generation_preset(name),
+            DiscardBatch => self.discard_batch(),
+            EditTableOverrides => self.edit_table_overrides(),
+            ExportAllWorldSheets => self.export_all_world_sheets(),
+            ExportColumnDelimitedTable => self.export_column_delimited_table(),
+            ExportCsv => self.export_csv(),
+            ExportPlayerHandout => self.export_player_handout(),
+            ExportPlayerSafeSubsectorJson => self.export_player_safe_subsector_json(),
+            ExportSectorMetadata => self.export_sector_metadata(),
+            ExportSubsectorMap => self.export_subsector_map(),
+            FastForwardSubsector => self.fast_forward_subsector(),
+            FillEmptyHexes => self.fill_empty_hexes(),
+            FixAllSubsectorInconsistencies => self.fix_all_subsector_inconsistencies(),
+            FixWorldInconsistency { inconsistency } => self.fix_world_inconsistency(inconsistency),
+            FuelMarketTick => self.fuel_market_tick(),
+            GenerateBatch => self.generate_batch(),
+            HexGridClicked { new_point } => self.hex_grid_clicked(new_point),
+            ImportCsv => self.import_csv(),
+            ImportText => self.import_text(),
+            ImportTravellerMap => self.import_traveller_map(),
+            ImportWorlds => self.import_worlds(),
+            MoveSelection { dx, dy } => self.move_selection(dx, dy),
+            NameUnnamedFactions => self.name_unnamed_factions(),
+            NewEmptySubsector => self.new_empty_subsector(),
+            NewFactionGovSelected { new_code } => self.new_faction_gov_selected(new_code),
+            NewFactionStrengthSelected { new_code } => self.new_faction_strength_selected(new_code),
+            NewStarportClassSelected => self.new_starport_class_selected(),
+            NewTab => self.new_tab(),
+            NewWorldCultureSelected { new_code } => self.new_world_culture_selected(new_code),
+            NewWorldGovSelected { new_code } => self.new_world_gov_selected(new_code),
+            NewWorldSubtypeSelected { subtype } => self.new_world_subtype_selected(subtype),
+            NewWorldTagSelected { index, new_code } => self.new_world_tag_selected(index, new_code),
+            NoOp => Ok(None),
+            OpenBatchCandidate { index } => self.open_batch_candidate(index),
+            OpenJson => self.open_json(),
+            OpenJsonInNewTab => self.open_json_in_new_tab(),
+            OpenLibraryMatch { index } => self.open_library_match(index),
+            PasteWorld => self.paste_world(),
+            PoliticalCrisis => self.political_crisis(),
+            RegenAttribute { attribute } => self.regen_attribute(attribute),
+            RegenFactionName => self.regen_faction_name(),
+            RegenFactionReligion => self.regen_faction_religion(),
+            RegenPolities => self.regen_polities(),
+            RegenSelectedFaction => self.regen_selected_faction(),
+            RegenSelectedWorld => self.regen_selected_world(),
+            RegenSubsector => self.regen_subsector(),
+            RegenSubsectorHook { index } => self.regen_subsector_hook(index),
+            RegenSubsectorHooks => self.regen_subsector_hooks(),
+            RegenWorldAtmosphere => self.regen_world_atmosphere(),
+            RegenWorldCulturalDetail { facet } => self.regen_world_cultural_detail(facet),
+            RegenWorldCulture => self.regen_world_culture(),
+            RegenWorldEncounter { index } => self.regen_world_encounter(index),
+            RegenWorldEncounters => self.regen_world_encounters(),
+            RegenWorldFuelMarket => self.regen_world_fuel_market(),
+            RegenWorldGasGiants => self.regen_world_gas_giants(),
+            RegenWorldGovernment => self.regen_world_government(),
+            RegenWorldHazards => self.regen_world_hazards(),
+            RegenWorldHydrographics => self.regen_world_hydrographics(),
+            RegenWorldLawCategories => self.regen_world_law_categories(),
+            RegenWorldLawLevel => self.regen_world_law_level(),
+            RegenWorldNobility => self.regen_world_nobility(),
+            RegenWorldOrbitalFacilities => self.regen_world_orbital_facilities(),
+            RegenWorldPlanetoidBelts => self.regen_world_planetoid_belts(),
+            RegenWorldPopulation => self.regen_world_population(),
+            RegenWorldPronunciation => self.regen_world_pronunciation(),
+            RegenWorldReligion => self.regen_world_religion(),
+            RegenWorldSize => self.regen_world_size(),
+            RegenWorldSophonts => self.regen_world_sophonts(),
+            RegenWorldStarport => self.regen_world_starport(),
+            RegenWorldStarportDetails => self.regen_world_starport_details(),
+            RegenWorldStellar => self.regen_world_stellar(),
+            RegenWorldTag { index } => self.regen_world_tag(index),
+            RegenWorldTechLevel => self.regen_world_tech_level(),
+            RegenWorldTemperature => self.regen_world_temperature(),
+            RegenWorldTradeGoods => self.regen_world_trade_goods(),
+            RegenWorldTravelAdvisory => self.regen_world_travel_advisory(),
+            RegenXboatRoutes => self.regen_xboat_routes(),
+            ReloadSaveFile => self.reload_save_file(),
+            RemoveBookmark { index } => self.remove_bookmark(index),
+            RemoveJournalEntry { index } => self.remove_journal_entry(index),
+            RemoveLastAnnotation => self.remove_last_annotation(),
+            RemoveSelectedFaction => self.remove_selected_faction(),
+            RemoveSelectedWorld => self.remove_selected_world(),
+            RemoveSubsectorHook { index } => self.remove_subsector_hook(index),
+            RemoveTravelRouteStop { index } => self.remove_travel_route_stop(index),
+            RemoveWorldImage => self.remove_world_image(),
+            RemoveWorldOrbitalFacility { index } => self.remove_world_orbital_facility(index),
+            RemoveWorldSophont { index } => self.remove_world_sophont(index),
+            RenameDuplicateWorlds => self.rename_duplicate_worlds(),
+            RenameSubsector => self.rename_subsector(),
+            ResetGenerationScript => self.reset_generation_script(),
+            ResetLanguage => self.reset_language(),
+            ResetMapTemplate => self.reset_map_template(),
+            ResetTableOverrides => self.reset_table_overrides(),
+            RestoreDeletedWorld { index, point } => self.restore_deleted_world(index, point),
+            RevertWorldChanges => self.revert_world_changes(),
+            RunScript => self.run_script(),
+            Save => self.save(),
+            SaveAs => self.save_as(),
+            SaveCloseTab => self.save_close_tab(),
+            SaveConfigNewEmptySubsector => self.save_config_new_empty_subsector(),
+            SaveConfigRegenSubsector => self.save_config_regen_subsector(),
+            SaveConfirmImportJson => self.save_confirm_import_json(),
+            SaveExit => self.save_exit(),
+            SaveGenerationPreset { preset } => self.save_generation_preset(preset),
+            SearchLibrary => self.search_library(),
+            SelectGenerationScript => self.select_generation_script(),
+            SelectLanguage => self.select_language(),
+            SelectLibraryDirectory => self.select_library_directory(),
+            SelectMapTemplate => self.select_map_template(),
+            SelectTab { index } => self.select_tab(index),
+            SelectTableOverrides => self.select_table_overrides(),
+            SetCampaignDate { date } => self.set_campaign_date(date),
+            SetTravelCodes => self.set_travel_codes(),
+            SetUiScale { scale } => self.set_ui_scale(scale),
+            SubsectorModelUpdated => self.subsector_model_updated(),
+            ToggleBookmark => self.toggle_bookmark(),
+            TogglePlayerView => self.toggle_player_view(),
+            ToggleViewerMode => self.toggle_viewer_mode(),
+            WorldBerthingCostsUpdated => self.world_berthing_costs_updated(),
+            WorldDiameterUpdated => self.world_diameter_updated(),
+            WorldFieldEdited { field } => self.world_field_edited(field),
+            WorldGasGiantsUpdated => self.world_gas_giants_updated(),
+            WorldLocUpdated => self.world_loc_updated(),
+            WorldModelUpdated => self.world_model_updated(),
+            WorldPlanetoidBeltsUpdated => self.world_planetoid_belts_updated(),
+        }
+    }
+
+    /** Move the selected hex by `dx` columns and `dy` rows.
+
+    Moving off of one edge of the subsector wraps around to the opposite edge. If no hex is
+    selected yet, the top-left hex is selected instead.
+    */
+    fn move_selection(&mut self, dx: i32, dy: i32) -> MessageResult {
+        let new_point = if self.point_selected {
+            let wrap = |value: i32, max: usize| (value - 1).rem_euclid(max as i32) + 1;
+            Point {
+                x: wrap(self.point.x + dx, Subsector::COLUMNS),
+                y: wrap(self.point.y + dy, Subsector::ROWS),
+            }
+        } else {
+            Point { x: 1, y: 1 }
+        };
+        self.hex_grid_clicked(new_point)
+    }
+
+    fn name_unnamed_factions(&mut self) -> MessageResult {
+        // Names typed into the selected world's factions need to count
+        self.apply_world_changes()?;
+        let named = self.subsector.name_unnamed_factions();
+        if named.is_empty() {
+            return Ok(None);
+        }
+
+        if self.point_selected && named.contains(&self.point) {
+            let faction_idx = self.faction_idx;
+            self.confirm_hex_grid_clicked(self.point)?;
+            self.faction_idx = faction_idx;
+        }
+        self.subsector_model_updated()
+    }
+
+    fn new_faction_gov_selected(&mut self, new_code: u16) -> MessageResult {
+        if let Some(faction) = self.world.factions.get_mut(self.faction_idx) {
+            faction
+                .government
+                .safe_mutate(&TABLES.gov_table[new_code as usize]);
+            self.world_model_updated()?;
+            Ok(Some(()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn new_faction_strength_selected(&mut self, new_code: u16) -> MessageResult {
+        if let Some(faction) = self.world.factions.get_mut(self.faction_idx) {
+            let faction_strength = &TABLES.faction_table[new_code as usize];
+            faction.code = faction_strength.code;
+            faction.strength = faction_strength.strength.clone();
+            self.world_model_updated()?;
+            Ok(Some(()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn new_starport_class_selected(&mut self) -> MessageResult {
+        let starport = TABLES
+            .starport_table
+            .iter()
+            .find(|starport| starport.class == self.world.starport.class)
+            .unwrap();
+
+        self.world.starport = starport.clone();
+        self.world.generate_berthing_cost();
+        self.world.generate_fuel_market();
+        self.berthing_cost_buffer
+            .set_text(&self.world.starport.berthing_cost);
+        self.world_model_updated()?;
+        self.offer_dependent_rerolls(WorldField::Starport);
+        Ok(Some(()))
+    }
+
+    fn new_world_culture_selected(&mut self, new_code: u16) -> MessageResult {
+        self.world
+            .culture
+            .safe_mutate(&TABLES.culture_table[new_code as usize]);
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn new_world_gov_selected(&mut self, new_code: u16) -> MessageResult {
+        self.world
+            .government
+            .safe_mutate(&TABLES.gov_table[new_code as usize]);
+        self.world_model_updated()?;
+        self.offer_dependent_rerolls(WorldField::Government);
+        Ok(Some(()))
+    }
+
+    fn new_world_subtype_selected(&mut self, subtype: WorldSubtype) -> MessageResult {
+        if self.world.subtype == subtype {
+            return Ok(None);
+        }
+
+        self.world.set_subtype(subtype);
+        if self.faction_idx >= self.world.factions.len() {
+            self.faction_idx = 0;
+        }
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn new_world_tag_selected(&mut self, index: usize, new_code: u16) -> MessageResult {
+        if let Some(tag) = self.world.world_tags.get_mut(index) {
+            tag.safe_mutate(&TABLES.world_tag_table[new_code as usize]);
+            self.world_model_updated()?;
+            Ok(Some(()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /** Create a new `GeneratorApp`, restoring any persisted `Settings` from `eframe` storage. */
+    pub fn new(cc: &CreationContext) -> Self {
+        let mut settings: Settings = cc
+            .storage
+            .and_then(|storage| eframe::get_value(storage, Settings::STORAGE_KEY))
+            .unwrap_or_default();
+
+        // Fall back on English if the translation has been moved or broken since the last session
+        if let Some(path) = &settings.language_path {
+            if set_table_language(Some(path)).is_err() {
+                settings.language_path = None;
+            }
+        }
+        // Likewise for the table overrides, which are merged over the translation
+        if let Some(path) = &settings.table_overrides_path {
+            let result = load_table_overrides(&PlatformFileIo, path)
+                .and_then(|overrides| set_table_overrides(Some(overrides)));
+            if result.is_err() {
+                settings.table_overrides_path = None;
+            }
+        }
+        set_roll_logging(settings.log_generation_rolls);
+        Self {
+            settings,
+            ..Default::default()
+        }
+    }
+
+    /** Make the app a read-only viewer of the subsector JSON file at `path`, or of the subsector
+    it started with if there is no `path`.
+
+    # Returns
+    `Err` with the reason the file couldn't be loaded, in which case the app is still a viewer
+    */
+    pub fn open_viewer(&mut self, path: Option<&Path>) -> Result<(), String> {
+        self.viewer_mode = true;
+        if let Some(path) = path {
+            let json = self
+                .file_io
+                .read_to_string(path)
+                .map_err(|e| e.to_string())?;
+            self.load_json(path, &json)?;
+        }
+        Ok(())
+    }
+
+    fn new_empty_subsector(&mut self) -> MessageResult {
+        if self.has_unsaved_changes() {
+            self.unsaved_subsector_new_empty_popup();
+        } else {
+            self.config_new_empty_subsector()?;
+        }
+        Ok(Some(()))
+    }
+
+    fn new_tab(&mut self) -> MessageResult {
+        self.open_tab(Subsector::default())
+    }
+
+    /** Offer to reroll the fields of the selected world rolled from `field` after it was edited,
+    previewing what would change, if the user has turned on rerolling dependent fields.
+    */
+    fn offer_dependent_rerolls(&mut self, field: WorldField) {
+        if !self.settings.reroll_dependents {
+            return;
+        }
+
+        let world = self.world.with_dependents_rerolled(field);
+        let changes = world.changes_from(&self.world);
+        if !changes.is_empty() {
+            self.reroll_dependents_popup(field, world, &changes);
+        }
+    }
+
+    fn open_batch_candidate(&mut self, index: usize) -> MessageResult {
+        if index >= self.batch_candidates.len() {
+            return Ok(None);
+        }
+        let (subsector, _) = self.batch_candidates.remove(index);
+        self.open_tab(subsector)?;
+        self.flag_duplicate_names();
+        Ok(Some(()))
+    }
+
+    fn open_json(&mut self) -> MessageResult {
+        if self.has_unsaved_changes() {
+            self.unsaved_subsector_reload_popup();
+            Ok(Some(()))
+        } else {
+            self.confirm_import_json()
+        }
+    }
+
+    fn open_json_in_new_tab(&mut self) -> MessageResult {
+        self.open_tab(Subsector::empty())?;
+        match self.confirm_import_json() {
+            Ok(Some(())) => Ok(Some(())),
+            result => {
+                // Nothing was loaded into the new tab, so there's no reason to keep it
+                self.confirm_close_tab()?;
+                result
+            }
+        }
+    }
+
+    /** Open the subsector file of the library search match at `index` in a new tab and select the
+    matching world.
+    */
+    fn open_library_match(&mut self, index: usize) -> MessageResult {
+        let found = match self
+            .library_search
+            .as_ref()
+            .and_then(|search| search.matches.get(index))
+        {
+            Some(found) => found.clone(),
+            None => return Ok(None),
+        };
+
+        let json = match self.file_io.read_to_string(&found.path) {
+            Ok(json) => json,
+            Err(e) => {
+                self.file_io
+                    .show_error("Error: Failed to Read JSON", &e.to_string());
+                return Err(e.to_string());
+            }
+        };
+
+        self.open_tab(Subsector::empty())?;
+        if let Err(e) = self.load_json(&found.path, &json) {
+            // Nothing was loaded into the new tab, so there's no reason to keep it
+            self.confirm_close_tab()?;
+            return Err(e);
+        }
+        self.confirm_hex_grid_clicked(found.point)
+    }
+
+    /** Open `subsector` in a new tab after all of the others and select it. */
+    fn open_tab(&mut self, subsector: Subsector) -> MessageResult {
+        // Make sure any unapplied changes to the selected world aren't lost
+        self.apply_world_changes()?;
+
+        let mut document = Document::new(subsector, self.save_directory.clone());
+        self.swap_document(&mut document);
+        self.documents.insert(self.tab_index, document);
+        self.tab_index = self.documents.len();
+        Ok(Some(()))
+    }
+
+    fn paste_world(&mut self) -> MessageResult {
+        let world = match &self.copied_world {
+            Some(world) => world.clone(),
+            None => return Ok(None),
+        };
+
+        if !self.point_selected || self.world_selected {
+            return Ok(None);
+        }
+
+        self.subsector.insert_world(&self.point, world)?;
+        self.confirm_hex_grid_clicked(self.point)?;
+        self.subsector_model_updated()
+    }
+
+    fn political_crisis(&mut self) -> MessageResult {
+        if !self.world_selected {
+            return Ok(None);
+        }
+        self.political_crisis_popup();
+        Ok(Some(()))
+    }
+
+    fn process_hotkeys(&mut self, ctx: &Context) {
+        let hotkeys = [
+            (Modifiers::CTRL, Key::N, Message::RenameSubsector),
+            (Modifiers::CTRL, Key::O, Message::OpenJson),
+            (Modifiers::CTRL, Key::S, Message::Save),
+            (Modifiers::CTRL | Modifiers::SHIFT, Key::S, Message::SaveAs),
+            (Modifiers::CTRL, Key::T, Message::NewTab),
+            (
+                Modifiers::CTRL | Modifiers::SHIFT,
+                Key::ArrowUp,
+                Message::SetUiScale {
+                    scale: self.settings.ui_scale + UI_SCALE_STEP,
+                },
+            ),
+            (
+                Modifiers::CTRL | Modifiers::SHIFT,
+                Key::ArrowDown,
+                Message::SetUiScale {
+                    scale: self.settings.ui_scale - UI_SCALE_STEP,
+                },
+            ),
+            (
+                Modifiers::CTRL,
+                Key::Num0,
+                Message::SetUiScale { scale: 1.0 },
+            ),
+            (
+                Modifiers::CTRL | Modifiers::SHIFT,
+                Key::C,
+                Message::CopyWorldSummary {
+                    format: SummaryFormat::PlainText,
+                },
+            ),
+            (
+                Modifiers::CTRL | Modifiers::SHIFT,
+                Key::M,
+                Message::CopyWorldSummary {
+                    format: SummaryFormat::Markdown,
+                },
+            ),
+            (
+                Modifiers::CTRL | Modifiers::SHIFT,
+                Key::U,
+                Message::CopyWorldSummary {
+                    format: SummaryFormat::UwpLine,
+                },
+            ),
+        ];
+
+        for (modifiers, key, message) in hotkeys {
+            if ctx.input_mut().consume_key(modifiers, key) {
+                self.message(message);
+            }
+        }
+
+        // Toggled directly so that opening the console doesn't show up in its own log
+        if ctx
+            .input_mut()
+            .consume_key(Modifiers::CTRL | Modifiers::SHIFT, DEBUG_CONSOLE_KEY)
+        {
+            self.show_debug_console = !self.show_debug_console;
+        }
+
+        // Navigating the hex grid with the keyboard would get in the way of editing text fields
+        if ctx.memory().focus().is_some() || !self.popup_queue.is_empty() {
+            return;
+        }
+
+        let navigation_keys = [
+            (Key::ArrowLeft, Message::MoveSelection { dx: -1, dy: 0 }),
+            (Key::ArrowRight, Message::MoveSelection { dx: 1, dy: 0 }),
+            (Key::ArrowUp, Message::MoveSelection { dx: 0, dy: -1 }),
+            (Key::ArrowDown, Message::MoveSelection { dx: 0, dy: 1 }),
+        ];
+
+        for (key, message) in navigation_keys {
+            if ctx.input_mut().consume_key(Modifiers::NONE, key) {
+                self.message(message);
+            }
+        }
+
+        if ctx.input_mut().consume_key(Modifiers::NONE, Key::Enter)
+            && self.point_selected
+            && !self.world_selected
+        {
+            self.message(Message::AddNewWorld);
+        }
+
+        if ctx.input_mut().consume_key(Modifiers::NONE, Key::Delete) && self.world_selected {
+            self.message(Message::RemoveSelectedWorld);
+        }
+    }
+
+    /** Process all messages in the queue. */
+    fn process_message_queue(&mut self) {
+        while !self.message_rx.is_empty() {
+            let message = self.message_rx.receive().unwrap();
+            // Kept in case it opens a file dialog that only finishes after it has been handled
+            let retry = message.clone();
+            let _ = self.message_immediate(message);
+            if platform::take_file_pick_started() {
+                self.file_pick_message = Some(retry);
+            }
+        }
+    }
+
+    /** Redraw the subsector grid in the background once no redraw has been asked for in a while.
+
+    The grid is left alone if it would look the same as it already does.
+    */
+    fn process_map_redraw(&mut self, ctx: &Context) {
+        if self.map_redraw.is_waiting() {
+            // Nothing else wakes the GUI up once the redraw is due if the user stops doing things
+            ctx.request_repaint();
+        } else if self.map_redraw.take_due() {
+            self.redraw_subsector_grid();
+        }
+    }
+
+    /** Queue the subsector grid to be rasterized in the background if it has changed.
+
+    Only the blank grid is rasterized; the worlds and everything else about the `Subsector` are
+    painted over it every frame, so editing them never has to wait on a redraw.
+
+    # Returns
+    Whether a new image of the grid was queued.
+    */
+    fn redraw_subsector_grid(&mut self) -> bool {
+        let hex_numbers = self.settings.map_hex_numbers;
+        let svg = self
+            .subsector
+            .generate_grid_svg(hex_numbers)
+            .unwrap_or_else(|_| Subsector::generate_blank_grid_svg(hex_numbers));
+        if !self.map_redraw.mark_drawn(&svg) {
+            return false;
+        }
+        self.map_worker.send(svg);
+        true
+    }
+
+    fn regen_attribute(&mut self, attribute: WorldAttribute) -> MessageResult {
+        if self.subsector.get_map().is_empty() {
+            return Ok(None);
+        }
+        self.regen_attribute_popup(attribute);
+        Ok(Some(()))
+    }
+
+    fn regen_faction_name(&mut self) -> MessageResult {
+        if let Some(faction) = self.world.factions.get_mut(self.faction_idx) {
+            faction.name = random_faction_name(faction.government.code);
+            self.world_model_updated()?;
+            Ok(Some(()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn regen_faction_religion(&mut self) -> MessageResult {
+        if let Some(faction) = self.world.factions.get_mut(self.faction_idx) {
+            faction.religion = Religion::random();
+            self.world_model_updated()?;
+            Ok(Some(()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn regen_polities(&mut self) -> MessageResult {
+        // Allegiances of every world change, so unapplied edits would otherwise be lost
+        self.apply_world_changes()?;
+        self.subsector.generate_polities();
+
+        if self.world_selected {
+            let point = self.point;
+            self.load_world(&point)?;
+        }
+        self.subsector_model_updated()
+    }
+
+    fn regen_selected_faction(&mut self) -> MessageResult {
+        let index = self.faction_idx;
+        if let Some(faction) = self.world.factions.get_mut(index) {
+            let mut old_gov = faction.government.clone();
+            let name = faction.name.clone();
+            *faction = Faction::random();
+
+            faction.name = name;
+            old_gov.safe_mutate(&faction.government);
+            faction.government = old_gov;
+            self.world_model_updated()?;
+            Ok(Some(()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn regen_selected_world(&mut self) -> MessageResult {
+        self.regen_world_popup();
+        Ok(Some(()))
+    }
+
+    fn regen_subsector(&mut self) -> MessageResult {
+        if self.has_unsaved_changes() {
+            self.unsaved_subsector_regen_popup();
+            Ok(Some(()))
+        } else {
+            self.config_regen_subsector()?;
+            Ok(Some(()))
+        }
+    }
+
+    fn regen_subsector_hook(&mut self, index: usize) -> MessageResult {
+        if index >= self.subsector.hooks().len() {
+            return Err(format!("No subsector hook at index {}", index));
+        }
+
+        match self.subsector.generate_hook() {
+            Some(hook) => {
+                self.subsector.hooks_mut()[index] = hook;
+                self.subsector_model_updated()?;
+                Ok(Some(()))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn regen_subsector_hooks(&mut self) -> MessageResult {
+        self.subsector.generate_hooks();
+        self.subsector_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn regen_world_atmosphere(&mut self) -> MessageResult {
+        self.world.generate_atmosphere();
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn regen_world_cultural_detail(&mut self, facet: CulturalFacet) -> MessageResult {
+        self.world.generate_cultural_detail(facet);
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn regen_world_culture(&mut self) -> MessageResult {
+        let mut old_culture = self.world.culture.clone();
+        self.world.generate_culture();
+        old_culture.safe_mutate(&self.world.culture);
+        self.world.culture = old_culture;
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn regen_world_encounter(&mut self, index: usize) -> MessageResult {
+        if index >= self.world.encounters.len() {
+            return Ok(None);
+        }
+        self.world.encounters[index] = self.world.generate_encounter();
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn regen_world_encounters(&mut self) -> MessageResult {
+        self.world.generate_encounters();
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn regen_world_fuel_market(&mut self) -> MessageResult {
+        self.world.generate_fuel_market();
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn regen_world_gas_giants(&mut self) -> MessageResult {
+        self.world.generate_gas_giants();
+        self.gas_giant_buffer.set_text(&self.world.gas_giants);
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn regen_world_government(&mut self) -> MessageResult {
+        let mut old_gov = self.world.government.clone();
+        self.world.generate_government();
+        old_gov.safe_mutate(&self.world.government);
+        self.world.government = old_gov;
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn regen_world_hazards(&mut self) -> MessageResult {
+        self.world.generate_hazards();
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn regen_world_hydrographics(&mut self) -> MessageResult {
+        self.world.generate_hydrographics();
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn regen_world_law_categories(&mut self) -> MessageResult {
+        self.world.generate_law_categories();
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn regen_world_law_level(&mut self) -> MessageResult {
+        self.world.generate_law_level();
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn regen_world_nobility(&mut self) -> MessageResult {
+        self.world.generate_nobility();
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn regen_world_orbital_facilities(&mut self) -> MessageResult {
+        self.world.generate_orbital_facilities();
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn regen_world_planetoid_belts(&mut self) -> MessageResult {
+        self.world.generate_planetoid_belts();
+        self.belt_buffer.set_text(
+            &self
+                .world
+                .planetoid_belts
+                .expect("World planetoid belts should not be None"),
+        );
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn regen_world_population(&mut self) -> MessageResult {
+        self.world.generate_population();
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn regen_world_pronunciation(&mut self) -> MessageResult {
+        self.world.generate_pronunciation();
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn regen_world_religion(&mut self) -> MessageResult {
+        self.world.generate_religion();
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn regen_world_size(&mut self) -> MessageResult {
+        self.world.generate_size();
+        self.diameter_buffer.set_text(&self.world.diameter);
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn regen_world_sophonts(&mut self) -> MessageResult {
+        self.world.generate_sophonts();
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn regen_world_starport(&mut self) -> MessageResult {
+        self.world.generate_starport();
+        self.world.generate_fuel_market();
+        self.berthing_cost_buffer
+            .set_text(&self.world.starport.berthing_cost);
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn regen_world_starport_details(&mut self) -> MessageResult {
+        self.world.generate_starport_details();
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn regen_world_stellar(&mut self) -> MessageResult {
+        self.world.generate_stellar();
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn regen_world_tag(&mut self, index: usize) -> MessageResult {
+        match self.world.generate_world_tag(index) {
+            Some(mut old_tag) => {
+                old_tag.safe_mutate(&self.world.world_tags[index]);
+                self.world.world_tags[index] = old_tag;
+                self.world_model_updated()?;
+                Ok(Some(()))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn regen_world_tech_level(&mut self) -> MessageResult {
+        self.world.generate_tech_level();
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn regen_world_temperature(&mut self) -> MessageResult {
+        self.world.generate_temperature();
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn regen_world_trade_goods(&mut self) -> MessageResult {
+        self.world.generate_trade_goods();
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn regen_world_travel_advisory(&mut self) -> MessageResult {
+        // Bring the travel code up to date first, since the advisory starts with it
+        self.world_model_updated()?;
+        self.world.generate_travel_advisory();
+        Ok(Some(()))
+    }
+
+    fn regen_xboat_routes(&mut self) -> MessageResult {
+        // Hubs are picked by starport and importance, so the selected world's edits need to count
+        self.apply_world_changes()?;
+        self.subsector.generate_xboat_routes();
+        self.subsector_model_updated()
+    }
+
+    fn reload_save_file(&mut self) -> MessageResult {
+        let path = self.save_path();
+        match self.file_io.read_to_string(&path) {
+            Ok(json) => self.load_json(&path, &json),
+            Err(e) => {
+                self.file_io
+                    .show_error("Error: Failed to Read JSON", &e.to_string());
+                Err(e.to_string())
+            }
+        }
+    }
+
+    fn remove_selected_faction(&mut self) -> MessageResult {
+        self.faction_idx = self.world.remove_faction(self.faction_idx);
+        self.world_model_updated()?;
+        Ok(Some(()))
+    }
+
+    fn remove_selected_world(&mut self) -> MessageResult {
+        self.remove_world_popup();
+        Ok(Some(()))
+    }
+
+    fn remove_bookmark(&mut self, index: usize) -> MessageResult {
+        if index < self.subsector.bookmarks().len() {
+            self.subsector.bookmarks_mut().remove(index);
+            self.subsector_model_updated()
+        } else {
+            Err(format!("No bookmark at index {}", index))
+        }
+    }
+
+    fn remove_journal_entry(&mut self, index: usize) -> MessageResult {
+        if index < self.subsector.journal().len() {
+            self.subsector.journal_mut().remove(index);
+            self.journal_editing = None;
+            self.subsector_model_updated()?;
+            Ok(Some(()))
+        } else {
+            Err(format!("No journal entry at index {}", index))
+        }
+    }
+
+    fn remove_last_annotation(&mut self) -> MessageResult {
+        match self.subsector.remove_last_annotation() {
+            Some(_) => self.subsector_model_updated(),
+            None => Ok(None),
+        }
+    }
+
+    fn remove_subsector_hook(&mut self, index: usize) -> MessageResult {
+        if index < self.subsector.hooks().len() {
+            self.subsector.hooks_mut().remove(index);
+            self.subsector_model_updated()?;
+            Ok(Some(()))
+        } else {
+            Err(format!("No subsector hook at index {}", index))
+        }
+    }
+
+    fn remove_travel_route_stop(&mut self, index: usize) -> MessageResult {
+        if index < self.travel_route.len() {
+            self.travel_route.remove(index);
+            Ok(Some(()))
+        } else {
+            Err(format!("No travel route stop at index {}", index))
+        }
+    }
+
+    fn remove_world_image(&mut self) -> MessageResult {
+        if self.world.image.take().is_some() {
+            self.world_image = None;
+            self.world_model_updated()?;
+            Ok(Some(()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn remove_world_orbital_facility(&mut self, index: usize) -> MessageResult {
+        if index < self.world.orbital_facilities.len() {
+            self.world.orbital_facilities.remove(index);
+            self.world_model_updated()?;
+            Ok(Some(()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn remove_world_sophont(&mut self, index: usize) -> MessageResult {
+        if index < self.world.sophonts.len() {
+            self.world.sophonts.remove(index);
+            self.world_model_updated()?;
+            Ok(Some(()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn rename_duplicate_worlds(&mut self) -> MessageResult {
+        // Edits to the selected world's name need to count
+        self.apply_world_changes()?;
+        let renamed = self.subsector.rename_duplicates();
+        if renamed.is_empty() {
+            return Ok(None);
+        }
+
+        if self.point_selected && renamed.contains(&self.point) {
+            self.confirm_hex_grid_clicked(self.point)?;
+        }
+        self.subsector_model_updated()
+    }
+
+    fn rename_subsector(&mut self) -> MessageResult {
+        self.subsector_rename_popup();
+        Ok(Some(()))
+    }
+
+    fn reset_generation_script(&mut self) -> MessageResult {
+        if self.settings.generation_script_path.take().is_some() {
+            Ok(Some(()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn reset_language(&mut self) -> MessageResult {
+        if self.settings.language_path.take().is_some() {
+            set_table_language(None).map_err(|e| e.to_string())?;
+            Ok(Some(()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn reset_map_template(&mut self) -> MessageResult {
+        if self.settings.map_template_path.take().is_some() {
+            Ok(Some(()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn reset_table_overrides(&mut self) -> MessageResult {
+        if self.settings.table_overrides_path.take().is_some() {
+            set_table_overrides(None).map_err(|e| e.to_string())?;
+            Ok(Some(()))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /** Put the deleted `World` at `index` back into the subsector at `point` and select it. */
+    fn restore_deleted_world(&mut self, index: usize, point: Point) -> MessageResult {
+        let world = match self.deleted_worlds.get(index) {
+            Some((_, world)) => world.clone(),
+            None => return Err(format!("No deleted world at index {}", index)),
+        };
+        if self.subsector.get_world(&point).is_some() {
+            return Err(format!("Hex {} already has a world", point));
+        }
+
+        self.apply_world_changes()?;
+        self.subsector.insert_world(&point, world)?;
+        self.deleted_worlds.remove(index);
+        self.confirm_hex_grid_clicked(point)?;
+        self.subsector_model_updated()
+    }
+
+    /** Handle the `Message` that opened a file dialog again once a file has been picked in it, on
+    platforms where file dialogs finish late. The message is dropped if the dialog was cancelled.
+    */
+    fn retry_file_pick_message(&mut self, ctx: &Context) {
+        if platform::file_picked() {
+            if let Some(message) = self.file_pick_message.take() {
+                self.message(message);
+            }
+        } else if platform::waiting_for_file_pick() {
+            // Nothing else wakes the GUI up when the file is done being read
+            ctx.request_repaint();
+        } else {
+            self.file_pick_message = None;
+        }
+    }
+
+    fn revert_world_changes(&mut self) -> MessageResult {
+        if self.world_selected {
+            let point = self.point;
+            self.load_world(&point)?;
+            Ok(Some(()))
+        } else {
+            unreachable!("Reverting a world without one selected should be impossible");
+        }
+    }
+
+    fn run_script(&mut self) -> MessageResult {
+        let result = load_file_to_string(
+            self.file_io.as_ref(),
+            &self.save_directory,
+            "Script",
+            Script::EXTENSIONS,
+        );
+
+        let (path, source) = match result {
+            Ok(Some((path, source))) => (path, source),
+            Ok(None) => return Ok(None),
+            Err(e) => {
+                self.file_io
+                    .show_error("Error: Failed to Read Script", &e.to_string());
+                return Err(e.to_string());
+            }
+        };
+
+        let script = match Script::from_file(&path, &source) {
+            Ok(script) => script,
+            Err(e) => {
+                self.file_io
+                    .show_error("Error: Invalid Script", &e.to_string());
+                return Err(e.to_string());
+            }
+        };
+
+        match self.subsector.run_script(&script) {
+            Ok(0) => return Ok(None),
+            Ok(_) => (),
+            Err(e) => {
+                self.file_io
+                    .show_error("Error: Failed to Run Script", &e.to_string());
+                return Err(e.to_string());
+            }
+        }
+
+        // Pick up any changes the script made to the selected world
+        if self.world_selected {
+            let point = self.point;
+            self.load_world(&point)?;
+        }
+        self.subsector_model_updated()
+    }
+
+    fn save(&mut self) -> MessageResult {
+        // Make sure any unapplied changes the selected world are also saved
+        self.apply_world_changes()?;
+
+        let path = self.save_path();
+        if self.save_filename.is_empty() || !path.exists() {
+            // This is our first time saving or the path has been invalidated underneath us
+            self.save_as()
+        } else {
+            let result = backup::back_up_file(
+                &path,
+                self.settings.backup_style,
+                self.settings.backup_count,
+            )
+            .map_err(|e| e.into())
+            .and_then(|_| {
+                save_file(
+                    self.file_io.as_ref(),
+                    &self.save_directory,
+                    &self.save_filename,
+                    self.subsector_json(),
+                )
+            });
+            match result {
+                Ok(()) => {
+                    self.save_file_modified = platform::file_modified(&path);
+                    self.saved_worlds = self.subsector.get_map().clone();
+                    self.subsector_edited = false;
+                    Ok(Some(()))
+                }
+                Err(e) => {
+                    self.file_io
+                        .show_error("Error: Failed to Save JSON", &e.to_string());
+                    Err(e.to_string())
+                }
+            }
+        }
+    }
+
+    fn save_as(&mut self) -> MessageResult {
+        // Make sure any unapplied changes the selected world are also saved
+        self.apply_world_changes()?;
+
+        let default_filename = format!("{} Subsector.json", self.subsector.name());
+        let filename = if !self.save_filename.is_empty() {
+            &self.save_filename
+        } else {
+            // This is our first time saving
+            &default_filename
+        };
+
+        let default_directory = DEFAULT_DIRECTORY.to_string();
+        let directory = if <String as AsRef<Path>>::as_ref(&self.save_directory).is_dir() {
+            &self.save_directory
+        } else {
+            // The directory has been invalidated underneath us
+            &default_directory
+        };
+
+        let (backup_style, backup_count) = (self.settings.backup_style, self.settings.backup_count);
+        let result = save_path_dialog(
+            self.file_io.as_ref(),
+            directory,
+            filename,
+            "JSON",
+            &["json"],
+        )
+        .and_then(|path| match path {
+            Some(path) => {
+                backup::back_up_file(&path, backup_style, backup_count)?;
+                self.file_io
+                    .write(&path, self.subsector_json().as_bytes())?;
+                Ok(Some(path))
+            }
+            None => Ok(None),
+        });
+
+        match result {
+            Ok(Some(path)) => {
+                self.save_directory = path.parent().unwrap().to_str().unwrap().to_string();
+                self.save_filename = path.file_name().unwrap().to_str().unwrap().to_string();
+                self.save_file_modified = platform::file_modified(&path);
+                self.saved_worlds = self.subsector.get_map().clone();
+                self.subsector_edited = false;
+                Ok(Some(()))
+            }
+            Ok(None) => Ok(None),
+            Err(e) => {
+                self.file_io
+                    .show_error("Error: Failed to Save JSON", &e.to_string());
+                Err(e.to_string())
+            }
+        }
+    }
+
+    fn save_config_new_empty_subsector(&mut self) -> MessageResult {
+        match self.save() {
+            Ok(Some(())) => self.config_new_empty_subsector(),
+            Ok(None) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn save_config_regen_subsector(&mut self) -> MessageResult {
+        match self.save() {
+            Ok(Some(())) => self.config_regen_subsector(),
+            Ok(None) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn save_confirm_import_json(&mut self) -> MessageResult {
+        match self.save() {
+            Ok(Some(())) => self.confirm_import_json(),
+            Ok(None) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn save_close_tab(&mut self) -> MessageResult {
+        match self.save() {
+            Ok(Some(())) => self.confirm_close_tab(),
+            Ok(None) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn save_generation_preset(&mut self, preset: GenerationPreset) -> MessageResult {
+        if preset.name.trim().is_empty() {
+            return Err("Generation presets must have a name".to_string());
+        }
+        self.settings.save_generation_preset(preset);
+        Ok(Some(()))
+    }
+
+    fn save_exit(&mut self) -> MessageResult {
+        match self.save() {
+            Ok(Some(())) => {
+                // Ask about the next tab with unsaved changes before exiting, if there is one
+                match self.documents.iter().position(Document::is_edited) {
+                    Some(index) => {
+                        let index = if index < self.tab_index {
+                            index
+                        } else {
+                            index + 1
+                        };
+                        self.select_tab(index)?;
+                        self.unsaved_exit_popup();
+                    }
+                    None => self.can_exit = true,
+                }
+                Ok(Some(()))
+            }
+            Ok(None) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /** Get the path of the file the `Subsector` was last saved to or loaded from. */
+    fn save_path(&self) -> PathBuf {
+        Path::new(&self.save_directory).join(&self.save_filename)
+    }
+
+    /** Search every subsector in the library directory for worlds matching the library query,
+    asking for the directory first if none has been chosen.
+    */
+    fn search_library(&mut self) -> MessageResult {
+        let directory = match &self.settings.library_path {
+            Some(directory) => directory.clone(),
+            None => return self.select_library_directory(),
+        };
+
+        match library::search_library(&directory, &self.library_query) {
+            Ok(search) => {
+                self.library_search = Some(search);
+                Ok(Some(()))
+            }
+            Err(e) => {
+                self.file_io
+                    .show_error("Error: Failed to Search Library", &e.to_string());
+                Err(e.to_string())
+            }
+        }
+    }
+
+    fn select_generation_script(&mut self) -> MessageResult {
+        let result = load_file_to_string(
+            self.file_io.as_ref(),
+            &self.save_directory,
+            "Script",
+            Script::EXTENSIONS,
+        );
+
+        let (path, source) = match result {
+            Ok(Some((path, source))) => (path, source),
+            Ok(None) => return Ok(None),
+            Err(e) => {
+                self.file_io
+                    .show_error("Error: Failed to Read Script", &e.to_string());
+                return Err(e.to_string());
+            }
+        };
+
+        // Validate the script now so problems are found before the next subsector is generated
+        if let Err(e) = Script::from_file(&path, &source) {
+            self.file_io
+                .show_error("Error: Invalid Script", &e.to_string());
+            return Err(e.to_string());
+        }
+
+        self.settings.generation_script_path = Some(path);
+        Ok(Some(()))
+    }
+
+    fn select_language(&mut self) -> MessageResult {
+        let path = match self.file_io.pick_directory(Path::new(&self.save_directory)) {
+            Ok(Some(path)) => path,
+            Ok(None) => return Ok(None),
+            Err(e) => return Err(e.to_string()),
+        };
+
+        // Only switch languages if every translated table is valid
+        if let Err(e) = set_table_language(Some(&path)) {
+            self.file_io
+                .show_error("Error: Invalid Translation", &e.to_string());
+            return Err(e.to_string());
+        }
+
+        self.settings.language_path = Some(path);
+        Ok(Some(()))
+    }
+
+    fn select_library_directory(&mut self) -> MessageResult {
+        let location = match &self.settings.library_path {
+            Some(directory) => directory.to_string_lossy().to_string(),
+            None => self.save_directory.clone(),
+        };
+        let path = match self.file_io.pick_directory(Path::new(&location)) {
+            Ok(Some(path)) => path,
+            Ok(None) => return Ok(None),
+            Err(e) => return Err(e.to_string()),
+        };
+
+        self.settings.library_path = Some(path);
+        self.library_search = None;
+        if self.library_query.is_empty() {
+            Ok(Some(()))
+        } else {
+            self.search_library()
+        }
+    }
+
+    /** Select the tab at `index`, counting every open tab. */
+    fn select_tab(&mut self, index: usize) -> MessageResult {
+        if index == self.tab_index || index > self.documents.len() {
+            return Ok(None);
+        }
+
+        // Make sure any unapplied changes to the selected world aren't lost
+        self.apply_world_changes()?;
+
+        let document_index = if index < self.tab_index {
+            index
+        } else {
+            index - 1
+        };
+        let mut document = self.documents.remove(document_index);
+        self.swap_document(&mut document);
+
+        // `document` now holds the previously selected tab, which goes back in its place
+        let previous_index = if self.tab_index < index {
+            self.tab_index
+        } else {
+            self.tab_index - 1
+        };
+        self.documents.insert(previous_index, document);
+        self.tab_index = index;
+        Ok(Some(()))
+    }
+
+    fn select_map_template(&mut self) -> MessageResult {
+        let result =
+            load_file_to_string(self.file_io.as_ref(), &self.save_directory, "SVG", &["svg"]);
+
+        let (path, svg) = match result {
+            Ok(Some((path, svg))) => (path, svg),
+            Ok(None) => return Ok(None),
+            Err(e) => {
+                self.file_io
+                    .show_error("Error: Failed to Read SVG", &e.to_string());
+                return Err(e.to_string());
+            }
+        };
+
+        // Validate the template now so problems are found before the user tries to export with it
+        if let Err(e) = MapTemplate::try_from_svg(svg) {
+            self.file_io
+                .show_error("Error: Invalid Map Template", &e.to_string());
+            return Err(e.to_string());
+        }
+
+        self.settings.map_template_path = Some(path);
+        Ok(Some(()))
+    }
+
+    fn select_table_overrides(&mut self) -> MessageResult {
+        let result = load_file_to_string(
+            self.file_io.as_ref(),
+            &self.save_directory,
+            "JSON",
+            &["json"],
+        );
+
+        let (path, json) = match result {
+            Ok(Some((path, json))) => (path, json),
+            Ok(None) => return Ok(None),
+            Err(e) => {
+                self.file_io
+                    .show_error("Error: Failed to Read Table Overrides", &e.to_string());
+                return Err(e.to_string());
+            }
+        };
+
+        // Only switch to the overrides if every one of them can be applied
+        let result = TableOverrides::try_from_json(&json)
+            .and_then(|overrides| set_table_overrides(Some(overrides)));
+        if let Err(e) = result {
+            self.file_io
+                .show_error("Error: Invalid Table Overrides", &e.to_string());
+            return Err(e.to_string());
+        }
+
+        self.settings.table_overrides_path = Some(path);
+        Ok(Some(()))
+    }
+
+    fn set_campaign_date(&mut self, date: Option<ImperialDate>) -> MessageResult {
+        if date == self.subsector.campaign_date() {
+            return Ok(None);
+        }
+        self.subsector.set_campaign_date(date);
+        self.subsector_model_updated()
+    }
+
+    fn set_travel_codes(&mut self) -> MessageResult {
+        self.travel_code_policy_popup();
+        Ok(Some(()))
+    }
+
+    fn set_ui_scale(&mut self, scale: f32) -> MessageResult {
+        let scale = scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE);
+        if scale == self.settings.ui_scale {
+            return Ok(None);
+        }
+        self.settings.ui_scale = scale;
+        Ok(Some(()))
+    }
+
+    /** Snap the selected world's diameter and berthing cost into the ranges its size and starport
+    class allow, updating their text buffers to match.
+    */
+    fn snap_world_to_legal_ranges(&mut self) {
+        let diameter = self.world.snapped_diameter(self.world.diameter);
+        if diameter != self.world.diameter {
+            self.world.diameter = diameter;
+            self.diameter_buffer.set_text(&diameter);
+        }
+
+        let berthing_cost = self
+            .world
+            .snapped_berthing_cost(self.world.starport.berthing_cost);
+        if berthing_cost != self.world.starport.berthing_cost {
+            self.world.starport.berthing_cost = berthing_cost;
+            self.berthing_cost_buffer.set_text(&berthing_cost);
+        }
+    }
+
+    /** Get the JSON of the `Subsector` to save, leaving out the history of its `World`s unless the
+    user has chosen to save it.
+    */
+    fn subsector_json(&self) -> String {
+        if self.settings.save_world_history {
+            self.subsector.to_json()
+        } else {
+            let mut subsector = self.subsector.clone();
+            subsector.clear_world_history();
+            subsector.to_json()
+        }
+    }
+
+    fn subsector_model_updated(&mut self) -> MessageResult {
+        self.subsector_edited = true;
+        Ok(Some(()))
+    }
+
+    /** Find every world that differs from the last time the [`Subsector`] was saved or loaded.
+
+    Unapplied edits to the selected world count as changes too.
+
+    # Returns
+    The location of each changed world and how it changed, in order.
+    */
+    fn toggle_bookmark(&mut self) -> MessageResult {
+        if !self.point_selected {
+            return Ok(None);
+        }
+        self.subsector.toggle_bookmark(&self.point);
+        self.subsector_model_updated()
+    }
+
+    fn toggle_player_view(&mut self) -> MessageResult {
+        if self.player_view && self.player_view_passphrase.is_none() {
+            return self.confirm_leave_player_view(String::new());
+        }
+        self.player_view_popup();
+        Ok(Some(()))
+    }
+
+    /** Switch the read-only viewer on or off, applying any changes to the selected world first. */
+    fn toggle_viewer_mode(&mut self) -> MessageResult {
+        if !self.viewer_mode {
+            self.apply_world_changes()?;
+            // Nothing is left to apply, so moving around in the viewer shouldn't ask about it
+            self.check_world_edited();
+        }
+        self.viewer_mode = !self.viewer_mode;
+        Ok(Some(()))
+    }
+
+    fn unsaved_worlds(&self) -> Vec<(Point, UnsavedChange)> {
+        let points: BTreeSet<Point> = self
+            .saved_worlds
+            .keys()
+            .chain(self.subsector.get_map().keys())
+            .cloned()
+            .collect();
+
+        points
+            .into_iter()
+            .filter_map(|point| {
+                let current = if self.world_selected && self.world_edited && point == self.point {
+                    Some(&self.world)
+                } else {
+                    self.subsector.get_world(&point)
+                };
+
+                match (self.saved_worlds.get(&point), current) {
+                    (None, Some(_)) => Some((point, UnsavedChange::Added)),
+                    (Some(_), None) => Some((point, UnsavedChange::Removed)),
+                    (Some(saved), Some(current)) if saved != current => {
+                        Some((point, UnsavedChange::Edited))
+                    }
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    fn with_world_abundance(world_abundance_dm: i16, clustering: u8, rule_set: RuleSet) -> Self {
+        Self::from(Subsector::new(world_abundance_dm, clustering, rule_set))
+    }
+
+    fn world_berthing_costs_updated(&mut self) -> MessageResult {
+        let current = self.world.starport.berthing_cost;
+        match self.berthing_cost_buffer.apply(&current, |_| true) {
+            Some(berthing_cost) => {
+                self.world.starport.berthing_cost = berthing_cost;
+                self.world_model_updated()?;
+                Ok(Some(()))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn world_diameter_updated(&mut self) -> MessageResult {
+        match self.diameter_buffer.apply(&self.world.diameter, |_| true) {
+            Some(diameter) => {
+                self.world.diameter = diameter;
+                self.world_model_updated()?;
+                Ok(Some(()))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn world_field_edited(&mut self, field: WorldField) -> MessageResult {
+        self.world_model_updated()?;
+        self.offer_dependent_rerolls(field);
+        Ok(Some(()))
+    }
+
+    fn world_gas_giants_updated(&mut self) -> MessageResult {
+        let current = self.world.gas_giants;
+        match self.gas_giant_buffer.apply(&current, |&count| count >= 0) {
+            Some(gas_giants) => {
+                self.world.gas_giants = gas_giants;
+                self.world_model_updated()?;
+                Ok(Some(()))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn world_loc_updated(&mut self) -> MessageResult {
+        let location = match self.point_buffer.apply(&self.point, |_| true) {
+            Some(location) => location,
+            None => return Ok(None),
+        };
+        if !Subsector::point_is_inbounds(&location) {
+            let error = format!("{} is outside of the subsector", location);
+            self.point_buffer.reject(error, &self.point);
+            return Ok(None);
+        }
+
+        if location == self.point {
+            return Ok(None);
+        }
+
+        match self.subsector.get_world(&location) {
+            Some(world) => {
+                self.occupied_hex_popup(world.name.clone(), location);
+                Ok(Some(()))
+            }
+            None => {
+                self.confirm_loc_update(location)?;
+                Ok(Some(()))
+            }
+        }
+    }
+
+    fn world_model_updated(&mut self) -> MessageResult {
+        self.world.normalize_data();
+        if self.settings.snap_to_legal_ranges {
+            self.snap_world_to_legal_ranges();
+        }
+        Ok(Some(()))
+    }
+
+    fn world_planetoid_belts_updated(&mut self) -> MessageResult {
+        let current = self
+            .world
+            .planetoid_belts
+            .expect("World planetoid belts should not be None");
+        match self.belt_buffer.apply(&current, |&count| count >= 0) {
+            Some(belts) => {
+                self.world.planetoid_belts = Some(belts);
+                self.world_model_updated()?;
+                Ok(Some(()))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+impl App for GeneratorApp {
+    fn save(&mut self, storage: &mut dyn Storage) {
+        eframe::set_value(storage, Settings::STORAGE_KEY, &self.settings);
+    }
+
+    fn persist_native_window(&self) -> bool {
+        // Restores the window's position and size from the last session
+        true
+    }
+
+    fn on_exit_event(&mut self) -> bool {
+        let unsaved_tab = self.documents.iter().position(Document::is_edited);
+        let can_exit = (!self.has_unsaved_changes() && unsaved_tab.is_none()) || self.can_exit;
+        if !can_exit {
+            // Ask about a tab with unsaved changes, switching to it if the selected one has none
+            if let (false, Some(index)) = (self.has_unsaved_changes(), unsaved_tab) {
+                let index = if index < self.tab_index {
+                    index
+                } else {
+                    index + 1
+                };
+                let _ = self.select_tab(index);
+            }
+            self.unsaved_exit_popup();
+        }
+        can_exit
+    }
+
+    fn update(&mut self, ctx: &Context, frame: &mut Frame) {
+        if self.can_exit {
+            frame.quit();
+        }
+
+        self.check_world_edited();
+        self.process_hotkeys(ctx);
+        self.process_message_queue();
+        self.process_map_redraw(ctx);
+        self.retry_file_pick_message(ctx);
+        self.check_save_file();
+
+        let native_pixels_per_point = frame.info().native_pixels_per_point.unwrap_or(1.0);
+        let pixels_per_point = native_pixels_per_point * self.settings.ui_scale;
+        if (ctx.pixels_per_point() - pixels_per_point).abs() > f32::EPSILON {
+            ctx.set_pixels_per_point(pixels_per_point);
+        }
+
+        let visuals = gui::visuals(self.settings.high_contrast);
+        if ctx.style().visuals != visuals {
+            ctx.set_visuals(visuals);
+        }
+
+        if let Some(text) = self.copied_text.take() {
+            ctx.output().copied_text = text;
+        }
+
+        let unsaved_indicator = if self.has_unsaved_changes() { "*" } else { "" };
+        frame.set_window_title(&format!(
+            "{}{} Subsector",
+            unsaved_indicator,
+            self.subsector.name()
+        ));
+
+        self.show_gui(ctx);
+    }
+}
+
+impl Default for GeneratorApp {
+    fn default() -> Self {
+        Self::with_world_abundance(0, 0, RuleSet::default())
+    }
+}
+
+impl From<Subsector> for GeneratorApp {
+    fn from(subsector: Subsector) -> Self {
+        Self {
+            saved_worlds: subsector.get_map().clone(),
+            subsector,
+            ..Self::empty()
+        }
+    }
+}
+
+/** Save `contents` directly to the file described by `directory` and `filename` *without* a dialog.
+
+# Returns
+- `Err` if there was an error while trying to write to the file
+- `Ok(())` if the file was successfully written to
+*/
+fn save_file<P, C>(
+    file_io: &dyn FileIo,
+    directory: &P,
+    filename: &P,
+    contents: C,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    P: AsRef<Path>,
+    C: AsRef<[u8]>,
+{
+    let directory: &Path = directory.as_ref();
+    let filename: &Path = filename.as_ref();
+    let path = directory.join(filename);
+    file_io.write(&path, contents.as_ref())?;
+    Ok(())
+}
+
+/** Open a file dialog and save `contents` to the selected file.
+
+# Arguments
+- `file_io`: What opens the dialog and writes the file
+- `directory`: Directory to which the file dialog initially opens
+- `filename`: Filename to be pre-filled into the file dialog
+- `description`: Description of the file type to be filtered
+- `extensions`: Array of file extensions to filter
+- `contents`: Contents of the file to write to the file system
+
+# Returns
+- `Err` if there was an error while trying to save the file
+- `Ok(save_file)` with the path to the selected file if it was able to save successfully
+- `Ok(None)` if there was no error but no directory was selected and no save occurred; usually means
+  the "Cancel" button was selected
+*/
+fn save_file_dialog<P, C>(
+    file_io: &dyn FileIo,
+    directory: &P,
+    filename: &str,
+    description: &str,
+    extensions: &[&str],
+    contents: C,
+) -> Result<Option<PathBuf>, Box<dyn std::error::Error>>
+where
+    P: AsRef<Path>,
+    C: AsRef<[u8]>,
+{
+    let save_path = match save_path_dialog(file_io, directory, filename, description, extensions)? {
+        Some(path) => {
+            file_io.write(&path, contents.as_ref())?;
+            Some(path)
+        }
+
+        None => None,
+    };
+
+    Ok(save_path)
+}
+
+/** Open a file dialog to choose where to save a file without saving anything.
+
+Takes the same arguments as `save_file_dialog` other than the contents.
+
+# Returns
+- `Err` if the dialog failed to open
+- `Ok(Some(path))` with the selected path
+- `Ok(None)` if the dialog was cancelled
+*/
+fn save_path_dialog<P: AsRef<Path>>(
+    file_io: &dyn FileIo,
+    directory: &P,
+    filename: &str,
+    description: &str,
+    extensions: &[&str],
+) -> Result<Option<PathBuf>, Box<dyn std::error::Error>> {
+    file_io.pick_save_file(directory.as_ref(), filename, description, extensions)
+}
+
+/** Ask where to save a set of tiles and write each of `tiles` next to the chosen path.
+
+Each tile is named after the chosen file with its position appended, e.g. "Map (Tile 2 of 4).svg".
+
+# Returns
+- `Ok(Some(path))` with the path that was chosen if every tile was written
+- `Ok(None)` if the dialog was cancelled
+- `Err` if a tile failed to be written
+*/
+fn save_tiles_dialog<P, C>(
+    file_io: &dyn FileIo,
+    directory: &P,
+    filename: &str,
+    description: &str,
+    extensions: &[&str],
+    tiles: &[C],
+) -> Result<Option<PathBuf>, Box<dyn std::error::Error>>
+where
+    P: AsRef<Path>,
+    C: AsRef<[u8]>,
+{
+    let path = match save_path_dialog(file_io, directory, filename, description, extensions)? {
+        Some(path) => path,
+        None => return Ok(None),
+    };
+
+    for (index, tile) in tiles.iter().enumerate() {
+        file_io.write(&tile_path(&path, index, tiles.len()), tile.as_ref())?;
+    }
+    Ok(Some(path))
+}
+
+/** Get the path of tile `index` out of `count` tiles saved as `path`. */
+fn tile_path(path: &Path, index: usize, count: usize) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map_or_else(String::new, |stem| stem.to_string_lossy().to_string());
+    let mut file_name = format!("{} (Tile {} of {})", stem, index + 1, count);
+    if let Some(extension) = path.extension() {
+        file_name = format!("{}.{}", file_name, extension.to_string_lossy());
+    }
+    path.with_file_name(file_name)
+}
+
+/** Everything about how `World` data sheets are written, besides where they go. */
+struct SheetOptions {
+    format: SheetFormat,
+    /// How numbers are written
+    numbers: NumberFormat,
+    /// Whether to include surface thumbnails
+    thumbnail: bool,
+    /// Adventure hooks, of which each sheet lists the ones that involve its world
+    hooks: Vec<String>,
+}
+
+/** Write a data sheet for each of `worlds` to `directory` through `file_io` as set by `options`.
+
+The number of sheets written so far is sent through `progress_tx` after each one. If a sheet fails
+to be written, the error is sent instead and no more sheets are written.
+*/
+fn write_world_sheets(
+    file_io: &dyn FileIo,
+    directory: &Path,
+    worlds: &[(Point, World)],
+    options: &SheetOptions,
+    progress_tx: &mpsc::Sender<Result<usize, String>>,
+) {
+    let SheetOptions {
+        format,
+        numbers,
+        thumbnail,
+        hooks,
+    } = options;
+    for (index, (point, world)) in worlds.iter().enumerate() {
+        let file_name = world.sheet_file_name(point, *format);
+        let result = file_io
+            .write(
+                &directory.join(&file_name),
+                &world.sheet(point, *format, numbers, *thumbnail, hooks),
+            )
+            .map(|_| index + 1)
+            .map_err(|e| format!("{}: {}", file_name, e));
+
+        let failed = result.is_err();
+        // The receiver only hangs up if the progress popup is gone, which is no reason to stop
+        let _ = progress_tx.send(result);
+        if failed {
+            return;
+        }
+    }
+}
+
+/** Open a file dialog and read in the selected file.
+
+# Arguments
+- `file_io`: What opens the dialog and reads the file
+- `directory`: Directory to which the file dialog initially opens
+- `description`: Description of the file type to be filtered
+- `extensions`: Array of file extensions to filter
+
+# Returns
+- `Err` if there was an error while trying to read the file
+- `Ok((loaded_path, contents))` with the path to the loaded file if it was able to save successfully
+- `Ok(None)` if there was no error but no file was selected and no contents were loaded; usually
+  means the "Cancel" button was selected
+*/
+fn load_file_to_string<P: AsRef<Path>>(
+    file_io: &dyn FileIo,
+    directory: &P,
+    description: &str,
+    extensions: &[&str],
+) -> Result<Option<(PathBuf, String)>, Box<dyn std::error::Error>> {
+    let path = file_io.pick_open_file(directory.as_ref(), description, extensions)?;
+
+    let loaded_file = match path {
+        Some(path) => {
+            let contents = file_io.read_to_string(&path)?;
+            Some((path, contents))
+        }
+        None => None,
+    };
+
+    Ok(loaded_file)
+}
+
+/** Read and parse the `TableOverrides` at `path`. */
+fn load_table_overrides(
+    file_io: &dyn FileIo,
+    path: &Path,
+) -> Result<TableOverrides, Box<dyn std::error::Error>> {
+    TableOverrides::try_from_json(&file_io.read_to_string(path)?)
+}
+
+/** Read and parse the `Script` at `path`. */
+fn load_script(file_io: &dyn FileIo, path: &Path) -> Result<Script, Box<dyn std::error::Error>> {
+    let source = file_io.read_to_string(path)?;
+    Ok(Script::from_file(path, &source)?)
+}
+
+#[cfg(test)]
+// Some of these tests predate the lints and are kept as they were written
+#[allow(clippy::get_first, clippy::manual_is_multiple_of)]
+mod tests {
+    use super::file_io::FakeFileIo;
+    use super::*;
+    use swt_gen_core::{StarportClass, TravelCode, TravelCodeCondition, WorldAbundance};
+
+    fn empty_app() -> GeneratorApp {
+        GeneratorApp {
+            subsector: Subsector::empty(),
+            ..Default::default()
+        }
+    }
+
+    /** Get an empty app whose dialogs and files are faked, along with the fake to drive them. */
+    fn fake_file_io_app() -> (GeneratorApp, FakeFileIo) {
+        let file_io = FakeFileIo::default();
+        let app = GeneratorApp {
+            file_io: Arc::new(file_io.clone()),
+            ..empty_app()
+        };
+        (app, file_io)
+    }
+
+    mod message_tests {
+        use super::*;
+
+        #[test]
+        fn add_new_faction() {
+            let mut app = GeneratorApp::default();
+            let occupied_points: Vec<_> = app.subsector.get_map().keys().cloned().collect();
+            assert!(occupied_points.get(0).is_some());
+            let point = occupied_points[0];
+            app.message_immediate(Message::HexGridClicked { new_point: point })
+                .unwrap();
+            match app.subsector.get_world(&point) {
+                Some(world) => assert_eq!(app.world.factions, world.factions),
+                None => panic!("Empty point got in somehow"),
+            }
+
+            app.message_immediate(Message::AddNewFaction).unwrap();
+            match app.subsector.get_world(&point) {
+                Some(world) => {
+                    assert_ne!(app.world.factions, world.factions);
+                    assert_eq!(app.world.factions.len(), world.factions.len() + 1);
+                }
+                None => panic!("Empty point got in somehow"),
+            }
+        }
+
+        #[test]
+        fn add_new_world() {
+            let mut app = empty_app();
+
+            let unoccupied_point = Point { x: 1, y: 1 };
+            assert!(app.subsector.get_world(&unoccupied_point).is_none());
+
+            app.message_immediate(Message::HexGridClicked {
+                new_point: unoccupied_point,
+            })
+            .unwrap();
+            assert!(app.point_selected);
+            assert_eq!(app.point, unoccupied_point);
+            assert!(!app.world_selected);
+
+            app.message_immediate(Message::AddNewWorld).unwrap();
+            assert!(app.subsector.get_world(&unoccupied_point).is_some());
+            assert!(app.point_selected);
+            assert_eq!(app.point, unoccupied_point);
+            assert!(app.world_selected);
+
+            assert!(app.has_unsaved_changes());
+        }
+
+        #[test]
+        fn advance_campaign_date() {
+            let mut app = empty_app();
+            app.message_immediate(Message::AddRandomWorld).unwrap();
+            // Nothing to advance until the clock is started
+            assert_eq!(
+                app.message_immediate(Message::AdvanceCampaignDate { days: 7 }),
+                Ok(None)
+            );
+
+            let date = ImperialDate::default();
+            app.message_immediate(Message::SetCampaignDate { date: Some(date) })
+                .unwrap();
+            app.world.fuel_market = None;
+            app.message_immediate(Message::AdvanceCampaignDate { days: 7 })
+                .unwrap();
+            assert_eq!(app.subsector.campaign_date(), Some(date.plus_days(7)));
+            // A week always starts a new week, so the markets have ticked
+            assert!(app.world.fuel_market.is_some());
+
+            app.message_immediate(Message::AddJournalEntry).unwrap();
+            assert_eq!(app.subsector.journal()[0].date, "008-1105");
+
+            app.message_immediate(Message::SetCampaignDate { date: None })
+                .unwrap();
+            assert_eq!(app.subsector.campaign_date(), None);
+        }
+
+        #[test]
+        fn annotations() {
+            use swt_gen_core::Translation;
+
+            let mut app = empty_app();
+            assert_eq!(
+                app.message_immediate(Message::RemoveLastAnnotation),
+                Ok(None)
+            );
+            assert_eq!(app.message_immediate(Message::ClearAnnotations), Ok(None));
+
+            let circle = Annotation {
+                shape: AnnotationShape::Circle {
+                    center: Translation { x: 50.0, y: 50.0 },
+                    radius: 10.0,
+                },
+                color: AnnotationColor::Blue,
+            };
+            for _ in 0..2 {
+                app.message_immediate(Message::AddAnnotation {
+                    annotation: circle.clone(),
+                })
+                .unwrap();
+            }
+            assert_eq!(app.subsector.annotations(), [circle.clone(), circle]);
+            assert!(app.subsector_edited);
+
+            app.message_immediate(Message::RemoveLastAnnotation)
+                .unwrap();
+            assert_eq!(app.subsector.annotations().len(), 1);
+            app.message_immediate(Message::ClearAnnotations).unwrap();
+            assert!(app.subsector.annotations().is_empty());
+        }
+
+        #[test]
+        fn apply_world_changes() {
+            let mut app = empty_app();
+            let point = Point { x: 1, y: 1 };
+            assert!(app.subsector.get_world(&point).is_none());
+
+            app.message_immediate(Message::HexGridClicked { new_point: point })
+                .unwrap();
+            app.message_immediate(Message::AddNewWorld).unwrap();
+            app.check_world_edited();
+            assert!(app.subsector.get_world(&point).is_some());
+            assert_eq!(app.world, *app.subsector.get_world(&point).unwrap());
+            assert!(!app.world_edited);
+
+            app.world.secret_notes = "Blah blah blah".to_string();
+            app.check_world_edited();
+            assert_ne!(app.world, *app.subsector.get_world(&point).unwrap());
+            assert!(app.world_edited);
+
+            app.message_immediate(Message::ApplyWorldChanges).unwrap();
+            app.check_world_edited();
+            assert_eq!(app.world, *app.subsector.get_world(&point).unwrap());
+            assert!(!app.world_edited);
+
+            let history = &app.subsector.get_world(&point).unwrap().history;
+            assert_eq!(history.len(), 1);
+            assert_eq!(history[0].field, "Secret Notes");
+            assert_eq!(history[0].new_value, "Blah blah blah");
+        }
+
+        #[test]
+        fn compare_world() {
+            let mut app = empty_app();
+            let point = Point { x: 1, y: 1 };
+
+            // Empty hexes can't be compared
+            assert_eq!(
+                app.message_immediate(Message::CompareWorld { point }),
+                Ok(None)
+            );
+            assert!(app.compared_world.is_none());
+            assert!(!app.split_view);
+
+            app.subsector.insert_random_world(&point).unwrap();
+            app.message_immediate(Message::CompareWorld { point })
+                .unwrap();
+            assert_eq!(app.compared_world.as_ref().unwrap().point(), point);
+            assert!(app.split_view);
+        }
+
+        #[test]
+        fn copy_roll_log() {
+            let mut app = GeneratorApp::default();
+            let point = *app.subsector.get_map().keys().next().unwrap();
+            app.message_immediate(Message::HexGridClicked { new_point: point })
+                .unwrap();
+
+            // Nothing to copy from a world generated without logging its rolls
+            app.message_immediate(Message::CopyRollLog).unwrap();
+            assert!(app.copied_text.is_none());
+
+            set_roll_logging(true);
+            app.world = World::new("Regina".to_string());
+            set_roll_logging(false);
+            app.message_immediate(Message::CopyRollLog).unwrap();
+            assert_eq!(app.copied_text, Some(app.world.roll_log_text()));
+        }
+
+        #[test]
+        fn copy_world_summary() {
+            let mut app = GeneratorApp::default();
+            let copy_uwp_line = Message::CopyWorldSummary {
+                format: SummaryFormat::UwpLine,
+            };
+
+            // Nothing to copy without a selected world
+            app.message_immediate(copy_uwp_line.clone()).unwrap();
+            assert!(app.copied_text.is_none());
+
+            let point = *app.subsector.get_map().keys().next().unwrap();
+            app.message_immediate(Message::HexGridClicked { new_point: point })
+                .unwrap();
+            app.message_immediate(copy_uwp_line).unwrap();
+            let expected =
+                app.world
+                    .summary(&point, SummaryFormat::UwpLine, &app.settings.number_format);
+            assert_eq!(app.copied_text, Some(expected));
+        }
+
+        #[test]
+        fn export_csv() {
+            let (mut app, file_io) = fake_file_io_app();
+            app.message_immediate(Message::AddRandomWorld).unwrap();
+
+            // Nothing is written if the dialog is cancelled
+            file_io.cancel();
+            assert_eq!(app.message_immediate(Message::ExportCsv), Ok(None));
+            assert!(file_io.paths().is_empty());
+
+            let path = Path::new("/exports/Subsector.csv");
+            file_io.pick(path);
+            app.message_immediate(Message::ExportCsv).unwrap();
+            assert_eq!(file_io.paths(), [path]);
+            assert_eq!(
+                file_io.file(path).unwrap(),
+                app.subsector.to_csv().into_bytes()
+            );
+            assert!(file_io.errors().is_empty());
+        }
+
+        #[test]
+        fn fast_forward() {
+            let mut app = GeneratorApp::default();
+            let original = app.subsector.clone();
+            let rules = GrowthRules {
+                tech_advance: 100,
+                ..GrowthRules::default()
+            };
+
+            app.message_immediate(Message::ConfirmFastForward { decades: 5, rules })
+                .unwrap();
+            assert_eq!(app.tabs().len(), 2);
+            assert_eq!(app.tab_index, 1);
+            assert!(app.subsector_edited);
+            assert!(app.fast_forward_summary.is_some());
+            assert_eq!(app.settings.growth_rules, rules);
+            assert!(app
+                .subsector
+                .get_map()
+                .values()
+                .any(|world| !world.history.is_empty()));
+
+            // The original subsector is untouched in its own tab
+            app.message_immediate(Message::SelectTab { index: 0 })
+                .unwrap();
+            assert_eq!(app.subsector, original);
+        }
+
+        #[test]
+        fn fill_empty_hexes() {
+            let mut app = empty_app();
+            app.message_immediate(Message::AddRandomWorld).unwrap();
+            assert_eq!(app.subsector.get_map().len(), 1);
+            assert!(app.world_selected);
+            assert!(app.subsector.get_world(&app.point).is_some());
+
+            app.message_immediate(Message::ConfirmFillEmptyHexes { count: 5 })
+                .unwrap();
+            assert_eq!(app.subsector.get_map().len(), 6);
+            assert!(app.world_selected);
+            assert!(app.has_unsaved_changes());
+
+            // Only the hexes that are left get filled
+            let empty_hexes = Subsector::COLUMNS * Subsector::ROWS - 6;
+            app.message_immediate(Message::ConfirmFillEmptyHexes {
+                count: empty_hexes + 1,
+            })
+            .unwrap();
+            assert_eq!(app.subsector.random_empty_point(), None);
+            assert_eq!(app.message_immediate(Message::AddRandomWorld), Ok(None));
+        }
+
+        #[test]
+        fn fix_world_inconsistency() {
+            let mut app = empty_app();
+            let point = Point { x: 1, y: 1 };
+            app.message_immediate(Message::HexGridClicked { new_point: point })
+                .unwrap();
+            app.message_immediate(Message::AddNewWorld).unwrap();
+
+            app.world.size = 0;
+            app.world.hydrographics = TABLES.hydro_table[5].clone();
+            let inconsistency = Inconsistency::HydrographicsOnTinyWorld;
+            assert!(app.world.inconsistencies().contains(&inconsistency));
+
+            app.message_immediate(Message::FixWorldInconsistency { inconsistency })
+                .unwrap();
+            assert_eq!(app.world.hydrographics.code, 0);
+            assert!(!app.world.inconsistencies().contains(&inconsistency));
+
+            // Fixing an inconsistency that isn't there shouldn't do anything
+            assert_eq!(
+                app.message_immediate(Message::FixWorldInconsistency { inconsistency }),
+                Ok(None)
+            );
+        }
+
+        #[test]
+        fn fuel_market_tick() {
+            let mut app = empty_app();
+            app.message_immediate(Message::AddRandomWorld).unwrap();
+            app.world.fuel_market = None;
+
+            app.message_immediate(Message::FuelMarketTick).unwrap();
+            assert!(app.world.fuel_market.is_some());
+            let world = app.subsector.get_world(&app.point).unwrap();
+            assert!(world.fuel_market.is_some());
+            assert!(app.has_unsaved_changes());
+        }
+
+        #[test]
+        fn generation_presets() {
+            let mut app = empty_app();
+            let rift = GenerationPreset {
+                clustering: 80,
+                name: "Frontier Rift".to_string(),
+                name_style: NameStyle::Colonial,
+                rule_set: RuleSet::default(),
+                world_abundance: WorldAbundance::Rift,
+            };
+            let core = GenerationPreset {
+                name: "Core Worlds".to_string(),
+                world_abundance: WorldAbundance::Abundant,
+                ..rift.clone()
+            };
+            app.message_immediate(Message::SaveGenerationPreset {
+                preset: rift.clone(),
+            })
+            .unwrap();
+            app.message_immediate(Message::SaveGenerationPreset {
+                preset: core.clone(),
+            })
+            .unwrap();
+
+            // Saving under a name that's taken replaces that preset in place
+            let rift = GenerationPreset {
+                clustering: 20,
+                ..rift
+            };
+            app.message_immediate(Message::SaveGenerationPreset {
+                preset: rift.clone(),
+            })
+            .unwrap();
+            assert_eq!(app.settings.generation_presets, [rift, core.clone()]);
+
+            assert!(app
+                .message_immediate(Message::SaveGenerationPreset {
+                    preset: GenerationPreset {
+                        name: " ".to_string(),
+                        ..core.clone()
+                    },
+                })
+                .is_err());
+
+            assert_eq!(
+                app.message_immediate(Message::DeleteGenerationPreset {
+                    name: "Frontier Rift".to_string(),
+                }),
+                Ok(Some(()))
+            );
+            assert_eq!(
+                app.message_immediate(Message::DeleteGenerationPreset {
+                    name: "Frontier Rift".to_string(),
+                }),
+                Ok(None)
+            );
+            assert_eq!(app.settings.generation_presets, [core]);
+        }
+
+        #[test]
+        fn generate_batch() {
+            let mut app = empty_app();
+            app.message_immediate(Message::ConfirmGenerateBatch {
+                count: 3,
+                world_abundance_dm: 0,
+                clustering: 0,
+                name_style: NameStyle::Colonial,
+                rule_set: RuleSet::default(),
+            })
+            .unwrap();
+            assert_eq!(app.batch_candidates.len(), 3);
+
+            let name = app.batch_candidates[1].0.name().to_string();
+            app.message_immediate(Message::OpenBatchCandidate { index: 1 })
+                .unwrap();
+            assert_eq!(app.batch_candidates.len(), 2);
+            assert_eq!(app.tabs().len(), 2);
+            assert_eq!(app.subsector.name(), name);
+            assert!(!app.has_unsaved_changes());
+            assert_eq!(
+                app.message_immediate(Message::OpenBatchCandidate { index: 2 }),
+                Ok(None)
+            );
+
+            app.message_immediate(Message::DiscardBatch).unwrap();
+            assert!(app.batch_candidates.is_empty());
+        }
+
+        #[test]
+        fn hex_grid_clicked() {
+            let mut app = GeneratorApp::default();
+
+            // Test hex clicking on all points with no world changes
+            for x in 1..=Subsector::COLUMNS {
+                for y in 1..=Subsector::ROWS {
+                    let point = Point {
+                        x: x as i32,
+                        y: y as i32,
+                    };
+
+                    app.message_immediate(Message::HexGridClicked { new_point: point })
+                        .unwrap();
+                    assert!(app.point_selected);
+                    assert_eq!(app.point, point);
+                    match app.subsector.get_world(&point) {
+                        Some(world) => {
+                            assert!(app.world_selected);
+                            assert_eq!(app.world, *world);
+                            assert_eq!(app.point_buffer.text, point.to_string());
+                            assert_eq!(app.diameter_buffer.text, world.diameter.to_string());
+                            assert_eq!(
+                                app.berthing_cost_buffer.text,
+                                world.starport.berthing_cost.to_string()
+                            );
+                        }
+
+                        None => {
+                            assert!(!app.world_selected);
+                        }
+                    }
+                }
+            }
+
+            // Test hex clicking after making changes to selected world
+            let occupied_points: Vec<_> = app.subsector.get_map().keys().cloned().collect();
+            assert!(occupied_points.get(0).is_some());
+            let point = occupied_points[0];
+            assert!(app.subsector.get_world(&point).is_some());
+
+            let other_x = if point.x == Subsector::COLUMNS as i32 {
+                point.x - 1
+            } else {
+                point.x + 1
+            };
+
+            let other_y = if point.y == Subsector::ROWS as i32 {
+                point.y - 1
+            } else {
+                point.y + 1
+            };
+
+            let new_point = Point {
+                x: other_x,
+                y: other_y,
+            };
+
+            let blah = "Blah blah blah blah".to_string();
+
+            app.message_immediate(Message::HexGridClicked { new_point: point })
+                .unwrap();
+
+            // Just making some/any change to the now selected world
+            app.world.secret_notes = blah.clone();
+            app.check_world_edited();
+            assert!(app.world_edited);
+
+            app.message_immediate(Message::HexGridClicked { new_point })
+                .unwrap();
+            assert!(app.popup_queue.get(0).is_some());
+            app.popup_queue.remove(0);
+
+            // Nothing should change if the "cancel" button was hit on the popup
+            app.message_immediate(Message::NoOp).unwrap();
+            assert_eq!(app.point, point);
+
+            // Repeat as if the user had pressed the "don't apply" button
+            app.message_immediate(Message::HexGridClicked { new_point })
+                .unwrap();
+            assert!(app.popup_queue.get(0).is_some());
+            app.popup_queue.remove(0);
+
+            app.message_immediate(Message::ConfirmHexGridClicked { new_point })
+                .unwrap();
+            assert_eq!(app.point, new_point);
+
+            app.check_world_edited();
+            assert!(!app.world_edited);
+
+            // Confirm that the change was not kept
+            app.message_immediate(Message::HexGridClicked { new_point: point })
+                .unwrap();
+            assert_eq!(app.world.secret_notes, String::new());
+
+            // Repeat as if the "apply" button had been pressed
+            app.world.secret_notes = blah.clone();
+            app.check_world_edited();
+            assert!(app.world_edited);
+
+            app.message_immediate(Message::HexGridClicked { new_point })
+                .unwrap();
+            assert!(app.popup_queue.get(0).is_some());
+            app.popup_queue.remove(0);
+            app.message_immediate(Message::ApplyConfirmHexGridClicked { new_point })
+                .unwrap();
+            assert_eq!(app.point, new_point);
+
+            app.check_world_edited();
+            assert!(!app.world_edited);
+
+            // Confirm that the change was kept
+            assert_eq!(app.subsector.get_world(&point).unwrap().secret_notes, blah);
+        }
+
+        #[test]
+        fn import_traveller_map() {
+            let tsv = "Sector\tHex\tName\tUWP\n\
+                Spinward Marches\t1910\tRegina\tA788899-C\n";
+            let table = TravellerMapTable::try_from_tsv(tsv).unwrap();
+            let subsector = table.to_subsector('C', RuleSet::default()).unwrap();
+
+            let mut app = GeneratorApp::default();
+            app.message_immediate(Message::ConfirmImportTravellerMap { subsector })
+                .unwrap();
+            assert_eq!(app.tab_index, 1);
+            assert_eq!(app.subsector.name(), "Spinward Marches C");
+            assert!(app.subsector.get_world(&Point { x: 3, y: 10 }).is_some());
+            assert!(app.subsector_edited);
+        }
+
+        #[test]
+        fn import_worlds() {
+            let (mut app, file_io) = fake_file_io_app();
+            let mut other = Subsector::empty();
+            for (x, name) in [(1, "First"), (2, "Second")] {
+                let mut world = World::new(name.to_string());
+                world.fix_all_inconsistencies();
+                other.insert_world(&Point { x, y: 1 }, world).unwrap();
+            }
+
+            // Worlds can be read from a CSV table as well as from JSON
+            let csv_path = Path::new("/imports/Other.csv");
+            file_io.insert_file(csv_path, other.to_csv());
+            file_io.pick(csv_path);
+            app.message_immediate(Message::ImportWorlds).unwrap();
+            assert!(app.popup_queue.is_empty());
+            assert_eq!(app.subsector.get_map().len(), 2);
+
+            // Rows with invalid fields are reported without importing anything
+            let bad_path = Path::new("/imports/Bad.csv");
+            let mut lines: Vec<String> = other.to_csv().lines().map(str::to_string).collect();
+            lines[1] = lines[1].replacen("0101", "0911", 1);
+            file_io.insert_file(bad_path, lines.join("\n"));
+            file_io.pick(bad_path);
+            assert!(app.message_immediate(Message::ImportWorlds).is_err());
+            assert_eq!(file_io.errors(), ["Error: Failed to Load Worlds from CSV"]);
+            assert_eq!(app.subsector.get_map().len(), 2);
+
+            // Worlds in occupied hexes aren't overwritten without asking
+            let mut conflicting = Subsector::empty();
+            conflicting
+                .insert_world(&Point { x: 1, y: 1 }, World::new("Third".to_string()))
+                .unwrap();
+            let json_path = Path::new("/imports/Conflicting.json");
+            file_io.insert_file(json_path, conflicting.to_json());
+            file_io.pick(json_path);
+            app.message_immediate(Message::ImportWorlds).unwrap();
+            assert_eq!(app.popup_queue.len(), 1);
+            assert_eq!(
+                app.subsector.get_world(&Point { x: 1, y: 1 }).unwrap().name,
+                "First"
+            );
+            app.popup_queue.clear();
+
+            // Neither are worlds that break the generation rules
+            let mut inconsistent = Subsector::empty();
+            let mut world = World::new("Fourth".to_string());
+            world.starport = TABLES
+                .starport_table
+                .iter()
+                .find(|starport| starport.class == StarportClass::X)
+                .unwrap()
+                .clone();
+            world.has_naval_base = true;
+            inconsistent
+                .insert_world(&Point { x: 3, y: 3 }, world)
+                .unwrap();
+            file_io.insert_file(json_path, inconsistent.to_json());
+            file_io.pick(json_path);
+            app.message_immediate(Message::ImportWorlds).unwrap();
+            assert_eq!(app.popup_queue.len(), 1);
+            assert!(app.subsector.get_world(&Point { x: 3, y: 3 }).is_none());
+
+            app.message_immediate(Message::ConfirmImportWorlds {
+                subsector: inconsistent,
+                resolution: MergeResolution::Skip,
+            })
+            .unwrap();
+            assert_eq!(
+                app.subsector.get_world(&Point { x: 3, y: 3 }).unwrap().name,
+                "Fourth"
+            );
+        }
+
+        #[test]
+        fn bookmarks() {
+            let mut app = empty_app();
+            // Nothing to bookmark without a selected hex
+            assert_eq!(app.message_immediate(Message::ToggleBookmark), Ok(None));
+
+            let point = Point { x: 2, y: 3 };
+            app.message_immediate(Message::HexGridClicked { new_point: point })
+                .unwrap();
+            app.message_immediate(Message::ToggleBookmark).unwrap();
+            assert!(app.subsector.is_bookmarked(&point));
+            assert!(app.subsector_edited);
+
+            app.message_immediate(Message::ToggleBookmark).unwrap();
+            assert!(app.subsector.bookmarks().is_empty());
+
+            app.message_immediate(Message::ToggleBookmark).unwrap();
+            app.message_immediate(Message::RemoveBookmark { index: 0 })
+                .unwrap();
+            assert!(app.subsector.bookmarks().is_empty());
+            assert!(app
+                .message_immediate(Message::RemoveBookmark { index: 0 })
+                .is_err());
+        }
+
+        #[test]
+        fn journal_entries() {
+            let mut app = empty_app();
+            app.message_immediate(Message::AddJournalEntry).unwrap();
+            app.message_immediate(Message::AddJournalEntry).unwrap();
+            assert_eq!(app.subsector.journal().len(), 2);
+            assert_eq!(app.journal_editing, Some(1));
+            assert!(app.subsector_edited);
+
+            app.message_immediate(Message::RemoveJournalEntry { index: 0 })
+                .unwrap();
+            assert_eq!(app.subsector.journal().len(), 1);
+            assert_eq!(app.journal_editing, None);
+            assert!(app
+                .message_immediate(Message::RemoveJournalEntry { index: 1 })
+                .is_err());
+        }
+
+        #[test]
+        fn message_log() {
+            use message_log::MessageOutcome;
+
+            let mut app = empty_app();
+            app.message_immediate(Message::AddJournalEntry).unwrap();
+            assert!(app
+                .message_immediate(Message::RemoveJournalEntry { index: 1 })
+                .is_err());
+            app.message_immediate(Message::ConfirmNewEmptySubsector {
+                name: "Spinward".to_string(),
+            })
+            .unwrap();
+
+            // The log outlives the subsector it was started with
+            let entries = app.message_log.entries();
+            assert_eq!(entries.len(), 3);
+            assert_eq!(entries[0].message, "AddJournalEntry");
+            assert_eq!(entries[0].outcome, MessageOutcome::Handled);
+            assert_eq!(entries[1].message, "RemoveJournalEntry { index: 1 }");
+            assert!(matches!(entries[1].outcome, MessageOutcome::Failed(_)));
+            assert_eq!(entries[2].index, 2);
+        }
+
+        #[test]
+        fn move_selection() {
+            let mut app = empty_app();
+
+            // The first move selects the top-left hex
+            app.message_immediate(Message::MoveSelection { dx: 1, dy: 0 })
+                .unwrap();
+            assert_eq!(app.point, Point { x: 1, y: 1 });
+
+            app.message_immediate(Message::MoveSelection { dx: 1, dy: 1 })
+                .unwrap();
+            assert_eq!(app.point, Point { x: 2, y: 2 });
+
+            // Moving off of an edge wraps around to the other side
+            app.message_immediate(Message::MoveSelection { dx: -2, dy: 0 })
+                .unwrap();
+            assert_eq!(app.point, Point { x: 8, y: 2 });
+
+            app.message_immediate(Message::MoveSelection { dx: 1, dy: -2 })
+                .unwrap();
+            assert_eq!(app.point, Point { x: 1, y: 10 });
+        }
+
+        #[test]
+        fn new_tab() {
+            let mut app = GeneratorApp::default();
+            let first_name = app.subsector.name().to_string();
+            let point = *app.subsector.get_map().keys().next().unwrap();
+            app.message_immediate(Message::HexGridClicked { new_point: point })
+                .unwrap();
+            app.message_immediate(Message::CopyWorld).unwrap();
+            let copied = app.world.clone();
+            app.polity_filter = Some("Im".to_string());
+            app.measurement = Some((point, point));
+            app.journal_editing = Some(0);
+
+            app.message_immediate(Message::NewTab).unwrap();
+            assert_eq!(app.tab_index, 1);
+            assert_eq!(app.tabs().len(), 2);
+            assert!(!app.point_selected);
+            assert_eq!(app.polity_filter, None);
+            assert_eq!(app.measurement, None);
+            assert_eq!(app.journal_editing, None);
+            assert!(app.world_thumbnail.is_none());
+
+            // Paste the world into an empty hex of the new tab
+            app.subsector = Subsector::empty();
+            app.message_immediate(Message::HexGridClicked { new_point: point })
+                .unwrap();
+            app.message_immediate(Message::PasteWorld).unwrap();
+            assert_eq!(app.subsector.get_world(&point), Some(&copied));
+            assert!(app.subsector_edited);
+
+            app.message_immediate(Message::SelectTab { index: 0 })
+                .unwrap();
+            assert_eq!(app.subsector.name(), first_name);
+            assert!(!app.subsector_edited);
+            assert!(app.tabs()[1].1);
+            assert_eq!(app.polity_filter.as_deref(), Some("Im"));
+
+            app.message_immediate(Message::SelectTab { index: 1 })
+                .unwrap();
+            app.message_immediate(Message::ConfirmCloseTab).unwrap();
+            assert_eq!(app.tab_index, 0);
+            assert_eq!(app.subsector.name(), first_name);
+            assert!(app.documents.is_empty());
+        }
+
+        #[test]
+        fn new_empty_subsector() {
+            let mut app = GeneratorApp {
+                subsector_edited: true,
+                ..Default::default()
+            };
+            app.message_immediate(Message::NewEmptySubsector).unwrap();
+            assert_eq!(app.popup_queue.len(), 1);
+
+            app.popup_queue.clear();
+            app.message_immediate(Message::ConfirmNewEmptySubsector {
+                name: "Efate".to_string(),
+            })
+            .unwrap();
+            assert_eq!(app.subsector.name(), "Efate");
+            assert!(app.subsector.get_map().is_empty());
+            assert!(!app.has_unsaved_changes());
+            assert!(!app.world_selected);
+        }
+
+        #[test]
+        fn new_faction_gov_selected() {
+            let mut app = empty_app();
+            let point = Point { x: 1, y: 1 };
+            app.message_immediate(Message::HexGridClicked { new_point: point })
+                .unwrap();
+            app.message_immediate(Message::AddNewWorld).unwrap();
+
+            if app.world.factions.is_empty() {
+                app.message_immediate(Message::AddNewFaction).unwrap();
+            }
+            assert!(!app.world.factions.is_empty());
+
+            // Simulate selecting a new faction by simply changing the faction_idx
+            app.faction_idx = 0;
+            let faction = &mut app.world.factions[app.faction_idx];
+
+            let gov_table = &TABLES.gov_table;
+            let default_description = &gov_table
+                .iter()
+                .find(|g| g.kind == faction.government.kind)
+                .unwrap()
+                .description;
+            assert_eq!(faction.government.description, *default_description);
+
+            let new_gov = gov_table
+                .iter()
+                .find(|g| g.kind != faction.government.kind)
+                .unwrap();
+            assert_ne!(*new_gov, faction.government);
+
+            // Simulate choosing a new faction government selectable value on the GUI by changing
+            // government "kind" directly and messaging NewFactionGovSelected with the new code
+            faction.government.kind = new_gov.kind.clone();
+            app.message_immediate(Message::NewFactionGovSelected {
+                new_code: new_gov.code,
+            })
+            .unwrap();
+
+            let faction = &mut app.world.factions[app.faction_idx];
+            assert_eq!(faction.government.code, new_gov.code);
+            assert_eq!(faction.government.kind, new_gov.kind);
+            // Because we didn't change the faction's government description from the default on the
+            // gov_table, it should have updated to that of the newly selected government
+            assert_eq!(faction.government.description, new_gov.description);
+            // We don't change the contraband of the faction's government because we don't care or
+            // display contraband in the context of factions
+
+            // We repeat the same test, but this time we change the faction's government description
+            // from the default and confirm that it is retained
+            let default_description = &gov_table
+                .iter()
+                .find(|g| g.kind == faction.government.kind)
+                .unwrap()
+                .description;
+            assert_eq!(faction.government.description, *default_description);
+
+            let new_gov = gov_table
+                .iter()
+                .find(|g| g.kind != faction.government.kind)
+                .unwrap();
+            assert_ne!(*new_gov, faction.government);
+
+            let blah = "Blah blah blah".to_string();
+            faction.government.description = blah.clone();
+            faction.government.kind = new_gov.kind.clone();
+            app.message_immediate(Message::NewFactionGovSelected {
+                new_code: new_gov.code,
+            })
+            .unwrap();
+
+            let faction = &app.world.factions[app.faction_idx];
+            assert_eq!(faction.government.code, new_gov.code);
+            assert_eq!(faction.government.kind, new_gov.kind);
+            // Because we changed the faction's government description from the default it should
+            // have been retained
+            assert_eq!(faction.government.description, blah);
+        }
+
+        #[test]
+        fn new_starport_class_selected() {
+            use swt_gen_core::StarportClass;
+
+            let mut app = empty_app();
+            let point = Point { x: 1, y: 1 };
+            app.message_immediate(Message::HexGridClicked { new_point: point })
+                .unwrap();
+            app.message_immediate(Message::AddNewWorld).unwrap();
+
+            let old_starport = app.world.starport.clone();
+            let new_class = match app.world.starport.class {
+                StarportClass::A => StarportClass::B,
+                StarportClass::B => StarportClass::C,
+                StarportClass::C => StarportClass::D,
+                StarportClass::D => StarportClass::E,
+                StarportClass::E => StarportClass::X,
+                StarportClass::X => StarportClass::A,
+            };
+            let new_starport = TABLES
+                .starport_table
+                .iter()
+                .find(|sp| sp.class == new_class)
+                .unwrap();
+
+            app.world.starport.class = new_class;
+            app.message_immediate(Message::NewStarportClassSelected)
+                .unwrap();
+            assert_ne!(app.world.starport, old_starport);
+            assert_eq!(app.world.starport.code, new_starport.code);
+            assert_eq!(app.world.starport.class, new_starport.class);
+            // Generated berthing costs are 1d6 * the "base" starport table berthing cost; just need
+            // to account for when berthing costs are zero
+            if new_starport.berthing_cost != 0 {
+                assert!(app.world.starport.berthing_cost % new_starport.berthing_cost == 0);
+            } else {
+                assert_eq!(app.world.starport.berthing_cost, new_starport.berthing_cost);
             }
+            assert_eq!(app.world.starport.fuel, new_starport.fuel);
+            assert_eq!(app.world.starport.facilities, new_starport.facilities);
         }
-    }
-}
 
-impl App for GeneratorApp {
-    fn on_exit_event(&mut self) -> bool {
-        let can_exit = !self.has_unsaved_changes() || self.can_exit;
-        if !can_exit {
-            self.unsaved_exit_popup();
+        #[test]
+        fn open_json() {
+            let (mut app, file_io) = fake_file_io_app();
+            let mut subsector = Subsector::default();
+            subsector.set_name("Regina".to_string());
+            let path = Path::new("/saves/Regina Subsector.json");
+            file_io.insert_file(path, subsector.to_json());
+
+            file_io.pick(path);
+            app.message_immediate(Message::OpenJson).unwrap();
+            assert_eq!(app.subsector.name(), "Regina");
+            assert_eq!(app.save_path(), path);
+            assert!(!app.has_unsaved_changes());
+
+            // Files that can't be read are reported rather than opened
+            file_io.pick("/saves/Missing.json");
+            assert!(app.message_immediate(Message::OpenJson).is_err());
+            assert_eq!(app.subsector.name(), "Regina");
+            assert_eq!(file_io.errors(), ["Error: Failed to Read JSON"]);
         }
-        can_exit
-    }
 
-    fn update(&mut self, ctx: &Context, frame: &mut Frame) {
-        if self.can_exit {
-            frame.quit();
+        #[test]
+        fn player_view() {
+            let mut app = empty_app();
+            app.show_hooks = true;
+            app.tab = gui::TabLabel::Factions;
+
+            app.message_immediate(Message::ConfirmEnterPlayerView {
+                passphrase: "jump".to_string(),
+            })
+            .unwrap();
+            assert!(app.hides_spoilers());
+            assert!(!app.show_hooks);
+            assert!(app.tab == gui::TabLabel::WorldSurvey);
+
+            // Locked with a passphrase, so toggling asks for it instead of leaving
+            app.message_immediate(Message::TogglePlayerView).unwrap();
+            assert!(app.player_view);
+            assert_eq!(app.popup_queue.len(), 1);
+            assert!(app
+                .message_immediate(Message::ConfirmLeavePlayerView {
+                    passphrase: "wrong".to_string(),
+                })
+                .is_err());
+            assert!(app.player_view);
+            assert_eq!(
+                app.message_immediate(Message::ConfirmLeavePlayerView {
+                    passphrase: "jump".to_string(),
+                }),
+                Ok(Some(()))
+            );
+            assert!(!app.player_view);
+
+            // Without a passphrase, toggling leaves right away
+            app.message_immediate(Message::ConfirmEnterPlayerView {
+                passphrase: String::new(),
+            })
+            .unwrap();
+            app.message_immediate(Message::TogglePlayerView).unwrap();
+            assert!(!app.player_view);
         }
 
-        self.check_world_edited();
-        self.process_hotkeys(ctx);
-        self.process_message_queue();
+        #[test]
+        fn political_crisis() {
+            let mut app = empty_app();
+            let point = Point { x: 1, y: 1 };
+            app.message_immediate(Message::ConfirmPoliticalCrisis { event: None })
+                .unwrap();
+            assert!(app.popup_queue.is_empty());
 
-        let unsaved_indicator = if self.has_unsaved_changes() { "*" } else { "" };
-        frame.set_window_title(&format!(
-            "{}{} Subsector",
-            unsaved_indicator,
-            self.subsector.name()
-        ));
+            app.message_immediate(Message::HexGridClicked { new_point: point })
+                .unwrap();
+            app.message_immediate(Message::AddNewWorld).unwrap();
+            app.world.population = TABLES.pop_table[6].clone();
+            app.world.factions.clear();
+            assert!(app
+                .message_immediate(Message::ConfirmPoliticalCrisis {
+                    event: Some(PoliticalEvent::Secession),
+                })
+                .is_err());
+
+            let government = app.world.government.clone();
+            app.message_immediate(Message::ConfirmPoliticalCrisis {
+                event: Some(PoliticalEvent::Coup),
+            })
+            .unwrap();
+            app.check_world_edited();
+            assert!(app.world_edited);
+            assert!(app.world.secret_notes.contains("Coup: "));
+            assert_eq!(app.world.factions[0].government, government);
+        }
 
-        self.show_gui(ctx);
-    }
-}
+        #[test]
+        fn regen_attribute() {
+            let mut app = empty_app();
+            let attribute = WorldAttribute::WorldTags;
+            assert_eq!(
+                app.message_immediate(Message::RegenAttribute { attribute }),
+                Ok(None)
+            );
 
-impl Default for GeneratorApp {
-    fn default() -> Self {
-        Self::with_world_abundance(0)
-    }
-}
+            let mut app = GeneratorApp::default();
+            let point = *app.subsector.get_map().keys().next().unwrap();
+            app.message_immediate(Message::HexGridClicked { new_point: point })
+                .unwrap();
+            app.message_immediate(Message::RegenAttribute { attribute })
+                .unwrap();
+            assert_eq!(app.popup_queue.len(), 1);
 
-impl From<Subsector> for GeneratorApp {
-    fn from(subsector: Subsector) -> Self {
-        Self {
-            subsector,
-            ..Self::empty()
+            app.world.world_tags[0].description = "Edited description".to_string();
+            app.check_world_edited();
+            app.message_immediate(Message::ConfirmRegenAttribute { attribute })
+                .unwrap();
+            assert!(app.subsector_edited);
+            assert_eq!(app.world.world_tags[0].description, "Edited description");
+            assert_eq!(
+                app.subsector.get_world(&point).unwrap().world_tags[0].description,
+                "Edited description"
+            );
         }
-    }
-}
 
-/** Save `contents` directly to the file described by `directory` and `filename` *without* a dialog.
+        #[test]
+        fn regen_world_cultural_detail() {
+            let mut app = GeneratorApp::default();
+            let point = *app.subsector.get_map().keys().next().unwrap();
+            app.message_immediate(Message::HexGridClicked { new_point: point })
+                .unwrap();
+            app.world.cultural_details.dress.clear();
+            app.world.cultural_details.cuisine = "Edited cuisine".to_string();
 
-# Returns
-- `Err` if there was an error while trying to write to the file
-- `Ok(())` if the file was successfully written to
-*/
-fn save_file<P, C>(
-    directory: &P,
-    filename: &P,
-    contents: C,
-) -> Result<(), Box<dyn std::error::Error>>
-where
-    P: AsRef<Path>,
-    C: AsRef<[u8]>,
-{
-    let directory: &Path = directory.as_ref();
-    let filename: &Path = filename.as_ref();
-    let path = directory.join(filename);
-    std::fs::write(path, contents)?;
-    Ok(())
-}
+            app.message_immediate(Message::RegenWorldCulturalDetail {
+                facet: CulturalFacet::Dress,
+            })
+            .unwrap();
+            assert!(!app.world.cultural_details.dress.is_empty());
+            assert_eq!(app.world.cultural_details.cuisine, "Edited cuisine");
+        }
 
-/** Open a `FileDialog` and save `contents` to the selected file.
+        #[test]
+        fn regen_world_encounters() {
+            use swt_gen_core::ENCOUNTER_TABLE_SIZE;
 
-# Arguments
-- `directory`: Directory to which the `FileDialog` initially opens
-- `filename`: Filename to be pre-filled into the `FileDialog`
-- `description`: Description of the file type to be filtered
-- `extensions`: Array of file extensions to filter
-- `contents`: Contents of the file to write to the file system
+            let mut app = GeneratorApp::default();
+            let point = *app.subsector.get_map().keys().next().unwrap();
+            app.message_immediate(Message::HexGridClicked { new_point: point })
+                .unwrap();
+            assert_eq!(app.world.encounters.len(), ENCOUNTER_TABLE_SIZE);
 
-# Returns
-- `Err` if there was an error while trying to save the file
-- `Ok(save_file)` with the path to the selected file if it was able to save successfully
-- `Ok(None)` if there was no error but no directory was selected and no save occurred; usually means
-  the "Cancel" button was selected
-*/
-fn save_file_dialog<P, C>(
-    directory: &P,
-    filename: &str,
-    description: &str,
-    extensions: &[&str],
-    contents: C,
-) -> Result<Option<PathBuf>, Box<dyn std::error::Error>>
-where
-    P: AsRef<Path>,
-    C: AsRef<[u8]>,
-{
-    let path = FileDialog::new()
-        .set_location(directory)
-        .set_filename(filename)
-        .add_filter(description, extensions)
-        .show_save_single_file()?;
+            app.world.encounters[1].description = "Nothing happens".to_string();
+            app.message_immediate(Message::RegenWorldEncounter { index: 1 })
+                .unwrap();
+            assert_eq!(app.world.encounters.len(), ENCOUNTER_TABLE_SIZE);
+            assert_ne!(app.world.encounters[1].description, "Nothing happens");
+            assert_eq!(
+                app.message_immediate(Message::RegenWorldEncounter {
+                    index: ENCOUNTER_TABLE_SIZE
+                }),
+                Ok(None)
+            );
 
-    let save_path = match path {
-        Some(path) => {
-            std::fs::write(path.clone(), contents)?;
-            Some(path)
+            app.world.encounters.truncate(2);
+            app.message_immediate(Message::RegenWorldEncounters)
+                .unwrap();
+            assert_eq!(app.world.encounters.len(), ENCOUNTER_TABLE_SIZE);
         }
 
-        None => None,
-    };
+        #[test]
+        fn regen_world_law_categories() {
+            use swt_gen_core::{LawCategory, TABLES};
 
-    Ok(save_path)
-}
+            let mut app = empty_app();
+            let point = Point { x: 1, y: 1 };
+            app.message_immediate(Message::HexGridClicked { new_point: point })
+                .unwrap();
+            app.message_immediate(Message::AddNewWorld).unwrap();
 
-/** Open a `FileDialog` and read in the selected file.
+            app.world.law_level = TABLES.law_table[0].clone();
+            app.world.law_categories = None;
+            app.message_immediate(Message::RegenWorldLawCategories)
+                .unwrap();
+            for category in LawCategory::ALL_VALUES {
+                assert_eq!(app.world.law_category_level(category), 0);
+            }
+            assert!(app.world.law_categories.is_some());
+        }
 
-# Arguments
-- `directory`: Directory to which the `FileDialog` initially opens
-- `description`: Description of the file type to be filtered
-- `extensions`: Array of file extensions to filter
+        #[test]
+        fn regen_world_nobility() {
+            let mut app = GeneratorApp::default();
+            let point = *app.subsector.get_map().keys().next().unwrap();
+            app.message_immediate(Message::HexGridClicked { new_point: point })
+                .unwrap();
+            let mut generated = app.world.clone();
+            generated.generate_nobility();
 
-# Returns
-- `Err` if there was an error while trying to read the file
-- `Ok((loaded_path, contents))` with the path to the loaded file if it was able to save successfully
-- `Ok(None)` if there was no error but no file was selected and no contents were loaded; usually
-  means the "Cancel" button was selected
-*/
-fn load_file_to_string<P: AsRef<Path>>(
-    directory: &P,
-    description: &str,
-    extensions: &[&str],
-) -> Result<Option<(PathBuf, String)>, Box<dyn std::error::Error>> {
-    let path = FileDialog::new()
-        .set_location(directory)
-        .add_filter(description, extensions)
-        .show_open_single_file()?;
+            app.world.nobility = "BF".to_string();
+            app.message_immediate(Message::WorldModelUpdated).unwrap();
+            assert_eq!(app.world.nobility, "BF");
 
-    let loaded_file = match path {
-        Some(path) => {
-            let contents = std::fs::read_to_string(path.clone())?;
-            Some((path, contents))
+            app.message_immediate(Message::RegenWorldNobility).unwrap();
+            assert_eq!(app.world.nobility, generated.nobility);
         }
-        None => None,
-    };
 
-    Ok(loaded_file)
-}
+        #[test]
+        fn regen_world_religion() {
+            let mut app = GeneratorApp::default();
+            let point = *app.subsector.get_map().keys().next().unwrap();
+            app.message_immediate(Message::HexGridClicked { new_point: point })
+                .unwrap();
+            app.world.subtype = WorldSubtype::Mainworld;
+            app.world.religion = Religion::default();
+            app.world.factions = vec![Faction::random()];
+            app.world.factions[0].religion = Religion::default();
+            app.faction_idx = 0;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+            app.message_immediate(Message::RegenWorldReligion).unwrap();
+            assert!(!app.world.religion.is_empty());
+            assert!(app.world.factions[0].religion.is_empty());
 
-    fn empty_app() -> GeneratorApp {
-        GeneratorApp {
-            subsector: Subsector::empty(),
-            ..Default::default()
-        }
-    }
+            app.message_immediate(Message::RegenFactionReligion)
+                .unwrap();
+            assert!(!app.world.factions[0].religion.is_empty());
 
-    mod message_tests {
-        use super::*;
+            app.faction_idx = 1;
+            assert_eq!(
+                app.message_immediate(Message::RegenFactionReligion),
+                Ok(None)
+            );
+        }
 
         #[test]
-        fn add_new_faction() {
+        fn regen_world_travel_advisory() {
             let mut app = GeneratorApp::default();
-            let occupied_points: Vec<_> = app.subsector.get_map().keys().cloned().collect();
-            assert!(occupied_points.get(0).is_some());
-            let point = occupied_points[0];
+            let point = *app.subsector.get_map().keys().next().unwrap();
             app.message_immediate(Message::HexGridClicked { new_point: point })
                 .unwrap();
-            match app.subsector.get_world(&point) {
-                Some(world) => assert_eq!(app.world.factions, world.factions),
-                None => panic!("Empty point got in somehow"),
-            }
+            assert!(!app.world.travel_advisory.is_empty());
 
-            app.message_immediate(Message::AddNewFaction).unwrap();
-            match app.subsector.get_world(&point) {
-                Some(world) => {
-                    assert_ne!(app.world.factions, world.factions);
-                    assert_eq!(app.world.factions.len(), world.factions.len() + 1);
-                }
-                None => panic!("Empty point got in somehow"),
-            }
+            app.world.travel_advisory = "Edited by hand".to_string();
+            app.world.law_level = TABLES.law_table[9].clone();
+            app.world.travel_code_overridden = false;
+            app.message_immediate(Message::RegenWorldTravelAdvisory)
+                .unwrap();
+            assert_eq!(app.world.travel_code, TravelCode::Amber);
+            assert!(app.world.travel_advisory.starts_with("Amber Zone: "));
+            assert!(app
+                .world
+                .travel_advisory
+                .contains("customs inspections common under law level 9"));
         }
 
         #[test]
-        fn add_new_world() {
+        fn reload_save_file() {
+            let directory =
+                std::env::temp_dir().join(format!("swt-gen-reload-{}", std::process::id()));
+            std::fs::create_dir_all(&directory).unwrap();
+            let path = directory.join("Regina Subsector.json");
+            let mut subsector = Subsector::default();
+            std::fs::write(&path, subsector.to_json()).unwrap();
+
             let mut app = empty_app();
+            app.load_json(&path, &subsector.to_json()).unwrap();
+            app.check_save_file();
+            assert!(app.popup_queue.is_empty());
+
+            // Edit the file as if by hand, making sure its modification time changes
+            subsector.set_name("Efate".to_string());
+            std::fs::write(&path, subsector.to_json()).unwrap();
+            std::fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(SystemTime::now() + Duration::from_secs(60))
+                .unwrap();
 
-            let unoccupied_point = Point { x: 1, y: 1 };
-            assert!(app.subsector.get_world(&unoccupied_point).is_none());
+            // Changes are only looked for once every `SAVE_FILE_CHECK_INTERVAL`
+            app.check_save_file();
+            assert!(app.popup_queue.is_empty());
+            app.save_file_checked = None;
+            app.check_save_file();
+            assert_eq!(app.popup_queue.len(), 1);
+
+            // The same change isn't brought up twice
+            app.popup_queue.clear();
+            app.save_file_checked = None;
+            app.check_save_file();
+            assert!(app.popup_queue.is_empty());
+
+            app.message_immediate(Message::ReloadSaveFile).unwrap();
+            assert_eq!(app.subsector.name(), "Efate");
+            assert!(!app.has_unsaved_changes());
+
+            std::fs::remove_dir_all(&directory).unwrap();
+        }
 
+        #[test]
+        fn rename_duplicate_worlds() {
+            let mut app = empty_app();
+            let points = [Point { x: 1, y: 1 }, Point { x: 2, y: 2 }];
+            for point in points {
+                let mut world = World::empty();
+                world.name = "Regina".to_string();
+                app.subsector.insert_world(&point, world).unwrap();
+            }
             app.message_immediate(Message::HexGridClicked {
-                new_point: unoccupied_point,
+                new_point: points[1],
             })
             .unwrap();
-            assert!(app.point_selected);
-            assert_eq!(app.point, unoccupied_point);
-            assert!(!app.world_selected);
-
-            app.message_immediate(Message::AddNewWorld).unwrap();
-            assert!(app.subsector.get_world(&unoccupied_point).is_some());
-            assert!(app.point_selected);
-            assert_eq!(app.point, unoccupied_point);
-            assert!(app.world_selected);
+            assert_eq!(app.subsector.duplicate_names().len(), 1);
 
-            assert!(app.has_unsaved_changes());
+            assert_eq!(
+                app.message_immediate(Message::RenameDuplicateWorlds),
+                Ok(Some(()))
+            );
+            assert!(app.subsector.duplicate_names().is_empty());
+            assert_eq!(app.subsector.get_world(&points[0]).unwrap().name, "Regina");
+            // The selected world picks up its new name
+            assert_ne!(app.world.name, "Regina");
+            assert_eq!(app.world, *app.subsector.get_world(&points[1]).unwrap());
+
+            assert_eq!(
+                app.message_immediate(Message::RenameDuplicateWorlds),
+                Ok(None)
+            );
         }
 
         #[test]
-        fn apply_world_changes() {
+        fn reroll_dependents() {
             let mut app = empty_app();
             let point = Point { x: 1, y: 1 };
-            assert!(app.subsector.get_world(&point).is_none());
-
             app.message_immediate(Message::HexGridClicked { new_point: point })
                 .unwrap();
             app.message_immediate(Message::AddNewWorld).unwrap();
-            app.check_world_edited();
-            assert!(app.subsector.get_world(&point).is_some());
-            assert_eq!(app.world, *app.subsector.get_world(&point).unwrap());
-            assert!(!app.world_edited);
 
-            app.world.notes = "Blah blah blah".to_string();
-            app.check_world_edited();
-            assert_ne!(app.world, *app.subsector.get_world(&point).unwrap());
-            assert!(app.world_edited);
+            // An uninhabited world can't keep its government
+            app.world.population = TABLES.pop_table[0].clone();
+            app.world.government = TABLES.gov_table[5].clone();
+            let edited = Message::WorldFieldEdited {
+                field: WorldField::Population,
+            };
+            app.message_immediate(edited.clone()).unwrap();
+            assert!(app.popup_queue.is_empty());
 
-            app.message_immediate(Message::ApplyWorldChanges).unwrap();
-            app.check_world_edited();
-            assert_eq!(app.world, *app.subsector.get_world(&point).unwrap());
-            assert!(!app.world_edited);
+            app.settings.reroll_dependents = true;
+            app.message_immediate(edited).unwrap();
+            assert_eq!(app.popup_queue.len(), 1);
+            app.popup_queue.clear();
+
+            let world = Box::new(app.world.with_dependents_rerolled(WorldField::Population));
+            app.message_immediate(Message::ConfirmRerollDependents { world })
+                .unwrap();
+            assert_eq!(app.world.population.code, 0);
+            assert_eq!(app.world.government.code, 0);
+            assert_eq!(app.world.law_level.code, 0);
         }
 
         #[test]
-        fn hex_grid_clicked() {
-            let mut app = GeneratorApp::default();
-
-            // Test hex clicking on all points with no world changes
-            for x in 1..=Subsector::COLUMNS {
-                for y in 1..=Subsector::ROWS {
-                    let point = Point {
-                        x: x as i32,
-                        y: y as i32,
-                    };
+        fn restore_deleted_world() {
+            let mut app = empty_app();
+            let first = Point { x: 1, y: 1 };
+            let second = Point { x: 2, y: 2 };
+            let elsewhere = Point { x: 3, y: 3 };
+            app.subsector.insert_random_world(&first).unwrap();
+            app.subsector.insert_random_world(&second).unwrap();
+            let first_world = app.subsector.get_world(&first).unwrap().clone();
+            let second_world = app.subsector.get_world(&second).unwrap().clone();
+
+            app.message_immediate(Message::ConfirmRemoveWorld { point: first })
+                .unwrap();
+            app.message_immediate(Message::ConfirmRemoveWorld { point: second })
+                .unwrap();
+            assert!(app.subsector.get_map().is_empty());
+            assert_eq!(app.deleted_worlds.len(), 2);
 
-                    app.message_immediate(Message::HexGridClicked { new_point: point })
-                        .unwrap();
-                    assert!(app.point_selected);
-                    assert_eq!(app.point, point);
-                    match app.subsector.get_world(&point) {
-                        Some(world) => {
-                            assert!(app.world_selected);
-                            assert_eq!(app.world, *world);
-                            assert_eq!(app.point_str, point.to_string());
-                            assert_eq!(app.diameter_str, world.diameter.to_string());
-                            assert_eq!(
-                                app.berthing_cost_str,
-                                world.starport.berthing_cost.to_string()
-                            );
-                        }
+            // Restoring to the original hex
+            app.message_immediate(Message::RestoreDeletedWorld {
+                index: 0,
+                point: first,
+            })
+            .unwrap();
+            assert_eq!(app.subsector.get_world(&first), Some(&first_world));
+            assert!(app.world_selected);
+            assert_eq!(app.point, first);
+
+            // Restoring to another hex, which must be empty
+            assert!(app
+                .message_immediate(Message::RestoreDeletedWorld {
+                    index: 0,
+                    point: first,
+                })
+                .is_err());
+            app.message_immediate(Message::RestoreDeletedWorld {
+                index: 0,
+                point: elsewhere,
+            })
+            .unwrap();
+            assert_eq!(app.subsector.get_world(&elsewhere), Some(&second_world));
+            assert!(app.deleted_worlds.is_empty());
+            assert!(app
+                .message_immediate(Message::RestoreDeletedWorld {
+                    index: 0,
+                    point: second,
+                })
+                .is_err());
+
+            app.message_immediate(Message::ConfirmRemoveWorld { point: first })
+                .unwrap();
+            app.message_immediate(Message::ClearDeletedWorlds).unwrap();
+            assert!(app.deleted_worlds.is_empty());
+        }
 
-                        None => {
-                            assert!(!app.world_selected);
-                        }
-                    }
-                }
-            }
+        #[test]
+        fn save_as() {
+            let (mut app, file_io) = fake_file_io_app();
+            app.message_immediate(Message::AddRandomWorld).unwrap();
+            assert!(app.has_unsaved_changes());
 
-            // Test hex clicking after making changes to selected world
-            let occupied_points: Vec<_> = app.subsector.get_map().keys().cloned().collect();
-            assert!(occupied_points.get(0).is_some());
-            let point = occupied_points[0];
-            assert!(app.subsector.get_world(&point).is_some());
+            let path = Path::new("/saves/Test Subsector.json");
+            file_io.pick(path);
+            app.message_immediate(Message::SaveAs).unwrap();
+            assert_eq!(app.save_path(), path);
+            assert!(!app.has_unsaved_changes());
+            assert_eq!(
+                file_io.file(path).unwrap(),
+                app.subsector_json().into_bytes()
+            );
+        }
 
-            let other_x = if point.x == Subsector::COLUMNS as i32 {
-                point.x - 1
-            } else {
-                point.x + 1
-            };
+        #[test]
+        fn set_travel_codes() {
+            let mut app = GeneratorApp::default();
+            let point = *app.subsector.get_map().keys().next().unwrap();
+            app.message_immediate(Message::HexGridClicked { new_point: point })
+                .unwrap();
 
-            let other_y = if point.y == Subsector::ROWS as i32 {
-                point.y - 1
-            } else {
-                point.y + 1
+            let policy = TravelCodePolicy {
+                condition: TravelCodeCondition::All,
+                travel_code: TravelCode::Red,
             };
+            assert_eq!(
+                app.message_immediate(Message::ConfirmSetTravelCodes { policy }),
+                Ok(Some(()))
+            );
+            assert!(app.subsector_edited);
+            assert_eq!(app.world.travel_code, TravelCode::Red);
+            assert!(app
+                .subsector
+                .get_map()
+                .values()
+                .all(|world| world.travel_code == TravelCode::Red));
+
+            // Nothing left to change
+            assert_eq!(
+                app.message_immediate(Message::ConfirmSetTravelCodes { policy }),
+                Ok(None)
+            );
+        }
 
-            let new_point = Point {
-                x: other_x,
-                y: other_y,
-            };
+        #[test]
+        fn set_ui_scale() {
+            let mut app = GeneratorApp::default();
+            assert_eq!(app.settings.ui_scale, 1.0);
 
-            let blah = "Blah blah blah blah".to_string();
+            app.message_immediate(Message::SetUiScale { scale: 1.5 })
+                .unwrap();
+            assert_eq!(app.settings.ui_scale, 1.5);
 
-            app.message_immediate(Message::HexGridClicked { new_point: point })
+            // Out of range scales are clamped rather than rejected
+            app.message_immediate(Message::SetUiScale { scale: 10.0 })
                 .unwrap();
+            assert_eq!(app.settings.ui_scale, MAX_UI_SCALE);
+            app.message_immediate(Message::SetUiScale { scale: 0.0 })
+                .unwrap();
+            assert_eq!(app.settings.ui_scale, MIN_UI_SCALE);
+        }
 
-            // Just making some/any change to the now selected world
-            app.world.notes = blah.clone();
-            app.check_world_edited();
-            assert!(app.world_edited);
+        #[test]
+        fn subsector_model_updated() {
+            let mut app = GeneratorApp::default();
+            assert!(app.redraw_subsector_grid());
 
-            app.message_immediate(Message::HexGridClicked { new_point })
+            // Edits are painted over the grid, so they never wait on it to be redrawn
+            app.subsector.set_name("Renamed".to_string());
+            app.message_immediate(Message::SubsectorModelUpdated)
                 .unwrap();
-            assert!(app.popup_queue.get(0).is_some());
-            app.popup_queue.remove(0);
+            assert!(app.subsector_edited);
+            assert!(!app.map_redraw.is_waiting());
+            assert!(!app.redraw_subsector_grid());
 
-            // Nothing should change if the "cancel" button was hit on the popup
-            app.message_immediate(Message::NoOp).unwrap();
-            assert_eq!(app.point, point);
+            app.settings.map_hex_numbers = !app.settings.map_hex_numbers;
+            assert!(app.redraw_subsector_grid());
+        }
 
-            // Repeat as if the user had pressed the "don't apply" button
-            app.message_immediate(Message::HexGridClicked { new_point })
+        #[test]
+        fn unsaved_worlds() {
+            let mut app = GeneratorApp::default();
+            assert!(app.unsaved_worlds().is_empty());
+
+            let mut points = app.subsector.get_map().keys().cloned();
+            let edited = points.next().unwrap();
+            let removed = points.next().unwrap();
+            let added = (1..=Subsector::COLUMNS as i32)
+                .flat_map(|x| (1..=Subsector::ROWS as i32).map(move |y| Point { x, y }))
+                .find(|point| app.subsector.get_world(point).is_none())
                 .unwrap();
-            assert!(app.popup_queue.get(0).is_some());
-            app.popup_queue.remove(0);
 
-            app.message_immediate(Message::ConfirmHexGridClicked { new_point })
+            app.message_immediate(Message::HexGridClicked { new_point: edited })
                 .unwrap();
-            assert_eq!(app.point, new_point);
-
+            app.world.secret_notes = "Changed".to_string();
             app.check_world_edited();
-            assert!(!app.world_edited);
+            assert_eq!(app.unsaved_worlds(), vec![(edited, UnsavedChange::Edited)]);
+            app.message_immediate(Message::ApplyWorldChanges).unwrap();
 
-            // Confirm that the change was not kept
-            app.message_immediate(Message::HexGridClicked { new_point: point })
+            app.message_immediate(Message::ConfirmRemoveWorld { point: removed })
                 .unwrap();
-            assert_eq!(app.world.notes, String::new());
+            app.subsector.insert_random_world(&added).unwrap();
+            let mut expected = vec![
+                (added, UnsavedChange::Added),
+                (edited, UnsavedChange::Edited),
+                (removed, UnsavedChange::Removed),
+            ];
+            expected.sort_by_key(|(point, _)| *point);
+            assert_eq!(app.unsaved_worlds(), expected);
+
+            // Saving makes the current worlds the new baseline
+            app.saved_worlds = app.subsector.get_map().clone();
+            assert!(app.unsaved_worlds().is_empty());
+        }
 
-            // Repeat as if the "apply" button had been pressed
-            app.world.notes = blah.clone();
+        #[test]
+        fn viewer_mode() {
+            let mut app = empty_app();
+            let point = Point { x: 1, y: 1 };
+            app.message_immediate(Message::HexGridClicked { new_point: point })
+                .unwrap();
+            app.message_immediate(Message::AddNewWorld).unwrap();
+            app.world.name = "Unapplied".to_string();
             app.check_world_edited();
-            assert!(app.world_edited);
 
-            app.message_immediate(Message::HexGridClicked { new_point })
-                .unwrap();
-            assert!(app.popup_queue.get(0).is_some());
-            app.popup_queue.remove(0);
-            app.message_immediate(Message::ApplyConfirmHexGridClicked { new_point })
-                .unwrap();
-            assert_eq!(app.point, new_point);
+            // Changes made before entering the viewer are kept
+            app.message_immediate(Message::ToggleViewerMode).unwrap();
+            assert!(app.viewer_mode);
+            assert_eq!(app.subsector.get_world(&point).unwrap().name, "Unapplied");
 
-            app.check_world_edited();
-            assert!(!app.world_edited);
+            let other = Point { x: 2, y: 2 };
+            assert_eq!(
+                app.message_immediate(Message::HexGridClicked { new_point: other }),
+                Ok(Some(()))
+            );
+            assert_eq!(app.point, other);
+            assert_eq!(app.message_immediate(Message::AddNewWorld), Ok(None));
+            assert_eq!(app.message_immediate(Message::Save), Ok(None));
+            assert!(app.subsector.get_world(&other).is_none());
 
-            // Confirm that the change was kept
-            assert_eq!(app.subsector.get_world(&point).unwrap().notes, blah);
+            app.message_immediate(Message::ToggleViewerMode).unwrap();
+            assert!(!app.viewer_mode);
+            app.message_immediate(Message::AddNewWorld).unwrap();
+            assert!(app.subsector.get_world(&other).is_some());
         }
 
         #[test]
-        fn new_faction_gov_selected() {
+        fn world_buffers_validated() {
             let mut app = empty_app();
             let point = Point { x: 1, y: 1 };
             app.message_immediate(Message::HexGridClicked { new_point: point })
                 .unwrap();
             app.message_immediate(Message::AddNewWorld).unwrap();
+            app.world.size = 5;
+            let diameter = app.world.diameter;
 
-            if app.world.factions.is_empty() {
-                app.message_immediate(Message::AddNewFaction).unwrap();
-            }
-            assert!(!app.world.factions.is_empty());
+            // Rejected entries are reverted and explained
+            app.diameter_buffer.text = "big".to_string();
+            app.point_buffer.text = "0911".to_string();
+            app.message_immediate(Message::WorldDiameterUpdated)
+                .unwrap();
+            app.message_immediate(Message::WorldLocUpdated).unwrap();
+            assert_eq!(app.diameter_buffer.text, diameter.to_string());
+            assert!(app.diameter_buffer.warning(None).unwrap().contains("big"));
+            assert!(app.point_buffer.warning(None).unwrap().contains("outside"));
+
+            // Out of range values are kept unless snapping is enabled
+            app.diameter_buffer.text = "100".to_string();
+            app.message_immediate(Message::WorldDiameterUpdated)
+                .unwrap();
+            assert_eq!(app.world.diameter, 100);
+            assert_eq!(app.diameter_buffer.warning(None), None);
+            assert!(app.world.diameter_warning().is_some());
+
+            app.settings.snap_to_legal_ranges = true;
+            app.diameter_buffer.text = "100".to_string();
+            app.berthing_cost_buffer.text = "1".to_string();
+            app.message_immediate(Message::WorldDiameterUpdated)
+                .unwrap();
+            app.message_immediate(Message::WorldBerthingCostsUpdated)
+                .unwrap();
+            assert_eq!(app.world.diameter, 7800);
+            assert_eq!(app.diameter_buffer.text, "7800");
+            assert_eq!(app.world.diameter_warning(), None);
+            assert_eq!(app.world.berthing_cost_warning(), None);
+            assert_eq!(
+                app.berthing_cost_buffer.text,
+                app.world.starport.berthing_cost.to_string()
+            );
 
-            // Simulate selecting a new faction by simply changing the faction_idx
-            app.faction_idx = 0;
-            let faction = &mut app.world.factions[app.faction_idx];
+            app.message_immediate(Message::HexGridClicked { new_point: point })
+                .unwrap();
+            assert_eq!(app.diameter_buffer.warning(None), None);
+            assert_eq!(app.point_buffer.warning(None), None);
+        }
 
-            let gov_table = &TABLES.gov_table;
-            let default_description = &gov_table
-                .iter()
-                .find(|g| g.kind == faction.government.kind)
-                .unwrap()
-                .description;
-            assert_eq!(faction.government.description, *default_description);
+        #[test]
+        fn world_orbital_facilities() {
+            use swt_gen_core::FacilityKind;
 
-            let new_gov = gov_table
-                .iter()
-                .find(|g| g.kind != faction.government.kind)
+            let mut app = GeneratorApp::default();
+            let point = *app.subsector.get_map().keys().next().unwrap();
+            app.message_immediate(Message::HexGridClicked { new_point: point })
                 .unwrap();
-            assert_ne!(*new_gov, faction.government);
 
-            // Simulate choosing a new faction government selectable value on the GUI by changing
-            // government "kind" directly and messaging NewFactionGovSelected with the new code
-            faction.government.kind = new_gov.kind.clone();
-            app.message_immediate(Message::NewFactionGovSelected {
-                new_code: new_gov.code,
-            })
-            .unwrap();
+            app.message_immediate(Message::RegenWorldOrbitalFacilities)
+                .unwrap();
+            let mut expected = app.world.clone();
+            expected.generate_orbital_facilities();
+            assert_eq!(app.world.orbital_facilities, expected.orbital_facilities);
 
-            let faction = &mut app.world.factions[app.faction_idx];
-            assert_eq!(faction.government.code, new_gov.code);
-            assert_eq!(faction.government.kind, new_gov.kind);
-            // Because we didn't change the faction's government description from the default on the
-            // gov_table, it should have updated to that of the newly selected government
-            assert_eq!(faction.government.description, new_gov.description);
-            // We don't change the contraband of the faction's government because we don't care or
-            // display contraband in the context of factions
+            app.world.orbital_facilities.clear();
+            app.message_immediate(Message::AddWorldOrbitalFacility)
+                .unwrap();
+            app.message_immediate(Message::AddWorldOrbitalFacility)
+                .unwrap();
+            app.world.orbital_facilities[1].kind = FacilityKind::NavalStation;
+            let kept = app.world.orbital_facilities[1].clone();
+            app.message_immediate(Message::RemoveWorldOrbitalFacility { index: 0 })
+                .unwrap();
+            assert_eq!(app.world.orbital_facilities, vec![kept]);
+            assert_eq!(
+                app.message_immediate(Message::RemoveWorldOrbitalFacility { index: 5 })
+                    .unwrap(),
+                None
+            );
 
-            // We repeat the same test, but this time we change the faction's government description
-            // from the default and confirm that it is retained
-            let default_description = &gov_table
-                .iter()
-                .find(|g| g.kind == faction.government.kind)
-                .unwrap()
-                .description;
-            assert_eq!(faction.government.description, *default_description);
+            app.check_world_edited();
+            app.message_immediate(Message::ApplyWorldChanges).unwrap();
+            let world = app.subsector.get_world(&point).unwrap();
+            assert_eq!(world.orbital_facilities, app.world.orbital_facilities);
+        }
 
-            let new_gov = gov_table
-                .iter()
-                .find(|g| g.kind != faction.government.kind)
+        #[test]
+        fn world_sophonts() {
+            let mut app = GeneratorApp::default();
+            let point = *app.subsector.get_map().keys().next().unwrap();
+            app.message_immediate(Message::HexGridClicked { new_point: point })
                 .unwrap();
-            assert_ne!(*new_gov, faction.government);
+            app.world.sophonts.clear();
 
-            let blah = "Blah blah blah".to_string();
-            faction.government.description = blah.clone();
-            faction.government.kind = new_gov.kind.clone();
-            app.message_immediate(Message::NewFactionGovSelected {
-                new_code: new_gov.code,
-            })
-            .unwrap();
+            app.message_immediate(Message::AddWorldSophont).unwrap();
+            app.message_immediate(Message::AddWorldSophont).unwrap();
+            assert_eq!(app.world.sophonts.len(), 2);
 
-            let faction = &app.world.factions[app.faction_idx];
-            assert_eq!(faction.government.code, new_gov.code);
-            assert_eq!(faction.government.kind, new_gov.kind);
-            // Because we changed the faction's government description from the default it should
-            // have been retained
-            assert_eq!(faction.government.description, blah);
+            let kept = app.world.sophonts[1].clone();
+            app.message_immediate(Message::RemoveWorldSophont { index: 0 })
+                .unwrap();
+            assert_eq!(app.world.sophonts, vec![kept]);
+            app.message_immediate(Message::RemoveWorldSophont { index: 5 })
+                .unwrap();
+            assert_eq!(app.world.sophonts.len(), 1);
+
+            app.check_world_edited();
+            app.message_immediate(Message::ApplyWorldChanges).unwrap();
+            let world = app.subsector.get_world(&point).unwrap();
+            assert_eq!(world.sophonts, app.world.sophonts);
+            assert!(world
+                .history
+                .iter()
+                .any(|change| change.field == "Sophonts"));
         }
 
         #[test]
-        fn new_starport_class_selected() {
-            use crate::astrography::StarportClass;
-
+        fn world_system_contents_updated() {
             let mut app = empty_app();
             let point = Point { x: 1, y: 1 };
             app.message_immediate(Message::HexGridClicked { new_point: point })
                 .unwrap();
             app.message_immediate(Message::AddNewWorld).unwrap();
 
-            let old_starport = app.world.starport.clone();
-            let new_class = match app.world.starport.class {
-                StarportClass::A => StarportClass::B,
-                StarportClass::B => StarportClass::C,
-                StarportClass::C => StarportClass::D,
-                StarportClass::D => StarportClass::E,
-                StarportClass::E => StarportClass::X,
-                StarportClass::X => StarportClass::A,
-            };
-            let new_starport = TABLES
-                .starport_table
-                .iter()
-                .find(|sp| sp.class == new_class)
+            app.gas_giant_buffer.text = " 3 ".to_string();
+            app.belt_buffer.text = "2".to_string();
+            app.message_immediate(Message::WorldGasGiantsUpdated)
                 .unwrap();
-
-            app.world.starport.class = new_class;
-            app.message_immediate(Message::NewStarportClassSelected)
+            app.message_immediate(Message::WorldPlanetoidBeltsUpdated)
                 .unwrap();
-            assert_ne!(app.world.starport, old_starport);
-            assert_eq!(app.world.starport.code, new_starport.code);
-            assert_eq!(app.world.starport.class, new_starport.class);
-            // Generated berthing costs are 1d6 * the "base" starport table berthing cost; just need
-            // to account for when berthing costs are zero
-            if new_starport.berthing_cost != 0 {
-                assert!(app.world.starport.berthing_cost % new_starport.berthing_cost == 0);
-            } else {
-                assert_eq!(app.world.starport.berthing_cost, new_starport.berthing_cost);
-            }
-            assert_eq!(app.world.starport.fuel, new_starport.fuel);
-            assert_eq!(app.world.starport.facilities, new_starport.facilities);
+            assert_eq!(app.world.gas_giants, 3);
+            assert_eq!(app.world.planetoid_belts, Some(2));
+            assert_eq!(app.world.pbg_str(), "123");
+
+            // Negative counts are rejected and the buffers are reset
+            app.gas_giant_buffer.text = "-1".to_string();
+            app.belt_buffer.text = "-1".to_string();
+            app.message_immediate(Message::WorldGasGiantsUpdated)
+                .unwrap();
+            app.message_immediate(Message::WorldPlanetoidBeltsUpdated)
+                .unwrap();
+            assert_eq!(app.world.gas_giants, 3);
+            assert_eq!(app.world.planetoid_belts, Some(2));
+            assert_eq!(app.gas_giant_buffer.text, "3");
+            assert_eq!(app.belt_buffer.text, "2");
+
+            app.message_immediate(Message::RegenWorldGasGiants).unwrap();
+            assert_eq!(app.gas_giant_buffer.text, app.world.gas_giants.to_string());
+        }
+    }
+
+    #[test]
+    fn tile_paths() {
+        let path = Path::new("maps").join("Regina Subsector Map.svg");
+        assert_eq!(
+            tile_path(&path, 1, 4),
+            Path::new("maps").join("Regina Subsector Map (Tile 2 of 4).svg")
+        );
+        assert_eq!(
+            tile_path(Path::new("Map"), 0, 4),
+            PathBuf::from("Map (Tile 1 of 4)")
+        );
+    }
+
+    #[test]
+    fn world_sheets_written() {
+        let directory = std::env::temp_dir().join(format!("swt-gen-sheets-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let worlds: Vec<(Point, World)> = ["Regina", "Efate", "Ruie"]
+            .iter()
+            .enumerate()
+            .map(|(x, name)| {
+                let mut world = World::empty();
+                world.name = name.to_string();
+                (
+                    Point {
+                        x: x as i32 + 1,
+                        y: 1,
+                    },
+                    world,
+                )
+            })
+            .collect();
+
+        let (progress_tx, progress_rx) = mpsc::channel();
+        let mut options = SheetOptions {
+            format: SheetFormat::Markdown,
+            numbers: NumberFormat::default(),
+            thumbnail: true,
+            hooks: vec!["Rumors about Regina (0101) have spread across the subsector.".to_string()],
+        };
+        write_world_sheets(&PlatformFileIo, &directory, &worlds, &options, &progress_tx);
+        let progress: Vec<usize> = progress_rx.try_iter().map(Result::unwrap).collect();
+        assert_eq!(progress, (1..=worlds.len()).collect::<Vec<_>>());
+
+        for (point, world) in worlds.iter() {
+            let path = directory.join(world.sheet_file_name(point, SheetFormat::Markdown));
+            assert_eq!(
+                std::fs::read(path).unwrap(),
+                world.sheet(
+                    point,
+                    SheetFormat::Markdown,
+                    &options.numbers,
+                    true,
+                    &options.hooks
+                )
+            );
         }
+
+        // Writing to a directory that doesn't exist stops at the first failure
+        std::fs::remove_dir_all(&directory).unwrap();
+        options.format = SheetFormat::Pdf;
+        options.thumbnail = false;
+        write_world_sheets(&PlatformFileIo, &directory, &worlds, &options, &progress_tx);
+        let progress: Vec<_> = progress_rx.try_iter().collect();
+        assert_eq!(progress.len(), 1);
+        assert!(progress[0].is_err());
     }
 }
@@ -0,0 +1,185 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use swt_gen_core::{current_timestamp, file_timestamp_str};
+
+/// Folder next to a saved file that `BackupStyle::Timestamped` backups are kept in
+const HISTORY_DIRECTORY: &str = ".history";
+
+/** How earlier versions of a subsector file are kept when it is saved over. */
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub(crate) enum BackupStyle {
+    /// Files are overwritten without keeping anything
+    #[default]
+    None,
+    /// Earlier versions are kept next to the file as "name.json.bak1", "name.json.bak2", and so
+    /// on, from newest to oldest
+    Numbered,
+    /// Earlier versions are kept in a ".history" folder next to the file, named after when they
+    /// were saved over, e.g. "name.json.2022-06-01_134500"
+    Timestamped,
+}
+
+impl BackupStyle {
+    pub(crate) const ALL_VALUES: [BackupStyle; 3] = [Self::None, Self::Numbered, Self::Timestamped];
+}
+
+impl fmt::Display for BackupStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::None => "Don't Keep Backups",
+            Self::Numbered => "Numbered (.bak1, .bak2, ...)",
+            Self::Timestamped => "Timestamped in .history Folder",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/** Keep a copy of the file at `path` in the given `style` before it is saved over.
+
+At most `retention` versions are kept; the oldest ones beyond that are deleted. Nothing is done if
+the file doesn't exist yet.
+*/
+pub(crate) fn back_up_file(path: &Path, style: BackupStyle, retention: usize) -> io::Result<()> {
+    if !path.is_file() || retention == 0 {
+        return Ok(());
+    }
+
+    match style {
+        BackupStyle::None => Ok(()),
+        BackupStyle::Numbered => back_up_numbered(path, retention),
+        BackupStyle::Timestamped => {
+            back_up_timestamped(path, &file_timestamp_str(current_timestamp()), retention)
+        }
+    }
+}
+
+/** Shift every numbered backup of `path` up by one, dropping the oldest, and copy `path` to the
+first one.
+*/
+fn back_up_numbered(path: &Path, retention: usize) -> io::Result<()> {
+    let numbered = |number: usize| {
+        let mut name = path.as_os_str().to_owned();
+        name.push(format!(".bak{}", number));
+        PathBuf::from(name)
+    };
+
+    let oldest = numbered(retention);
+    if oldest.exists() {
+        fs::remove_file(oldest)?;
+    }
+    for number in (1..retention).rev() {
+        let backup = numbered(number);
+        if backup.exists() {
+            fs::rename(backup, numbered(number + 1))?;
+        }
+    }
+    fs::copy(path, numbered(1))?;
+    Ok(())
+}
+
+/** Copy `path` into the history folder next to it stamped with `timestamp`, then delete the oldest
+of its backups there beyond `retention`.
+*/
+fn back_up_timestamped(path: &Path, timestamp: &str, retention: usize) -> io::Result<()> {
+    let file_name = match path.file_name() {
+        Some(file_name) => file_name.to_string_lossy().to_string(),
+        None => return Ok(()),
+    };
+    let directory = path
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join(HISTORY_DIRECTORY);
+    fs::create_dir_all(&directory)?;
+    fs::copy(path, directory.join(format!("{}.{}", file_name, timestamp)))?;
+
+    // Timestamps sort in chronological order, so the oldest backups come first
+    let prefix = format!("{}.", file_name);
+    let mut backups: Vec<PathBuf> = fs::read_dir(&directory)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|backup| {
+            backup
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_prefix(&prefix))
+                .is_some_and(|stamp| {
+                    !stamp.is_empty()
+                        && stamp
+                            .chars()
+                            .all(|c| c.is_ascii_digit() || c == '-' || c == '_')
+                })
+        })
+        .collect();
+    backups.sort();
+
+    let excess = backups.len().saturating_sub(retention);
+    for backup in backups.drain(..excess) {
+        fs::remove_file(backup)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backups() {
+        let directory =
+            std::env::temp_dir().join(format!("swt-gen-backups-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("Regina Subsector.json");
+        let read = |path: PathBuf| fs::read_to_string(path).unwrap();
+
+        // Nothing to back up before the first save
+        back_up_file(&path, BackupStyle::Numbered, 2).unwrap();
+        assert_eq!(fs::read_dir(&directory).unwrap().count(), 0);
+
+        for version in 1..=3 {
+            fs::write(&path, version.to_string()).unwrap();
+            back_up_file(&path, BackupStyle::Numbered, 2).unwrap();
+        }
+        assert_eq!(read(directory.join("Regina Subsector.json.bak1")), "3");
+        assert_eq!(read(directory.join("Regina Subsector.json.bak2")), "2");
+        assert!(!directory.join("Regina Subsector.json.bak3").exists());
+
+        let history = directory.join(HISTORY_DIRECTORY);
+        for (version, timestamp) in [
+            "2022-06-01_134500",
+            "2022-06-02_090000",
+            "2022-06-03_000000",
+        ]
+        .iter()
+        .enumerate()
+        {
+            fs::write(&path, version.to_string()).unwrap();
+            back_up_timestamped(&path, timestamp, 2).unwrap();
+        }
+        // Backups of other files in the history folder are left alone
+        fs::write(history.join("Efate Subsector.json.2022-01-01_000000"), "").unwrap();
+        back_up_timestamped(&path, "2022-06-04_000000", 2).unwrap();
+
+        let mut names: Vec<String> = fs::read_dir(&history)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "Efate Subsector.json.2022-01-01_000000",
+                "Regina Subsector.json.2022-06-03_000000",
+                "Regina Subsector.json.2022-06-04_000000",
+            ]
+        );
+        assert_eq!(
+            read(history.join("Regina Subsector.json.2022-06-04_000000")),
+            "2"
+        );
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
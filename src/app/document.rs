@@ -0,0 +1,112 @@
+use std::{
+    collections::BTreeMap,
+    mem,
+    time::{Instant, SystemTime},
+};
+
+use egui_extras::RetainedImage;
+use swt_gen_core::{Point, Subsector, World};
+
+use crate::app::{gui, GeneratorApp};
+
+/** State of a subsector file open in a tab other than the selected one.
+
+The selected tab's state is held directly by `GeneratorApp`, so selecting a tab swaps its
+`Document` with the app's state.
+*/
+pub(crate) struct Document {
+    deleted_worlds: Vec<(Point, World)>,
+    map_view: gui::MapView,
+    polity_filter: Option<String>,
+    save_directory: String,
+    save_file_checked: Option<Instant>,
+    save_file_modified: Option<SystemTime>,
+    save_filename: String,
+    saved_worlds: BTreeMap<Point, World>,
+    subsector: Subsector,
+    subsector_edited: bool,
+    subsector_grid_image: Option<RetainedImage>,
+    travel_route: Vec<Point>,
+}
+
+impl Document {
+    pub(crate) fn new(subsector: Subsector, save_directory: String) -> Self {
+        Self {
+            deleted_worlds: Vec::new(),
+            map_view: gui::MapView::default(),
+            polity_filter: None,
+            save_directory,
+            save_file_checked: None,
+            save_file_modified: None,
+            save_filename: String::new(),
+            saved_worlds: subsector.get_map().clone(),
+            subsector,
+            subsector_edited: false,
+            subsector_grid_image: None,
+            travel_route: Vec::new(),
+        }
+    }
+
+    pub(crate) fn is_edited(&self) -> bool {
+        self.subsector_edited
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        self.subsector.name()
+    }
+}
+
+impl GeneratorApp {
+    /** Swap the state of the selected tab with `document` and clear the selection. */
+    pub(crate) fn swap_document(&mut self, document: &mut Document) {
+        mem::swap(&mut self.deleted_worlds, &mut document.deleted_worlds);
+        mem::swap(&mut self.map_view, &mut document.map_view);
+        mem::swap(&mut self.polity_filter, &mut document.polity_filter);
+        mem::swap(&mut self.save_directory, &mut document.save_directory);
+        mem::swap(&mut self.save_file_checked, &mut document.save_file_checked);
+        mem::swap(
+            &mut self.save_file_modified,
+            &mut document.save_file_modified,
+        );
+        mem::swap(&mut self.save_filename, &mut document.save_filename);
+        mem::swap(&mut self.saved_worlds, &mut document.saved_worlds);
+        mem::swap(&mut self.subsector, &mut document.subsector);
+        mem::swap(&mut self.subsector_edited, &mut document.subsector_edited);
+        mem::swap(
+            &mut self.subsector_grid_image,
+            &mut document.subsector_grid_image,
+        );
+        mem::swap(&mut self.travel_route, &mut document.travel_route);
+        // The swapped in grid image may have been drawn from something else
+        self.map_redraw.forget_drawn();
+
+        // Whatever was in progress on the map or in the journal belongs to the other subsector
+        self.annotation_draft = None;
+        self.compared_world = None;
+        self.journal_editing = None;
+        self.measurement = None;
+        self.point_selected = false;
+        self.world = World::empty();
+        self.world_edited = false;
+        self.world_image = None;
+        self.world_selected = false;
+        self.world_thumbnail = None;
+    }
+
+    /** Get the name of the `Subsector` open in every tab and whether it has unsaved changes. */
+    pub(crate) fn tabs(&self) -> Vec<(String, bool)> {
+        let mut tabs: Vec<(String, bool)> = self
+            .documents
+            .iter()
+            .map(|document| (document.name().to_string(), document.is_edited()))
+            .collect();
+        tabs.insert(
+            self.tab_index,
+            (
+                self.subsector.name().to_string(),
+                self.has_unsaved_changes(),
+            ),
+        );
+        tabs
+    }
+}
@@ -0,0 +1,238 @@
+/*! File dialogs, file reads and writes, and error alerts, behind a trait that can be swapped out.
+
+`GeneratorApp` opens, saves, and exports everything through a `FileIo`. The app itself uses
+`PlatformFileIo`, which goes through the dialogs and files of whichever platform it was compiled
+for, while tests use `FakeFileIo` to drive the same flows without anyone there to click through
+the dialogs.
+*/
+use std::{
+    error::Error,
+    io,
+    path::{Path, PathBuf},
+};
+
+use super::platform::{self, FileDialog, MessageDialog, MessageType};
+
+/** Everything the app does that involves the user picking files or reading and writing them. */
+pub(crate) trait FileIo: Send + Sync {
+    /** Ask the user for a file to open, starting in `directory` and showing only files with any
+    of `extensions`.
+
+    # Returns
+    - `Ok(Some(path))` with the path to the picked file
+    - `Ok(None)` if the dialog was cancelled
+    - `Err` if the dialog failed to open
+    */
+    fn pick_open_file(
+        &self,
+        directory: &Path,
+        description: &str,
+        extensions: &[&str],
+    ) -> Result<Option<PathBuf>, Box<dyn Error>>;
+
+    /** Ask the user where to save a file, starting in `directory` with `filename` filled in.
+
+    Returns the same as `pick_open_file`.
+    */
+    fn pick_save_file(
+        &self,
+        directory: &Path,
+        filename: &str,
+        description: &str,
+        extensions: &[&str],
+    ) -> Result<Option<PathBuf>, Box<dyn Error>>;
+
+    /** Ask the user for a directory, starting in `directory`.
+
+    Returns the same as `pick_open_file`.
+    */
+    fn pick_directory(&self, directory: &Path) -> Result<Option<PathBuf>, Box<dyn Error>>;
+
+    /** Read the whole file at `path`. */
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>>;
+
+    /** Read the whole file at `path` as text. */
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        String::from_utf8(self.read(path)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /** Write `contents` to the file at `path`, replacing it if it exists. */
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()>;
+
+    /** Tell the user something went wrong. */
+    fn show_error(&self, title: &str, text: &str);
+}
+
+/** `FileIo` through the native file dialogs and file system, or the browser's equivalents. */
+pub(crate) struct PlatformFileIo;
+
+impl FileIo for PlatformFileIo {
+    fn pick_open_file(
+        &self,
+        directory: &Path,
+        description: &str,
+        extensions: &[&str],
+    ) -> Result<Option<PathBuf>, Box<dyn Error>> {
+        Ok(FileDialog::new()
+            .set_location(directory)
+            .add_filter(description, extensions)
+            .show_open_single_file()?)
+    }
+
+    fn pick_save_file(
+        &self,
+        directory: &Path,
+        filename: &str,
+        description: &str,
+        extensions: &[&str],
+    ) -> Result<Option<PathBuf>, Box<dyn Error>> {
+        Ok(FileDialog::new()
+            .set_location(directory)
+            .set_filename(filename)
+            .add_filter(description, extensions)
+            .show_save_single_file()?)
+    }
+
+    fn pick_directory(&self, directory: &Path) -> Result<Option<PathBuf>, Box<dyn Error>> {
+        Ok(FileDialog::new()
+            .set_location(directory)
+            .show_open_single_dir()?)
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        platform::read(path)
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        platform::read_to_string(path)
+    }
+
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        platform::write(path, contents)
+    }
+
+    fn show_error(&self, title: &str, text: &str) {
+        MessageDialog::new()
+            .set_type(MessageType::Error)
+            .set_title(title)
+            .set_text(text)
+            .show_alert()
+            .unwrap();
+    }
+}
+
+#[cfg(test)]
+pub(crate) use fake::FakeFileIo;
+
+#[cfg(test)]
+mod fake {
+    use std::{
+        collections::{BTreeMap, VecDeque},
+        sync::{Arc, Mutex},
+    };
+
+    use super::*;
+
+    /** `FileIo` over files kept in memory, whose dialogs pick whatever they were told to.
+
+    Clones share the same files, so a test can keep one to look at what the app wrote through
+    another.
+    */
+    #[derive(Clone, Default)]
+    pub(crate) struct FakeFileIo {
+        state: Arc<Mutex<FakeState>>,
+    }
+
+    #[derive(Default)]
+    struct FakeState {
+        /// Contents of every file, by path
+        files: BTreeMap<PathBuf, Vec<u8>>,
+        /// What each of the next dialogs picks, in order; dialogs are cancelled once there are none
+        picks: VecDeque<Option<PathBuf>>,
+        /// Title of every error shown, in order
+        errors: Vec<String>,
+    }
+
+    impl FakeFileIo {
+        /** Have the next dialog that hasn't been told what to do pick `path`. */
+        pub(crate) fn pick<P: Into<PathBuf>>(&self, path: P) {
+            self.lock().picks.push_back(Some(path.into()));
+        }
+
+        /** Have the next dialog that hasn't been told what to do be cancelled. */
+        pub(crate) fn cancel(&self) {
+            self.lock().picks.push_back(None);
+        }
+
+        pub(crate) fn insert_file<P: Into<PathBuf>, C: Into<Vec<u8>>>(&self, path: P, contents: C) {
+            self.lock().files.insert(path.into(), contents.into());
+        }
+
+        pub(crate) fn file(&self, path: &Path) -> Option<Vec<u8>> {
+            self.lock().files.get(path).cloned()
+        }
+
+        pub(crate) fn paths(&self) -> Vec<PathBuf> {
+            self.lock().files.keys().cloned().collect()
+        }
+
+        /** Get the title of every error shown so far, in order. */
+        pub(crate) fn errors(&self) -> Vec<String> {
+            self.lock().errors.clone()
+        }
+
+        fn lock(&self) -> std::sync::MutexGuard<'_, FakeState> {
+            self.state
+                .lock()
+                .expect("Fake file system should not be poisoned")
+        }
+
+        fn next_pick(&self) -> Result<Option<PathBuf>, Box<dyn Error>> {
+            Ok(self.lock().picks.pop_front().flatten())
+        }
+    }
+
+    impl FileIo for FakeFileIo {
+        fn pick_open_file(
+            &self,
+            _directory: &Path,
+            _description: &str,
+            _extensions: &[&str],
+        ) -> Result<Option<PathBuf>, Box<dyn Error>> {
+            self.next_pick()
+        }
+
+        fn pick_save_file(
+            &self,
+            _directory: &Path,
+            _filename: &str,
+            _description: &str,
+            _extensions: &[&str],
+        ) -> Result<Option<PathBuf>, Box<dyn Error>> {
+            self.next_pick()
+        }
+
+        fn pick_directory(&self, _directory: &Path) -> Result<Option<PathBuf>, Box<dyn Error>> {
+            self.next_pick()
+        }
+
+        fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+            self.file(path).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("{} doesn't exist", path.display()),
+                )
+            })
+        }
+
+        fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+            self.insert_file(path, contents);
+            Ok(())
+        }
+
+        fn show_error(&self, title: &str, _text: &str) {
+            self.lock().errors.push(title.to_string());
+        }
+    }
+}
@@ -1,14 +1,46 @@
+mod batch_display;
+mod bookmarks_display;
+mod buffered_field;
+mod debug_console_display;
+mod deleted_worlds_display;
+mod dice_panel;
+mod duplicate_names_display;
+mod fast_forward_display;
+mod hooks_display;
+mod journal_display;
+mod jump_range_display;
+mod library_display;
+mod polities_display;
 mod popup;
+mod rules_display;
+mod split_view_display;
 mod subsector_map_display;
+mod travel_planner_display;
+mod unsaved_display;
 mod world_data_display;
 
-use egui::{menu, Button, CentralPanel, Color32, Context, FontId, TopBottomPanel};
+use egui::{
+    menu, style::Selection, Button, CentralPanel, Color32, Context, DragValue, FontId, ScrollArea,
+    SidePanel, Slider, Stroke, TopBottomPanel, Visuals,
+};
+use swt_gen_core::{set_roll_logging, CountStyle, LengthUnit, SummaryFormat, WorldAttribute};
 
-use crate::app::{GeneratorApp, Message};
+use crate::app::{
+    backup::BackupStyle,
+    settings::{MAX_BACKUP_COUNT, MAX_UI_SCALE, MIN_UI_SCALE},
+    GeneratorApp, Message,
+};
 
+pub(crate) use buffered_field::BufferedField;
+pub(crate) use dice_panel::DiceRoller;
+pub(crate) use fast_forward_display::FastForwardSummary;
 pub(crate) use popup::Popup;
-pub(crate) use subsector_map_display::rasterize_svg;
-pub(crate) use world_data_display::TabLabel;
+pub(crate) use rules_display::rule_hover_ui;
+pub(crate) use split_view_display::ComparedWorld;
+pub(crate) use subsector_map_display::{
+    rasterize_map_preview, rasterize_svg, rasterize_thumbnail, svg_to_png, AnnotationTool, MapView,
+};
+pub(crate) use world_data_display::{decode_image, TabLabel};
 
 pub(crate) const LABEL_FONT: FontId = FontId::proportional(11.0);
 pub(crate) const LABEL_COLOR: Color32 = Color32::GRAY;
@@ -20,6 +52,9 @@ pub(crate) const POSITIVE_BLUE: Color32 = Color32::from_rgb(144, 209, 255);
 /// "Negative" red color meant to pair well with `POSITIVE_BLUE` aesthetically
 pub(crate) const NEGATIVE_RED: Color32 = Color32::from_rgb(255, 144, 144);
 
+/// Warning orange meant to stand out without being as alarming as `NEGATIVE_RED`
+pub(crate) const WARNING_ORANGE: Color32 = Color32::from_rgb(255, 180, 80);
+
 pub(crate) const BUTTON_FONT_SIZE: f32 = 16.0;
 
 pub(crate) const FIELD_SPACING: f32 = 15.0;
@@ -30,6 +65,19 @@ pub(crate) const DICE_ICON: &str = "🎲";
 pub(crate) const X_ICON: &str = "❌";
 pub(crate) const SAVE_ICON: &str = "💾";
 pub(crate) const CLIPBOARD_ICON: &str = "📋";
+pub(crate) const WARNING_ICON: &str = "⚠";
+pub(crate) const BOOKMARK_ICON: &str = "★";
+pub(crate) const UNBOOKMARKED_ICON: &str = "☆";
+const COLLAPSE_ICON: &str = "⏵";
+const EXPAND_ICON: &str = "⏴";
+
+/// Outline and highlight color of hovered and selected widgets in the high-contrast theme
+const HIGH_CONTRAST_YELLOW: Color32 = Color32::from_rgb(255, 230, 0);
+
+/// Width of the world panel before the user resizes it
+const WORLD_PANEL_DEFAULT_WIDTH: f32 = 820.0;
+/// Narrowest the world panel can be resized to; anything narrower scrolls
+const WORLD_PANEL_MIN_WIDTH: f32 = 320.0;
 
 impl GeneratorApp {
     /** Handles displaying the overall central panel of the app.
@@ -38,29 +86,109 @@ impl GeneratorApp {
     selected `Point` and/or `World` on the right half.
     If there is no `World` at the selected `Point`, it shows a button to add a new world at there.
     If there is a `World` there, displays the data associated with that `World`.
+    In split view, the map is replaced by the selected and compared `World`s side by side.
     */
     fn show_central_panel(&mut self, ctx: &Context) {
         CentralPanel::default().show(ctx, |ui| {
             ui.add_enabled_ui(self.popup_queue.is_empty(), |ui| {
-                ui.horizontal_top(|ui| {
-                    self.subsector_map_display(ctx, ui);
+                if self.split_view {
+                    self.split_view_display(ui);
+                    return;
+                }
+
+                self.subsector_map_display(ctx, ui);
+            });
+        });
+    }
+
+    /** Displays the collapsible side panel with the selected hex's `World`, if there is one.
 
-                    ui.separator();
+    Must be shown before the central panel so that the map fills whatever space is left over.
+    */
+    fn show_world_panel(&mut self, ctx: &Context) {
+        if self.split_view || !self.point_selected {
+            return;
+        }
 
-                    if self.point_selected && self.world_selected {
-                        self.world_data_display(ui);
-                    } else if self.point_selected {
-                        self.new_world_dialog(ui);
+        if !self.settings.show_world_panel {
+            SidePanel::right("world_panel_collapsed")
+                .resizable(false)
+                .show(ctx, |ui| {
+                    if ui
+                        .button(EXPAND_ICON)
+                        .on_hover_text("Show the world panel")
+                        .clicked()
+                    {
+                        self.settings.show_world_panel = true;
                     }
                 });
+            return;
+        }
+
+        SidePanel::right("world_panel")
+            .resizable(true)
+            .default_width(WORLD_PANEL_DEFAULT_WIDTH)
+            .min_width(WORLD_PANEL_MIN_WIDTH)
+            .show(ctx, |ui| {
+                ui.add_enabled_ui(self.popup_queue.is_empty(), |ui| {
+                    ui.horizontal(|ui| {
+                        if ui
+                            .small_button(COLLAPSE_ICON)
+                            .on_hover_text("Hide the world panel")
+                            .clicked()
+                        {
+                            self.settings.show_world_panel = false;
+                        }
+
+                        let (icon, hover_text) = if self.subsector.is_bookmarked(&self.point) {
+                            (BOOKMARK_ICON, "Remove this hex's bookmark")
+                        } else {
+                            (UNBOOKMARKED_ICON, "Bookmark this hex")
+                        };
+                        if ui
+                            .add_enabled(!self.viewer_mode, Button::new(icon).small())
+                            .on_hover_text(hover_text)
+                            .clicked()
+                        {
+                            self.message(Message::ToggleBookmark);
+                        }
+                    });
+
+                    ScrollArea::vertical()
+                        .id_source("world_panel_scroll")
+                        .auto_shrink([false, false])
+                        .show(ui, |ui| {
+                            if self.world_selected && self.viewer_mode {
+                                self.read_only_world_data_display(ui);
+                            } else if self.world_selected {
+                                self.world_data_display(ui);
+                            } else {
+                                self.new_world_dialog(ui);
+                            }
+                        });
+                });
             });
-        });
     }
 
     /** Render all GUI elements. */
     pub(crate) fn show_gui(&mut self, ctx: &Context) {
         self.show_top_panel(ctx);
+        self.show_world_panel(ctx);
+        self.bookmarks_panel(ctx);
+        self.dice_panel(ctx);
         self.show_central_panel(ctx);
+        self.travel_planner_window(ctx);
+        self.hooks_window(ctx);
+        self.journal_window(ctx);
+        self.jump_range_window(ctx);
+        self.library_window(ctx);
+        self.polities_window(ctx);
+        self.unsaved_worlds_window(ctx);
+        self.duplicate_names_window(ctx);
+        self.deleted_worlds_window(ctx);
+        self.fast_forward_window(ctx);
+        self.batch_window(ctx);
+        self.debug_console_window(ctx);
         self.show_popups(ctx);
     }
 
@@ -84,20 +212,38 @@ impl GeneratorApp {
 
     /** Displays the top panel of the app.
 
-    Currently just a menu bar.
+    Contains the menu bar and a row of tabs, one per open subsector.
     */
     fn show_top_panel(&mut self, ctx: &Context) {
         TopBottomPanel::top("top_panel").show(ctx, |ui| {
             ui.add_enabled_ui(self.popup_queue.is_empty(), |ui| {
                 menu::bar(ui, |ui| {
                     ui.menu_button("File", |ui| {
+                        let editable = !self.viewer_mode;
                         let new_subsector_button =
                             Button::new("Generate New Subsector...").wrap(false);
-                        if ui.add(new_subsector_button).clicked() {
+                        if ui.add_enabled(editable, new_subsector_button).clicked() {
                             ui.close_menu();
                             self.message(Message::RegenSubsector);
                         }
 
+                        let batch_button = Button::new("Generate Batch...").wrap(false);
+                        if ui
+                            .add_enabled(editable, batch_button)
+                            .on_hover_text("Generate several subsectors at once to pick from")
+                            .clicked()
+                        {
+                            ui.close_menu();
+                            self.message(Message::GenerateBatch);
+                        }
+
+                        let empty_subsector_button =
+                            Button::new("New Empty Subsector...").wrap(false);
+                        if ui.add_enabled(editable, empty_subsector_button).clicked() {
+                            ui.close_menu();
+                            self.message(Message::NewEmptySubsector);
+                        }
+
                         ui.separator();
 
                         if ui.button("Open...              Ctrl-O").clicked() {
@@ -105,12 +251,45 @@ impl GeneratorApp {
                             self.message(Message::OpenJson);
                         }
 
-                        if ui.button("Save                   Ctrl-S").clicked() {
+                        let open_tab_button = Button::new("Open in New Tab...").wrap(false);
+                        if ui.add(open_tab_button).clicked() {
+                            ui.close_menu();
+                            self.message(Message::OpenJsonInNewTab);
+                        }
+
+                        let import_button = Button::new("Import Worlds...").wrap(false);
+                        if ui.add_enabled(editable, import_button).clicked() {
+                            ui.close_menu();
+                            self.message(Message::ImportWorlds);
+                        }
+
+                        let import_button = Button::new("Import CSV...").wrap(false);
+                        if ui.add_enabled(editable, import_button).clicked() {
+                            ui.close_menu();
+                            self.message(Message::ImportCsv);
+                        }
+
+                        let import_button = Button::new("Import from Text...").wrap(false);
+                        if ui.add_enabled(editable, import_button).clicked() {
+                            ui.close_menu();
+                            self.message(Message::ImportText);
+                        }
+
+                        let import_button =
+                            Button::new("Import Traveller Map Subsector...").wrap(false);
+                        if ui.add_enabled(editable, import_button).clicked() {
+                            ui.close_menu();
+                            self.message(Message::ImportTravellerMap);
+                        }
+
+                        let save_button = Button::new("Save                   Ctrl-S");
+                        if ui.add_enabled(editable, save_button).clicked() {
                             ui.close_menu();
                             self.message(Message::Save);
                         }
 
-                        if ui.button("Save As...           Ctrl-Shift-S").clicked() {
+                        let save_as_button = Button::new("Save As...           Ctrl-Shift-S");
+                        if ui.add_enabled(editable, save_as_button).clicked() {
                             ui.close_menu();
                             self.message(Message::SaveAs);
                         }
@@ -118,9 +297,19 @@ impl GeneratorApp {
                         ui.separator();
 
                         ui.menu_button("Export", |ui| {
-                            if ui.button("Subsector Map SVG...").clicked() {
+                            if ui.button("Subsector Map...").clicked() {
                                 ui.close_menu();
-                                self.message(Message::ExportSubsectorMapSvg);
+                                self.message(Message::ExportSubsectorMap);
+                            }
+
+                            let button = Button::new("All World Sheets...").wrap(false);
+                            if ui.add(button).clicked() {
+                                self.message(Message::ExportAllWorldSheets);
+                            }
+
+                            let button = Button::new("Player Handout...").wrap(false);
+                            if ui.add(button).clicked() {
+                                self.message(Message::ExportPlayerHandout);
                             }
 
                             let button = Button::new("Player-Safe Subsector JSON...").wrap(false);
@@ -132,19 +321,636 @@ impl GeneratorApp {
                             if ui.add(button).clicked() {
                                 self.message(Message::ExportColumnDelimitedTable);
                             }
+
+                            let button = Button::new("Sector Metadata XML...").wrap(false);
+                            if ui.add(button).clicked() {
+                                self.message(Message::ExportSectorMetadata);
+                            }
+
+                            let button = Button::new("Spreadsheet CSV...").wrap(false);
+                            if ui.add(button).clicked() {
+                                self.message(Message::ExportCsv);
+                            }
                         });
                     });
 
-                    ui.menu_button("Edit", |ui| {
-                        let rename_button =
-                            Button::new("Rename Subsector...    Ctrl-N").wrap(false);
-                        if ui.add(rename_button).clicked() {
+                    ui.add_enabled_ui(!self.viewer_mode, |ui| {
+                        ui.menu_button("Edit", |ui| {
+                            let rename_button =
+                                Button::new("Rename Subsector...    Ctrl-N").wrap(false);
+                            if ui.add(rename_button).clicked() {
+                                ui.close_menu();
+                                self.message(Message::RenameSubsector);
+                            }
+
+                            let travel_code_button = Button::new("Set Travel Codes...").wrap(false);
+                            if ui
+                                .add(travel_code_button)
+                                .on_hover_text(
+                                    "Set the travel codes of every world matching a rule",
+                                )
+                                .clicked()
+                            {
+                                ui.close_menu();
+                                self.message(Message::SetTravelCodes);
+                            }
+
+                            let factions_button = Button::new("Name Unnamed Factions").wrap(false);
+                            if ui
+                                .add(factions_button)
+                                .on_hover_text(
+                                    "Give every unnamed faction a name suiting its leadership",
+                                )
+                                .clicked()
+                            {
+                                ui.close_menu();
+                                self.message(Message::NameUnnamedFactions);
+                            }
+
+                            let xboat_button = Button::new("Regenerate X-Boat Routes").wrap(false);
+                            if ui.add(xboat_button).clicked() {
+                                ui.close_menu();
+                                self.message(Message::RegenXboatRoutes);
+                            }
+
+                            let fuel_button = Button::new("Fuel Market Tick").wrap(false);
+                            if ui
+                                .add(fuel_button)
+                                .on_hover_text("Reroll the fuel market of every world")
+                                .clicked()
+                            {
+                                ui.close_menu();
+                                self.message(Message::FuelMarketTick);
+                            }
+
+                            ui.menu_button("Regenerate in Every World", |ui| {
+                                for attribute in WorldAttribute::ALL_VALUES {
+                                    let button =
+                                        Button::new(format!("{}...", attribute)).wrap(false);
+                                    if ui.add(button).clicked() {
+                                        ui.close_menu();
+                                        self.message(Message::RegenAttribute { attribute });
+                                    }
+                                }
+                            });
+
+                            ui.separator();
+
+                            let copy_button = Button::new("Copy World");
+                            if ui.add_enabled(self.world_selected, copy_button).clicked() {
+                                ui.close_menu();
+                                self.message(Message::CopyWorld);
+                            }
+
+                            let paste_button = Button::new("Paste World");
+                            let can_paste = self.copied_world.is_some()
+                                && self.point_selected
+                                && !self.world_selected;
+                            if ui.add_enabled(can_paste, paste_button).clicked() {
+                                ui.close_menu();
+                                self.message(Message::PasteWorld);
+                            }
+
+                            let random_world_button = Button::new("Add Random World").wrap(false);
+                            if ui
+                                .add(random_world_button)
+                                .on_hover_text("Add a random world to a random empty hex")
+                                .clicked()
+                            {
+                                ui.close_menu();
+                                self.message(Message::AddRandomWorld);
+                            }
+
+                            let fill_button = Button::new("Fill Empty Hexes...").wrap(false);
+                            if ui
+                                .add(fill_button)
+                                .on_hover_text(
+                                    "Add random worlds to any number of random empty hexes",
+                                )
+                                .clicked()
+                            {
+                                ui.close_menu();
+                                self.message(Message::FillEmptyHexes);
+                            }
+
+                            let deleted_button = Button::new("Recently Deleted...").wrap(false);
+                            if ui
+                                .add(deleted_button)
+                                .on_hover_text(
+                                    "Restore worlds removed from the subsector this session",
+                                )
+                                .clicked()
+                            {
+                                ui.close_menu();
+                                self.show_deleted_worlds = true;
+                            }
+
+                            ui.separator();
+
+                            let copy_shortcuts = ["Ctrl-Shift-U", "Ctrl-Shift-C", "Ctrl-Shift-M"];
+                            for (format, shortcut) in
+                                SummaryFormat::ALL_VALUES.into_iter().zip(copy_shortcuts)
+                            {
+                                let button =
+                                    Button::new(format!("Copy {}    {}", format, shortcut))
+                                        .wrap(false);
+                                if ui.add_enabled(self.world_selected, button).clicked() {
+                                    ui.close_menu();
+                                    self.message(Message::CopyWorldSummary { format });
+                                }
+                            }
+                        });
+                    });
+
+                    ui.menu_button("View", |ui| {
+                        if ui.checkbox(&mut self.split_view, "Split View").clicked() {
+                            ui.close_menu();
+                        }
+
+                        if ui
+                            .checkbox(&mut self.settings.show_world_panel, "World Panel")
+                            .clicked()
+                        {
+                            ui.close_menu();
+                        }
+
+                        let mut viewer_mode = self.viewer_mode;
+                        if ui
+                            .checkbox(&mut viewer_mode, "Read-Only Viewer")
+                            .on_hover_text(
+                                "Only allow navigating, searching, and exporting the subsector",
+                            )
+                            .clicked()
+                        {
+                            ui.close_menu();
+                            self.message(Message::ToggleViewerMode);
+                        }
+
+                        let mut player_view = self.player_view;
+                        if ui
+                            .checkbox(&mut player_view, "Player View")
+                            .on_hover_text("Hide GM-only tabs, tools, and notes from players")
+                            .clicked()
+                        {
+                            ui.close_menu();
+                            self.message(Message::TogglePlayerView);
+                        }
+
+                        if ui
+                            .checkbox(&mut self.settings.high_contrast, "High Contrast")
+                            .on_hover_text("Bright text and outlines on black")
+                            .clicked()
+                        {
+                            ui.close_menu();
+                        }
+
+                        ui.separator();
+
+                        let mut scale = self.settings.ui_scale;
+                        let slider = Slider::new(&mut scale, MIN_UI_SCALE..=MAX_UI_SCALE)
+                            .text("UI Scale")
+                            .fixed_decimals(1);
+                        // Only apply the scale once dragging stops so the slider doesn't move
+                        // out from under the pointer
+                        let response = ui.add(slider);
+                        if response.drag_released() || (response.changed() && !response.dragged()) {
+                            self.message(Message::SetUiScale { scale });
+                        }
+
+                        let reset_button = Button::new("Reset UI Scale    Ctrl-0").wrap(false);
+                        if ui.add(reset_button).clicked() {
+                            ui.close_menu();
+                            self.message(Message::SetUiScale { scale: 1.0 });
+                        }
+                    });
+
+                    ui.menu_button("Tools", |ui| {
+                        #[cfg(not(feature = "player-safe-gui"))]
+                        if !self.hides_spoilers()
+                            && ui
+                                .checkbox(&mut self.show_hooks, "Adventure Hooks")
+                                .clicked()
+                        {
+                            ui.close_menu();
+                        }
+
+                        if ui
+                            .checkbox(&mut self.show_bookmarks, "Bookmarks")
+                            .on_hover_text("Show the bookmarked hexes in a sidebar")
+                            .clicked()
+                        {
+                            ui.close_menu();
+                        }
+
+                        if ui
+                            .checkbox(&mut self.show_debug_console, "Debug Console")
+                            .on_hover_text("Log every message the app handles (Ctrl+Shift+D)")
+                            .clicked()
+                        {
+                            ui.close_menu();
+                        }
+
+                        if ui
+                            .checkbox(&mut self.show_dice_panel, "Dice Roller")
+                            .clicked()
+                        {
+                            ui.close_menu();
+                        }
+
+                        if ui
+                            .checkbox(&mut self.show_duplicate_names, "Duplicate Names")
+                            .clicked()
+                        {
+                            ui.close_menu();
+                        }
+
+                        if ui
+                            .checkbox(&mut self.show_journal, "Campaign Journal")
+                            .clicked()
+                        {
+                            ui.close_menu();
+                        }
+
+                        if ui
+                            .checkbox(&mut self.show_jump_range, "Jump Range")
+                            .on_hover_text(
+                                "Highlight the worlds a ship can reach from the selected hex",
+                            )
+                            .clicked()
+                        {
+                            ui.close_menu();
+                        }
+
+                        if ui
+                            .checkbox(&mut self.show_library, "Library Search")
+                            .on_hover_text("Search the worlds of every subsector in a directory")
+                            .clicked()
+                        {
+                            ui.close_menu();
+                        }
+
+                        #[cfg(not(feature = "player-safe-gui"))]
+                        if !self.hides_spoilers()
+                            && ui.checkbox(&mut self.show_polities, "Polities").clicked()
+                        {
+                            ui.close_menu();
+                        }
+
+                        if ui
+                            .checkbox(&mut self.show_travel_planner, "Travel Planner")
+                            .clicked()
+                        {
+                            ui.close_menu();
+                        }
+
+                        if ui
+                            .checkbox(&mut self.show_unsaved_worlds, "Unsaved Changes")
+                            .on_hover_text("List and highlight the worlds changed since saving")
+                            .clicked()
+                        {
+                            ui.close_menu();
+                        }
+
+                        ui.separator();
+
+                        let button = Button::new("Fast-Forward...").wrap(false);
+                        if ui
+                            .add(button)
+                            .on_hover_text("Advance a copy of the subsector by some decades")
+                            .clicked()
+                        {
+                            ui.close_menu();
+                            self.message(Message::FastForwardSubsector);
+                        }
+
+                        if !self.hides_spoilers() {
+                            let button = Button::new("Political Crisis...").wrap(false);
+                            if ui
+                                .add_enabled(self.world_selected, button)
+                                .on_hover_text(
+                                    "Put the selected world through a coup, succession, or \
+                                    secession",
+                                )
+                                .clicked()
+                            {
+                                ui.close_menu();
+                                self.message(Message::PoliticalCrisis);
+                            }
+                        }
+
+                        if ui.button("Run Script...").clicked() {
+                            ui.close_menu();
+                            self.message(Message::RunScript);
+                        }
+                    });
+
+                    ui.menu_button("Settings", |ui| {
+                        if ui
+                            .checkbox(&mut self.settings.save_world_history, "Save World History")
+                            .on_hover_text("Include the history of each world when saving")
+                            .clicked()
+                        {
+                            ui.close_menu();
+                        }
+
+                        if ui
+                            .checkbox(&mut self.settings.watch_save_file, "Watch Save File")
+                            .on_hover_text(
+                                "Offer to reload the open subsector file when it is changed \
+                                outside of the generator",
+                            )
+                            .clicked()
+                        {
+                            ui.close_menu();
+                        }
+
+                        if ui
+                            .checkbox(
+                                &mut self.settings.reroll_dependents,
+                                "Offer to Reroll Dependent Fields",
+                            )
+                            .on_hover_text(
+                                "After editing a world's size, atmosphere, population, or other \
+                                field, offer to reroll the fields rolled from it",
+                            )
+                            .clicked()
+                        {
+                            ui.close_menu();
+                        }
+
+                        if ui
+                            .checkbox(
+                                &mut self.settings.log_generation_rolls,
+                                "Log Generation Rolls",
+                            )
+                            .on_hover_text(
+                                "Keep a log of the dice rolled to generate each new world, shown \
+                                in its Notes tab",
+                            )
+                            .clicked()
+                        {
+                            set_roll_logging(self.settings.log_generation_rolls);
+                            ui.close_menu();
+                        }
+
+                        if ui
+                            .checkbox(
+                                &mut self.settings.snap_to_legal_ranges,
+                                "Snap Values to Legal Ranges",
+                            )
+                            .on_hover_text(
+                                "Keep diameters in line with size and berthing costs in line with \
+                                starport class",
+                            )
+                            .clicked()
+                        {
                             ui.close_menu();
-                            self.message(Message::RenameSubsector);
+                            if self.world_selected {
+                                self.message(Message::WorldModelUpdated);
+                            }
                         }
+
+                        ui.menu_button("Backups", |ui| {
+                            for style in BackupStyle::ALL_VALUES {
+                                if ui
+                                    .radio_value(
+                                        &mut self.settings.backup_style,
+                                        style,
+                                        style.to_string(),
+                                    )
+                                    .clicked()
+                                {
+                                    ui.close_menu();
+                                }
+                            }
+
+                            ui.separator();
+
+                            ui.add_enabled_ui(
+                                self.settings.backup_style != BackupStyle::None,
+                                |ui| {
+                                    ui.horizontal(|ui| {
+                                        ui.label("Versions Kept");
+                                        ui.add(
+                                            DragValue::new(&mut self.settings.backup_count)
+                                                .clamp_range(1..=MAX_BACKUP_COUNT),
+                                        );
+                                    });
+                                },
+                            );
+                        });
+
+                        ui.menu_button("Generation Script", |ui| {
+                            let script_name = match &self.settings.generation_script_path {
+                                Some(path) => path
+                                    .file_name()
+                                    .map(|name| name.to_string_lossy().to_string())
+                                    .unwrap_or_default(),
+                                None => "None".to_string(),
+                            };
+                            ui.label(format!("Current: {}", script_name));
+
+                            ui.separator();
+
+                            let button = Button::new("Select Script...").wrap(false);
+                            if ui.add(button).clicked() {
+                                ui.close_menu();
+                                self.message(Message::SelectGenerationScript);
+                            }
+
+                            let button = Button::new("Don't Run a Script").wrap(false);
+                            let enabled = self.settings.generation_script_path.is_some();
+                            if ui.add_enabled(enabled, button).clicked() {
+                                ui.close_menu();
+                                self.message(Message::ResetGenerationScript);
+                            }
+                        });
+
+                        ui.menu_button("Language", |ui| {
+                            let language = match &self.settings.language_path {
+                                Some(path) => path
+                                    .file_name()
+                                    .map(|name| name.to_string_lossy().to_string())
+                                    .unwrap_or_default(),
+                                None => "English".to_string(),
+                            };
+                            ui.label(format!("Current: {}", language));
+
+                            ui.separator();
+
+                            let button = Button::new("Select Translation Folder...").wrap(false);
+                            if ui
+                                .add(button)
+                                .on_hover_text(
+                                    "Folder of table CSV files translated from the built-in ones",
+                                )
+                                .clicked()
+                            {
+                                ui.close_menu();
+                                self.message(Message::SelectLanguage);
+                            }
+
+                            let button = Button::new("Use English").wrap(false);
+                            let enabled = self.settings.language_path.is_some();
+                            if ui.add_enabled(enabled, button).clicked() {
+                                ui.close_menu();
+                                self.message(Message::ResetLanguage);
+                            }
+                        });
+
+                        ui.menu_button("Table Overrides", |ui| {
+                            let overrides_name = match &self.settings.table_overrides_path {
+                                Some(path) => path
+                                    .file_name()
+                                    .map(|name| name.to_string_lossy().to_string())
+                                    .unwrap_or_default(),
+                                None => "None".to_string(),
+                            };
+                            ui.label(format!("Current: {}", overrides_name));
+
+                            ui.separator();
+
+                            let button = Button::new("Edit Table Rows...").wrap(false);
+                            if ui
+                                .add(button)
+                                .on_hover_text(
+                                    "Change the text and costs of individual table rows for \
+                                    errata and house rules",
+                                )
+                                .clicked()
+                            {
+                                ui.close_menu();
+                                self.message(Message::EditTableOverrides);
+                            }
+
+                            let button = Button::new("Select Override File...").wrap(false);
+                            if ui.add(button).clicked() {
+                                ui.close_menu();
+                                self.message(Message::SelectTableOverrides);
+                            }
+
+                            let button = Button::new("Don't Override Tables").wrap(false);
+                            let enabled = self.settings.table_overrides_path.is_some();
+                            if ui.add_enabled(enabled, button).clicked() {
+                                ui.close_menu();
+                                self.message(Message::ResetTableOverrides);
+                            }
+                        });
+
+                        ui.menu_button("Map Template", |ui| {
+                            let template_name = match &self.settings.map_template_path {
+                                Some(path) => path
+                                    .file_name()
+                                    .map(|name| name.to_string_lossy().to_string())
+                                    .unwrap_or_default(),
+                                None => "Built-in".to_string(),
+                            };
+                            ui.label(format!("Current: {}", template_name));
+
+                            ui.separator();
+
+                            let button = Button::new("Select Custom SVG Template...").wrap(false);
+                            if ui.add(button).clicked() {
+                                ui.close_menu();
+                                self.message(Message::SelectMapTemplate);
+                            }
+
+                            let button = Button::new("Use Built-in Template").wrap(false);
+                            let enabled = self.settings.map_template_path.is_some();
+                            if ui.add_enabled(enabled, button).clicked() {
+                                ui.close_menu();
+                                self.message(Message::ResetMapTemplate);
+                            }
+                        });
+
+                        ui.menu_button("Number Format", |ui| {
+                            let numbers = &mut self.settings.number_format;
+                            ui.label("Lengths");
+                            for unit in LengthUnit::ALL_VALUES {
+                                if ui
+                                    .radio_value(&mut numbers.length_unit, unit, unit.to_string())
+                                    .clicked()
+                                {
+                                    ui.close_menu();
+                                }
+                            }
+
+                            ui.separator();
+
+                            ui.label("Large Numbers");
+                            for style in CountStyle::ALL_VALUES {
+                                if ui
+                                    .radio_value(&mut numbers.count_style, style, style.to_string())
+                                    .clicked()
+                                {
+                                    ui.close_menu();
+                                }
+                            }
+                        });
                     });
                 });
+
+                ui.horizontal(|ui| {
+                    let tabs = self.tabs();
+                    let can_close = tabs.len() > 1;
+                    for (index, (name, edited)) in tabs.into_iter().enumerate() {
+                        let text = if edited { format!("{}*", name) } else { name };
+                        let selected = index == self.tab_index;
+                        if ui.selectable_label(selected, text).clicked() {
+                            self.message(Message::SelectTab { index });
+                        }
+
+                        if selected && can_close && ui.small_button("x").clicked() {
+                            self.message(Message::CloseTab);
+                        }
+                        ui.separator();
+                    }
+
+                    if ui
+                        .small_button("+")
+                        .on_hover_text("New Tab    Ctrl-T")
+                        .clicked()
+                    {
+                        self.message(Message::NewTab);
+                    }
+                });
             });
         });
     }
 }
+
+/** Get the look of the GUI: egui's usual dark theme, or a high-contrast version of it with bright
+text and outlines on black and yellow highlights.
+*/
+pub(crate) fn visuals(high_contrast: bool) -> Visuals {
+    let mut visuals = Visuals::dark();
+    if !high_contrast {
+        return visuals;
+    }
+
+    visuals.override_text_color = Some(Color32::WHITE);
+    visuals.extreme_bg_color = Color32::BLACK;
+    visuals.faint_bg_color = Color32::from_gray(32);
+    visuals.code_bg_color = Color32::BLACK;
+    visuals.hyperlink_color = HIGH_CONTRAST_YELLOW;
+    visuals.selection = Selection {
+        bg_fill: Color32::from_rgb(0, 64, 160),
+        stroke: Stroke::new(2.0, HIGH_CONTRAST_YELLOW),
+    };
+
+    let widgets = &mut visuals.widgets;
+    widgets.noninteractive.bg_fill = Color32::BLACK;
+    widgets.noninteractive.bg_stroke = Stroke::new(1.0, Color32::WHITE);
+    widgets.noninteractive.fg_stroke = Stroke::new(1.0, Color32::WHITE);
+    for widget in [&mut widgets.inactive, &mut widgets.open] {
+        widget.bg_fill = Color32::BLACK;
+        widget.bg_stroke = Stroke::new(1.0, Color32::WHITE);
+        widget.fg_stroke = Stroke::new(1.5, Color32::WHITE);
+    }
+    for widget in [&mut widgets.hovered, &mut widgets.active] {
+        widget.bg_fill = Color32::from_gray(48);
+        widget.bg_stroke = Stroke::new(2.0, HIGH_CONTRAST_YELLOW);
+        widget.fg_stroke = Stroke::new(2.0, HIGH_CONTRAST_YELLOW);
+    }
+    visuals
+}
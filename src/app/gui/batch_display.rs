@@ -0,0 +1,64 @@
+use egui::{Context, Grid, RichText, ScrollArea, Window};
+
+use crate::app::{
+    gui::{FIELD_SPACING, LABEL_COLOR, LABEL_FONT, LABEL_SPACING},
+    GeneratorApp, Message,
+};
+
+/// Number of candidate previews in each row of the batch window
+const CANDIDATE_COLUMNS: usize = 4;
+
+impl GeneratorApp {
+    /** Show the window of generated batch candidates if there are any.
+
+    Each candidate has a preview of its map and a button to open it in a new tab, so a favorite can
+    be picked or several can be opened to build up a sector. Closing the window discards the rest.
+    */
+    pub(crate) fn batch_window(&mut self, ctx: &Context) {
+        if self.batch_candidates.is_empty() {
+            return;
+        }
+
+        let mut open = true;
+        Window::new("Generated Batch")
+            .open(&mut open)
+            .enabled(self.popup_queue.is_empty())
+            .show(ctx, |ui| {
+                ScrollArea::vertical().show(ui, |ui| {
+                    Grid::new("batch_candidates_grid")
+                        .spacing([FIELD_SPACING, FIELD_SPACING])
+                        .show(ui, |ui| {
+                            for (index, (subsector, preview)) in
+                                self.batch_candidates.iter().enumerate()
+                            {
+                                ui.vertical(|ui| {
+                                    preview.show(ui);
+                                    ui.label(
+                                        RichText::new(subsector.name())
+                                            .font(LABEL_FONT)
+                                            .color(LABEL_COLOR),
+                                    );
+                                    ui.label(format!("{} worlds", subsector.get_map().len()));
+                                    if ui.button("Open in New Tab").clicked() {
+                                        self.message(Message::OpenBatchCandidate { index });
+                                    }
+                                });
+
+                                if index % CANDIDATE_COLUMNS == CANDIDATE_COLUMNS - 1 {
+                                    ui.end_row();
+                                }
+                            }
+                        });
+                });
+
+                ui.add_space(LABEL_SPACING);
+                if ui.button("Discard All").clicked() {
+                    self.message(Message::DiscardBatch);
+                }
+            });
+
+        if !open {
+            self.message(Message::DiscardBatch);
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use egui::{Button, Context, Grid, RichText, ScrollArea, SidePanel, TextEdit};
+
+use crate::app::{
+    gui::{
+        FIELD_SELECTION_WIDTH, FIELD_SPACING, LABEL_COLOR, LABEL_FONT, LABEL_SPACING,
+        UNBOOKMARKED_ICON, X_ICON,
+    },
+    GeneratorApp, Message,
+};
+
+impl GeneratorApp {
+    /** Show the bookmarks sidebar docked to the left of the window, if it is open.
+
+    Each bookmark jumps to its hex when clicked and has a label that can be edited in place. It must
+    be shown before the central panel so that the map fits beside it.
+    */
+    pub(crate) fn bookmarks_panel(&mut self, ctx: &Context) {
+        if !self.show_bookmarks {
+            return;
+        }
+
+        SidePanel::left("bookmarks_panel").show(ctx, |ui| {
+            ui.add_enabled_ui(self.popup_queue.is_empty(), |ui| {
+                ui.horizontal(|ui| {
+                    ui.heading("Bookmarks");
+                    if ui.small_button("Close").clicked() {
+                        self.show_bookmarks = false;
+                    }
+                });
+                ui.separator();
+
+                if self.subsector.bookmarks().is_empty() {
+                    ui.label(format!(
+                        "Click {} above a hex's data to bookmark it.",
+                        UNBOOKMARKED_ICON
+                    ));
+                    return;
+                }
+
+                ScrollArea::vertical().show(ui, |ui| {
+                    Grid::new("bookmarks_grid")
+                        .spacing([FIELD_SPACING / 2.0, LABEL_SPACING])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            let mut edited = false;
+                            let subsector = &mut self.subsector;
+                            let names: Vec<String> = subsector
+                                .bookmarks()
+                                .iter()
+                                .map(|bookmark| {
+                                    subsector.get_world(&bookmark.point).map_or_else(
+                                        || "Empty hex".to_string(),
+                                        |world| world.name.clone(),
+                                    )
+                                })
+                                .collect();
+
+                            for (index, (bookmark, name)) in
+                                subsector.bookmarks_mut().iter_mut().zip(names).enumerate()
+                            {
+                                let point = bookmark.point;
+                                if ui
+                                    .link(format!("{} {}", point, name))
+                                    .on_hover_text("Jump to this hex")
+                                    .clicked()
+                                {
+                                    self.message_tx
+                                        .send(Message::HexGridClicked { new_point: point });
+                                }
+
+                                let text_edit = TextEdit::singleline(&mut bookmark.label)
+                                    .hint_text("Label")
+                                    .desired_width(FIELD_SELECTION_WIDTH / 2.0);
+                                edited |= ui.add_enabled(!self.viewer_mode, text_edit).changed();
+
+                                if ui
+                                    .add_enabled(!self.viewer_mode, Button::new(X_ICON).small())
+                                    .clicked()
+                                {
+                                    self.message_tx.send(Message::RemoveBookmark { index });
+                                }
+                                ui.end_row();
+                            }
+
+                            if edited {
+                                self.message_tx.send(Message::SubsectorModelUpdated);
+                            }
+                        });
+                });
+                ui.add_space(LABEL_SPACING);
+                ui.label(
+                    RichText::new("Bookmarks are saved with the subsector")
+                        .font(LABEL_FONT)
+                        .color(LABEL_COLOR),
+                );
+            });
+        });
+    }
+}
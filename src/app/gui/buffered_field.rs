@@ -0,0 +1,138 @@
+use std::{fmt::Display, marker::PhantomData, str::FromStr};
+
+use egui::{Key, TextEdit, Ui};
+
+use crate::app::{gui::SHORT_SELECTION_WIDTH, pipe, Message};
+
+/** Text entry for a value that is only applied to the model once the text parses and is valid.
+
+Submitting the text sends the field's `Message`, whose handler calls `apply` to parse it. Text that
+is rejected is reverted to the current value and the reason is kept to be shown next to the field
+until a value is applied.
+*/
+#[derive(Clone)]
+pub(crate) struct BufferedField<T> {
+    /// Text being edited
+    pub(crate) text: String,
+    /// Why the last text applied was rejected, if it was
+    error: Option<String>,
+    /// What text that doesn't parse should have been instead, e.g. "a whole number of km"
+    expected: &'static str,
+    /// Sent when the text is submitted
+    message: Message,
+    /// Whether text is only submitted with Enter and reverted when focus is lost any other way
+    submit_on_enter: bool,
+    value: PhantomData<T>,
+}
+
+impl<T: Display + FromStr> BufferedField<T> {
+    /** Create an empty field that sends `message` when its text is submitted. */
+    pub(crate) fn new(message: Message, expected: &'static str) -> Self {
+        Self {
+            text: String::new(),
+            error: None,
+            expected,
+            message,
+            submit_on_enter: false,
+            value: PhantomData,
+        }
+    }
+
+    /** Only submit the text when Enter is pressed, reverting it when focus is lost otherwise. */
+    pub(crate) fn submit_on_enter(mut self) -> Self {
+        self.submit_on_enter = true;
+        self
+    }
+
+    /** Parse the text and check it with `valid`.
+
+    # Returns
+    - `Some(value)` with the parsed value, after clearing the error and rewriting the text from it
+    - `None` if the text was rejected, after reverting it to `current`
+    */
+    pub(crate) fn apply(&mut self, current: &T, valid: impl FnOnce(&T) -> bool) -> Option<T> {
+        match self.text.trim().parse() {
+            Ok(value) if valid(&value) => {
+                self.error = None;
+                self.text = value.to_string();
+                Some(value)
+            }
+            _ => {
+                let error = format!("'{}' is not {}", self.text.trim(), self.expected);
+                self.reject(error, current);
+                None
+            }
+        }
+    }
+
+    /** Reject the text for a reason of the caller's own, reverting it to `current`. */
+    pub(crate) fn reject(&mut self, error: String, current: &T) {
+        self.error = Some(error);
+        self.set_text(current);
+    }
+
+    /** Replace the text with `value` and forget any error, e.g. when another world is selected. */
+    pub(crate) fn reset(&mut self, value: &T) {
+        self.error = None;
+        self.set_text(value);
+    }
+
+    /** Replace the text with `value`, e.g. after the value was changed by something else. */
+    pub(crate) fn set_text(&mut self, value: &T) {
+        self.text = value.to_string();
+    }
+
+    /** Show the text entry, sending the field's `Message` over `message_tx` when it is submitted.
+
+    Unsubmitted text is reverted to `current` when the field loses focus.
+    */
+    pub(crate) fn show(&mut self, ui: &mut Ui, current: &T, message_tx: &pipe::Sender<Message>) {
+        let response =
+            ui.add(TextEdit::singleline(&mut self.text).desired_width(SHORT_SELECTION_WIDTH));
+        if response.lost_focus() {
+            if !self.submit_on_enter || ui.input().key_pressed(Key::Enter) {
+                message_tx.send(self.message.clone());
+            } else {
+                self.set_text(current);
+            }
+        }
+    }
+
+    /** Get why the last text was rejected, or else `value_warning` about the applied value. */
+    pub(crate) fn warning(&self, value_warning: Option<String>) -> Option<String> {
+        self.error.clone().or(value_warning)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply() {
+        let mut field = BufferedField::new(Message::WorldGasGiantsUpdated, "a whole number");
+        field.text = " 3 ".to_string();
+        assert_eq!(field.apply(&1, |&value: &i32| value >= 0), Some(3));
+        assert_eq!(field.text, "3");
+        assert_eq!(field.warning(None), None);
+
+        field.text = "-1".to_string();
+        assert_eq!(field.apply(&3, |&value| value >= 0), None);
+        assert_eq!(field.text, "3");
+        assert_eq!(
+            field.warning(Some("Too many".to_string())),
+            Some("'-1' is not a whole number".to_string())
+        );
+
+        field.reject("Too many".to_string(), &2);
+        assert_eq!(field.text, "2");
+        assert_eq!(field.warning(None), Some("Too many".to_string()));
+
+        field.reset(&4);
+        assert_eq!(field.text, "4");
+        assert_eq!(
+            field.warning(Some("Too many".to_string())),
+            Some("Too many".to_string())
+        );
+    }
+}
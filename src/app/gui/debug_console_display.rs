@@ -0,0 +1,64 @@
+use egui::{Context, RichText, ScrollArea, TextStyle, Window};
+
+use crate::app::{
+    gui::{CLIPBOARD_ICON, LABEL_COLOR, LABEL_FONT, LABEL_SPACING, WARNING_ORANGE},
+    message_log::{MessageOutcome, MESSAGE_LOG_CAPACITY},
+    GeneratorApp,
+};
+
+impl GeneratorApp {
+    /** Show the debug console if it is open.
+
+    Every message the app handled recently is listed newest first, with how it turned out and how
+    long it took. Failed messages are highlighted, and the whole log can be copied to paste into a
+    bug report.
+    */
+    pub(crate) fn debug_console_window(&mut self, ctx: &Context) {
+        let mut open = self.show_debug_console;
+        Window::new("Debug Console")
+            .open(&mut open)
+            .resizable(true)
+            .default_width(600.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(
+                        RichText::new(format!(
+                            "Last {} of up to {} handled messages, newest first",
+                            self.message_log.entries().len(),
+                            MESSAGE_LOG_CAPACITY
+                        ))
+                        .font(LABEL_FONT)
+                        .color(LABEL_COLOR),
+                    );
+                    if ui
+                        .button(CLIPBOARD_ICON.to_string() + " Copy")
+                        .on_hover_text(
+                            "Copy the whole log, oldest first, to paste into a bug report",
+                        )
+                        .clicked()
+                    {
+                        ui.output().copied_text = self.message_log.to_text();
+                    }
+                    if ui.button("Clear").clicked() {
+                        self.message_log.clear();
+                    }
+                });
+                ui.add_space(LABEL_SPACING);
+
+                let entries = self.message_log.entries();
+                let row_height = ui.text_style_height(&TextStyle::Monospace);
+                ScrollArea::vertical()
+                    .auto_shrink([false, false])
+                    .show_rows(ui, row_height, entries.len(), |ui, rows| {
+                        for entry in entries.iter().rev().skip(rows.start).take(rows.len()) {
+                            let mut text = RichText::new(entry.to_string()).monospace();
+                            if matches!(entry.outcome, MessageOutcome::Failed(_)) {
+                                text = text.color(WARNING_ORANGE);
+                            }
+                            ui.label(text);
+                        }
+                    });
+            });
+        self.show_debug_console = open;
+    }
+}
@@ -0,0 +1,85 @@
+use egui::{Button, Context, Grid, RichText, ScrollArea, Window};
+
+use crate::app::{
+    gui::{FIELD_SPACING, LABEL_COLOR, LABEL_FONT, LABEL_SPACING},
+    GeneratorApp, Message,
+};
+
+impl GeneratorApp {
+    /** Show the window of worlds removed from the subsector this session if it is open.
+
+    Each world can be restored to the hex it was removed from, or to the selected hex if that's
+    empty. The list is forgotten when the app is closed or another subsector is opened in the tab.
+    */
+    pub(crate) fn deleted_worlds_window(&mut self, ctx: &Context) {
+        let mut open = self.show_deleted_worlds;
+        Window::new("Recently Deleted")
+            .open(&mut open)
+            .enabled(self.popup_queue.is_empty())
+            .show(ctx, |ui| {
+                if self.deleted_worlds.is_empty() {
+                    ui.label("No worlds have been removed this session.");
+                    return;
+                }
+
+                let selected_empty = self.point_selected && !self.world_selected;
+                ScrollArea::vertical().show(ui, |ui| {
+                    Grid::new("deleted_worlds_grid")
+                        .spacing([FIELD_SPACING, LABEL_SPACING])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for header in ["Hex", "Name", "UWP"] {
+                                ui.label(RichText::new(header).font(LABEL_FONT).color(LABEL_COLOR));
+                            }
+                            ui.end_row();
+
+                            // Most recently deleted first
+                            for (index, (point, world)) in
+                                self.deleted_worlds.iter().enumerate().rev()
+                            {
+                                ui.label(point.to_string());
+                                ui.label(&world.name);
+                                ui.label(world.profile_str());
+
+                                let original_empty = self.subsector.get_world(point).is_none();
+                                if ui
+                                    .add_enabled(original_empty, Button::new("Restore"))
+                                    .on_hover_text(
+                                        "Put the world back in the hex it was removed from",
+                                    )
+                                    .on_disabled_hover_text("Another world is in the hex now")
+                                    .clicked()
+                                {
+                                    self.message_tx.send(Message::RestoreDeletedWorld {
+                                        index,
+                                        point: *point,
+                                    });
+                                }
+                                if ui
+                                    .add_enabled(selected_empty, Button::new("Restore Here"))
+                                    .on_hover_text(format!("Put the world in hex {}", self.point))
+                                    .on_disabled_hover_text("Select an empty hex to restore it to")
+                                    .clicked()
+                                {
+                                    self.message_tx.send(Message::RestoreDeletedWorld {
+                                        index,
+                                        point: self.point,
+                                    });
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+
+                ui.add_space(LABEL_SPACING);
+                if ui
+                    .button("Empty")
+                    .on_hover_text("Forget every deleted world for good")
+                    .clicked()
+                {
+                    self.message(Message::ClearDeletedWorlds);
+                }
+            });
+        self.show_deleted_worlds = open;
+    }
+}
@@ -0,0 +1,240 @@
+use std::{collections::VecDeque, fmt};
+
+use egui::{Button, Context, DragValue, Grid, RichText, ScrollArea, SidePanel, Ui, Window};
+use swt_gen_core::dice;
+
+use crate::app::{
+    gui::{DICE_ICON, FIELD_SPACING, LABEL_COLOR, LABEL_FONT, LABEL_SPACING},
+    GeneratorApp,
+};
+
+/// Most rolls kept in the log; the oldest are dropped first
+const MAX_LOGGED_ROLLS: usize = 50;
+/// Most dice that can be rolled at once with a custom roll
+const MAX_CUSTOM_DICE: u32 = 100;
+/// Most sides the dice of a custom roll can have
+const MAX_CUSTOM_SIDES: u32 = 1000;
+
+/** Kind of roll that can be made with the dice roller. */
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum RollKind {
+    /// Two six-sided dice plus a modifier
+    TwoD6 { modifier: i32 },
+    /// Two six-sided dice read as tens and ones
+    D66,
+    /// Any number of dice with any number of sides, plus a modifier
+    Custom {
+        count: u32,
+        sides: u32,
+        modifier: i32,
+    },
+}
+
+impl fmt::Display for RollKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let with_modifier = |f: &mut fmt::Formatter<'_>, dice: String, modifier: i32| {
+            if modifier == 0 {
+                write!(f, "{}", dice)
+            } else {
+                write!(f, "{}{:+}", dice, modifier)
+            }
+        };
+
+        match self {
+            Self::TwoD6 { modifier } => with_modifier(f, "2D6".to_string(), *modifier),
+            Self::D66 => write!(f, "D66"),
+            Self::Custom {
+                count,
+                sides,
+                modifier,
+            } => with_modifier(f, format!("{}D{}", count, sides), *modifier),
+        }
+    }
+}
+
+impl RollKind {
+    /** Roll the dice and return the result with any modifier applied. */
+    fn roll(&self) -> i64 {
+        match *self {
+            Self::TwoD6 { modifier } => (dice::roll_2d(6) + modifier) as i64,
+            Self::D66 => dice::roll_d66() as i64,
+            Self::Custom {
+                count,
+                sides,
+                modifier,
+            } => dice::roll(count as i64, sides as i64) + modifier as i64,
+        }
+    }
+}
+
+/** State of the dice roller: the settings of its rolls and a log of their results. */
+pub(crate) struct DiceRoller {
+    /// Whether the roller is docked to the side of the window instead of floating
+    docked: bool,
+    /// Modifier applied to 2D6 rolls
+    modifier: i32,
+    custom_count: u32,
+    custom_sides: u32,
+    /// Modifier applied to custom rolls
+    custom_modifier: i32,
+    /// Results of previous rolls, newest first
+    log: VecDeque<(RollKind, i64)>,
+}
+
+impl Default for DiceRoller {
+    fn default() -> Self {
+        Self {
+            docked: false,
+            modifier: 0,
+            custom_count: 3,
+            custom_sides: 6,
+            custom_modifier: 0,
+            log: VecDeque::new(),
+        }
+    }
+}
+
+impl DiceRoller {
+    /** Make a roll of `kind`, log it, and return the result. */
+    fn roll(&mut self, kind: RollKind) -> i64 {
+        let result = kind.roll();
+        self.log.push_front((kind, result));
+        self.log.truncate(MAX_LOGGED_ROLLS);
+        result
+    }
+
+    /** Display the roll buttons and their settings followed by the log of results. */
+    fn show(&mut self, ui: &mut Ui) {
+        Grid::new("dice_roller_grid")
+            .spacing([FIELD_SPACING / 2.0, LABEL_SPACING])
+            .show(ui, |ui| {
+                if ui.button(format!("{} 2D6", DICE_ICON)).clicked() {
+                    self.roll(RollKind::TwoD6 {
+                        modifier: self.modifier,
+                    });
+                }
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("DM").font(LABEL_FONT).color(LABEL_COLOR));
+                    ui.add(DragValue::new(&mut self.modifier).clamp_range(-20..=20));
+                });
+                ui.end_row();
+
+                if ui.button(format!("{} D66", DICE_ICON)).clicked() {
+                    self.roll(RollKind::D66);
+                }
+                ui.end_row();
+
+                if ui.button(format!("{} Custom", DICE_ICON)).clicked() {
+                    self.roll(RollKind::Custom {
+                        count: self.custom_count,
+                        sides: self.custom_sides,
+                        modifier: self.custom_modifier,
+                    });
+                }
+                ui.horizontal(|ui| {
+                    ui.add(DragValue::new(&mut self.custom_count).clamp_range(1..=MAX_CUSTOM_DICE));
+                    ui.label("D");
+                    ui.add(
+                        DragValue::new(&mut self.custom_sides).clamp_range(2..=MAX_CUSTOM_SIDES),
+                    );
+                    ui.label("+");
+                    ui.add(DragValue::new(&mut self.custom_modifier).clamp_range(-100..=100));
+                });
+                ui.end_row();
+            });
+        ui.separator();
+
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("Results").font(LABEL_FONT).color(LABEL_COLOR));
+            if ui
+                .add_enabled(!self.log.is_empty(), Button::new("Clear"))
+                .clicked()
+            {
+                self.log.clear();
+            }
+        });
+
+        ScrollArea::vertical()
+            .id_source("dice_roller_log")
+            .show(ui, |ui| {
+                for (index, (kind, result)) in self.log.iter().enumerate() {
+                    let text = RichText::new(format!("{}: {}", kind, result));
+                    // Make the latest roll stand out from the rest
+                    if index == 0 {
+                        ui.label(text.strong());
+                    } else {
+                        ui.label(text);
+                    }
+                }
+            });
+    }
+}
+
+impl GeneratorApp {
+    /** Show the dice roller, either docked to the left of the window or floating, if it is open.
+
+    When docked, it must be shown before the central panel so that the map fits beside it.
+    */
+    pub(crate) fn dice_panel(&mut self, ctx: &Context) {
+        if !self.show_dice_panel {
+            return;
+        }
+
+        let mut open = self.show_dice_panel;
+        if self.dice_roller.docked {
+            SidePanel::left("dice_panel").show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.heading("Dice Roller");
+                    if ui.small_button("Undock").clicked() {
+                        self.dice_roller.docked = false;
+                    }
+                    if ui.small_button("Close").clicked() {
+                        open = false;
+                    }
+                });
+                ui.separator();
+                self.dice_roller.show(ui);
+            });
+        } else {
+            Window::new("Dice Roller")
+                .open(&mut open)
+                .resizable(true)
+                .show(ctx, |ui| {
+                    if ui.small_button("Dock").clicked() {
+                        self.dice_roller.docked = true;
+                    }
+                    self.dice_roller.show(ui);
+                });
+        }
+        self.show_dice_panel = open;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn logged_rolls() {
+        let mut roller = DiceRoller::default();
+        for _ in 0..MAX_LOGGED_ROLLS {
+            let result = roller.roll(RollKind::TwoD6 { modifier: -2 });
+            assert!((0..=10).contains(&result));
+        }
+
+        let kind = RollKind::Custom {
+            count: 3,
+            sides: 4,
+            modifier: 1,
+        };
+        let result = roller.roll(kind);
+        assert!((4..=13).contains(&result));
+        assert_eq!(roller.log.len(), MAX_LOGGED_ROLLS);
+        assert_eq!(roller.log[0], (kind, result));
+
+        assert_eq!(RollKind::TwoD6 { modifier: 0 }.to_string(), "2D6");
+        assert_eq!(RollKind::TwoD6 { modifier: -1 }.to_string(), "2D6-1");
+        assert_eq!(kind.to_string(), "3D4+1");
+        assert_eq!(RollKind::D66.to_string(), "D66");
+    }
+}
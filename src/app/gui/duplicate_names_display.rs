@@ -0,0 +1,73 @@
+use egui::{Context, Grid, RichText, ScrollArea, Window};
+
+use crate::app::{
+    gui::{FIELD_SPACING, LABEL_COLOR, LABEL_FONT, LABEL_SPACING, WARNING_ICON, WARNING_ORANGE},
+    GeneratorApp, Message,
+};
+
+impl GeneratorApp {
+    /** Show the window warning about worlds that share a name if it is open.
+
+    The window is opened automatically when a generated or imported subsector has duplicate names.
+    Clicking a hex code in the list jumps to that hex.
+    */
+    pub(crate) fn duplicate_names_window(&mut self, ctx: &Context) {
+        let mut open = self.show_duplicate_names;
+        Window::new("Duplicate Names")
+            .open(&mut open)
+            .enabled(self.popup_queue.is_empty())
+            .show(ctx, |ui| {
+                let duplicates = self.subsector.duplicate_names();
+                if duplicates.is_empty() {
+                    ui.label("Every world in the subsector has a unique name.");
+                    return;
+                }
+
+                ui.label(
+                    RichText::new(format!(
+                        "{} {} names are shared by more than one world",
+                        WARNING_ICON,
+                        duplicates.len()
+                    ))
+                    .color(WARNING_ORANGE),
+                );
+                ui.add_space(LABEL_SPACING);
+
+                ScrollArea::vertical().show(ui, |ui| {
+                    Grid::new("duplicate_names_grid")
+                        .spacing([FIELD_SPACING, LABEL_SPACING])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for header in ["Name", "Hexes"] {
+                                ui.label(RichText::new(header).font(LABEL_FONT).color(LABEL_COLOR));
+                            }
+                            ui.end_row();
+
+                            for (name, points) in duplicates {
+                                ui.label(name);
+                                ui.horizontal(|ui| {
+                                    for point in points {
+                                        if ui.link(point.to_string()).clicked() {
+                                            self.message(Message::HexGridClicked {
+                                                new_point: point,
+                                            });
+                                        }
+                                    }
+                                });
+                                ui.end_row();
+                            }
+                        });
+                });
+
+                ui.add_space(LABEL_SPACING);
+                if ui
+                    .button("Rename Duplicates")
+                    .on_hover_text("Give a new unique name to all but the first world of each name")
+                    .clicked()
+                {
+                    self.message(Message::RenameDuplicateWorlds);
+                }
+            });
+        self.show_duplicate_names = open;
+    }
+}
@@ -0,0 +1,102 @@
+use std::collections::BTreeMap;
+
+use egui::{Context, Grid, RichText, ScrollArea, Window};
+use swt_gen_core::{Point, Subsector, WorldChange};
+
+use crate::app::{
+    gui::{FIELD_SPACING, LABEL_COLOR, LABEL_FONT, LABEL_SPACING},
+    GeneratorApp,
+};
+
+/** What changed in the last fast-forwarded `Subsector`, kept to be shown until dismissed. */
+pub(crate) struct FastForwardSummary {
+    decades: u32,
+    subsector_name: String,
+    /// Location and name of each `World` that changed, with every change to it
+    worlds: Vec<(Point, String, Vec<WorldChange>)>,
+}
+
+impl FastForwardSummary {
+    /** Summarize `changes` made to `subsector` by fast-forwarding it `decades` decades. */
+    pub(crate) fn new(
+        subsector: &Subsector,
+        decades: u32,
+        changes: BTreeMap<Point, Vec<WorldChange>>,
+    ) -> Self {
+        let worlds = changes
+            .into_iter()
+            .map(|(point, changes)| {
+                let name = subsector
+                    .get_world(&point)
+                    .map_or(String::new(), |world| world.name.clone());
+                (point, name, changes)
+            })
+            .collect();
+
+        Self {
+            decades,
+            subsector_name: subsector.name().to_string(),
+            worlds,
+        }
+    }
+}
+
+impl GeneratorApp {
+    /** Show the changes made by the last fast-forward if they haven't been dismissed. */
+    pub(crate) fn fast_forward_window(&mut self, ctx: &Context) {
+        let summary = match &self.fast_forward_summary {
+            Some(summary) => summary,
+            None => return,
+        };
+
+        let mut open = true;
+        Window::new("Fast-Forward Summary")
+            .open(&mut open)
+            .enabled(self.popup_queue.is_empty())
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "{} world(s) of {} Subsector changed over {} decade(s).",
+                    summary.worlds.len(),
+                    summary.subsector_name,
+                    summary.decades
+                ));
+                if summary.worlds.is_empty() {
+                    return;
+                }
+                ui.add_space(LABEL_SPACING);
+
+                ScrollArea::vertical().show(ui, |ui| {
+                    Grid::new("fast_forward_summary_grid")
+                        .spacing([FIELD_SPACING, LABEL_SPACING])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for header in ["Hex", "World", "Field", "Before", "After"] {
+                                ui.label(RichText::new(header).font(LABEL_FONT).color(LABEL_COLOR));
+                            }
+                            ui.end_row();
+
+                            for (point, name, changes) in &summary.worlds {
+                                for (index, change) in changes.iter().enumerate() {
+                                    // Only the first change of each world is labelled with it
+                                    if index == 0 {
+                                        ui.label(point.to_string());
+                                        ui.label(name);
+                                    } else {
+                                        ui.label("");
+                                        ui.label("");
+                                    }
+                                    ui.label(&change.field);
+                                    ui.label(&change.old_value);
+                                    ui.label(&change.new_value);
+                                    ui.end_row();
+                                }
+                            }
+                        });
+                });
+            });
+
+        if !open {
+            self.fast_forward_summary = None;
+        }
+    }
+}
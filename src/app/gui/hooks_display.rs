@@ -0,0 +1,61 @@
+use egui::{Button, Context, Grid, ScrollArea, TextEdit, Window};
+
+use crate::app::{
+    gui::{DICE_ICON, FIELD_SELECTION_WIDTH, FIELD_SPACING, LABEL_SPACING, X_ICON},
+    GeneratorApp, Message,
+};
+
+impl GeneratorApp {
+    /** Show the adventure hooks window if it is open.
+
+    Each hook can be edited, rerolled, or removed individually and the whole list can be rerolled at
+    once.
+    */
+    pub(crate) fn hooks_window(&mut self, ctx: &Context) {
+        let mut open = self.show_hooks;
+        Window::new("Adventure Hooks")
+            .open(&mut open)
+            .enabled(self.popup_queue.is_empty() && !self.viewer_mode)
+            .show(ctx, |ui| {
+                ScrollArea::vertical().show(ui, |ui| {
+                    Grid::new("subsector_hooks_grid")
+                        .spacing([FIELD_SPACING / 2.0, LABEL_SPACING])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            let mut edited = false;
+                            for (index, hook) in self.subsector.hooks_mut().iter_mut().enumerate() {
+                                let text_edit = TextEdit::multiline(hook)
+                                    .desired_rows(2)
+                                    .desired_width(2.0 * FIELD_SELECTION_WIDTH);
+                                edited |= ui.add(text_edit).changed();
+
+                                if ui.small_button(DICE_ICON).clicked() {
+                                    self.message_tx.send(Message::RegenSubsectorHook { index });
+                                }
+                                if ui.small_button(X_ICON).clicked() {
+                                    self.message_tx.send(Message::RemoveSubsectorHook { index });
+                                }
+                                ui.end_row();
+                            }
+
+                            if edited {
+                                self.message(Message::SubsectorModelUpdated);
+                            }
+                        });
+                });
+                ui.add_space(LABEL_SPACING);
+
+                ui.horizontal(|ui| {
+                    if ui.button("Add Hook").clicked() {
+                        self.message(Message::AddSubsectorHook);
+                    }
+
+                    let reroll_button = Button::new(format!("{} Reroll All", DICE_ICON));
+                    if ui.add(reroll_button).clicked() {
+                        self.message(Message::RegenSubsectorHooks);
+                    }
+                });
+            });
+        self.show_hooks = open;
+    }
+}
@@ -0,0 +1,193 @@
+use egui::{Context, DragValue, RichText, ScrollArea, TextEdit, TextStyle, Ui, Window};
+use swt_gen_core::{journal_links, ImperialDate, DAYS_PER_YEAR};
+
+use crate::app::{
+    gui::{FIELD_SELECTION_WIDTH, LABEL_COLOR, LABEL_FONT, LABEL_SPACING, X_ICON},
+    pipe, GeneratorApp, Message,
+};
+
+impl GeneratorApp {
+    /** Display the campaign's date with buttons to move it forward, or a button to start keeping
+    track of it if its clock hasn't been started.
+    */
+    fn campaign_date_display(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label(
+                RichText::new("Campaign Date")
+                    .font(LABEL_FONT)
+                    .color(LABEL_COLOR),
+            );
+
+            let date = match self.subsector.campaign_date() {
+                Some(date) => date,
+                None => {
+                    if ui
+                        .button("Start Clock")
+                        .on_hover_text("Keep track of the campaign's date in the Imperial calendar")
+                        .clicked()
+                    {
+                        self.message_tx.send(Message::SetCampaignDate {
+                            date: Some(ImperialDate::default()),
+                        });
+                    }
+                    return;
+                }
+            };
+
+            let mut day = date.day();
+            let mut year = date.year();
+            let day_changed = ui
+                .add(DragValue::new(&mut day).clamp_range(1..=DAYS_PER_YEAR))
+                .changed();
+            ui.label("-");
+            let year_changed = ui.add(DragValue::new(&mut year)).changed();
+            if day_changed || year_changed {
+                self.message_tx.send(Message::SetCampaignDate {
+                    date: ImperialDate::new(day, year),
+                });
+            }
+
+            for (label, days) in [("+1 Day", 1), ("+1 Week", 7)] {
+                if ui
+                    .button(label)
+                    .on_hover_text("Fuel markets change at the start of each week")
+                    .clicked()
+                {
+                    self.message_tx.send(Message::AdvanceCampaignDate { days });
+                }
+            }
+            if ui
+                .small_button(X_ICON)
+                .on_hover_text("Stop keeping track of the date")
+                .clicked()
+            {
+                self.message_tx
+                    .send(Message::SetCampaignDate { date: None });
+            }
+        });
+    }
+
+    /** Show the campaign journal window if it is open.
+
+    Entries are displayed with light markdown formatting, and any hex code in them links to that
+    hex. Only the entry being edited is shown as editable text. New entries are dated with the
+    campaign's date, which is shown above them.
+    */
+    pub(crate) fn journal_window(&mut self, ctx: &Context) {
+        let mut open = self.show_journal;
+        Window::new("Campaign Journal")
+            .open(&mut open)
+            .enabled(self.popup_queue.is_empty() && !self.viewer_mode)
+            .default_width(2.0 * FIELD_SELECTION_WIDTH)
+            .show(ctx, |ui| {
+                self.campaign_date_display(ui);
+                ui.separator();
+
+                if self.subsector.journal().is_empty() {
+                    ui.label("There are no journal entries for this subsector.");
+                }
+
+                ScrollArea::vertical().show(ui, |ui| {
+                    let mut edited = false;
+                    for (index, entry) in self.subsector.journal_mut().iter_mut().enumerate() {
+                        let editing = self.journal_editing == Some(index);
+                        ui.horizontal(|ui| {
+                            if editing {
+                                edited |= ui
+                                    .add(TextEdit::singleline(&mut entry.date).desired_width(80.0))
+                                    .changed();
+                                if ui.small_button("Done").clicked() {
+                                    self.journal_editing = None;
+                                }
+                            } else {
+                                ui.label(RichText::new(&entry.date).strong());
+                                if ui.small_button("Edit").clicked() {
+                                    self.journal_editing = Some(index);
+                                }
+                            }
+                            if ui.small_button(X_ICON).clicked() {
+                                self.message_tx.send(Message::RemoveJournalEntry { index });
+                            }
+                        });
+
+                        if editing {
+                            let text_edit = TextEdit::multiline(&mut entry.text)
+                                .desired_rows(6)
+                                .desired_width(2.0 * FIELD_SELECTION_WIDTH);
+                            edited |= ui.add(text_edit).changed();
+                        } else {
+                            journal_text(ui, &entry.text, &self.message_tx);
+                        }
+                        ui.separator();
+                    }
+
+                    if edited {
+                        self.message_tx.send(Message::SubsectorModelUpdated);
+                    }
+                });
+                ui.add_space(LABEL_SPACING);
+
+                ui.horizontal(|ui| {
+                    if ui.button("Add Entry").clicked() {
+                        self.message_tx.send(Message::AddJournalEntry);
+                    }
+                    ui.label(
+                        RichText::new("Hex codes like 0504 link to that hex")
+                            .font(LABEL_FONT)
+                            .color(LABEL_COLOR),
+                    );
+                });
+            });
+        self.show_journal = open;
+    }
+}
+
+/** Display the text of a journal entry with light markdown formatting.
+
+Lines starting with `#` are headings and lines starting with `-` or `*` are bullet points. Hex codes
+become links that select their hex when clicked.
+*/
+fn journal_text(ui: &mut Ui, text: &str, message_tx: &pipe::Sender<Message>) {
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if let Some(heading) = trimmed.strip_prefix('#') {
+            let heading = heading.trim_start_matches('#').trim();
+            ui.label(RichText::new(heading).text_style(TextStyle::Heading));
+        } else if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        {
+            ui.horizontal_wrapped(|ui| {
+                ui.label("• ");
+                linked_line(ui, item, message_tx);
+            });
+        } else {
+            ui.horizontal_wrapped(|ui| {
+                // Keep blank lines from collapsing so paragraphs stay apart
+                if line.trim().is_empty() {
+                    ui.label(" ");
+                } else {
+                    linked_line(ui, line, message_tx);
+                }
+            });
+        }
+    }
+}
+
+/** Display a single line of journal text with its hex codes as links. */
+fn linked_line(ui: &mut Ui, line: &str, message_tx: &pipe::Sender<Message>) {
+    ui.spacing_mut().item_spacing.x = 0.0;
+    let mut start = 0;
+    for (range, new_point) in journal_links(line) {
+        if range.start > start {
+            ui.label(&line[start..range.start]);
+        }
+        if ui.link(&line[range.clone()]).clicked() {
+            message_tx.send(Message::HexGridClicked { new_point });
+        }
+        start = range.end;
+    }
+    if start < line.len() {
+        ui.label(&line[start..]);
+    }
+}
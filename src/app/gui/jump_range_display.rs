@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+
+use egui::{Context, DragValue, Grid, RichText, Window};
+use swt_gen_core::{Point, Subsector};
+
+use crate::app::{
+    gui::{FIELD_SPACING, LABEL_COLOR, LABEL_FONT, LABEL_SPACING},
+    GeneratorApp,
+};
+
+impl GeneratorApp {
+    /** Get the hexes in jump range of the selected hex, along with the fewest jumps to each.
+
+    Every hex within a single jump is in range, whether or not it has a world; hexes further out are
+    only in range if they have a world that can be reached by hopping from world to world.
+    */
+    pub(crate) fn jump_range(&self) -> BTreeMap<Point, u32> {
+        let mut range = self.subsector.jump_reachability(
+            &self.point,
+            self.jump_range_rating,
+            self.jump_range_jumps,
+        );
+        for x in 1..=Subsector::COLUMNS as i32 {
+            for y in 1..=Subsector::ROWS as i32 {
+                let point = Point { x, y };
+                if point != self.point && point.distance(&self.point) <= self.jump_range_rating {
+                    range.insert(point, 1);
+                }
+            }
+        }
+        range
+    }
+
+    /** Show the jump range window if it is open. */
+    pub(crate) fn jump_range_window(&mut self, ctx: &Context) {
+        let mut open = self.show_jump_range;
+        Window::new("Jump Range")
+            .open(&mut open)
+            .enabled(self.popup_queue.is_empty())
+            .resizable(false)
+            .show(ctx, |ui| {
+                Grid::new("jump_range_settings_grid")
+                    .spacing([FIELD_SPACING, LABEL_SPACING])
+                    .show(ui, |ui| {
+                        for label in ["Jump", "Jumps"] {
+                            ui.label(RichText::new(label).font(LABEL_FONT).color(LABEL_COLOR));
+                        }
+                        ui.end_row();
+
+                        ui.add(DragValue::new(&mut self.jump_range_rating).clamp_range(1..=6));
+                        ui.add(DragValue::new(&mut self.jump_range_jumps).clamp_range(1..=9))
+                            .on_hover_text("Follow chains of worlds for up to this many jumps");
+                    });
+                ui.separator();
+
+                if !self.point_selected {
+                    ui.label("Select a hex to see what is in range of it.");
+                    return;
+                }
+
+                let reachable: Vec<(Point, u32)> = self
+                    .jump_range()
+                    .into_iter()
+                    .filter(|(point, _)| self.subsector.get_world(point).is_some())
+                    .collect();
+                if reachable.is_empty() {
+                    ui.label(format!("No worlds are in range of {}.", self.point));
+                    return;
+                }
+
+                Grid::new("jump_range_worlds_grid")
+                    .spacing([FIELD_SPACING, LABEL_SPACING])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for header in ["Hex", "World", "Jumps"] {
+                            ui.label(RichText::new(header).font(LABEL_FONT).color(LABEL_COLOR));
+                        }
+                        ui.end_row();
+
+                        for (point, jumps) in reachable {
+                            ui.label(point.to_string());
+                            if let Some(world) = self.subsector.get_world(&point) {
+                                ui.label(&world.name);
+                            }
+                            ui.label(jumps.to_string());
+                            ui.end_row();
+                        }
+                    });
+            });
+        self.show_jump_range = open;
+    }
+}
@@ -0,0 +1,146 @@
+use egui::{Button, ComboBox, Context, Grid, Key, RichText, ScrollArea, TextEdit, Window};
+use swt_gen_core::TABLES;
+
+use crate::app::{
+    gui::{FIELD_SPACING, LABEL_COLOR, LABEL_FONT, LABEL_SPACING, WARNING_ORANGE},
+    GeneratorApp, Message,
+};
+
+impl GeneratorApp {
+    /** Show the library search window if it is open.
+
+    The library is a directory of saved subsector files, searched all at once by world name, UWP
+    pattern, and trade code. Each match can be opened in a new tab with its world selected.
+    */
+    pub(crate) fn library_window(&mut self, ctx: &Context) {
+        let mut open = self.show_library;
+        Window::new("Search Library")
+            .open(&mut open)
+            .enabled(self.popup_queue.is_empty())
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let directory = match &self.settings.library_path {
+                        Some(path) => path.to_string_lossy().to_string(),
+                        None => "No directory chosen".to_string(),
+                    };
+                    ui.label(directory);
+                    if ui.button("Choose...").clicked() {
+                        self.message(Message::SelectLibraryDirectory);
+                    }
+                });
+                ui.add_space(LABEL_SPACING);
+
+                let mut submitted = false;
+                Grid::new("library_query_grid")
+                    .spacing([FIELD_SPACING, LABEL_SPACING])
+                    .show(ui, |ui| {
+                        for label in ["Name", "UWP", "Trade Code"] {
+                            ui.label(RichText::new(label).font(LABEL_FONT).color(LABEL_COLOR));
+                        }
+                        ui.end_row();
+
+                        let response = ui.add(
+                            TextEdit::singleline(&mut self.library_query.name).desired_width(120.0),
+                        );
+                        submitted |= response.lost_focus() && ui.input().key_pressed(Key::Enter);
+
+                        let response = ui
+                            .add(
+                                TextEdit::singleline(&mut self.library_query.uwp_pattern)
+                                    .hint_text("e.g. A?8*")
+                                    .desired_width(90.0),
+                            )
+                            .on_hover_text(
+                                "Start of the UWP; ? matches any one character and * any number",
+                            );
+                        submitted |= response.lost_focus() && ui.input().key_pressed(Key::Enter);
+
+                        let selected = match &self.library_query.trade_code {
+                            Some(trade_code) => format!("{:?}", trade_code),
+                            None => "Any".to_string(),
+                        };
+                        ComboBox::from_id_source("library_trade_code")
+                            .selected_text(selected)
+                            .show_ui(ui, |ui| {
+                                let trade_code = &mut self.library_query.trade_code;
+                                ui.selectable_value(trade_code, None, "Any");
+                                for record in TABLES.trade_code_table.iter() {
+                                    ui.selectable_value(
+                                        trade_code,
+                                        Some(record.trade_code.clone()),
+                                        format!("{:?} ({})", record.trade_code, record.name),
+                                    );
+                                }
+                            });
+                    });
+                ui.add_space(LABEL_SPACING);
+
+                let can_search = !self.library_query.is_empty();
+                let clicked = ui.add_enabled(can_search, Button::new("Search")).clicked();
+                if can_search && (clicked || submitted) {
+                    self.message(Message::SearchLibrary);
+                }
+                ui.separator();
+
+                let search = match &self.library_search {
+                    Some(search) => search,
+                    None => {
+                        ui.label("Search for worlds across every subsector in the directory.");
+                        return;
+                    }
+                };
+
+                ui.label(format!(
+                    "{} matching worlds in {} subsector files",
+                    search.matches.len(),
+                    search.files_searched
+                ));
+                if !search.unreadable.is_empty() {
+                    let skipped = search
+                        .unreadable
+                        .iter()
+                        .map(|(path, e)| format!("{}: {}", path.display(), e))
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    ui.label(
+                        RichText::new(format!(
+                            "{} JSON files couldn't be read as subsectors",
+                            search.unreadable.len()
+                        ))
+                        .color(WARNING_ORANGE),
+                    )
+                    .on_hover_text(skipped);
+                }
+                if search.matches.is_empty() {
+                    return;
+                }
+                ui.add_space(LABEL_SPACING);
+
+                ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    Grid::new("library_matches_grid")
+                        .spacing([FIELD_SPACING, LABEL_SPACING])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for header in ["World", "UWP", "Subsector", "Hex", ""] {
+                                ui.label(RichText::new(header).font(LABEL_FONT).color(LABEL_COLOR));
+                            }
+                            ui.end_row();
+
+                            for (index, found) in search.matches.iter().enumerate() {
+                                ui.label(&found.world_name);
+                                ui.monospace(&found.uwp);
+                                ui.label(&found.subsector_name)
+                                    .on_hover_text(found.path.display().to_string());
+                                ui.label(found.point.to_string());
+                                if ui.button("Open").clicked() {
+                                    self.message(Message::OpenLibraryMatch { index });
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+        self.show_library = open;
+    }
+}
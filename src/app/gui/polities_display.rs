@@ -0,0 +1,75 @@
+use egui::{Button, Context, Grid, ScrollArea, TextEdit, Window};
+
+use crate::app::{
+    gui::{DICE_ICON, FIELD_SELECTION_WIDTH, FIELD_SPACING, LABEL_SPACING},
+    GeneratorApp, Message,
+};
+
+impl GeneratorApp {
+    /** Show the polities window if it is open.
+
+    Each polity's name and summary can be edited, and all polities can be regenerated at once, which
+    also reassigns the allegiance of every world.
+    */
+    pub(crate) fn polities_window(&mut self, ctx: &Context) {
+        let mut open = self.show_polities;
+        Window::new("Polities")
+            .open(&mut open)
+            .enabled(self.popup_queue.is_empty() && !self.viewer_mode)
+            .show(ctx, |ui| {
+                if self.subsector.polities().is_empty() {
+                    ui.label("There are no polities in this subsector.");
+                }
+
+                let member_counts: Vec<usize> = (0..self.subsector.polities().len())
+                    .map(|index| self.subsector.polity_member_count(index))
+                    .collect();
+
+                ScrollArea::vertical().show(ui, |ui| {
+                    Grid::new("subsector_polities_grid")
+                        .spacing([FIELD_SPACING / 2.0, LABEL_SPACING])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            let mut edited = false;
+                            for (polity, members) in
+                                self.subsector.polities_mut().iter_mut().zip(member_counts)
+                            {
+                                ui.vertical(|ui| {
+                                    edited |= ui
+                                        .add(
+                                            TextEdit::singleline(&mut polity.name)
+                                                .desired_width(FIELD_SELECTION_WIDTH),
+                                        )
+                                        .changed();
+                                    ui.label(format!(
+                                        "Code: {}    Capital: {}    Worlds: {}",
+                                        polity.code, polity.capital, members
+                                    ));
+                                });
+
+                                let text_edit = TextEdit::multiline(&mut polity.summary)
+                                    .desired_rows(3)
+                                    .desired_width(2.0 * FIELD_SELECTION_WIDTH);
+                                edited |= ui.add(text_edit).changed();
+                                ui.end_row();
+                            }
+
+                            if edited {
+                                self.message(Message::SubsectorModelUpdated);
+                            }
+                        });
+                });
+                ui.add_space(LABEL_SPACING);
+
+                let regen_button = Button::new(format!("{} Regenerate", DICE_ICON));
+                if ui
+                    .add(regen_button)
+                    .on_hover_text("Regenerate all polities and reassign every world's allegiance")
+                    .clicked()
+                {
+                    self.message(Message::RegenPolities);
+                }
+            });
+        self.show_polities = open;
+    }
+}
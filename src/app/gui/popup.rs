@@ -1,14 +1,34 @@
-use egui::{vec2, Context, Grid, Layout, Pos2, RichText, TextEdit, Vec2, Window};
+use std::{collections::BTreeMap, mem, sync::mpsc};
 
-use crate::{
-    app::{
-        gui::{FIELD_SPACING, LABEL_COLOR, LABEL_FONT, LABEL_SPACING},
-        pipe, GeneratorApp, Message,
+use egui::{
+    vec2, Button, ComboBox, Context, DragValue, FontId, Grid, Layout, Pos2, ProgressBar, RichText,
+    ScrollArea, Slider, TextEdit, Ui, Vec2, Window,
+};
+use swt_gen_core::{
+    random_subsector_name, tables_without_overrides, GrowthRules, HandoutOptions, HexOrientation,
+    Inconsistency, MapFormat, MapLayout, MapOptions, MapRenderError, MapRenderer, MapStyle,
+    MergeResolution, NameStyle, Point, PoliticalEvent, RuleSet, SheetFormat, Subsector, TableKind,
+    TableOverrides, TravelCode, TravelCodeCondition, TravelCodePolicy, World, WorldAbundance,
+    WorldAttribute, WorldChange, WorldField, WorldLabels, WorldList, TABLES,
+};
+
+use crate::app::{
+    gui::{
+        BUTTON_FONT_SIZE, DICE_ICON, FIELD_SPACING, LABEL_COLOR, LABEL_FONT, LABEL_SPACING,
+        NEGATIVE_RED,
     },
-    astrography::{Point, WorldAbundance},
+    pipe,
+    settings::GenerationPreset,
+    GeneratorApp, Message,
 };
 
 const DEFAULT_POPUP_SIZE: Vec2 = vec2(256.0, 144.0);
+/// Number of subsectors generated in a batch unless the user chooses otherwise
+const DEFAULT_BATCH_SIZE: usize = 8;
+/// Most subsectors that can be generated in one batch
+const MAX_BATCH_SIZE: usize = 16;
+/// Jump rating of the handout's jump table when it is first turned on
+const DEFAULT_JUMP_TABLE_RATING: u32 = 2;
 
 impl GeneratorApp {
     /** Add a `Popup` to the queue to be shown and awaiting response. */
@@ -16,6 +36,182 @@ impl GeneratorApp {
         self.popup_queue.push(Box::new(popup));
     }
 
+    pub(crate) fn fast_forward_popup(&mut self) {
+        self.add_popup(FastForwardPopup::new(
+            self.settings.growth_rules,
+            self.message_tx.clone(),
+        ));
+    }
+
+    pub(crate) fn fill_empty_hexes_popup(&mut self) {
+        let empty_hexes = Subsector::COLUMNS * Subsector::ROWS - self.subsector.get_map().len();
+        self.add_popup(FillEmptyHexesPopup::new(
+            empty_hexes,
+            self.message_tx.clone(),
+        ));
+    }
+
+    pub(crate) fn handout_export_popup(&mut self) {
+        self.add_popup(HandoutExportPopup::new(
+            self.settings.handout_options,
+            self.message_tx.clone(),
+        ));
+    }
+
+    pub(crate) fn inconsistent_import_popup(
+        &mut self,
+        inconsistencies: &BTreeMap<Point, Vec<Inconsistency>>,
+    ) {
+        let mut text = format!(
+            "{} world(s) in Subsector {} break the world generation rules:\n",
+            inconsistencies.len(),
+            self.subsector.name()
+        );
+        for (point, world_inconsistencies) in inconsistencies {
+            for inconsistency in world_inconsistencies {
+                text += &format!("\n{}: {}", point, inconsistency);
+            }
+        }
+        text += "\n\nWould you like to fix all of them?";
+
+        let popup = ButtonPopup::new(
+            "Inconsistent Worlds".to_string(),
+            text,
+            self.message_tx.clone(),
+        )
+        .add_button(
+            "Fix All".to_string(),
+            Message::FixAllSubsectorInconsistencies,
+        )
+        .add_button("Ignore".to_string(), Message::NoOp);
+
+        self.add_popup(popup);
+    }
+
+    pub(crate) fn map_export_popup(&mut self) {
+        self.add_popup(MapExportPopup::new(
+            self.settings.map_options.clone(),
+            self.message_tx.clone(),
+        ));
+    }
+
+    /** Tell the user the subsector map couldn't be drawn and that a plain grid is shown instead. */
+    pub(crate) fn map_render_error_popup(&mut self, e: &MapRenderError) {
+        let text = format!(
+            "The subsector map could not be drawn:\n\n{}\n\n\
+            A plain hex grid is shown in its place.",
+            e
+        );
+        let popup = ButtonPopup::new(
+            "Map Render Failed".to_string(),
+            text,
+            self.message_tx.clone(),
+        )
+        .add_button("OK".to_string(), Message::NoOp);
+        self.add_popup(popup);
+    }
+
+    pub(crate) fn merge_conflict_popup(
+        &mut self,
+        subsector: Subsector,
+        conflicts: &[Point],
+        inconsistencies: &BTreeMap<Point, Vec<Inconsistency>>,
+    ) {
+        let mut sections = Vec::new();
+        if !conflicts.is_empty() {
+            let mut text = format!(
+                "{} imported world(s) are in hexes that are already occupied:\n",
+                conflicts.len()
+            );
+            for point in conflicts {
+                if let (Some(existing), Some(imported)) =
+                    (self.subsector.get_world(point), subsector.get_world(point))
+                {
+                    let (existing_uwp, imported_uwp) =
+                        (existing.profile_str(), imported.profile_str());
+                    text += &format!(
+                        "\n{}: {} {} here, {} {} imported",
+                        point, existing.name, existing_uwp, imported.name, imported_uwp
+                    );
+                    if existing_uwp == imported_uwp {
+                        text += " (same UWP)";
+                    }
+                }
+            }
+            sections.push(text);
+        }
+
+        let out_of_bounds: Vec<_> = subsector
+            .get_map()
+            .keys()
+            .filter(|point| !Subsector::point_is_inbounds(point))
+            .map(Point::to_string)
+            .collect();
+        if !out_of_bounds.is_empty() {
+            sections.push(format!(
+                "{} imported world(s) are outside of the subsector and will be skipped:\n\n{}",
+                out_of_bounds.len(),
+                out_of_bounds.join(", ")
+            ));
+        }
+
+        if !inconsistencies.is_empty() {
+            let mut text = format!(
+                "{} imported world(s) break the world generation rules:\n",
+                inconsistencies.len()
+            );
+            for (point, world_inconsistencies) in inconsistencies {
+                for inconsistency in world_inconsistencies {
+                    text += &format!("\n{}: {}", point, inconsistency);
+                }
+            }
+            sections.push(text);
+        }
+
+        let question = if conflicts.is_empty() {
+            "Import them anyway?"
+        } else {
+            "What should be done with the worlds in occupied hexes?"
+        };
+        let text = format!("{}\n\n{}", sections.join("\n\n"), question);
+
+        let confirm_import = |resolution| Message::ConfirmImportWorlds {
+            subsector: subsector.clone(),
+            resolution,
+        };
+
+        let popup = ButtonPopup::new(
+            "Import Conflicts".to_string(),
+            text,
+            self.message_tx.clone(),
+        );
+        let popup = if conflicts.is_empty() {
+            popup.add_button("Import".to_string(), confirm_import(MergeResolution::Skip))
+        } else {
+            popup
+                .add_button("Skip".to_string(), confirm_import(MergeResolution::Skip))
+                .add_button(
+                    "Overwrite".to_string(),
+                    confirm_import(MergeResolution::Overwrite),
+                )
+                .add_button(
+                    "Move to Nearest Empty Hex".to_string(),
+                    confirm_import(MergeResolution::MoveToNearestEmpty),
+                )
+        };
+
+        self.add_popup(popup.add_button("Cancel".to_string(), Message::NoOp));
+    }
+
+    pub(crate) fn new_empty_subsector_popup(&mut self) {
+        self.add_popup(SubsectorRenamePopup::new(
+            "New Empty Subsector",
+            &random_subsector_name(NameStyle::default()),
+            |name| Message::ConfirmNewEmptySubsector { name },
+            self.message_tx.clone(),
+        ));
+    }
+
     pub(crate) fn occupied_hex_popup(&mut self, world_name: String, location: Point) {
         let popup = ButtonPopup::new(
             "Destination Hex Occupied".to_string(),
@@ -33,17 +229,45 @@ impl GeneratorApp {
         self.add_popup(popup);
     }
 
-    pub(crate) fn regen_world_popup(&mut self) {
+    pub(crate) fn player_view_popup(&mut self) {
+        // Leaving the player view needs the passphrase it was locked with
+        let passphrase_to_leave = if self.player_view {
+            Some(self.player_view_passphrase.clone().unwrap_or_default())
+        } else {
+            None
+        };
+        self.add_popup(PlayerViewPopup::new(
+            passphrase_to_leave,
+            self.message_tx.clone(),
+        ));
+    }
+
+    pub(crate) fn political_crisis_popup(&mut self) {
+        self.add_popup(PoliticalCrisisPopup::new(
+            self.world.name.clone(),
+            self.message_tx.clone(),
+        ));
+    }
+
+    pub(crate) fn regen_attribute_popup(&mut self, attribute: WorldAttribute) {
+        let world_count = self.subsector.get_map().len();
         let popup = ButtonPopup::new(
-            "Regenerating World".to_string(),
+            format!("Regenerate {}", attribute),
             format!(
-                "Do you want to completely regenerate '{}'? This can not be undone.",
-                self.world.name
+                "{} world(s) will have their {} regenerated. Descriptions you have edited will be \
+                kept, but this can not be undone.",
+                world_count,
+                attribute.to_string().to_lowercase()
             ),
             self.message_tx.clone(),
         )
-        .add_confirm_buttons(Message::ConfirmRegenWorld, Message::NoOp);
+        .add_confirm_buttons(Message::ConfirmRegenAttribute { attribute }, Message::NoOp);
+
+        self.add_popup(popup);
+    }
 
+    pub(crate) fn regen_world_popup(&mut self) {
+        let popup = WorldRegenPopup::new(&self.world.name, self.message_tx.clone());
         self.add_popup(popup);
     }
 
@@ -51,7 +275,8 @@ impl GeneratorApp {
         let popup = ButtonPopup::new(
             "Removing World".to_string(),
             format!(
-                "Do you want to completely remove '{}'? This can not be undone.",
+                "Do you want to remove '{}'? It can be restored from Edit > Recently Deleted until \
+                 the app is closed.",
                 self.world.name
             ),
             self.message_tx.clone(),
@@ -64,17 +289,139 @@ impl GeneratorApp {
         self.add_popup(popup);
     }
 
+    pub(crate) fn reroll_dependents_popup(
+        &mut self,
+        field: WorldField,
+        world: World,
+        changes: &[WorldChange],
+    ) {
+        let changes: Vec<String> = changes
+            .iter()
+            .map(|change| {
+                format!(
+                    "{}: {} -> {}",
+                    change.field, change.old_value, change.new_value
+                )
+            })
+            .collect();
+        let popup = ButtonPopup::new(
+            "Reroll Dependent Fields".to_string(),
+            format!(
+                "Fields rolled from {} may no longer fit it. Rerolling them would change:\n\n{}",
+                field.to_string().to_lowercase(),
+                changes.join("\n")
+            ),
+            self.message_tx.clone(),
+        )
+        .add_button(
+            "Reroll".to_string(),
+            Message::ConfirmRerollDependents {
+                world: Box::new(world),
+            },
+        )
+        .add_button("Keep".to_string(), Message::NoOp);
+
+        self.add_popup(popup);
+    }
+
+    pub(crate) fn save_file_changed_popup(&mut self) {
+        let mut text = format!(
+            "{} was changed outside of the generator.\n\nReload it to see the outside changes, \
+            or keep the current version to overwrite them the next time it is saved.",
+            self.save_filename
+        );
+        if self.has_unsaved_changes() {
+            text += "\n\nReloading will discard the unsaved changes made here.";
+        }
+
+        let popup = ButtonPopup::new(
+            "Save File Changed".to_string(),
+            text,
+            self.message_tx.clone(),
+        )
+        .add_button("Reload".to_string(), Message::ReloadSaveFile)
+        .add_button("Keep Current Version".to_string(), Message::NoOp);
+
+        self.add_popup(popup);
+    }
+
+    /** Show the progress of exporting `total` world sheets, as reported through `progress_rx`. */
+    pub(crate) fn sheet_export_progress_popup(
+        &mut self,
+        total: usize,
+        progress_rx: mpsc::Receiver<Result<usize, String>>,
+    ) {
+        self.add_popup(SheetExportProgressPopup::new(total, progress_rx));
+    }
+
+    pub(crate) fn subsector_batch_popup(&mut self) {
+        self.add_popup(SubsectorRegenPopup::new(
+            self.subsector.rule_set(),
+            self.settings.generation_presets.clone(),
+            Some(DEFAULT_BATCH_SIZE),
+            self.message_tx.clone(),
+        ));
+    }
+
     pub(crate) fn subsector_regen_popup(&mut self) {
-        self.add_popup(SubsectorRegenPopup::new(self.message_tx.clone()));
+        self.add_popup(SubsectorRegenPopup::new(
+            self.subsector.rule_set(),
+            self.settings.generation_presets.clone(),
+            None,
+            self.message_tx.clone(),
+        ));
     }
 
     pub(crate) fn subsector_rename_popup(&mut self) {
         self.add_popup(SubsectorRenamePopup::new(
+            "Rename Subsector",
             self.subsector.name(),
+            |new_name| Message::ConfirmRenameSubsector { new_name },
+            self.message_tx.clone(),
+        ));
+    }
+
+    pub(crate) fn table_overrides_popup(&mut self, overrides: TableOverrides) {
+        self.add_popup(TableOverridesPopup::new(overrides, self.message_tx.clone()));
+    }
+
+    pub(crate) fn text_import_popup(&mut self) {
+        self.add_popup(TextImportPopup::new(
+            self.subsector.rule_set(),
+            self.message_tx.clone(),
+        ));
+    }
+
+    pub(crate) fn travel_code_policy_popup(&mut self) {
+        self.add_popup(TravelCodePolicyPopup::new(
+            self.subsector.clone(),
             self.message_tx.clone(),
         ));
     }
 
+    pub(crate) fn traveller_map_subsector_popup(&mut self, subsectors: Vec<Subsector>) {
+        let popup = ButtonPopup::new(
+            "Choose Subsector".to_string(),
+            format!(
+                "The file has worlds in {} subsectors. Which one should be opened in a new tab?",
+                subsectors.len()
+            ),
+            self.message_tx.clone(),
+        );
+        let popup = subsectors
+            .into_iter()
+            .fold(popup, |popup, subsector| {
+                let index = subsector.location().map_or('?', |location| location.index);
+                popup.add_button(
+                    index.to_string(),
+                    Message::ConfirmImportTravellerMap { subsector },
+                )
+            })
+            .add_button("Cancel".to_string(), Message::NoOp);
+
+        self.add_popup(popup);
+    }
+
     pub(crate) fn unapplied_world_popup(&mut self, new_point: Point) {
         let popup = ButtonPopup::new(
             "Unapplied World Changes".to_string(),
@@ -97,6 +444,21 @@ impl GeneratorApp {
         self.add_popup(popup);
     }
 
+    pub(crate) fn unsaved_close_tab_popup(&mut self) {
+        let popup = ButtonPopup::unsaved_changes_dialog(
+            format!(
+                "Do you want to save changes to Subsector {}?",
+                self.subsector.name()
+            ),
+            Message::SaveCloseTab,
+            Message::ConfirmCloseTab,
+            Message::NoOp,
+            self.message_tx.clone(),
+        );
+
+        self.add_popup(popup);
+    }
+
     pub(crate) fn unsaved_exit_popup(&mut self) {
         let popup = ButtonPopup::unsaved_changes_dialog(
             format!(
@@ -112,6 +474,21 @@ impl GeneratorApp {
         self.add_popup(popup);
     }
 
+    pub(crate) fn unsaved_subsector_new_empty_popup(&mut self) {
+        let popup = ButtonPopup::unsaved_changes_dialog(
+            format!(
+                "Do you want to save changes to Subsector {}?",
+                self.subsector.name()
+            ),
+            Message::SaveConfigNewEmptySubsector,
+            Message::ConfigNewEmptySubsector,
+            Message::NoOp,
+            self.message_tx.clone(),
+        );
+
+        self.add_popup(popup);
+    }
+
     pub(crate) fn unsaved_subsector_regen_popup(&mut self) {
         let popup = ButtonPopup::unsaved_changes_dialog(
             format!(
@@ -127,6 +504,10 @@ impl GeneratorApp {
         self.add_popup(popup);
     }
 
+    pub(crate) fn world_sheet_export_popup(&mut self) {
+        self.add_popup(WorldSheetExportPopup::new(self.message_tx.clone()));
+    }
+
     pub(crate) fn unsaved_subsector_reload_popup(&mut self) {
         let popup = ButtonPopup::unsaved_changes_dialog(
             format!(
@@ -246,76 +627,156 @@ impl Popup for ButtonPopup {
     }
 }
 
-struct SubsectorRegenPopup {
+struct FastForwardPopup {
+    decades: u32,
     is_done: bool,
     message_tx: pipe::Sender<Message>,
-    world_abundance: WorldAbundance,
+    rules: GrowthRules,
 }
 
-impl SubsectorRegenPopup {
-    fn new(message_tx: pipe::Sender<Message>) -> SubsectorRegenPopup {
+impl FastForwardPopup {
+    fn new(rules: GrowthRules, message_tx: pipe::Sender<Message>) -> Self {
         Self {
+            decades: 1,
             is_done: false,
             message_tx,
-            world_abundance: WorldAbundance::Nominal,
+            rules,
         }
     }
 }
 
-impl Popup for SubsectorRegenPopup {
+impl Popup for FastForwardPopup {
     fn is_done(&self) -> bool {
         self.is_done
     }
 
     fn show(&mut self, ctx: &Context) {
-        let title = "Choose World Abundance";
-        let popup_size = DEFAULT_POPUP_SIZE;
-
+        let title = "Fast-Forward Subsector";
         Window::new(title)
             .title_bar(false)
             .resizable(false)
-            .fixed_size(popup_size)
             .default_pos(center(ctx))
             .show(ctx, |ui| {
                 ui.vertical_centered(|ui| {
                     ui.heading(title);
-                    ui.separator();
-                    ui.add_space(FIELD_SPACING / 2.0);
+                });
+                ui.separator();
+                ui.label("The result opens in a new tab, leaving this subsector as it is.");
+                ui.add_space(FIELD_SPACING / 2.0);
 
-                    let column_count = WorldAbundance::WORLD_ABUNDANCE_VALUES.len() as f32;
-                    let grid_spacing = vec2(FIELD_SPACING / 2.0, LABEL_SPACING);
-                    let column_width =
-                        (popup_size.x - (column_count - 1.0) * grid_spacing.x) / column_count;
+                let max_tech_level = TABLES.tech_level_table.len() as u16 - 1;
+                Grid::new("fast_forward_grid")
+                    .num_columns(2)
+                    .spacing(vec2(FIELD_SPACING, LABEL_SPACING))
+                    .show(ui, |ui| {
+                        ui.label(RichText::new("Decades").font(LABEL_FONT).color(LABEL_COLOR));
+                        ui.add(DragValue::new(&mut self.decades).clamp_range(1..=100));
+                        ui.end_row();
 
-                    Grid::new("subsector_regen_grid")
-                        .spacing(grid_spacing)
-                        .min_col_width(column_width)
-                        .show(ui, |ui| {
-                            for world_abundance in WorldAbundance::WORLD_ABUNDANCE_VALUES {
-                                ui.vertical_centered(|ui| {
-                                    ui.radio_value(&mut self.world_abundance, world_abundance, "");
-                                });
-                            }
+                        let rules = &mut self.rules;
+                        let chances = [
+                            (&mut rules.population_change, "Population Change"),
+                            (&mut rules.tech_advance, "Tech Advance"),
+                            (&mut rules.tech_regress, "Tech Regress"),
+                            (&mut rules.starport_change, "Starport Change"),
+                            (&mut rules.zone_change, "Travel Zone Change"),
+                        ];
+                        for (chance, label) in chances {
+                            ui.label(RichText::new(label).font(LABEL_FONT).color(LABEL_COLOR));
+                            ui.add(Slider::new(chance, 0..=100).suffix("% per decade"));
                             ui.end_row();
+                        }
 
-                            for world_abundance in WorldAbundance::WORLD_ABUNDANCE_VALUES {
-                                ui.vertical_centered(|ui| {
-                                    ui.label(
-                                        RichText::new(world_abundance.to_string())
-                                            .font(LABEL_FONT)
-                                            .color(LABEL_COLOR),
-                                    );
-                                });
-                            }
+                        ui.label(
+                            RichText::new("Max Tech Level")
+                                .font(LABEL_FONT)
+                                .color(LABEL_COLOR),
+                        );
+                        ui.add(Slider::new(
+                            &mut self.rules.max_tech_level,
+                            0..=max_tech_level,
+                        ));
+                        ui.end_row();
+                    });
+                ui.add_space(FIELD_SPACING);
+
+                ui.horizontal(|ui| {
+                    if ui.button("Fast-Forward").clicked() {
+                        self.message_tx.send(Message::ConfirmFastForward {
+                            decades: self.decades,
+                            rules: self.rules,
                         });
+                        self.is_done = true;
+                    }
+
+                    if ui.button("Defaults").clicked() {
+                        self.rules = GrowthRules::default();
+                    }
+
+                    ui.with_layout(Layout::right_to_left(), |ui| {
+                        if ui.button("Cancel").clicked() {
+                            self.message_tx.send(Message::NoOp);
+                            self.is_done = true;
+                        }
+                    });
+                });
+            });
+    }
+}
+
+struct FillEmptyHexesPopup {
+    count: usize,
+    empty_hexes: usize,
+    is_done: bool,
+    message_tx: pipe::Sender<Message>,
+}
+
+impl FillEmptyHexesPopup {
+    fn new(empty_hexes: usize, message_tx: pipe::Sender<Message>) -> Self {
+        Self {
+            count: empty_hexes.min(1),
+            empty_hexes,
+            is_done: false,
+            message_tx,
+        }
+    }
+}
+
+impl Popup for FillEmptyHexesPopup {
+    fn is_done(&self) -> bool {
+        self.is_done
+    }
+
+    fn show(&mut self, ctx: &Context) {
+        let title = "Fill Empty Hexes";
+        Window::new(title)
+            .title_bar(false)
+            .resizable(false)
+            .default_pos(center(ctx))
+            .show(ctx, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.heading(title);
+                });
+                ui.separator();
+                ui.label(format!(
+                    "Add random worlds to empty hexes picked at random; {} hexes are empty.",
+                    self.empty_hexes
+                ));
+                ui.add_space(FIELD_SPACING / 2.0);
+
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("Worlds").font(LABEL_FONT).color(LABEL_COLOR));
+                    ui.add(DragValue::new(&mut self.count).clamp_range(0..=self.empty_hexes));
                 });
                 ui.add_space(FIELD_SPACING);
 
                 ui.horizontal(|ui| {
-                    if ui.button("Generate").clicked() {
-                        self.message_tx.send(Message::ConfirmRegenSubsector {
-                            world_abundance_dm: self.world_abundance.into(),
-                        });
+                    if ui
+                        .add_enabled(self.count > 0, Button::new("Fill"))
+                        .clicked()
+                    {
+                        self.message_tx
+                            .send(Message::ConfirmFillEmptyHexes { count: self.count });
                         self.is_done = true;
                     }
 
@@ -330,48 +791,81 @@ impl Popup for SubsectorRegenPopup {
     }
 }
 
-struct SubsectorRenamePopup {
+struct HandoutExportPopup {
     is_done: bool,
     message_tx: pipe::Sender<Message>,
-    name: String,
+    options: HandoutOptions,
 }
 
-impl SubsectorRenamePopup {
-    fn new(initial_name: &str, message_tx: pipe::Sender<Message>) -> Self {
+impl HandoutExportPopup {
+    fn new(options: HandoutOptions, message_tx: pipe::Sender<Message>) -> Self {
         Self {
             is_done: false,
             message_tx,
-            name: initial_name.to_string(),
+            options,
         }
     }
 }
 
-impl Popup for SubsectorRenamePopup {
+impl Popup for HandoutExportPopup {
     fn is_done(&self) -> bool {
         self.is_done
     }
 
     fn show(&mut self, ctx: &Context) {
-        const TITLE: &str = "Rename Subsector";
+        const TITLE: &str = "Export Player Handout";
+        // Extra height for the field checkboxes and jump table
+        let popup_size = DEFAULT_POPUP_SIZE + vec2(0.0, 130.0);
 
         Window::new(TITLE)
             .title_bar(false)
             .resizable(false)
-            .fixed_size(DEFAULT_POPUP_SIZE)
+            .fixed_size(popup_size)
             .default_pos(center(ctx))
             .show(ctx, |ui| {
                 ui.vertical_centered(|ui| {
                     ui.heading(TITLE);
                     ui.separator();
-                    ui.add_space(FIELD_SPACING / 2.0);
-                    ui.add(TextEdit::singleline(&mut self.name).margin(vec2(16.0, 4.0)));
+                });
+                ui.add_space(FIELD_SPACING / 2.0);
+
+                ui.label(
+                    RichText::new("Reveal to Players")
+                        .font(LABEL_FONT)
+                        .color(LABEL_COLOR),
+                );
+                ui.checkbox(&mut self.options.culture, "Culture");
+                ui.checkbox(&mut self.options.factions, "Faction names")
+                    .on_hover_text("Faction governments always stay in the GM appendix");
+                ui.checkbox(&mut self.options.travel_advisory, "Travel advisories")
+                    .on_hover_text("Advisories may name the factions of troubled worlds");
+                ui.checkbox(&mut self.options.world_tags, "World tags as rumors");
+                ui.add_space(LABEL_SPACING);
+                ui.label(
+                    "Player notes are always revealed. Everything else, secret notes included, \
+                    goes into a GM appendix at the end.",
+                );
+                ui.add_space(LABEL_SPACING);
+
+                ui.horizontal(|ui| {
+                    let mut jump_table = self.options.jump_table.is_some();
+                    if ui.checkbox(&mut jump_table, "Jump table").changed() {
+                        self.options.jump_table = jump_table.then_some(DEFAULT_JUMP_TABLE_RATING);
+                    }
+                    if let Some(jump_rating) = &mut self.options.jump_table {
+                        ui.add(
+                            DragValue::new(jump_rating)
+                                .clamp_range(1..=6)
+                                .prefix("Jump-"),
+                        );
+                    }
                 });
                 ui.add_space(FIELD_SPACING);
 
                 ui.horizontal(|ui| {
-                    if ui.button("Confirm").clicked() {
-                        self.message_tx.send(Message::ConfirmRenameSubsector {
-                            new_name: self.name.clone(),
+                    if ui.button("Export...").clicked() {
+                        self.message_tx.send(Message::ConfirmExportPlayerHandout {
+                            options: self.options,
                         });
                         self.is_done = true;
                     }
@@ -387,8 +881,1458 @@ impl Popup for SubsectorRenamePopup {
     }
 }
 
-/// Calculate and return the centered position of a default-sized popup for a given `Context`.
-#[inline]
-fn center(ctx: &Context) -> Pos2 {
-    ctx.available_rect().center() - DEFAULT_POPUP_SIZE / 2.0
+struct MapExportPopup {
+    format: MapFormat,
+    is_done: bool,
+    message_tx: pipe::Sender<Message>,
+    options: MapOptions,
+}
+
+impl MapExportPopup {
+    fn new(options: MapOptions, message_tx: pipe::Sender<Message>) -> Self {
+        Self {
+            format: MapFormat::Svg,
+            is_done: false,
+            message_tx,
+            options,
+        }
+    }
+}
+
+impl Popup for MapExportPopup {
+    fn is_done(&self) -> bool {
+        self.is_done
+    }
+
+    fn show(&mut self, ctx: &Context) {
+        const TITLE: &str = "Export Subsector Map";
+        // Extra height for the renderer, layout, and grid options, layers, and style fields
+        let popup_size = DEFAULT_POPUP_SIZE + vec2(0.0, 410.0);
+
+        Window::new(TITLE)
+            .title_bar(false)
+            .resizable(false)
+            .fixed_size(popup_size)
+            .default_pos(center(ctx))
+            .show(ctx, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.heading(TITLE);
+                    ui.separator();
+                });
+                ui.add_space(FIELD_SPACING / 2.0);
+
+                ui.label(RichText::new("Format").font(LABEL_FONT).color(LABEL_COLOR));
+                ui.horizontal(|ui| {
+                    for format in MapFormat::ALL_VALUES {
+                        ui.radio_value(&mut self.format, format, format.to_string());
+                    }
+                });
+                ui.add_space(FIELD_SPACING / 2.0);
+
+                ui.label(
+                    RichText::new("Drawn With")
+                        .font(LABEL_FONT)
+                        .color(LABEL_COLOR),
+                );
+                ui.horizontal(|ui| {
+                    for renderer in MapRenderer::ALL_VALUES {
+                        ui.radio_value(&mut self.options.renderer, renderer, renderer.to_string())
+                            .on_hover_text(match renderer {
+                                MapRenderer::Template => "Fill the map into the map template",
+                                MapRenderer::Standalone => {
+                                    "Draw the whole map without the map template, as plain SVG \
+                                    for browsers and other vector tools"
+                                }
+                            });
+                    }
+                });
+                ui.add_space(FIELD_SPACING / 2.0);
+
+                ui.label(RichText::new("Layout").font(LABEL_FONT).color(LABEL_COLOR));
+                Grid::new("map_export_layout_grid")
+                    .num_columns(2)
+                    .spacing(vec2(FIELD_SPACING, LABEL_SPACING))
+                    .show(ui, |ui| {
+                        for (i, layout) in MapLayout::ALL_VALUES.into_iter().enumerate() {
+                            ui.radio_value(&mut self.options.layout, layout, layout.to_string());
+                            if i % 2 == 1 {
+                                ui.end_row();
+                            }
+                        }
+                    });
+                ui.add_space(FIELD_SPACING / 2.0);
+
+                ui.label(
+                    RichText::new("Hex Grid")
+                        .font(LABEL_FONT)
+                        .color(LABEL_COLOR),
+                );
+                const GRID_HOVER_TEXT: &str =
+                    "The map template's grid can't be changed, so these maps are always drawn \
+                    without it";
+                ui.horizontal(|ui| {
+                    for orientation in HexOrientation::ALL_VALUES {
+                        ui.radio_value(
+                            &mut self.options.hex_orientation,
+                            orientation,
+                            orientation.to_string(),
+                        )
+                        .on_hover_text(match orientation {
+                            HexOrientation::FlatTop => "Columns run down the map".to_string(),
+                            HexOrientation::PointyTop => {
+                                "Columns run across the map turned on its side\n\n".to_string()
+                                    + GRID_HOVER_TEXT
+                            }
+                        });
+                    }
+                });
+                ui.checkbox(&mut self.options.grid_headers, "Row & Column Headers")
+                    .on_hover_text(
+                        "Number the columns and rows along the edges of the grid\n\n".to_string()
+                            + GRID_HOVER_TEXT,
+                    );
+                ui.add_space(FIELD_SPACING / 2.0);
+
+                ui.label(
+                    RichText::new("World Labels")
+                        .font(LABEL_FONT)
+                        .color(LABEL_COLOR),
+                );
+                ui.horizontal(|ui| {
+                    let mut labels = self.options.world_labels();
+                    for choice in WorldLabels::ALL_VALUES {
+                        ui.radio_value(&mut labels, Some(choice), choice.to_string());
+                    }
+                    if let Some(labels) = labels {
+                        self.options.set_world_labels(labels);
+                    }
+                })
+                .response
+                .on_hover_text("Drop world names to print the UWPs larger on dense maps");
+                ui.add_space(FIELD_SPACING / 2.0);
+
+                ui.label(RichText::new("Layers").font(LABEL_FONT).color(LABEL_COLOR));
+                let options = &mut self.options;
+                let layers = [
+                    (&mut options.starport_tl, "Starport-TL"),
+                    (&mut options.bases, "Bases"),
+                    (&mut options.travel_zones, "Travel Zones"),
+                    (&mut options.routes, "Travel Route"),
+                    (&mut options.xboat_routes, "X-Boat Routes"),
+                    (&mut options.annotations, "Annotations"),
+                    (&mut options.polity_colors, "Polity Colors"),
+                    (&mut options.hex_numbers, "Hex Numbers"),
+                    (&mut options.legend, "Legend"),
+                ];
+                Grid::new("map_export_layer_grid")
+                    .num_columns(2)
+                    .spacing(vec2(FIELD_SPACING, LABEL_SPACING))
+                    .show(ui, |ui| {
+                        for (i, (enabled, label)) in layers.into_iter().enumerate() {
+                            ui.checkbox(enabled, label);
+                            if i % 2 == 1 {
+                                ui.end_row();
+                            }
+                        }
+                    });
+                ui.add_space(FIELD_SPACING / 2.0);
+
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("Style").font(LABEL_FONT).color(LABEL_COLOR));
+                    ui.with_layout(Layout::right_to_left(), |ui| {
+                        if ui.button("Reset").clicked() {
+                            self.options.style = MapStyle::default();
+                        }
+                    });
+                });
+                let style = &mut self.options.style;
+                Grid::new("map_export_style_grid")
+                    .num_columns(2)
+                    .spacing(vec2(FIELD_SPACING, LABEL_SPACING))
+                    .show(ui, |ui| {
+                        ui.label("Font Family");
+                        ui.add(TextEdit::singleline(&mut style.font_family).desired_width(128.0))
+                            .on_hover_text(
+                                "CSS font family of world text, e.g. \"Georgia, serif\"",
+                            );
+                        ui.end_row();
+
+                        let font_sizes = [
+                            (&mut style.name_font_size, "Name Size"),
+                            (&mut style.uwp_font_size, "UWP Size"),
+                            (&mut style.label_font_size, "Starport-TL Size"),
+                        ];
+                        for (size, label) in font_sizes {
+                            ui.label(label);
+                            ui.add(
+                                DragValue::new(size)
+                                    .clamp_range(MapStyle::MIN_FONT_SIZE..=MapStyle::MAX_FONT_SIZE)
+                                    .fixed_decimals(2)
+                                    .speed(0.05)
+                                    .suffix(" mm"),
+                            );
+                            ui.end_row();
+                        }
+
+                        ui.label("Symbol Scale");
+                        ui.add(
+                            DragValue::new(&mut style.symbol_scale)
+                                .clamp_range(
+                                    MapStyle::MIN_SYMBOL_SCALE..=MapStyle::MAX_SYMBOL_SCALE,
+                                )
+                                .fixed_decimals(2)
+                                .speed(0.01)
+                                .suffix("x"),
+                        );
+                        ui.end_row();
+
+                        let line_weights = [
+                            (&mut style.zone_line_weight, "Travel Zone Lines"),
+                            (&mut style.route_line_weight, "Route Lines"),
+                        ];
+                        for (weight, label) in line_weights {
+                            ui.label(label);
+                            ui.add(
+                                DragValue::new(weight)
+                                    .clamp_range(
+                                        MapStyle::MIN_LINE_WEIGHT..=MapStyle::MAX_LINE_WEIGHT,
+                                    )
+                                    .fixed_decimals(2)
+                                    .speed(0.01)
+                                    .suffix(" mm"),
+                            );
+                            ui.end_row();
+                        }
+                    });
+                ui.add_space(FIELD_SPACING);
+
+                ui.horizontal(|ui| {
+                    if ui.button("Export").clicked() {
+                        self.message_tx.send(Message::ConfirmExportSubsectorMap {
+                            format: self.format,
+                            options: self.options.clone(),
+                        });
+                        self.is_done = true;
+                    }
+
+                    ui.with_layout(Layout::right_to_left(), |ui| {
+                        if ui.button("Cancel").clicked() {
+                            self.message_tx.send(Message::NoOp);
+                            self.is_done = true;
+                        }
+                    });
+                });
+            });
+    }
+}
+
+/** Progress of world sheets being written by a worker thread.
+
+The worker sends the number of sheets written so far after each one, or an error message if it
+fails and gives up.
+*/
+/** Popup asking for the passphrase that locks the player view when entering it, or for the same
+passphrase again when leaving it.
+*/
+struct PlayerViewPopup {
+    /// Whether the last passphrase entered to leave the player view was wrong
+    incorrect: bool,
+    is_done: bool,
+    message_tx: pipe::Sender<Message>,
+    passphrase: String,
+    /// Passphrase needed to leave the player view, or `None` if it is being entered instead
+    passphrase_to_leave: Option<String>,
+}
+
+impl PlayerViewPopup {
+    fn new(passphrase_to_leave: Option<String>, message_tx: pipe::Sender<Message>) -> Self {
+        Self {
+            incorrect: false,
+            is_done: false,
+            message_tx,
+            passphrase: String::new(),
+            passphrase_to_leave,
+        }
+    }
+}
+
+impl Popup for PlayerViewPopup {
+    fn is_done(&self) -> bool {
+        self.is_done
+    }
+
+    fn show(&mut self, ctx: &Context) {
+        let title = match self.passphrase_to_leave {
+            Some(_) => "Leave Player View",
+            None => "Enter Player View",
+        };
+
+        Window::new(title)
+            .title_bar(false)
+            .resizable(false)
+            .fixed_size(DEFAULT_POPUP_SIZE)
+            .default_pos(center(ctx))
+            .show(ctx, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.heading(title);
+                    ui.separator();
+                    ui.add_space(FIELD_SPACING / 2.0);
+
+                    let hint_text = match self.passphrase_to_leave {
+                        Some(_) => "Passphrase",
+                        None => "Passphrase to leave (optional)",
+                    };
+                    ui.add(
+                        TextEdit::singleline(&mut self.passphrase)
+                            .password(true)
+                            .hint_text(hint_text)
+                            .margin(vec2(16.0, 4.0)),
+                    );
+                    if self.incorrect {
+                        ui.colored_label(NEGATIVE_RED, "Incorrect passphrase");
+                    }
+                });
+                ui.add_space(FIELD_SPACING);
+
+                ui.horizontal(|ui| {
+                    if ui.button("Confirm").clicked() {
+                        let passphrase = self.passphrase.clone();
+                        match &self.passphrase_to_leave {
+                            Some(expected) if *expected != passphrase => self.incorrect = true,
+                            Some(_) => {
+                                self.message_tx
+                                    .send(Message::ConfirmLeavePlayerView { passphrase });
+                                self.is_done = true;
+                            }
+                            None => {
+                                self.message_tx
+                                    .send(Message::ConfirmEnterPlayerView { passphrase });
+                                self.is_done = true;
+                            }
+                        }
+                    }
+
+                    ui.with_layout(Layout::right_to_left(), |ui| {
+                        if ui.button("Cancel").clicked() {
+                            self.message_tx.send(Message::NoOp);
+                            self.is_done = true;
+                        }
+                    });
+                });
+            });
+    }
+}
+
+struct PoliticalCrisisPopup {
+    /// Kind of crisis to put the world through; `None` to roll for it
+    event: Option<PoliticalEvent>,
+    is_done: bool,
+    message_tx: pipe::Sender<Message>,
+    world_name: String,
+}
+
+impl PoliticalCrisisPopup {
+    fn new(world_name: String, message_tx: pipe::Sender<Message>) -> Self {
+        Self {
+            event: None,
+            is_done: false,
+            message_tx,
+            world_name,
+        }
+    }
+}
+
+impl Popup for PoliticalCrisisPopup {
+    fn is_done(&self) -> bool {
+        self.is_done
+    }
+
+    fn show(&mut self, ctx: &Context) {
+        const TITLE: &str = "Political Crisis";
+        Window::new(TITLE)
+            .title_bar(false)
+            .resizable(false)
+            .fixed_size(DEFAULT_POPUP_SIZE)
+            .default_pos(center(ctx))
+            .show(ctx, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.heading(TITLE);
+                    ui.separator();
+                });
+                ui.label(format!(
+                    "The government, law level, and factions of {} change, and a summary is \
+                    added to its secret notes.",
+                    self.world_name
+                ));
+                ui.add_space(FIELD_SPACING / 2.0);
+
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut self.event, None, "Random");
+                    for event in PoliticalEvent::ALL_VALUES {
+                        ui.radio_value(&mut self.event, Some(event), event.to_string());
+                    }
+                });
+                ui.add_space(FIELD_SPACING);
+
+                ui.horizontal(|ui| {
+                    if ui.button("Confirm").clicked() {
+                        self.message_tx
+                            .send(Message::ConfirmPoliticalCrisis { event: self.event });
+                        self.is_done = true;
+                    }
+
+                    ui.with_layout(Layout::right_to_left(), |ui| {
+                        if ui.button("Cancel").clicked() {
+                            self.message_tx.send(Message::NoOp);
+                            self.is_done = true;
+                        }
+                    });
+                });
+            });
+    }
+}
+
+struct SheetExportProgressPopup {
+    error: Option<String>,
+    is_done: bool,
+    progress_rx: mpsc::Receiver<Result<usize, String>>,
+    total: usize,
+    written: usize,
+}
+
+impl SheetExportProgressPopup {
+    fn new(total: usize, progress_rx: mpsc::Receiver<Result<usize, String>>) -> Self {
+        Self {
+            error: None,
+            is_done: false,
+            progress_rx,
+            total,
+            written: 0,
+        }
+    }
+}
+
+impl Popup for SheetExportProgressPopup {
+    fn is_done(&self) -> bool {
+        self.is_done
+    }
+
+    fn show(&mut self, ctx: &Context) {
+        const TITLE: &str = "Exporting World Sheets";
+
+        while let Ok(progress) = self.progress_rx.try_recv() {
+            match progress {
+                Ok(written) => self.written = written,
+                Err(e) => self.error = Some(e),
+            }
+        }
+        let finished = self.error.is_some() || self.written >= self.total;
+        if !finished {
+            // Keep polling the worker even if the user isn't moving the mouse
+            ctx.request_repaint();
+        }
+
+        Window::new(TITLE)
+            .title_bar(false)
+            .resizable(false)
+            .fixed_size(DEFAULT_POPUP_SIZE)
+            .default_pos(center(ctx))
+            .show(ctx, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.heading(TITLE);
+                    ui.separator();
+                    ui.add_space(FIELD_SPACING / 2.0);
+
+                    let fraction = if self.total == 0 {
+                        1.0
+                    } else {
+                        self.written as f32 / self.total as f32
+                    };
+                    ui.add(
+                        ProgressBar::new(fraction)
+                            .text(format!("{} of {} written", self.written, self.total)),
+                    );
+
+                    if let Some(error) = &self.error {
+                        ui.add_space(LABEL_SPACING);
+                        ui.label(format!("Export failed: {}", error));
+                    }
+                });
+                ui.add_space(FIELD_SPACING);
+
+                ui.with_layout(Layout::right_to_left(), |ui| {
+                    if ui.add_enabled(finished, Button::new("OK")).clicked() {
+                        self.is_done = true;
+                    }
+                });
+            });
+    }
+}
+
+struct SubsectorRegenPopup {
+    /// Number of subsectors to generate at once to pick from; `None` to replace the loaded one
+    batch_size: Option<usize>,
+    /// Percentage from 0 to 100 of how strongly worlds cluster together
+    clustering: u8,
+    is_done: bool,
+    message_tx: pipe::Sender<Message>,
+    /// Style of the name given to the new subsector
+    name_style: NameStyle,
+    /// Name the current options are saved as a preset under
+    preset_name: String,
+    /// Saved presets, kept in step with the ones in the app's settings
+    presets: Vec<GenerationPreset>,
+    rule_set: RuleSet,
+    world_abundance: WorldAbundance,
+}
+
+impl SubsectorRegenPopup {
+    fn new(
+        rule_set: RuleSet,
+        presets: Vec<GenerationPreset>,
+        batch_size: Option<usize>,
+        message_tx: pipe::Sender<Message>,
+    ) -> SubsectorRegenPopup {
+        Self {
+            batch_size,
+            clustering: 0,
+            is_done: false,
+            message_tx,
+            name_style: NameStyle::default(),
+            preset_name: String::new(),
+            presets,
+            rule_set,
+            world_abundance: WorldAbundance::Nominal,
+        }
+    }
+
+    /** Select the options saved in `preset`. */
+    fn load_preset(&mut self, preset: GenerationPreset) {
+        self.clustering = preset.clustering;
+        self.name_style = preset.name_style;
+        self.preset_name = preset.name;
+        self.rule_set = preset.rule_set;
+        self.world_abundance = preset.world_abundance;
+    }
+
+    /** Show the selection of saved presets and the buttons to save and delete them. */
+    fn presets_row(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            let mut selected = None;
+            ComboBox::from_id_source("subsector_regen_preset")
+                .selected_text("Load Preset")
+                .show_ui(ui, |ui| {
+                    for preset in self.presets.iter() {
+                        if ui.selectable_label(false, &preset.name).clicked() {
+                            selected = Some(preset.clone());
+                        }
+                    }
+                });
+            if let Some(preset) = selected {
+                self.load_preset(preset);
+            }
+
+            ui.add(TextEdit::singleline(&mut self.preset_name).desired_width(96.0))
+                .on_hover_text("Name to save the selected options under");
+
+            let name = self.preset_name.trim().to_string();
+            if ui
+                .add_enabled(!name.is_empty(), Button::new("Save"))
+                .on_hover_text(
+                    "Save the selected options as a preset, replacing one of the same name",
+                )
+                .clicked()
+            {
+                let preset = GenerationPreset {
+                    clustering: self.clustering,
+                    name: name.clone(),
+                    name_style: self.name_style,
+                    rule_set: self.rule_set,
+                    world_abundance: self.world_abundance,
+                };
+                match self.presets.iter_mut().find(|saved| saved.name == name) {
+                    Some(saved) => *saved = preset.clone(),
+                    None => self.presets.push(preset.clone()),
+                }
+                self.message_tx
+                    .send(Message::SaveGenerationPreset { preset });
+            }
+
+            let is_saved = self.presets.iter().any(|saved| saved.name == name);
+            if ui
+                .add_enabled(is_saved, Button::new("Delete"))
+                .on_hover_text("Delete the preset with this name")
+                .clicked()
+            {
+                self.presets.retain(|saved| saved.name != name);
+                self.message_tx
+                    .send(Message::DeleteGenerationPreset { name });
+            }
+        });
+    }
+}
+
+impl Popup for SubsectorRegenPopup {
+    fn is_done(&self) -> bool {
+        self.is_done
+    }
+
+    fn show(&mut self, ctx: &Context) {
+        let title = match self.batch_size {
+            Some(_) => "Generate Batch",
+            None => "Choose World Abundance",
+        };
+        // Extra height and width for the clustering, generation rules, name style, and preset
+        // selections, plus the batch size if there is one
+        let popup_size = match self.batch_size {
+            Some(_) => DEFAULT_POPUP_SIZE + vec2(64.0, 224.0),
+            None => DEFAULT_POPUP_SIZE + vec2(64.0, 176.0),
+        };
+
+        Window::new(title)
+            .title_bar(false)
+            .resizable(false)
+            .fixed_size(popup_size)
+            .default_pos(center(ctx))
+            .show(ctx, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.heading(title);
+                    ui.separator();
+                    ui.add_space(FIELD_SPACING / 2.0);
+
+                    let column_count = WorldAbundance::WORLD_ABUNDANCE_VALUES.len() as f32;
+                    let grid_spacing = vec2(FIELD_SPACING / 2.0, LABEL_SPACING);
+                    let column_width =
+                        (popup_size.x - (column_count - 1.0) * grid_spacing.x) / column_count;
+
+                    Grid::new("subsector_regen_grid")
+                        .spacing(grid_spacing)
+                        .min_col_width(column_width)
+                        .show(ui, |ui| {
+                            for world_abundance in WorldAbundance::WORLD_ABUNDANCE_VALUES {
+                                ui.vertical_centered(|ui| {
+                                    ui.radio_value(&mut self.world_abundance, world_abundance, "");
+                                });
+                            }
+                            ui.end_row();
+
+                            for world_abundance in WorldAbundance::WORLD_ABUNDANCE_VALUES {
+                                ui.vertical_centered(|ui| {
+                                    ui.label(
+                                        RichText::new(world_abundance.to_string())
+                                            .font(LABEL_FONT)
+                                            .color(LABEL_COLOR),
+                                    );
+                                });
+                            }
+                        });
+                    ui.add_space(FIELD_SPACING);
+
+                    ui.label(
+                        RichText::new("Clustering")
+                            .font(LABEL_FONT)
+                            .color(LABEL_COLOR),
+                    );
+                    ui.add(Slider::new(&mut self.clustering, 0..=100).suffix("%"))
+                        .on_hover_text(
+                            "How strongly worlds group into clusters, chains, and rifts",
+                        );
+                    ui.add_space(FIELD_SPACING);
+
+                    ui.label(
+                        RichText::new("Generation Rules")
+                            .font(LABEL_FONT)
+                            .color(LABEL_COLOR),
+                    );
+                    ui.horizontal(|ui| {
+                        for rule_set in RuleSet::ALL_VALUES {
+                            ui.radio_value(&mut self.rule_set, rule_set, rule_set.to_string());
+                        }
+                    });
+                    ui.add_space(FIELD_SPACING);
+
+                    ui.label(
+                        RichText::new("Name Style")
+                            .font(LABEL_FONT)
+                            .color(LABEL_COLOR),
+                    );
+                    ui.horizontal(|ui| {
+                        for style in NameStyle::ALL_VALUES {
+                            ui.radio_value(&mut self.name_style, style, style.to_string());
+                        }
+                    });
+                    ui.add_space(FIELD_SPACING);
+
+                    if let Some(batch_size) = &mut self.batch_size {
+                        ui.label(
+                            RichText::new("Batch Size")
+                                .font(LABEL_FONT)
+                                .color(LABEL_COLOR),
+                        );
+                        ui.add(Slider::new(batch_size, 2..=MAX_BATCH_SIZE))
+                            .on_hover_text("Number of subsectors to generate and preview");
+                        ui.add_space(FIELD_SPACING);
+                    }
+                    ui.separator();
+                });
+                self.presets_row(ui);
+                ui.add_space(FIELD_SPACING);
+
+                ui.horizontal(|ui| {
+                    if ui.button("Generate").clicked() {
+                        let message = match self.batch_size {
+                            Some(count) => Message::ConfirmGenerateBatch {
+                                count,
+                                world_abundance_dm: self.world_abundance.into(),
+                                clustering: self.clustering,
+                                name_style: self.name_style,
+                                rule_set: self.rule_set,
+                            },
+                            None => Message::ConfirmRegenSubsector {
+                                world_abundance_dm: self.world_abundance.into(),
+                                clustering: self.clustering,
+                                name_style: self.name_style,
+                                rule_set: self.rule_set,
+                            },
+                        };
+                        self.message_tx.send(message);
+                        self.is_done = true;
+                    }
+
+                    ui.with_layout(Layout::right_to_left(), |ui| {
+                        if ui.button("Cancel").clicked() {
+                            self.message_tx.send(Message::NoOp);
+                            self.is_done = true;
+                        }
+                    });
+                });
+            });
+    }
+}
+
+/** Prompt for the name of a subsector, either to rename it or to name a new one. */
+struct SubsectorRenamePopup {
+    is_done: bool,
+    message_tx: pipe::Sender<Message>,
+    name: String,
+    /// Style of the names suggested by rerolling
+    name_style: NameStyle,
+    /// Creates the `Message` sent with the chosen name when confirmed
+    on_confirm: fn(String) -> Message,
+    title: &'static str,
+}
+
+impl SubsectorRenamePopup {
+    fn new(
+        title: &'static str,
+        initial_name: &str,
+        on_confirm: fn(String) -> Message,
+        message_tx: pipe::Sender<Message>,
+    ) -> Self {
+        Self {
+            is_done: false,
+            message_tx,
+            name: initial_name.to_string(),
+            name_style: NameStyle::default(),
+            on_confirm,
+            title,
+        }
+    }
+}
+
+impl Popup for SubsectorRenamePopup {
+    fn is_done(&self) -> bool {
+        self.is_done
+    }
+
+    fn show(&mut self, ctx: &Context) {
+        // Extra height for the name style selection
+        let popup_size = DEFAULT_POPUP_SIZE + vec2(0.0, 32.0);
+
+        Window::new(self.title)
+            .title_bar(false)
+            .resizable(false)
+            .fixed_size(popup_size)
+            .default_pos(center(ctx))
+            .show(ctx, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.heading(self.title);
+                    ui.separator();
+                    ui.add_space(FIELD_SPACING / 2.0);
+                    ui.add(TextEdit::singleline(&mut self.name).margin(vec2(16.0, 4.0)));
+                });
+                ui.add_space(FIELD_SPACING / 2.0);
+
+                ui.horizontal(|ui| {
+                    ComboBox::from_id_source("subsector_name_style")
+                        .selected_text(self.name_style.to_string())
+                        .show_ui(ui, |ui| {
+                            for style in NameStyle::ALL_VALUES {
+                                ui.selectable_value(&mut self.name_style, style, style.to_string());
+                            }
+                        });
+
+                    if ui
+                        .button(
+                            RichText::new(DICE_ICON).font(FontId::proportional(BUTTON_FONT_SIZE)),
+                        )
+                        .on_hover_text("Suggest a name in the selected style")
+                        .clicked()
+                    {
+                        self.name = random_subsector_name(self.name_style);
+                    }
+                });
+                ui.add_space(FIELD_SPACING);
+
+                ui.horizontal(|ui| {
+                    if ui.button("Confirm").clicked() {
+                        self.message_tx.send((self.on_confirm)(self.name.clone()));
+                        self.is_done = true;
+                    }
+
+                    ui.with_layout(Layout::right_to_left(), |ui| {
+                        if ui.button("Cancel").clicked() {
+                            self.message_tx.send(Message::NoOp);
+                            self.is_done = true;
+                        }
+                    });
+                });
+            });
+    }
+}
+
+/** Popup for editing errata and house-rule overrides of individual table rows.
+
+Edits to the shown row are stored in `overrides` whenever another row is chosen or the overrides are
+saved; only columns that differ from the tables without overrides are kept.
+*/
+struct TableOverridesPopup {
+    /// Code of the row being edited
+    code: u16,
+    /// Columns of the row being edited with their edited values
+    fields: Vec<(String, String)>,
+    is_done: bool,
+    message_tx: pipe::Sender<Message>,
+    overrides: TableOverrides,
+    table: TableKind,
+}
+
+impl TableOverridesPopup {
+    fn new(overrides: TableOverrides, message_tx: pipe::Sender<Message>) -> Self {
+        let mut popup = Self {
+            code: 0,
+            fields: Vec::new(),
+            is_done: false,
+            message_tx,
+            overrides,
+            table: TableKind::Governments,
+        };
+        popup.load_row();
+        popup
+    }
+
+    /** Fill the fields with the selected row, with its overrides merged in. */
+    fn load_row(&mut self) {
+        let mut fields = tables_without_overrides()
+            .row_fields(self.table, self.code)
+            .unwrap_or_default();
+        if let Some(overridden) = self.overrides.row(self.table, self.code) {
+            for (column, value) in fields.iter_mut() {
+                if let Some(new_value) = overridden.get(column) {
+                    *value = new_value.clone();
+                }
+            }
+        }
+        self.fields = fields;
+    }
+
+    /** Store the fields that differ from the tables without overrides as the selected row's
+    overrides.
+    */
+    fn store_row(&mut self) {
+        let original = tables_without_overrides()
+            .row_fields(self.table, self.code)
+            .unwrap_or_default();
+        let changed = self
+            .fields
+            .iter()
+            .zip(original)
+            .filter(|((_, value), (_, original))| value != original)
+            .map(|((column, value), _)| (column.clone(), value.clone()))
+            .collect();
+        self.overrides.set_row(self.table, self.code, changed);
+    }
+
+    /** Get the label of row `code` of `table`, e.g. "7: Balkanization". */
+    fn row_label(&self, table: TableKind, code: u16) -> String {
+        let fields = tables_without_overrides()
+            .row_fields(table, code)
+            .unwrap_or_default();
+        let overridden = self.overrides.row(table, code);
+        let name = fields
+            .first()
+            .map(|(column, value)| {
+                overridden
+                    .and_then(|overridden| overridden.get(column))
+                    .unwrap_or(value)
+                    .clone()
+            })
+            .unwrap_or_default();
+        let marker = if overridden.is_some() { "*" } else { "" };
+        format!("{}: {}{}", code, name, marker)
+    }
+}
+
+impl Popup for TableOverridesPopup {
+    fn is_done(&self) -> bool {
+        self.is_done
+    }
+
+    fn show(&mut self, ctx: &Context) {
+        const TITLE: &str = "Edit Table Rows";
+        // Extra room for the row selection and the columns of the row
+        let popup_size = DEFAULT_POPUP_SIZE + vec2(192.0, 160.0);
+
+        Window::new(TITLE)
+            .title_bar(false)
+            .resizable(false)
+            .fixed_size(popup_size)
+            .default_pos(center(ctx))
+            .show(ctx, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.heading(TITLE);
+                    ui.separator();
+                });
+                ui.label(
+                    "Changes apply to newly generated text; rows marked with * are overridden.",
+                );
+                ui.add_space(FIELD_SPACING / 2.0);
+
+                let mut table = self.table;
+                let mut code = self.code;
+                ui.horizontal(|ui| {
+                    ComboBox::from_id_source("table_override_table")
+                        .selected_text(table.to_string())
+                        .show_ui(ui, |ui| {
+                            for kind in TableKind::ALL_VALUES {
+                                if ui
+                                    .selectable_value(&mut table, kind, kind.to_string())
+                                    .clicked()
+                                {
+                                    code = 0;
+                                }
+                            }
+                        });
+
+                    ComboBox::from_id_source("table_override_row")
+                        .selected_text(self.row_label(table, code))
+                        .width(192.0)
+                        .show_ui(ui, |ui| {
+                            let row_count = tables_without_overrides().table_len(table);
+                            for row in 0..row_count as u16 {
+                                ui.selectable_value(&mut code, row, self.row_label(table, row));
+                            }
+                        });
+                });
+                if table != self.table || code != self.code {
+                    self.store_row();
+                    self.table = table;
+                    self.code = code;
+                    self.load_row();
+                }
+                ui.add_space(FIELD_SPACING / 2.0);
+
+                ScrollArea::vertical().max_height(160.0).show(ui, |ui| {
+                    Grid::new("table_override_fields")
+                        .num_columns(2)
+                        .spacing(vec2(FIELD_SPACING, LABEL_SPACING))
+                        .show(ui, |ui| {
+                            for (column, value) in self.fields.iter_mut() {
+                                ui.label(column_label(column));
+                                ui.add(TextEdit::singleline(value).desired_width(320.0));
+                                ui.end_row();
+                            }
+                        });
+                });
+                ui.add_space(FIELD_SPACING / 2.0);
+
+                let is_overridden = self.overrides.row(self.table, self.code).is_some();
+                if ui
+                    .add_enabled(is_overridden, Button::new("Revert Row"))
+                    .on_hover_text("Go back to the row as it is without overrides")
+                    .clicked()
+                {
+                    self.overrides
+                        .set_row(self.table, self.code, Default::default());
+                    self.load_row();
+                }
+                ui.add_space(FIELD_SPACING);
+
+                ui.horizontal(|ui| {
+                    if ui.button("Save").clicked() {
+                        self.store_row();
+                        self.message_tx.send(Message::ConfirmTableOverrides {
+                            overrides: self.overrides.clone(),
+                        });
+                        self.is_done = true;
+                    }
+
+                    ui.with_layout(Layout::right_to_left(), |ui| {
+                        if ui.button("Cancel").clicked() {
+                            self.message_tx.send(Message::NoOp);
+                            self.is_done = true;
+                        }
+                    });
+                });
+            });
+    }
+}
+
+/** Popup for pasting a list of worlds, one per line, to be placed on the map.
+
+The text is parsed again whenever it changes, so lines that can't be read are listed as they are
+typed or pasted.
+*/
+struct TextImportPopup {
+    is_done: bool,
+    message_tx: pipe::Sender<Message>,
+    /// Rule set the imported worlds are created with
+    rule_set: RuleSet,
+    text: String,
+    /// Worlds and errors parsed from `text`
+    world_list: WorldList,
+}
+
+impl TextImportPopup {
+    fn new(rule_set: RuleSet, message_tx: pipe::Sender<Message>) -> Self {
+        Self {
+            is_done: false,
+            message_tx,
+            rule_set,
+            text: String::new(),
+            world_list: WorldList::parse("", rule_set),
+        }
+    }
+}
+
+impl Popup for TextImportPopup {
+    fn is_done(&self) -> bool {
+        self.is_done
+    }
+
+    fn show(&mut self, ctx: &Context) {
+        const TITLE: &str = "Import from Text";
+        // Extra room for the pasted text and its parse errors
+        let popup_size = DEFAULT_POPUP_SIZE + vec2(224.0, 256.0);
+
+        Window::new(TITLE)
+            .title_bar(false)
+            .resizable(false)
+            .fixed_size(popup_size)
+            .default_pos(center(ctx))
+            .show(ctx, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.heading(TITLE);
+                    ui.separator();
+                });
+                ui.label(
+                    "Paste a UWP list or SEC file below with a hex, name, and UWP on each line.",
+                );
+                ui.add_space(FIELD_SPACING / 2.0);
+
+                ScrollArea::vertical()
+                    .id_source("text_import_text")
+                    .max_height(192.0)
+                    .show(ui, |ui| {
+                        let response = ui.add(
+                            TextEdit::multiline(&mut self.text)
+                                .code_editor()
+                                .desired_rows(10)
+                                .desired_width(f32::INFINITY)
+                                .hint_text("0101 Regina A788899-C"),
+                        );
+                        if response.changed() {
+                            self.world_list = WorldList::parse(&self.text, self.rule_set);
+                        }
+                    });
+                ui.add_space(FIELD_SPACING / 2.0);
+
+                ui.label(format!("{} worlds found", self.world_list.len()));
+                ScrollArea::vertical()
+                    .id_source("text_import_errors")
+                    .max_height(64.0)
+                    .show(ui, |ui| {
+                        for (line, error) in self.world_list.errors() {
+                            ui.colored_label(NEGATIVE_RED, format!("Line {}: {}", line, error));
+                        }
+                    });
+                ui.add_space(FIELD_SPACING);
+
+                ui.horizontal(|ui| {
+                    let import_button = Button::new("Import");
+                    if ui
+                        .add_enabled(!self.world_list.is_empty(), import_button)
+                        .clicked()
+                    {
+                        self.message_tx.send(Message::ConfirmImportText {
+                            subsector: self.world_list.to_subsector(),
+                        });
+                        self.is_done = true;
+                    }
+
+                    ui.with_layout(Layout::right_to_left(), |ui| {
+                        if ui.button("Cancel").clicked() {
+                            self.message_tx.send(Message::NoOp);
+                            self.is_done = true;
+                        }
+                    });
+                });
+            });
+    }
+}
+
+struct TravelCodePolicyPopup {
+    is_done: bool,
+    message_tx: pipe::Sender<Message>,
+    policy: TravelCodePolicy,
+    /// Copy of the subsector the policy would be applied to, for previewing which worlds change
+    subsector: Subsector,
+}
+
+impl TravelCodePolicyPopup {
+    fn new(subsector: Subsector, message_tx: pipe::Sender<Message>) -> Self {
+        Self {
+            is_done: false,
+            message_tx,
+            policy: TravelCodePolicy {
+                condition: TravelCodeCondition::LawLevelAtLeast(9),
+                travel_code: TravelCode::Amber,
+            },
+            subsector,
+        }
+    }
+}
+
+impl Popup for TravelCodePolicyPopup {
+    fn is_done(&self) -> bool {
+        self.is_done
+    }
+
+    fn show(&mut self, ctx: &Context) {
+        const TITLE: &str = "Set Travel Codes";
+        const TRAVEL_CODES: [TravelCode; 3] =
+            [TravelCode::Safe, TravelCode::Amber, TravelCode::Red];
+
+        Window::new(TITLE)
+            .title_bar(false)
+            .resizable(false)
+            .default_pos(center(ctx))
+            .show(ctx, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.heading(TITLE);
+                });
+                ui.separator();
+
+                let max_law_level = TABLES.law_table.len() as u16 - 1;
+                Grid::new("travel_code_policy_grid")
+                    .num_columns(2)
+                    .spacing(vec2(FIELD_SPACING, LABEL_SPACING))
+                    .show(ui, |ui| {
+                        ui.label(RichText::new("Worlds").font(LABEL_FONT).color(LABEL_COLOR));
+                        ui.horizontal(|ui| {
+                            let conditions = [
+                                (
+                                    TravelCodeCondition::LawLevelAtLeast(9),
+                                    "Law Level At Least",
+                                ),
+                                (TravelCodeCondition::PirateBase, "With a Pirate Base"),
+                                (
+                                    TravelCodeCondition::TravelCode(TravelCode::Red),
+                                    "With Travel Code",
+                                ),
+                                (TravelCodeCondition::All, "All Worlds"),
+                            ];
+                            let selected = conditions
+                                .iter()
+                                .find(|(condition, _)| {
+                                    mem::discriminant(condition)
+                                        == mem::discriminant(&self.policy.condition)
+                                })
+                                .map_or("", |(_, label)| label);
+                            ComboBox::from_id_source("travel_code_condition")
+                                .selected_text(selected)
+                                .show_ui(ui, |ui| {
+                                    for (condition, label) in conditions {
+                                        let is_selected = mem::discriminant(&condition)
+                                            == mem::discriminant(&self.policy.condition);
+                                        if ui.selectable_label(is_selected, label).clicked()
+                                            && !is_selected
+                                        {
+                                            self.policy.condition = condition;
+                                        }
+                                    }
+                                });
+
+                            match &mut self.policy.condition {
+                                TravelCodeCondition::LawLevelAtLeast(law_level) => {
+                                    ui.add(
+                                        DragValue::new(law_level).clamp_range(0..=max_law_level),
+                                    );
+                                }
+                                TravelCodeCondition::TravelCode(travel_code) => {
+                                    ComboBox::from_id_source("travel_code_condition_code")
+                                        .selected_text(format!("{:?}", travel_code))
+                                        .show_ui(ui, |ui| {
+                                            for code in TRAVEL_CODES {
+                                                ui.selectable_value(
+                                                    travel_code,
+                                                    code,
+                                                    format!("{:?}", code),
+                                                );
+                                            }
+                                        });
+                                }
+                                TravelCodeCondition::All | TravelCodeCondition::PirateBase => (),
+                            }
+                        });
+                        ui.end_row();
+
+                        ui.label(
+                            RichText::new("New Travel Code")
+                                .font(LABEL_FONT)
+                                .color(LABEL_COLOR),
+                        );
+                        ui.horizontal(|ui| {
+                            for code in TRAVEL_CODES {
+                                ui.radio_value(
+                                    &mut self.policy.travel_code,
+                                    code,
+                                    format!("{:?}", code),
+                                );
+                            }
+                        });
+                        ui.end_row();
+                    });
+                ui.add_space(FIELD_SPACING / 2.0);
+
+                let targets = self.subsector.travel_code_policy_targets(&self.policy);
+                ui.label(format!(
+                    "{}: {} world(s) will change.",
+                    self.policy,
+                    targets.len()
+                ));
+                ScrollArea::vertical().max_height(160.0).show(ui, |ui| {
+                    for point in targets.iter() {
+                        if let Some(world) = self.subsector.get_world(point) {
+                            ui.label(format!(
+                                "{} {}: {:?} -> {:?}",
+                                point, world.name, world.travel_code, self.policy.travel_code
+                            ));
+                        }
+                    }
+                });
+                ui.add_space(FIELD_SPACING);
+
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(!targets.is_empty(), Button::new("Apply"))
+                        .clicked()
+                    {
+                        self.message_tx.send(Message::ConfirmSetTravelCodes {
+                            policy: self.policy,
+                        });
+                        self.is_done = true;
+                    }
+
+                    ui.with_layout(Layout::right_to_left(), |ui| {
+                        if ui.button("Cancel").clicked() {
+                            self.message_tx.send(Message::NoOp);
+                            self.is_done = true;
+                        }
+                    });
+                });
+            });
+    }
+}
+
+struct WorldRegenPopup {
+    /// Whether the new world is biased toward its neighbors instead of rolled independently
+    contextual: bool,
+    is_done: bool,
+    message_tx: pipe::Sender<Message>,
+    /// How strongly a contextual world follows its neighbors, in percent
+    weighting: u8,
+    world_name: String,
+}
+
+impl WorldRegenPopup {
+    fn new(world_name: &str, message_tx: pipe::Sender<Message>) -> Self {
+        Self {
+            contextual: false,
+            is_done: false,
+            message_tx,
+            weighting: 50,
+            world_name: world_name.to_string(),
+        }
+    }
+}
+
+impl Popup for WorldRegenPopup {
+    fn is_done(&self) -> bool {
+        self.is_done
+    }
+
+    fn show(&mut self, ctx: &Context) {
+        const TITLE: &str = "Regenerating World";
+        // Extra height for the mode and weighting selections
+        let popup_size = DEFAULT_POPUP_SIZE + vec2(0.0, 64.0);
+
+        Window::new(TITLE)
+            .title_bar(false)
+            .resizable(false)
+            .fixed_size(popup_size)
+            .default_pos(center(ctx))
+            .show(ctx, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.heading(TITLE);
+                    ui.separator();
+                    ui.add_space(FIELD_SPACING / 2.0);
+                    ui.label(format!(
+                        "Do you want to completely regenerate '{}'? This can not be undone.",
+                        self.world_name
+                    ));
+                });
+                ui.add_space(FIELD_SPACING / 2.0);
+
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut self.contextual, false, "Independent")
+                        .on_hover_text("Roll every characteristic without regard to other worlds");
+                    ui.radio_value(&mut self.contextual, true, "Contextual")
+                        .on_hover_text(
+                            "Bias the tech level and allegiance toward the worlds within Jump-2",
+                        );
+                });
+                ui.add_enabled_ui(self.contextual, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new("Weighting")
+                                .font(LABEL_FONT)
+                                .color(LABEL_COLOR),
+                        );
+                        ui.add(Slider::new(&mut self.weighting, 1..=100).suffix("%"))
+                            .on_hover_text("How strongly the new world follows its neighbors");
+                    });
+                });
+                ui.add_space(FIELD_SPACING);
+
+                ui.horizontal(|ui| {
+                    if ui.button("Confirm").clicked() {
+                        let weighting = if self.contextual { self.weighting } else { 0 };
+                        self.message_tx
+                            .send(Message::ConfirmRegenWorld { weighting });
+                        self.is_done = true;
+                    }
+
+                    ui.with_layout(Layout::right_to_left(), |ui| {
+                        if ui.button("Cancel").clicked() {
+                            self.message_tx.send(Message::NoOp);
+                            self.is_done = true;
+                        }
+                    });
+                });
+            });
+    }
+}
+
+struct WorldSheetExportPopup {
+    format: SheetFormat,
+    is_done: bool,
+    message_tx: pipe::Sender<Message>,
+    /// Whether a picture of each world's surface is included in its sheet
+    thumbnail: bool,
+}
+
+impl WorldSheetExportPopup {
+    fn new(message_tx: pipe::Sender<Message>) -> Self {
+        Self {
+            format: SheetFormat::Markdown,
+            is_done: false,
+            message_tx,
+            thumbnail: true,
+        }
+    }
+}
+
+impl Popup for WorldSheetExportPopup {
+    fn is_done(&self) -> bool {
+        self.is_done
+    }
+
+    fn show(&mut self, ctx: &Context) {
+        const TITLE: &str = "Export All World Sheets";
+        // Extra height for the thumbnail checkbox
+        let popup_size = DEFAULT_POPUP_SIZE + vec2(0.0, 24.0);
+
+        Window::new(TITLE)
+            .title_bar(false)
+            .resizable(false)
+            .fixed_size(popup_size)
+            .default_pos(center(ctx))
+            .show(ctx, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.heading(TITLE);
+                    ui.separator();
+                });
+                ui.add_space(FIELD_SPACING / 2.0);
+
+                ui.label(RichText::new("Format").font(LABEL_FONT).color(LABEL_COLOR));
+                ui.horizontal(|ui| {
+                    for format in SheetFormat::ALL_VALUES {
+                        ui.radio_value(&mut self.format, format, format.to_string());
+                    }
+                });
+                ui.checkbox(&mut self.thumbnail, "Include surface thumbnail");
+                ui.add_space(LABEL_SPACING);
+                ui.label("One file per world is written to the folder chosen next.");
+                ui.add_space(FIELD_SPACING);
+
+                ui.horizontal(|ui| {
+                    if ui.button("Choose Folder...").clicked() {
+                        self.message_tx.send(Message::ConfirmExportAllWorldSheets {
+                            format: self.format,
+                            thumbnail: self.thumbnail,
+                        });
+                        self.is_done = true;
+                    }
+
+                    ui.with_layout(Layout::right_to_left(), |ui| {
+                        if ui.button("Cancel").clicked() {
+                            self.message_tx.send(Message::NoOp);
+                            self.is_done = true;
+                        }
+                    });
+                });
+            });
+    }
+}
+
+/// Calculate and return the centered position of a default-sized popup for a given `Context`.
+#[inline]
+fn center(ctx: &Context) -> Pos2 {
+    ctx.available_rect().center() - DEFAULT_POPUP_SIZE / 2.0
+}
+
+/// Turn the name of a table column into a label, e.g. "berthing_cost" into "Berthing Cost".
+fn column_label(column: &str) -> String {
+    column
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
 }
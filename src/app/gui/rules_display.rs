@@ -0,0 +1,111 @@
+use egui::{CollapsingHeader, Grid, RichText, ScrollArea, TextEdit, Ui};
+use swt_gen_core::{rule_entry, search_rules, RuleEntry, RuleTopic};
+
+use crate::app::{
+    gui::{FIELD_SPACING, LABEL_COLOR, LABEL_FONT, LABEL_SPACING},
+    GeneratorApp,
+};
+
+/// Widest the rules text of a single entry is allowed to get before wrapping
+const RULE_TEXT_WIDTH: f32 = 320.0;
+
+impl GeneratorApp {
+    /** Tab for searching the rules text of every starport class, trade code, and travel code.
+
+    Entries are grouped by topic; those of codes the selected world has are listed open.
+    */
+    pub(crate) fn rules_display(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("Search").font(LABEL_FONT).color(LABEL_COLOR));
+            ui.add(TextEdit::singleline(&mut self.rules_search).hint_text("e.g. berthing"));
+        });
+        ui.add_space(LABEL_SPACING);
+
+        let entries = search_rules(&self.rules_search);
+        if entries.is_empty() {
+            ui.label("No rules mention that.");
+            return;
+        }
+
+        ScrollArea::vertical()
+            .id_source("rules_reference")
+            .max_height(ui.available_height() * 0.9)
+            .show(ui, |ui| {
+                for topic in RuleTopic::ALL_VALUES {
+                    let topic_entries: Vec<_> = entries
+                        .iter()
+                        .filter(|entry| entry.topic == topic)
+                        .collect();
+                    if topic_entries.is_empty() {
+                        continue;
+                    }
+
+                    ui.heading(topic.to_string());
+                    for entry in topic_entries {
+                        let world_has_code = self.world_selected && self.world_has_code(entry);
+                        CollapsingHeader::new(&entry.title)
+                            .id_source(("rules_reference_entry", entry.topic, &entry.key))
+                            .default_open(world_has_code)
+                            .show(ui, |ui| rule_entry_ui(ui, entry));
+                    }
+                    ui.add_space(LABEL_SPACING);
+                }
+            });
+    }
+
+    /** Whether the selected world has the starport class, trade code, or travel code of `entry`. */
+    fn world_has_code(&self, entry: &RuleEntry) -> bool {
+        match entry.topic {
+            RuleTopic::StarportClass => self.world.starport.class.to_string() == entry.key,
+            RuleTopic::TradeCode => self
+                .world
+                .trade_codes
+                .iter()
+                .any(|trade_code| format!("{:?}", trade_code) == entry.key),
+            RuleTopic::TravelCode => format!("{:?}", self.world.travel_code) == entry.key,
+        }
+    }
+}
+
+/** Show the rules text of `entry`, along with the trade DMs its code gives, if any. */
+fn rule_entry_ui(ui: &mut Ui, entry: &RuleEntry) {
+    ui.set_max_width(RULE_TEXT_WIDTH);
+    ui.label(&entry.text);
+
+    let dms = entry.trade_dms();
+    if dms.is_empty() {
+        return;
+    }
+    ui.add_space(LABEL_SPACING);
+
+    let dm_str = |dm: Option<i32>| dm.map_or_else(|| "-".to_string(), |dm| format!("{:+}", dm));
+    Grid::new(("rule_trade_dms", entry.topic, &entry.key))
+        .spacing([FIELD_SPACING, 0.0])
+        .striped(true)
+        .show(ui, |ui| {
+            for header in ["Good", "Purchase DM", "Sale DM"] {
+                ui.label(RichText::new(header).font(LABEL_FONT).color(LABEL_COLOR));
+            }
+            ui.end_row();
+
+            for (good, purchase_dm, sale_dm) in dms {
+                ui.label(good);
+                ui.label(dm_str(purchase_dm));
+                ui.label(dm_str(sale_dm));
+                ui.end_row();
+            }
+        });
+}
+
+/** Show the title and rules text of the code `key` of `topic` in a hover panel. */
+pub(crate) fn rule_hover_ui(ui: &mut Ui, topic: RuleTopic, key: &str) {
+    match rule_entry(topic, key) {
+        Some(entry) => {
+            ui.label(RichText::new(&entry.title).strong());
+            rule_entry_ui(ui, entry);
+        }
+        None => {
+            ui.label(key);
+        }
+    }
+}
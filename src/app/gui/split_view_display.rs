@@ -0,0 +1,158 @@
+use std::mem;
+
+use egui::{ComboBox, RichText, Ui};
+use egui_extras::RetainedImage;
+use swt_gen_core::{Point, World};
+
+use crate::app::{
+    gui::{BufferedField, TabLabel, FIELD_SELECTION_WIDTH, LABEL_COLOR, LABEL_FONT, LABEL_SPACING},
+    GeneratorApp, Message,
+};
+
+/** Second, read-only `World` selection shown next to the selected `World` in split view.
+
+Holds the same display state that `GeneratorApp` keeps for the selected `World` so that the two can
+be swapped and the compared `World` rendered with the regular world data display.
+*/
+pub(crate) struct ComparedWorld {
+    belt_buffer: BufferedField<i32>,
+    berthing_cost_buffer: BufferedField<u32>,
+    diameter_buffer: BufferedField<u32>,
+    faction_idx: usize,
+    gas_giant_buffer: BufferedField<i32>,
+    point: Point,
+    point_buffer: BufferedField<Point>,
+    tab: TabLabel,
+    world: World,
+    world_image: Option<RetainedImage>,
+}
+
+impl ComparedWorld {
+    /** Create a compared `World` at `point`, reusing `app`'s buffers so that they behave the same
+    way while swapped in.
+    */
+    pub(crate) fn new(
+        app: &GeneratorApp,
+        point: Point,
+        world_image: Option<RetainedImage>,
+    ) -> Self {
+        let mut point_buffer = app.point_buffer.clone();
+        point_buffer.reset(&point);
+        Self {
+            belt_buffer: app.belt_buffer.clone(),
+            berthing_cost_buffer: app.berthing_cost_buffer.clone(),
+            diameter_buffer: app.diameter_buffer.clone(),
+            faction_idx: 0,
+            gas_giant_buffer: app.gas_giant_buffer.clone(),
+            point,
+            point_buffer,
+            tab: TabLabel::WorldSurvey,
+            world: World::empty(),
+            world_image,
+        }
+    }
+
+    pub(crate) fn point(&self) -> Point {
+        self.point
+    }
+
+    /** Update the compared `World` and its buffers from the `World` currently at its `Point`. */
+    fn refresh(&mut self, world: &World) {
+        self.world = world.clone();
+        self.berthing_cost_buffer
+            .reset(&world.starport.berthing_cost);
+        self.diameter_buffer.reset(&world.diameter);
+        self.gas_giant_buffer.reset(&world.gas_giants);
+        self.belt_buffer.reset(&world.planetoid_belts.unwrap_or(0));
+    }
+}
+
+impl GeneratorApp {
+    /** Display the compared `World` with the read-only variant of the world data display.
+
+    Above it is a selector for which `World` of the `Subsector` to compare against.
+    */
+    fn compared_world_display(&mut self, ui: &mut Ui) {
+        ui.vertical(|ui| {
+            ui.label(
+                RichText::new("Compare With")
+                    .font(LABEL_FONT)
+                    .color(LABEL_COLOR),
+            );
+            ui.add_space(LABEL_SPACING);
+
+            let selected_text = match &self.compared_world {
+                Some(compared) => match self.subsector.get_world(&compared.point()) {
+                    Some(world) => format!("{} {}", compared.point(), world.name),
+                    None => compared.point().to_string(),
+                },
+                None => String::new(),
+            };
+            ComboBox::from_id_source("compared_world_selection")
+                .selected_text(selected_text)
+                .width(FIELD_SELECTION_WIDTH)
+                .show_ui(ui, |ui| {
+                    for (point, world) in self.subsector.get_map() {
+                        let text = format!("{} {}", point, world.name);
+                        if ui.selectable_label(false, text).clicked() {
+                            self.message_tx
+                                .send(Message::CompareWorld { point: *point });
+                        }
+                    }
+                });
+            ui.separator();
+
+            let mut compared = match self.compared_world.take() {
+                Some(compared) => compared,
+                None => {
+                    ui.label("Right-click a world on the map or select one above to compare it.");
+                    return;
+                }
+            };
+
+            match self.subsector.get_world(&compared.point) {
+                Some(world) => {
+                    compared.refresh(world);
+                    self.swap_compared_world(&mut compared);
+                    ui.push_id("compared_world", |ui| self.read_only_world_data_display(ui));
+                    self.swap_compared_world(&mut compared);
+                }
+                None => {
+                    ui.label(format!("There is no longer a world at {}.", compared.point));
+                }
+            }
+
+            self.compared_world = Some(compared);
+        });
+    }
+
+    /** Display the selected and compared `World`s side by side in place of the subsector map. */
+    pub(crate) fn split_view_display(&mut self, ui: &mut Ui) {
+        ui.columns(2, |columns| {
+            if self.point_selected && self.world_selected {
+                self.world_data_display(&mut columns[0]);
+            } else {
+                columns[0]
+                    .label("Turn off split view and select a world on the map to display it here.");
+            }
+            self.compared_world_display(&mut columns[1]);
+        });
+    }
+
+    /** Swap the display state of the selected `World` with that of `compared`. */
+    fn swap_compared_world(&mut self, compared: &mut ComparedWorld) {
+        mem::swap(&mut self.belt_buffer, &mut compared.belt_buffer);
+        mem::swap(
+            &mut self.berthing_cost_buffer,
+            &mut compared.berthing_cost_buffer,
+        );
+        mem::swap(&mut self.diameter_buffer, &mut compared.diameter_buffer);
+        mem::swap(&mut self.faction_idx, &mut compared.faction_idx);
+        mem::swap(&mut self.gas_giant_buffer, &mut compared.gas_giant_buffer);
+        mem::swap(&mut self.point, &mut compared.point);
+        mem::swap(&mut self.point_buffer, &mut compared.point_buffer);
+        mem::swap(&mut self.tab, &mut compared.tab);
+        mem::swap(&mut self.world, &mut compared.world);
+        mem::swap(&mut self.world_image, &mut compared.world_image);
+    }
+}
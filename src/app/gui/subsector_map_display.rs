@@ -1,15 +1,25 @@
-use eframe::epaint::{CircleShape, QuadraticBezierShape, TextShape};
+use std::fmt;
+
+use eframe::epaint::{CircleShape, PathShape, QuadraticBezierShape, TextShape};
 use egui::{
-    vec2, Color32, ColorImage, Context, FontId, Image, Pos2, Rect, Sense, Shape, Stroke, Ui, Vec2,
+    pos2, vec2, Align2, Area, Button, CollapsingHeader, Color32, ColorImage, ComboBox, Context,
+    FontId, Frame, Grid, Key, Painter, Pos2, Rect, Response, RichText, Sense, Shape, Stroke,
+    TextEdit, TextureId, Ui, Vec2,
 };
 use egui_extras::RetainedImage;
-
-use crate::{
-    app::{GeneratorApp, Message},
-    astrography::{Point, Subsector, World, CENTER_MARKERS},
+use swt_gen_core::{
+    Annotation, AnnotationColor, AnnotationShape, HazardSeverity, LegendEntry, LegendSymbol,
+    MapOptions, NameLayout, Point, Subsector, Translation, World, WorldLabels, WorldSubtype,
+    CENTER_MARKERS,
 };
 
-const SUBSECTOR_IMAGE_MIN_SIZE: Vec2 = vec2(1584.0, 834.0);
+use crate::app::{
+    gui::{
+        BOOKMARK_ICON, FIELD_SELECTION_WIDTH, FIELD_SPACING, LABEL_COLOR, LABEL_FONT,
+        LABEL_SPACING, WARNING_ICON, X_ICON,
+    },
+    GeneratorApp, Message,
+};
 
 // SVG document dimensions in inches
 const SVG_WIDTH: f32 = 8.5;
@@ -27,7 +37,6 @@ const TOP_MARGIN: f32 = 0.50;
 const BOTTOM_MARGIN: f32 = 1.11;
 
 // Hex dimensions in inches
-#[allow(dead_code)]
 const HEX_LONG_RADIUS: f32 = 0.52;
 #[allow(dead_code)]
 const HEX_LONG_DIAMETER: f32 = HEX_LONG_RADIUS * 2.0;
@@ -37,39 +46,543 @@ const HEX_SHORT_DIAMETER: f32 = HEX_SHORT_RADIUS * 2.0;
 
 const WORLD_FONT_ID: FontId = FontId::proportional(13.0);
 
+const SELECTED_HEX_COLOR: Color32 = Color32::from_rgb(0x1e, 0x78, 0xd2);
+const SEARCH_RESULT_COLOR: Color32 = Color32::from_rgb(0xff, 0xc8, 0x00);
+const FILTERED_HEX_COLOR: Color32 = Color32::from_rgba_premultiplied(0x30, 0x60, 0x30, 0x30);
+const JUMP_RANGE_COLOR: Color32 = Color32::from_rgba_premultiplied(0x10, 0x30, 0x60, 0x40);
+const ROUTE_PREVIEW_COLOR: Color32 = Color32::from_rgb(0x2e, 0x8b, 0x57);
+const OWNERSHIP_ARROW_COLOR: Color32 = Color32::from_rgb(0x80, 0x40, 0xa0);
+const UNSAVED_HEX_COLOR: Color32 = Color32::from_rgb(0xe0, 0x70, 0x20);
+const MEASUREMENT_COLOR: Color32 = Color32::from_rgb(0xc0, 0x20, 0x80);
+const BOOKMARK_COLOR: Color32 = Color32::from_rgb(0xff, 0xd7, 0x00);
+const XBOAT_ROUTE_COLOR: Color32 = Color32::from_rgb(0xa0, 0x5a, 0x2c);
+const AMBER_ZONE_COLOR: Color32 = Color32::from_rgb(0xff, 0xbf, 0x00);
+const RED_ZONE_COLOR: Color32 = Color32::from_rgb(0xd4, 0x00, 0x00);
+/// Colors of the hazard warning icons, from least to most severe
+const HAZARD_COLORS: [Color32; 3] = [
+    Color32::from_rgb(0xc8, 0xa0, 0x00),
+    Color32::from_rgb(0xe0, 0x70, 0x20),
+    Color32::from_rgb(0xd4, 0x00, 0x00),
+];
+/// Size of the space each symbol is drawn in on the map legend
+const LEGEND_SYMBOL_SIZE: Vec2 = vec2(24.0, 14.0);
+/// Seconds for the glow around search results to fade out and back in
+const GLOW_PERIOD: f64 = 1.5;
+/// Width in pixels of the map previews of generated batch candidates
+const MAP_PREVIEW_WIDTH: u32 = 192;
+/// Furthest the map can be zoomed in, as a multiple of the size that fits the map panel
+const MAX_MAP_ZOOM: f32 = 4.0;
+/// How much each press of a zoom button zooms the map in or out
+const MAP_ZOOM_STEP: f32 = 1.25;
+/// Pixels of mouse wheel scrolling that zoom the map in or out by a factor of e
+const MAP_SCROLL_ZOOM_PIXELS: f32 = 200.0;
+/// Size of the overview mini-map shown while zoomed in, as a fraction of the map panel's size
+const MINI_MAP_SCALE: f32 = 0.2;
+/// Width in pixels of the lines of annotations drawn on the map
+const ANNOTATION_LINE_WIDTH: f32 = 2.5;
+/// Shortest distance in SVG userspace units between the points of a freehand annotation
+const FREEHAND_STEP: f64 = 0.75;
+
+/** Something drawn over the subsector map to call attention to one or more hexes.
+
+Highlights are rebuilt from the app's state every frame by [`GeneratorApp::map_highlights`], so
+features only need to add their highlights there for them to show up on the map.
+*/
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum HexHighlight {
+    /// Outline around the selected hex
+    Selected(Point),
+    /// Pulsing glow around a world matching the map search
+    SearchResult(Point),
+    /// Shading of a hex whose world passes the map's polity filter
+    Filtered(Point),
+    /// Shading of a hex in jump range of the selected hex, fainter the more jumps it takes
+    JumpRange(Point, u32),
+    /// Line through the stops of a route, in order
+    RoutePreview(Vec<Point>),
+    /// Dashed outline around a hex whose world changed since the subsector was last saved
+    Unsaved(Point),
+}
+
+/** Tool that annotations are drawn on the subsector map with. */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum AnnotationTool {
+    /// Drag to draw a line that follows the pointer
+    Freehand,
+    /// Drag from the tail of an arrow to its tip
+    Arrow,
+    /// Drag from the center of a circle out to its edge
+    Circle,
+    /// Click to write the annotation text on the map
+    Text,
+}
+
+impl AnnotationTool {
+    const ALL_VALUES: [AnnotationTool; 4] = [Self::Freehand, Self::Arrow, Self::Circle, Self::Text];
+}
+
+impl fmt::Display for AnnotationTool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Freehand => "Freehand",
+            Self::Arrow => "Arrow",
+            Self::Circle => "Circle",
+            Self::Text => "Text",
+        };
+        write!(f, "{}", s)
+    }
+}
+
 enum ClickKind {
     Hex(Point),
     SubsectorName,
     None,
 }
 
+/** How far the subsector map is zoomed in and which part of it is in view.
+
+Positions on the map are kept as fractions of its width and height, so the view stays put when the
+map panel is resized.
+*/
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct MapView {
+    /// Multiple of the size that fits the map panel, from 1 for the whole map up to `MAX_MAP_ZOOM`
+    zoom: f32,
+    /// Point of the map at the center of the view
+    center: Pos2,
+}
+
+impl Default for MapView {
+    fn default() -> Self {
+        Self {
+            zoom: 1.0,
+            center: pos2(0.5, 0.5),
+        }
+    }
+}
+
+impl MapView {
+    fn is_zoomed(&self) -> bool {
+        self.zoom > 1.0
+    }
+
+    /** Get where the whole map is drawn for the part in view to fill `view_rect`. */
+    fn map_rect(&self, view_rect: &Rect) -> Rect {
+        let size = view_rect.size() * self.zoom;
+        let min = view_rect.center() - vec2(self.center.x * size.x, self.center.y * size.y);
+        Rect::from_min_size(min, size)
+    }
+
+    /** Get the part of the map in view, as fractions of its width and height. */
+    fn visible_fraction(&self) -> Rect {
+        Rect::from_center_size(self.center, Vec2::splat(1.0 / self.zoom))
+    }
+
+    /** Center the view on `center`, or as near to it as it gets without leaving the map. */
+    fn center_on(&mut self, center: Pos2) {
+        let margin = 0.5 / self.zoom;
+        self.center = pos2(
+            center.x.clamp(margin, 1.0 - margin),
+            center.y.clamp(margin, 1.0 - margin),
+        );
+    }
+
+    /** Move the view as if the map was dragged `delta` pixels across `view_rect`. */
+    fn pan(&mut self, delta: Vec2, view_rect: &Rect) {
+        let size = view_rect.size() * self.zoom;
+        self.center_on(self.center - vec2(delta.x / size.x, delta.y / size.y));
+    }
+
+    /** Zoom in by `factor`, or out if it's less than 1, keeping the part of the map under `anchor`
+    where it is in `view_rect`.
+    */
+    fn zoom_by(&mut self, factor: f32, anchor: Pos2, view_rect: &Rect) {
+        let map_rect = self.map_rect(view_rect);
+        let anchored = pos2(
+            (anchor.x - map_rect.left()) / map_rect.width(),
+            (anchor.y - map_rect.top()) / map_rect.height(),
+        );
+
+        self.zoom = (self.zoom * factor).clamp(1.0, MAX_MAP_ZOOM);
+        let size = view_rect.size() * self.zoom;
+        let offset = anchor - view_rect.center();
+        self.center_on(anchored - vec2(offset.x / size.x, offset.y / size.y));
+    }
+
+    /** Zoom in by `factor`, or out if it's less than 1, keeping the center of the view in place. */
+    fn zoom_centered(&mut self, factor: f32) {
+        self.zoom = (self.zoom * factor).clamp(1.0, MAX_MAP_ZOOM);
+        self.center_on(self.center);
+    }
+}
+
 impl GeneratorApp {
-    /** Displays a map of the [`Subsector`] and handles any mouse clicks on it. */
+    /** Collect everything that should currently be highlighted on the subsector map.
+
+    Highlights are listed in the order they are drawn, so later ones are drawn on top.
+    */
+    fn map_highlights(&self) -> Vec<HexHighlight> {
+        let mut highlights = Vec::new();
+
+        if self.show_jump_range && self.point_selected {
+            highlights.extend(
+                self.jump_range()
+                    .into_iter()
+                    .map(|(point, jumps)| HexHighlight::JumpRange(point, jumps)),
+            );
+        }
+
+        if let Some(code) = &self.polity_filter {
+            highlights.extend(
+                self.subsector
+                    .polity_members(code)
+                    .into_iter()
+                    .map(HexHighlight::Filtered),
+            );
+        }
+
+        if self.show_unsaved_worlds {
+            highlights.extend(
+                self.unsaved_worlds()
+                    .into_iter()
+                    .map(|(point, _)| HexHighlight::Unsaved(point)),
+            );
+        }
+
+        if self.show_travel_planner && self.travel_route.len() > 1 {
+            highlights.push(HexHighlight::RoutePreview(self.travel_route.clone()));
+        }
+
+        highlights.extend(
+            self.subsector
+                .search_worlds(&self.map_search)
+                .into_iter()
+                .map(HexHighlight::SearchResult),
+        );
+
+        if self.point_selected {
+            highlights.push(HexHighlight::Selected(self.point));
+        }
+
+        highlights
+    }
+
+    /** Display the search field and polity filter that pick which hexes are highlighted. */
+    fn map_highlight_controls(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("Search").font(LABEL_FONT).color(LABEL_COLOR));
+            ui.add(
+                TextEdit::singleline(&mut self.map_search)
+                    .hint_text("Name, UWP, or hex")
+                    .desired_width(FIELD_SELECTION_WIDTH),
+            );
+            if !self.map_search.is_empty() && ui.button(X_ICON).clicked() {
+                self.map_search.clear();
+            }
+            ui.add_space(FIELD_SPACING);
+
+            if ui
+                .toggle_value(&mut self.measure_mode, "Measure")
+                .on_hover_text("Drag between hexes to measure the distance between them")
+                .changed()
+            {
+                self.annotation_tool = None;
+                self.annotation_draft = None;
+            }
+            ui.add_space(FIELD_SPACING);
+
+            let zoom_hint = "Scroll over the map to zoom, and drag it around while zoomed in";
+            if ui.button("-").on_hover_text(zoom_hint).clicked() {
+                self.map_view.zoom_centered(1.0 / MAP_ZOOM_STEP);
+            }
+            if ui.button("+").on_hover_text(zoom_hint).clicked() {
+                self.map_view.zoom_centered(MAP_ZOOM_STEP);
+            }
+            if ui
+                .add_enabled(self.map_view.is_zoomed(), Button::new("Fit"))
+                .on_hover_text("Zoom out to the whole subsector")
+                .clicked()
+            {
+                self.map_view = MapView::default();
+            }
+            ui.add_space(FIELD_SPACING);
+
+            if self.hides_spoilers() {
+                self.show_hazards = false;
+            } else {
+                ui.toggle_value(&mut self.show_hazards, format!("{} Hazards", WARNING_ICON))
+                    .on_hover_text("Mark worlds by the worst stellar hazard in their system");
+                ui.add_space(FIELD_SPACING);
+            }
+
+            ComboBox::from_id_source("map_world_labels_combo")
+                .selected_text(self.settings.map_world_labels.to_string())
+                .show_ui(ui, |ui| {
+                    for labels in WorldLabels::ALL_VALUES {
+                        ui.selectable_value(
+                            &mut self.settings.map_world_labels,
+                            labels,
+                            labels.to_string(),
+                        );
+                    }
+                })
+                .response
+                .on_hover_text("Labels drawn next to each world");
+            if ui
+                .checkbox(&mut self.settings.map_hex_numbers, "Hex Numbers")
+                .on_hover_text("Number every hex of the map, e.g. 0203")
+                .changed()
+            {
+                // The numbers are part of the grid image, so it has to be drawn again
+                self.map_redraw.request();
+            }
+            ui.add_space(FIELD_SPACING);
+
+            if self.hides_spoilers() || self.subsector.polities().is_empty() {
+                self.polity_filter = None;
+                return;
+            }
+
+            ui.label(RichText::new("Polity").font(LABEL_FONT).color(LABEL_COLOR));
+            let selected_text = match &self.polity_filter {
+                Some(code) => code.clone(),
+                None => "None".to_string(),
+            };
+            ComboBox::from_id_source("map_polity_filter_combo")
+                .selected_text(selected_text)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.polity_filter, None, "None");
+                    for polity in self.subsector.polities() {
+                        ui.selectable_value(
+                            &mut self.polity_filter,
+                            Some(polity.code.clone()),
+                            format!("{} ({})", polity.name, polity.code),
+                        );
+                    }
+                });
+        });
+    }
+
+    /** Display the tools for drawing annotations over the map, and for undoing or clearing them. */
+    fn map_annotation_controls(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("Draw").font(LABEL_FONT).color(LABEL_COLOR));
+            for tool in AnnotationTool::ALL_VALUES {
+                let selected = self.annotation_tool == Some(tool);
+                if ui.selectable_label(selected, tool.to_string()).clicked() {
+                    self.annotation_tool = if selected { None } else { Some(tool) };
+                    self.annotation_draft = None;
+                    self.measure_mode = false;
+                }
+            }
+            if self.annotation_tool == Some(AnnotationTool::Text) {
+                ui.add(
+                    TextEdit::singleline(&mut self.annotation_text)
+                        .hint_text("Click the map to place")
+                        .desired_width(FIELD_SELECTION_WIDTH),
+                );
+            }
+
+            ComboBox::from_id_source("map_annotation_color_combo")
+                .selected_text(self.annotation_color.to_string())
+                .show_ui(ui, |ui| {
+                    for color in AnnotationColor::ALL_VALUES {
+                        ui.selectable_value(&mut self.annotation_color, color, color.to_string());
+                    }
+                })
+                .response
+                .on_hover_text("Color of new annotations");
+            ui.add_space(FIELD_SPACING);
+
+            let annotated = !self.subsector.annotations().is_empty();
+            if ui
+                .add_enabled(annotated, Button::new("Undo"))
+                .on_hover_text("Remove the last annotation drawn")
+                .clicked()
+            {
+                self.message(Message::RemoveLastAnnotation);
+            }
+            if ui
+                .add_enabled(annotated, Button::new("Clear"))
+                .on_hover_text("Remove every annotation from the map")
+                .clicked()
+            {
+                self.message(Message::ClearAnnotations);
+            }
+        });
+    }
+
+    /** Draw an annotation over the map in `rect` with `tool`, by dragging across it or by clicking
+    it for text.
+
+    Shapes are drafted while the drag goes on and only added to the [`Subsector`] once it ends.
+    */
+    fn annotation_input(
+        &mut self,
+        ctx: &Context,
+        tool: AnnotationTool,
+        response: &Response,
+        rect: &Rect,
+    ) {
+        if response.drag_released() {
+            if let Some(shape) = self.annotation_draft.take().filter(is_drawn) {
+                let annotation = Annotation {
+                    shape,
+                    color: self.annotation_color,
+                };
+                self.message(Message::AddAnnotation { annotation });
+            }
+            return;
+        }
+
+        let position = match response.interact_pointer_pos() {
+            Some(pointer_pos) => map_position(pointer_pos, rect),
+            None => return,
+        };
+        if tool == AnnotationTool::Text {
+            let text = self.annotation_text.trim();
+            if response.clicked() && !text.is_empty() {
+                let annotation = Annotation {
+                    shape: AnnotationShape::Text {
+                        position,
+                        text: text.to_string(),
+                    },
+                    color: self.annotation_color,
+                };
+                self.message(Message::AddAnnotation { annotation });
+            }
+        } else if response.drag_started() {
+            let origin = ctx
+                .input()
+                .pointer
+                .press_origin()
+                .map_or(position, |origin| map_position(origin, rect));
+            self.annotation_draft = Some(match tool {
+                AnnotationTool::Freehand => AnnotationShape::Freehand {
+                    points: vec![origin, position],
+                },
+                AnnotationTool::Arrow => AnnotationShape::Arrow {
+                    from: origin,
+                    to: position,
+                },
+                _ => AnnotationShape::Circle {
+                    center: origin,
+                    radius: distance(origin, position),
+                },
+            });
+        } else if response.dragged() {
+            match &mut self.annotation_draft {
+                Some(AnnotationShape::Freehand { points })
+                    if points
+                        .last()
+                        .is_none_or(|last| distance(*last, position) >= FREEHAND_STEP) =>
+                {
+                    points.push(position);
+                }
+                Some(AnnotationShape::Arrow { to, .. }) => *to = position,
+                Some(AnnotationShape::Circle { center, radius }) => {
+                    *radius = distance(*center, position)
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /** Displays a map of the [`Subsector`] and handles any mouse clicks on or hovering over it. */
     pub(crate) fn subsector_map_display(&mut self, ctx: &Context, ui: &mut Ui) {
-        if let Ok(new_image) = self.worker_rx.try_recv() {
+        if let Some(new_image) = self.map_worker.try_recv() {
             self.subsector_grid_image = Some(new_image);
         }
 
         if self.subsector_grid_image.is_none() {
-            let svg = self.subsector.generate_grid_svg();
+            let hex_numbers = self.settings.map_hex_numbers;
+            let svg = match self.subsector.generate_grid_svg(hex_numbers) {
+                Ok(svg) => svg,
+                Err(e) => {
+                    self.map_render_error_popup(&e);
+                    Subsector::generate_blank_grid_svg(hex_numbers)
+                }
+            };
+            self.map_redraw.mark_drawn(&svg);
             self.subsector_grid_image = Some(rasterize_svg(svg));
         }
 
+        self.map_highlight_controls(ui);
+        if self.viewer_mode {
+            self.annotation_tool = None;
+            self.annotation_draft = None;
+        } else {
+            self.map_annotation_controls(ui);
+        }
+
+        if ctx.input().key_pressed(Key::Escape) {
+            self.measurement = None;
+        }
+
         let max_size = ui.available_size();
-        ui.set_min_size(SUBSECTOR_IMAGE_MIN_SIZE);
         ui.set_max_size(max_size);
 
-        if let Some(grid_image) = &self.subsector_grid_image {
-            let mut desired_size = grid_image.size_vec2();
+        // Taken up front so the image isn't borrowed while the map's input is handled
+        let grid_image = self
+            .subsector_grid_image
+            .as_ref()
+            .map(|grid_image| (grid_image.texture_id(ctx), grid_image.size_vec2()));
+        if let Some((texture_id, mut desired_size)) = grid_image {
             desired_size *= (max_size.x / desired_size.x).min(1.0);
             desired_size *= (max_size.y / desired_size.y).min(1.0);
 
-            let grid_widget =
-                Image::new(grid_image.texture_id(ctx), desired_size).sense(Sense::click());
-            let grid_response = ui.add(grid_widget);
-            if grid_response.clicked() {
+            // Dragging pans the map while zoomed in, unless it's measuring
+            let (view_rect, grid_response) =
+                ui.allocate_exact_size(desired_size, Sense::click_and_drag());
+            if let Some(hover_pos) = grid_response.hover_pos() {
+                let input = ctx.input();
+                let scroll = input.scroll_delta.y / MAP_SCROLL_ZOOM_PIXELS;
+                let factor = input.zoom_delta() * scroll.exp();
+                if factor != 1.0 {
+                    self.map_view.zoom_by(factor, hover_pos, &view_rect);
+                }
+            }
+
+            let mini_map_rect = self.map_view.is_zoomed().then(|| mini_map_rect(&view_rect));
+            let on_mini_map = |pos: Option<Pos2>| match (pos, mini_map_rect) {
+                (Some(pos), Some(rect)) => rect.contains(pos),
+                _ => false,
+            };
+
+            // Clicking or dragging on the mini-map jumps the view there
+            let pointer_pos = grid_response.interact_pointer_pos();
+            let mini_map_clicked = (grid_response.clicked() && on_mini_map(pointer_pos))
+                || (grid_response.dragged() && on_mini_map(ctx.input().pointer.press_origin()));
+            if mini_map_clicked {
+                if let (Some(pointer_pos), Some(rect)) = (pointer_pos, mini_map_rect) {
+                    let center = pos2(
+                        (pointer_pos.x - rect.left()) / rect.width(),
+                        (pointer_pos.y - rect.top()) / rect.height(),
+                    );
+                    self.map_view.center_on(center);
+                }
+            } else if let Some(tool) = self.annotation_tool {
+                let map_rect = self.map_view.map_rect(&view_rect);
+                self.annotation_input(ctx, tool, &grid_response, &map_rect);
+            } else if self.measure_mode && grid_response.dragged() {
+                // Measure from the hex the drag started on to the one under the pointer
+                let map_rect = self.map_view.map_rect(&view_rect);
+                let origin = ctx.input().pointer.press_origin();
+                let pointer_pos = grid_response.interact_pointer_pos();
+                if let (Some(origin), Some(pointer_pos)) = (origin, pointer_pos) {
+                    if let (ClickKind::Hex(start), ClickKind::Hex(end)) = (
+                        determine_click_kind(origin, &map_rect),
+                        determine_click_kind(pointer_pos, &map_rect),
+                    ) {
+                        self.measurement = Some((start, end));
+                    }
+                }
+            } else if grid_response.dragged() {
+                self.map_view.pan(grid_response.drag_delta(), &view_rect);
+            } else if grid_response.clicked() {
                 if let Some(pointer_pos) = grid_response.interact_pointer_pos() {
-                    let new_point = determine_click_kind(pointer_pos, &grid_response.rect);
+                    let map_rect = self.map_view.map_rect(&view_rect);
+                    let new_point = determine_click_kind(pointer_pos, &map_rect);
 
                     // A new point has been selected
                     match new_point {
@@ -81,30 +594,269 @@ impl GeneratorApp {
                         ClickKind::None => (),
                     }
                 }
+            } else if grid_response.secondary_clicked() {
+                if let Some(pointer_pos) = grid_response.interact_pointer_pos() {
+                    // Right-clicking a world selects it for comparison in split view
+                    let map_rect = self.map_view.map_rect(&view_rect);
+                    if let ClickKind::Hex(point) = determine_click_kind(pointer_pos, &map_rect) {
+                        self.message(Message::CompareWorld { point });
+                    }
+                }
+            }
+
+            // Everything is drawn over the whole map, of which only the part in view is shown
+            let map_rect = self.map_view.map_rect(&view_rect);
+
+            // Show an info card for whichever hex is under the pointer
+            let hover_pos = grid_response.hover_pos();
+            if let Some(pointer_pos) = hover_pos.filter(|_| !on_mini_map(hover_pos)) {
+                if let ClickKind::Hex(point) = determine_click_kind(pointer_pos, &map_rect) {
+                    let world = self.subsector.get_world(&point);
+                    grid_response.on_hover_ui_at_pointer(|ui| hex_info_card(ui, &point, world));
+                }
             }
 
-            let mut shapes = Vec::new();
-            shapes.push(draw_subsector_name(
-                ctx,
-                self.subsector.name(),
-                &grid_response.rect,
+            let highlights = self.map_highlights();
+            if highlights
+                .iter()
+                .any(|highlight| matches!(highlight, HexHighlight::SearchResult(_)))
+            {
+                // Keep the search result glow animating
+                ctx.request_repaint();
+            }
+
+            let mut shapes = vec![Shape::image(
+                texture_id,
+                map_rect,
+                Rect::from_min_max(Pos2::ZERO, pos2(1.0, 1.0)),
+                Color32::WHITE,
+            )];
+            shapes.append(&mut draw_highlights(
+                &highlights,
+                &map_rect,
+                ctx.input().time,
+            ));
+            shapes.push(draw_subsector_name(ctx, self.subsector.name(), &map_rect));
+            shapes.append(&mut draw_xboat_routes(
+                self.subsector.xboat_routes(),
+                &map_rect,
             ));
+            shapes.append(&mut draw_ownership_arrows(
+                &self.subsector.owners(),
+                &map_rect,
+            ));
+            for annotation in self.subsector.annotations() {
+                shapes.append(&mut draw_annotation(
+                    ctx,
+                    &annotation.shape,
+                    annotation.color,
+                    &map_rect,
+                ));
+            }
+            if let Some(shape) = &self.annotation_draft {
+                shapes.append(&mut draw_annotation(
+                    ctx,
+                    shape,
+                    self.annotation_color,
+                    &map_rect,
+                ));
+            }
+            if let Some((start, end)) = &self.measurement {
+                shapes.append(&mut draw_measurement(ctx, start, end, &map_rect));
+            }
+            for bookmark in self.subsector.bookmarks() {
+                shapes.push(draw_bookmark(ctx, &bookmark.point, &map_rect));
+            }
             for (point, world) in self.subsector.get_map() {
-                shapes.append(&mut draw_world(ctx, point, world, &grid_response.rect));
+                shapes.append(&mut draw_world(
+                    ctx,
+                    point,
+                    world,
+                    self.settings.map_world_labels,
+                    &map_rect,
+                ));
+                if let Some(severity) = world.worst_hazard().filter(|_| self.show_hazards) {
+                    shapes.push(draw_world_hazard(ctx, point, severity, &map_rect));
+                }
 
                 // DO NOT DELETE: Uncomment to see centers of all hexes; useful for debugging
-                // let center = hex_center(point, &grid_response.rect);
+                // let center = hex_center(point, &map_rect);
                 // let center = vec2(center.x, center.y);
                 // let center_circle =
                 //     CircleShape::filled(Pos2::from([0.0, 0.0]) + center, 3.5, Color32::GREEN);
                 // shapes.push(Shape::Circle(center_circle));
             }
+            if let Some(mini_map_rect) = mini_map_rect {
+                shapes.append(&mut draw_mini_map(
+                    texture_id,
+                    &self.subsector,
+                    &self.map_view,
+                    &mini_map_rect,
+                ));
+            }
+
+            ui.painter_at(view_rect).extend(shapes);
+            self.map_legend_overlay(ctx, &view_rect);
+        }
+    }
+
+    /** The layers drawn on the on-screen map, which only has some of the layers of exported maps.
+
+    The travel route is drawn while the travel planner is open.
+    */
+    fn screen_map_options(&self) -> MapOptions {
+        let mut options = MapOptions {
+            bases: false,
+            hex_numbers: self.settings.map_hex_numbers,
+            polity_colors: false,
+            routes: self.show_travel_planner,
+            travel_zones: false,
+            ..Default::default()
+        };
+        options.set_world_labels(self.settings.map_world_labels);
+        options
+    }
+
+    /** Show a collapsible legend of the symbols on the map over its top left corner.
+
+    The legend is built from the same layers as the map itself, so it only lists what is drawn.
+    */
+    fn map_legend_overlay(&self, ctx: &Context, rect: &Rect) {
+        let legend = self
+            .subsector
+            .map_legend(&self.screen_map_options(), &self.travel_route);
+        let owned = !self.subsector.owners().is_empty();
+        if legend.is_empty() && !owned {
+            return;
+        }
+
+        Area::new("map_legend")
+            .fixed_pos(rect.left_top() + vec2(LABEL_SPACING, LABEL_SPACING))
+            .show(ctx, |ui| {
+                // The map is always drawn on white, so the legend is too
+                Frame::popup(ui.style())
+                    .fill(Color32::WHITE)
+                    .show(ui, |ui| {
+                        let title = RichText::new("Legend").color(Color32::BLACK);
+                        CollapsingHeader::new(title)
+                            .id_source("map_legend_header")
+                            .show(ui, |ui| {
+                                for entry in &legend {
+                                    legend_row(ui, entry);
+                                }
+                                if owned {
+                                    ui.horizontal(|ui| {
+                                        let (rect, _) = ui.allocate_exact_size(
+                                            LEGEND_SYMBOL_SIZE,
+                                            Sense::hover(),
+                                        );
+                                        let from = rect.left_center() + vec2(2.0, 0.0);
+                                        let to = rect.right_center() - vec2(2.0, 0.0);
+                                        let stroke = Stroke::from((1.5, OWNERSHIP_ARROW_COLOR));
+                                        ui.painter().arrow(from, to - from, stroke);
+                                        ui.label(RichText::new("Owned By").color(Color32::BLACK));
+                                    });
+                                }
+                            });
+                    });
+            });
+    }
+}
+
+/** Add a row to the map legend with the symbol of `entry` and its label. */
+fn legend_row(ui: &mut Ui, entry: &LegendEntry) {
+    ui.horizontal(|ui| {
+        let (rect, _) = ui.allocate_exact_size(LEGEND_SYMBOL_SIZE, Sense::hover());
+        draw_legend_symbol(ui.ctx(), ui.painter(), &rect, &entry.symbol);
+        ui.label(RichText::new(&entry.label).color(Color32::BLACK));
+    });
+}
+
+/** Draw `symbol` in `rect` the same way it is drawn on the map. */
+fn draw_legend_symbol(ctx: &Context, painter: &Painter, rect: &Rect, symbol: &LegendSymbol) {
+    let center = rect.center();
+    let (left, right) = (rect.left_center(), rect.right_center());
+    let text = |text: &str| {
+        painter.text(
+            center,
+            Align2::CENTER_CENTER,
+            text,
+            WORLD_FONT_ID,
+            Color32::BLACK,
+        );
+    };
 
-            ui.painter_at(grid_response.rect).extend(shapes);
+    match symbol {
+        LegendSymbol::DryWorld | LegendSymbol::WetWorld => {
+            // The indicator is offset from the hex center by a distance scaled to the map
+            let is_wet_world = *symbol == LegendSymbol::WetWorld;
+            painter.add(draw_world_wet_dry_indicator(&center, 0.0, is_wet_world));
+        }
+        LegendSymbol::GasGiant => {
+            painter.extend(draw_world_gas_giant(&center, 0.0));
+        }
+        LegendSymbol::Subtype(subtype) => {
+            painter.extend(draw_world_subtype(&center, 0.0, *subtype, 0.5));
+        }
+        LegendSymbol::StarportTl => {
+            let galley =
+                ctx.fonts()
+                    .layout_no_wrap("B-12".to_string(), WORLD_FONT_ID, Color32::BLACK);
+            let position = center - galley.rect.size() / 2.0;
+            painter.add(Shape::Text(TextShape::new(position, galley)));
+        }
+        LegendSymbol::Base(code) => text(code),
+        LegendSymbol::AmberZone => {
+            painter.circle_stroke(center, 6.0, Stroke::from((1.5, AMBER_ZONE_COLOR)));
+        }
+        LegendSymbol::RedZone => {
+            painter.circle_stroke(center, 6.0, Stroke::from((1.5, RED_ZONE_COLOR)));
+        }
+        LegendSymbol::XboatRoute => {
+            let stroke = Stroke::from((2.0, XBOAT_ROUTE_COLOR));
+            painter.extend(Shape::dashed_line(&[left, right], stroke, 4.0, 3.0));
+        }
+        LegendSymbol::TravelRoute => {
+            painter.line_segment([left, right], Stroke::from((3.0, ROUTE_PREVIEW_COLOR)));
+        }
+        LegendSymbol::Polity([r, g, b]) => {
+            painter.rect(
+                rect.shrink(2.0),
+                0.0,
+                Color32::from_rgb(*r, *g, *b),
+                Stroke::from((1.0, Color32::BLACK)),
+            );
         }
     }
 }
 
+/** Fill a hover tooltip with a summary of the hex at `point` and the `World` in it, if any. */
+fn hex_info_card(ui: &mut Ui, point: &Point, world: Option<&World>) {
+    let world = match world {
+        Some(world) => world,
+        None => {
+            ui.label(format!("Empty hex {}", point));
+            return;
+        }
+    };
+
+    ui.heading(format!("{} {}", point, world.name));
+    Grid::new("hex_info_card_grid")
+        .spacing([FIELD_SPACING, LABEL_SPACING])
+        .show(ui, |ui| {
+            let mut row = |label: &str, value: String| {
+                ui.label(RichText::new(label).font(LABEL_FONT).color(LABEL_COLOR));
+                ui.label(value);
+                ui.end_row();
+            };
+
+            row("UWP", world.profile_str());
+            row("Trade Codes", world.trade_code_str());
+            row("Bases", world.base_str());
+            row("Travel Code", world.travel_code_str());
+        });
+}
+
 /** Generates a [`RetainedImage`] from an SVG string.
 
 # Panics
@@ -117,6 +869,53 @@ pub(crate) fn rasterize_svg(svg: String) -> RetainedImage {
     )
 }
 
+/** Generates a [`RetainedImage`] of a `World`'s surface thumbnail from its SVG.
+
+# Returns
+- `Ok<RetainedImage>` if successful,
+- `Err<String>` if the given SVG is invalid
+*/
+pub(crate) fn rasterize_thumbnail(svg: &str) -> Result<RetainedImage, String> {
+    let pixmap = render_svg(svg.as_bytes(), usvg::FitTo::Original, false)?;
+    let image = ColorImage::from_rgba_unmultiplied(
+        [pixmap.width() as _, pixmap.height() as _],
+        pixmap.data(),
+    );
+    Ok(RetainedImage::from_color_image(
+        "world_thumbnail.svg",
+        image,
+    ))
+}
+
+/** Generates a small [`RetainedImage`] preview of a subsector map from its SVG.
+
+The system fonts aren't loaded, so the preview has no text; it's too small to read anyway.
+
+# Returns
+- `Ok<RetainedImage>` if successful,
+- `Err<String>` if the given SVG is invalid
+*/
+pub(crate) fn rasterize_map_preview(svg: &str) -> Result<RetainedImage, String> {
+    let pixmap = render_svg(svg.as_bytes(), usvg::FitTo::Width(MAP_PREVIEW_WIDTH), false)?;
+    let image = ColorImage::from_rgba_unmultiplied(
+        [pixmap.width() as _, pixmap.height() as _],
+        pixmap.data(),
+    );
+    Ok(RetainedImage::from_color_image("map_preview.svg", image))
+}
+
+/** Rasterizes an SVG string into PNG data for export, scaled up by `zoom`.
+
+# Returns
+- `Ok<Vec<u8>>` with the encoded PNG if successful,
+- `Err<String>` if the given SVG is invalid or the image could not be encoded
+*/
+pub(crate) fn svg_to_png(svg: &str, zoom: f32) -> Result<Vec<u8>, String> {
+    render_svg(svg.as_bytes(), usvg::FitTo::Zoom(zoom), true)?
+        .encode_png()
+        .map_err(|err| err.to_string())
+}
+
 /** Loads an SVG byte array and rasterizes it into a [`ColorImage`].
 
 # Returns
@@ -124,15 +923,36 @@ pub(crate) fn rasterize_svg(svg: String) -> RetainedImage {
 - `Err<String>` if the given SVG is invalid
 */
 fn load_svg_bytes(svg_bytes: &[u8]) -> Result<ColorImage, String> {
-    let mut opt = usvg::Options {
-        font_family: system_sans_serif_font(),
-        ..Default::default()
-    };
-    opt.fontdb.load_system_fonts();
+    let pixmap = render_svg(svg_bytes, usvg::FitTo::Original, true)?;
+
+    let image = ColorImage::from_rgba_unmultiplied(
+        [pixmap.width() as _, pixmap.height() as _],
+        pixmap.data(),
+    );
+
+    Ok(image)
+}
+
+/** Renders an SVG byte array into a [`tiny_skia::Pixmap`] sized according to `fit_to`.
+
+Loading the system fonts is slow, so SVGs without any text can skip it by unsetting `with_fonts`.
+*/
+fn render_svg(
+    svg_bytes: &[u8],
+    fit_to: usvg::FitTo,
+    with_fonts: bool,
+) -> Result<tiny_skia::Pixmap, String> {
+    let mut opt = usvg::Options::default();
+    if with_fonts {
+        opt.font_family = system_sans_serif_font();
+        opt.fontdb.load_system_fonts();
+    }
 
     let rtree = usvg::Tree::from_data(svg_bytes, &opt.to_ref()).map_err(|err| err.to_string())?;
 
-    let pixmap_size = rtree.svg_node().size.to_screen_size();
+    let pixmap_size = fit_to
+        .fit_to(rtree.svg_node().size.to_screen_size())
+        .ok_or_else(|| "Failed to size SVG Pixmap".to_owned())?;
     let [w, h] = [pixmap_size.width(), pixmap_size.height()];
 
     let mut pixmap = tiny_skia::Pixmap::new(w, h)
@@ -140,18 +960,13 @@ fn load_svg_bytes(svg_bytes: &[u8]) -> Result<ColorImage, String> {
 
     resvg::render(
         &rtree,
-        usvg::FitTo::Original,
+        fit_to,
         tiny_skia::Transform::default(),
         pixmap.as_mut(),
     )
     .ok_or_else(|| "Failed to render SVG".to_owned())?;
 
-    let image = ColorImage::from_rgba_unmultiplied(
-        [pixmap.width() as _, pixmap.height() as _],
-        pixmap.data(),
-    );
-
-    Ok(image)
+    Ok(pixmap)
 }
 
 /** Converts a pointer position to its corresponding interaction type with the subsector map image.
@@ -223,6 +1038,157 @@ fn determine_click_kind(pointer_pos: Pos2, rect: &Rect) -> ClickKind {
     }
 }
 
+/** Draw each of `highlights` over the map, animated according to the current `time` in seconds. */
+fn draw_highlights(highlights: &[HexHighlight], rect: &Rect, time: f64) -> Vec<Shape> {
+    let pixels_per_unit = rect.width() / SVG_VIEW_BOX_WIDTH as f32;
+    let pixels_per_inch = rect.width() / SVG_WIDTH;
+
+    // Fades smoothly between 0 and 1 over the course of each `GLOW_PERIOD`
+    let pulse = (0.5 - 0.5 * (time * std::f64::consts::TAU / GLOW_PERIOD).cos()) as f32;
+
+    let mut shapes = Vec::new();
+    for highlight in highlights {
+        match highlight {
+            HexHighlight::Selected(point) => {
+                let stroke = Stroke::from((3.0, SELECTED_HEX_COLOR));
+                shapes.push(Shape::Path(PathShape::closed_line(
+                    hex_corners(point, rect),
+                    stroke,
+                )));
+            }
+            HexHighlight::SearchResult(point) => {
+                let center = hex_center(point, rect);
+                let radius = HEX_SHORT_RADIUS * pixels_per_inch;
+                let fill = SEARCH_RESULT_COLOR.linear_multiply(0.15 + 0.25 * pulse);
+                shapes.push(Shape::Circle(CircleShape::filled(center, radius, fill)));
+                let stroke = Stroke::from((2.0, SEARCH_RESULT_COLOR.linear_multiply(0.5 + pulse)));
+                shapes.push(Shape::Circle(CircleShape::stroke(center, radius, stroke)));
+            }
+            HexHighlight::Filtered(point) => {
+                shapes.push(Shape::convex_polygon(
+                    hex_corners(point, rect),
+                    FILTERED_HEX_COLOR,
+                    Stroke::none(),
+                ));
+            }
+            HexHighlight::JumpRange(point, jumps) => {
+                shapes.push(Shape::convex_polygon(
+                    hex_corners(point, rect),
+                    JUMP_RANGE_COLOR.linear_multiply(1.0 / *jumps as f32),
+                    Stroke::none(),
+                ));
+            }
+            HexHighlight::RoutePreview(stops) => {
+                let points: Vec<Pos2> = stops.iter().map(|stop| hex_center(stop, rect)).collect();
+                let stroke = Stroke::from((3.0, ROUTE_PREVIEW_COLOR));
+                shapes.push(Shape::line(points.clone(), stroke));
+                for point in points {
+                    shapes.push(Shape::Circle(CircleShape::filled(
+                        point,
+                        2.0 * pixels_per_unit,
+                        ROUTE_PREVIEW_COLOR,
+                    )));
+                }
+            }
+            HexHighlight::Unsaved(point) => {
+                let mut corners = hex_corners(point, rect);
+                corners.push(corners[0]);
+                let stroke = Stroke::from((2.0, UNSAVED_HEX_COLOR));
+                shapes.extend(Shape::dashed_line(&corners, stroke, 6.0, 4.0));
+            }
+        }
+    }
+    shapes
+}
+
+/** Draw a ruler from `start` to `end` labeled at its midpoint with the distance between them. */
+fn draw_measurement(ctx: &Context, start: &Point, end: &Point, rect: &Rect) -> Vec<Shape> {
+    let pixels_per_unit = rect.width() / SVG_VIEW_BOX_WIDTH as f32;
+    let (from, to) = (hex_center(start, rect), hex_center(end, rect));
+
+    let mut shapes = vec![Shape::line_segment([from, to], (3.0, MEASUREMENT_COLOR))];
+    for point in [from, to] {
+        shapes.push(Shape::Circle(CircleShape::filled(
+            point,
+            2.0 * pixels_per_unit,
+            MEASUREMENT_COLOR,
+        )));
+    }
+
+    let galley =
+        ctx.fonts()
+            .layout_no_wrap(measurement_label(start, end), WORLD_FONT_ID, Color32::WHITE);
+    let label_rect = Align2::CENTER_CENTER
+        .anchor_rect(Rect::from_min_size(from + (to - from) / 2.0, galley.size()));
+    shapes.push(Shape::rect_filled(
+        label_rect.expand(3.0),
+        3.0,
+        MEASUREMENT_COLOR,
+    ));
+    shapes.push(Shape::Text(TextShape::new(label_rect.min, galley)));
+    shapes
+}
+
+/** Draw an overview of the whole map in `rect`, with its worlds as dots and the part of the map in
+`view` outlined.
+*/
+fn draw_mini_map(
+    texture_id: TextureId,
+    subsector: &Subsector,
+    view: &MapView,
+    rect: &Rect,
+) -> Vec<Shape> {
+    let uv = Rect::from_min_max(Pos2::ZERO, pos2(1.0, 1.0));
+    let mut shapes = vec![
+        Shape::rect_filled(rect.expand(1.0), 0.0, Color32::WHITE),
+        Shape::image(texture_id, *rect, uv, Color32::WHITE),
+        Shape::rect_stroke(rect.expand(1.0), 0.0, Stroke::new(1.0, Color32::GRAY)),
+    ];
+
+    for point in subsector.get_map().keys() {
+        let center = hex_center(point, rect);
+        shapes.push(Shape::circle_filled(center, 1.5, Color32::BLACK));
+    }
+
+    let visible = view.visible_fraction();
+    let to_mini_map = |pos: Pos2| rect.left_top() + vec2(pos.x, pos.y) * rect.size();
+    let viewport = Rect::from_min_max(to_mini_map(visible.min), to_mini_map(visible.max));
+    shapes.push(Shape::rect_stroke(
+        viewport,
+        0.0,
+        Stroke::new(2.0, SELECTED_HEX_COLOR),
+    ));
+    shapes
+}
+
+/** Draw an arrow from each owned world to its owner in `owners`. */
+fn draw_ownership_arrows(owners: &[(Point, Point)], rect: &Rect) -> Vec<Shape> {
+    let pixels_per_unit = rect.width() / SVG_VIEW_BOX_WIDTH as f32;
+    let stroke = Stroke::from((1.5, OWNERSHIP_ARROW_COLOR));
+    // Arrows stop short of the hex centers so they don't cover the worlds' names
+    let inset = 8.0 * pixels_per_unit;
+    let head_length = 3.0 * pixels_per_unit;
+
+    let mut shapes = Vec::new();
+    for (owned, owner) in owners {
+        let (from, to) = (hex_center(owned, rect), hex_center(owner, rect));
+        let direction = (to - from).normalized();
+        let (from, to) = (from + direction * inset, to - direction * inset);
+        shapes.push(Shape::line_segment([from, to], stroke));
+
+        // Arrowhead made of two short lines swept back from the tip
+        for angle in [0.5_f32, -0.5] {
+            let (sin, cos) = angle.sin_cos();
+            let back = vec2(
+                -direction.x * cos + direction.y * sin,
+                -direction.x * sin - direction.y * cos,
+            );
+            shapes.push(Shape::line_segment([to, to + back * head_length], stroke));
+        }
+    }
+    shapes
+}
+
 fn draw_subsector_name(ctx: &Context, subsector_name: &str, rect: &Rect) -> Shape {
     const SUBSECTOR_NAME_FONT_ID: FontId = FontId::proportional(28.0);
     let galley = ctx.fonts().layout_no_wrap(
@@ -236,7 +1202,13 @@ fn draw_subsector_name(ctx: &Context, subsector_name: &str, rect: &Rect) -> Shap
     Shape::Text(TextShape::new(position, galley))
 }
 
-fn draw_world(ctx: &Context, point: &Point, world: &World, rect: &Rect) -> Vec<Shape> {
+fn draw_world(
+    ctx: &Context,
+    point: &Point,
+    world: &World,
+    labels: WorldLabels,
+    rect: &Rect,
+) -> Vec<Shape> {
     let mut shapes = Vec::new();
 
     let center = hex_center(point, rect);
@@ -247,15 +1219,25 @@ fn draw_world(ctx: &Context, point: &Point, world: &World, rect: &Rect) -> Vec<S
         shapes.append(&mut draw_world_gas_giant(&center, pixels_per_unit));
     }
 
-    // Draw world name
-    shapes.push(draw_world_name(ctx, &center, &world.name));
+    // Draw world name, or the UWP in its place if names are left off
+    let name = match labels {
+        WorldLabels::UwpOnly => world.profile_str(),
+        _ => world.name.clone(),
+    };
+    shapes.push(draw_world_name(ctx, &center, &name));
 
-    // Draw wet/dry world indicator
+    // Draw wet/dry world indicator, outlined if the world isn't a mainworld
     shapes.push(draw_world_wet_dry_indicator(
         &center,
         pixels_per_unit,
         world.is_wet_world(),
     ));
+    shapes.append(&mut draw_world_subtype(
+        &center,
+        pixels_per_unit,
+        world.subtype,
+        1.0,
+    ));
 
     // Draw Starport-TechLevel
     shapes.push(draw_world_starport_tl(
@@ -266,16 +1248,46 @@ fn draw_world(ctx: &Context, point: &Point, world: &World, rect: &Rect) -> Vec<S
     ));
 
     // Draw UWP
-    shapes.push(draw_world_profile(
-        ctx,
-        &center,
-        pixels_per_unit,
-        &world.profile_str(),
-    ));
+    if labels == WorldLabels::NameAndUwp {
+        shapes.push(draw_world_profile(
+            ctx,
+            &center,
+            pixels_per_unit,
+            &world.profile_str(),
+        ));
+    }
 
     shapes
 }
 
+/** Draw a star in the corner of the bookmarked hex at `point`. */
+fn draw_bookmark(ctx: &Context, point: &Point, rect: &Rect) -> Shape {
+    // How much offset from hex's center to place the star in SVG userspace units
+    const OFFSET: Vec2 = vec2(8.5, -6.5);
+    let pixels_per_unit = rect.width() / SVG_VIEW_BOX_WIDTH as f32;
+    let galley =
+        ctx.fonts()
+            .layout_no_wrap(BOOKMARK_ICON.to_string(), WORLD_FONT_ID, BOOKMARK_COLOR);
+    let position = hex_center(point, rect) + OFFSET * pixels_per_unit - galley.rect.size() / 2.0;
+    Shape::Text(TextShape::new(position, galley))
+}
+
+/** Draw a warning icon in the corner of the hex at `point`, colored by the `severity` of the worst
+hazard of its world.
+*/
+fn draw_world_hazard(ctx: &Context, point: &Point, severity: HazardSeverity, rect: &Rect) -> Shape {
+    // How much offset from hex's center to place the icon in SVG userspace units
+    const OFFSET: Vec2 = vec2(-8.5, -6.5);
+    let pixels_per_unit = rect.width() / SVG_VIEW_BOX_WIDTH as f32;
+    let galley = ctx.fonts().layout_no_wrap(
+        WARNING_ICON.to_string(),
+        WORLD_FONT_ID,
+        HAZARD_COLORS[severity as usize],
+    );
+    let position = hex_center(point, rect) + OFFSET * pixels_per_unit - galley.rect.size() / 2.0;
+    Shape::Text(TextShape::new(position, galley))
+}
+
 fn draw_world_gas_giant(center: &Pos2, pixels_per_unit: f32) -> Vec<Shape> {
     // How much offset from hex's center to place the gas giant in SVG userspace units
     const OFFSET: Vec2 = vec2(0.0, -6.0);
@@ -330,13 +1342,19 @@ fn draw_world_gas_giant(center: &Pos2, pixels_per_unit: f32) -> Vec<Shape> {
     ]
 }
 
+/** Draw `name` over the hex centered on `center`, fit to the hex the same way as on maps. */
 fn draw_world_name(ctx: &Context, center: &Pos2, name: &str) -> Shape {
+    let layout = NameLayout::fit(name);
+    let font_id = FontId::proportional(WORLD_FONT_ID.size * layout.scale as f32);
+    let line_count = layout.lines.len() as f32;
     let galley = ctx
         .fonts()
-        .layout_no_wrap(name.to_string(), WORLD_FONT_ID, Color32::BLACK);
+        .layout_no_wrap(layout.lines.join("\n"), font_id, Color32::BLACK);
     let text_width = galley.rect.width();
     let text_height = galley.rect.height();
-    let offset = vec2(-text_width / 2.0, -text_height / 1.5);
+    // Extra lines are spread evenly above and below where a single line would be
+    let line_height = text_height / line_count;
+    let offset = vec2(-text_width / 2.0, -text_height / 2.0 - line_height / 6.0);
     let position = *center + offset;
     Shape::Text(TextShape::new(position, galley))
 }
@@ -389,6 +1407,133 @@ fn draw_world_wet_dry_indicator(center: &Pos2, pixels_per_unit: f32, is_wet_worl
     }
 }
 
+/** Draw the outline around the wet/dry indicator marking a world of `subtype`, `scale` times its
+usual size: a dashed ring for colonies, a triangle for outposts, and a square for research stations.
+*/
+fn draw_world_subtype(
+    center: &Pos2,
+    pixels_per_unit: f32,
+    subtype: WorldSubtype,
+    scale: f32,
+) -> Vec<Shape> {
+    let offset = vec2(-5.0 * pixels_per_unit, 4.5 * pixels_per_unit);
+    let position = *center + offset;
+    let stroke = Stroke::from((1.0, Color32::BLACK));
+    let polygon = |corners: &[(f32, f32)]| {
+        let points = corners
+            .iter()
+            .map(|(x, y)| position + vec2(*x, *y) * scale)
+            .collect();
+        vec![Shape::closed_line(points, stroke)]
+    };
+
+    match subtype {
+        WorldSubtype::Mainworld => Vec::new(),
+        WorldSubtype::Colony => {
+            let radius = 8.5 * scale;
+            let points: Vec<Pos2> = (0..=32)
+                .map(|i| {
+                    let angle = i as f32 / 32.0 * std::f32::consts::TAU;
+                    position + vec2(angle.cos(), angle.sin()) * radius
+                })
+                .collect();
+            Shape::dashed_line(&points, stroke, 2.0 * scale, 1.5 * scale)
+        }
+        WorldSubtype::Outpost => polygon(&[(0.0, -11.0), (9.5, 5.5), (-9.5, 5.5)]),
+        WorldSubtype::ResearchStation => {
+            polygon(&[(-6.7, -6.7), (6.7, -6.7), (6.7, 6.7), (-6.7, 6.7)])
+        }
+    }
+}
+
+/** Draw a dashed line between the hubs of each x-boat link in `links`. */
+fn draw_xboat_routes(links: &[(Point, Point)], rect: &Rect) -> Vec<Shape> {
+    let pixels_per_unit = rect.width() / SVG_VIEW_BOX_WIDTH as f32;
+    let stroke = Stroke::from((2.0, XBOAT_ROUTE_COLOR));
+
+    let mut shapes = Vec::new();
+    for (from, to) in links {
+        let points = [hex_center(from, rect), hex_center(to, rect)];
+        shapes.extend(Shape::dashed_line(
+            &points,
+            stroke,
+            2.0 * pixels_per_unit,
+            1.5 * pixels_per_unit,
+        ));
+    }
+    shapes
+}
+
+/** Draw `shape` of an annotation over the map in `color`. */
+fn draw_annotation(
+    ctx: &Context,
+    shape: &AnnotationShape,
+    color: AnnotationColor,
+    rect: &Rect,
+) -> Vec<Shape> {
+    let pixels_per_unit = rect.width() / SVG_VIEW_BOX_WIDTH as f32;
+    let [r, g, b] = color.rgb();
+    let color = Color32::from_rgb(r, g, b);
+    let stroke = Stroke::new(ANNOTATION_LINE_WIDTH, color);
+    let screen = |position: &Translation| screen_position(position, rect);
+
+    match shape {
+        AnnotationShape::Freehand { points } => {
+            vec![Shape::line(points.iter().map(screen).collect(), stroke)]
+        }
+        AnnotationShape::Arrow { from, to } => {
+            let [left, right] = shape.arrowhead().unwrap();
+            let tip = screen(to);
+            vec![
+                Shape::line_segment([screen(from), tip], stroke),
+                Shape::line(vec![screen(&left), tip, screen(&right)], stroke),
+            ]
+        }
+        AnnotationShape::Circle { center, radius } => vec![Shape::circle_stroke(
+            screen(center),
+            *radius as f32 * pixels_per_unit,
+            stroke,
+        )],
+        AnnotationShape::Text { position, text } => {
+            let galley = ctx
+                .fonts()
+                .layout_no_wrap(text.clone(), WORLD_FONT_ID, color);
+            let position = screen(position) - galley.size() / 2.0;
+            vec![Shape::Text(TextShape::new(position, galley))]
+        }
+    }
+}
+
+/** Whether enough of `shape` was drawn to be worth keeping, e.g. a circle with a radius. */
+fn is_drawn(shape: &AnnotationShape) -> bool {
+    match shape {
+        AnnotationShape::Freehand { points } => points.len() > 1,
+        AnnotationShape::Arrow { from, to } => from != to,
+        AnnotationShape::Circle { radius, .. } => *radius > 0.0,
+        AnnotationShape::Text { text, .. } => !text.is_empty(),
+    }
+}
+
+/** Describe the length of a ruler from `start` to `end` in both hexes spanned and parsecs. */
+fn measurement_label(start: &Point, end: &Point) -> String {
+    let parsecs = start.distance(end);
+    let hexes = parsecs + 1;
+    format!(
+        "{} {} / {} {}",
+        hexes,
+        if hexes == 1 { "hex" } else { "hexes" },
+        parsecs,
+        if parsecs == 1 { "parsec" } else { "parsecs" },
+    )
+}
+
+/** Get where the mini-map goes, in the bottom right corner of the map's `view_rect`. */
+fn mini_map_rect(view_rect: &Rect) -> Rect {
+    let size = view_rect.size() * MINI_MAP_SCALE;
+    let margin = Vec2::splat(LABEL_SPACING * 2.0);
+    Rect::from_min_size(view_rect.right_bottom() - margin - size, size)
+}
+
 fn hex_center(point: &Point, rect: &Rect) -> Pos2 {
     let pixels_per_unit = rect.width() as f64 / SVG_VIEW_BOX_WIDTH;
 
@@ -398,6 +1543,46 @@ fn hex_center(point: &Point, rect: &Rect) -> Pos2 {
 
     Pos2::from([x as f32, y as f32])
 }
+
+/** Get the position on the map in SVG userspace units of `pos` on the screen. */
+fn map_position(pos: Pos2, rect: &Rect) -> Translation {
+    let units_per_pixel = SVG_VIEW_BOX_WIDTH / rect.width() as f64;
+    Translation {
+        x: (pos.x - rect.left()) as f64 * units_per_pixel,
+        y: (pos.y - rect.top()) as f64 * units_per_pixel,
+    }
+}
+
+/** Get the position on the screen of `position` on the map in SVG userspace units. */
+fn screen_position(position: &Translation, rect: &Rect) -> Pos2 {
+    let pixels_per_unit = rect.width() as f64 / SVG_VIEW_BOX_WIDTH;
+    pos2(
+        (position.x * pixels_per_unit) as f32 + rect.left(),
+        (position.y * pixels_per_unit) as f32 + rect.top(),
+    )
+}
+
+fn distance(a: Translation, b: Translation) -> f64 {
+    (a.x - b.x).hypot(a.y - b.y)
+}
+
+/** Get the corners of the hex at `point`, starting from the right and going clockwise. */
+fn hex_corners(point: &Point, rect: &Rect) -> Vec<Pos2> {
+    let pixels_per_inch = rect.width() / SVG_WIDTH;
+    let center = hex_center(point, rect);
+    let radius = HEX_LONG_RADIUS * pixels_per_inch;
+    let half_height = HEX_SHORT_RADIUS * pixels_per_inch;
+
+    vec![
+        center + vec2(radius, 0.0),
+        center + vec2(radius / 2.0, half_height),
+        center + vec2(-radius / 2.0, half_height),
+        center + vec2(-radius, 0.0),
+        center + vec2(-radius / 2.0, -half_height),
+        center + vec2(radius / 2.0, -half_height),
+    ]
+}
+
 /** Returns the best guess of the system's default sans-serif font. */
 fn system_sans_serif_font() -> String {
     #[cfg(target_os = "windows")]
@@ -416,3 +1601,132 @@ fn system_sans_serif_font() -> String {
         "Liberation Sans".to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swt_gen_core::{MapRenderer, DEFAULT_MAP_TEMPLATE};
+
+    #[test]
+    fn map_highlights() {
+        let mut app = GeneratorApp {
+            subsector: Subsector::empty(),
+            ..Default::default()
+        };
+        assert!(app.map_highlights().is_empty());
+
+        let point = Point { x: 2, y: 3 };
+        let mut world = World::empty();
+        world.name = "Regina".to_string();
+        world.allegiance = "Re".to_string();
+        app.subsector.insert_world(&point, world).unwrap();
+
+        app.point = point;
+        app.point_selected = true;
+        app.map_search = "regina".to_string();
+        app.polity_filter = Some("Re".to_string());
+        app.travel_route = vec![Point { x: 1, y: 1 }, point];
+        app.show_travel_planner = true;
+
+        // The selection is drawn last so it is on top of everything else
+        assert_eq!(
+            app.map_highlights(),
+            vec![
+                HexHighlight::Filtered(point),
+                HexHighlight::RoutePreview(app.travel_route.clone()),
+                HexHighlight::SearchResult(point),
+                HexHighlight::Selected(point),
+            ]
+        );
+
+        app.show_travel_planner = false;
+        app.map_search.clear();
+        app.polity_filter = None;
+        assert_eq!(app.map_highlights(), vec![HexHighlight::Selected(point)]);
+
+        app.saved_worlds.clear();
+        app.show_unsaved_worlds = true;
+        assert_eq!(
+            app.map_highlights(),
+            vec![HexHighlight::Unsaved(point), HexHighlight::Selected(point)]
+        );
+
+        let far = Point { x: 2, y: 5 };
+        app.subsector.insert_world(&far, World::empty()).unwrap();
+        app.saved_worlds = app.subsector.get_map().clone();
+        app.point = Point { x: 2, y: 1 };
+        app.jump_range_rating = 2;
+        app.jump_range_jumps = 2;
+        app.show_jump_range = true;
+        let highlights = app.map_highlights();
+        // Every hex within one jump, and worlds further out by hopping through other worlds
+        assert_eq!(highlights.len(), 12);
+        assert!(highlights.contains(&HexHighlight::JumpRange(Point { x: 2, y: 2 }, 1)));
+        assert!(highlights.contains(&HexHighlight::JumpRange(point, 1)));
+        assert!(highlights.contains(&HexHighlight::JumpRange(far, 2)));
+        assert!(!highlights.contains(&HexHighlight::JumpRange(Point { x: 2, y: 1 }, 1)));
+    }
+
+    #[test]
+    fn map_view() {
+        let view_rect = Rect::from_min_size(pos2(10.0, 20.0), vec2(400.0, 500.0));
+        let mut view = MapView::default();
+        assert!(!view.is_zoomed());
+        assert_eq!(view.map_rect(&view_rect), view_rect);
+
+        // Zooming keeps the part of the map under the pointer where it is
+        let anchor = pos2(110.0, 145.0);
+        view.zoom_by(2.0, anchor, &view_rect);
+        assert!(view.is_zoomed());
+        let map_rect = view.map_rect(&view_rect);
+        assert_eq!(map_rect.size(), view_rect.size() * 2.0);
+        assert_eq!(map_rect.min + (anchor - view_rect.min) * 2.0, anchor);
+        assert_eq!(view.visible_fraction().left_top(), pos2(0.125, 0.125));
+
+        // The view can't be dragged or jumped off the edge of the map
+        view.pan(vec2(-10_000.0, 0.0), &view_rect);
+        assert_eq!(view.visible_fraction().right(), 1.0);
+        view.center_on(Pos2::ZERO);
+        assert_eq!(view.visible_fraction().left_top(), Pos2::ZERO);
+
+        view.zoom_centered(1.0 / MAX_MAP_ZOOM);
+        assert_eq!(view, MapView::default());
+    }
+
+    #[test]
+    fn measurement_label() {
+        let start = Point { x: 1, y: 1 };
+        assert_eq!(
+            super::measurement_label(&start, &start),
+            "1 hex / 0 parsecs"
+        );
+        assert_eq!(
+            super::measurement_label(&start, &Point { x: 1, y: 2 }),
+            "2 hexes / 1 parsec"
+        );
+        assert_eq!(
+            super::measurement_label(&start, &Point { x: 4, y: 1 }),
+            "4 hexes / 3 parsecs"
+        );
+    }
+
+    #[test]
+    fn standalone_map_png() {
+        let options = MapOptions {
+            legend: true,
+            renderer: MapRenderer::Standalone,
+            ..MapOptions::default()
+        };
+        let svg = Subsector::default()
+            .generate_svg(&DEFAULT_MAP_TEMPLATE, &options, &[])
+            .unwrap();
+        let png = svg_to_png(&svg, 1.0).unwrap();
+        assert!(!png.is_empty());
+    }
+
+    #[test]
+    fn world_thumbnail() {
+        let image = rasterize_thumbnail(&World::new("Regina".to_string()).thumbnail_svg()).unwrap();
+        assert_eq!(image.size(), [160, 160]);
+    }
+}
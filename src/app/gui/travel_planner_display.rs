@@ -0,0 +1,170 @@
+use egui::{Button, Context, DragValue, Grid, RichText, Ui, Window};
+use swt_gen_core::{plan_route, Refueling, RouteLeg};
+
+use crate::app::{
+    gui::{
+        FIELD_SPACING, LABEL_COLOR, LABEL_FONT, LABEL_SPACING, NEGATIVE_RED, WARNING_ORANGE, X_ICON,
+    },
+    GeneratorApp, Message,
+};
+
+impl GeneratorApp {
+    /** Display editable fields for the ship used by the travel planner. */
+    fn ship_spec_display(&mut self, ui: &mut Ui) {
+        ui.heading("Ship");
+        ui.add_space(LABEL_SPACING);
+
+        Grid::new("ship_spec_grid")
+            .spacing([FIELD_SPACING, LABEL_SPACING])
+            .show(ui, |ui| {
+                for label in ["Hull (tons)", "Jump", "Thrust (G)", "Fuel (tons)"] {
+                    ui.label(RichText::new(label).font(LABEL_FONT).color(LABEL_COLOR));
+                }
+                ui.end_row();
+
+                let spec = &mut self.ship_spec;
+                ui.add(DragValue::new(&mut spec.hull_tonnage).clamp_range(10..=1_000_000));
+                ui.add(DragValue::new(&mut spec.jump_rating).clamp_range(0..=6));
+                ui.add(DragValue::new(&mut spec.thrust).clamp_range(1..=9));
+                ui.add(DragValue::new(&mut spec.fuel_capacity).clamp_range(0..=1_000_000));
+            });
+    }
+
+    /** Display a table of each leg of the planned route and the totals for the whole trip. */
+    fn travel_legs_display(&mut self, ui: &mut Ui) {
+        let legs = plan_route(&self.subsector, &self.travel_route, &self.ship_spec);
+        if legs.is_empty() {
+            ui.label("Add at least two stops to plan a route.");
+            return;
+        }
+
+        ui.heading("Itinerary");
+        ui.add_space(LABEL_SPACING);
+
+        Grid::new("travel_legs_grid")
+            .spacing([FIELD_SPACING, LABEL_SPACING])
+            .striped(true)
+            .show(ui, |ui| {
+                for header in [
+                    "Leg",
+                    "Parsecs",
+                    "Fuel Used",
+                    "Fuel Left",
+                    "Days",
+                    "Refueling",
+                    "",
+                ] {
+                    ui.label(RichText::new(header).font(LABEL_FONT).color(LABEL_COLOR));
+                }
+                ui.end_row();
+
+                for leg in legs.iter() {
+                    ui.label(format!("{} → {}", leg.from, leg.to));
+                    ui.label(leg.parsecs.to_string());
+                    ui.label(leg.fuel_required.to_string());
+                    ui.label(leg.fuel_remaining.to_string());
+                    ui.label(format!("{:.1}", leg.total_hours() / 24.0));
+
+                    let refueling = RichText::new(leg.refueling.to_string());
+                    match leg.refueling {
+                        Refueling::Wilderness => {
+                            let hover_text =
+                                "Fuel must be skimmed from a gas giant or taken from surface water";
+                            ui.label(refueling.color(WARNING_ORANGE))
+                                .on_hover_text(hover_text);
+                        }
+                        Refueling::None => {
+                            let hover_text = "There is no way to refuel at this stop";
+                            ui.label(refueling.color(WARNING_ORANGE))
+                                .on_hover_text(hover_text);
+                        }
+                        _ => {
+                            ui.label(refueling);
+                        }
+                    }
+
+                    if let Some(problem) = leg.problem {
+                        ui.label(RichText::new(problem.to_string()).color(NEGATIVE_RED));
+                    }
+                    ui.end_row();
+                }
+            });
+        ui.add_space(FIELD_SPACING);
+
+        let total_hours: f64 = legs.iter().map(RouteLeg::total_hours).sum();
+        let total_fuel: u32 = legs.iter().map(|leg| leg.fuel_required).sum();
+        ui.label(format!(
+            "Total: {} jump(s), {:.1} days, {} tons of fuel",
+            legs.len(),
+            total_hours / 24.0,
+            total_fuel
+        ));
+        if let Some(date) = self.subsector.campaign_date() {
+            ui.label(format!(
+                "Leaving on {} arrives on {}",
+                date,
+                date.plus_hours(total_hours)
+            ));
+        }
+    }
+
+    /** Show the travel planner window if it is open. */
+    pub(crate) fn travel_planner_window(&mut self, ctx: &Context) {
+        let mut open = self.show_travel_planner;
+        Window::new("Travel Planner")
+            .open(&mut open)
+            .enabled(self.popup_queue.is_empty())
+            .resizable(false)
+            .show(ctx, |ui| {
+                self.ship_spec_display(ui);
+                ui.separator();
+                self.travel_route_display(ui);
+                ui.separator();
+                self.travel_legs_display(ui);
+            });
+        self.show_travel_planner = open;
+    }
+
+    /** Display the route of the travel planner along with each stop's world. */
+    fn travel_route_display(&mut self, ui: &mut Ui) {
+        ui.heading("Route");
+        ui.add_space(LABEL_SPACING);
+
+        Grid::new("travel_route_grid")
+            .spacing([FIELD_SPACING, LABEL_SPACING])
+            .show(ui, |ui| {
+                for (index, point) in self.travel_route.iter().enumerate() {
+                    ui.label(point.to_string());
+                    match self.subsector.get_world(point) {
+                        Some(world) => ui.label(&world.name),
+                        None => ui.label(RichText::new("Empty Hex").color(LABEL_COLOR)),
+                    };
+                    if ui.small_button(X_ICON).clicked() {
+                        self.message(Message::RemoveTravelRouteStop { index });
+                    }
+                    ui.end_row();
+                }
+            });
+        ui.add_space(LABEL_SPACING);
+
+        ui.horizontal(|ui| {
+            let label = match self.point_selected {
+                true => format!("Add {}", self.point),
+                false => "Add Selected Hex".to_string(),
+            };
+            if ui
+                .add_enabled(self.point_selected, Button::new(label))
+                .clicked()
+            {
+                self.message(Message::AddTravelRouteStop);
+            }
+
+            if ui
+                .add_enabled(!self.travel_route.is_empty(), Button::new("Clear"))
+                .clicked()
+            {
+                self.message(Message::ClearTravelRoute);
+            }
+        });
+    }
+}
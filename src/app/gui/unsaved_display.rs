@@ -0,0 +1,54 @@
+use egui::{Context, Grid, RichText, ScrollArea, Window};
+
+use crate::app::{
+    gui::{FIELD_SPACING, LABEL_COLOR, LABEL_FONT, LABEL_SPACING},
+    GeneratorApp, Message, UnsavedChange,
+};
+
+impl GeneratorApp {
+    /** Show the window listing every world changed since the last save if it is open.
+
+    Clicking a hex code in the list jumps to that hex. While the window is open, the changed hexes
+    are also highlighted on the map.
+    */
+    pub(crate) fn unsaved_worlds_window(&mut self, ctx: &Context) {
+        let mut open = self.show_unsaved_worlds;
+        Window::new("Unsaved Changes")
+            .open(&mut open)
+            .enabled(self.popup_queue.is_empty())
+            .show(ctx, |ui| {
+                let unsaved_worlds = self.unsaved_worlds();
+                if unsaved_worlds.is_empty() {
+                    ui.label("No worlds have changed since the subsector was last saved.");
+                    return;
+                }
+
+                ScrollArea::vertical().show(ui, |ui| {
+                    Grid::new("unsaved_worlds_grid")
+                        .spacing([FIELD_SPACING, LABEL_SPACING])
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for header in ["Hex", "World", "Change"] {
+                                ui.label(RichText::new(header).font(LABEL_FONT).color(LABEL_COLOR));
+                            }
+                            ui.end_row();
+
+                            for (point, change) in unsaved_worlds {
+                                if ui.link(point.to_string()).clicked() {
+                                    self.message(Message::HexGridClicked { new_point: point });
+                                }
+
+                                let world = match change {
+                                    UnsavedChange::Removed => self.saved_worlds.get(&point),
+                                    _ => self.subsector.get_world(&point),
+                                };
+                                ui.label(world.map_or("", |world| &world.name[..]));
+                                ui.label(change.to_string());
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+        self.show_unsaved_worlds = open;
+    }
+}
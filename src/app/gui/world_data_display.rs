@@ -1,25 +1,50 @@
 use std::fmt;
 
 use egui::{
-    vec2, Align, Button, ComboBox, FontId, Grid, Key, Layout, RichText, ScrollArea, Style,
-    TextEdit, TextStyle, Ui,
+    vec2, Align, Button, CollapsingHeader, ColorImage, ComboBox, DragValue, FontId, Grid, Layout,
+    Response, RichText, ScrollArea, Style, TextEdit, TextStyle, Ui, WidgetInfo, WidgetType,
+};
+use egui_extras::RetainedImage;
+use swt_gen_core::{
+    CulturalFacet, FacilityKind, FacilitySize, FuelAvailability, FuelKind, GovRecord,
+    HazardSeverity, LawCategory, LengthUnit, Point, Religion, ReligionAspect, Repairs, RuleTopic,
+    Shipyard, StarportClass, TonnageClass, TravelCode, World, WorldField, WorldSubtype, TABLES,
+    TRADE_GOODS_TABLE,
 };
 
-use crate::{
-    app::{
-        gui::{
-            BUTTON_FONT_SIZE, CLIPBOARD_ICON, DICE_ICON, FIELD_SELECTION_WIDTH, FIELD_SPACING,
-            LABEL_COLOR, LABEL_FONT, LABEL_SPACING, NEGATIVE_RED, POSITIVE_BLUE, SAVE_ICON,
-            SHORT_SELECTION_WIDTH, X_ICON,
-        },
-        GeneratorApp, Message,
-    },
-    astrography::{
-        CulturalDiffRecord, Faction, GovRecord, StarportClass, TravelCode, World, TABLES,
+use crate::app::{
+    gui::{
+        rasterize_thumbnail, rule_hover_ui, BUTTON_FONT_SIZE, CLIPBOARD_ICON, DICE_ICON,
+        FIELD_SELECTION_WIDTH, FIELD_SPACING, LABEL_COLOR, LABEL_FONT, LABEL_SPACING, NEGATIVE_RED,
+        POSITIVE_BLUE, SAVE_ICON, SHORT_SELECTION_WIDTH, WARNING_ICON, WARNING_ORANGE, X_ICON,
     },
+    GeneratorApp, Message,
 };
 
-#[derive(PartialEq)]
+/// Maximum width or height of a world image thumbnail
+const WORLD_IMAGE_MAX_SIZE: f32 = 200.0;
+
+/// Maximum height of the list of changes in the world history
+const HISTORY_MAX_HEIGHT: f32 = 200.0;
+
+/// Values longer than this many characters are cut short in the world history
+const HISTORY_VALUE_MAX_CHARS: usize = 40;
+
+/** Name for screen readers of a widget whose visible label is a separate widget, like the small
+gray labels above most fields, or that only shows an icon.
+*/
+trait ScreenReaderLabel {
+    fn screen_reader_label(self, widget_type: WidgetType, label: &str) -> Self;
+}
+
+impl ScreenReaderLabel for Response {
+    fn screen_reader_label(self, widget_type: WidgetType, label: &str) -> Self {
+        self.widget_info(|| WidgetInfo::labeled(widget_type, label));
+        self
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
 pub(crate) enum TabLabel {
     WorldSurvey,
     GovernmentLaw,
@@ -27,22 +52,43 @@ pub(crate) enum TabLabel {
     Factions,
     #[allow(dead_code)]
     CultureErrata,
+    Trade,
+    #[allow(dead_code)]
+    Encounters,
     Notes,
+    Rules,
 }
 
 impl TabLabel {
     #[cfg(not(feature = "player-safe-gui"))]
-    pub(crate) const ALL_VALUES: [TabLabel; 5] = [
+    pub(crate) const ALL_VALUES: [TabLabel; 8] = [
         Self::WorldSurvey,
         Self::GovernmentLaw,
         Self::Factions,
         Self::CultureErrata,
+        Self::Trade,
+        Self::Encounters,
         Self::Notes,
+        Self::Rules,
     ];
 
     #[cfg(feature = "player-safe-gui")]
-    pub(crate) const ALL_VALUES: [TabLabel; 3] =
-        [Self::WorldSurvey, Self::GovernmentLaw, Self::Notes];
+    pub(crate) const ALL_VALUES: [TabLabel; 5] = [
+        Self::WorldSurvey,
+        Self::GovernmentLaw,
+        Self::Trade,
+        Self::Notes,
+        Self::Rules,
+    ];
+
+    /// Tabs shown in the player view, whose notes tab leaves out the secret notes
+    pub(crate) const PLAYER_VIEW_VALUES: [TabLabel; 5] = [
+        Self::WorldSurvey,
+        Self::GovernmentLaw,
+        Self::Trade,
+        Self::Notes,
+        Self::Rules,
+    ];
 }
 
 impl fmt::Display for TabLabel {
@@ -52,7 +98,10 @@ impl fmt::Display for TabLabel {
             TabLabel::GovernmentLaw => "Government & Law",
             TabLabel::Factions => "Factions",
             TabLabel::CultureErrata => "Culture & Errata",
+            TabLabel::Trade => "Trade",
+            TabLabel::Encounters => "Encounters & Hazards",
             TabLabel::Notes => "Notes",
+            TabLabel::Rules => "Rules",
         };
         write!(f, "{}", s)
     }
@@ -115,13 +164,18 @@ impl GeneratorApp {
                             )
                             .clicked()
                         {
-                            self.message(Message::WorldModelUpdated);
+                            self.message(Message::WorldFieldEdited {
+                                field: WorldField::Atmosphere,
+                            });
                         }
                     }
-                });
+                })
+                .response
+                .screen_reader_label(WidgetType::ComboBox, "Atmosphere");
 
             if ui
                 .button(RichText::new(DICE_ICON).font(FontId::proportional(BUTTON_FONT_SIZE)))
+                .screen_reader_label(WidgetType::Button, "Reroll atmosphere")
                 .clicked()
             {
                 self.message(Message::RegenWorldAtmosphere);
@@ -129,10 +183,22 @@ impl GeneratorApp {
         });
     }
 
+    /** Get the warning shown next to the text field of `field`, if there is one.
+
+    A rejected entry takes priority over `value_warning` about the value the field currently holds.
+    */
     fn culture_display(&mut self, ui: &mut Ui) {
         ui.heading("Culture");
         ui.add_space(LABEL_SPACING);
 
+        if !self.world.subtype.has_society() {
+            ui.label(format!(
+                "This {} has no culture of its own.",
+                self.world.subtype.to_string().to_lowercase()
+            ));
+            return;
+        }
+
         ui.horizontal(|ui| {
             let code = self.world.culture.code as usize;
             ComboBox::from_id_source("culture_selection")
@@ -140,14 +206,9 @@ impl GeneratorApp {
                 .width(FIELD_SELECTION_WIDTH)
                 .show_ui(ui, |ui| {
                     for item in TABLES.culture_table.iter() {
-                        let CulturalDiffRecord {
-                            cultural_difference,
-                            ..
-                        } = &self.world.culture;
-
                         if ui
                             .selectable_label(
-                                cultural_difference == &item.cultural_difference,
+                                self.world.culture.code == item.code,
                                 &item.cultural_difference,
                             )
                             .on_hover_text(&item.description)
@@ -158,10 +219,13 @@ impl GeneratorApp {
                             });
                         }
                     }
-                });
+                })
+                .response
+                .screen_reader_label(WidgetType::ComboBox, "Culture");
 
             if ui
                 .button(RichText::new(DICE_ICON).font(FontId::proportional(BUTTON_FONT_SIZE)))
+                .screen_reader_label(WidgetType::Button, "Reroll culture")
                 .clicked()
             {
                 self.message(Message::RegenWorldCulture);
@@ -176,12 +240,58 @@ impl GeneratorApp {
         );
         ui.add_space(LABEL_SPACING);
 
+        // Leaves room below for the cultural details
         ScrollArea::vertical()
             .id_source("culture_description")
-            .max_height(ui.available_height() * 0.9)
+            .max_height(ui.available_height() * 0.4)
+            .show(ui, |ui| {
+                ui.add(TextEdit::multiline(&mut self.world.culture.description))
+                    .screen_reader_label(WidgetType::TextEdit, "Culture description");
+            });
+        ui.add_space(FIELD_SPACING);
+
+        self.cultural_details_display(ui);
+
+        CollapsingHeader::new("Religion")
+            .id_source("world_religion")
+            .default_open(true)
             .show(ui, |ui| {
-                ui.add(TextEdit::multiline(&mut self.world.culture.description));
+                if religion_display(ui, &mut self.world.religion, "world religion") {
+                    self.message(Message::RegenWorldReligion);
+                }
+            });
+    }
+
+    fn cultural_details_display(&mut self, ui: &mut Ui) {
+        for facet in CulturalFacet::ALL_VALUES {
+            ui.label(
+                RichText::new(facet.to_string())
+                    .font(LABEL_FONT)
+                    .color(LABEL_COLOR),
+            );
+            ui.add_space(LABEL_SPACING);
+
+            ui.horizontal(|ui| {
+                ui.add(
+                    TextEdit::multiline(self.world.cultural_details.get_mut(facet))
+                        .desired_rows(2)
+                        .desired_width(FIELD_SELECTION_WIDTH),
+                )
+                .screen_reader_label(WidgetType::TextEdit, &facet.to_string());
+
+                if ui
+                    .button(RichText::new(DICE_ICON).font(FontId::proportional(BUTTON_FONT_SIZE)))
+                    .screen_reader_label(
+                        WidgetType::Button,
+                        &format!("Reroll {}", facet.to_string().to_lowercase()),
+                    )
+                    .clicked()
+                {
+                    self.message(Message::RegenWorldCulturalDetail { facet });
+                }
             });
+            ui.add_space(LABEL_SPACING * 1.5);
+        }
     }
 
     /** Tab displaying information about the culture and world tags of the `World`.
@@ -198,6 +308,104 @@ impl GeneratorApp {
     }
 
     /** Tab displaying the non-government factions that exist on this `World`. */
+    /** Tab displaying the random encounter table of the `World`, with a reroll for each entry. */
+    fn encounters_display(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label(
+                RichText::new("Encounters (1D)")
+                    .font(LABEL_FONT)
+                    .color(LABEL_COLOR),
+            );
+            if ui
+                .button(RichText::new(DICE_ICON).size(BUTTON_FONT_SIZE))
+                .on_hover_text("Reroll the whole encounter table")
+                .screen_reader_label(WidgetType::Button, "Reroll encounters")
+                .clicked()
+            {
+                self.message(Message::RegenWorldEncounters);
+            }
+        });
+        ui.add_space(LABEL_SPACING);
+
+        Grid::new("world_encounters_grid")
+            .spacing([FIELD_SPACING, LABEL_SPACING])
+            .striped(true)
+            .show(ui, |ui| {
+                for header in ["1D", "Kind", "Encounter", ""] {
+                    ui.label(RichText::new(header).font(LABEL_FONT).color(LABEL_COLOR));
+                }
+                ui.end_row();
+
+                for (index, encounter) in self.world.encounters.iter_mut().enumerate() {
+                    ui.label((index + 1).to_string());
+                    ui.label(encounter.kind.to_string());
+                    ui.add(
+                        TextEdit::singleline(&mut encounter.description)
+                            .desired_width(FIELD_SELECTION_WIDTH * 1.5),
+                    )
+                    .screen_reader_label(WidgetType::TextEdit, "Encounter");
+                    if ui
+                        .button(RichText::new(DICE_ICON).size(BUTTON_FONT_SIZE))
+                        .on_hover_text("Reroll this encounter")
+                        .screen_reader_label(WidgetType::Button, "Reroll encounter")
+                        .clicked()
+                    {
+                        self.message_tx.send(Message::RegenWorldEncounter { index });
+                    }
+                    ui.end_row();
+                }
+            });
+        ui.add_space(FIELD_SPACING);
+
+        ui.horizontal(|ui| {
+            ui.label(RichText::new("Hazards").font(LABEL_FONT).color(LABEL_COLOR));
+            if ui
+                .button(RichText::new(DICE_ICON).size(BUTTON_FONT_SIZE))
+                .on_hover_text("Reroll the stellar hazards of the system")
+                .screen_reader_label(WidgetType::Button, "Reroll hazards")
+                .clicked()
+            {
+                self.message(Message::RegenWorldHazards);
+            }
+        });
+        ui.add_space(LABEL_SPACING);
+
+        if self.world.hazards.is_empty() {
+            ui.label("The system has no notable hazards.");
+            return;
+        }
+        Grid::new("world_hazards_grid")
+            .spacing([FIELD_SPACING, LABEL_SPACING])
+            .striped(true)
+            .show(ui, |ui| {
+                for header in ["Hazard", "Severity", "Description"] {
+                    ui.label(RichText::new(header).font(LABEL_FONT).color(LABEL_COLOR));
+                }
+                ui.end_row();
+
+                for hazard in self.world.hazards.iter_mut() {
+                    ui.label(hazard.kind.to_string());
+                    ComboBox::from_id_source(format!("world_hazard_severity_{}", hazard.kind))
+                        .selected_text(hazard.severity.to_string())
+                        .show_ui(ui, |ui| {
+                            for severity in HazardSeverity::ALL_VALUES {
+                                ui.selectable_value(
+                                    &mut hazard.severity,
+                                    severity,
+                                    severity.to_string(),
+                                );
+                            }
+                        });
+                    ui.add(
+                        TextEdit::singleline(&mut hazard.description)
+                            .desired_width(FIELD_SELECTION_WIDTH * 1.5),
+                    )
+                    .screen_reader_label(WidgetType::TextEdit, "Hazard description");
+                    ui.end_row();
+                }
+            });
+    }
+
     fn factions_display(&mut self, ui: &mut Ui) {
         ui.label(
             RichText::new("Factions")
@@ -217,7 +425,11 @@ impl GeneratorApp {
                         for (index, faction) in self.world.factions.iter().enumerate() {
                             ui.selectable_value(&mut self.faction_idx, index, &faction.name);
                         }
-                        if ui.button("+").clicked() {
+                        if ui
+                            .button("+")
+                            .screen_reader_label(WidgetType::Button, "Add faction")
+                            .clicked()
+                        {
                             self.message(Message::AddNewFaction)
                         }
                     });
@@ -241,6 +453,7 @@ impl GeneratorApp {
                             if ui
                                 .add(faction_removal_button)
                                 .on_hover_text_at_pointer("Double click to delete this faction")
+                                .screen_reader_label(WidgetType::Button, "Remove faction")
                                 .double_clicked()
                             {
                                 self.message(Message::RemoveSelectedFaction);
@@ -252,6 +465,7 @@ impl GeneratorApp {
                                     RichText::new(DICE_ICON)
                                         .font(FontId::proportional(BUTTON_FONT_SIZE)),
                                 )
+                                .screen_reader_label(WidgetType::Button, "Reroll faction")
                                 .clicked()
                             {
                                 self.message(Message::RegenSelectedFaction);
@@ -260,10 +474,25 @@ impl GeneratorApp {
                     });
 
                     // Faction name
-                    ui.add(
-                        TextEdit::singleline(&mut self.world.factions[self.faction_idx].name)
-                            .desired_width(FIELD_SELECTION_WIDTH),
-                    );
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            TextEdit::singleline(&mut self.world.factions[self.faction_idx].name)
+                                .desired_width(FIELD_SELECTION_WIDTH),
+                        )
+                        .screen_reader_label(WidgetType::TextEdit, "Faction name");
+
+                        if ui
+                            .button(
+                                RichText::new(DICE_ICON)
+                                    .font(FontId::proportional(BUTTON_FONT_SIZE)),
+                            )
+                            .on_hover_text("Name the faction to suit its leadership")
+                            .screen_reader_label(WidgetType::Button, "Reroll faction name")
+                            .clicked()
+                        {
+                            self.message(Message::RegenFactionName);
+                        }
+                    });
                     ui.add_space(LABEL_SPACING * 1.5);
 
                     ui.label(
@@ -283,12 +512,9 @@ impl GeneratorApp {
                         .width(FIELD_SELECTION_WIDTH)
                         .show_ui(ui, |ui| {
                             for faction in TABLES.faction_table.iter() {
-                                let Faction { strength, .. } =
-                                    &self.world.factions[self.faction_idx];
-
                                 if ui
                                     .selectable_label(
-                                        strength == &faction.strength,
+                                        strength_code == faction.code as usize,
                                         format!("{}: {}", faction.code, faction.strength),
                                     )
                                     .clicked()
@@ -298,7 +524,9 @@ impl GeneratorApp {
                                     });
                                 }
                             }
-                        });
+                        })
+                        .response
+                        .screen_reader_label(WidgetType::ComboBox, "Faction strength");
                     ui.add_space(LABEL_SPACING * 1.5);
 
                     ui.label(
@@ -315,13 +543,9 @@ impl GeneratorApp {
                         .width(FIELD_SELECTION_WIDTH)
                         .show_ui(ui, |ui| {
                             for gov in TABLES.gov_table.iter() {
-                                let GovRecord {
-                                    kind: fac_gov_kind, ..
-                                } = &self.world.factions[self.faction_idx].government;
-
                                 if ui
                                     .selectable_label(
-                                        fac_gov_kind == &gov.kind,
+                                        gov_code == gov.code as usize,
                                         format!("{}: {}", gov.code, gov.kind),
                                     )
                                     .on_hover_text(&gov.description)
@@ -332,9 +556,16 @@ impl GeneratorApp {
                                     });
                                 }
                             }
-                        });
+                        })
+                        .response
+                        .screen_reader_label(WidgetType::ComboBox, "Faction government");
                     ui.add_space(LABEL_SPACING * 1.5);
 
+                    let religion = &mut self.world.factions[self.faction_idx].religion;
+                    if religion_display(ui, religion, "faction religion") {
+                        self.message(Message::RegenFactionReligion);
+                    }
+
                     ui.label(
                         RichText::new("Description")
                             .font(LABEL_FONT)
@@ -350,6 +581,10 @@ impl GeneratorApp {
                             let GovRecord { description, .. } =
                                 &mut self.world.factions[self.faction_idx].government;
                             ui.add(TextEdit::multiline(description).desired_width(f32::INFINITY))
+                                .screen_reader_label(
+                                    WidgetType::TextEdit,
+                                    "Faction government description",
+                                )
                         });
                 });
             }
@@ -370,14 +605,9 @@ impl GeneratorApp {
                 .width(FIELD_SELECTION_WIDTH)
                 .show_ui(ui, |ui| {
                     for gov in TABLES.gov_table.iter() {
-                        let GovRecord {
-                            kind: world_gov_kind,
-                            ..
-                        } = &mut self.world.government;
-
                         if ui
                             .selectable_label(
-                                world_gov_kind == &gov.kind,
+                                self.world.government.code == gov.code,
                                 format!("{}: {}", gov.code, gov.kind),
                             )
                             .on_hover_text(&gov.description)
@@ -386,10 +616,13 @@ impl GeneratorApp {
                             self.message(Message::NewWorldGovSelected { new_code: gov.code });
                         }
                     }
-                });
+                })
+                .response
+                .screen_reader_label(WidgetType::ComboBox, "Government");
 
             if ui
                 .button(RichText::new(DICE_ICON).font(FontId::proportional(BUTTON_FONT_SIZE)))
+                .screen_reader_label(WidgetType::Button, "Reroll government")
                 .clicked()
             {
                 self.message(Message::RegenWorldGovernment);
@@ -411,7 +644,40 @@ impl GeneratorApp {
         .on_hover_text(format!(
             "Common contraband: {}",
             TABLES.gov_table[self.world.government.code as usize].contraband
-        ));
+        ))
+        .screen_reader_label(WidgetType::TextEdit, "Contraband");
+
+        ui.add_space(LABEL_SPACING * 1.5);
+        ui.label(
+            RichText::new("Allegiance")
+                .font(LABEL_FONT)
+                .color(LABEL_COLOR),
+        );
+        ui.add_space(LABEL_SPACING);
+
+        let allegiance = self
+            .subsector
+            .polities()
+            .iter()
+            .find(|polity| polity.code == self.world.allegiance)
+            .map_or("Non-Aligned".to_string(), |polity| polity.name.clone());
+        ComboBox::from_id_source("allegiance_selection")
+            .selected_text(allegiance)
+            .width(FIELD_SELECTION_WIDTH)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut self.world.allegiance, String::new(), "Non-Aligned");
+                for polity in self.subsector.polities() {
+                    ui.selectable_value(
+                        &mut self.world.allegiance,
+                        polity.code.clone(),
+                        format!("{} ({})", polity.name, polity.code),
+                    );
+                }
+            })
+            .response
+            .screen_reader_label(WidgetType::ComboBox, "Allegiance");
+
+        self.owner_display(ui);
 
         ui.add_space(LABEL_SPACING * 1.5);
         ui.label(
@@ -425,7 +691,8 @@ impl GeneratorApp {
             .id_source("government_description")
             .max_height(ui.available_height() * 0.9)
             .show(ui, |ui| {
-                ui.add(TextEdit::multiline(&mut self.world.government.description));
+                ui.add(TextEdit::multiline(&mut self.world.government.description))
+                    .screen_reader_label(WidgetType::TextEdit, "Government description");
             });
     }
 
@@ -437,6 +704,43 @@ impl GeneratorApp {
         });
     }
 
+    /** Collapsible list of every change applied to the `World`, most recent first. */
+    fn history_display(&mut self, ui: &mut Ui) {
+        CollapsingHeader::new("History")
+            .id_source("world_history")
+            .show(ui, |ui| {
+                if self.world.history.is_empty() {
+                    ui.label("No changes have been applied to this world yet.");
+                    return;
+                }
+
+                ScrollArea::vertical()
+                    .id_source("world_history_scroll")
+                    .max_height(HISTORY_MAX_HEIGHT)
+                    .show(ui, |ui| {
+                        Grid::new("world_history_grid")
+                            .spacing([FIELD_SPACING, LABEL_SPACING])
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for header in ["Applied", "Field", "Old Value", "New Value"] {
+                                    ui.label(
+                                        RichText::new(header).font(LABEL_FONT).color(LABEL_COLOR),
+                                    );
+                                }
+                                ui.end_row();
+
+                                for change in self.world.history.iter().rev() {
+                                    ui.label(change.timestamp_str());
+                                    ui.label(&change.field);
+                                    history_value_label(ui, &change.old_value);
+                                    history_value_label(ui, &change.new_value);
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            });
+    }
+
     fn hydrographics_display(&mut self, ui: &mut Ui) {
         ui.label(
             RichText::new("Hydrographics")
@@ -466,13 +770,18 @@ impl GeneratorApp {
                             )
                             .clicked()
                         {
-                            self.message(Message::WorldModelUpdated);
+                            self.message(Message::WorldFieldEdited {
+                                field: WorldField::Hydrographics,
+                            });
                         }
                     }
-                });
+                })
+                .response
+                .screen_reader_label(WidgetType::ComboBox, "Hydrographics");
 
             if ui
                 .button(RichText::new(DICE_ICON).font(FontId::proportional(BUTTON_FONT_SIZE)))
+                .screen_reader_label(WidgetType::Button, "Reroll hydrographics")
                 .clicked()
             {
                 self.message(Message::RegenWorldHydrographics);
@@ -480,6 +789,35 @@ impl GeneratorApp {
         });
     }
 
+    /** Display a warning for each way the selected `World` breaks the generation rules.
+
+    Each warning comes with a button to fix it. Displays nothing if the `World` is consistent.
+    */
+    fn inconsistencies_display(&mut self, ui: &mut Ui) {
+        let inconsistencies = self.world.inconsistencies();
+        if inconsistencies.is_empty() {
+            return;
+        }
+
+        ui.add_space(LABEL_SPACING);
+        for inconsistency in inconsistencies {
+            ui.horizontal(|ui| {
+                ui.label(
+                    RichText::new(format!("{} {}", WARNING_ICON, inconsistency))
+                        .color(WARNING_ORANGE),
+                );
+
+                if ui
+                    .small_button("Fix")
+                    .on_hover_text(inconsistency.fix_description())
+                    .clicked()
+                {
+                    self.message(Message::FixWorldInconsistency { inconsistency });
+                }
+            });
+        }
+    }
+
     fn law_level_display(&mut self, ui: &mut Ui) {
         ui.heading("Law Level");
         ui.add_space(LABEL_SPACING);
@@ -498,44 +836,82 @@ impl GeneratorApp {
                             )
                             .clicked()
                         {
-                            self.message(Message::WorldModelUpdated);
+                            // The categories vary around the law level, so they follow it
+                            self.message(Message::RegenWorldLawCategories);
                         }
                     }
-                });
+                })
+                .response
+                .screen_reader_label(WidgetType::ComboBox, "Law level");
 
             if ui
                 .button(RichText::new(DICE_ICON).font(FontId::proportional(BUTTON_FONT_SIZE)))
+                .screen_reader_label(WidgetType::Button, "Reroll law level")
                 .clicked()
             {
                 self.message(Message::RegenWorldLawLevel);
             }
         });
 
-        Grid::new("banned_equipment_grid")
+        ui.add_space(LABEL_SPACING);
+
+        ui.horizontal(|ui| {
+            ui.label(
+                RichText::new("Law Categories")
+                    .font(LABEL_FONT)
+                    .color(LABEL_COLOR),
+            );
+            if ui
+                .button(RichText::new(DICE_ICON).font(FontId::proportional(BUTTON_FONT_SIZE)))
+                .on_hover_text("Roll each category around the law level")
+                .screen_reader_label(WidgetType::Button, "Reroll law categories")
+                .clicked()
+            {
+                self.message(Message::RegenWorldLawCategories);
+            }
+        });
+
+        let max_code = TABLES.law_table.len() as u16 - 1;
+        let mut edited = false;
+        Grid::new("law_categories_grid")
+            .num_columns(3)
             .spacing([FIELD_SPACING / 2.0, LABEL_SPACING])
-            .min_col_width(FIELD_SELECTION_WIDTH)
             .max_col_width(FIELD_SELECTION_WIDTH)
             .striped(true)
             .show(ui, |ui| {
-                ui.label(
-                    RichText::new("Banned Weapons")
-                        .font(LABEL_FONT)
-                        .color(LABEL_COLOR),
-                );
-                ui.label(
-                    RichText::new("Banned Armor")
-                        .font(LABEL_FONT)
-                        .color(LABEL_COLOR),
-                );
+                for header in ["Category", "Level", "Banned"] {
+                    ui.label(RichText::new(header).font(LABEL_FONT).color(LABEL_COLOR));
+                }
                 ui.end_row();
 
-                let law_level = self.world.law_level.code as usize;
-                for i in 0..=law_level {
-                    ui.label(&TABLES.law_table[i].banned_weapons);
-                    ui.label(&TABLES.law_table[i].banned_armor);
+                for category in LawCategory::ALL_VALUES {
+                    let level = self.world.law_category_level(category);
+                    ui.label(category.to_string());
+                    match &mut self.world.law_categories {
+                        Some(categories) => {
+                            edited |= ui
+                                .add(
+                                    DragValue::new(categories.get_mut(category))
+                                        .clamp_range(0..=max_code),
+                                )
+                                .screen_reader_label(
+                                    WidgetType::DragValue,
+                                    &format!("{} law level", category),
+                                )
+                                .changed();
+                        }
+                        None => {
+                            ui.label(level.to_string());
+                        }
+                    }
+                    ui.label(category.banned(level));
                     ui.end_row();
                 }
             });
+
+        if edited {
+            self.message(Message::WorldModelUpdated);
+        }
     }
 
     pub(crate) fn new_world_dialog(&mut self, ui: &mut Ui) {
@@ -546,25 +922,158 @@ impl GeneratorApp {
             ui.heading(self.point.to_string());
             let header_font = TextStyle::Heading.resolve(&Style::default());
             let text = RichText::new("Add New World").font(header_font);
-            if ui.button(text).clicked() {
+            if ui
+                .add_enabled(!self.viewer_mode, Button::new(text))
+                .clicked()
+            {
                 self.message(Message::AddNewWorld);
             }
         });
     }
 
-    /** Tab displaying a large text area for writing notes about the `World`. */
+    /** Tab displaying text areas for writing notes about the `World`, one for notes the players
+    may see and one for the GM's secret notes, which is left out when spoilers are hidden.
+    */
     fn notes_display(&mut self, ui: &mut Ui) {
-        ScrollArea::vertical()
-            .id_source("world_notes")
-            .max_height(ui.available_height() * 0.9)
+        let hides_spoilers = self.hides_spoilers();
+        if !hides_spoilers {
+            self.history_display(ui);
+            if !self.world.roll_log.is_empty() {
+                ui.add_space(LABEL_SPACING);
+                self.roll_log_display(ui);
+            }
+            ui.add_space(LABEL_SPACING);
+        }
+
+        let max_height = if hides_spoilers {
+            ui.available_height() * 0.9
+        } else {
+            ui.available_height() * 0.45
+        };
+        notes_text_edit(ui, "Player Notes", &mut self.world.player_notes, max_height);
+        if !hides_spoilers {
+            ui.add_space(LABEL_SPACING);
+            notes_text_edit(ui, "Secret Notes", &mut self.world.secret_notes, max_height);
+        }
+    }
+
+    /** Display the world that owns the selected world, if any, and the worlds it owns in turn. */
+    fn orbital_facilities_display(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.heading("Orbital Facilities");
+            if ui
+                .button(RichText::new(DICE_ICON).size(BUTTON_FONT_SIZE))
+                .on_hover_text("Work out the facilities from the starport, population, and bases")
+                .screen_reader_label(WidgetType::Button, "Reroll orbital facilities")
+                .clicked()
+            {
+                self.message(Message::RegenWorldOrbitalFacilities);
+            }
+            if ui.button("Add").clicked() {
+                self.message(Message::AddWorldOrbitalFacility);
+            }
+        });
+        ui.add_space(LABEL_SPACING);
+
+        if self.world.orbital_facilities.is_empty() {
+            ui.label("Nothing of note orbits the world.");
+            return;
+        }
+
+        let mut edited = false;
+        Grid::new("world_orbital_facilities_grid")
+            .spacing([FIELD_SPACING / 2.0, LABEL_SPACING])
+            .striped(true)
             .show(ui, |ui| {
-                ui.add(
-                    TextEdit::multiline(&mut self.world.notes)
-                        .desired_width(f32::INFINITY)
-                        .desired_rows(50)
-                        .margin(vec2(64.0, 32.0)),
-                );
+                for header in ["Facility", "Size", "Description"] {
+                    ui.label(RichText::new(header).font(LABEL_FONT).color(LABEL_COLOR));
+                }
+                ui.end_row();
+
+                for (index, facility) in self.world.orbital_facilities.iter_mut().enumerate() {
+                    ComboBox::from_id_source(format!("world_orbital_facility_kind_{}", index))
+                        .selected_text(facility.kind.to_string())
+                        .show_ui(ui, |ui| {
+                            for kind in FacilityKind::ALL_VALUES {
+                                edited |= ui
+                                    .selectable_value(&mut facility.kind, kind, kind.to_string())
+                                    .clicked();
+                            }
+                        })
+                        .response
+                        .screen_reader_label(WidgetType::ComboBox, "Facility");
+                    ComboBox::from_id_source(format!("world_orbital_facility_size_{}", index))
+                        .selected_text(facility.size.to_string())
+                        .show_ui(ui, |ui| {
+                            for size in FacilitySize::ALL_VALUES {
+                                edited |= ui
+                                    .selectable_value(&mut facility.size, size, size.to_string())
+                                    .clicked();
+                            }
+                        })
+                        .response
+                        .screen_reader_label(WidgetType::ComboBox, "Facility size");
+                    edited |= ui
+                        .add(
+                            TextEdit::singleline(&mut facility.description)
+                                .desired_width(FIELD_SELECTION_WIDTH * 1.5),
+                        )
+                        .screen_reader_label(WidgetType::TextEdit, "Facility description")
+                        .changed();
+                    if ui
+                        .small_button(X_ICON)
+                        .screen_reader_label(WidgetType::Button, "Remove facility")
+                        .clicked()
+                    {
+                        self.message_tx
+                            .send(Message::RemoveWorldOrbitalFacility { index });
+                    }
+                    ui.end_row();
+                }
             });
+
+        if edited {
+            self.message(Message::WorldModelUpdated);
+        }
+    }
+
+    fn owner_display(&mut self, ui: &mut Ui) {
+        ui.add_space(LABEL_SPACING * 1.5);
+        ui.label(RichText::new("Owner").font(LABEL_FONT).color(LABEL_COLOR));
+        ui.add_space(LABEL_SPACING);
+
+        let point = self.point;
+        let worlds: Vec<(Point, String)> = self
+            .subsector
+            .get_map()
+            .iter()
+            .filter(|(other, _)| **other != point)
+            .map(|(other, world)| (*other, format!("{} {}", other, world.name)))
+            .collect();
+
+        let owner = self
+            .world
+            .owner
+            .and_then(|owner| worlds.iter().find(|(other, _)| *other == owner))
+            .map_or("Independent".to_string(), |(_, label)| label.clone());
+        ComboBox::from_id_source("owner_selection")
+            .selected_text(owner)
+            .width(FIELD_SELECTION_WIDTH)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut self.world.owner, None, "Independent");
+                for (other, label) in worlds.iter() {
+                    ui.selectable_value(&mut self.world.owner, Some(*other), label);
+                }
+            })
+            .response
+            .screen_reader_label(WidgetType::ComboBox, "Owner")
+            .on_hover_text("World that holds this one as a colony or possession");
+
+        let colonies = self.subsector.colonies_of(&point);
+        if !colonies.is_empty() {
+            let colonies: Vec<String> = colonies.iter().map(|colony| colony.to_string()).collect();
+            ui.label(format!("Colonies: {}", colonies.join(", ")));
+        }
     }
 
     fn planetary_data_display(&mut self, ui: &mut Ui) {
@@ -586,7 +1095,13 @@ impl GeneratorApp {
         self.population_display(ui);
         ui.add_space(FIELD_SPACING);
 
+        self.sophonts_display(ui);
+        ui.add_space(FIELD_SPACING);
+
         self.tech_level_display(ui);
+        ui.add_space(FIELD_SPACING);
+
+        self.system_contents_display(ui);
     }
 
     fn population_display(&mut self, ui: &mut Ui) {
@@ -618,17 +1133,28 @@ impl GeneratorApp {
                             )
                             .clicked()
                         {
-                            self.message(Message::WorldModelUpdated);
+                            self.message(Message::WorldFieldEdited {
+                                field: WorldField::Population,
+                            });
                         }
                     }
-                });
+                })
+                .response
+                .screen_reader_label(WidgetType::ComboBox, "Population");
 
             if ui
                 .button(RichText::new(DICE_ICON).font(FontId::proportional(BUTTON_FONT_SIZE)))
+                .screen_reader_label(WidgetType::Button, "Reroll population")
                 .clicked()
             {
                 self.message(Message::RegenWorldPopulation);
             }
+
+            let count = self.world.population_count();
+            if count > 0 {
+                ui.label(format!("~{}", self.settings.number_format.count(count)))
+                    .on_hover_text("Estimated number of inhabitants");
+            }
         });
     }
 
@@ -640,22 +1166,69 @@ impl GeneratorApp {
     */
     fn profile_display(&mut self, ui: &mut Ui) {
         ui.horizontal(|ui| {
-            // World name editor
-            ui.add(TextEdit::singleline(&mut self.world.name).font(TextStyle::Heading));
+            // World name editor, with a hint of how the name is pronounced
+            ui.vertical(|ui| {
+                ui.add(TextEdit::singleline(&mut self.world.name).font(TextStyle::Heading))
+                    .screen_reader_label(WidgetType::TextEdit, "World name");
+                ui.horizontal(|ui| {
+                    ui.add(
+                        TextEdit::singleline(&mut self.world.pronunciation)
+                            .hint_text("Pronunciation")
+                            .font(LABEL_FONT)
+                            .text_color(LABEL_COLOR)
+                            .frame(false),
+                    )
+                    .on_hover_text("How the world's name is pronounced")
+                    .screen_reader_label(WidgetType::TextEdit, "Pronunciation");
+                    if ui
+                        .small_button(DICE_ICON)
+                        .on_hover_text("Spell out the pronunciation of the name")
+                        .screen_reader_label(WidgetType::Button, "Spell out pronunciation")
+                        .clicked()
+                    {
+                        self.message(Message::RegenWorldPronunciation);
+                    }
+                });
 
-            ui.with_layout(Layout::right_to_left(), |ui| {
-                ui.add_space(FIELD_SPACING);
-                let header_font = TextStyle::Heading.resolve(&Style::default());
+                ComboBox::from_id_source("subtype_selection")
+                    .selected_text(self.world.subtype.to_string())
+                    .width(FIELD_SELECTION_WIDTH)
+                    .show_ui(ui, |ui| {
+                        for subtype in WorldSubtype::ALL_VALUES {
+                            if ui
+                                .selectable_label(
+                                    self.world.subtype == subtype,
+                                    subtype.to_string(),
+                                )
+                                .clicked()
+                            {
+                                self.message(Message::NewWorldSubtypeSelected { subtype });
+                            }
+                        }
+                    })
+                    .response
+                    .on_hover_text("Role of the world in its system, which limits its population")
+                    .screen_reader_label(WidgetType::ComboBox, "World subtype");
+            });
+
+            ui.with_layout(Layout::right_to_left(), |ui| {
+                ui.add_space(FIELD_SPACING);
+                let header_font = TextStyle::Heading.resolve(&Style::default());
 
                 let world_removal_button =
                     Button::new(RichText::new(X_ICON).font(header_font.clone())).fill(NEGATIVE_RED);
-                if ui.add(world_removal_button).clicked() {
+                if ui
+                    .add(world_removal_button)
+                    .screen_reader_label(WidgetType::Button, "Remove world")
+                    .clicked()
+                {
                     self.message(Message::RemoveSelectedWorld);
                 }
 
                 // World regen button
                 if ui
                     .button(RichText::new(DICE_ICON).font(header_font))
+                    .screen_reader_label(WidgetType::Button, "Reroll world")
                     .clicked()
                 {
                     self.message(Message::RegenSelectedWorld);
@@ -683,6 +1256,7 @@ impl GeneratorApp {
                     if ui
                         .button(CLIPBOARD_ICON)
                         .on_hover_text("Click to copy")
+                        .screen_reader_label(WidgetType::Button, "Copy world profile")
                         .clicked()
                     {
                         ui.output().copied_text = self.world.profile_str();
@@ -699,6 +1273,7 @@ impl GeneratorApp {
                     if ui
                         .button(CLIPBOARD_ICON)
                         .on_hover_text("Click to copy")
+                        .screen_reader_label(WidgetType::Button, "Copy trade codes")
                         .clicked()
                     {
                         ui.output().copied_text = self.world.trade_code_str();
@@ -711,82 +1286,199 @@ impl GeneratorApp {
                         .color(LABEL_COLOR),
                 );
 
-                ui.label(
-                    RichText::new("Planetoid Belts")
-                        .font(LABEL_FONT)
-                        .color(LABEL_COLOR),
-                );
+                ui.label(RichText::new("PBG").font(LABEL_FONT).color(LABEL_COLOR));
 
                 ui.label(
-                    RichText::new("Gas Giants")
+                    RichText::new("Extensions")
                         .font(LABEL_FONT)
                         .color(LABEL_COLOR),
                 );
+
+                ui.label(RichText::new("Nobility").font(LABEL_FONT).color(LABEL_COLOR));
                 ui.end_row();
 
                 // Location
-                let response = ui.add(
-                    TextEdit::singleline(&mut self.point_str).desired_width(SHORT_SELECTION_WIDTH),
-                );
-                if response.lost_focus() {
-                    if ui.input().key_pressed(Key::Enter) {
-                        self.message(Message::WorldLocUpdated);
-                    } else {
-                        self.point_str = self.point.to_string();
-                    }
-                }
+                let warning = self.point_buffer.warning(None);
+                ui.horizontal(|ui| {
+                    self.point_buffer.show(ui, &self.point, &self.message_tx);
+                    field_warning(ui, warning);
+                });
 
-                // World profile
-                ui.label(&self.world.profile_str());
+                // World profile, with the travel advisory in its tooltip
+                let profile = ui.label(self.world.profile_str());
+                if !self.world.travel_advisory.is_empty() && !self.hides_spoilers() {
+                    profile.on_hover_text(&self.world.travel_advisory);
+                }
 
                 // Trade codes
-                let response = ui.label(self.world.trade_code_str());
-                if !self.world.trade_codes.is_empty() {
-                    response.on_hover_text(self.world.trade_code_long_str());
-                }
+                ui.horizontal(|ui| {
+                    if self.world.trade_codes.is_empty() {
+                        ui.label(self.world.trade_code_str());
+                    }
+                    for trade_code in self.world.trade_codes.iter() {
+                        let key = format!("{:?}", trade_code);
+                        ui.label(&key)
+                            .on_hover_ui(|ui| rule_hover_ui(ui, RuleTopic::TradeCode, &key));
+                    }
+                });
 
                 // Travel Code
-                ComboBox::from_id_source("travel_code_selection")
-                    .selected_text(self.world.travel_code_str())
-                    .show_ui(ui, |ui| {
-                        for code in [TravelCode::Safe, TravelCode::Amber, TravelCode::Red] {
-                            ui.selectable_value(
-                                &mut self.world.travel_code,
-                                code,
-                                format!("{:?}", code),
-                            );
-                        }
-                    });
-
-                // Planetoid Belts
-                let response = ui.add(
-                    TextEdit::singleline(&mut self.belt_str)
-                        .desired_width(SHORT_SELECTION_WIDTH / 2.0),
-                );
-                if response.lost_focus() {
-                    if ui.input().key_pressed(Key::Enter) {
-                        self.message(Message::WorldPlanetoidBeltsUpdated);
+                ui.horizontal(|ui| {
+                    let travel_code = self.world.travel_code_str();
+                    ComboBox::from_id_source("travel_code_selection")
+                        .selected_text(&travel_code)
+                        .show_ui(ui, |ui| {
+                            for code in [TravelCode::Safe, TravelCode::Amber, TravelCode::Red] {
+                                if ui
+                                    .selectable_value(
+                                        &mut self.world.travel_code,
+                                        code,
+                                        format!("{:?}", code),
+                                    )
+                                    .clicked()
+                                {
+                                    self.world.travel_code_overridden = true;
+                                }
+                            }
+                        })
+                        .response
+.screen_reader_label(WidgetType::ComboBox, "Travel code")
+                        .on_hover_ui(|ui| rule_hover_ui(ui, RuleTopic::TravelCode, &travel_code));
+
+                    let mut auto = !self.world.travel_code_overridden;
+                    let response = ui.checkbox(&mut auto, "Auto");
+                    let response = if auto {
+                        response.on_hover_text(
+                            "Travel code is kept in line with atmosphere, government, and law level",
+                        )
                     } else {
-                        self.belt_str = self
-                            .world
-                            .planetoid_belts
-                            .expect("World planetoid belts should not be None")
-                            .to_string();
+                        ui.colored_label(WARNING_ORANGE, WARNING_ICON)
+                            .on_hover_text("Travel code has been set by hand");
+                        response.on_hover_text("Check to resolve the travel code automatically")
+                    };
+                    if response.changed() {
+                        self.world.travel_code_overridden = !auto;
+                        self.message(Message::WorldModelUpdated);
                     }
-                }
+                });
 
-                // Gas giants
-                let response = ui.add(
-                    TextEdit::singleline(&mut self.gas_giant_str)
-                        .desired_width(SHORT_SELECTION_WIDTH / 2.0),
-                );
-                if response.lost_focus() {
-                    if ui.input().key_pressed(Key::Enter) {
-                        self.message(Message::WorldGasGiantsUpdated);
+                // Population modifier, planetoid belts, and gas giants
+                ui.label(self.world.pbg_str())
+                    .on_hover_text("Population modifier, planetoid belts, and gas giants");
+
+                // T5 extensions
+                ui.label(format!(
+                    "{} {} {}",
+                    self.world.importance_extension(),
+                    self.world.economic_extension(),
+                    self.world.cultural_extension()
+                ))
+                .on_hover_text("Importance {Ix}, Economic (Ex), and Cultural [Cx] extensions");
+
+                // Nobility
+                ui.horizontal(|ui| {
+                    let ranks = self.world.noble_ranks();
+                    let hover_text = if ranks.is_empty() {
+                        "No resident nobles".to_string()
                     } else {
-                        self.gas_giant_str = self.world.gas_giants.to_string();
+                        ranks.join(", ")
+                    };
+                    if ui
+                        .add(TextEdit::singleline(&mut self.world.nobility).desired_width(60.0))
+                        .on_hover_text(hover_text)
+                        .screen_reader_label(WidgetType::TextEdit, "Nobility")
+                        .changed()
+                    {
+                        self.message(Message::WorldModelUpdated);
+                    }
+
+                    let dice =
+                        RichText::new(DICE_ICON).font(FontId::proportional(BUTTON_FONT_SIZE));
+                    if ui
+                        .button(dice)
+                        .on_hover_text("Work out the nobility from the world's data again")
+                        .screen_reader_label(WidgetType::Button, "Regenerate nobility")
+                        .clicked()
+                    {
+                        self.message(Message::RegenWorldNobility);
                     }
+                });
+            });
+
+        // Advisories may name the factions, so they're kept from the players like them
+        if !self.hides_spoilers() {
+            ui.horizontal(|ui| {
+                ui.label(
+                    RichText::new("Travel Advisory")
+                        .font(LABEL_FONT)
+                        .color(LABEL_COLOR),
+                );
+                let dice = RichText::new(DICE_ICON).font(FontId::proportional(BUTTON_FONT_SIZE));
+                if ui
+                    .button(dice)
+                    .on_hover_text("Write the advisory from the world's data again")
+                    .screen_reader_label(WidgetType::Button, "Regenerate travel advisory")
+                    .clicked()
+                {
+                    self.message(Message::RegenWorldTravelAdvisory);
+                }
+                if ui
+                    .add(
+                        TextEdit::singleline(&mut self.world.travel_advisory)
+                            .desired_width(f32::INFINITY),
+                    )
+                    .on_hover_text("Warning to visitors, also shown over the world profile")
+                    .screen_reader_label(WidgetType::TextEdit, "Travel advisory")
+                    .changed()
+                {
+                    self.message(Message::WorldModelUpdated);
+                }
+            });
+        }
+    }
+
+    /** Collapsible list of the dice rolled to generate the `World`, in the order rolled. */
+    fn roll_log_display(&mut self, ui: &mut Ui) {
+        CollapsingHeader::new("Generation Rolls")
+            .id_source("world_roll_log")
+            .show(ui, |ui| {
+                if ui
+                    .button(format!("{} Copy Log", CLIPBOARD_ICON))
+                    .on_hover_text("Copy every roll as plain text")
+                    .clicked()
+                {
+                    self.message(Message::CopyRollLog);
                 }
+
+                ScrollArea::vertical()
+                    .id_source("world_roll_log_scroll")
+                    .max_height(HISTORY_MAX_HEIGHT)
+                    .show(ui, |ui| {
+                        Grid::new("world_roll_log_grid")
+                            .spacing([FIELD_SPACING, LABEL_SPACING])
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for header in ["Field", "Table", "Roll"] {
+                                    ui.label(
+                                        RichText::new(header).font(LABEL_FONT).color(LABEL_COLOR),
+                                    );
+                                }
+                                ui.end_row();
+
+                                for entry in self.world.roll_log.iter() {
+                                    let table = entry
+                                        .table
+                                        .map(|table| table.to_string())
+                                        .unwrap_or_default();
+                                    for roll in entry.rolls.iter() {
+                                        ui.label(&entry.field);
+                                        ui.label(&table);
+                                        ui.label(roll.to_string());
+                                        ui.end_row();
+                                    }
+                                }
+                            });
+                    });
             });
     }
 
@@ -813,27 +1505,35 @@ impl GeneratorApp {
                                 .selectable_value(&mut self.world.size, size, size.to_string())
                                 .clicked()
                             {
-                                self.message(Message::WorldModelUpdated);
+                                self.message(Message::WorldFieldEdited {
+                                    field: WorldField::Size,
+                                });
                             }
                         }
-                    });
+                    })
+                    .response
+                    .screen_reader_label(WidgetType::ComboBox, "Size");
 
                 // Diameter
-                if ui
-                    .add(
-                        TextEdit::singleline(&mut self.diameter_str)
-                            .desired_width(SHORT_SELECTION_WIDTH),
-                    )
-                    .lost_focus()
-                {
-                    self.message(Message::WorldDiameterUpdated);
-                }
+                let warning = self.diameter_buffer.warning(self.world.diameter_warning());
+                ui.horizontal(|ui| {
+                    self.diameter_buffer
+                        .show(ui, &self.world.diameter, &self.message_tx);
+                    // Diameters are always entered in kilometers
+                    let numbers = &self.settings.number_format;
+                    match numbers.length_unit {
+                        LengthUnit::Kilometers => ui.label("km"),
+                        _ => ui.label(format!("km ({})", numbers.length(self.world.diameter))),
+                    };
+                    field_warning(ui, warning);
+                });
 
                 // Gravity
                 ui.label(self.world.gravity());
 
                 if ui
                     .button(RichText::new(DICE_ICON).font(FontId::proportional(BUTTON_FONT_SIZE)))
+                    .screen_reader_label(WidgetType::Button, "Reroll size")
                     .clicked()
                 {
                     self.message(Message::RegenWorldSize);
@@ -841,6 +1541,224 @@ impl GeneratorApp {
             });
     }
 
+    /** Display the non-human species of the `World` and the human share of the population. */
+    fn sophonts_display(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label(
+                RichText::new("Sophonts")
+                    .font(LABEL_FONT)
+                    .color(LABEL_COLOR),
+            );
+            if ui
+                .button(RichText::new(DICE_ICON).size(BUTTON_FONT_SIZE))
+                .on_hover_text("Generate native and minor species")
+                .screen_reader_label(WidgetType::Button, "Reroll sophonts")
+                .clicked()
+            {
+                self.message(Message::RegenWorldSophonts);
+            }
+            if ui.button("Add").clicked() {
+                self.message(Message::AddWorldSophont);
+            }
+        });
+        ui.add_space(LABEL_SPACING);
+
+        let mut edited = false;
+        Grid::new("sophonts_grid")
+            .spacing([FIELD_SPACING / 2.0, LABEL_SPACING])
+            .show(ui, |ui| {
+                for (index, sophont) in self.world.sophonts.iter_mut().enumerate() {
+                    edited |= ui
+                        .add(TextEdit::singleline(&mut sophont.name).desired_width(120.0))
+                        .screen_reader_label(WidgetType::TextEdit, "Sophont name")
+                        .changed();
+                    edited |= ui
+                        .add(
+                            DragValue::new(&mut sophont.tenths)
+                                .clamp_range(0..=10)
+                                .suffix("/10"),
+                        )
+                        .on_hover_text("Share of the population in tenths")
+                        .screen_reader_label(WidgetType::DragValue, "Share of the population")
+                        .changed();
+                    edited |= ui
+                        .checkbox(&mut sophont.native, "Native")
+                        .on_hover_text("This is the species' homeworld")
+                        .changed();
+                    if ui
+                        .small_button(X_ICON)
+                        .screen_reader_label(WidgetType::Button, "Remove sophont")
+                        .clicked()
+                    {
+                        self.message_tx.send(Message::RemoveWorldSophont { index });
+                    }
+                    ui.end_row();
+                }
+            });
+
+        ui.label(format!("Humans: {}/10", self.world.human_tenths()));
+        if edited {
+            self.message(Message::WorldModelUpdated);
+        }
+    }
+
+    fn starport_details_display(&mut self, ui: &mut Ui) {
+        let class = self.world.starport.class.clone();
+        let details = match &mut self.world.starport.details {
+            Some(details) => details,
+            None => return,
+        };
+
+        let mut edited = false;
+        Grid::new("starport_details_grid")
+            .spacing([FIELD_SPACING, LABEL_SPACING])
+            .show(ui, |ui| {
+                ui.label(
+                    RichText::new("Highport")
+                        .font(LABEL_FONT)
+                        .color(LABEL_COLOR),
+                );
+                ui.label(
+                    RichText::new("Shipyard")
+                        .font(LABEL_FONT)
+                        .color(LABEL_COLOR),
+                );
+                ui.label(RichText::new("Repairs").font(LABEL_FONT).color(LABEL_COLOR));
+                ui.label(RichText::new("Layout").font(LABEL_FONT).color(LABEL_COLOR));
+                ui.end_row();
+
+                edited |= ui
+                    .checkbox(&mut details.highport, "")
+                    .screen_reader_label(WidgetType::Checkbox, "Highport")
+                    .changed();
+
+                ComboBox::from_id_source("starport_shipyard_selection")
+                    .selected_text(details.shipyard.to_string())
+                    .show_ui(ui, |ui| {
+                        for shipyard in Shipyard::ALL_VALUES {
+                            edited |= ui
+                                .selectable_value(
+                                    &mut details.shipyard,
+                                    shipyard,
+                                    shipyard.to_string(),
+                                )
+                                .clicked();
+                        }
+                    })
+                    .response
+                    .screen_reader_label(WidgetType::ComboBox, "Shipyard");
+
+                ComboBox::from_id_source("starport_repairs_selection")
+                    .selected_text(details.repairs.to_string())
+                    .show_ui(ui, |ui| {
+                        for repairs in Repairs::ALL_VALUES {
+                            edited |= ui
+                                .selectable_value(
+                                    &mut details.repairs,
+                                    repairs,
+                                    repairs.to_string(),
+                                )
+                                .clicked();
+                        }
+                    })
+                    .response
+                    .screen_reader_label(WidgetType::ComboBox, "Repairs");
+
+                ui.label(details.layout_str(&class));
+
+                if ui
+                    .button(RichText::new(DICE_ICON).font(FontId::proportional(BUTTON_FONT_SIZE)))
+                    .on_hover_text("Reroll the highport and reset the shipyard and repairs")
+                    .screen_reader_label(WidgetType::Button, "Reroll starport details")
+                    .clicked()
+                {
+                    self.message_tx.send(Message::RegenWorldStarportDetails);
+                }
+            });
+
+        if edited {
+            self.message(Message::WorldModelUpdated);
+        }
+    }
+
+    fn starport_fuel_market_display(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label(
+                RichText::new("Fuel Market")
+                    .font(LABEL_FONT)
+                    .color(LABEL_COLOR),
+            )
+            .on_hover_text("Prices and availability of fuel from starport class and trade codes");
+            if ui
+                .button(RichText::new(DICE_ICON).font(FontId::proportional(BUTTON_FONT_SIZE)))
+                .on_hover_text("Reroll this starport's fuel market")
+                .screen_reader_label(WidgetType::Button, "Reroll fuel market")
+                .clicked()
+            {
+                self.message_tx.send(Message::RegenWorldFuelMarket);
+            }
+            if ui
+                .button("Market Tick")
+                .on_hover_text(
+                    "Reroll the fuel market of every world, as time passes in the campaign",
+                )
+                .clicked()
+            {
+                self.message_tx.send(Message::FuelMarketTick);
+            }
+        });
+        ui.add_space(LABEL_SPACING);
+
+        let market = match &mut self.world.fuel_market {
+            Some(market) => market,
+            None => return,
+        };
+        let mut edited = false;
+        Grid::new("starport_fuel_market_grid")
+            .num_columns(3)
+            .spacing([FIELD_SPACING, LABEL_SPACING])
+            .striped(true)
+            .show(ui, |ui| {
+                for header in ["Fuel", "Availability", "Cr/ton"] {
+                    ui.label(RichText::new(header).font(LABEL_FONT).color(LABEL_COLOR));
+                }
+                ui.end_row();
+
+                for kind in FuelKind::ALL_VALUES {
+                    let fuel = market.get_mut(kind);
+                    ui.label(kind.to_string());
+                    ComboBox::from_id_source(format!("fuel_availability_selection_{}", kind))
+                        .selected_text(fuel.availability.to_string())
+                        .show_ui(ui, |ui| {
+                            for availability in FuelAvailability::ALL_VALUES {
+                                edited |= ui
+                                    .selectable_value(
+                                        &mut fuel.availability,
+                                        availability,
+                                        availability.to_string(),
+                                    )
+                                    .clicked();
+                            }
+                        })
+                        .response
+                        .screen_reader_label(
+                            WidgetType::ComboBox,
+                            &format!("{} fuel availability", kind),
+                        );
+                    let available = fuel.availability != FuelAvailability::Unavailable;
+                    edited |= ui
+                        .add_enabled(available, DragValue::new(&mut fuel.price).speed(5.0))
+                        .screen_reader_label(WidgetType::DragValue, &format!("{} fuel price", kind))
+                        .changed();
+                    ui.end_row();
+                }
+            });
+
+        if edited {
+            self.message(Message::WorldModelUpdated);
+        }
+    }
+
     fn starport_information_display(&mut self, ui: &mut Ui) {
         ui.heading("Starport Information");
         ui.add_space(LABEL_SPACING);
@@ -849,8 +1767,9 @@ impl GeneratorApp {
         ui.add_space(LABEL_SPACING);
 
         ui.horizontal(|ui| {
+            let class = self.world.starport.class.to_string();
             ComboBox::from_id_source("starport_class_selection")
-                .selected_text(self.world.starport.class.to_string())
+                .selected_text(&class)
                 .width(SHORT_SELECTION_WIDTH)
                 .show_ui(ui, |ui| {
                     use StarportClass::*;
@@ -863,10 +1782,14 @@ impl GeneratorApp {
                             self.message(Message::NewStarportClassSelected);
                         }
                     }
-                });
+                })
+                .response
+                .screen_reader_label(WidgetType::ComboBox, "Starport class")
+                .on_hover_ui(|ui| rule_hover_ui(ui, RuleTopic::StarportClass, &class));
 
             if ui
                 .button(RichText::new(DICE_ICON).font(FontId::proportional(BUTTON_FONT_SIZE)))
+                .screen_reader_label(WidgetType::Button, "Reroll starport")
                 .clicked()
             {
                 self.message(Message::RegenWorldStarport);
@@ -891,21 +1814,35 @@ impl GeneratorApp {
                 );
                 ui.end_row();
 
-                if ui
-                    .add(
-                        TextEdit::singleline(&mut self.berthing_cost_str)
-                            .desired_width(SHORT_SELECTION_WIDTH),
-                    )
-                    .lost_focus()
-                {
-                    self.message(Message::WorldBerthingCostsUpdated);
-                }
+                let warning = self
+                    .berthing_cost_buffer
+                    .warning(self.world.berthing_cost_warning());
+                ui.horizontal(|ui| {
+                    self.berthing_cost_buffer.show(
+                        ui,
+                        &self.world.starport.berthing_cost,
+                        &self.message_tx,
+                    );
+                    field_warning(ui, warning);
+                });
 
                 ui.label(&self.world.starport.fuel);
                 ui.label(&self.world.starport.facilities);
             });
         ui.add_space(FIELD_SPACING);
 
+        self.starport_details_display(ui);
+        ui.add_space(FIELD_SPACING);
+
+        self.starport_fuel_market_display(ui);
+        ui.add_space(FIELD_SPACING);
+
+        self.starport_traffic_display(ui);
+        ui.add_space(FIELD_SPACING);
+
+        self.orbital_facilities_display(ui);
+        ui.add_space(FIELD_SPACING);
+
         ui.heading("Bases");
         Grid::new("bases_grid")
             .spacing([FIELD_SPACING, LABEL_SPACING])
@@ -921,18 +1858,136 @@ impl GeneratorApp {
                 ui.label(RichText::new("Pirate").font(LABEL_FONT).color(LABEL_COLOR));
                 ui.end_row();
 
-                ui.checkbox(&mut self.world.has_naval_base, "");
-                ui.checkbox(&mut self.world.has_scout_base, "");
-                ui.checkbox(&mut self.world.has_research_base, "");
-                ui.checkbox(&mut self.world.has_tas, "");
-                ui.checkbox(&mut self.world.has_pirate_base, "");
+                let bases = [
+                    (&mut self.world.has_naval_base, "Naval base"),
+                    (&mut self.world.has_scout_base, "Scout base"),
+                    (&mut self.world.has_research_base, "Research base"),
+                    (&mut self.world.has_tas, "TAS hostel"),
+                    (&mut self.world.has_pirate_base, "Pirate base"),
+                ];
+                for (has_base, label) in bases {
+                    ui.checkbox(has_base, "")
+                        .screen_reader_label(WidgetType::Checkbox, label);
+                }
+            });
+    }
+
+    fn starport_traffic_display(&mut self, ui: &mut Ui) {
+        let traffic = self.world.weekly_traffic();
+        ui.label(
+            RichText::new("Weekly Traffic")
+                .font(LABEL_FONT)
+                .color(LABEL_COLOR),
+        )
+        .on_hover_text(
+            "Estimated ship arrivals per week from starport class, population, and trade codes",
+        );
+        ui.add_space(LABEL_SPACING);
+
+        Grid::new("starport_traffic_grid")
+            .spacing([FIELD_SPACING, LABEL_SPACING])
+            .show(ui, |ui| {
+                for class in TonnageClass::ALL_VALUES {
+                    ui.label(class.to_string());
+                    ui.label(traffic.arrivals_of(class).to_string());
+                    ui.end_row();
+                }
+            });
+
+        match traffic.hours_between_arrivals() {
+            Some(hours) => ui.label(format!(
+                "{} ships/week, about one every {:.1} hours",
+                traffic.total(),
+                hours
+            )),
+            None => ui.label("No regular traffic"),
+        };
+    }
+
+    fn system_contents_display(&mut self, ui: &mut Ui) {
+        Grid::new("world_system_contents_grid")
+            .spacing([FIELD_SPACING, LABEL_SPACING])
+            .show(ui, |ui| {
+                ui.label(
+                    RichText::new("Planetoid Belts")
+                        .font(LABEL_FONT)
+                        .color(LABEL_COLOR),
+                );
+                ui.label("");
+                ui.label(
+                    RichText::new("Gas Giants")
+                        .font(LABEL_FONT)
+                        .color(LABEL_COLOR),
+                );
+                ui.end_row();
+
+                // Planetoid belts
+                let warning = self.belt_buffer.warning(None);
+                ui.horizontal(|ui| {
+                    let belts = self
+                        .world
+                        .planetoid_belts
+                        .expect("World planetoid belts should not be None");
+                    self.belt_buffer.show(ui, &belts, &self.message_tx);
+                    field_warning(ui, warning);
+                });
+
+                if ui
+                    .button(RichText::new(DICE_ICON).font(FontId::proportional(BUTTON_FONT_SIZE)))
+                    .screen_reader_label(WidgetType::Button, "Reroll planetoid belts")
+                    .clicked()
+                {
+                    self.message(Message::RegenWorldPlanetoidBelts);
+                }
+
+                // Gas giants
+                let warning = self.gas_giant_buffer.warning(None);
+                ui.horizontal(|ui| {
+                    self.gas_giant_buffer
+                        .show(ui, &self.world.gas_giants, &self.message_tx);
+                    field_warning(ui, warning);
+                });
+
+                if ui
+                    .button(RichText::new(DICE_ICON).font(FontId::proportional(BUTTON_FONT_SIZE)))
+                    .screen_reader_label(WidgetType::Button, "Reroll gas giants")
+                    .clicked()
+                {
+                    self.message(Message::RegenWorldGasGiants);
+                }
             });
+
+        ui.add_space(LABEL_SPACING);
+        ui.label(RichText::new("Stellar").font(LABEL_FONT).color(LABEL_COLOR));
+        ui.horizontal(|ui| {
+            if ui
+                .add(TextEdit::singleline(&mut self.world.stellar).desired_width(160.0))
+                .on_hover_text("T5 stellar data of the system, e.g. \"G2 V M5 V\"")
+                .screen_reader_label(WidgetType::TextEdit, "Stellar data")
+                .changed()
+            {
+                self.message(Message::WorldModelUpdated);
+            }
+
+            if ui
+                .button(RichText::new(DICE_ICON).font(FontId::proportional(BUTTON_FONT_SIZE)))
+                .screen_reader_label(WidgetType::Button, "Reroll stellar")
+                .clicked()
+            {
+                self.message(Message::RegenWorldStellar);
+            }
+        });
     }
 
     /** Display a row of selectable "tabs" of data for the user to look through. */
     fn tab_labels(&mut self, ui: &mut Ui) {
+        let tab_labels: &[TabLabel] = if self.player_view {
+            &TabLabel::PLAYER_VIEW_VALUES
+        } else {
+            &TabLabel::ALL_VALUES
+        };
         ui.horizontal(|ui| {
-            for tab_label in TabLabel::ALL_VALUES {
+            for &tab_label in tab_labels {
                 let text = tab_label.to_string();
                 ui.selectable_value(&mut self.tab, tab_label, text);
             }
@@ -968,10 +2023,13 @@ impl GeneratorApp {
                             self.message(Message::WorldModelUpdated);
                         }
                     }
-                });
+                })
+                .response
+                .screen_reader_label(WidgetType::ComboBox, "Technology level");
 
             if ui
                 .button(RichText::new(DICE_ICON).font(FontId::proportional(BUTTON_FONT_SIZE)))
+                .screen_reader_label(WidgetType::Button, "Reroll tech level")
                 .clicked()
             {
                 self.message(Message::RegenWorldTechLevel);
@@ -1008,13 +2066,18 @@ impl GeneratorApp {
                             )
                             .clicked()
                         {
-                            self.message(Message::WorldModelUpdated);
+                            self.message(Message::WorldFieldEdited {
+                                field: WorldField::Temperature,
+                            });
                         }
                     }
-                });
+                })
+                .response
+                .screen_reader_label(WidgetType::ComboBox, "Temperature");
 
             if ui
                 .button(RichText::new(DICE_ICON).font(FontId::proportional(BUTTON_FONT_SIZE)))
+                .screen_reader_label(WidgetType::Button, "Reroll temperature")
                 .clicked()
             {
                 self.message(Message::RegenWorldTemperature);
@@ -1029,23 +2092,158 @@ impl GeneratorApp {
     beneath.
     */
     pub(crate) fn world_data_display(&mut self, ui: &mut Ui) {
+        self.world_data(ui, true);
+    }
+
+    /** Variant of the world data display in which only the tabs can be changed. */
+    pub(crate) fn read_only_world_data_display(&mut self, ui: &mut Ui) {
+        self.world_data(ui, false);
+    }
+
+    fn world_data(&mut self, ui: &mut Ui, editable: bool) {
         ui.vertical(|ui| {
-            self.profile_display(ui);
+            ui.add_enabled_ui(editable, |ui| self.profile_display(ui));
+            if editable {
+                self.inconsistencies_display(ui);
+            }
             ui.add_space(FIELD_SPACING);
 
             self.tab_labels(ui);
             ui.separator();
 
-            use TabLabel::*;
-            match self.tab {
-                WorldSurvey => self.world_survey_display(ui),
-                GovernmentLaw => self.government_law_display(ui),
-                Factions => self.factions_display(ui),
-                CultureErrata => self.culture_errata_display(ui),
-                Notes => self.notes_display(ui),
+            ui.add_enabled_ui(editable, |ui| {
+                use TabLabel::*;
+                match self.tab {
+                    WorldSurvey => self.world_survey_display(ui),
+                    GovernmentLaw => self.government_law_display(ui),
+                    Factions => self.factions_display(ui),
+                    CultureErrata => self.culture_errata_display(ui),
+                    Trade => self.trade_display(ui),
+                    Encounters => self.encounters_display(ui),
+                    Notes => self.notes_display(ui),
+                    Rules => self.rules_display(ui),
+                }
+            });
+
+            if editable {
+                self.apply_revert_buttons(ui);
+            }
+        });
+    }
+
+    /** Tab displaying the trade goods market of the `World`.
+
+    Every good in the trade goods table is listed along with the tons of it available to buy here,
+    if any, and the DMs that apply to buying and selling it here.
+    */
+    fn trade_display(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label(
+                RichText::new("Trade Goods")
+                    .font(LABEL_FONT)
+                    .color(LABEL_COLOR),
+            );
+            if ui
+                .button(RichText::new(DICE_ICON).size(BUTTON_FONT_SIZE))
+                .on_hover_text("Reroll available trade goods")
+                .screen_reader_label(WidgetType::Button, "Reroll trade goods")
+                .clicked()
+            {
+                self.message(Message::RegenWorldTradeGoods);
+            }
+        });
+        ui.add_space(LABEL_SPACING);
+
+        ScrollArea::vertical()
+            .id_source("world_trade_goods")
+            .max_height(ui.available_height() * 0.9)
+            .show(ui, |ui| {
+                Grid::new("world_trade_goods_grid")
+                    .spacing([FIELD_SPACING, LABEL_SPACING])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for header in ["Good", "Tons", "Base Price", "Purchase DM", "Sale DM"] {
+                            ui.label(RichText::new(header).font(LABEL_FONT).color(LABEL_COLOR));
+                        }
+                        ui.end_row();
+
+                        for record in TRADE_GOODS_TABLE.iter() {
+                            let good = RichText::new(&record.good);
+                            if record.illegal {
+                                ui.label(good.color(WARNING_ORANGE))
+                                    .on_hover_text("Trading this good is illegal");
+                            } else {
+                                ui.label(good);
+                            }
+
+                            let tons = self
+                                .world
+                                .trade_goods
+                                .iter()
+                                .find(|good| good.code == record.code)
+                                .map(|good| good.tons.to_string())
+                                .unwrap_or_else(|| "-".to_string());
+                            ui.label(tons);
+
+                            ui.label(format!("Cr{}", record.base_price));
+
+                            let dm_hover = |dms: &[_]| {
+                                dms.iter()
+                                    .map(|(condition, dm)| format!("{} {:+}", condition, dm))
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
+                            };
+                            ui.label(format!("{:+}", record.purchase_dm(&self.world)))
+                                .on_hover_text(dm_hover(&record.purchase_dms));
+                            ui.label(format!("{:+}", record.sale_dm(&self.world)))
+                                .on_hover_text(dm_hover(&record.sale_dms));
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+
+    /** Display a thumbnail of the image attached to the `World`, along with controls to change it. */
+    fn world_image_display(&mut self, ui: &mut Ui) {
+        ui.heading("Image");
+        ui.add_space(LABEL_SPACING);
+
+        if let Some(image) = &self.world_image {
+            let mut size = image.size_vec2();
+            size *= (WORLD_IMAGE_MAX_SIZE / size.x.max(size.y)).min(1.0);
+            image.show_size(ui, size);
+            ui.add_space(LABEL_SPACING);
+        } else if self.world.image.is_some() {
+            ui.label(
+                RichText::new(format!("{} Could not load image", WARNING_ICON))
+                    .color(WARNING_ORANGE),
+            );
+            ui.add_space(LABEL_SPACING);
+        }
+
+        ui.horizontal(|ui| {
+            if ui
+                .button("Link...")
+                .on_hover_text("Store the path to an image file with the subsector")
+                .clicked()
+            {
+                self.message(Message::AttachWorldImage { embed: false });
+            }
+
+            if ui
+                .button("Embed...")
+                .on_hover_text("Store a copy of an image file inside the subsector")
+                .clicked()
+            {
+                self.message(Message::AttachWorldImage { embed: true });
             }
 
-            self.apply_revert_buttons(ui);
+            if ui
+                .add_enabled(self.world.image.is_some(), Button::new("Remove"))
+                .clicked()
+            {
+                self.message(Message::RemoveWorldImage);
+            }
         });
     }
 
@@ -1054,126 +2252,258 @@ impl GeneratorApp {
         ui.columns(2, |columns| {
             self.planetary_data_display(&mut columns[0]);
             self.starport_information_display(&mut columns[1]);
+            columns[1].add_space(FIELD_SPACING);
+            self.world_thumbnail_display(&mut columns[1]);
+            columns[1].add_space(FIELD_SPACING);
+            self.world_image_display(&mut columns[1]);
         });
     }
 
+    /** Display a procedural picture of the `World`'s surface, redrawn whenever its planetology
+    changes.
+    */
+    fn world_thumbnail_display(&mut self, ui: &mut Ui) {
+        ui.heading("Surface");
+        ui.add_space(LABEL_SPACING);
+
+        let svg = self.world.thumbnail_svg();
+        if self
+            .world_thumbnail
+            .as_ref()
+            .is_none_or(|(drawn_svg, _)| *drawn_svg != svg)
+        {
+            self.world_thumbnail = rasterize_thumbnail(&svg).ok().map(|image| (svg, image));
+        }
+
+        if let Some((_, image)) = &self.world_thumbnail {
+            image.show(ui);
+        }
+    }
+
+    /** Display each of the `World`'s tags in `columns`, going back to the first column below the
+    others if there are more tags than columns.
+    */
     fn world_tags_display(&mut self, columns: &mut [Ui]) {
-        // In a perfect world, this would loop through the `Subsector::world_tags` array with
-        // something like,
-        //
-        // `for (index, (column, world_tag)) in zip(columns, world_tags.iter_mut()).enumerate()`
-        //
-        // Unfortunately, Rust's borrowing rules will not allow mutably borrowing the
-        // `world_tags` iterator and calling a method at the same time. The only way around this
-        // would be to collect copies of the world tags into a temporary collection or to
-        // heavily refactor the `Subsector` struct to allow for interior mutability with
-        // `RefCell`.
-        //
-        // The length of `world_tags` isn't expected to ever grow, so this manual option works
-        // for now. Refactoring for interior mutability would be a "nice-to-have" in the distant
-        // future for several reasons, but copying arbitrarily long `description` strings into
-        // a temporary collection is a no-go.
-        let index = 0;
-        columns[index].heading("World Tags");
-        columns[index].add_space(LABEL_SPACING);
-        columns[index].horizontal(|ui| {
-            let code = self.world.world_tags[index].code as usize;
-            ComboBox::from_id_source(format!("world_tag_{}_selection", index))
-                .selected_text(&TABLES.world_tag_table[code].tag)
-                .width(FIELD_SELECTION_WIDTH)
-                .show_ui(ui, |ui| {
-                    for item in TABLES.world_tag_table.iter() {
-                        if ui
-                            .selectable_value(
-                                &mut self.world.world_tags[index].tag,
-                                item.tag.clone(),
-                                &item.tag,
-                            )
-                            .clicked()
-                        {
-                            self.message(Message::NewWorldTagSelected {
-                                index,
-                                new_code: item.code,
-                            })
+        // Messages go through the sender alone so the tags can be borrowed mutably all the while
+        let message_tx = &self.message_tx;
+        let num_rows = self.world.world_tags.len().div_ceil(columns.len());
+        for (index, world_tag) in self.world.world_tags.iter_mut().enumerate() {
+            let row = index / columns.len();
+            let ui = &mut columns[index % columns.len()];
+
+            if row == 0 {
+                // Every column but the first gets an empty heading to line up with it
+                ui.heading(if index == 0 { "World Tags" } else { "" });
+                ui.add_space(LABEL_SPACING);
+            } else {
+                ui.add_space(FIELD_SPACING);
+            }
+
+            ui.horizontal(|ui| {
+                let code = world_tag.code as usize;
+                ComboBox::from_id_source(format!("world_tag_{}_selection", index))
+                    .selected_text(&TABLES.world_tag_table[code].tag)
+                    .width(FIELD_SELECTION_WIDTH)
+                    .show_ui(ui, |ui| {
+                        for item in TABLES.world_tag_table.iter() {
+                            if ui
+                                .selectable_value(&mut world_tag.tag, item.tag.clone(), &item.tag)
+                                .clicked()
+                            {
+                                message_tx.send(Message::NewWorldTagSelected {
+                                    index,
+                                    new_code: item.code,
+                                })
+                            }
                         }
-                    }
+                    })
+                    .response
+                    .screen_reader_label(WidgetType::ComboBox, &format!("World tag {}", index + 1));
+
+                if ui
+                    .button(RichText::new(DICE_ICON).font(FontId::proportional(BUTTON_FONT_SIZE)))
+                    .screen_reader_label(WidgetType::Button, "Reroll tag")
+                    .clicked()
+                {
+                    message_tx.send(Message::RegenWorldTag { index });
+                }
+            });
+            ui.add_space(LABEL_SPACING * 1.5);
+
+            ui.label(
+                RichText::new("Description")
+                    .font(LABEL_FONT)
+                    .color(LABEL_COLOR),
+            );
+            ui.add_space(LABEL_SPACING);
+
+            // Tags stacked in a column share what height is left in it
+            let max_height = ui.available_height() * 0.9 / (num_rows - row) as f32;
+            ScrollArea::vertical()
+                .id_source(format!("world_tag_{}_description", index))
+                .max_height(max_height)
+                .show(ui, |ui| {
+                    ui.add(TextEdit::multiline(&mut world_tag.description))
+                        .screen_reader_label(
+                            WidgetType::TextEdit,
+                            &format!("World tag {} description", index + 1),
+                        );
                 });
+        }
+    }
+}
 
-            if ui
-                .button(RichText::new(DICE_ICON).font(FontId::proportional(BUTTON_FONT_SIZE)))
-                .clicked()
-            {
-                self.message(Message::RegenWorldTag { index });
-            }
-        });
-        columns[index].add_space(LABEL_SPACING * 1.5);
+/** Decode the bytes of a PNG or JPEG image file into a [`RetainedImage`].
+
+# Returns
+- `Ok<RetainedImage>` if successful,
+- `Err<String>` if the bytes are not a supported image format or are invalid
+*/
+pub(crate) fn decode_image(name: &str, bytes: &[u8]) -> Result<RetainedImage, String> {
+    const PNG_SIGNATURE: &[u8] = &[0x89, b'P', b'N', b'G'];
+    const JPEG_SIGNATURE: &[u8] = &[0xFF, 0xD8];
+
+    let image = if bytes.starts_with(PNG_SIGNATURE) {
+        decode_png(bytes)?
+    } else if bytes.starts_with(JPEG_SIGNATURE) {
+        decode_jpeg(bytes)?
+    } else {
+        return Err("Unsupported image format; only PNG and JPEG images are supported".to_string());
+    };
+
+    Ok(RetainedImage::from_color_image(name, image))
+}
 
-        columns[index].label(
-            RichText::new("Description")
-                .font(LABEL_FONT)
-                .color(LABEL_COLOR),
-        );
-        columns[index].add_space(LABEL_SPACING);
+fn decode_jpeg(bytes: &[u8]) -> Result<ColorImage, String> {
+    let mut decoder = jpeg_decoder::Decoder::new(bytes);
+    let pixels = decoder.decode().map_err(|err| err.to_string())?;
+    let info = decoder
+        .info()
+        .ok_or_else(|| "Failed to read JPEG metadata".to_string())?;
+    let size = [info.width as usize, info.height as usize];
+
+    match info.pixel_format {
+        jpeg_decoder::PixelFormat::RGB24 => Ok(ColorImage::from_rgba_unmultiplied(
+            size,
+            &rgb_to_rgba(&pixels),
+        )),
+        jpeg_decoder::PixelFormat::L8 => Ok(ColorImage::from_rgba_unmultiplied(
+            size,
+            &gray_to_rgba(&pixels),
+        )),
+        format => Err(format!("Unsupported JPEG pixel format {:?}", format)),
+    }
+}
 
-        ScrollArea::vertical()
-            .id_source(format!("world_tag_{}_description", index))
-            .max_height(columns[index].available_height() * 0.9)
-            .show(&mut columns[index], |ui| {
-                ui.add(TextEdit::multiline(
-                    &mut self.world.world_tags[index].description,
-                ));
-            });
+fn decode_png(bytes: &[u8]) -> Result<ColorImage, String> {
+    let mut decoder = png::Decoder::new(bytes);
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder.read_info().map_err(|err| err.to_string())?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader
+        .next_frame(&mut buffer)
+        .map_err(|err| err.to_string())?;
+    let pixels = &buffer[..info.buffer_size()];
+    let size = [info.width as usize, info.height as usize];
+
+    let rgba = match info.color_type {
+        png::ColorType::Rgba => pixels.to_vec(),
+        png::ColorType::Rgb => rgb_to_rgba(pixels),
+        png::ColorType::Grayscale => gray_to_rgba(pixels),
+        png::ColorType::GrayscaleAlpha => pixels
+            .chunks_exact(2)
+            .flat_map(|p| [p[0], p[0], p[0], p[1]])
+            .collect(),
+        png::ColorType::Indexed => unreachable!("Indexed PNGs should be expanded to RGB"),
+    };
+    Ok(ColorImage::from_rgba_unmultiplied(size, &rgba))
+}
 
-        let index = 1;
-        // This is just to push down the rest of the column in line
-        columns[index].heading("");
-        columns[index].add_space(LABEL_SPACING);
-        columns[index].horizontal(|ui| {
-            let code = self.world.world_tags[index].code as usize;
-            ComboBox::from_id_source(format!("world_tag_{}_selection", index))
-                .selected_text(&TABLES.world_tag_table[code].tag)
-                .width(FIELD_SELECTION_WIDTH)
-                .show_ui(ui, |ui| {
-                    for item in TABLES.world_tag_table.iter() {
-                        if ui
-                            .selectable_value(
-                                &mut self.world.world_tags[index].tag,
-                                item.tag.clone(),
-                                &item.tag,
-                            )
-                            .clicked()
-                        {
-                            self.message(Message::NewWorldTagSelected {
-                                index,
-                                new_code: item.code,
-                            })
-                        }
-                    }
-                });
+/** Show a small warning icon with `warning` as its hover text if there is a warning. */
+fn field_warning(ui: &mut Ui, warning: Option<String>) {
+    if let Some(warning) = warning {
+        ui.colored_label(WARNING_ORANGE, WARNING_ICON)
+            .on_hover_text(warning);
+    }
+}
 
-            if ui
-                .button(RichText::new(DICE_ICON).font(FontId::proportional(BUTTON_FONT_SIZE)))
-                .clicked()
-            {
-                self.message(Message::RegenWorldTag { index });
+fn gray_to_rgba(pixels: &[u8]) -> Vec<u8> {
+    pixels.iter().flat_map(|&l| [l, l, l, u8::MAX]).collect()
+}
+
+/** Label a value in the world history, cutting long values short and showing them in full on
+hover.
+*/
+fn history_value_label(ui: &mut Ui, value: &str) {
+    if value.chars().count() > HISTORY_VALUE_MAX_CHARS {
+        let short: String = value.chars().take(HISTORY_VALUE_MAX_CHARS).collect();
+        ui.label(short + "…").on_hover_text(value);
+    } else {
+        ui.label(value);
+    }
+}
+
+/** Show a labeled, scrolling text area for one set of a world's notes, no taller than
+`max_height`.
+*/
+fn notes_text_edit(ui: &mut Ui, label: &str, notes: &mut String, max_height: f32) {
+    ui.label(RichText::new(label).font(LABEL_FONT).color(LABEL_COLOR));
+    ScrollArea::vertical()
+        .id_source(label)
+        .max_height(max_height)
+        .show(ui, |ui| {
+            ui.add(
+                TextEdit::multiline(notes)
+                    .desired_width(f32::INFINITY)
+                    .desired_rows(25)
+                    .margin(vec2(64.0, 32.0)),
+            )
+            .screen_reader_label(WidgetType::TextEdit, label);
+        });
+}
+
+fn rgb_to_rgba(pixels: &[u8]) -> Vec<u8> {
+    pixels
+        .chunks_exact(3)
+        .flat_map(|p| [p[0], p[1], p[2], u8::MAX])
+        .collect()
+}
+
+/** Display an editable field for each aspect of `religion`, under a label with a button to reroll
+all of them, whose screen reader name is made from `name`.
+
+# Returns
+Whether the reroll button was clicked.
+*/
+fn religion_display(ui: &mut Ui, religion: &mut Religion, name: &str) -> bool {
+    let mut reroll = false;
+    for aspect in ReligionAspect::ALL_VALUES {
+        ui.horizontal(|ui| {
+            ui.label(
+                RichText::new(aspect.to_string())
+                    .font(LABEL_FONT)
+                    .color(LABEL_COLOR),
+            );
+            if aspect == ReligionAspect::Organization {
+                reroll = ui
+                    .button(RichText::new(DICE_ICON).font(FontId::proportional(BUTTON_FONT_SIZE)))
+                    .on_hover_text("Reroll every aspect of the religion")
+                    .screen_reader_label(WidgetType::Button, &format!("Reroll {}", name))
+                    .clicked();
             }
         });
-        columns[index].add_space(LABEL_SPACING * 1.5);
+        ui.add_space(LABEL_SPACING);
 
-        columns[index].label(
-            RichText::new("Description")
-                .font(LABEL_FONT)
-                .color(LABEL_COLOR),
+        ui.add(
+            TextEdit::multiline(religion.get_mut(aspect))
+                .desired_rows(2)
+                .desired_width(FIELD_SELECTION_WIDTH),
+        )
+        .screen_reader_label(
+            WidgetType::TextEdit,
+            &format!("{} {}", name, aspect.to_string().to_lowercase()),
         );
-        columns[index].add_space(LABEL_SPACING);
-
-        ScrollArea::vertical()
-            .id_source(format!("world_tag_{}_description", index))
-            .max_height(columns[index].available_height() * 0.9)
-            .show(&mut columns[index], |ui| {
-                ui.add(TextEdit::multiline(
-                    &mut self.world.world_tags[index].description,
-                ));
-            });
+        ui.add_space(LABEL_SPACING * 1.5);
     }
+    reroll
 }
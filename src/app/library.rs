@@ -0,0 +1,249 @@
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use swt_gen_core::{Point, Subsector, TradeCode, World};
+
+/** What to look for in the [`World`]s of every subsector in a library directory.
+
+Every part of the query that isn't empty has to match for a world to be found.
+*/
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct LibraryQuery {
+    /// Text that must be part of the world's name, ignoring case
+    pub(crate) name: String,
+    /// Trade code the world must have
+    pub(crate) trade_code: Option<TradeCode>,
+    /// Pattern the start of the world's UWP must match, ignoring case; `?` matches any one
+    /// character and `*` matches any number of them
+    pub(crate) uwp_pattern: String,
+}
+
+impl LibraryQuery {
+    /** Whether the query would match every world. */
+    pub(crate) fn is_empty(&self) -> bool {
+        self.name.trim().is_empty()
+            && self.trade_code.is_none()
+            && self.uwp_pattern.trim().is_empty()
+    }
+
+    /** Whether `world` matches every part of the query. */
+    pub(crate) fn matches(&self, world: &World) -> bool {
+        let name = self.name.trim().to_lowercase();
+        if !name.is_empty() && !world.name.to_lowercase().contains(&name) {
+            return false;
+        }
+
+        if let Some(trade_code) = &self.trade_code {
+            if !world.trade_codes.contains(trade_code) {
+                return false;
+            }
+        }
+
+        let pattern: Vec<char> = self.uwp_pattern.trim().to_uppercase().chars().collect();
+        let uwp: Vec<char> = world.profile_str().to_uppercase().chars().collect();
+        pattern.is_empty() || matches_prefix(&pattern, &uwp)
+    }
+}
+
+/** A [`World`] found by searching a library directory. */
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct LibraryMatch {
+    /// Subsector file the world is in
+    pub(crate) path: PathBuf,
+    /// Hex the world is in
+    pub(crate) point: Point,
+    pub(crate) subsector_name: String,
+    pub(crate) uwp: String,
+    pub(crate) world_name: String,
+}
+
+/** Results of searching every subsector file in a library directory. */
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct LibrarySearch {
+    /// Number of subsector files that were read and searched
+    pub(crate) files_searched: usize,
+    /// Worlds that matched the query, grouped by file in path order
+    pub(crate) matches: Vec<LibraryMatch>,
+    /// JSON files that couldn't be read as subsectors, along with why
+    pub(crate) unreadable: Vec<(PathBuf, String)>,
+}
+
+/** Search the worlds of every subsector JSON file in `directory` and its subdirectories.
+
+Hidden directories, like the ".history" folders backups are kept in, are skipped. Files that fail to
+parse are listed rather than failing the whole search, since a library may hold other JSON files.
+*/
+pub(crate) fn search_library(
+    directory: &Path,
+    query: &LibraryQuery,
+) -> Result<LibrarySearch, Box<dyn Error>> {
+    let mut paths = Vec::new();
+    find_json_files(directory, &mut paths)?;
+    paths.sort();
+
+    let mut search = LibrarySearch::default();
+    for path in paths {
+        let subsector = match fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|json| Subsector::try_from_json(&json).map_err(|e| e.to_string()))
+        {
+            Ok(subsector) => subsector,
+            Err(e) => {
+                search.unreadable.push((path, e));
+                continue;
+            }
+        };
+
+        search.files_searched += 1;
+        for (point, world) in subsector.get_map() {
+            if query.matches(world) {
+                search.matches.push(LibraryMatch {
+                    path: path.clone(),
+                    point: *point,
+                    subsector_name: subsector.name().to_string(),
+                    uwp: world.profile_str(),
+                    world_name: world.name.clone(),
+                });
+            }
+        }
+    }
+    Ok(search)
+}
+
+/** Add every ".json" file in `directory` and its non-hidden subdirectories to `paths`. */
+fn find_json_files(directory: &Path, paths: &mut Vec<PathBuf>) -> Result<(), Box<dyn Error>> {
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        let is_hidden = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with('.'));
+        if is_hidden {
+            continue;
+        }
+
+        if path.is_dir() {
+            find_json_files(&path, paths)?;
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+/** Whether the start of `text` matches `pattern`, where `?` matches any one character and `*`
+matches any number of them.
+*/
+fn matches_prefix(pattern: &[char], text: &[char]) -> bool {
+    match pattern.split_first() {
+        None => true,
+        Some(('*', rest)) => (0..=text.len()).any(|skip| matches_prefix(rest, &text[skip..])),
+        Some((c, rest)) => match text.split_first() {
+            Some((t, text_rest)) => (*c == '?' || c == t) && matches_prefix(rest, text_rest),
+            None => false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swt_gen_core::TABLES;
+
+    #[test]
+    fn uwp_patterns() {
+        let uwp: Vec<char> = "A788899-C".chars().collect();
+        let matches = |pattern: &str| matches_prefix(&pattern.chars().collect::<Vec<_>>(), &uwp);
+
+        assert!(matches("A"));
+        assert!(matches("A788899-C"));
+        assert!(matches("A?8"));
+        assert!(matches("A*-C"));
+        assert!(matches("*C"));
+        assert!(!matches("B"));
+        assert!(!matches("A?9"));
+        assert!(!matches("A788899-CC"));
+    }
+
+    #[test]
+    fn library_search() {
+        let directory =
+            std::env::temp_dir().join(format!("swt-gen-library-{}", std::process::id()));
+        fs::create_dir_all(directory.join("Spinward Marches")).unwrap();
+        fs::create_dir_all(directory.join(".history")).unwrap();
+
+        let mut subsector = Subsector::empty();
+        subsector.set_name("Regina".to_string());
+        let mut world = World::empty();
+        world.name = "Efate".to_string();
+        // Trade codes are resolved from the UWP when the file is loaded, so it has to qualify
+        world.atmosphere = TABLES.atmo_table[6].clone();
+        world.hydrographics = TABLES.hydro_table[5].clone();
+        world.population = TABLES.pop_table[6].clone();
+        world.resolve_trade_codes();
+        let uwp = world.profile_str();
+        subsector
+            .insert_world(&Point { x: 4, y: 5 }, world)
+            .unwrap();
+
+        let json = subsector.to_json();
+        fs::write(
+            directory.join("Spinward Marches").join("Regina.json"),
+            &json,
+        )
+        .unwrap();
+        fs::write(
+            directory
+                .join(".history")
+                .join("Regina.json.2022-06-01_134500"),
+            &json,
+        )
+        .unwrap();
+        fs::write(directory.join(".history").join("Regina.json"), &json).unwrap();
+        fs::write(directory.join("notes.json"), "[1, 2, 3]").unwrap();
+        fs::write(directory.join("notes.txt"), "Efate").unwrap();
+
+        let query = LibraryQuery {
+            name: "efa".to_string(),
+            ..Default::default()
+        };
+        let search = search_library(&directory, &query).unwrap();
+        assert_eq!(search.files_searched, 1);
+        assert_eq!(search.unreadable.len(), 1);
+        assert_eq!(search.unreadable[0].0, directory.join("notes.json"));
+        assert_eq!(
+            search.matches,
+            vec![LibraryMatch {
+                path: directory.join("Spinward Marches").join("Regina.json"),
+                point: Point { x: 4, y: 5 },
+                subsector_name: "Regina".to_string(),
+                uwp: uwp.clone(),
+                world_name: "Efate".to_string(),
+            }]
+        );
+
+        let query = LibraryQuery {
+            trade_code: Some(TradeCode::Ag),
+            uwp_pattern: format!("{}*", &uwp[..1].to_lowercase()),
+            ..Default::default()
+        };
+        assert_eq!(search_library(&directory, &query).unwrap().matches.len(), 1);
+
+        let query = LibraryQuery {
+            trade_code: Some(TradeCode::In),
+            ..Default::default()
+        };
+        assert!(search_library(&directory, &query)
+            .unwrap()
+            .matches
+            .is_empty());
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
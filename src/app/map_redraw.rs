@@ -0,0 +1,106 @@
+/*! Scheduling of redraws of the subsector grid image in the background.
+
+Rasterizing the grid is slow, so redraws asked for in quick succession, e.g. while flipping map
+settings back and forth, are coalesced into a single one once the changes stop. Grids that would
+come out the same as the one already drawn aren't redrawn at all.
+*/
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    time::Duration,
+};
+
+use super::platform::Stopwatch;
+
+/// How long after the first of a burst of redraw requests the map is actually redrawn
+pub(crate) const REDRAW_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/** Whether the subsector map should be redrawn yet, and what it was last drawn from. */
+pub(crate) struct MapRedraw {
+    /// How long requests are coalesced before a redraw is due
+    debounce: Duration,
+    /// Started when the first request since the last redraw came in
+    requested: Option<Stopwatch>,
+    /// Hash of the SVG the map image shown or being rasterized was drawn from
+    drawn: Option<u64>,
+}
+
+impl Default for MapRedraw {
+    fn default() -> Self {
+        Self {
+            debounce: REDRAW_DEBOUNCE,
+            requested: None,
+            drawn: None,
+        }
+    }
+}
+
+impl MapRedraw {
+    /** Ask for the map to be redrawn; does nothing if a redraw is already waiting. */
+    pub(crate) fn request(&mut self) {
+        if self.requested.is_none() {
+            self.requested = Some(Stopwatch::start());
+        }
+    }
+
+    /** Whether a redraw has been asked for that isn't due yet. */
+    pub(crate) fn is_waiting(&self) -> bool {
+        self.requested
+            .as_ref()
+            .is_some_and(|requested| requested.elapsed() < self.debounce)
+    }
+
+    /** Whether a redraw is due, in which case it's no longer waiting to be done. */
+    pub(crate) fn take_due(&mut self) -> bool {
+        if self.requested.is_none() || self.is_waiting() {
+            return false;
+        }
+        self.requested = None;
+        true
+    }
+
+    /** Remember that the map is being drawn from `svg`.
+
+    # Returns
+    Whether the map would look any different than when it was last drawn.
+    */
+    pub(crate) fn mark_drawn(&mut self, svg: &str) -> bool {
+        let mut hasher = DefaultHasher::new();
+        svg.hash(&mut hasher);
+        let hash = hasher.finish();
+        self.drawn.replace(hash) != Some(hash)
+    }
+
+    /** Forget what the map was drawn from, so that it's redrawn even if it looks the same. */
+    pub(crate) fn forget_drawn(&mut self) {
+        self.drawn = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn map_redraw() {
+        let mut redraw = MapRedraw::default();
+        assert!(!redraw.take_due());
+
+        // Requests are held back until the debounce is over
+        redraw.request();
+        redraw.request();
+        assert!(redraw.is_waiting());
+        assert!(!redraw.take_due());
+
+        redraw.debounce = Duration::ZERO;
+        assert!(!redraw.is_waiting());
+        assert!(redraw.take_due());
+        assert!(!redraw.take_due());
+
+        assert!(redraw.mark_drawn("<svg/>"));
+        assert!(!redraw.mark_drawn("<svg/>"));
+        assert!(redraw.mark_drawn("<svg></svg>"));
+        redraw.forget_drawn();
+        assert!(redraw.mark_drawn("<svg></svg>"));
+    }
+}
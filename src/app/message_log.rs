@@ -0,0 +1,187 @@
+/*! Record of the most recent `Message`s the app handled, shown in the debug console.
+
+Every handled message is logged with how it turned out and how long it took, so that users
+reporting a bug can copy what led up to it, and so that messages handled in a surprising order can
+be spotted.
+*/
+use std::{
+    collections::VecDeque,
+    fmt::{self, Write},
+    time::Duration,
+};
+
+use super::{Message, MessageResult};
+
+/// Most messages kept in the log; the oldest are dropped to make room for new ones
+pub(crate) const MESSAGE_LOG_CAPACITY: usize = 500;
+
+/// Messages are cut short after this many characters, since some carry whole subsectors
+const MAX_MESSAGE_CHARS: usize = 200;
+
+/** How handling a `Message` turned out, mirroring `MessageResult`. */
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum MessageOutcome {
+    /// `Ok(Some(()))`: the message was handled
+    Handled,
+    /// `Ok(None)`: nothing came of the message, usually because the user cancelled it
+    Ignored,
+    /// `Err(msg)`: handling the message failed with the error in the message
+    Failed(String),
+}
+
+impl From<&MessageResult> for MessageOutcome {
+    fn from(result: &MessageResult) -> Self {
+        match result {
+            Ok(Some(())) => Self::Handled,
+            Ok(None) => Self::Ignored,
+            Err(e) => Self::Failed(e.clone()),
+        }
+    }
+}
+
+impl fmt::Display for MessageOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Handled => write!(f, "Ok(Some)"),
+            Self::Ignored => write!(f, "Ok(None)"),
+            Self::Failed(e) => write!(f, "Err({})", e),
+        }
+    }
+}
+
+/** One handled `Message` in the log. */
+#[derive(Clone, Debug)]
+pub(crate) struct MessageLogEntry {
+    /// Number of messages handled before this one since the app started
+    pub(crate) index: u64,
+    /// The message as it would be printed with `{:?}`, cut short if it's too long
+    pub(crate) message: String,
+    pub(crate) outcome: MessageOutcome,
+    /// How long handling the message took
+    pub(crate) duration: Duration,
+}
+
+impl fmt::Display for MessageLogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "#{} {} -> {} in {:.2} ms",
+            self.index,
+            self.message,
+            self.outcome,
+            self.duration.as_secs_f64() * 1000.0
+        )
+    }
+}
+
+/** Ring buffer of the last `MESSAGE_LOG_CAPACITY` handled `Message`s, oldest first. */
+#[derive(Default)]
+pub(crate) struct MessageLog {
+    entries: VecDeque<MessageLogEntry>,
+    /// Number of messages logged since the app started, including those since dropped
+    count: u64,
+}
+
+impl MessageLog {
+    /** Log that handling `message` gave `result` after `duration`. */
+    pub(crate) fn record(&mut self, message: String, result: &MessageResult, duration: Duration) {
+        if self.entries.len() >= MESSAGE_LOG_CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(MessageLogEntry {
+            index: self.count,
+            message,
+            outcome: result.into(),
+            duration,
+        });
+        self.count += 1;
+    }
+
+    pub(crate) fn entries(&self) -> &VecDeque<MessageLogEntry> {
+        &self.entries
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /** Get every entry on a line of its own, oldest first, to be pasted into a bug report. */
+    pub(crate) fn to_text(&self) -> String {
+        self.entries
+            .iter()
+            .map(|entry| entry.to_string() + "\n")
+            .collect()
+    }
+}
+
+/** Describe `message` for the log, cut short after `MAX_MESSAGE_CHARS` characters without
+formatting the rest of it.
+*/
+pub(crate) fn describe(message: &Message) -> String {
+    let mut description = Truncated::default();
+    if write!(description, "{:?}", message).is_err() {
+        description.text.push('…');
+    }
+    description.text
+}
+
+/** Text that stops being written to, with an error, once it's `MAX_MESSAGE_CHARS` long. */
+#[derive(Default)]
+struct Truncated {
+    text: String,
+    chars: usize,
+}
+
+impl Write for Truncated {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if self.chars == MAX_MESSAGE_CHARS {
+                return Err(fmt::Error);
+            }
+            self.text.push(c);
+            self.chars += 1;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swt_gen_core::Subsector;
+
+    #[test]
+    fn message_log() {
+        let mut log = MessageLog::default();
+        for _ in 0..MESSAGE_LOG_CAPACITY {
+            log.record(describe(&Message::Save), &Ok(Some(())), Duration::ZERO);
+        }
+        log.record(
+            describe(&Message::AttachWorldImage { embed: true }),
+            &Err("Failed".to_string()),
+            Duration::from_micros(1500),
+        );
+
+        assert_eq!(log.entries().len(), MESSAGE_LOG_CAPACITY);
+        assert_eq!(log.entries()[0].index, 1);
+        let last = log.entries().back().unwrap();
+        assert_eq!(last.outcome, MessageOutcome::Failed("Failed".to_string()));
+        assert_eq!(
+            last.to_string(),
+            format!(
+                "#{} AttachWorldImage {{ embed: true }} -> Err(Failed) in 1.50 ms",
+                MESSAGE_LOG_CAPACITY
+            )
+        );
+        assert!(log.to_text().ends_with("in 1.50 ms\n"));
+
+        let description = describe(&Message::ConfirmImportText {
+            subsector: Subsector::default(),
+        });
+        assert_eq!(description.chars().count(), MAX_MESSAGE_CHARS + 1);
+        assert!(description.ends_with('…'));
+
+        log.clear();
+        assert!(log.to_text().is_empty());
+    }
+}
@@ -0,0 +1,18 @@
+/*! Everything the app does differently depending on the platform it's compiled for.
+
+Natively, files are read from and written to the file system through native file dialogs, and slow
+work is done on worker threads. In the browser, files are opened with the browser's file picker and
+saved as downloads, and since there are no threads, slow work is done on the GUI thread instead.
+
+Both platforms provide the same set of types and functions, so the rest of the app never has to
+check which one it was compiled for.
+*/
+#[cfg(not(target_arch = "wasm32"))]
+mod native;
+#[cfg(target_arch = "wasm32")]
+mod web;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use native::*;
+#[cfg(target_arch = "wasm32")]
+pub(crate) use web::*;
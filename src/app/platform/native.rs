@@ -0,0 +1,129 @@
+use std::{
+    io,
+    path::Path,
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+pub(crate) use native_dialog::{FileDialog, MessageDialog, MessageType};
+
+/// Whether files live on a real file system, where they can be watched for changes and backed up
+pub(crate) const HAS_FILE_SYSTEM: bool = true;
+
+/** Read the whole file at `path`. */
+pub(crate) fn read(path: &Path) -> io::Result<Vec<u8>> {
+    std::fs::read(path)
+}
+
+/** Read the whole file at `path` as text. */
+pub(crate) fn read_to_string(path: &Path) -> io::Result<String> {
+    std::fs::read_to_string(path)
+}
+
+/** Write `contents` to the file at `path`, replacing it if it exists. */
+pub(crate) fn write<C: AsRef<[u8]>>(path: &Path, contents: C) -> io::Result<()> {
+    std::fs::write(path, contents)
+}
+
+/** Get when the file at `path` was last modified, or `None` if it can't be read. */
+pub(crate) fn file_modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/** Run `f` in the background on a new thread. */
+pub(crate) fn spawn<F: FnOnce() + Send + 'static>(f: F) {
+    thread::spawn(f);
+}
+
+/** Call `f` on each of `items` with one thread per item, keeping the results in the same order. */
+pub(crate) fn map_parallel<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let f = &f;
+    thread::scope(|scope| {
+        let workers: Vec<_> = items
+            .iter()
+            .map(|item| scope.spawn(move || f(item)))
+            .collect();
+        workers
+            .into_iter()
+            .map(|worker| worker.join().expect("Worker threads should not panic"))
+            .collect()
+    })
+}
+
+/** Whether a file dialog was opened that will finish after the `Message` that opened it was
+handled; native file dialogs always finish first, so never.
+*/
+pub(crate) fn take_file_pick_started() -> bool {
+    false
+}
+
+/** Whether a file has been picked since a file dialog finished late; never, natively. */
+pub(crate) fn file_picked() -> bool {
+    false
+}
+
+/** Whether a file dialog that will finish late is still open; never, natively. */
+pub(crate) fn waiting_for_file_pick() -> bool {
+    false
+}
+
+/** Measures how long something takes. */
+pub(crate) struct Stopwatch(Instant);
+
+impl Stopwatch {
+    pub(crate) fn start() -> Self {
+        Self(Instant::now())
+    }
+
+    pub(crate) fn elapsed(&self) -> Duration {
+        self.0.elapsed()
+    }
+}
+
+/** Long-lived worker that turns each input it's sent into an output, one at a time, on its own
+thread.
+*/
+pub(crate) struct Worker<I, O> {
+    input_tx: mpsc::Sender<I>,
+    output_rx: mpsc::Receiver<O>,
+}
+
+impl<I: Send + 'static, O: Send + 'static> Worker<I, O> {
+    /** Start a worker that turns inputs into outputs with `work`. */
+    pub(crate) fn new<F: Fn(I) -> O + Send + 'static>(work: F) -> Self {
+        let (input_tx, input_rx) = mpsc::channel::<I>();
+        let (output_tx, output_rx) = mpsc::channel::<O>();
+        thread::spawn(move || {
+            while let Ok(input) = input_rx.recv() {
+                if output_tx.send(work(input)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            input_tx,
+            output_rx,
+        }
+    }
+
+    /** Queue `input` to be worked on. */
+    pub(crate) fn send(&self, input: I) {
+        self.input_tx
+            .send(input)
+            .expect("Worker thread should never hang up");
+    }
+
+    /** Get the next finished output, if there is one yet. */
+    pub(crate) fn try_recv(&self) -> Option<O> {
+        self.output_rx.try_recv().ok()
+    }
+}
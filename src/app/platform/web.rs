@@ -0,0 +1,329 @@
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use js_sys::{Array, Date, Uint8Array};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{Blob, Document, File, HtmlAnchorElement, HtmlInputElement, Url};
+
+/// Whether files live on a real file system, where they can be watched for changes and backed up
+pub(crate) const HAS_FILE_SYSTEM: bool = false;
+
+/// Directory picked files are put in, since the browser doesn't say where they came from
+const PICKED_DIRECTORY: &str = "/picked";
+
+thread_local! {
+    /// Contents of every file picked or saved this session, by path
+    static FILES: RefCell<HashMap<PathBuf, Vec<u8>>> = RefCell::new(HashMap::new());
+    /// State of the browser's file picker
+    static PICKER: RefCell<Picker> = RefCell::new(Picker::default());
+}
+
+#[derive(Default)]
+struct Picker {
+    /// Whether the picker was opened since `take_file_pick_started` was last called
+    started: bool,
+    /// Whether the picker is still open or its file is still being read
+    waiting: bool,
+    /// Path of the file that was picked, until a file dialog takes it
+    picked: Option<PathBuf>,
+}
+
+/** Kind of message shown by a `MessageDialog`; the browser shows every kind the same way. */
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum MessageType {
+    Error,
+}
+
+/** Message shown to the user with the browser's alert box, built like `native_dialog`'s. */
+#[derive(Default)]
+pub(crate) struct MessageDialog<'a> {
+    title: &'a str,
+    text: &'a str,
+}
+
+impl<'a> MessageDialog<'a> {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn set_type(self, _message_type: MessageType) -> Self {
+        self
+    }
+
+    pub(crate) fn set_title(mut self, title: &'a str) -> Self {
+        self.title = title;
+        self
+    }
+
+    pub(crate) fn set_text(mut self, text: &'a str) -> Self {
+        self.text = text;
+        self
+    }
+
+    pub(crate) fn show_alert(self) -> io::Result<()> {
+        web_sys::window()
+            .ok_or_else(no_window)?
+            .alert_with_message(&format!("{}\n\n{}", self.title, self.text))
+            .map_err(js_error)
+    }
+}
+
+/** File dialog built like `native_dialog`'s, that saves by downloading and opens with the
+browser's file picker.
+
+The file picker can't be waited on, so opening a file finishes late: the first call to
+`show_open_single_file` opens the picker and returns `None`, and once a file has been picked and
+read, the next call returns its path.
+*/
+#[derive(Default)]
+pub(crate) struct FileDialog<'a> {
+    filename: &'a str,
+    extensions: Vec<&'a str>,
+}
+
+impl<'a> FileDialog<'a> {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /** Does nothing; browsers decide where files are opened from and saved to themselves. */
+    pub(crate) fn set_location<P: AsRef<Path> + ?Sized>(self, _location: &'a P) -> Self {
+        self
+    }
+
+    pub(crate) fn set_filename(mut self, filename: &'a str) -> Self {
+        self.filename = filename;
+        self
+    }
+
+    pub(crate) fn add_filter(mut self, _description: &'a str, extensions: &'a [&'a str]) -> Self {
+        self.extensions.extend_from_slice(extensions);
+        self
+    }
+
+    /** Get the path to save to, which is just the file name to download the file as. */
+    pub(crate) fn show_save_single_file(self) -> io::Result<Option<PathBuf>> {
+        Ok(Some(PathBuf::from(self.filename)))
+    }
+
+    pub(crate) fn show_open_single_file(self) -> io::Result<Option<PathBuf>> {
+        if let Some(path) = PICKER.with(|picker| picker.borrow_mut().picked.take()) {
+            return Ok(Some(path));
+        }
+        open_picker(&self.extensions)?;
+        Ok(None)
+    }
+
+    pub(crate) fn show_open_single_dir(self) -> io::Result<Option<PathBuf>> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "Folders can't be opened in the browser",
+        ))
+    }
+}
+
+/** Read the whole file at `path`, which must have been picked or saved this session. */
+pub(crate) fn read(path: &Path) -> io::Result<Vec<u8>> {
+    FILES
+        .with(|files| files.borrow().get(path).cloned())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} hasn't been opened in this session", path.display()),
+            )
+        })
+}
+
+/** Read the whole file at `path` as text, which must have been picked or saved this session. */
+pub(crate) fn read_to_string(path: &Path) -> io::Result<String> {
+    String::from_utf8(read(path)?).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/** Download `contents` as a file named after `path`, keeping them to be read back later. */
+pub(crate) fn write<C: AsRef<[u8]>>(path: &Path, contents: C) -> io::Result<()> {
+    let contents = contents.as_ref();
+    let file_name = path
+        .file_name()
+        .map_or_else(String::new, |name| name.to_string_lossy().to_string());
+
+    let parts = Array::of1(&Uint8Array::from(contents));
+    let blob = Blob::new_with_u8_array_sequence(&parts).map_err(js_error)?;
+    let url = Url::create_object_url_with_blob(&blob).map_err(js_error)?;
+    let anchor: HtmlAnchorElement = document()?
+        .create_element("a")
+        .map_err(js_error)?
+        .unchecked_into();
+    anchor.set_href(&url);
+    anchor.set_download(&file_name);
+    anchor.click();
+    Url::revoke_object_url(&url).map_err(js_error)?;
+
+    FILES.with(|files| {
+        files
+            .borrow_mut()
+            .insert(path.to_path_buf(), contents.to_vec())
+    });
+    Ok(())
+}
+
+/** Get when the file at `path` was last modified; never known in the browser. */
+pub(crate) fn file_modified(_path: &Path) -> Option<SystemTime> {
+    None
+}
+
+/** Run `f` right away, since there are no threads to run it on in the background. */
+pub(crate) fn spawn<F: FnOnce() + Send + 'static>(f: F) {
+    f()
+}
+
+/** Call `f` on each of `items` in turn, keeping the results in the same order. */
+pub(crate) fn map_parallel<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    items.iter().map(f).collect()
+}
+
+/** Whether the file picker was opened since this was last called, and so will finish after the
+`Message` that opened it was handled.
+*/
+pub(crate) fn take_file_pick_started() -> bool {
+    PICKER.with(|picker| std::mem::take(&mut picker.borrow_mut().started))
+}
+
+/** Whether a file was picked and read that no file dialog has taken yet. */
+pub(crate) fn file_picked() -> bool {
+    PICKER.with(|picker| picker.borrow().picked.is_some())
+}
+
+/** Whether the file picker is still open or its file is still being read. */
+pub(crate) fn waiting_for_file_pick() -> bool {
+    PICKER.with(|picker| picker.borrow().waiting)
+}
+
+/** Measures how long something takes with the browser's clock, since `Instant` isn't supported
+there.
+*/
+pub(crate) struct Stopwatch(f64);
+
+impl Stopwatch {
+    pub(crate) fn start() -> Self {
+        Self(Date::now())
+    }
+
+    pub(crate) fn elapsed(&self) -> Duration {
+        Duration::from_secs_f64((Date::now() - self.0).max(0.0) / 1000.0)
+    }
+}
+
+/** Worker that turns each input it's sent into an output, straight away, on the GUI thread. */
+pub(crate) struct Worker<I, O> {
+    work: Box<dyn Fn(I) -> O>,
+    outputs: RefCell<VecDeque<O>>,
+}
+
+impl<I: Send + 'static, O: Send + 'static> Worker<I, O> {
+    /** Start a worker that turns inputs into outputs with `work`. */
+    pub(crate) fn new<F: Fn(I) -> O + Send + 'static>(work: F) -> Self {
+        Self {
+            work: Box::new(work),
+            outputs: RefCell::new(VecDeque::new()),
+        }
+    }
+
+    /** Work on `input` right away. */
+    pub(crate) fn send(&self, input: I) {
+        let output = (self.work)(input);
+        self.outputs.borrow_mut().push_back(output);
+    }
+
+    /** Get the next finished output, if there is one. */
+    pub(crate) fn try_recv(&self) -> Option<O> {
+        self.outputs.borrow_mut().pop_front()
+    }
+}
+
+/** Open the browser's file picker for files with any of `extensions`. */
+fn open_picker(extensions: &[&str]) -> io::Result<()> {
+    let input: HtmlInputElement = document()?
+        .create_element("input")
+        .map_err(js_error)?
+        .unchecked_into();
+    input.set_type("file");
+    let accept: Vec<String> = extensions
+        .iter()
+        .map(|extension| format!(".{}", extension))
+        .collect();
+    input.set_accept(&accept.join(","));
+
+    let picked_input = input.clone();
+    let on_change =
+        Closure::once_into_js(
+            move || match picked_input.files().and_then(|files| files.get(0)) {
+                Some(file) => spawn_local(read_picked_file(file)),
+                None => finish_pick(None),
+            },
+        );
+    input.set_onchange(Some(on_change.unchecked_ref()));
+    let on_cancel = Closure::once_into_js(|| finish_pick(None));
+    input
+        .add_event_listener_with_callback("cancel", on_cancel.unchecked_ref())
+        .map_err(js_error)?;
+
+    PICKER.with(|picker| {
+        *picker.borrow_mut() = Picker {
+            started: true,
+            waiting: true,
+            picked: None,
+        }
+    });
+    input.click();
+    Ok(())
+}
+
+/** Read the contents of a picked `file` so it can be read like any other file. */
+async fn read_picked_file(file: File) {
+    let path = Path::new(PICKED_DIRECTORY).join(file.name());
+    match JsFuture::from(file.array_buffer()).await {
+        Ok(buffer) => {
+            let contents = Uint8Array::new(&buffer).to_vec();
+            FILES.with(|files| files.borrow_mut().insert(path.clone(), contents));
+            finish_pick(Some(path));
+        }
+        Err(_) => finish_pick(None),
+    }
+}
+
+/** Close out the file picker, with the path of the file that was picked, if any. */
+fn finish_pick(picked: Option<PathBuf>) {
+    PICKER.with(|picker| {
+        let mut picker = picker.borrow_mut();
+        picker.waiting = false;
+        picker.picked = picked;
+    });
+}
+
+fn document() -> io::Result<Document> {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(no_window)
+}
+
+fn no_window() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "No browser window to show it in",
+    )
+}
+
+fn js_error(error: JsValue) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, format!("{:?}", error))
+}
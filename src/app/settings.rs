@@ -0,0 +1,144 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::app::{backup::BackupStyle, platform};
+use swt_gen_core::{
+    GrowthRules, HandoutOptions, MapOptions, MapTemplate, NameStyle, NumberFormat, RuleSet,
+    WorldAbundance, WorldLabels, DEFAULT_MAP_TEMPLATE,
+};
+
+/// Largest allowed `Settings::backup_count`
+pub(crate) const MAX_BACKUP_COUNT: usize = 20;
+/// Smallest allowed `Settings::ui_scale`
+pub(crate) const MIN_UI_SCALE: f32 = 0.5;
+/// Largest allowed `Settings::ui_scale`
+pub(crate) const MAX_UI_SCALE: f32 = 2.0;
+
+/** Named set of subsector generation options, so setups like "Frontier Rift" or "Core Worlds" can be
+regenerated consistently.
+*/
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub(crate) struct GenerationPreset {
+    /// Percentage from 0 to 100 of how strongly worlds cluster together
+    pub(crate) clustering: u8,
+    pub(crate) name: String,
+    /// Style of the name given to the new subsector
+    pub(crate) name_style: NameStyle,
+    pub(crate) rule_set: RuleSet,
+    pub(crate) world_abundance: WorldAbundance,
+}
+
+/** User settings that persist between sessions of the app.
+
+The position and size of the window are persisted alongside these by `eframe` itself.
+*/
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub(crate) struct Settings {
+    /// Most earlier versions of a subsector file kept by `backup_style`
+    pub(crate) backup_count: usize,
+    /// How earlier versions of a subsector file are kept when it is saved over
+    pub(crate) backup_style: BackupStyle,
+    /// Saved subsector generation options, in the order they were first saved
+    pub(crate) generation_presets: Vec<GenerationPreset>,
+    /// Path to a `Script` run on every newly generated subsector; `None` if there isn't one
+    pub(crate) generation_script_path: Option<PathBuf>,
+    /// Chances last chosen for fast-forwarding subsectors
+    pub(crate) growth_rules: GrowthRules,
+    /// Fields last chosen to be revealed in player handouts
+    pub(crate) handout_options: HandoutOptions,
+    /// Whether the GUI is drawn with bright text and outlines on black for low vision
+    pub(crate) high_contrast: bool,
+    /// Layers last chosen for exporting subsector maps
+    pub(crate) map_options: MapOptions,
+    /// Directory of translated table files; `None` if the built-in English tables should be used
+    pub(crate) language_path: Option<PathBuf>,
+    /// Directory of subsector files searched by the library search; `None` until one is chosen
+    pub(crate) library_path: Option<PathBuf>,
+    /// Whether newly generated worlds keep a log of the dice rolled to generate them
+    pub(crate) log_generation_rolls: bool,
+    /// Path to a user-provided SVG map template; `None` if the built-in template should be used
+    pub(crate) map_template_path: Option<PathBuf>,
+    /// Whether the number of each hex is drawn on the on-screen map
+    pub(crate) map_hex_numbers: bool,
+    /// Labels drawn next to each world on the on-screen map
+    pub(crate) map_world_labels: WorldLabels,
+    /// How diameters, populations, and other numbers are displayed and exported
+    pub(crate) number_format: NumberFormat,
+    /// Whether editing a field of a world offers to reroll the fields rolled from it
+    pub(crate) reroll_dependents: bool,
+    /// Whether the history of each world is saved in the subsector JSON
+    pub(crate) save_world_history: bool,
+    /// Whether the side panel with the selected world's data is expanded
+    pub(crate) show_world_panel: bool,
+    /// Whether edited diameters and berthing costs are snapped into the ranges the rules allow
+    pub(crate) snap_to_legal_ranges: bool,
+    /// Path to a file of errata and house-rule overrides of table rows; `None` if the tables aren't
+    /// overridden
+    pub(crate) table_overrides_path: Option<PathBuf>,
+    /// Scale of the whole GUI relative to the display's native scale
+    pub(crate) ui_scale: f32,
+    /// Whether to prompt to reload the open save file when it is changed outside of the app
+    pub(crate) watch_save_file: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            backup_count: 3,
+            backup_style: BackupStyle::default(),
+            generation_presets: Vec::new(),
+            generation_script_path: None,
+            growth_rules: GrowthRules::default(),
+            handout_options: HandoutOptions::default(),
+            high_contrast: false,
+            language_path: None,
+            library_path: None,
+            log_generation_rolls: false,
+            map_hex_numbers: true,
+            map_options: MapOptions::default(),
+            map_template_path: None,
+            map_world_labels: WorldLabels::default(),
+            number_format: NumberFormat::default(),
+            reroll_dependents: false,
+            save_world_history: false,
+            show_world_panel: true,
+            snap_to_legal_ranges: false,
+            table_overrides_path: None,
+            ui_scale: 1.0,
+            watch_save_file: true,
+        }
+    }
+}
+
+impl Settings {
+    /// Key used to store `Settings` in `eframe`'s persistent storage
+    pub(crate) const STORAGE_KEY: &'static str = "settings";
+
+    /** Save `preset`, replacing the saved preset with the same name if there is one. */
+    pub(crate) fn save_generation_preset(&mut self, preset: GenerationPreset) {
+        match self
+            .generation_presets
+            .iter_mut()
+            .find(|saved| saved.name == preset.name)
+        {
+            Some(saved) => *saved = preset,
+            None => self.generation_presets.push(preset),
+        }
+    }
+
+    /** Load the SVG map template that should be used for exporting subsector maps.
+
+    # Returns
+    - `Ok(MapTemplate)` with the user-provided template if there is one, or the built-in template
+      otherwise
+    - `Err` if the user-provided template could not be read or is not a valid map template
+    */
+    pub(crate) fn load_map_template(&self) -> Result<MapTemplate, Box<dyn std::error::Error>> {
+        match &self.map_template_path {
+            Some(path) => MapTemplate::try_from_svg(platform::read_to_string(path)?),
+            None => Ok(DEFAULT_MAP_TEMPLATE.clone()),
+        }
+    }
+}
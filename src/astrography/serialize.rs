@@ -1,5 +0,0 @@
-mod json;
-mod t5_table;
-
-pub(crate) use json::JsonableSubsector;
-pub(crate) use t5_table::T5Table;
@@ -1,7 +1,4 @@
 #![warn(clippy::todo)]
 mod app;
-mod astrography;
-mod dice;
-mod histogram;
 
 pub use app::GeneratorApp;
@@ -2,19 +2,57 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 #![warn(clippy::todo)]
 
-use egui::vec2;
-
 use swt_gen::GeneratorApp;
 
+/// Launch flag that opens the generator as a read-only viewer, optionally followed by the path of
+/// the subsector JSON to view, e.g. `swt-gen --viewer Spinward.json`
+#[cfg(not(target_arch = "wasm32"))]
+const VIEWER_FLAG: &str = "--viewer";
+
+#[cfg(not(target_arch = "wasm32"))]
 fn main() {
+    use std::path::PathBuf;
+
+    use egui::vec2;
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let viewer = args.iter().any(|arg| arg == VIEWER_FLAG);
+    let viewer_path = args
+        .into_iter()
+        .find(|arg| arg != VIEWER_FLAG)
+        .map(PathBuf::from);
+
+    // The size of the window from the last session takes precedence over the initial size
     let options = eframe::NativeOptions {
-        min_window_size: Some(vec2(1760.0, 990.0)),
+        initial_window_size: Some(vec2(1760.0, 990.0)),
+        min_window_size: Some(vec2(640.0, 480.0)),
         ..Default::default()
     };
 
     eframe::run_native(
         "Subsector Generator",
         options,
-        Box::new(|_cc| Box::<GeneratorApp>::default()),
+        Box::new(move |cc| {
+            let mut app = GeneratorApp::new(cc);
+            if viewer {
+                if let Err(e) = app.open_viewer(viewer_path.as_deref()) {
+                    eprintln!("Failed to open the subsector to view: {}", e);
+                }
+            }
+            Box::new(app)
+        }),
     );
 }
+
+/// Id of the canvas in `index.html` that the generator is drawn on in the browser
+#[cfg(target_arch = "wasm32")]
+const CANVAS_ID: &str = "swt_gen_canvas";
+
+#[cfg(target_arch = "wasm32")]
+fn main() {
+    // Show panics in the browser console rather than failing silently
+    console_error_panic_hook::set_once();
+
+    eframe::start_web(CANVAS_ID, Box::new(|cc| Box::new(GeneratorApp::new(cc))))
+        .expect("The generator should be able to start in the browser");
+}